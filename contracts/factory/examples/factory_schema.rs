@@ -5,7 +5,8 @@ use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
 use astroport::asset::PairInfo;
 use astroport::factory::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PairsResponse, QueryMsg,
+    ConfigResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg, PairType,
+    PairsResponse, QueryMsg,
 };
 
 fn main() {
@@ -21,4 +22,11 @@ fn main() {
     export_schema_with_title(&schema_for!(PairsResponse), &out_dir, "PairsResponse");
     export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(PairInfo), &out_dir, "PairResponse");
+    export_schema_with_title(&schema_for!(FeeInfoResponse), &out_dir, "FeeInfoResponse");
+    export_schema_with_title(
+        &schema_for!(Vec<PairType>),
+        &out_dir,
+        "BlacklistedPairTypesResponse",
+    );
 }
@@ -5,26 +5,34 @@ use cosmwasm_std::{
 
 use crate::error::ContractError;
 use crate::migration;
-use crate::querier::query_pair_info;
+use crate::querier::{query_pair_info, query_token_minter};
 
 use crate::state::{
-    pair_key, read_pairs, Config, TmpPairInfo, CONFIG, OWNERSHIP_PROPOSAL, PAIRS, PAIR_CONFIGS,
-    TMP_PAIR_INFO,
+    add_pair_type, read_pairs, remove_pair_type, Config, PairTypesMigration, TmpPairInfo, CONFIG,
+    DEFAULT_MIGRATION_LIMIT, EXTERNAL_ADAPTERS, FEE_DISCOUNT, MAX_MIGRATION_LIMIT,
+    OWNERSHIP_PROPOSAL, PAIRS, PAIRS_COUNT, PAIR_CONFIGS, PAIR_TYPES, PAIR_TYPES_MIGRATION,
+    PRICE_GUARDS, SWAP_HOOKS_WHITELIST, TMP_PAIR_INFO,
 };
 
 use crate::response::MsgInstantiateContractResponse;
 
-use astroport::asset::{addr_validate_to_lower, AssetInfo, PairInfo};
+use astroport::asset::{
+    addr_validate_to_lower, has_duplicate_asset_infos, pair_key, AssetInfo, PairInfo,
+};
+use astroport::constants::{BPS_DENOMINATOR, MAX_FEE_DISCOUNT_BPS, MAX_TWAP_WINDOW};
 use astroport::factory::{
-    ConfigResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg, PairConfig, PairType,
-    PairsResponse, QueryMsg,
+    ConfigResponse, ExecuteMsg, ExternalAdapterInfo, FeeDiscountConfig, FeeInfoResponse,
+    InstantiateMsg, MigrateMsg, PairConfig, PairType, PairsResponse, PriceGuard, QueryMsg,
+    UpdateAddr,
 };
 
-use crate::migration::migrate_pair_configs_to_v120;
-use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+use crate::migration::{migrate_pair_configs_to_v120, migrate_pair_types};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
 use astroport::generator::ExecuteMsg::DeactivatePool;
 use astroport::pair::InstantiateMsg as PairInstantiateMsg;
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 use protobuf::Message;
 use std::collections::HashSet;
 
@@ -61,6 +69,8 @@ pub fn instantiate(
         fee_address: None,
         generator_address: None,
         whitelist_code_id: msg.whitelist_code_id,
+        referral_registry: None,
+        emergency_admin: None,
     };
 
     if let Some(generator_address) = msg.generator_address {
@@ -107,6 +117,8 @@ pub struct UpdateConfig {
     generator_address: Option<String>,
     /// CW1 whitelist contract code id used to store 3rd party staking rewards
     whitelist_code_id: Option<u64>,
+    /// Referral registry contract address
+    referral_registry: Option<UpdateAddr>,
 }
 
 /// ## Description
@@ -125,6 +137,8 @@ pub struct UpdateConfig {
 ///             token_code_id,
 ///             fee_address,
 ///             generator_address,
+///             whitelist_code_id,
+///             referral_registry,
 ///         }** Updates general contract parameters.
 ///
 /// * **ExecuteMsg::UpdatePairConfig { config }** Updates a pair type
@@ -144,6 +158,27 @@ pub struct UpdateConfig {
 /// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
 ///
 /// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+///
+/// * **ExecuteMsg::UpdateSwapHooksWhitelist { add, remove }** Adds or removes contracts that are allowed
+/// to register swap hooks in pairs.
+///
+/// * **ExecuteMsg::RegisterExternalAdapter { adapter, venue }** Registers an adapter contract that the router
+/// may use for external swaps.
+///
+/// * **ExecuteMsg::SetExternalAdapterEnabled { adapter, enabled }** Enables or disables a registered adapter.
+///
+/// * **ExecuteMsg::DeregisterExternalAdapter { adapter }** Removes an adapter from the registry.
+///
+/// * **ExecuteMsg::UpdateEmergencyAdmin { emergency_admin }** Sets or removes the emergency admin contract.
+///
+/// * **ExecuteMsg::UpdateFeeDiscount { discount_contract, max_discount_bps }** Sets or removes the fee discount
+/// contract queried by pairs.
+///
+/// * **ExecuteMsg::DisablePairType { pair_type }** Disables the creation of new pairs of a specific type.
+///
+/// * **ExecuteMsg::UpdatePriceGuard { pair_addr, price_guard }** Sets or removes the TWAP price guard of a pair.
+///
+/// * **ExecuteMsg::MigratePairTypes { limit }** Saves the types of the next batch of pairs registered before V1.3.0.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -157,6 +192,7 @@ pub fn execute(
             fee_address,
             generator_address,
             whitelist_code_id,
+            referral_registry,
         } => execute_update_config(
             deps,
             env,
@@ -166,6 +202,7 @@ pub fn execute(
                 fee_address,
                 generator_address,
                 whitelist_code_id,
+                referral_registry,
             },
         ),
         ExecuteMsg::UpdatePairConfig { config } => execute_update_pair_config(deps, info, config),
@@ -206,6 +243,31 @@ pub fn execute(
             })
             .map_err(|e| e.into())
         }
+        ExecuteMsg::UpdateSwapHooksWhitelist { add, remove } => {
+            update_swap_hooks_whitelist(deps, info, add, remove)
+        }
+        ExecuteMsg::RegisterExternalAdapter { adapter, venue } => {
+            register_external_adapter(deps, info, adapter, venue)
+        }
+        ExecuteMsg::SetExternalAdapterEnabled { adapter, enabled } => {
+            set_external_adapter_enabled(deps, info, adapter, enabled)
+        }
+        ExecuteMsg::DeregisterExternalAdapter { adapter } => {
+            deregister_external_adapter(deps, info, adapter)
+        }
+        ExecuteMsg::UpdateEmergencyAdmin { emergency_admin } => {
+            update_emergency_admin(deps, info, emergency_admin)
+        }
+        ExecuteMsg::UpdateFeeDiscount {
+            discount_contract,
+            max_discount_bps,
+        } => update_fee_discount(deps, info, discount_contract, max_discount_bps),
+        ExecuteMsg::DisablePairType { pair_type } => disable_pair_type(deps, info, pair_type),
+        ExecuteMsg::UpdatePriceGuard {
+            pair_addr,
+            price_guard,
+        } => update_price_guard(deps, info, pair_addr, price_guard),
+        ExecuteMsg::MigratePairTypes { limit } => execute_migrate_pair_types(deps, info, limit),
     }
 }
 
@@ -257,6 +319,13 @@ pub fn execute_update_config(
         config.whitelist_code_id = code_id;
     }
 
+    if let Some(referral_registry) = param.referral_registry {
+        config.referral_registry = match referral_registry {
+            UpdateAddr::Set(addr) => Some(addr_validate_to_lower(deps.api, &addr)?),
+            UpdateAddr::Remove {} => None,
+        };
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
@@ -324,7 +393,7 @@ pub fn execute_create_pair(
     asset_infos[0].check(deps.api)?;
     asset_infos[1].check(deps.api)?;
 
-    if asset_infos[0] == asset_infos[1] {
+    if has_duplicate_asset_infos(&asset_infos) {
         return Err(ContractError::DoublingAssets {});
     }
 
@@ -348,7 +417,13 @@ pub fn execute_create_pair(
     }
 
     let pair_key = pair_key(&asset_infos);
-    TMP_PAIR_INFO.save(deps.storage, &TmpPairInfo { pair_key })?;
+    TMP_PAIR_INFO.save(
+        deps.storage,
+        &TmpPairInfo {
+            pair_key,
+            pair_type: pair_type.clone(),
+        },
+    )?;
 
     let sub_msg: Vec<SubMsg> = vec![SubMsg {
         id: INSTANTIATE_PAIR_REPLY_ID,
@@ -400,7 +475,21 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
     let pair_contract = addr_validate_to_lower(deps.api, res.get_contract_address())?;
 
+    // The pair must report the requested assets, otherwise its code can't be trusted
+    let pair_info = query_pair_info(deps.as_ref(), &pair_contract)?;
+    if pair_info.contract_addr != pair_contract || pair_key(&pair_info.asset_infos) != tmp.pair_key
+    {
+        return Err(ContractError::PairInfoMismatch {});
+    }
+
+    // Only the pair itself may mint its LP token
+    if query_token_minter(deps.as_ref(), &pair_info.liquidity_token)? != Some(pair_contract.clone())
+    {
+        return Err(ContractError::InvalidLiquidityToken {});
+    }
+
     PAIRS.save(deps.storage, &tmp.pair_key, &pair_contract)?;
+    add_pair_type(deps.storage, &pair_contract, &tmp.pair_type)?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "register"),
@@ -437,6 +526,8 @@ pub fn deregister(
 
     let pair_addr: Addr = PAIRS.load(deps.storage, &pair_key(&asset_infos))?;
     PAIRS.remove(deps.storage, &pair_key(&asset_infos));
+    remove_pair_type(deps.storage, &pair_addr)?;
+    PRICE_GUARDS.remove(deps.storage, &pair_addr);
 
     let mut messages: Vec<CosmosMsg> = vec![];
     if let Some(generator) = config.generator_address {
@@ -458,6 +549,431 @@ pub fn deregister(
     ]))
 }
 
+/// ## Description
+/// Adds or removes contracts that are allowed to register swap hooks in pairs. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **add** is an [`Option`] field of type [`Vec<String>`]. These are the contracts to add to the whitelist.
+///
+/// * **remove** is an [`Option`] field of type [`Vec<String>`]. These are the contracts to remove from the whitelist.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn update_swap_hooks_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut whitelist = SWAP_HOOKS_WHITELIST
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    for addr in remove.unwrap_or_default() {
+        let addr = addr_validate_to_lower(deps.api, &addr)?;
+        whitelist.retain(|hook| *hook != addr);
+    }
+
+    for addr in add.unwrap_or_default() {
+        let addr = addr_validate_to_lower(deps.api, &addr)?;
+        if !whitelist.contains(&addr) {
+            whitelist.push(addr);
+        }
+    }
+
+    SWAP_HOOKS_WHITELIST.save(deps.storage, &whitelist)?;
+
+    Ok(Response::new().add_attribute("action", "update_swap_hooks_whitelist"))
+}
+
+/// ## Description
+/// Registers an adapter contract that the router may use for external swaps. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **adapter** is an object of type [`String`]. This is the adapter contract address.
+///
+/// * **venue** is an object of type [`String`]. This is the external venue the adapter swaps on.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn register_external_adapter(
+    deps: DepsMut,
+    info: MessageInfo,
+    adapter: String,
+    venue: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let adapter = addr_validate_to_lower(deps.api, &adapter)?;
+
+    // Each venue can only have one adapter
+    for item in EXTERNAL_ADAPTERS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, registered) = item?;
+        if registered.venue == venue && registered.adapter != adapter {
+            return Err(ContractError::VenueAlreadyRegistered(venue));
+        }
+    }
+
+    EXTERNAL_ADAPTERS.save(
+        deps.storage,
+        &adapter,
+        &ExternalAdapterInfo {
+            adapter: adapter.clone(),
+            venue: venue.clone(),
+            enabled: true,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_external_adapter"),
+        attr("adapter", adapter),
+        attr("venue", venue),
+    ]))
+}
+
+/// ## Description
+/// Enables or disables a registered adapter contract. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **adapter** is an object of type [`String`]. This is the adapter contract address.
+///
+/// * **enabled** is an object of type [`bool`]. This is whether the router may use the adapter.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn set_external_adapter_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    adapter: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let adapter_addr = addr_validate_to_lower(deps.api, &adapter)?;
+    let mut adapter_info = EXTERNAL_ADAPTERS
+        .may_load(deps.storage, &adapter_addr)?
+        .ok_or(ContractError::ExternalAdapterNotFound(adapter))?;
+    adapter_info.enabled = enabled;
+    EXTERNAL_ADAPTERS.save(deps.storage, &adapter_addr, &adapter_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_external_adapter_enabled"),
+        attr("adapter", adapter_addr),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Removes an adapter contract from the registry. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **adapter** is an object of type [`String`]. This is the adapter contract address.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn deregister_external_adapter(
+    deps: DepsMut,
+    info: MessageInfo,
+    adapter: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let adapter_addr = addr_validate_to_lower(deps.api, &adapter)?;
+    if !EXTERNAL_ADAPTERS.has(deps.storage, &adapter_addr) {
+        return Err(ContractError::ExternalAdapterNotFound(adapter));
+    }
+    EXTERNAL_ADAPTERS.remove(deps.storage, &adapter_addr);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deregister_external_adapter"),
+        attr("adapter", adapter_addr),
+    ]))
+}
+
+/// ## Description
+/// Sets or removes the emergency admin contract. Returns a [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **emergency_admin** is an object of type [`UpdateAddr`]. This is the new emergency admin contract.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn update_emergency_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    emergency_admin: UpdateAddr,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.emergency_admin = match emergency_admin {
+        UpdateAddr::Set(addr) => Some(addr_validate_to_lower(deps.api, &addr)?),
+        UpdateAddr::Remove {} => None,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_emergency_admin"),
+        attr(
+            "emergency_admin",
+            config
+                .emergency_admin
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
+/// ## Description
+/// Sets or removes the fee discount contract that pairs query when computing the swap commission.
+/// Returns a [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **discount_contract** is an object of type [`UpdateAddr`]. This is the new fee discount contract.
+///
+/// * **max_discount_bps** is an object of type [`u16`]. This is the maximum share of the commission that can be waived.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn update_fee_discount(
+    deps: DepsMut,
+    info: MessageInfo,
+    discount_contract: UpdateAddr,
+    max_discount_bps: u16,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match discount_contract {
+        UpdateAddr::Set(addr) => {
+            if max_discount_bps > MAX_FEE_DISCOUNT_BPS {
+                return Err(ContractError::FeeDiscountTooHigh(MAX_FEE_DISCOUNT_BPS));
+            }
+
+            let discount_contract = addr_validate_to_lower(deps.api, &addr)?;
+            FEE_DISCOUNT.save(
+                deps.storage,
+                &FeeDiscountConfig {
+                    discount_contract: discount_contract.clone(),
+                    max_discount_bps,
+                },
+            )?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "update_fee_discount"),
+                attr("discount_contract", discount_contract),
+                attr("max_discount_bps", max_discount_bps.to_string()),
+            ]))
+        }
+        UpdateAddr::Remove {} => {
+            FEE_DISCOUNT.remove(deps.storage);
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "update_fee_discount"),
+                attr("discount_contract", ""),
+            ]))
+        }
+    }
+}
+
+/// ## Description
+/// Disables the creation of new pairs of a specific type. Existing pairs keep working.
+/// Returns a [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair_type** is an object of type [`PairType`]. This is the pair type to disable.
+///
+/// ## Executor
+/// The owner or the emergency admin can execute this.
+pub fn disable_pair_type(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_type: PairType,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner && Some(&info.sender) != config.emergency_admin.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pair_config = PAIR_CONFIGS
+        .may_load(deps.storage, pair_type.to_string())?
+        .ok_or(ContractError::PairConfigNotFound {})?;
+    pair_config.is_disabled = true;
+    PAIR_CONFIGS.save(deps.storage, pair_type.to_string(), &pair_config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "disable_pair_type"),
+        attr("pair_type", pair_type.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Sets or removes the guard that rejects swaps in a pair whose execution price deviates too much
+/// from the pair's TWAP. Returns a [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair_addr** is an object of type [`String`]. This is the pair contract address.
+///
+/// * **price_guard** is an [`Option`] field of type [`PriceGuard`]. This is the new price guard.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn update_price_guard(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_addr: String,
+    price_guard: Option<PriceGuard>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+    if PAIR_TYPES.may_load(deps.storage, &pair_addr)? != Some(PairType::Xyk {}) {
+        return Err(ContractError::PriceGuardNotSupported {});
+    }
+
+    match price_guard {
+        Some(price_guard) => {
+            if price_guard.max_deviation_bps == 0
+                || price_guard.max_deviation_bps > BPS_DENOMINATOR
+                || price_guard.twap_window == 0
+                || price_guard.twap_window > MAX_TWAP_WINDOW
+            {
+                return Err(ContractError::InvalidPriceGuard(MAX_TWAP_WINDOW));
+            }
+
+            PRICE_GUARDS.save(deps.storage, &pair_addr, &price_guard)?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "update_price_guard"),
+                attr("pair_contract_addr", pair_addr),
+                attr(
+                    "max_deviation_bps",
+                    price_guard.max_deviation_bps.to_string(),
+                ),
+                attr("twap_window", price_guard.twap_window.to_string()),
+            ]))
+        }
+        None => {
+            PRICE_GUARDS.remove(deps.storage, &pair_addr);
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "update_price_guard"),
+                attr("pair_contract_addr", pair_addr),
+            ]))
+        }
+    }
+}
+
+/// ## Description
+/// Saves the types of the next batch of pairs registered before V1.3.0. Returns a [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of pairs to migrate.
+///
+/// ## Executor
+/// Only the owner can execute this.
+pub fn execute_migrate_pair_types(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let progress = PAIR_TYPES_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::PairTypesAlreadyMigrated {})?;
+    let limit = limit
+        .unwrap_or(DEFAULT_MIGRATION_LIMIT)
+        .clamp(1, MAX_MIGRATION_LIMIT) as usize;
+
+    let last_key = migrate_pair_types(deps.branch(), progress.last_key, limit)?;
+    let finished = last_key.is_none();
+    if finished {
+        PAIR_TYPES_MIGRATION.remove(deps.storage);
+    } else {
+        PAIR_TYPES_MIGRATION.save(deps.storage, &PairTypesMigration { last_key })?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate_pair_types"),
+        attr("finished", finished.to_string()),
+    ]))
+}
+
 /// ## Description
 /// Exposes all the queries available in the contract.
 /// ## Params
@@ -478,6 +994,15 @@ pub fn deregister(
 /// * **QueryMsg::FeeInfo { pair_type }** Returns the fee structure (total and maker fees) for a specific pair type.
 ///
 /// * **QueryMsg::BlacklistedPairTypes {}** Returns a vector that contains blacklisted pair types
+///
+/// * **QueryMsg::SwapHooksWhitelist {}** Returns a vector with the contracts that are allowed to register swap hooks
+///
+/// * **QueryMsg::ExternalAdapter { adapter }** Returns information about a registered external adapter
+/// using an [`ExternalAdapterInfo`] object.
+///
+/// * **QueryMsg::ActiveExternalAdapters {}** Returns a vector with the enabled external adapters
+///
+/// * **QueryMsg::PriceGuard { pair_addr }** Returns the price guard of a pair, if any.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -488,9 +1013,36 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::FeeInfo { pair_type } => to_binary(&query_fee_info(deps, pair_type)?),
         QueryMsg::BlacklistedPairTypes {} => to_binary(&query_blacklisted_pair_types(deps)?),
+        QueryMsg::SwapHooksWhitelist {} => to_binary(
+            &SWAP_HOOKS_WHITELIST
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::ExternalAdapter { adapter } => to_binary(
+            &EXTERNAL_ADAPTERS.load(deps.storage, &addr_validate_to_lower(deps.api, &adapter)?)?,
+        ),
+        QueryMsg::ActiveExternalAdapters {} => to_binary(&query_active_external_adapters(deps)?),
+        QueryMsg::PriceGuard { pair_addr } => to_binary(
+            &PRICE_GUARDS.may_load(deps.storage, &addr_validate_to_lower(deps.api, &pair_addr)?)?,
+        ),
     }
 }
 
+/// ## Description
+/// Returns a vector that contains the enabled external adapters
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+pub fn query_active_external_adapters(deps: Deps) -> StdResult<Vec<ExternalAdapterInfo>> {
+    EXTERNAL_ADAPTERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((_, adapter)) if adapter.enabled => Some(Ok(adapter)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
+
 /// ## Description
 /// Returns a vector that contains blacklisted pair types
 pub fn query_blacklisted_pair_types(deps: Deps) -> StdResult<Vec<PairType>> {
@@ -516,19 +1068,32 @@ pub fn query_blacklisted_pair_types(deps: Deps) -> StdResult<Vec<PairType>> {
 /// * **deps** is an object of type [`Deps`].
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
+    let pair_configs: Vec<PairConfig> = PAIR_CONFIGS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (_, cfg) = item.unwrap();
+            cfg
+        })
+        .collect();
+    let pairs_count = pair_configs
+        .iter()
+        .map(|cfg| {
+            let count = PAIRS_COUNT
+                .may_load(deps.storage, cfg.pair_type.to_string())?
+                .unwrap_or_default();
+            Ok((cfg.pair_type.clone(), count))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     let resp = ConfigResponse {
         owner: config.owner,
         token_code_id: config.token_code_id,
-        pair_configs: PAIR_CONFIGS
-            .range(deps.storage, None, None, Order::Ascending)
-            .map(|item| {
-                let (_, cfg) = item.unwrap();
-                cfg
-            })
-            .collect(),
+        pair_configs,
         fee_address: config.fee_address,
         generator_address: config.generator_address,
         whitelist_code_id: config.whitelist_code_id,
+        referral_registry: config.referral_registry,
+        emergency_admin: config.emergency_admin,
+        pairs_count,
     };
 
     Ok(resp)
@@ -581,46 +1146,53 @@ pub fn query_fee_info(deps: Deps, pair_type: PairType) -> StdResult<FeeInfoRespo
         fee_address: config.fee_address,
         total_fee_bps: pair_config.total_fee_bps,
         maker_fee_bps: pair_config.maker_fee_bps,
+        referral_registry: config.referral_registry,
+        fee_discount: FEE_DISCOUNT.may_load(deps.storage)?,
     })
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version.
 /// ## Params
-/// * **_deps** is an object of type [`Deps`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`MigrateMsg`].
+/// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    let contract_version = get_contract_version(deps.storage)?;
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    match contract_version.contract.as_ref() {
-        "astroport-factory" => match contract_version.version.as_ref() {
-            "1.0.0" => {
-                let msg: migration::MigrationMsgV100 = from_binary(&msg.params)?;
+    match contract_version.version.as_ref() {
+        "1.0.0" => {
+            let msg: migration::MigrationMsgV100 = from_binary(&msg.params)?;
 
-                let config_v100 = migration::CONFIGV100.load(deps.storage)?;
+            let config_v100 = migration::CONFIGV100.load(deps.storage)?;
 
-                let new_config = Config {
-                    whitelist_code_id: msg.whitelist_code_id,
-                    fee_address: config_v100.fee_address,
-                    generator_address: config_v100.generator_address,
-                    owner: config_v100.owner,
-                    token_code_id: config_v100.token_code_id,
-                };
+            let new_config = Config {
+                whitelist_code_id: msg.whitelist_code_id,
+                fee_address: config_v100.fee_address,
+                generator_address: config_v100.generator_address,
+                owner: config_v100.owner,
+                token_code_id: config_v100.token_code_id,
+                referral_registry: None,
+                emergency_admin: None,
+            };
 
-                CONFIG.save(deps.storage, &new_config)?;
+            CONFIG.save(deps.storage, &new_config)?;
 
-                migrate_pair_configs_to_v120(deps.storage)?
-            }
-            "1.1.0" => migrate_pair_configs_to_v120(deps.storage)?,
-            _ => return Err(ContractError::MigrationError {}),
-        },
+            migrate_pair_configs_to_v120(deps.storage)?
+        }
+        "1.1.0" => migrate_pair_configs_to_v120(deps.storage)?,
+        // The stored config is still valid, only the pair types need to be saved
+        "1.2.0" => {}
         _ => return Err(ContractError::MigrationError {}),
     }
 
+    // Pair types are saved in batches with ExecuteMsg::MigratePairTypes
+    PAIR_TYPES_MIGRATION.save(deps.storage, &PairTypesMigration::default())?;
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
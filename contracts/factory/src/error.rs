@@ -17,6 +17,12 @@ pub enum ContractError {
     #[error("Pair was already registered")]
     PairWasRegistered {},
 
+    #[error("The registered pair info doesn't match the requested pair")]
+    PairInfoMismatch {},
+
+    #[error("The LP token of the registered pair must be minted by the pair")]
+    InvalidLiquidityToken {},
+
     #[error("Duplicate of pair configs")]
     PairConfigDuplicate {},
 
@@ -32,6 +38,24 @@ pub enum ContractError {
     #[error("Doubling assets in asset infos")]
     DoublingAssets {},
 
+    #[error("External adapter {0} not found")]
+    ExternalAdapterNotFound(String),
+
+    #[error("An adapter is already registered for the {0} venue")]
+    VenueAlreadyRegistered(String),
+
+    #[error("The maximum fee discount must be smaller than or equal to {0} bps")]
+    FeeDiscountTooHigh(u16),
+
+    #[error("Price guards are only supported by registered constant product pairs")]
+    PriceGuardNotSupported {},
+
+    #[error("The maximum price deviation must be between 1 and 10,000 bps and the TWAP window between 1 and {0} seconds")]
+    InvalidPriceGuard(u64),
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
+
+    #[error("All pair types are already migrated!")]
+    PairTypesAlreadyMigrated {},
 }
@@ -1,7 +1,8 @@
-use crate::state::PAIR_CONFIGS;
+use crate::querier::query_pair_info;
+use crate::state::{add_pair_type, PAIRS, PAIR_CONFIGS, PAIR_TYPES};
 use astroport::factory::{PairConfig, PairType};
-use cosmwasm_std::{Addr, StdError, Storage};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, DepsMut, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -66,3 +67,42 @@ pub fn migrate_pair_configs_to_v120(storage: &mut dyn Storage) -> Result<(), Std
 
     Ok(())
 }
+
+/// ## Description
+/// Saves the type of the next batch of registered pairs and counts the registered pairs of each type.
+/// The pair type is taken from the pair contract itself. Pairs registered after the upgrade already
+/// have a saved type, so they are skipped and not counted twice.
+///
+/// Returns the key of the last migrated pair, or [`None`] if all pairs are migrated.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **last_key** is an [`Option`] field of type [`Binary`]. This is the key of the last pair migrated by the previous batch.
+///
+/// * **limit** is the maximum amount of pairs to migrate.
+pub fn migrate_pair_types(
+    deps: DepsMut,
+    last_key: Option<Binary>,
+    limit: usize,
+) -> StdResult<Option<Binary>> {
+    let start = last_key.map(|key| Bound::exclusive(key.to_vec()));
+    let pairs = PAIRS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(Vec<u8>, Addr)>>>()?;
+
+    for (_, pair) in &pairs {
+        if PAIR_TYPES.may_load(deps.storage, pair)?.is_some() {
+            continue;
+        }
+
+        let pair_info = query_pair_info(deps.as_ref(), pair)?;
+        add_pair_type(deps.storage, pair, &pair_info.pair_type)?;
+    }
+
+    if pairs.len() < limit {
+        return Ok(None);
+    }
+
+    Ok(pairs.last().map(|(key, _)| Binary::from(key.as_slice())))
+}
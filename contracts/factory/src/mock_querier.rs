@@ -5,6 +5,7 @@ use cosmwasm_std::{
     from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
     QueryRequest, SystemError, SystemResult, WasmQuery,
 };
+use cw20::{Cw20QueryMsg, MinterResponse};
 use std::collections::HashMap;
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies.
@@ -30,12 +31,22 @@ pub struct WasmMockQuerier {
 #[derive(Clone, Default)]
 pub struct AstroportPairQuerier {
     pairs: HashMap<String, PairInfo>,
+    /// LP token minters. The first configured pair that reports an LP token is its minter
+    minters: HashMap<String, String>,
 }
 
 impl AstroportPairQuerier {
     pub fn new(pairs: &[(&String, &PairInfo)]) -> Self {
+        let mut minters: HashMap<String, String> = HashMap::new();
+        for (_, pair) in pairs.iter() {
+            minters
+                .entry(pair.liquidity_token.to_string())
+                .or_insert_with(|| pair.contract_addr.to_string());
+        }
+
         AstroportPairQuerier {
             pairs: pairs_to_map(pairs),
+            minters,
         }
     }
 }
@@ -67,22 +78,37 @@ impl Querier for WasmMockQuerier {
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match &request {
-            QueryRequest::Wasm(WasmQuery::Smart {contract_addr, msg})// => {
-                => match from_binary(&msg).unwrap() {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Ok(Cw20QueryMsg::Minter {}) = from_binary(msg) {
+                    // LP tokens are minted by the pairs that own them
+                    let minter =
+                        self.astroport_pair_querier
+                            .minters
+                            .get(contract_addr)
+                            .map(|minter| MinterResponse {
+                                minter: minter.clone(),
+                                cap: None,
+                            });
+
+                    return SystemResult::Ok(to_binary(&minter).into());
+                }
+
+                match from_binary(msg).unwrap() {
                     QueryMsg::Pair {} => {
-                       let pair_info: PairInfo =
-                        match self.astroport_pair_querier.pairs.get(contract_addr) {
-                            Some(v) => v.clone(),
-                            None => {
-                                return SystemResult::Err(SystemError::NoSuchContract {
-                                    addr: contract_addr.clone(),
-                                })
-                            }
-                        };
+                        let pair_info: PairInfo =
+                            match self.astroport_pair_querier.pairs.get(contract_addr) {
+                                Some(v) => v.clone(),
+                                None => {
+                                    return SystemResult::Err(SystemError::NoSuchContract {
+                                        addr: contract_addr.clone(),
+                                    })
+                                }
+                            };
 
-                    SystemResult::Ok(to_binary(&pair_info).into())
+                        SystemResult::Ok(to_binary(&pair_info).into())
                     }
-                    _ => panic!("DO NOT ENTER HERE")
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
             }
             _ => self.base.handle_query(request),
         }
@@ -1,6 +1,7 @@
 use astroport::asset::PairInfo;
 use astroport::pair::QueryMsg;
 use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, WasmQuery};
+use cw20::{Cw20QueryMsg, MinterResponse};
 
 /// ## Description
 /// Returns information about a pair (using the [`PairInfo`] struct).
@@ -12,3 +13,17 @@ pub fn query_pair_info(deps: Deps, pair_contract: &Addr) -> StdResult<PairInfo>
         msg: to_binary(&QueryMsg::Pair {})?,
     }))
 }
+
+/// ## Description
+/// Returns the minter of a CW20 token or [`None`] if the token has no minter.
+/// ## Params
+/// `token_contract` is a param of type [`Addr`]. This is the token for which to retrieve the minter.
+pub fn query_token_minter(deps: Deps, token_contract: &Addr) -> StdResult<Option<Addr>> {
+    let res: Option<MinterResponse> =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: token_contract.to_string(),
+            msg: to_binary(&Cw20QueryMsg::Minter {})?,
+        }))?;
+
+    Ok(res.map(|minter| Addr::unchecked(minter.minter)))
+}
@@ -2,12 +2,14 @@ use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Deps, Order};
+use cosmwasm_std::{Addr, Binary, Deps, Order, StdResult, Storage};
 
-use astroport::asset::AssetInfo;
+use astroport::asset::{pair_key, AssetInfo};
 
 use astroport::common::OwnershipProposal;
-use astroport::factory::PairConfig;
+use astroport::factory::{
+    ExternalAdapterInfo, FeeDiscountConfig, PairConfig, PairType, PriceGuard,
+};
 
 /// ## Description
 /// This structure holds the main contract parameters.
@@ -23,6 +25,10 @@ pub struct Config {
     pub fee_address: Option<Addr>,
     /// CW1 whitelist contract code id used to store 3rd party generator staking rewards
     pub whitelist_code_id: u64,
+    /// Referral registry contract address
+    pub referral_registry: Option<Addr>,
+    /// Emergency admin contract address
+    pub emergency_admin: Option<Addr>,
 }
 
 /// ## Description
@@ -30,6 +36,7 @@ pub struct Config {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TmpPairInfo {
     pub pair_key: Vec<u8>,
+    pub pair_type: PairType,
 }
 
 /// Saves a pair's key
@@ -38,23 +45,68 @@ pub const TMP_PAIR_INFO: Item<TmpPairInfo> = Item::new("tmp_pair_info");
 /// Saves factory settings
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// Saves the fee discount contract that pairs query when computing the swap commission
+pub const FEE_DISCOUNT: Item<FeeDiscountConfig> = Item::new("fee_discount");
+
 /// Saves created pairs (from olders to latest)
 pub const PAIRS: Map<&[u8], Addr> = Map::new("pair_info");
 
-/// ## Description
-/// Calculates a pair key from the specified parameters in the `asset_infos` variable.
-/// ## Params
-/// `asset_infos` is an array with two items of type [`AssetInfo`].
-pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
-    let mut asset_infos = asset_infos.to_vec();
-    asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+/// Saves the type of each registered pair
+pub const PAIR_TYPES: Map<&Addr, PairType> = Map::new("pair_types");
 
-    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
-}
+/// Saves the price guards of pairs. The key is the pair address
+pub const PRICE_GUARDS: Map<&Addr, PriceGuard> = Map::new("price_guards");
+
+/// Saves the number of registered pairs of each type. The key is the pair type
+pub const PAIRS_COUNT: Map<String, u64> = Map::new("pairs_count");
+
+/// Saves the contracts that are allowed to register swap hooks in pairs
+pub const SWAP_HOOKS_WHITELIST: Item<Vec<Addr>> = Item::new("swap_hooks_whitelist");
+
+/// Saves the adapter contracts that the router may use for external swaps
+pub const EXTERNAL_ADAPTERS: Map<&Addr, ExternalAdapterInfo> = Map::new("external_adapters");
 
 /// Saves pair type configurations
 pub const PAIR_CONFIGS: Map<String, PairConfig> = Map::new("pair_configs");
 
+/// ## Description
+/// Saves the type of a registered pair and increases the number of pairs of that type.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **pair** is an object of type [`Addr`]. This is the registered pair.
+///
+/// * **pair_type** is an object of type [`PairType`]. This is the type of the pair.
+pub fn add_pair_type(
+    storage: &mut dyn Storage,
+    pair: &Addr,
+    pair_type: &PairType,
+) -> StdResult<()> {
+    PAIR_TYPES.save(storage, pair, pair_type)?;
+    PAIRS_COUNT.update(storage, pair_type.to_string(), |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Removes the type of a deregistered pair and decreases the number of pairs of that type.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **pair** is an object of type [`Addr`]. This is the deregistered pair.
+pub fn remove_pair_type(storage: &mut dyn Storage, pair: &Addr) -> StdResult<()> {
+    if let Some(pair_type) = PAIR_TYPES.may_load(storage, pair)? {
+        PAIR_TYPES.remove(storage, pair);
+        PAIRS_COUNT.update(storage, pair_type.to_string(), |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default().saturating_sub(1))
+        })?;
+    }
+
+    Ok(())
+}
+
 /// ## Pagination settings
 /// The maximum limit for reading pairs from [`PAIRS`]
 const MAX_LIMIT: u32 = 30;
@@ -93,16 +145,26 @@ pub fn read_pairs(
 /// It is the token pair which we use to determine the start index for a range when returning data for multiple pairs
 fn calc_range_start(start_after: Option<[AssetInfo; 2]>) -> Option<Vec<u8>> {
     start_after.map(|asset_infos| {
-        let mut asset_infos = asset_infos.to_vec();
-        asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
-
-        let mut v = [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()]
-            .concat()
-            .as_slice()
-            .to_vec();
+        let mut v = pair_key(&asset_infos);
         v.push(1);
         v
     })
 }
 
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// This structure stores the progress of saving the types of the pairs registered before V1.3.0.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PairTypesMigration {
+    /// The key of the last migrated pair in [`PAIRS`]
+    pub last_key: Option<Binary>,
+}
+
+/// Stores the progress of saving the types of the pairs registered before V1.3.0. It is removed once all pairs are migrated
+pub const PAIR_TYPES_MIGRATION: Item<PairTypesMigration> = Item::new("pair_types_migration");
+
+/// The default amount of pairs migrated at once
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 10;
+
+/// The maximum amount of pairs migrated at once. Every pair is queried, which bounds the gas used by a migration transaction
+pub const MAX_MIGRATION_LIMIT: u32 = 30;
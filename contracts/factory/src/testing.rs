@@ -1,24 +1,27 @@
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, ContractResult, Reply, ReplyOn, SubMsg,
+    attr, from_binary, to_binary, Addr, ContractResult, Deps, Reply, ReplyOn, SubMsg,
     SubMsgExecutionResponse, WasmMsg,
 };
 
 use crate::mock_querier::mock_dependencies;
-use crate::state::CONFIG;
+use crate::state::{add_pair_type, CONFIG, PAIRS};
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, migrate, query},
     error::ContractError,
 };
 
-use astroport::asset::{AssetInfo, PairInfo};
+use astroport::asset::{pair_key, AssetInfo, PairInfo};
 use astroport::factory::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, PairConfig, PairType, PairsResponse, QueryMsg,
+    ConfigResponse, ExecuteMsg, ExternalAdapterInfo, FeeDiscountConfig, FeeInfoResponse,
+    InstantiateMsg, MigrateMsg, PairConfig, PairType, PairsResponse, PriceGuard, QueryMsg,
+    UpdateAddr,
 };
 
 use crate::contract::reply;
 use crate::response::MsgInstantiateContractResponse;
 use astroport::pair::InstantiateMsg as PairInstantiateMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cw2::{get_contract_version, set_contract_version};
 use protobuf::Message;
 
 #[test]
@@ -164,6 +167,7 @@ fn update_config() {
         fee_address: Some(String::from("new_fee_addr")),
         generator_address: Some(String::from("new_generator_addr")),
         whitelist_code_id: None,
+        referral_registry: None,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
@@ -191,6 +195,7 @@ fn update_config() {
         fee_address: None,
         generator_address: None,
         whitelist_code_id: None,
+        referral_registry: None,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
@@ -275,6 +280,498 @@ fn update_owner() {
     assert_eq!(new_owner, config.owner);
 }
 
+#[test]
+fn update_swap_hooks_whitelist() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        generator_address: None,
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    let info = mock_info(owner, &[]);
+
+    instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateSwapHooksWhitelist {
+        add: Some(vec![String::from("hook0000"), String::from("hook0001")]),
+        remove: None,
+    };
+
+    // Unauthorized check
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(owner, &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info(owner, &[]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::UpdateSwapHooksWhitelist {
+            add: Some(vec![String::from("hook0001")]),
+            remove: Some(vec![String::from("hook0000")]),
+        },
+    )
+    .unwrap();
+
+    let whitelist: Vec<Addr> =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::SwapHooksWhitelist {}).unwrap()).unwrap();
+    assert_eq!(whitelist, vec![Addr::unchecked("hook0001")]);
+}
+
+#[test]
+fn external_adapters() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        generator_address: None,
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterExternalAdapter {
+        adapter: String::from("adapter0000"),
+        venue: String::from("terraswap"),
+    };
+
+    // Unauthorized check
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // Only one adapter can be registered per venue
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::RegisterExternalAdapter {
+            adapter: String::from("adapter0001"),
+            venue: String::from("terraswap"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VenueAlreadyRegistered(String::from("terraswap"))
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::RegisterExternalAdapter {
+            adapter: String::from("adapter0001"),
+            venue: String::from("loop"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SetExternalAdapterEnabled {
+            adapter: String::from("adapter0000"),
+            enabled: false,
+        },
+    )
+    .unwrap();
+
+    let adapter: ExternalAdapterInfo = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ExternalAdapter {
+                adapter: String::from("adapter0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        adapter,
+        ExternalAdapterInfo {
+            adapter: Addr::unchecked("adapter0000"),
+            venue: String::from("terraswap"),
+            enabled: false,
+        }
+    );
+
+    let active: Vec<ExternalAdapterInfo> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ActiveExternalAdapters {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].adapter, Addr::unchecked("adapter0001"));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::DeregisterExternalAdapter {
+            adapter: String::from("adapter0001"),
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SetExternalAdapterEnabled {
+            adapter: String::from("adapter0001"),
+            enabled: true,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExternalAdapterNotFound(String::from("adapter0001"))
+    );
+
+    let active: Vec<ExternalAdapterInfo> =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::ActiveExternalAdapters {}).unwrap())
+            .unwrap();
+    assert!(active.is_empty());
+}
+
+#[test]
+fn emergency_admin() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+    let pair_config = PairConfig {
+        code_id: 123u64,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 100,
+        maker_fee_bps: 10,
+        is_disabled: false,
+        is_generator_disabled: false,
+    };
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![pair_config.clone()],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        generator_address: None,
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let disable_msg = ExecuteMsg::DisablePairType {
+        pair_type: PairType::Xyk {},
+    };
+
+    // The emergency admin isn't set yet
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("emergency0000", &[]),
+        disable_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Only the owner can set the emergency admin
+    let set_msg = ExecuteMsg::UpdateEmergencyAdmin {
+        emergency_admin: UpdateAddr::Set(String::from("emergency0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("emergency0000", &[]),
+        set_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), set_msg).unwrap();
+
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config_res.emergency_admin,
+        Some(Addr::unchecked("emergency0000"))
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("emergency0000", &[]),
+        ExecuteMsg::DisablePairType {
+            pair_type: PairType::Stable {},
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairConfigNotFound {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("emergency0000", &[]),
+        disable_msg.clone(),
+    )
+    .unwrap();
+
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config_res.pair_configs,
+        vec![PairConfig {
+            is_disabled: true,
+            ..pair_config
+        }]
+    );
+
+    // The owner revokes the emergency admin's authority
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateEmergencyAdmin {
+            emergency_admin: UpdateAddr::Remove {},
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("emergency0000", &[]),
+        disable_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn fee_discount() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 3333,
+            is_disabled: false,
+            is_generator_disabled: false,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        generator_address: None,
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let fee_info_msg = QueryMsg::FeeInfo {
+        pair_type: PairType::Xyk {},
+    };
+    let fee_info: FeeInfoResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), fee_info_msg.clone()).unwrap()).unwrap();
+    assert_eq!(fee_info.fee_discount, None);
+
+    // Only the owner can set the fee discount contract
+    let set_msg = ExecuteMsg::UpdateFeeDiscount {
+        discount_contract: UpdateAddr::Set(String::from("discount0000")),
+        max_discount_bps: 2_500,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        set_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The discount is capped by the factory
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateFeeDiscount {
+            discount_contract: UpdateAddr::Set(String::from("discount0000")),
+            max_discount_bps: 5_001,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FeeDiscountTooHigh(5_000));
+
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), set_msg).unwrap();
+
+    let fee_info: FeeInfoResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), fee_info_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        fee_info.fee_discount,
+        Some(FeeDiscountConfig {
+            discount_contract: Addr::unchecked("discount0000"),
+            max_discount_bps: 2_500,
+        })
+    );
+
+    // Removing the contract disables fee discounts
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateFeeDiscount {
+            discount_contract: UpdateAddr::Remove {},
+            max_discount_bps: 0,
+        },
+    )
+    .unwrap();
+
+    let fee_info: FeeInfoResponse =
+        from_binary(&query(deps.as_ref(), env, fee_info_msg).unwrap()).unwrap();
+    assert_eq!(fee_info.fee_discount, None);
+}
+
+#[test]
+fn price_guard() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        generator_address: None,
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    add_pair_type(
+        deps.as_mut().storage,
+        &Addr::unchecked("pair0000"),
+        &PairType::Xyk {},
+    )
+    .unwrap();
+    add_pair_type(
+        deps.as_mut().storage,
+        &Addr::unchecked("pair0001"),
+        &PairType::Stable {},
+    )
+    .unwrap();
+
+    let guard = PriceGuard {
+        max_deviation_bps: 500,
+        twap_window: 600,
+    };
+    let update_msg =
+        |pair_addr: &str, price_guard: Option<PriceGuard>| ExecuteMsg::UpdatePriceGuard {
+            pair_addr: pair_addr.to_string(),
+            price_guard,
+        };
+
+    // Only the owner can set a price guard
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        update_msg("pair0000", Some(guard.clone())),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Only registered constant product pairs support price guards
+    for pair in ["pair0001", "pair0002"] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            update_msg(pair, Some(guard.clone())),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PriceGuardNotSupported {});
+    }
+
+    for invalid_guard in [
+        PriceGuard {
+            max_deviation_bps: 0,
+            twap_window: 600,
+        },
+        PriceGuard {
+            max_deviation_bps: 10_001,
+            twap_window: 600,
+        },
+        PriceGuard {
+            max_deviation_bps: 500,
+            twap_window: 0,
+        },
+        PriceGuard {
+            max_deviation_bps: 500,
+            twap_window: 86_401,
+        },
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            update_msg("pair0000", Some(invalid_guard)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidPriceGuard(86_400));
+    }
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        update_msg("pair0000", Some(guard.clone())),
+    )
+    .unwrap();
+
+    let query_msg = QueryMsg::PriceGuard {
+        pair_addr: String::from("pair0000"),
+    };
+    let res: Option<PriceGuard> =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(res, Some(guard));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        update_msg("pair0000", None),
+    )
+    .unwrap();
+
+    let res: Option<PriceGuard> =
+        from_binary(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(res, None);
+}
+
 #[test]
 fn update_pair_config() {
     let mut deps = mock_dependencies(&[]);
@@ -659,6 +1156,10 @@ fn register() {
         ]
     );
 
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config_res: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config_res.pairs_count, vec![(PairType::Xyk {}, 2)]);
+
     let query_msg = QueryMsg::Pairs {
         start_after: None,
         limit: Some(1),
@@ -739,4 +1240,323 @@ fn register() {
             pair_type: PairType::Xyk {},
         },]
     );
+
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config_res: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config_res.pairs_count, vec![(PairType::Xyk {}, 1)]);
+}
+
+#[test]
+fn register_mismatched_pair() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 100,
+            maker_fee_bps: 10,
+            is_disabled: false,
+            is_generator_disabled: false,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: Some(String::from("generator")),
+        owner: String::from("owner0000"),
+        whitelist_code_id: 234u64,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let asset_infos = [
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0001"),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreatePair {
+        pair_type: PairType::Xyk {},
+        asset_infos: asset_infos.clone(),
+        init_params: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let data = MsgInstantiateContractResponse {
+        contract_address: String::from("pair0000"),
+        data: vec![],
+        unknown_fields: Default::default(),
+        cached_size: Default::default(),
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(data.into()),
+        }),
+    };
+
+    // The pair reports assets that weren't requested
+    let pair_addr = String::from("pair0000");
+    let pair_info = PairInfo {
+        asset_infos: [
+            asset_infos[0].clone(),
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0002"),
+            },
+        ],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&pair_addr, &pair_info)]);
+
+    let err = reply(deps.as_mut(), mock_env(), reply_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::PairInfoMismatch {});
+
+    // The pair reports another contract address
+    let pair_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0001"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&pair_addr, &pair_info)]);
+
+    let err = reply(deps.as_mut(), mock_env(), reply_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::PairInfoMismatch {});
+
+    // The pair reports an LP token that it can't mint
+    let other_pair_addr = String::from("pair0001");
+    let other_pair_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0001"),
+        liquidity_token: Addr::unchecked("liquidity0001"),
+        pair_type: PairType::Xyk {},
+    };
+    let pair_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0001"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier.with_astroport_pairs(&[
+        (&other_pair_addr, &other_pair_info),
+        (&pair_addr, &pair_info),
+    ]);
+
+    let err = reply(deps.as_mut(), mock_env(), reply_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLiquidityToken {});
+
+    // The pair info matches the request, assets are compared regardless of their order
+    let pair_info = PairInfo {
+        asset_infos: [asset_infos[1].clone(), asset_infos[0].clone()],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&pair_addr, &pair_info)]);
+
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+}
+
+#[test]
+fn migrate_from_v120() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![
+            PairConfig {
+                code_id: 123u64,
+                pair_type: PairType::Xyk {},
+                total_fee_bps: 100,
+                maker_fee_bps: 10,
+                is_disabled: false,
+                is_generator_disabled: false,
+            },
+            PairConfig {
+                code_id: 124u64,
+                pair_type: PairType::Stable {},
+                total_fee_bps: 5,
+                maker_fee_bps: 10,
+                is_disabled: false,
+                is_generator_disabled: false,
+            },
+        ],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: Some(String::from("generator")),
+        owner: "owner0000".to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // V1.2.0 only stored the pair addresses
+    let mut deployed_pairs = vec![];
+    for (i, pair_type) in [PairType::Xyk {}, PairType::Xyk {}, PairType::Stable {}]
+        .into_iter()
+        .enumerate()
+    {
+        let asset_infos = [
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked(format!("asset{:04}", i)),
+            },
+        ];
+        let pair_info = PairInfo {
+            asset_infos: asset_infos.clone(),
+            contract_addr: Addr::unchecked(format!("pair{:04}", i)),
+            liquidity_token: Addr::unchecked(format!("liquidity{:04}", i)),
+            pair_type,
+        };
+        PAIRS
+            .save(
+                deps.as_mut().storage,
+                &pair_key(&asset_infos),
+                &pair_info.contract_addr,
+            )
+            .unwrap();
+        deployed_pairs.push((pair_info.contract_addr.to_string(), pair_info));
+    }
+    deps.querier.with_astroport_pairs(
+        &deployed_pairs
+            .iter()
+            .map(|(addr, pair_info)| (addr, pair_info))
+            .collect::<Vec<_>>(),
+    );
+
+    set_contract_version(deps.as_mut().storage, "astroport-factory", "1.2.0").unwrap();
+
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            params: Default::default(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-factory"),
+            attr("previous_contract_version", "1.2.0"),
+            attr("new_contract_name", "astroport-factory"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+    assert_eq!(
+        get_contract_version(deps.as_ref().storage).unwrap().version,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let query_pairs_count = |deps: Deps| {
+        let res = query(deps, mock_env(), QueryMsg::Config {}).unwrap();
+        let config_res: ConfigResponse = from_binary(&res).unwrap();
+        config_res.pairs_count
+    };
+
+    // Pair types are saved in batches
+    assert_eq!(
+        query_pairs_count(deps.as_ref()),
+        vec![(PairType::Stable {}, 0), (PairType::Xyk {}, 0)]
+    );
+
+    let migrate_pair_types = |limit| ExecuteMsg::MigratePairTypes { limit };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        migrate_pair_types(Some(2)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        migrate_pair_types(Some(2)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_pair_types"),
+            attr("finished", "false")
+        ]
+    );
+    assert_eq!(
+        query_pairs_count(deps.as_ref()),
+        vec![(PairType::Stable {}, 0), (PairType::Xyk {}, 2)]
+    );
+
+    // A pair registered during the migration is counted once
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0003"),
+        },
+    ];
+    let new_pair = Addr::unchecked("pair0003");
+    PAIRS
+        .save(deps.as_mut().storage, &pair_key(&asset_infos), &new_pair)
+        .unwrap();
+    add_pair_type(deps.as_mut().storage, &new_pair, &PairType::Stable {}).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        migrate_pair_types(None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_pair_types"),
+            attr("finished", "true")
+        ]
+    );
+    assert_eq!(
+        query_pairs_count(deps.as_ref()),
+        vec![(PairType::Stable {}, 2), (PairType::Xyk {}, 2)]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        migrate_pair_types(None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairTypesAlreadyMigrated {});
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            params: Default::default(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
 }
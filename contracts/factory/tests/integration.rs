@@ -129,6 +129,7 @@ fn update_config() {
         fee_address: fee_address.clone(),
         generator_address: generator_address.clone(),
         whitelist_code_id: None,
+        referral_registry: None,
     };
 
     app.execute_contract(
@@ -161,6 +162,7 @@ fn update_config() {
         fee_address: None,
         generator_address: None,
         whitelist_code_id: None,
+        referral_registry: None,
     };
 
     let res = app
@@ -231,6 +233,7 @@ fn create_pair() {
             minter: owner_addr.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_instance0 = app
@@ -255,6 +258,7 @@ fn create_pair() {
             minter: owner_addr.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_instance1 = app
@@ -3,10 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use astroport::asset::PairInfo;
+use astroport::asset::{Asset, PairInfo};
 use astroport::pair::{
-    CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse,
-    QueryMsg, ReverseSimulationResponse, SimulationResponse,
+    ConfigResponse, CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    PoolResponse, QueryMsg, ReverseSimulationResponse, SimulationResponse,
 };
 
 fn main() {
@@ -37,4 +37,7 @@ fn main() {
         &out_dir,
         "CumulativePricesResponse",
     );
+    export_schema_with_title(&schema_for!(PairInfo), &out_dir, "PairResponse");
+    export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
+    export_schema_with_title(&schema_for!(Vec<Asset>), &out_dir, "ShareResponse");
 }
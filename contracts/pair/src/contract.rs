@@ -1,24 +1,38 @@
 use crate::error::ContractError;
-use crate::state::{Config, CONFIG};
+use crate::state::{Config, PriceObservation, CONFIG, PAUSED, SWAP_HOOKS, TWAP_OBSERVATIONS};
 
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128,
-    WasmMsg,
+    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
 };
 
 use crate::response::MsgInstantiateContractResponse;
 use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
-use astroport::factory::PairType;
+use astroport::attr_builder::AttrBuilder;
+use astroport::common::assert_migration_allowed;
+use astroport::constants::BPS_DENOMINATOR;
+use astroport::factory::{PairType, PriceGuard};
+use astroport::fee_discount::discounted_fee_rate;
 use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
-use astroport::pair::{ConfigResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE};
+use astroport::pair::{build_swap_hook_msgs, SwapHook, SwapHookMsg, MAX_SWAP_HOOKS};
+use astroport::pair::{
+    ConfigResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE, MINIMUM_LIQUIDITY_AMOUNT,
+};
 use astroport::pair::{
     CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse,
     QueryMsg, ReverseSimulationResponse, SimulationResponse, TWAP_PRECISION,
 };
-use astroport::querier::{query_factory_config, query_fee_info, query_supply};
-use astroport::{token::InstantiateMsg as TokenInstantiateMsg, U256};
+use astroport::querier::{
+    query_factory_config, query_fee_info, query_price_guard, query_supply,
+    query_swap_hooks_whitelist,
+};
+use astroport::referral::build_referral_fee_msgs;
+use astroport::simulation::{
+    compute_lp_share, compute_offer_amount, compute_swap, compute_withdrawn_amounts,
+};
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
@@ -31,6 +45,10 @@ const CONTRACT_NAME: &str = "astroport-pair";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// A `reply` call code ID used for sub-messages.
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
+/// A `reply` call code ID used for swap hook callbacks.
+const SWAP_HOOK_REPLY_ID: u64 = 2;
+/// The number of cumulative price observations kept for the price guard
+const TWAP_OBSERVATIONS_SIZE: usize = 2;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
@@ -89,6 +107,7 @@ pub fn instantiate(
                     minter: env.contract.address.to_string(),
                     cap: None,
                 }),
+                marketing: None,
             })?,
             funds: vec![],
             admin: None,
@@ -113,6 +132,11 @@ pub fn instantiate(
 /// * **msg** is an object of type [`Reply`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id == SWAP_HOOK_REPLY_ID {
+        // A failing swap hook must not revert the swap
+        return Ok(Response::new().add_attribute("action", "swap_hook_failed"));
+    }
+
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     if config.pair_info.liquidity_token != Addr::unchecked("") {
@@ -162,7 +186,16 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             belief_price,
 ///             max_spread,
 ///             to,
+///             referral_code,
 ///         }** Performs a swap operation with the specified parameters.
+///
+/// * **ExecuteMsg::RegisterSwapHook { thresholds }** Registers the sender as a swap hook.
+///
+/// * **ExecuteMsg::DeregisterSwapHook { contract_addr }** Removes a swap hook.
+///
+/// * **ExecuteMsg::Pause {}** Pauses swaps and liquidity provision.
+///
+/// * **ExecuteMsg::Unpause {}** Resumes swaps and liquidity provision.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -192,6 +225,7 @@ pub fn execute(
             belief_price,
             max_spread,
             to,
+            referral_code,
         } => {
             offer_asset.info.check(deps.api)?;
             if !offer_asset.is_native_token() {
@@ -213,8 +247,15 @@ pub fn execute(
                 belief_price,
                 max_spread,
                 to_addr,
+                referral_code,
             )
         }
+        ExecuteMsg::RegisterSwapHook { thresholds } => register_swap_hook(deps, info, thresholds),
+        ExecuteMsg::DeregisterSwapHook { contract_addr } => {
+            deregister_swap_hook(deps, info, contract_addr)
+        }
+        ExecuteMsg::Pause {} => pause(deps, info),
+        ExecuteMsg::Unpause {} => unpause(deps, info),
     }
 }
 
@@ -242,6 +283,7 @@ pub fn receive_cw20(
             belief_price,
             max_spread,
             to,
+            referral_code,
         }) => {
             // Only asset contract can execute this message
             let mut authorized: bool = false;
@@ -277,6 +319,7 @@ pub fn receive_cw20(
                 belief_price,
                 max_spread,
                 to_addr,
+                referral_code,
             )
         }
         Ok(Cw20HookMsg::WithdrawLiquidity {}) => withdraw_liquidity(
@@ -321,6 +364,10 @@ pub fn provide_liquidity(
     auto_stake: Option<bool>,
     receiver: Option<String>,
 ) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
     assets[0].info.check(deps.api)?;
     assets[1].info.check(deps.api)?;
 
@@ -371,27 +418,30 @@ pub fn provide_liquidity(
     }
 
     let total_share = query_supply(&deps.querier, config.pair_info.liquidity_token.clone())?;
-    let share = if total_share.is_zero() {
-        // Initial share = collateral amount
-        Uint128::new(
-            (U256::from(deposits[0].u128()) * U256::from(deposits[1].u128()))
-                .integer_sqrt()
-                .as_u128(),
-        )
-    } else {
+    if !total_share.is_zero() {
         // Assert slippage tolerance
         assert_slippage_tolerance(slippage_tolerance, &deposits, &pools)?;
+    }
+    let mut share = compute_lp_share(deposits, [pools[0].amount, pools[1].amount], total_share)?;
 
-        // min(1, 2)
-        // 1. sqrt(deposit_0 * exchange_rate_0_to_1 * deposit_0) * (total_share / sqrt(pool_0 * pool_1))
-        // == deposit_0 * total_share / pool_0
-        // 2. sqrt(deposit_1 * exchange_rate_1_to_0 * deposit_1) * (total_share / sqrt(pool_1 * pool_1))
-        // == deposit_1 * total_share / pool_1
-        std::cmp::min(
-            deposits[0].multiply_ratio(total_share, pools[0].amount),
-            deposits[1].multiply_ratio(total_share, pools[1].amount),
-        )
-    };
+    if total_share.is_zero() {
+        if share <= MINIMUM_LIQUIDITY_AMOUNT {
+            return Err(ContractError::MinimumLiquidityAmountError {
+                min_lp_to_receive: MINIMUM_LIQUIDITY_AMOUNT,
+            });
+        }
+
+        // Lock a small amount of LP tokens in the pair forever so that the share price can't be inflated
+        messages.extend(mint_liquidity_token_message(
+            deps.as_ref(),
+            &config,
+            env.clone(),
+            env.contract.address.clone(),
+            MINIMUM_LIQUIDITY_AMOUNT,
+            false,
+        )?);
+        share = share.checked_sub(MINIMUM_LIQUIDITY_AMOUNT)?;
+    }
 
     // Mint LP tokens for the sender or for the receiver (if set)
     let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
@@ -414,13 +464,17 @@ pub fn provide_liquidity(
         CONFIG.save(deps.storage, &config)?;
     }
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender.as_str()),
-        attr("receiver", receiver.as_str()),
-        attr("assets", format!("{}, {}", assets[0], assets[1])),
-        attr("share", share.to_string()),
-    ]))
+    let attributes = AttrBuilder::new("provide_liquidity")
+        .sender(info.sender.as_str())
+        .receiver(receiver.as_str())
+        .attr("assets", format!("{}, {}", assets[0], assets[1]))
+        .attr("share", share)
+        .assets("deposit", &assets);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -516,7 +570,7 @@ pub fn withdraw_liquidity(
     }
 
     let (pools, total_share) = pool_info(deps.as_ref(), config.clone())?;
-    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+    let refund_assets = get_share_in_assets(&pools, amount, total_share)?;
 
     // Accumulate prices for the pair assets
     if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
@@ -543,19 +597,19 @@ pub fn withdraw_liquidity(
         }),
     ];
 
-    let attributes = vec![
-        attr("action", "withdraw_liquidity"),
-        attr("sender", sender.as_str()),
-        attr("withdrawn_share", &amount.to_string()),
-        attr(
+    let attributes = AttrBuilder::new("withdraw_liquidity")
+        .sender(sender.as_str())
+        .attr("withdrawn_share", amount)
+        .attr(
             "refund_assets",
             format!("{}, {}", refund_assets[0], refund_assets[1]),
-        ),
-    ];
+        )
+        .assets("refund", &refund_assets);
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attributes(attributes))
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -566,23 +620,24 @@ pub fn withdraw_liquidity(
 /// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to compute a corresponding amount of assets for.
 ///
 /// * **total_share** is an object of type [`Uint128`]. This is the total amount of LP tokens currently minted.
+///
+/// Amounts are rounded down so that the pool never pays out more than the burned share.
 pub fn get_share_in_assets(
     pools: &[Asset; 2],
     amount: Uint128,
     total_share: Uint128,
-) -> Vec<Asset> {
-    let mut share_ratio = Decimal::zero();
-    if !total_share.is_zero() {
-        share_ratio = Decimal::from_ratio(amount, total_share);
-    }
+) -> StdResult<Vec<Asset>> {
+    let amounts =
+        compute_withdrawn_amounts([pools[0].amount, pools[1].amount], amount, total_share)?;
 
-    pools
+    Ok(pools
         .iter()
-        .map(|a| Asset {
+        .zip(amounts.iter())
+        .map(|(a, amount)| Asset {
             info: a.info.clone(),
-            amount: a.amount * share_ratio,
+            amount: *amount,
         })
-        .collect()
+        .collect())
 }
 
 /// ## Description
@@ -605,6 +660,8 @@ pub fn get_share_in_assets(
 /// * **max_spread** is an object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
 ///
 /// * **to** is an object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code used to pay a share of the commission to the referrer.
 /// NOTE - the address that wants to swap should approve the pair contract to pull the offer token.
 #[allow(clippy::too_many_arguments)]
 pub fn swap(
@@ -616,7 +673,12 @@ pub fn swap(
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    referral_code: Option<String>,
 ) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
     offer_asset.assert_sent_native_token_balance(&info)?;
 
     let mut config: Config = CONFIG.load(deps.storage)?;
@@ -656,12 +718,15 @@ pub fn swap(
         config.pair_info.pair_type.clone(),
     )?;
 
+    // Traders holding xASTRO or vxASTRO may get part of the commission waived
+    let commission_rate = discounted_fee_rate(&deps.querier, &fee_info, &sender);
+
     let offer_amount = offer_asset.amount;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
         ask_pool.amount,
         offer_amount,
-        fee_info.total_fee_rate,
+        commission_rate,
     )?;
 
     // Check the max spread limit (if it was specified)
@@ -697,6 +762,37 @@ pub fn swap(
         }
     }
 
+    // Referral fee
+    let mut referral_fee_amount = Uint128::zero();
+    if let Some(referral_code) = referral_code {
+        let referral_registry = fee_info
+            .referral_registry
+            .ok_or(ContractError::ReferralRegistryNotSet {})?;
+        let (referral_fee, mut referral_msgs) = build_referral_fee_msgs(
+            &deps.querier,
+            &referral_registry,
+            &referral_code,
+            &offer_asset,
+            &Asset {
+                info: ask_pool.info.clone(),
+                amount: commission_amount,
+            },
+            commission_amount.checked_sub(maker_fee_amount)?,
+        )?;
+        messages.append(&mut referral_msgs);
+        referral_fee_amount = referral_fee.amount;
+    }
+
+    // Make sure a math bug can't drain the pool
+    assert_constant_product(
+        offer_pool.amount,
+        ask_pool.amount,
+        offer_amount,
+        return_amount
+            .checked_add(maker_fee_amount)?
+            .checked_add(referral_fee_amount)?,
+    )?;
+
     // Accumulate prices for the assets in the pool
     if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
         accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?
@@ -707,23 +803,202 @@ pub fn swap(
         CONFIG.save(deps.storage, &config)?;
     }
 
+    // Compare the execution price against the TWAP if the factory set a price guard for the pair
+    if let Some(price_guard) = query_price_guard(
+        &deps.querier,
+        config.factory_addr.clone(),
+        &config.pair_info.contract_addr,
+    )? {
+        assert_price_guard(
+            deps.storage,
+            &config,
+            &price_guard,
+            offer_pool.info.equal(&pools[0].info),
+            offer_amount,
+            return_amount + commission_amount,
+        )?;
+    }
+
+    // Notify the swap hooks triggered by the swap
+    let hook_msgs = build_swap_hook_msgs(
+        &SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default(),
+        &SwapHookMsg::AfterSwap {
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            spread_amount,
+            commission_amount,
+        },
+        SWAP_HOOK_REPLY_ID,
+    )?;
+
+    let attributes = AttrBuilder::new("swap")
+        .sender(sender.as_str())
+        .receiver(receiver.as_str())
+        .attr("offer_asset", offer_asset.info.to_string())
+        .attr("ask_asset", ask_pool.info.to_string())
+        .attr("offer_amount", offer_amount)
+        .attr("return_amount", return_amount)
+        .attr("tax_amount", tax_amount)
+        .attr("spread_amount", spread_amount)
+        .attr("commission_amount", commission_amount)
+        .attr("maker_fee_amount", maker_fee_amount)
+        .attr("referral_fee_amount", referral_fee_amount);
+
     Ok(Response::new()
         .add_messages(
             // 1. send collateral tokens from the contract to a user
             // 2. send inactive commission fees to the Maker ontract
             messages,
         )
-        .add_attribute("action", "swap")
-        .add_attribute("sender", sender.as_str())
-        .add_attribute("receiver", receiver.as_str())
-        .add_attribute("offer_asset", offer_asset.info.to_string())
-        .add_attribute("ask_asset", ask_pool.info.to_string())
-        .add_attribute("offer_amount", offer_amount.to_string())
-        .add_attribute("return_amount", return_amount.to_string())
-        .add_attribute("tax_amount", tax_amount.to_string())
-        .add_attribute("spread_amount", spread_amount.to_string())
-        .add_attribute("commission_amount", commission_amount.to_string())
-        .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
+        .add_submessages(hook_msgs)
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
+}
+
+/// ## Description
+/// Registers the sender as a swap hook or replaces its thresholds. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **thresholds** is a vector that contains objects of type [`Asset`]. These are the minimum offer amounts
+/// that trigger the hook.
+///
+/// ## Executor
+/// Only contracts whitelisted in the factory can execute this.
+pub fn register_swap_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    thresholds: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !query_swap_hooks_whitelist(&deps.querier, config.factory_addr)?.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for threshold in &thresholds {
+        threshold.info.check(deps.api)?;
+        if !config
+            .pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&threshold.info))
+        {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    let mut hooks = SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    hooks.retain(|hook| hook.contract_addr != info.sender);
+    if hooks.len() >= MAX_SWAP_HOOKS {
+        return Err(ContractError::TooManySwapHooks(MAX_SWAP_HOOKS));
+    }
+    hooks.push(SwapHook {
+        contract_addr: info.sender.clone(),
+        thresholds,
+    });
+    SWAP_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_swap_hook"),
+        attr("contract_addr", info.sender),
+    ]))
+}
+
+/// ## Description
+/// Removes a swap hook. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **contract_addr** is an object of type [`String`]. This is the hook contract to remove.
+///
+/// ## Executor
+/// The hook itself can execute this. Anyone can remove a hook that isn't whitelisted in the factory anymore.
+pub fn deregister_swap_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_addr: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let contract_addr = addr_validate_to_lower(deps.api, &contract_addr)?;
+
+    let mut hooks = SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if !hooks.iter().any(|hook| hook.contract_addr == contract_addr) {
+        return Err(ContractError::SwapHookNotFound(contract_addr.to_string()));
+    }
+
+    // Permission check
+    if info.sender != contract_addr
+        && query_swap_hooks_whitelist(&deps.querier, config.factory_addr)?.contains(&contract_addr)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    hooks.retain(|hook| hook.contract_addr != contract_addr);
+    SWAP_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deregister_swap_hook"),
+        attr("contract_addr", contract_addr),
+    ]))
+}
+
+/// ## Description
+/// Pauses swaps and liquidity provision. Withdrawing liquidity stays possible.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// The factory owner or the factory's emergency admin can execute this.
+pub fn pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let factory_config = query_factory_config(&deps.querier, config.factory_addr)?;
+
+    // Permission check
+    if info.sender != factory_config.owner
+        && Some(&info.sender) != factory_config.emergency_admin.as_ref()
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "pause"))
+}
+
+/// ## Description
+/// Resumes swaps and liquidity provision. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// Only the factory owner can execute this.
+pub fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != query_factory_config(&deps.querier, config.factory_addr)?.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::new().add_attribute("action", "unpause"))
 }
 
 /// ## Description
@@ -772,6 +1047,91 @@ pub fn accumulate_prices(
     Ok(Some((pcl0, pcl1, block_time)))
 }
 
+/// ## Description
+/// Ensures that the execution price of a swap doesn't deviate from the pair's TWAP by more than the
+/// price guard allows, then records the cumulative prices used as the start of later TWAP windows.
+/// Cumulative prices only include the prices of previous blocks, so the TWAP can't be moved within
+/// the block of the swap. The check is skipped until the pair has recorded price history.
+/// Returns a [`ContractError`] on failure, otherwise returns [`Ok`].
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **config** is an object of type [`Config`]. Its cumulative prices must be up to date.
+///
+/// * **price_guard** is an object of type [`PriceGuard`]. This is the guard set for the pair in the factory.
+///
+/// * **offer_asset0** is an object of type [`bool`]. This is true if asset 0 is offered in the swap.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of the offered asset.
+///
+/// * **ask_amount** is an object of type [`Uint128`]. This is the amount of the asked asset before the commission.
+pub fn assert_price_guard(
+    storage: &mut dyn Storage,
+    config: &Config,
+    price_guard: &PriceGuard,
+    offer_asset0: bool,
+    offer_amount: Uint128,
+    ask_amount: Uint128,
+) -> Result<(), ContractError> {
+    let current = PriceObservation {
+        timestamp: config.block_time_last,
+        price0_cumulative: config.price0_cumulative_last,
+        price1_cumulative: config.price1_cumulative_last,
+    };
+    let mut observations = TWAP_OBSERVATIONS.may_load(storage)?.unwrap_or_default();
+
+    if let Some(start) = observations
+        .first()
+        .filter(|start| start.timestamp < current.timestamp)
+    {
+        let price_cumulative_diff = if offer_asset0 {
+            current
+                .price0_cumulative
+                .wrapping_sub(start.price0_cumulative)
+        } else {
+            current
+                .price1_cumulative
+                .wrapping_sub(start.price1_cumulative)
+        };
+        let time_elapsed = current.timestamp - start.timestamp;
+
+        // Both amounts are the ask amount for the offer amount, scaled by the TWAP window and precision
+        let twap_amount = Uint256::from(price_cumulative_diff) * Uint256::from(offer_amount);
+        let execution_amount = Uint256::from(ask_amount)
+            * Uint256::from(time_elapsed)
+            * Uint256::from(Uint128::new(10u128.pow(TWAP_PRECISION.into())));
+
+        if !twap_amount.is_zero() {
+            let deviation = if execution_amount > twap_amount {
+                execution_amount - twap_amount
+            } else {
+                twap_amount - execution_amount
+            };
+            if deviation * Uint256::from(BPS_DENOMINATOR as u64)
+                > twap_amount * Uint256::from(price_guard.max_deviation_bps as u64)
+            {
+                return Err(ContractError::PriceDeviationTooHigh(
+                    price_guard.max_deviation_bps,
+                ));
+            }
+        }
+    }
+
+    let window_passed = match observations.last() {
+        Some(last) => current.timestamp >= last.timestamp + price_guard.twap_window,
+        None => true,
+    };
+    if window_passed {
+        observations.push(current);
+        if observations.len() > TWAP_OBSERVATIONS_SIZE {
+            observations.remove(0);
+        }
+        TWAP_OBSERVATIONS.save(storage, &observations)?;
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Calculates the amount of fees the Maker contract gets according to specified pair parameters.
 /// Returns a [`None`] if the Maker fee is zero, otherwise returns a [`Asset`] struct with the specified attributes.
@@ -824,6 +1184,10 @@ pub fn calculate_maker_fee(
 /// pool using a [`CumulativePricesResponse`] object.
 ///
 /// * **QueryMsg::Config {}** Returns the configuration for the pair contract using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::SwapHooks {}** Returns the registered swap hooks in a vector that contains objects of type [`SwapHook`].
+///
+/// * **QueryMsg::Paused {}** Returns whether swaps and liquidity provision are paused.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -836,6 +1200,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::SwapHooks {} => {
+            to_binary(&SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Paused {} => to_binary(&PAUSED.may_load(deps.storage)?.unwrap_or_default()),
     }
 }
 
@@ -875,7 +1243,7 @@ pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
 pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<Vec<Asset>> {
     let config: Config = CONFIG.load(deps.storage)?;
     let (pools, total_share) = pool_info(deps, config)?;
-    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+    let refund_assets = get_share_in_assets(&pools, amount, total_share)?;
 
     Ok(refund_assets)
 }
@@ -1034,84 +1402,6 @@ pub fn amount_of(coins: &[Coin], denom: String) -> Uint128 {
     }
 }
 
-/// ## Description
-/// Returns the result of a swap.
-/// ## Params
-/// * **offer_pool** is an object of type [`Uint128`]. This is the total amount of offer assets in the pool.
-///
-/// * **ask_pool** is an object of type [`Uint128`]. This is the total amount of ask assets in the pool.
-///
-/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of offer assets to swap.
-///
-/// * **commission_rate** is an object of type [`Decimal`]. This is the total amount of fees charged for the swap.
-pub fn compute_swap(
-    offer_pool: Uint128,
-    ask_pool: Uint128,
-    offer_amount: Uint128,
-    commission_rate: Decimal,
-) -> StdResult<(Uint128, Uint128, Uint128)> {
-    let offer_pool: Uint256 = offer_pool.into();
-    let ask_pool: Uint256 = ask_pool.into();
-    let offer_amount: Uint256 = offer_amount.into();
-    let commission_rate: Decimal256 = commission_rate.into();
-
-    // offer => ask
-    // ask_amount = (ask_pool - cp / (offer_pool + offer_amount))
-    let cp: Uint256 = offer_pool * ask_pool;
-    let return_amount: Uint256 = (Decimal256::from_uint256(ask_pool)
-        - Decimal256::from_ratio(cp, offer_pool + offer_amount))
-        * Uint256::one();
-
-    // Calculate spread & commission
-    let spread_amount: Uint256 =
-        (offer_amount * Decimal256::from_ratio(ask_pool, offer_pool)) - return_amount;
-    let commission_amount: Uint256 = return_amount * commission_rate;
-
-    // The commision (minus the part that goes to the Maker contract) will be absorbed by the pool
-    let return_amount: Uint256 = return_amount - commission_amount;
-    Ok((
-        return_amount.into(),
-        spread_amount.into(),
-        commission_amount.into(),
-    ))
-}
-
-/// ## Description
-/// Returns an amount of offer assets for a specified amount of ask assets.
-/// ## Params
-/// * **offer_pool** is an object of type [`Uint128`]. This is the total amount of offer assets in the pool.
-///
-/// * **ask_pool** is an object of type [`Uint128`]. This is the total amount of ask assets in the pool.
-///
-/// * **ask_amount** is an object of type [`Uint128`]. This is the amount of ask assets to swap to.
-///
-/// * **commission_rate** is an object of type [`Decimal`]. This is the total amount of fees charged for the swap.
-fn compute_offer_amount(
-    offer_pool: Uint128,
-    ask_pool: Uint128,
-    ask_amount: Uint128,
-    commission_rate: Decimal,
-) -> StdResult<(Uint128, Uint128, Uint128)> {
-    // ask => offer
-    // offer_amount = cp / (ask_pool - ask_amount / (1 - commission_rate)) - offer_pool
-    let cp = Uint256::from(offer_pool) * Uint256::from(ask_pool);
-    let one_minus_commission = Decimal256::one() - Decimal256::from(commission_rate);
-    let inv_one_minus_commission: Decimal = (Decimal256::one() / one_minus_commission).into();
-
-    let offer_amount: Uint128 = Uint128::from(cp.multiply_ratio(
-        Uint256::one(),
-        Uint256::from(ask_pool.checked_sub(ask_amount * inv_one_minus_commission)?),
-    ))
-    .checked_sub(offer_pool)?;
-
-    let before_commission_deduction = ask_amount * inv_one_minus_commission;
-    let spread_amount = (offer_amount * Decimal::from_ratio(ask_pool, offer_pool))
-        .checked_sub(before_commission_deduction)
-        .unwrap_or_else(|_| Uint128::zero());
-    let commission_amount = before_commission_deduction * commission_rate;
-    Ok((offer_amount, spread_amount, commission_amount))
-}
-
 /// ## Description
 /// Returns a [`ContractError`] on failure.
 /// If `belief_price` and `max_spread` are both specified, we compute a new spread,
@@ -1161,6 +1451,40 @@ pub fn assert_max_spread(
     Ok(())
 }
 
+/// ## Description
+/// Verifies that a swap doesn't decrease the constant product of the pool.
+/// Returns a [`ContractError`] on failure, otherwise returns [`Ok`].
+///
+/// Since [`compute_swap`] rounds the return amount up by at most one unit, the ask pool is allowed
+/// to be one unit short of keeping the product constant.
+/// ## Params
+/// * **offer_pool** is an object of type [`Uint128`]. This is the amount of offer assets in the pool before the swap.
+///
+/// * **ask_pool** is an object of type [`Uint128`]. This is the amount of ask assets in the pool before the swap.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of assets added to the pool.
+///
+/// * **ask_amount** is an object of type [`Uint128`]. This is the amount of assets leaving the pool,
+/// including the fees that are not absorbed by the pool.
+pub fn assert_constant_product(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    ask_amount: Uint128,
+) -> Result<(), ContractError> {
+    let new_offer_pool = offer_pool.checked_add(offer_amount)?;
+    let new_ask_pool = ask_pool
+        .checked_sub(ask_amount)
+        .map_err(|_| ContractError::InvariantViolated {})?
+        .checked_add(Uint128::new(1))?;
+
+    if new_offer_pool.full_mul(new_ask_pool) < offer_pool.full_mul(ask_pool) {
+        return Err(ContractError::InvariantViolated {});
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// This is an internal function that enforces slippage tolerance for swaps.
 /// Returns a [`ContractError`] on failure, otherwise returns [`Ok`].
@@ -1201,16 +1525,25 @@ fn assert_slippage_tolerance(
 }
 
 /// ## Description
-/// Used for the contract migration. Returns a default object of type [`Response`].
+/// Used for the contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
 /// * **_msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
 
 /// ## Description
@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 /// ## Description
@@ -17,6 +17,9 @@ pub enum ContractError {
     #[error("Event of zero transfer")]
     InvalidZeroAmount {},
 
+    #[error("Initial liquidity must be more than {min_lp_to_receive} LP tokens")]
+    MinimumLiquidityAmountError { min_lp_to_receive: Uint128 },
+
     #[error("Operation exceeds max spread limit")]
     MaxSpreadAssertion {},
 
@@ -37,6 +40,24 @@ pub enum ContractError {
 
     #[error("Generator address is not set in factory. Cannot auto-stake")]
     AutoStakeError {},
+
+    #[error("Referral registry is not set in the factory")]
+    ReferralRegistryNotSet {},
+
+    #[error("A pair can't have more than {0} swap hooks")]
+    TooManySwapHooks(usize),
+
+    #[error("Swap hook {0} isn't registered")]
+    SwapHookNotFound(String),
+
+    #[error("Swaps and liquidity provision are paused")]
+    Paused {},
+
+    #[error("Swap would decrease the pool invariant")]
+    InvariantViolated {},
+
+    #[error("Swap price deviates from the TWAP by more than {0} bps")]
+    PriceDeviationTooHigh(u16),
 }
 
 impl From<OverflowError> for ContractError {
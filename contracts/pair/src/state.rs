@@ -1,4 +1,5 @@
 use astroport::asset::PairInfo;
+use astroport::pair::SwapHook;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
@@ -20,6 +21,30 @@ pub struct Config {
     pub price1_cumulative_last: Uint128,
 }
 
+/// ## Description
+/// This structure stores the cumulative prices of the pair at a point in time.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceObservation {
+    /// The timestamp when the cumulative prices were recorded
+    pub timestamp: u64,
+    /// The cumulative price for asset 0
+    pub price0_cumulative: Uint128,
+    /// The cumulative price for asset 1
+    pub price1_cumulative: Uint128,
+}
+
 /// ## Description
 /// Stores the config struct at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the swap hooks registered in the pair
+pub const SWAP_HOOKS: Item<Vec<SwapHook>> = Item::new("swap_hooks");
+
+/// ## Description
+/// Stores whether swaps and liquidity provision are paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+/// ## Description
+/// Stores the cumulative price observations used by the price guard, from oldest to latest
+pub const TWAP_OBSERVATIONS: Item<Vec<PriceObservation>> = Item::new("twap_observations");
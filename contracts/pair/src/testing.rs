@@ -1,26 +1,30 @@
 use crate::contract::reply;
 use crate::contract::{
-    accumulate_prices, assert_max_spread, compute_swap, execute, instantiate, query_pair_info,
-    query_pool, query_reverse_simulation, query_share, query_simulation,
+    accumulate_prices, assert_constant_product, assert_max_spread, assert_price_guard, execute,
+    instantiate, migrate, query, query_pair_info, query_pool, query_reverse_simulation,
+    query_share, query_simulation,
 };
 use crate::error::ContractError;
 use crate::mock_querier::mock_dependencies;
 use crate::response::MsgInstantiateContractResponse;
-use crate::state::Config;
+use crate::state::{Config, PriceObservation, TWAP_OBSERVATIONS};
 use astroport::asset::{Asset, AssetInfo, PairInfo};
-use astroport::factory::PairType;
+use astroport::factory::{PairType, PriceGuard};
 
 use astroport::pair::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, ReverseSimulationResponse,
-    SimulationResponse, TWAP_PRECISION,
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse, QueryMsg,
+    ReverseSimulationResponse, SimulationResponse, SwapHookMsg, MINIMUM_LIQUIDITY_AMOUNT,
+    SWAP_HOOK_GAS_LIMIT, TWAP_PRECISION,
 };
+use astroport::simulation::compute_swap;
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::testing::{mock_env, mock_info, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, to_binary, Addr, BankMsg, BlockInfo, Coin, ContractResult, CosmosMsg, Decimal, DepsMut,
-    Env, Reply, ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Timestamp, Uint128,
-    WasmMsg,
+    attr, from_binary, to_binary, Addr, BankMsg, BlockInfo, Coin, ContractResult, CosmosMsg,
+    Decimal, DepsMut, Env, Reply, ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse,
+    Timestamp, Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use proptest::prelude::*;
 use protobuf::Message;
@@ -88,6 +92,7 @@ fn proper_initialization() {
                         minter: String::from(MOCK_CONTRACT_ADDR),
                         cap: None,
                     }),
+                    marketing: None,
                 })
                 .unwrap(),
                 funds: vec![],
@@ -160,6 +165,41 @@ fn provide_liquidity() {
     // Store liquidity token
     store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
 
+    // The first deposit must mint more LP tokens than the locked minimum liquidity
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::from(1_000u128),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::from(1_000u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1_000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinimumLiquidityAmountError {
+            min_lp_to_receive: MINIMUM_LIQUIDITY_AMOUNT,
+        }
+    );
+
     // Successfully provide liquidity for the existing pool
     let msg = ExecuteMsg::ProvideLiquidity {
         assets: [
@@ -191,7 +231,8 @@ fn provide_liquidity() {
     );
     let res = execute(deps.as_mut(), env.clone().clone(), info, msg).unwrap();
     let transfer_from_msg = res.messages.get(0).expect("no message");
-    let mint_msg = res.messages.get(1).expect("no message");
+    let mint_min_liquidity_msg = res.messages.get(1).expect("no message");
+    let mint_receiver_msg = res.messages.get(2).expect("no message");
     assert_eq!(
         transfer_from_msg,
         &SubMsg {
@@ -212,13 +253,31 @@ fn provide_liquidity() {
         }
     );
     assert_eq!(
-        mint_msg,
+        mint_min_liquidity_msg,
+        &SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: String::from(MOCK_CONTRACT_ADDR),
+                    amount: MINIMUM_LIQUIDITY_AMOUNT,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            id: 0,
+            gas_limit: None,
+            reply_on: ReplyOn::Never,
+        }
+    );
+    assert_eq!(
+        mint_receiver_msg,
         &SubMsg {
             msg: WasmMsg::Execute {
                 contract_addr: String::from("liquidity0000"),
                 msg: to_binary(&Cw20ExecuteMsg::Mint {
                     recipient: String::from("addr0000"),
-                    amount: Uint128::from(100_000000000000000000u128),
+                    amount: Uint128::from(100_000000000000000000u128) - MINIMUM_LIQUIDITY_AMOUNT,
                 })
                 .unwrap(),
                 funds: vec![],
@@ -728,6 +787,7 @@ fn try_native_to_token() {
         belief_price: None,
         max_spread: Some(Decimal::percent(50)),
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info(
@@ -824,6 +884,7 @@ fn try_native_to_token() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -909,6 +970,7 @@ fn try_token_to_native() {
         belief_price: None,
         max_spread: None,
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -923,6 +985,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: Some(Decimal::percent(50)),
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -1024,6 +1087,7 @@ fn try_token_to_native() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -1054,6 +1118,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -1063,6 +1128,289 @@ fn try_token_to_native() {
     assert_eq!(res, ContractError::Unauthorized {});
 }
 
+#[test]
+fn swap_hooks() {
+    let offer_amount = Uint128::new(1500000000u128);
+
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(30000000000u128) + offer_amount,
+    }]);
+
+    deps.querier.with_tax(
+        Decimal::zero(),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(30000000000u128),
+            )],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(20000000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: None,
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // Store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let threshold = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(1000000000u128),
+    };
+
+    // Only contracts whitelisted in the factory can register hooks
+    let msg = ExecuteMsg::RegisterSwapHook {
+        thresholds: vec![threshold.clone()],
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("hook0000", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::RegisterSwapHook {
+            thresholds: vec![Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                amount: Uint128::new(1000000000u128),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let info = mock_info("hook0000", &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Swaps below the threshold don't trigger the hook
+    let small_offer_amount = Uint128::new(1000u128);
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: small_offer_amount,
+        },
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+        referral_code: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: small_offer_amount,
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res
+        .messages
+        .iter()
+        .all(|sub_msg| sub_msg.reply_on == ReplyOn::Never));
+
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: offer_amount,
+        },
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+        referral_code: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: offer_amount,
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let hook_msg = res.messages.last().unwrap();
+    assert_eq!(hook_msg.id, 2);
+    assert_eq!(hook_msg.gas_limit, Some(SWAP_HOOK_GAS_LIMIT));
+    assert_eq!(hook_msg.reply_on, ReplyOn::Error);
+    match &hook_msg.msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, "hook0000");
+            let SwapHookMsg::AfterSwap {
+                sender,
+                offer_asset,
+                ..
+            } = from_binary(msg).unwrap();
+            assert_eq!(sender, Addr::unchecked("addr0000"));
+            assert_eq!(offer_asset.amount, offer_amount);
+        }
+        _ => panic!("Unexpected message"),
+    }
+
+    // A failing hook doesn't revert the swap
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 2,
+            result: ContractResult::Err(String::from("hook error")),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "swap_hook_failed")]);
+
+    // Hooks that are still whitelisted can only be removed by themselves
+    let msg = ExecuteMsg::DeregisterSwapHook {
+        contract_addr: String::from("hook0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("hook0000", &[]);
+    execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+
+    let info = mock_info("hook0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SwapHookNotFound(String::from("hook0000"))
+    );
+}
+
+#[test]
+fn pause() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: None,
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // Store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("emergency_admin", &[]);
+    execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Pause {}).unwrap();
+    let paused: bool =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Paused {}).unwrap()).unwrap();
+    assert!(paused);
+
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(1000u128),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        referral_code: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1000u128),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(1000u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // Only the factory owner can resume the pair
+    let info = mock_info("emergency_admin", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Unpause {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("owner", &[]);
+    execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Unpause {}).unwrap();
+    let paused: bool =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Paused {}).unwrap()).unwrap();
+    assert!(!paused);
+}
+
 #[test]
 fn test_max_spread() {
     assert_max_spread(
@@ -1102,6 +1450,35 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[test]
+fn test_constant_product() {
+    let offer_pool = Uint128::from(1_000_000u128);
+    let ask_pool = Uint128::from(1_000_000u128);
+    let offer_amount = Uint128::from(1_000u128);
+
+    let (return_amount, _, commission_amount) =
+        compute_swap(offer_pool, ask_pool, offer_amount, Decimal::permille(3)).unwrap();
+    assert_constant_product(offer_pool, ask_pool, offer_amount, return_amount).unwrap();
+    // The product doesn't decrease even if the whole commission leaves the pool
+    assert_constant_product(
+        offer_pool,
+        ask_pool,
+        offer_amount,
+        return_amount + commission_amount,
+    )
+    .unwrap();
+
+    // One unit of rounding is tolerated
+    assert_constant_product(offer_pool, ask_pool, offer_amount, Uint128::from(1_000u128)).unwrap();
+    let err = assert_constant_product(offer_pool, ask_pool, offer_amount, Uint128::from(1_001u128))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvariantViolated {});
+
+    let err = assert_constant_product(offer_pool, ask_pool, offer_amount, ask_pool + ask_pool)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvariantViolated {});
+}
+
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -1363,6 +1740,112 @@ fn test_accumulate_prices() {
     }
 }
 
+#[test]
+fn test_price_guard() {
+    let mut storage = MockStorage::new();
+    let price_precision = 10u128.pow(TWAP_PRECISION.into());
+    let price_guard = PriceGuard {
+        max_deviation_bps: 500,
+        twap_window: 600,
+    };
+
+    // One unit of asset 0 is worth two units of asset 1
+    let config_at = |block_time_last: u64| Config {
+        pair_info: PairInfo {
+            asset_infos: [
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+            ],
+            contract_addr: Addr::unchecked("pair"),
+            liquidity_token: Addr::unchecked("lp_token"),
+            pair_type: PairType::Xyk {},
+        },
+        factory_addr: Addr::unchecked("factory"),
+        block_time_last,
+        price0_cumulative_last: Uint128::from(block_time_last as u128 * 2 * price_precision),
+        price1_cumulative_last: Uint128::from(block_time_last as u128 * price_precision / 2),
+    };
+
+    // Without price history the check is skipped and the first observation is recorded
+    assert_price_guard(
+        &mut storage,
+        &config_at(1000),
+        &price_guard,
+        true,
+        Uint128::new(1000),
+        Uint128::new(5000),
+    )
+    .unwrap();
+    // Prices from the same block are not part of the TWAP yet
+    assert_price_guard(
+        &mut storage,
+        &config_at(1000),
+        &price_guard,
+        true,
+        Uint128::new(1000),
+        Uint128::new(5000),
+    )
+    .unwrap();
+
+    let config = config_at(1100);
+    for (offer_asset0, ask_amount, allowed) in [
+        (true, 2100, true),
+        (true, 2101, false),
+        (true, 1900, true),
+        (true, 1899, false),
+        (false, 525, true),
+        (false, 526, false),
+    ] {
+        let res = assert_price_guard(
+            &mut storage,
+            &config,
+            &price_guard,
+            offer_asset0,
+            Uint128::new(1000),
+            Uint128::new(ask_amount),
+        );
+        if allowed {
+            res.unwrap();
+        } else {
+            assert_eq!(res.unwrap_err(), ContractError::PriceDeviationTooHigh(500));
+        }
+    }
+
+    // A new observation is only recorded once the TWAP window has passed
+    let observation_at = |timestamp: u64| {
+        let config = config_at(timestamp);
+        PriceObservation {
+            timestamp,
+            price0_cumulative: config.price0_cumulative_last,
+            price1_cumulative: config.price1_cumulative_last,
+        }
+    };
+    assert_eq!(
+        TWAP_OBSERVATIONS.load(&storage).unwrap(),
+        vec![observation_at(1000)]
+    );
+
+    for block_time in [1600, 2200] {
+        assert_price_guard(
+            &mut storage,
+            &config_at(block_time),
+            &price_guard,
+            true,
+            Uint128::new(1000),
+            Uint128::new(2000),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        TWAP_OBSERVATIONS.load(&storage).unwrap(),
+        vec![observation_at(1600), observation_at(2200)]
+    );
+}
+
 fn mock_env_with_block_time(time: u64) -> Env {
     let mut env = mock_env();
     env.block = BlockInfo {
@@ -1410,3 +1893,30 @@ proptest! {
         ).unwrap();
     }
 }
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-pair", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-pair"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-pair"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
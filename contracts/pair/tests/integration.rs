@@ -119,11 +119,11 @@ fn test_provide_and_withdraw_liquidity() {
             vec![
                 Coin {
                     denom: "uusd".to_string(),
-                    amount: Uint128::new(233u128),
+                    amount: Uint128::new(2_233u128),
                 },
                 Coin {
                     denom: "uluna".to_string(),
-                    amount: Uint128::new(200u128),
+                    amount: Uint128::new(2_200u128),
                 },
             ],
         )
@@ -157,18 +157,28 @@ fn test_provide_and_withdraw_liquidity() {
             vec![
                 Coin {
                     denom: "uusd".to_string(),
-                    amount: Uint128::new(100u128),
+                    amount: Uint128::new(2_000u128),
                 },
                 Coin {
                     denom: "uluna".to_string(),
-                    amount: Uint128::new(100u128),
+                    amount: Uint128::new(2_000u128),
                 },
             ],
         )
         .unwrap();
 
+    // The first provision must mint more LP tokens than the locked minimum liquidity
+    let (msg, coins) = provide_liquidity_msg(Uint128::new(1_000), Uint128::new(1_000), None, None);
+    let err = router
+        .execute_contract(alice_address.clone(), pair_instance.clone(), &msg, &coins)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Initial liquidity must be more than 1000 LP tokens"
+    );
+
     // Provide liquidity
-    let (msg, coins) = provide_liquidity_msg(Uint128::new(100), Uint128::new(100), None, None);
+    let (msg, coins) = provide_liquidity_msg(Uint128::new(2_000), Uint128::new(2_000), None, None);
     let res = router
         .execute_contract(alice_address.clone(), pair_instance.clone(), &msg, &coins)
         .unwrap();
@@ -178,17 +188,31 @@ fn test_provide_and_withdraw_liquidity() {
         attr("action", "provide_liquidity")
     );
     assert_eq!(res.events[1].attributes[3], attr("receiver", "alice"),);
+    assert_eq!(res.events[2].ty, "wasm-astroport/pair/provide_liquidity");
     assert_eq!(
         res.events[1].attributes[4],
-        attr("assets", "100uusd, 100uluna")
+        attr("assets", "2000uusd, 2000uluna")
     );
     assert_eq!(
         res.events[1].attributes[5],
-        attr("share", 100u128.to_string())
+        attr("share", 1000u128.to_string())
+    );
+    // The minimum liquidity is minted to the pair itself and locked forever
+    assert_eq!(res.events[4].attributes[1], attr("action", "mint"));
+    assert_eq!(
+        res.events[4].attributes[2],
+        attr("to", pair_instance.as_str())
+    );
+    assert_eq!(
+        res.events[4].attributes[3],
+        attr("amount", 1000.to_string())
+    );
+    assert_eq!(res.events[6].attributes[1], attr("action", "mint"));
+    assert_eq!(res.events[6].attributes[2], attr("to", "alice"));
+    assert_eq!(
+        res.events[6].attributes[3],
+        attr("amount", 1000.to_string())
     );
-    assert_eq!(res.events[3].attributes[1], attr("action", "mint"));
-    assert_eq!(res.events[3].attributes[2], attr("to", "alice"));
-    assert_eq!(res.events[3].attributes[3], attr("amount", 100.to_string()));
 
     // Provide liquidity for receiver
     let (msg, coins) = provide_liquidity_msg(
@@ -214,9 +238,9 @@ fn test_provide_and_withdraw_liquidity() {
         res.events[1].attributes[5],
         attr("share", 50u128.to_string())
     );
-    assert_eq!(res.events[3].attributes[1], attr("action", "mint"));
-    assert_eq!(res.events[3].attributes[2], attr("to", "bob"));
-    assert_eq!(res.events[3].attributes[3], attr("amount", 50.to_string()));
+    assert_eq!(res.events[4].attributes[1], attr("action", "mint"));
+    assert_eq!(res.events[4].attributes[2], attr("to", "bob"));
+    assert_eq!(res.events[4].attributes[3], attr("amount", 50.to_string()));
 }
 
 fn provide_liquidity_msg(
@@ -286,6 +310,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_x_instance = app
@@ -313,6 +338,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_y_instance = app
@@ -439,6 +465,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             belief_price: None,
             max_spread: None,
             to: Some(user.to_string()),
+            referral_code: None,
         })
         .unwrap(),
         amount: x_offer,
@@ -0,0 +1,30 @@
+//! Fuzzes the stableswap D and y solvers. They must either return an error or a result that
+//! never pays out more than the pool holds; they must never panic.
+//!
+//! Run with `cargo fuzz run stableswap_math` from the `contracts/pair_stable` directory.
+#![no_main]
+
+use astroport_pair_stable::math::{
+    calc_ask_amount, calc_offer_amount, compute_d, AMP_PRECISION, MAX_AMP, N_COINS,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, u128, u128, u128)| {
+    let (amp, offer_pool, ask_pool, amount) = input;
+    let amp = (amp % MAX_AMP + 1) * AMP_PRECISION;
+
+    if let Ok(d) = compute_d(amp * N_COINS as u64, offer_pool, ask_pool) {
+        assert!(d <= offer_pool.saturating_add(ask_pool));
+    }
+
+    if let Ok(ask_amount) = calc_ask_amount(offer_pool, ask_pool, amount, amp) {
+        assert!(ask_amount <= ask_pool);
+    }
+
+    if let Ok(offer_amount) = calc_offer_amount(offer_pool, ask_pool, amount, amp) {
+        // Asking for a non-zero amount is never free
+        if amount > 0 {
+            assert!(offer_amount > 0 || amount >= ask_pool);
+        }
+    }
+});
@@ -62,6 +62,21 @@ pub enum ContractError {
 
     #[error("It is not possible to provide liquidity with one token for an empty pool")]
     InvalidProvideLPsWithSingleToken {},
+
+    #[error("Referral registry is not set in the factory")]
+    ReferralRegistryNotSet {},
+
+    #[error("A pair can't have more than {0} swap hooks")]
+    TooManySwapHooks(usize),
+
+    #[error("Swap hook {0} isn't registered")]
+    SwapHookNotFound(String),
+
+    #[error("Swaps and liquidity provision are paused")]
+    Paused {},
+
+    #[error("Swap would decrease the pool invariant")]
+    InvariantViolated {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,4 +1,5 @@
 use astroport::asset::PairInfo;
+use astroport::pair::SwapHook;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
@@ -29,3 +30,11 @@ pub struct Config {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the swap hooks registered in the pair
+pub const SWAP_HOOKS: Item<Vec<SwapHook>> = Item::new("swap_hooks");
+
+/// ## Description
+/// Stores whether swaps and liquidity provision are paused
+pub const PAUSED: Item<bool> = Item::new("paused");
@@ -1,9 +1,9 @@
 use crate::contract::{
-    accumulate_prices, assert_max_spread, execute, instantiate, query_pair_info, query_pool,
-    query_share, query_simulation, reply,
+    accumulate_prices, assert_invariant, assert_max_spread, execute, instantiate, migrate,
+    query_pair_info, query_pool, query_share, query_simulation, reply,
 };
 use crate::error::ContractError;
-use crate::math::{calc_ask_amount, calc_offer_amount, AMP_PRECISION};
+use crate::math::{calc_ask_amount, calc_offer_amount, compute_d, AMP_PRECISION, MAX_AMP, N_COINS};
 use crate::mock_querier::mock_dependencies;
 
 use crate::response::MsgInstantiateContractResponse;
@@ -11,8 +11,8 @@ use crate::state::Config;
 use astroport::asset::{Asset, AssetInfo, PairInfo};
 
 use astroport::pair::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, SimulationResponse, StablePoolParams,
-    TWAP_PRECISION,
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse, SimulationResponse,
+    StablePoolParams, TWAP_PRECISION,
 };
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
@@ -21,6 +21,7 @@ use cosmwasm_std::{
     Env, Reply, ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Timestamp, Uint128,
     WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
 
@@ -87,6 +88,7 @@ fn proper_initialization() {
                         minter: String::from(MOCK_CONTRACT_ADDR),
                         cap: None,
                     }),
+                    marketing: None,
                 })
                 .unwrap(),
                 funds: vec![],
@@ -660,6 +662,7 @@ fn try_native_to_token() {
         belief_price: None,
         max_spread: Some(Decimal::percent(50)),
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info(
@@ -729,6 +732,7 @@ fn try_native_to_token() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -814,6 +818,7 @@ fn try_token_to_native() {
         belief_price: None,
         max_spread: None,
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -828,6 +833,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -903,6 +909,7 @@ fn try_token_to_native() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -933,6 +940,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -981,6 +989,66 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[test]
+fn test_assert_invariant() {
+    let pool = Uint128::from(1_000_000_000_000u128);
+    let offer_amount = Uint128::from(1_000_000_000u128);
+
+    for amp in [1, 100, 10_000] {
+        let amp = amp * AMP_PRECISION;
+        let ask_amount = Uint128::new(
+            calc_ask_amount(pool.u128(), pool.u128(), offer_amount.u128(), amp).unwrap(),
+        );
+        assert_invariant(pool, 6, pool, 6, offer_amount, ask_amount, amp).unwrap();
+
+        let err = assert_invariant(
+            pool,
+            6,
+            pool,
+            6,
+            offer_amount,
+            ask_amount + pool / Uint128::from(100u128),
+            amp,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvariantViolated {});
+    }
+
+    // Balances are compared using the greater precision
+    let scale = Uint128::from(1_000_000_000_000u128);
+    let ask_amount = Uint128::new(
+        calc_ask_amount(
+            (pool * scale).u128(),
+            (pool * scale).u128(),
+            (offer_amount * scale).u128(),
+            100 * AMP_PRECISION,
+        )
+        .unwrap(),
+    );
+    assert_invariant(
+        pool,
+        6,
+        pool * scale,
+        18,
+        offer_amount,
+        ask_amount,
+        100 * AMP_PRECISION,
+    )
+    .unwrap();
+
+    let err = assert_invariant(
+        pool,
+        6,
+        pool,
+        6,
+        offer_amount,
+        pool + Uint128::from(1u128),
+        AMP_PRECISION,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvariantViolated {});
+}
+
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -1248,6 +1316,49 @@ fn test_accumulate_prices() {
     }
 }
 
+#[test]
+fn stableswap_math_converges() {
+    // Balanced, imbalanced and mixed decimals (a 6 decimal token scaled to 18 decimals) reserves
+    let pools: [(u128, u128); 6] = [
+        (1_000_000_000_000_000_000, 1_000_000_000_000_000_000),
+        (1_000_000_000_000_000_000_000_000, 1_000_000_000),
+        (1_000, 1_000_000_000_000_000_000_000_000),
+        (
+            5_000_000 * 1_000_000_000_000,
+            5_000_000_000_000_000_000_000_000,
+        ),
+        (1_000_000_000_000_000_000_000, 1),
+        (u64::MAX as u128, u64::MAX as u128 / 3),
+    ];
+
+    for amp in [1, 10, 100, 1_000, 10_000, MAX_AMP] {
+        let amp = amp * AMP_PRECISION;
+        for (offer_pool, ask_pool) in pools {
+            let d = compute_d(amp * N_COINS as u64, offer_pool, ask_pool).unwrap();
+            assert!(d <= offer_pool + ask_pool);
+
+            for offer_amount in [1, 1_000_000, offer_pool / 100 + 1, offer_pool] {
+                let ask_amount = calc_ask_amount(offer_pool, ask_pool, offer_amount, amp).unwrap();
+                assert!(ask_amount <= ask_pool);
+
+                if ask_amount > 0 && ask_amount < ask_pool {
+                    // The reverse simulation is within 0.1% of the offered amount
+                    let reverse_amount =
+                        calc_offer_amount(offer_pool, ask_pool, ask_amount, amp).unwrap();
+                    let tolerance = offer_amount / 1000 + 1;
+                    assert!(reverse_amount <= offer_amount + tolerance);
+                    assert!(reverse_amount + tolerance >= offer_amount);
+                }
+            }
+        }
+    }
+
+    // Out of range values return an error instead of panicking
+    assert!(compute_d(N_COINS as u64 * AMP_PRECISION, u128::MAX, u128::MAX).is_err());
+    assert!(calc_ask_amount(u128::MAX / 2, u128::MAX / 2, 1, AMP_PRECISION).is_err());
+    assert!(calc_offer_amount(1_000, 1_000, 1_001, AMP_PRECISION).is_err());
+}
+
 fn mock_env_with_block_time(time: u64) -> Env {
     let mut env = mock_env();
     env.block = BlockInfo {
@@ -1324,3 +1435,30 @@ proptest! {
         );
     }
 }
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-pair-stable", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-pair-stable"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-pair-stable"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
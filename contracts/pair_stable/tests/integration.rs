@@ -217,10 +217,10 @@ fn test_provide_and_withdraw_liquidity() {
         res.events[1].attributes[5],
         attr("share", 100u128.to_string())
     );
-    assert_eq!(res.events[3].attributes[1], attr("action", "mint"));
-    assert_eq!(res.events[3].attributes[2], attr("to", "alice"));
+    assert_eq!(res.events[4].attributes[1], attr("action", "mint"));
+    assert_eq!(res.events[4].attributes[2], attr("to", "alice"));
     assert_eq!(
-        res.events[3].attributes[3],
+        res.events[4].attributes[3],
         attr("amount", 100u128.to_string())
     );
 
@@ -247,9 +247,9 @@ fn test_provide_and_withdraw_liquidity() {
         res.events[1].attributes[5],
         attr("share", 50u128.to_string())
     );
-    assert_eq!(res.events[3].attributes[1], attr("action", "mint"));
-    assert_eq!(res.events[3].attributes[2], attr("to", "bob"));
-    assert_eq!(res.events[3].attributes[3], attr("amount", 50.to_string()));
+    assert_eq!(res.events[4].attributes[1], attr("action", "mint"));
+    assert_eq!(res.events[4].attributes[2], attr("to", "bob"));
+    assert_eq!(res.events[4].attributes[3], attr("amount", 50.to_string()));
 }
 
 fn provide_liquidity_msg(
@@ -318,6 +318,7 @@ fn provide_lp_for_single_token() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_x_instance = app
@@ -345,6 +346,7 @@ fn provide_lp_for_single_token() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_y_instance = app
@@ -522,6 +524,7 @@ fn provide_lp_for_single_token() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -537,6 +540,7 @@ fn provide_lp_for_single_token() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -576,6 +580,7 @@ fn provide_lp_for_single_token() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -591,6 +596,7 @@ fn provide_lp_for_single_token() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -629,6 +635,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_x_instance = app
@@ -656,6 +663,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             minter: String::from(OWNER),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_y_instance = app
@@ -782,6 +790,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             belief_price: None,
             max_spread: None,
             to: Some(user.to_string()),
+            referral_code: None,
         })
         .unwrap(),
         amount: x_offer,
@@ -1,10 +1,11 @@
 use crate::error::ContractError;
 use crate::math::{
-    calc_ask_amount, calc_offer_amount, compute_d, AMP_PRECISION, MAX_AMP, MAX_AMP_CHANGE,
-    MIN_AMP_CHANGING_TIME, N_COINS,
+    calc_ask_amount, calc_offer_amount, check_invariant, compute_d, AMP_PRECISION, MAX_AMP,
+    MAX_AMP_CHANGE, MIN_AMP_CHANGING_TIME, N_COINS,
 };
 use crate::state::{
     Config, BLUNA_REWARD_GLOBAL_INDEX, BLUNA_REWARD_HOLDER, BLUNA_REWARD_USER_INDEXES, CONFIG,
+    PAUSED, SWAP_HOOKS,
 };
 
 use cosmwasm_bignumber::Decimal256;
@@ -16,11 +17,12 @@ use cosmwasm_std::{
 
 use crate::response::MsgInstantiateContractResponse;
 use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
+use astroport::attr_builder::AttrBuilder;
+use astroport::common::assert_migration_allowed;
 use astroport::factory::PairType;
+use astroport::fee_discount::discounted_fee_rate;
 
-use astroport::generator::{
-    Cw20HookMsg as GeneratorHookMsg, PoolInfoResponse, QueryMsg as GeneratorQueryMsg,
-};
+use astroport::generator::{Cw20HookMsg as GeneratorHookMsg, GeneratorQuerier};
 use astroport::pair::{
     ConfigResponse, CumulativePricesResponse, Cw20HookMsg, InstantiateMsg, PoolResponse,
     ReverseSimulationResponse, SimulationResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
@@ -32,10 +34,13 @@ use astroport::pair_stable_bluna::{
 use astroport::whitelist::InstantiateMsg as WhitelistInstantiateMsg;
 
 use anchor_basset::reward::{AccruedRewardsResponse, QueryMsg as BAssetRewardQueryMsg};
+use astroport::pair::{build_swap_hook_msgs, SwapHook, SwapHookMsg, MAX_SWAP_HOOKS};
 use astroport::querier::{
-    query_factory_config, query_fee_info, query_supply, query_token_precision,
+    query_factory_config, query_fee_info, query_supply, query_swap_hooks_whitelist,
+    query_token_precision,
 };
-use astroport::{token::InstantiateMsg as TokenInstantiateMsg, U256};
+use astroport::referral::build_referral_fee_msgs;
+use astroport::{token::InstantiateMsg as TokenInstantiateMsg, DecimalCheckedOps, U256};
 use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
@@ -48,9 +53,13 @@ use std::vec;
 const CONTRACT_NAME: &str = "astroport-pair-stable-bluna";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Contract name of the stable pair that is migrated to a bLUNA pair.
+const STABLE_PAIR_CONTRACT_NAME: &str = "astroport-pair-stable";
 /// `reply` call code IDs used for sub-messages.
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
 const INSTANTIATE_BLUNA_REWARD_HOLDER_REPLY_ID: u64 = 2;
+/// A `reply` call code ID used for swap hook callbacks.
+const SWAP_HOOK_REPLY_ID: u64 = 3;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
@@ -131,6 +140,7 @@ pub fn instantiate(
                     minter: env.contract.address.to_string(),
                     cap: None,
                 }),
+                marketing: None,
             })?,
             funds: vec![],
             admin: None,
@@ -155,6 +165,11 @@ pub fn instantiate(
 /// * **msg** is an object of type [`Reply`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id == SWAP_HOOK_REPLY_ID {
+        // A failing swap hook must not revert the swap
+        return Ok(Response::new().add_attribute("action", "swap_hook_failed"));
+    }
+
     let data = msg.result.unwrap().data.unwrap();
     let res: MsgInstantiateContractResponse =
         Message::parse_from_bytes(data.as_slice()).map_err(|_| {
@@ -220,6 +235,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             belief_price,
 ///             max_spread,
 ///             to,
+///             referral_code,
 ///         }** Performs a swap operation with the specified parameters.
 ///
 /// * **ExecuteMsg::ClaimReward {
@@ -240,6 +256,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             total_share,
 ///             user,
 ///         }** Handles and distributes bLUNA rewards.
+///
+/// * **ExecuteMsg::RegisterSwapHook { thresholds }** Registers the sender as a swap hook.
+///
+/// * **ExecuteMsg::DeregisterSwapHook { contract_addr }** Removes a swap hook.
+///
+/// * **ExecuteMsg::Pause {}** Pauses swaps and liquidity provision.
+///
+/// * **ExecuteMsg::Unpause {}** Resumes swaps and liquidity provision.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -269,6 +293,7 @@ pub fn execute(
             belief_price,
             max_spread,
             to,
+            referral_code,
         } => {
             offer_asset.info.check(deps.api)?;
             if !offer_asset.is_native_token() {
@@ -290,6 +315,7 @@ pub fn execute(
                 belief_price,
                 max_spread,
                 to_addr,
+                referral_code,
             )
         }
         ExecuteMsg::ClaimReward { receiver } => claim_reward(deps, env, info, receiver),
@@ -314,6 +340,12 @@ pub fn execute(
             total_share,
             receiver,
         ),
+        ExecuteMsg::RegisterSwapHook { thresholds } => register_swap_hook(deps, info, thresholds),
+        ExecuteMsg::DeregisterSwapHook { contract_addr } => {
+            deregister_swap_hook(deps, info, contract_addr)
+        }
+        ExecuteMsg::Pause {} => pause(deps, info),
+        ExecuteMsg::Unpause {} => unpause(deps, info),
     }
 }
 
@@ -341,6 +373,7 @@ pub fn receive_cw20(
             belief_price,
             max_spread,
             to,
+            referral_code,
         }) => {
             // Only an asset contract can execute this message
             let mut authorized: bool = false;
@@ -376,6 +409,7 @@ pub fn receive_cw20(
                 belief_price,
                 max_spread,
                 to_addr,
+                referral_code,
             )
         }
         Ok(Cw20HookMsg::WithdrawLiquidity {}) => withdraw_liquidity(
@@ -418,6 +452,10 @@ pub fn provide_liquidity(
     auto_stake: Option<bool>,
     receiver: Option<String>,
 ) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
     assets[0].info.check(deps.api)?;
     assets[1].info.check(deps.api)?;
 
@@ -515,13 +553,13 @@ pub fn provide_liquidity(
             adjust_precision(pools[1].amount, token_precision_1, greater_precision)?;
 
         let d_before_addition_liquidity =
-            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128()).unwrap();
+            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128())?;
 
         pool_amount_0 = pool_amount_0.checked_add(deposit_amount_0)?;
         pool_amount_1 = pool_amount_1.checked_add(deposit_amount_1)?;
 
         let d_after_addition_liquidity =
-            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128()).unwrap();
+            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128())?;
 
         // d after adding liquidity may be less than or equal to d before adding liquidity because of rounding
         if d_before_addition_liquidity >= d_after_addition_liquidity {
@@ -564,13 +602,17 @@ pub fn provide_liquidity(
         CONFIG.save(deps.storage, &config)?;
     }
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender.as_str()),
-        attr("receiver", receiver.as_str()),
-        attr("assets", format!("{}, {}", assets[0], assets[1])),
-        attr("share", share.to_string()),
-    ]))
+    let attributes = AttrBuilder::new("provide_liquidity")
+        .sender(info.sender.as_str())
+        .receiver(receiver.as_str())
+        .attr("assets", format!("{}, {}", assets[0], assets[1]))
+        .attr("share", share)
+        .assets("deposit", &assets);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -665,7 +707,7 @@ pub fn withdraw_liquidity(
     }
 
     let (pools, total_share) = pool_info(deps.as_ref(), config.clone())?;
-    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+    let refund_assets = get_share_in_assets(&pools, amount, total_share)?;
 
     // Accumulate prices for the assets in the pool
     if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
@@ -696,19 +738,19 @@ pub fn withdraw_liquidity(
         }),
     ];
 
-    let attributes = vec![
-        attr("action", "withdraw_liquidity"),
-        attr("sender", sender.as_str()),
-        attr("withdrawn_share", &amount.to_string()),
-        attr(
+    let attributes = AttrBuilder::new("withdraw_liquidity")
+        .sender(sender.as_str())
+        .attr("withdrawn_share", amount)
+        .attr(
             "refund_assets",
             format!("{}, {}", refund_assets[0], refund_assets[1]),
-        ),
-    ];
+        )
+        .assets("refund", &refund_assets);
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attributes(attributes))
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -719,26 +761,28 @@ pub fn withdraw_liquidity(
 /// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to calculate underlying amounts for.
 ///
 /// * **total_share** is an object of type [`Uint128`]. This is the total amount of LP tokens currently issued by the pool.
+///
+/// Amounts are rounded down so that the pool never pays out more than the burned share.
 pub fn get_share_in_assets(
     pools: &[Asset; 2],
     amount: Uint128,
     total_share: Uint128,
-) -> [Asset; 2] {
+) -> StdResult<[Asset; 2]> {
     let mut share_ratio = Decimal::zero();
     if !total_share.is_zero() {
         share_ratio = Decimal::from_ratio(amount, total_share);
     }
 
-    [
+    Ok([
         Asset {
             info: pools[0].info.clone(),
-            amount: pools[0].amount * share_ratio,
+            amount: share_ratio.checked_mul_floor(pools[0].amount)?,
         },
         Asset {
             info: pools[1].info.clone(),
-            amount: pools[1].amount * share_ratio,
+            amount: share_ratio.checked_mul_floor(pools[1].amount)?,
         },
-    ]
+    ])
 }
 
 /// ## Description
@@ -762,6 +806,8 @@ pub fn get_share_in_assets(
 /// * **max_spread** is an object of type [`Option<Decimal>`]. This is the maximum spread allowed for the swap.
 ///
 /// * **to** is an object of type [`Option<Addr>`]. This is the address that receives ask tokens.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code used to pay a share of the commission to the referrer.
 #[allow(clippy::too_many_arguments)]
 pub fn swap(
     deps: DepsMut,
@@ -772,7 +818,12 @@ pub fn swap(
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    referral_code: Option<String>,
 ) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
     offer_asset.assert_sent_native_token_balance(&info)?;
 
     let mut config: Config = CONFIG.load(deps.storage)?;
@@ -813,15 +864,21 @@ pub fn swap(
         config.pair_info.pair_type.clone(),
     )?;
 
+    // Traders holding xASTRO or vxASTRO may get part of the commission waived
+    let commission_rate = discounted_fee_rate(&deps.querier, &fee_info, &sender);
+
     let offer_amount = offer_asset.amount;
+    let offer_precision = query_token_precision(&deps.querier, offer_pool.info)?;
+    let ask_precision = query_token_precision(&deps.querier, ask_pool.info.clone())?;
+    let amp = compute_current_amp(&config, &env)?;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
+        offer_precision,
         ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info.clone())?,
+        ask_precision,
         offer_amount,
-        fee_info.total_fee_rate,
-        compute_current_amp(&config, &env)?,
+        commission_rate,
+        amp,
     )?;
 
     // Check the max spread limit (if it was specified)
@@ -859,6 +916,40 @@ pub fn swap(
         }
     }
 
+    // Referral fee
+    let mut referral_fee_amount = Uint128::zero();
+    if let Some(referral_code) = referral_code {
+        let referral_registry = fee_info
+            .referral_registry
+            .ok_or(ContractError::ReferralRegistryNotSet {})?;
+        let (referral_fee, mut referral_msgs) = build_referral_fee_msgs(
+            &deps.querier,
+            &referral_registry,
+            &referral_code,
+            &offer_asset,
+            &Asset {
+                info: ask_pool.info.clone(),
+                amount: commission_amount,
+            },
+            commission_amount.checked_sub(maker_fee_amount)?,
+        )?;
+        messages.append(&mut referral_msgs);
+        referral_fee_amount = referral_fee.amount;
+    }
+
+    // Make sure a math bug can't drain the pool
+    assert_invariant(
+        offer_pool.amount,
+        offer_precision,
+        ask_pool.amount,
+        ask_precision,
+        offer_amount,
+        return_amount
+            .checked_add(maker_fee_amount)?
+            .checked_add(referral_fee_amount)?,
+        amp,
+    )?;
+
     // Accumulate prices for the assets in the pool
     if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
         env,
@@ -874,23 +965,186 @@ pub fn swap(
         CONFIG.save(deps.storage, &config)?;
     }
 
+    // Notify the swap hooks triggered by the swap
+    let hook_msgs = build_swap_hook_msgs(
+        &SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default(),
+        &SwapHookMsg::AfterSwap {
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            spread_amount,
+            commission_amount,
+        },
+        SWAP_HOOK_REPLY_ID,
+    )?;
+
+    let attributes = AttrBuilder::new("swap")
+        .sender(sender.as_str())
+        .receiver(receiver.as_str())
+        .attr("offer_asset", offer_asset.info.to_string())
+        .attr("ask_asset", ask_pool.info.to_string())
+        .attr("offer_amount", offer_amount)
+        .attr("return_amount", return_amount)
+        .attr("tax_amount", tax_amount)
+        .attr("spread_amount", spread_amount)
+        .attr("commission_amount", commission_amount)
+        .attr("maker_fee_amount", maker_fee_amount)
+        .attr("referral_fee_amount", referral_fee_amount);
+
     Ok(Response::new()
         .add_messages(
             // 1. send collateral token from the contract to a user
             // 2. send inactive commission to collector
             messages,
         )
-        .add_attribute("action", "swap")
-        .add_attribute("sender", sender.as_str())
-        .add_attribute("receiver", receiver.as_str())
-        .add_attribute("offer_asset", offer_asset.info.to_string())
-        .add_attribute("ask_asset", ask_pool.info.to_string())
-        .add_attribute("offer_amount", offer_amount.to_string())
-        .add_attribute("return_amount", return_amount.to_string())
-        .add_attribute("tax_amount", tax_amount.to_string())
-        .add_attribute("spread_amount", spread_amount.to_string())
-        .add_attribute("commission_amount", commission_amount.to_string())
-        .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
+        .add_submessages(hook_msgs)
+        .add_event(attributes.event("pair"))
+        .add_attributes(attributes.build()))
+}
+
+/// ## Description
+/// Registers the sender as a swap hook or replaces its thresholds. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **thresholds** is a vector that contains objects of type [`Asset`]. These are the minimum offer amounts
+/// that trigger the hook.
+///
+/// ## Executor
+/// Only contracts whitelisted in the factory can execute this.
+pub fn register_swap_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    thresholds: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !query_swap_hooks_whitelist(&deps.querier, config.factory_addr)?.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for threshold in &thresholds {
+        threshold.info.check(deps.api)?;
+        if !config
+            .pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&threshold.info))
+        {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    let mut hooks = SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    hooks.retain(|hook| hook.contract_addr != info.sender);
+    if hooks.len() >= MAX_SWAP_HOOKS {
+        return Err(ContractError::TooManySwapHooks(MAX_SWAP_HOOKS));
+    }
+    hooks.push(SwapHook {
+        contract_addr: info.sender.clone(),
+        thresholds,
+    });
+    SWAP_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_swap_hook"),
+        attr("contract_addr", info.sender),
+    ]))
+}
+
+/// ## Description
+/// Removes a swap hook. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **contract_addr** is an object of type [`String`]. This is the hook contract to remove.
+///
+/// ## Executor
+/// The hook itself can execute this. Anyone can remove a hook that isn't whitelisted in the factory anymore.
+pub fn deregister_swap_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_addr: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let contract_addr = addr_validate_to_lower(deps.api, &contract_addr)?;
+
+    let mut hooks = SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if !hooks.iter().any(|hook| hook.contract_addr == contract_addr) {
+        return Err(ContractError::SwapHookNotFound(contract_addr.to_string()));
+    }
+
+    // Permission check
+    if info.sender != contract_addr
+        && query_swap_hooks_whitelist(&deps.querier, config.factory_addr)?.contains(&contract_addr)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    hooks.retain(|hook| hook.contract_addr != contract_addr);
+    SWAP_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deregister_swap_hook"),
+        attr("contract_addr", contract_addr),
+    ]))
+}
+
+/// ## Description
+/// Pauses swaps and liquidity provision. Withdrawing liquidity stays possible.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// The factory owner or the factory's emergency admin can execute this.
+pub fn pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let factory_config = query_factory_config(&deps.querier, config.factory_addr)?;
+
+    // Permission check
+    if info.sender != factory_config.owner
+        && Some(&info.sender) != factory_config.emergency_admin.as_ref()
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "pause"))
+}
+
+/// ## Description
+/// Resumes swaps and liquidity provision. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// Only the factory owner can execute this.
+pub fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != query_factory_config(&deps.querier, config.factory_addr)?.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::new().add_attribute("action", "unpause"))
 }
 
 /// ## Description
@@ -935,28 +1189,22 @@ pub fn accumulate_prices(
     if !x.is_zero() && !y.is_zero() {
         let current_amp = compute_current_amp(config, &env)?;
         pcl0 = config.price0_cumulative_last.wrapping_add(adjust_precision(
-            time_elapsed.checked_mul(Uint128::new(
-                calc_ask_amount(
-                    x.u128(),
-                    y.u128(),
-                    adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
-                    current_amp,
-                )
-                .unwrap(),
-            ))?,
+            time_elapsed.checked_mul(Uint128::new(calc_ask_amount(
+                x.u128(),
+                y.u128(),
+                adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
+                current_amp,
+            )?))?,
             greater_precision,
             TWAP_PRECISION,
         )?);
         pcl1 = config.price1_cumulative_last.wrapping_add(adjust_precision(
-            time_elapsed.checked_mul(Uint128::new(
-                calc_ask_amount(
-                    y.u128(),
-                    x.u128(),
-                    adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
-                    current_amp,
-                )
-                .unwrap(),
-            ))?,
+            time_elapsed.checked_mul(Uint128::new(calc_ask_amount(
+                y.u128(),
+                x.u128(),
+                adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
+                current_amp,
+            )?))?,
             greater_precision,
             TWAP_PRECISION,
         )?)
@@ -1019,6 +1267,10 @@ pub fn calculate_maker_fee(
 /// * **QueryMsg::Config {}** Returns the configuration for the pair contract using a [`ConfigResponse`] object.
 ///
 /// * **QueryMsg::PendingReward {}** Returns the amount of bLUNA pending rewards for a specific address using an [`Asset`] object.
+///
+/// * **QueryMsg::SwapHooks {}** Returns the registered swap hooks in a vector that contains objects of type [`SwapHook`].
+///
+/// * **QueryMsg::Paused {}** Returns whether swaps and liquidity provision are paused.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -1034,6 +1286,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
         QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
         QueryMsg::PendingReward { user } => to_binary(&query_pending_reward(deps, env, user)?),
+        QueryMsg::SwapHooks {} => {
+            to_binary(&SWAP_HOOKS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Paused {} => to_binary(&PAUSED.may_load(deps.storage)?.unwrap_or_default()),
     }
 }
 
@@ -1073,7 +1329,7 @@ pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
 pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<[Asset; 2]> {
     let config: Config = CONFIG.load(deps.storage)?;
     let (pools, total_share) = pool_info(deps, config)?;
-    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+    let refund_assets = get_share_in_assets(&pools, amount, total_share)?;
 
     Ok(refund_assets)
 }
@@ -1249,13 +1505,8 @@ pub fn query_pending_reward(deps: Deps, env: Env, user: String) -> StdResult<Ass
 
     let config = CONFIG.load(deps.storage)?;
 
-    let user_share: Uint128 = deps.querier.query_wasm_smart(
-        &config.generator,
-        &GeneratorQueryMsg::Deposit {
-            lp_token: config.pair_info.liquidity_token.to_string(),
-            user: user.to_string(),
-        },
-    )?;
+    let generator = GeneratorQuerier::new(&deps.querier, config.generator.clone());
+    let user_share = generator.deposit_of(&config.pair_info.liquidity_token, &user)?;
 
     let global_index = BLUNA_REWARD_GLOBAL_INDEX
         .may_load(deps.storage)?
@@ -1278,12 +1529,7 @@ pub fn query_pending_reward(deps: Deps, env: Env, user: String) -> StdResult<Ass
         },
     )?;
 
-    let pool_info: PoolInfoResponse = deps.querier.query_wasm_smart(
-        &config.generator,
-        &GeneratorQueryMsg::PoolInfo {
-            lp_token: config.pair_info.liquidity_token.to_string(),
-        },
-    )?;
+    let pool_info = generator.pool_info(&config.pair_info.liquidity_token)?;
 
     let mut accrued_rewards_index = Decimal256::zero();
     if !pool_info.lp_supply.is_zero() {
@@ -1347,9 +1593,12 @@ fn compute_swap(
     let ask_pool = adjust_precision(ask_pool, ask_precision, greater_precision)?;
     let offer_amount = adjust_precision(offer_amount, offer_precision, greater_precision)?;
 
-    let return_amount = Uint128::new(
-        calc_ask_amount(offer_pool.u128(), ask_pool.u128(), offer_amount.u128(), amp).unwrap(),
-    );
+    let return_amount = Uint128::new(calc_ask_amount(
+        offer_pool.u128(),
+        ask_pool.u128(),
+        offer_amount.u128(),
+        amp,
+    )?);
 
     // We assume the assets should stay in a 1:1 ratio, so the true exchange rate is 1. So any exchange rate <1 could be considered the spread
     let spread_amount = offer_amount.saturating_sub(return_amount);
@@ -1366,6 +1615,56 @@ fn compute_swap(
     Ok((return_amount, spread_amount, commission_amount))
 }
 
+/// ## Description
+/// Verifies that a swap doesn't decrease the stableswap invariant of the pool beyond rounding errors.
+/// Returns a [`ContractError`] on failure, otherwise returns [`Ok`].
+/// ## Params
+/// * **offer_pool** is an object of type [`Uint128`]. This is the total amount of offer assets in the pool before the swap.
+///
+/// * **offer_precision** is an object of type [`u8`]. This is the token precision used for the offer amount.
+///
+/// * **ask_pool** is an object of type [`Uint128`]. This is the total amount of ask assets in the pool before the swap.
+///
+/// * **ask_precision** is an object of type [`u8`]. This is the token precision used for the ask amount.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of offer assets added to the pool.
+///
+/// * **ask_amount** is an object of type [`Uint128`]. This is the amount of ask assets leaving the pool,
+/// including the fees that are not absorbed by the pool.
+///
+/// * **amp** is an object of type [`u64`]. This is the pool amplification used to calculate the swap result.
+pub fn assert_invariant(
+    offer_pool: Uint128,
+    offer_precision: u8,
+    ask_pool: Uint128,
+    ask_precision: u8,
+    offer_amount: Uint128,
+    ask_amount: Uint128,
+    amp: u64,
+) -> Result<(), ContractError> {
+    let greater_precision = offer_precision.max(ask_precision);
+    let offer_pool = adjust_precision(offer_pool, offer_precision, greater_precision)?;
+    let ask_pool = adjust_precision(ask_pool, ask_precision, greater_precision)?;
+    let offer_amount = adjust_precision(offer_amount, offer_precision, greater_precision)?;
+    let ask_amount = adjust_precision(ask_amount, ask_precision, greater_precision)?;
+
+    let new_ask_pool = ask_pool
+        .checked_sub(ask_amount)
+        .map_err(|_| ContractError::InvariantViolated {})?;
+
+    if !check_invariant(
+        offer_pool.u128(),
+        ask_pool.u128(),
+        offer_pool.checked_add(offer_amount)?.u128(),
+        new_ask_pool.u128(),
+        amp,
+    )? {
+        return Err(ContractError::InvariantViolated {});
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Returns an amount of offer assets for a specified amount of ask assets.
 /// ## Params
@@ -1400,15 +1699,12 @@ fn compute_offer_amount(
     let inv_one_minus_commission: Decimal = (Decimal256::one() / one_minus_commission).into();
     let before_commission_deduction = ask_amount * inv_one_minus_commission;
 
-    let offer_amount = Uint128::new(
-        calc_offer_amount(
-            offer_pool.u128(),
-            ask_pool.u128(),
-            before_commission_deduction.u128(),
-            amp,
-        )
-        .unwrap(),
-    );
+    let offer_amount = Uint128::new(calc_offer_amount(
+        offer_pool.u128(),
+        ask_pool.u128(),
+        before_commission_deduction.u128(),
+        amp,
+    )?);
 
     // We assume the assets should stay in a 1:1 ratio, so the true exchange rate is 1. So any exchange rate <1 could be considered the spread
     let spread_amount = offer_amount.saturating_sub(before_commission_deduction);
@@ -1514,38 +1810,44 @@ fn assert_slippage_tolerance(
 }
 
 /// ## Description
-/// Used for the contract migration. Returns a default object of type [`Response`].
+/// Used for the contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version. Stable pairs are converted to bLUNA pairs.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`MigrateMsg`].
+/// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    let contract_version = get_contract_version(deps.storage)?;
+    let contract_name = match get_contract_version(deps.storage)?.contract.as_ref() {
+        STABLE_PAIR_CONTRACT_NAME => STABLE_PAIR_CONTRACT_NAME,
+        _ => CONTRACT_NAME,
+    };
+    let contract_version = assert_migration_allowed(deps.storage, contract_name, CONTRACT_VERSION)?;
 
     let mut response = Response::new()
         .add_attribute("previous_contract_name", &contract_version.contract)
         .add_attribute("previous_contract_version", &contract_version.version);
 
-    match contract_version.contract.as_ref() {
-        "astroport-pair-stable" => match contract_version.version.as_ref() {
-            "1.0.0" => {
-                let mut config = CONFIG.load(deps.storage)?;
-                config.bluna_rewarder = addr_validate_to_lower(deps.api, &msg.bluna_rewarder)?;
-                config.generator = addr_validate_to_lower(deps.api, &msg.generator)?;
-                CONFIG.save(deps.storage, &config)?;
-                response
-                    .messages
-                    .push(get_bluna_reward_holder_instantiating_message(
-                        deps.as_ref(),
-                        &env,
-                        &config.factory_addr,
-                    )?);
-            }
-            _ => return Err(ContractError::MigrationError {}),
-        },
+    match (
+        contract_version.contract.as_ref(),
+        contract_version.version.as_ref(),
+    ) {
+        (STABLE_PAIR_CONTRACT_NAME, "1.0.0") => {
+            let mut config = CONFIG.load(deps.storage)?;
+            config.bluna_rewarder = addr_validate_to_lower(deps.api, &msg.bluna_rewarder)?;
+            config.generator = addr_validate_to_lower(deps.api, &msg.generator)?;
+            CONFIG.save(deps.storage, &config)?;
+            response
+                .messages
+                .push(get_bluna_reward_holder_instantiating_message(
+                    deps.as_ref(),
+                    &env,
+                    &config.factory_addr,
+                )?);
+        }
+        (CONTRACT_NAME, "1.0.1") => {}
         _ => return Err(ContractError::MigrationError {}),
     };
 
@@ -1831,24 +2133,14 @@ fn claim_reward(
 
     let config: Config = CONFIG.load(deps.storage)?;
 
-    let user_share: Uint128 = deps.querier.query_wasm_smart(
-        &config.generator,
-        &GeneratorQueryMsg::Deposit {
-            lp_token: config.pair_info.liquidity_token.to_string(),
-            user: info.sender.to_string(),
-        },
-    )?;
+    let generator = GeneratorQuerier::new(&deps.querier, config.generator.clone());
+    let user_share = generator.deposit_of(&config.pair_info.liquidity_token, &info.sender)?;
 
     if user_share.is_zero() {
         return Err(StdError::generic_err("No lp tokens staked to the generator!").into());
     }
 
-    let pool_info: PoolInfoResponse = deps.querier.query_wasm_smart(
-        &config.generator,
-        &GeneratorQueryMsg::PoolInfo {
-            lp_token: config.pair_info.liquidity_token.to_string(),
-        },
-    )?;
+    let pool_info = generator.pool_info(&config.pair_info.liquidity_token)?;
 
     Ok(
         Response::new().add_messages(get_bluna_reward_handling_messages(
@@ -1,15 +1,23 @@
 use std::convert::TryFrom;
 
+use astroport::constants::SECONDS_PER_DAY;
 use astroport::U256;
+use cosmwasm_std::{StdError, StdResult};
 
 const N_COINS_SQUARED: u8 = 4;
-const ITERATIONS: u8 = 32;
+/// The maximum number of Newton's method iterations used to approximate D and y
+pub const ITERATIONS: u8 = 255;
 
 pub const N_COINS: u8 = 2;
 pub const MAX_AMP: u64 = 1_000_000;
 pub const MAX_AMP_CHANGE: u64 = 10;
-pub const MIN_AMP_CHANGING_TIME: u64 = 86400;
+pub const MIN_AMP_CHANGING_TIME: u64 = SECONDS_PER_DAY;
 pub const AMP_PRECISION: u64 = 100;
+/// The number of units D is allowed to decrease by after a swap, on top of [`D_RELATIVE_TOLERANCE`]
+pub const D_TOLERANCE: u128 = 10;
+/// D is allowed to decrease by 1 / D_RELATIVE_TOLERANCE of its value after a swap. [`compute_d`] stops
+/// as soon as Newton's method stops decreasing, which leaves an error that grows with the pool imbalance
+pub const D_RELATIVE_TOLERANCE: u128 = 1_000_000;
 
 /// ## Description
 /// Calculates the ask amount (the amount of tokens swapped to) for a swap.
@@ -26,16 +34,20 @@ pub fn calc_ask_amount(
     ask_pool: u128,
     offer_amount: u128,
     amp: u64,
-) -> Option<u128> {
-    let leverage = amp.checked_mul(u64::from(N_COINS)).unwrap();
-    let new_offer_pool = offer_pool + offer_amount;
+) -> StdResult<u128> {
+    let leverage = amp
+        .checked_mul(u64::from(N_COINS))
+        .ok_or_else(math_overflow)?;
+    let new_offer_pool = offer_pool
+        .checked_add(offer_amount)
+        .ok_or_else(math_overflow)?;
 
-    let d = compute_d(leverage, offer_pool, ask_pool).unwrap();
+    let d = compute_d(leverage, offer_pool, ask_pool)?;
 
     let new_ask_pool = compute_new_balance(leverage, new_offer_pool, d)?;
 
-    let amount_swapped = ask_pool - new_ask_pool;
-    Some(amount_swapped)
+    // Rounding may leave the new ask pool slightly above the current one for dust swaps
+    Ok(ask_pool.saturating_sub(new_ask_pool))
 }
 
 /// ## Description
@@ -53,16 +65,52 @@ pub fn calc_offer_amount(
     ask_pool: u128,
     ask_amount: u128,
     amp: u64,
-) -> Option<u128> {
-    let leverage = amp.checked_mul(u64::from(N_COINS)).unwrap();
-    let new_ask_pool = ask_pool - ask_amount;
+) -> StdResult<u128> {
+    let leverage = amp
+        .checked_mul(u64::from(N_COINS))
+        .ok_or_else(math_overflow)?;
+    let new_ask_pool = ask_pool.checked_sub(ask_amount).ok_or_else(math_overflow)?;
 
-    let d = compute_d(leverage, offer_pool, ask_pool).unwrap();
+    let d = compute_d(leverage, offer_pool, ask_pool)?;
 
     let new_offer_pool = compute_new_balance(leverage, new_ask_pool, d)?;
 
-    let amount_swapped = new_offer_pool - offer_pool;
-    Some(amount_swapped)
+    new_offer_pool
+        .checked_sub(offer_pool)
+        .ok_or_else(math_overflow)
+}
+
+/// ## Description
+/// Returns `true` if moving the pool balances from (`offer_pool`, `ask_pool`) to (`new_offer_pool`,
+/// `new_ask_pool`) doesn't decrease the stableswap invariant (D) beyond the rounding tolerance.
+/// See [`D_TOLERANCE`] and [`D_RELATIVE_TOLERANCE`].
+/// ## Params
+/// * **offer_pool** is an object of type [`u128`]. This is the amount of offer tokens in the pool before the swap.
+///
+/// * **ask_pool** is an object of type [`u128`]. This is the amount of ask tokens in the pool before the swap.
+///
+/// * **new_offer_pool** is an object of type [`u128`]. This is the amount of offer tokens in the pool after the swap.
+///
+/// * **new_ask_pool** is an object of type [`u128`]. This is the amount of ask tokens in the pool after the swap.
+///
+/// * **amp** is an object of type [`u64`]. This is the pool's amplification parameter.
+pub fn check_invariant(
+    offer_pool: u128,
+    ask_pool: u128,
+    new_offer_pool: u128,
+    new_ask_pool: u128,
+    amp: u64,
+) -> StdResult<bool> {
+    let leverage = amp
+        .checked_mul(u64::from(N_COINS))
+        .ok_or_else(math_overflow)?;
+
+    let d = compute_d(leverage, offer_pool, ask_pool)?;
+    let new_d = compute_d(leverage, new_offer_pool, new_ask_pool)?;
+
+    let tolerance = D_TOLERANCE.saturating_add(d / D_RELATIVE_TOLERANCE);
+
+    Ok(new_d.saturating_add(tolerance) >= d)
 }
 
 /// ## Description
@@ -72,43 +120,46 @@ pub fn calc_offer_amount(
 ///
 /// A * sum(x_i) * n**n + D = A * D * n**n + D**(n+1) / (n**n * prod(x_i))
 ///
+/// Newton's method approaches D from above, so the iteration stops as soon as D stops decreasing
+/// (integer rounding makes it oscillate around the exact root by a few units). Returns an error if
+/// D does not converge in [`ITERATIONS`] iterations.
 /// ## Params
 /// * **leverage** is an object of type [`u128`].
 ///
 /// * **amount_a** is an object of type [`u128`].
 ///
 /// * **amount_b** is an object of type [`u128`].
-pub fn compute_d(leverage: u64, amount_a: u128, amount_b: u128) -> Option<u128> {
-    let amount_a_times_coins =
-        checked_u8_mul(&U256::from(amount_a), N_COINS)?.checked_add(U256::one())?;
-    let amount_b_times_coins =
-        checked_u8_mul(&U256::from(amount_b), N_COINS)?.checked_add(U256::one())?;
-    let sum_x = amount_a.checked_add(amount_b)?; // sum(x_i), a.k.a S
+pub fn compute_d(leverage: u64, amount_a: u128, amount_b: u128) -> StdResult<u128> {
+    let amount_a_times_coins = checked_u8_mul(&U256::from(amount_a), N_COINS)
+        .and_then(|amount| amount.checked_add(U256::one()))
+        .ok_or_else(math_overflow)?;
+    let amount_b_times_coins = checked_u8_mul(&U256::from(amount_b), N_COINS)
+        .and_then(|amount| amount.checked_add(U256::one()))
+        .ok_or_else(math_overflow)?;
+    let sum_x = amount_a.checked_add(amount_b).ok_or_else(math_overflow)?; // sum(x_i), a.k.a S
     if sum_x == 0 {
-        Some(0)
-    } else {
-        let mut d_previous: U256;
-        let mut d: U256 = sum_x.into();
-
-        // Newton's method to approximate D
-        for _ in 0..ITERATIONS {
-            let mut d_product = d;
-            d_product = d_product
-                .checked_mul(d)?
-                .checked_div(amount_a_times_coins)?;
-            d_product = d_product
-                .checked_mul(d)?
-                .checked_div(amount_b_times_coins)?;
-            d_previous = d;
-            // d = (leverage * sum_x + d_p * n_coins) * d / ((leverage - 1) * d + (n_coins + 1) * d_p);
-            d = calculate_step(&d, leverage, sum_x, &d_product)?;
-            // Equality with the precision of 1
-            if d == d_previous {
-                break;
-            }
+        return Ok(0);
+    }
+
+    let mut d: U256 = sum_x.into();
+
+    // Newton's method to approximate D
+    for _ in 0..ITERATIONS {
+        let d_previous = d;
+        let d_product = d
+            .checked_mul(d)
+            .and_then(|d_product| d_product.checked_div(amount_a_times_coins))
+            .and_then(|d_product| d_product.checked_mul(d))
+            .and_then(|d_product| d_product.checked_div(amount_b_times_coins))
+            .ok_or_else(math_overflow)?;
+        // d = (leverage * sum_x + d_p * n_coins) * d / ((leverage - 1) * d + (n_coins + 1) * d_p);
+        d = calculate_step(&d, leverage, sum_x, &d_product).ok_or_else(math_overflow)?;
+        if d >= d_previous {
+            return u128::try_from(d).map_err(|_| math_overflow());
         }
-        u128::try_from(d).ok()
     }
+
+    Err(not_converged("D"))
 }
 
 /// ## Description
@@ -140,7 +191,12 @@ fn calculate_step(initial_d: &U256, leverage: u64, sum_x: u128, d_product: &U256
 /// y**2 + y * (sum' - (A*n**n - 1) * D / (A * n**n)) = D ** (n + 1) / (n ** (2 * n) * prod' * A)
 ///
 /// y**2 + b*y = c
-fn compute_new_balance(leverage: u64, new_source_amount: u128, d_val: u128) -> Option<u128> {
+///
+/// After the first step, Newton's method approaches y from above, so the iteration stops as soon as
+/// y stops decreasing. The greater of the last two approximations is returned so that rounding
+/// always favors the pool.
+/// Returns an error if y does not converge in [`ITERATIONS`] iterations.
+fn compute_new_balance(leverage: u64, new_source_amount: u128, d_val: u128) -> StdResult<u128> {
     // Upscale to U256
     let leverage: U256 = leverage.into();
     let new_source_amount: U256 = new_source_amount.into();
@@ -148,29 +204,55 @@ fn compute_new_balance(leverage: u64, new_source_amount: u128, d_val: u128) -> O
 
     // sum' = prod' = x
     // c =  D ** (n + 1) / (n ** (2 * n) * prod' * A)
-    let c = checked_u8_power(&d_val, N_COINS.checked_add(1)?)?
-        .checked_mul(U256::from(AMP_PRECISION))?
-        .checked_div(checked_u8_mul(&new_source_amount, N_COINS_SQUARED)?.checked_mul(leverage)?)?;
+    let c = N_COINS
+        .checked_add(1)
+        .and_then(|power| checked_u8_power(&d_val, power))
+        .and_then(|c| c.checked_mul(U256::from(AMP_PRECISION)))
+        .and_then(|c| {
+            c.checked_div(
+                checked_u8_mul(&new_source_amount, N_COINS_SQUARED)?.checked_mul(leverage)?,
+            )
+        })
+        .ok_or_else(math_overflow)?;
 
     // b = sum' - (A*n**n - 1) * D / (A * n**n)
-    let b = new_source_amount.checked_add(
-        d_val
-            .checked_mul(U256::from(AMP_PRECISION))?
-            .checked_div(leverage)?,
-    )?;
+    let b = d_val
+        .checked_mul(U256::from(AMP_PRECISION))
+        .and_then(|b| b.checked_div(leverage))
+        .and_then(|b| new_source_amount.checked_add(b))
+        .ok_or_else(math_overflow)?;
 
     // Solve for y by approximating: y**2 + b*y = c
-    let mut y_prev: U256;
     let mut y = d_val;
-    for _ in 0..ITERATIONS {
-        y_prev = y;
-        y = (checked_u8_power(&y, 2)?.checked_add(c)?)
-            .checked_div(checked_u8_mul(&y, 2)?.checked_add(b)?.checked_sub(d_val)?)?;
-        if y == y_prev {
-            break;
+    for i in 0..ITERATIONS {
+        let y_prev = y;
+        y = checked_u8_power(&y, 2)
+            .and_then(|y_squared| y_squared.checked_add(c))
+            .and_then(|numerator| {
+                numerator.checked_div(checked_u8_mul(&y, 2)?.checked_add(b)?.checked_sub(d_val)?)
+            })
+            .ok_or_else(math_overflow)?;
+        if i > 0 && y >= y_prev {
+            return u128::try_from(y).map_err(|_| math_overflow());
         }
     }
-    u128::try_from(y).ok()
+
+    Err(not_converged("y"))
+}
+
+/// ## Description
+/// Returns an error for an overflow or a division by zero in the stableswap math.
+fn math_overflow() -> StdError {
+    StdError::generic_err("Stableswap math overflow")
+}
+
+/// ## Description
+/// Returns an error for a Newton's method approximation that did not converge.
+fn not_converged(value: &str) -> StdError {
+    StdError::generic_err(format!(
+        "Stableswap {} did not converge in {} iterations",
+        value, ITERATIONS
+    ))
 }
 
 /// ## Description
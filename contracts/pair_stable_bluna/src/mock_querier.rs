@@ -136,6 +136,8 @@ impl WasmMockQuerier {
                                 fee_address: Some(Addr::unchecked("fee_address")),
                                 total_fee_bps: 30,
                                 maker_fee_bps: 1660,
+                                referral_registry: None,
+                                fee_discount: None,
                             })
                             .into(),
                         ),
@@ -147,6 +149,8 @@ impl WasmMockQuerier {
                                 fee_address: Some(Addr::unchecked("fee_address")),
                                 generator_address: Some(Addr::unchecked("gen_address")),
                                 whitelist_code_id: 666,
+                                referral_registry: None,
+                                emergency_admin: None,
                             })
                             .into(),
                         ),
@@ -1,4 +1,5 @@
 use astroport::asset::PairInfo;
+use astroport::pair::SwapHook;
 use cosmwasm_std::{Addr, Decimal256, Uint128};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
@@ -34,6 +35,14 @@ pub struct Config {
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// ## Description
+/// Stores the swap hooks registered in the pair
+pub const SWAP_HOOKS: Item<Vec<SwapHook>> = Item::new("swap_hooks");
+
+/// ## Description
+/// Stores whether swaps and liquidity provision are paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
 pub const BLUNA_REWARD_HOLDER: Item<Addr> = Item::new("bluna_reward_holder");
 pub const BLUNA_REWARD_GLOBAL_INDEX: Item<Decimal256> = Item::new("bluna_reward_global_index");
 pub const BLUNA_REWARD_USER_INDEXES: Map<&Addr, Decimal256> = Map::new("bluna_reward_user_indexes");
@@ -1,6 +1,7 @@
 use crate::contract::{
-    accumulate_prices, assert_max_spread, calc_user_reward, execute, instantiate, query_pair_info,
-    query_pool, query_reverse_simulation, query_share, query_simulation, reply,
+    accumulate_prices, assert_invariant, assert_max_spread, calc_user_reward, execute, instantiate,
+    migrate, query_pair_info, query_pool, query_reverse_simulation, query_share, query_simulation,
+    reply,
 };
 use crate::error::ContractError;
 use crate::math::{calc_ask_amount, calc_offer_amount, AMP_PRECISION};
@@ -12,7 +13,7 @@ use astroport::pair::{
     Cw20HookMsg, InstantiateMsg, PoolResponse, ReverseSimulationResponse, SimulationResponse,
     TWAP_PRECISION,
 };
-use astroport::pair_stable_bluna::{ExecuteMsg, StablePoolParams};
+use astroport::pair_stable_bluna::{ExecuteMsg, MigrateMsg, StablePoolParams};
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use astroport::whitelist::InstantiateMsg as WhitelistInstantiateMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
@@ -21,6 +22,7 @@ use cosmwasm_std::{
     Decimal256, DepsMut, Env, Reply, ReplyOn, StdError, SubMsg, SubMsgExecutionResponse, Timestamp,
     Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
 use std::str::FromStr;
@@ -113,6 +115,7 @@ fn proper_initialization() {
                             minter: String::from(MOCK_CONTRACT_ADDR),
                             cap: None,
                         }),
+                        marketing: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -501,6 +504,7 @@ fn try_native_to_token() {
         belief_price: None,
         max_spread: Some(Decimal::percent(50)),
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info(
@@ -570,6 +574,7 @@ fn try_native_to_token() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -662,6 +667,7 @@ fn try_token_to_native() {
         belief_price: None,
         max_spread: None,
         to: None,
+        referral_code: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -676,6 +682,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -783,6 +790,7 @@ fn try_token_to_native() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("referral_fee_amount", "0"),
         ]
     );
 
@@ -813,6 +821,7 @@ fn try_token_to_native() {
             belief_price: None,
             max_spread: None,
             to: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -861,6 +870,66 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[test]
+fn test_assert_invariant() {
+    let pool = Uint128::from(1_000_000_000_000u128);
+    let offer_amount = Uint128::from(1_000_000_000u128);
+
+    for amp in [1, 100, 10_000] {
+        let amp = amp * AMP_PRECISION;
+        let ask_amount = Uint128::new(
+            calc_ask_amount(pool.u128(), pool.u128(), offer_amount.u128(), amp).unwrap(),
+        );
+        assert_invariant(pool, 6, pool, 6, offer_amount, ask_amount, amp).unwrap();
+
+        let err = assert_invariant(
+            pool,
+            6,
+            pool,
+            6,
+            offer_amount,
+            ask_amount + pool / Uint128::from(100u128),
+            amp,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvariantViolated {});
+    }
+
+    // Balances are compared using the greater precision
+    let scale = Uint128::from(1_000_000_000_000u128);
+    let ask_amount = Uint128::new(
+        calc_ask_amount(
+            (pool * scale).u128(),
+            (pool * scale).u128(),
+            (offer_amount * scale).u128(),
+            100 * AMP_PRECISION,
+        )
+        .unwrap(),
+    );
+    assert_invariant(
+        pool,
+        6,
+        pool * scale,
+        18,
+        offer_amount,
+        ask_amount,
+        100 * AMP_PRECISION,
+    )
+    .unwrap();
+
+    let err = assert_invariant(
+        pool,
+        6,
+        pool,
+        6,
+        offer_amount,
+        pool + Uint128::from(1u128),
+        AMP_PRECISION,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvariantViolated {});
+}
+
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -1268,3 +1337,51 @@ proptest! {
         );
     }
 }
+
+#[test]
+fn migrate_from_v101() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(
+        deps.as_mut().storage,
+        "astroport-pair-stable-bluna",
+        "1.0.1",
+    )
+    .unwrap();
+
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            bluna_rewarder: "bluna_rewarder".to_string(),
+            generator: "generator".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-pair-stable-bluna"),
+            attr("previous_contract_version", "1.0.1"),
+            attr("new_contract_name", "astroport-pair-stable-bluna"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            bluna_rewarder: "bluna_rewarder".to_string(),
+            generator: "generator".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -0,0 +1,735 @@
+use crate::error::ContractError;
+use crate::state::{
+    Config, Schedule, CONFIG, NEXT_SCHEDULE_ID, OWNERSHIP_PROPOSAL, OWNER_SCHEDULES, SCHEDULES,
+};
+use astroport::asset::{addr_validate_to_lower, has_duplicate_asset_infos, Asset, AssetInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::dca::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ScheduleResponse, SchedulesResponse,
+};
+use astroport::pair::MAX_ALLOWED_SLIPPAGE;
+use astroport::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, SwapOperation,
+    MAX_SWAP_OPERATIONS,
+};
+use astroport::DecimalCheckedOps;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use std::str::FromStr;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-dca";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading schedules
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading schedules
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_tips(deps.api, &msg.tips)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            router: addr_validate_to_lower(deps.api, &msg.router)?,
+            tips: msg.tips,
+        },
+    )?;
+    NEXT_SCHEDULE_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::CreateSchedule {
+///             deposit,
+///             operations,
+///             amount_per_interval,
+///             interval,
+///             max_slippage,
+///             min_price,
+///         }** Creates a schedule funded with native tokens.
+///
+/// * **ExecuteMsg::CancelSchedule { schedule_id }** Cancels a schedule and refunds the remaining deposit.
+///
+/// * **ExecuteMsg::Purchase { schedule_id }** Makes the next purchase of a schedule.
+///
+/// * **ExecuteMsg::UpdateConfig { router, tips }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateSchedule {
+            deposit,
+            operations,
+            amount_per_interval,
+            interval,
+            max_slippage,
+            min_price,
+        } => {
+            deposit.info.check(deps.api)?;
+            if !deposit.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+            deposit.assert_sent_native_token_balance(&info)?;
+
+            create_schedule(
+                deps,
+                env,
+                info.sender,
+                deposit,
+                operations,
+                amount_per_interval,
+                interval,
+                max_slippage,
+                min_price,
+            )
+        }
+        ExecuteMsg::CancelSchedule { schedule_id } => cancel_schedule(deps, info, schedule_id),
+        ExecuteMsg::Purchase { schedule_id } => purchase(deps, env, info, schedule_id),
+        ExecuteMsg::UpdateConfig { router, tips } => update_config(deps, info, router, tips),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then a [`ContractError`] is returned.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message that has to be processed.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateSchedule {
+            operations,
+            amount_per_interval,
+            interval,
+            max_slippage,
+            min_price,
+        } => {
+            let owner = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            create_schedule(
+                deps,
+                env,
+                owner,
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: info.sender,
+                    },
+                    amount: cw20_msg.amount,
+                },
+                operations,
+                amount_per_interval,
+                interval,
+                max_slippage,
+                min_price,
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Creates a new schedule. The first purchase can be made right away.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **owner** is an object of type [`Addr`]. This is the address that creates the schedule.
+///
+/// * **deposit** is an object of type [`Asset`]. This is the deposit that funds the schedule.
+///
+/// * **operations** is a vector that contains objects of type [`SwapOperation`]. These are the swap
+/// operations used for every purchase.
+///
+/// * **amount_per_interval** is an object of type [`Uint128`]. This is the amount swapped in every purchase.
+///
+/// * **interval** is an object of type [`u64`]. This is the minimum time between two purchases.
+///
+/// * **max_slippage** is an object of type [`Decimal`]. This is the maximum spread allowed in every swap.
+///
+/// * **min_price** is an object of type [`Decimal`]. This is the minimum amount of ask tokens bought per
+/// deposited token in every purchase.
+#[allow(clippy::too_many_arguments)]
+fn create_schedule(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    deposit: Asset,
+    operations: Vec<SwapOperation>,
+    amount_per_interval: Uint128,
+    interval: u64,
+    max_slippage: Decimal,
+    min_price: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let ask_asset_info = validate_operations(&deposit.info, &operations)?;
+
+    if amount_per_interval.is_zero() {
+        return Err(ContractError::InvalidSchedule(String::from(
+            "the amount per interval must be greater than zero",
+        )));
+    }
+    if interval == 0 {
+        return Err(ContractError::InvalidSchedule(String::from(
+            "the interval must be greater than zero",
+        )));
+    }
+    if max_slippage > Decimal::from_str(MAX_ALLOWED_SLIPPAGE)? {
+        return Err(ContractError::MaxSlippageTooHigh {});
+    }
+    if min_price.is_zero() {
+        return Err(ContractError::InvalidSchedule(String::from(
+            "the minimum price must be greater than zero",
+        )));
+    }
+
+    let tip = config
+        .tips
+        .iter()
+        .find(|tip| tip.info.equal(&deposit.info))
+        .map(|tip| tip.amount)
+        .ok_or_else(|| ContractError::TipNotSet(deposit.info.to_string()))?;
+    if deposit.amount <= tip {
+        return Err(ContractError::DepositTooLow {});
+    }
+
+    let schedule_id = NEXT_SCHEDULE_ID.load(deps.storage)?;
+    NEXT_SCHEDULE_ID.save(deps.storage, &(schedule_id + 1))?;
+
+    let schedule = Schedule {
+        schedule_id,
+        owner,
+        balance: deposit,
+        ask_asset_info,
+        operations,
+        amount_per_interval,
+        interval,
+        max_slippage,
+        min_price,
+        tip,
+        next_purchase_at: env.block.time.seconds(),
+        purchases: 0,
+    };
+    SCHEDULES.save(deps.storage, U64Key::new(schedule_id), &schedule)?;
+    OWNER_SCHEDULES.save(
+        deps.storage,
+        (&schedule.owner, U64Key::new(schedule_id)),
+        &(),
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "create_schedule"),
+        attr("schedule_id", schedule_id.to_string()),
+        attr("owner", schedule.owner),
+        attr("deposit", schedule.balance.to_string()),
+        attr("ask_asset", schedule.ask_asset_info.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Cancels a schedule and refunds the remaining deposit to the schedule owner.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **schedule_id** is an object of type [`u64`]. This is the schedule to cancel.
+///
+/// ## Executor
+/// Only the schedule owner can execute this.
+fn cancel_schedule(
+    deps: DepsMut,
+    info: MessageInfo,
+    schedule_id: u64,
+) -> Result<Response, ContractError> {
+    let schedule = load_schedule(deps.as_ref(), schedule_id)?;
+
+    // Permission check
+    if info.sender != schedule.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_schedule(deps.storage, &schedule);
+
+    Ok(Response::new()
+        .add_message(
+            schedule
+                .balance
+                .clone()
+                .into_msg(&deps.querier, schedule.owner.clone())?,
+        )
+        .add_attributes(vec![
+            attr("action", "cancel_schedule"),
+            attr("schedule_id", schedule_id.to_string()),
+            attr("refund", schedule.balance.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Makes the next purchase of a schedule through the router and sends the tip to the sender.
+/// The bought tokens go to the schedule owner. Once the remaining deposit can't fund another purchase,
+/// it is refunded and the schedule is removed. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **schedule_id** is an object of type [`u64`]. This is the schedule to make a purchase for.
+fn purchase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    schedule_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut schedule = load_schedule(deps.as_ref(), schedule_id)?;
+
+    let now = env.block.time.seconds();
+    if now < schedule.next_purchase_at {
+        return Err(ContractError::PurchaseNotDue(schedule.next_purchase_at));
+    }
+
+    // The deposit always covers the tip of the next purchase
+    let swap_amount = schedule
+        .amount_per_interval
+        .min(schedule.balance.amount.checked_sub(schedule.tip)?);
+    schedule.balance.amount = schedule
+        .balance
+        .amount
+        .checked_sub(swap_amount + schedule.tip)?;
+    schedule.purchases += 1;
+    schedule.next_purchase_at = now + schedule.interval;
+
+    // The purchase fails if the pool price was moved below the schedule's minimum price
+    let minimum_receive;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    match &schedule.balance.info {
+        AssetInfo::NativeToken { .. } => {
+            let coin = Asset {
+                info: schedule.balance.info.clone(),
+                amount: swap_amount,
+            }
+            .deduct_tax(&deps.querier)?;
+            minimum_receive = schedule.min_price.checked_mul_ceil(coin.amount)?;
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.router.to_string(),
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                    operations: schedule.operations.clone(),
+                    minimum_receive: Some(minimum_receive),
+                    to: Some(schedule.owner.clone()),
+                    max_spread: Some(schedule.max_slippage),
+                    referral_code: None,
+                })?,
+                funds: vec![coin],
+            }));
+        }
+        AssetInfo::Token { contract_addr } => {
+            minimum_receive = schedule.min_price.checked_mul_ceil(swap_amount)?;
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: config.router.to_string(),
+                    amount: swap_amount,
+                    msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                        operations: schedule.operations.clone(),
+                        minimum_receive: Some(minimum_receive),
+                        to: Some(schedule.owner.to_string()),
+                        max_spread: Some(schedule.max_slippage),
+                        referral_code: None,
+                    })?,
+                })?,
+                funds: vec![],
+            }));
+        }
+    }
+
+    if !schedule.tip.is_zero() {
+        messages.push(
+            Asset {
+                info: schedule.balance.info.clone(),
+                amount: schedule.tip,
+            }
+            .into_msg(&deps.querier, info.sender.clone())?,
+        );
+    }
+
+    let mut attributes = vec![
+        attr("action", "purchase"),
+        attr("schedule_id", schedule_id.to_string()),
+        attr("bot", info.sender),
+        attr("swap_amount", swap_amount),
+        attr("minimum_receive", minimum_receive),
+        attr("tip", schedule.tip),
+    ];
+
+    if schedule.balance.amount > schedule.tip {
+        SCHEDULES.save(deps.storage, U64Key::new(schedule_id), &schedule)?;
+    } else {
+        // The remaining deposit can't fund another purchase
+        remove_schedule(deps.storage, &schedule);
+        if !schedule.balance.amount.is_zero() {
+            messages.push(
+                schedule
+                    .balance
+                    .clone()
+                    .into_msg(&deps.querier, schedule.owner.clone())?,
+            );
+        }
+        attributes.push(attr("refund", schedule.balance.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **router** is an [`Option`] field of type [`String`]. This is the new router contract address.
+///
+/// * **tips** is an [`Option`] field of type [`Vec<Asset>`]. These are the new tips paid for every purchase.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    router: Option<String>,
+    tips: Option<Vec<Asset>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(router) = router {
+        config.router = addr_validate_to_lower(deps.api, &router)?;
+    }
+
+    if let Some(tips) = tips {
+        validate_tips(deps.api, &tips)?;
+        config.tips = tips;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Checks that the tip assets are valid and unique.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **tips** is a slice that contains objects of type [`Asset`].
+fn validate_tips(api: &dyn Api, tips: &[Asset]) -> Result<(), ContractError> {
+    for tip in tips {
+        tip.info.check(api)?;
+    }
+
+    let asset_infos: Vec<AssetInfo> = tips.iter().map(|tip| tip.info.clone()).collect();
+    if has_duplicate_asset_infos(&asset_infos) {
+        return Err(ContractError::DuplicateTips {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Checks that the swap operations start with the deposited asset and returns the asset they end with.
+/// ## Params
+/// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the deposited asset.
+///
+/// * **operations** is a slice that contains objects of type [`SwapOperation`].
+fn validate_operations(
+    offer_asset_info: &AssetInfo,
+    operations: &[SwapOperation],
+) -> Result<AssetInfo, ContractError> {
+    let (first, last) = match (operations.first(), operations.last()) {
+        (Some(first), Some(last)) if operations.len() <= MAX_SWAP_OPERATIONS => (first, last),
+        _ => {
+            return Err(ContractError::InvalidOperations(format!(
+                "a schedule must use 1 to {} operations",
+                MAX_SWAP_OPERATIONS
+            )))
+        }
+    };
+
+    if !first.get_offer_asset_info().equal(offer_asset_info) {
+        return Err(ContractError::InvalidOperations(String::from(
+            "the first operation must offer the deposited asset",
+        )));
+    }
+
+    for pair in operations.windows(2) {
+        if !pair[0]
+            .get_target_asset_info()
+            .equal(&pair[1].get_offer_asset_info())
+        {
+            return Err(ContractError::InvalidOperations(String::from(
+                "every operation must offer the asset returned by the previous one",
+            )));
+        }
+    }
+
+    let ask_asset_info = last.get_target_asset_info();
+    if ask_asset_info.equal(offer_asset_info) {
+        return Err(ContractError::InvalidOperations(String::from(
+            "the bought asset must differ from the deposited asset",
+        )));
+    }
+
+    Ok(ask_asset_info)
+}
+
+/// ## Description
+/// Loads a schedule. Returns a [`ContractError`] if the schedule doesn't exist.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **schedule_id** is an object of type [`u64`].
+fn load_schedule(deps: Deps, schedule_id: u64) -> Result<Schedule, ContractError> {
+    SCHEDULES
+        .may_load(deps.storage, U64Key::new(schedule_id))?
+        .ok_or(ContractError::ScheduleNotFound(schedule_id))
+}
+
+/// ## Description
+/// Removes a schedule together with its owner index entry.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **schedule** is an object of type [`Schedule`]. This is the schedule to remove.
+fn remove_schedule(storage: &mut dyn Storage, schedule: &Schedule) {
+    SCHEDULES.remove(storage, U64Key::new(schedule.schedule_id));
+    OWNER_SCHEDULES.remove(
+        storage,
+        (&schedule.owner, U64Key::new(schedule.schedule_id)),
+    );
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Schedule { schedule_id }** Returns a schedule using a [`ScheduleResponse`] object.
+///
+/// * **QueryMsg::Schedules { owner, start_after, limit }** Returns the schedules of a user
+/// using a [`SchedulesResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                router: config.router,
+                tips: config.tips,
+            })
+        }
+        QueryMsg::Schedule { schedule_id } => to_binary(&schedule_response(
+            SCHEDULES.load(deps.storage, U64Key::new(schedule_id))?,
+        )),
+        QueryMsg::Schedules {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_schedules(deps, owner, start_after, limit)?),
+    }
+}
+
+/// ## Description
+/// Returns the active schedules of a user ordered by their identifiers.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **owner** is an object of type [`String`]. This is the user whose schedules are returned.
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. This is the schedule identifier to start reading after.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of schedules to return.
+fn query_schedules(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SchedulesResponse> {
+    let owner = addr_validate_to_lower(deps.api, &owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    let schedules = OWNER_SCHEDULES
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            SCHEDULES
+                .load(deps.storage, key.into())
+                .map(schedule_response)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SchedulesResponse { schedules })
+}
+
+/// ## Description
+/// Converts a stored schedule into a [`ScheduleResponse`] object.
+/// ## Params
+/// * **schedule** is an object of type [`Schedule`].
+fn schedule_response(schedule: Schedule) -> ScheduleResponse {
+    ScheduleResponse {
+        schedule_id: schedule.schedule_id,
+        owner: schedule.owner,
+        balance: schedule.balance,
+        ask_asset_info: schedule.ask_asset_info,
+        operations: schedule.operations,
+        amount_per_interval: schedule.amount_per_interval,
+        interval: schedule.interval,
+        max_slippage: schedule.max_slippage,
+        min_price: schedule.min_price,
+        tip: schedule.tip,
+        next_purchase_at: schedule.next_purchase_at,
+        purchases: schedule.purchases,
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,43 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes DCA contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid swap operations: {0}")]
+    InvalidOperations(String),
+
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("The maximum slippage can't be higher than 50%")]
+    MaxSlippageTooHigh {},
+
+    #[error("No tip is set for {0}, so it can't fund a schedule")]
+    TipNotSet(String),
+
+    #[error("Duplicate tip assets")]
+    DuplicateTips {},
+
+    #[error("The deposit must be higher than the tip")]
+    DepositTooLow {},
+
+    #[error("Schedule {0} not found")]
+    ScheduleNotFound(u64),
+
+    #[error("The next purchase can't be made before {0}")]
+    PurchaseNotDue(u64),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
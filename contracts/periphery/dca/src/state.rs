@@ -0,0 +1,69 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport::common::OwnershipProposal;
+use astroport::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the DCA contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport router contract address used to make purchases
+    pub router: Addr,
+    /// The fixed tips paid to bots for every purchase
+    pub tips: Vec<Asset>,
+}
+
+/// ## Description
+/// This structure describes a recurring purchase schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Schedule {
+    /// The schedule identifier
+    pub schedule_id: u64,
+    /// The address that created the schedule
+    pub owner: Addr,
+    /// The remaining deposit that funds the next purchases and tips
+    pub balance: Asset,
+    /// The asset bought in every purchase
+    pub ask_asset_info: AssetInfo,
+    /// The swap operations used for every purchase
+    pub operations: Vec<SwapOperation>,
+    /// The amount of deposited tokens swapped in every purchase
+    pub amount_per_interval: Uint128,
+    /// The minimum time (in seconds) between two purchases
+    pub interval: u64,
+    /// The maximum spread allowed in every swap of a purchase
+    pub max_slippage: Decimal,
+    /// The minimum amount of ask tokens bought per deposited token in every purchase
+    pub min_price: Decimal,
+    /// The amount of deposited tokens paid to the bot that makes a purchase
+    pub tip: Uint128,
+    /// The timestamp (in seconds) after which the next purchase can be made
+    pub next_purchase_at: u64,
+    /// The amount of purchases made so far
+    pub purchases: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the identifier of the next schedule
+pub const NEXT_SCHEDULE_ID: Item<u64> = Item::new("next_schedule_id");
+
+/// ## Description
+/// Stores active schedules by their identifiers
+pub const SCHEDULES: Map<U64Key, Schedule> = Map::new("schedules");
+
+/// ## Description
+/// Stores the identifiers of active schedules by their owners
+pub const OWNER_SCHEDULES: Map<(&Addr, U64Key), ()> = Map::new("owner_schedules");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,326 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, Env, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use astroport::asset::{Asset, AssetInfo};
+use astroport::dca::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduleResponse,
+    SchedulesResponse,
+};
+use astroport::router::{Cw20HookMsg as RouterCw20HookMsg, SwapOperation};
+
+type DcaDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+const DAY: u64 = 86400;
+
+/// The minimum amount of uusd bought per ASTRO, in percent
+const MIN_PRICE: u64 = 150;
+
+fn token_asset(contract_addr: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked(contract_addr),
+        },
+        amount: Uint128::from(amount),
+    }
+}
+
+fn astro_ust_operations() -> Vec<SwapOperation> {
+    vec![SwapOperation::AstroSwap {
+        offer_asset_info: token_asset("astro", 0).info,
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: String::from("uusd"),
+        },
+    }]
+}
+
+fn env_at(offset: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(offset);
+    env
+}
+
+fn setup() -> DcaDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            router: String::from("router"),
+            tips: vec![token_asset("astro", 5)],
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn create_schedule(
+    deps: &mut DcaDeps,
+    token: &str,
+    amount: u128,
+    amount_per_interval: u128,
+    interval: u64,
+    max_slippage: Decimal,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::CreateSchedule {
+                operations: astro_ust_operations(),
+                amount_per_interval: Uint128::from(amount_per_interval),
+                interval,
+                max_slippage,
+                min_price: Decimal::percent(MIN_PRICE),
+            })
+            .unwrap(),
+        }),
+    )
+}
+
+fn purchase(deps: &mut DcaDeps, env: Env, schedule_id: u64) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("bot", &[]),
+        ExecuteMsg::Purchase { schedule_id },
+    )
+}
+
+fn swap_msg(amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("astro"),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: String::from("router"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                operations: astro_ust_operations(),
+                minimum_receive: Some(Uint128::from(amount * MIN_PRICE as u128 / 100)),
+                to: Some(String::from("user")),
+                max_spread: Some(Decimal::percent(1)),
+                referral_code: None,
+            })
+            .unwrap(),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("astro"),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: String::from(recipient),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn query_schedules(deps: &DcaDeps) -> SchedulesResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Schedules {
+                owner: String::from("user"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn create_schedule_validation() {
+    let mut deps = setup();
+    let max_slippage = Decimal::percent(1);
+
+    // The first operation must offer the sent token
+    let err = create_schedule(&mut deps, "xtoken", 1000, 100, DAY, max_slippage).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidOperations(String::from(
+            "the first operation must offer the deposited asset"
+        ))
+    );
+
+    let err = create_schedule(&mut deps, "astro", 1000, 0, DAY, max_slippage).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidSchedule(String::from(
+            "the amount per interval must be greater than zero"
+        ))
+    );
+
+    let err = create_schedule(&mut deps, "astro", 1000, 100, 0, max_slippage).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidSchedule(String::from("the interval must be greater than zero"))
+    );
+
+    let err =
+        create_schedule(&mut deps, "astro", 1000, 100, DAY, Decimal::percent(51)).unwrap_err();
+    assert_eq!(err, ContractError::MaxSlippageTooHigh {});
+
+    let err = create_schedule(&mut deps, "astro", 5, 100, DAY, max_slippage).unwrap_err();
+    assert_eq!(err, ContractError::DepositTooLow {});
+
+    // Purchases without a minimum price could be sandwiched
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::CreateSchedule {
+                operations: astro_ust_operations(),
+                amount_per_interval: Uint128::from(100u128),
+                interval: DAY,
+                max_slippage,
+                min_price: Decimal::zero(),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidSchedule(String::from("the minimum price must be greater than zero"))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: None,
+            tips: Some(vec![token_asset("xtoken", 1)]),
+        },
+    )
+    .unwrap();
+    let err = create_schedule(&mut deps, "astro", 1000, 100, DAY, max_slippage).unwrap_err();
+    assert_eq!(err, ContractError::TipNotSet(String::from("astro")));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: None,
+            tips: Some(vec![token_asset("astro", 1), token_asset("astro", 2)]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DuplicateTips {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: Some(String::from("router2")),
+            tips: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.tips, vec![token_asset("xtoken", 1)]);
+}
+
+#[test]
+fn purchases() {
+    let mut deps = setup();
+
+    create_schedule(&mut deps, "astro", 213, 100, DAY, Decimal::percent(1)).unwrap();
+
+    let res = purchase(&mut deps, mock_env(), 1).unwrap();
+    assert_eq!(res.messages[0].msg, swap_msg(100));
+    assert_eq!(res.messages[1].msg, transfer_msg("bot", 5));
+
+    let err = purchase(&mut deps, env_at(DAY - 1), 1).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PurchaseNotDue(mock_env().block.time.seconds() + DAY)
+    );
+
+    let schedule: ScheduleResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Schedule { schedule_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(schedule.balance, token_asset("astro", 108));
+    assert_eq!(schedule.purchases, 1);
+
+    // The remaining 3 tokens can't pay for another tip, so they are refunded
+    let res = purchase(&mut deps, env_at(DAY), 1).unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(res.messages[0].msg, swap_msg(100));
+    assert_eq!(res.messages[1].msg, transfer_msg("bot", 5));
+    assert_eq!(res.messages[2].msg, transfer_msg("user", 3));
+
+    assert_eq!(query_schedules(&deps).schedules, vec![]);
+    let err = purchase(&mut deps, env_at(2 * DAY), 1).unwrap_err();
+    assert_eq!(err, ContractError::ScheduleNotFound(1));
+}
+
+#[test]
+fn cancel_schedule() {
+    let mut deps = setup();
+
+    create_schedule(&mut deps, "astro", 1000, 100, DAY, Decimal::percent(1)).unwrap();
+    create_schedule(&mut deps, "astro", 500, 100, DAY, Decimal::percent(1)).unwrap();
+    purchase(&mut deps, mock_env(), 1).unwrap();
+
+    let schedules = query_schedules(&deps).schedules;
+    assert_eq!(schedules.len(), 2);
+    assert_eq!(schedules[0].balance, token_asset("astro", 895));
+    assert_eq!(schedules[1].balance, token_asset("astro", 500));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bot", &[]),
+        ExecuteMsg::CancelSchedule { schedule_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::CancelSchedule { schedule_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, transfer_msg("user", 895));
+
+    let schedules = query_schedules(&deps).schedules;
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules[0].schedule_id, 2);
+}
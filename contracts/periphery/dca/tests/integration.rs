@@ -0,0 +1,201 @@
+use astroport::asset::{token_asset, token_asset_info, AssetInfo};
+use astroport::dca::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduleResponse};
+use astroport::factory::PairType;
+use astroport::pair::Cw20HookMsg as PairCw20HookMsg;
+use astroport::router::{InstantiateMsg as RouterInstantiateMsg, SwapOperation};
+use astroport_test::contracts::store_router_code;
+use astroport_test::{Protocol, ProtocolBuilder};
+use cosmwasm_std::{to_binary, Addr, Decimal, Uint128};
+use cw20::Cw20ExecuteMsg;
+use terra_multi_test::{ContractWrapper, Executor, TerraApp};
+
+const DAY: u64 = 86400;
+
+fn store_dca_code(app: &mut TerraApp) -> u64 {
+    let dca_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_dca::contract::execute,
+        astroport_dca::contract::instantiate,
+        astroport_dca::contract::query,
+    ));
+
+    app.store_code(dca_contract)
+}
+
+/// Swaps CW20 tokens in a pair without any price protection.
+fn swap(protocol: &mut Protocol, sender: &Addr, token: &Addr, pair: &Addr, amount: u128) {
+    protocol
+        .app
+        .execute_contract(
+            sender.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(50)),
+                    to: None,
+                    referral_code: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn sandwiched_purchase_fails() {
+    let mut protocol = ProtocolBuilder::new().build();
+    let owner = protocol.owner.clone();
+    let user = Addr::unchecked("user");
+    let attacker = Addr::unchecked("attacker");
+
+    let astro = protocol.astro_token.clone();
+    let usdc = protocol.create_token("USDC");
+    let pair = protocol.create_pair(
+        PairType::Xyk {},
+        [
+            token_asset_info(usdc.clone()),
+            token_asset_info(astro.clone()),
+        ],
+        None,
+    );
+
+    // The pool holds 1M USDC and 1M ASTRO
+    protocol.mint_tokens(&usdc, &owner, 1_000_000_000000);
+    protocol.mint_tokens(&astro, &owner, 1_000_000_000000);
+    protocol
+        .provide_liquidity(
+            &owner,
+            &pair.contract_addr,
+            [
+                token_asset(usdc.clone(), Uint128::from(1_000_000_000000u128)),
+                token_asset(astro.clone(), Uint128::from(1_000_000_000000u128)),
+            ],
+        )
+        .unwrap();
+
+    let router_code_id = store_router_code(&mut protocol.app);
+    let router = protocol
+        .app
+        .instantiate_contract(
+            router_code_id,
+            owner.clone(),
+            &RouterInstantiateMsg {
+                astroport_factory: protocol.factory.to_string(),
+            },
+            &[],
+            "Router",
+            None,
+        )
+        .unwrap();
+
+    let dca_code_id = store_dca_code(&mut protocol.app);
+    let dca = protocol
+        .app
+        .instantiate_contract(
+            dca_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                owner: owner.to_string(),
+                router: router.to_string(),
+                tips: vec![token_asset(usdc.clone(), Uint128::from(1_000000u128))],
+            },
+            &[],
+            "DCA",
+            None,
+        )
+        .unwrap();
+
+    // The user buys ASTRO for 100 USDC a day and wants at least 0.99 ASTRO per USDC
+    protocol.mint_tokens(&usdc, &user, 1_000_000000);
+    protocol
+        .app
+        .execute_contract(
+            user.clone(),
+            usdc.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: dca.to_string(),
+                amount: Uint128::from(1_000_000000u128),
+                msg: to_binary(&Cw20HookMsg::CreateSchedule {
+                    operations: vec![SwapOperation::AstroSwap {
+                        offer_asset_info: token_asset_info(usdc.clone()),
+                        ask_asset_info: token_asset_info(astro.clone()),
+                    }],
+                    amount_per_interval: Uint128::from(100_000000u128),
+                    interval: DAY,
+                    max_slippage: Decimal::percent(1),
+                    min_price: Decimal::percent(99),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The attacker pushes the ASTRO price up right before the purchase. The purchase itself stays
+    // within the maximum slippage of the moved pool, but returns less than the minimum price
+    protocol.mint_tokens(&usdc, &attacker, 100_000_000000);
+    swap(
+        &mut protocol,
+        &attacker,
+        &usdc,
+        &pair.contract_addr,
+        100_000_000000,
+    );
+
+    let err = protocol
+        .app
+        .execute_contract(
+            attacker.clone(),
+            dca.clone(),
+            &ExecuteMsg::Purchase { schedule_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Assertion failed; minimum receive amount: 99000000"));
+    assert_eq!(protocol.token_balance(&astro, &user), 0);
+
+    // The attacker sells the ASTRO back, so the purchase succeeds at the restored price
+    let attacker_astro = protocol.token_balance(&astro, &attacker);
+    swap(
+        &mut protocol,
+        &attacker,
+        &astro,
+        &pair.contract_addr,
+        attacker_astro,
+    );
+
+    protocol
+        .app
+        .execute_contract(
+            Addr::unchecked("bot"),
+            dca.clone(),
+            &ExecuteMsg::Purchase { schedule_id: 1 },
+            &[],
+        )
+        .unwrap();
+    assert!(protocol.token_balance(&astro, &user) >= 99_000000);
+    assert_eq!(
+        protocol.token_balance(&usdc, &Addr::unchecked("bot")),
+        1_000000
+    );
+
+    let schedule: ScheduleResponse = protocol
+        .app
+        .wrap()
+        .query_wasm_smart(&dca, &QueryMsg::Schedule { schedule_id: 1 })
+        .unwrap();
+    assert_eq!(schedule.purchases, 1);
+    assert_eq!(
+        schedule.balance.info,
+        AssetInfo::Token {
+            contract_addr: usdc
+        }
+    );
+    assert_eq!(schedule.balance.amount, Uint128::from(899_000000u128));
+}
@@ -0,0 +1,394 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::emergency_admin::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use astroport::factory::{ExecuteMsg as FactoryExecuteMsg, PairType};
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::maker::ExecuteMsg as MakerExecuteMsg;
+use astroport::pair::ExecuteMsg as PairExecuteMsg;
+use astroport::querier::query_factory_config;
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-emergency-admin";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut guardians: Vec<Addr> = vec![];
+    for guardian in msg.guardians {
+        let guardian = addr_validate_to_lower(deps.api, &guardian)?;
+        if !guardians.contains(&guardian) {
+            guardians.push(guardian);
+        }
+    }
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            guardians,
+            factory: addr_validate_to_lower(deps.api, &msg.factory)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::PausePairs { pairs }** Pauses swaps and liquidity provision in the specified pairs.
+///
+/// * **ExecuteMsg::DisablePairType { pair_type }** Disables a pair type in the factory.
+///
+/// * **ExecuteMsg::DeactivatePool { lp_token }** Sets the generator allocation point of a pool to zero.
+///
+/// * **ExecuteMsg::PauseMaker {}** Pauses fee collection in the Maker.
+///
+/// * **ExecuteMsg::UpdateGuardians { add, remove }** Adds or removes guardians.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::PausePairs { pairs } => pause_pairs(deps, info, pairs),
+        ExecuteMsg::DisablePairType { pair_type } => disable_pair_type(deps, info, pair_type),
+        ExecuteMsg::DeactivatePool { lp_token } => deactivate_pool(deps, info, lp_token),
+        ExecuteMsg::PauseMaker {} => pause_maker(deps, info),
+        ExecuteMsg::UpdateGuardians { add, remove } => update_guardians(deps, info, add, remove),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Loads the config and makes sure the sender is a guardian. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **sender** is an object of type [`Addr`]. This is the address that triggered the action.
+fn load_config_for_guardian(deps: Deps, sender: &Addr) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_guardian(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(config)
+}
+
+/// ## Description
+/// Builds a message that executes `msg` on `contract_addr` without sending any funds.
+/// ## Params
+/// * **contract_addr** is an object of type [`Addr`]. This is the contract to call.
+///
+/// * **msg** is a reference to an object implementing [`serde::Serialize`]. This is the message to send.
+fn build_execute_msg<T: serde::Serialize>(contract_addr: &Addr, msg: &T) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }))
+}
+
+/// ## Description
+/// Pauses swaps and liquidity provision in the specified pairs. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pairs** is a vector of type [`String`]. These are the pairs to pause.
+///
+/// ## Executor
+/// Only a guardian can execute this.
+fn pause_pairs(
+    deps: DepsMut,
+    info: MessageInfo,
+    pairs: Vec<String>,
+) -> Result<Response, ContractError> {
+    load_config_for_guardian(deps.as_ref(), &info.sender)?;
+
+    if pairs.is_empty() {
+        return Err(ContractError::NoPairs {});
+    }
+
+    let messages = pairs
+        .iter()
+        .map(|pair| {
+            let pair = addr_validate_to_lower(deps.api, pair)?;
+            build_execute_msg(&pair, &PairExecuteMsg::Pause {})
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "pause_pairs"),
+        attr("guardian", info.sender),
+        attr("pairs", pairs.join(",")),
+    ]))
+}
+
+/// ## Description
+/// Disables the creation of new pairs of the specified type in the factory.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair_type** is an object of type [`PairType`]. This is the pair type to disable.
+///
+/// ## Executor
+/// Only a guardian can execute this.
+fn disable_pair_type(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_type: PairType,
+) -> Result<Response, ContractError> {
+    let config = load_config_for_guardian(deps.as_ref(), &info.sender)?;
+
+    Ok(Response::new()
+        .add_message(build_execute_msg(
+            &config.factory,
+            &FactoryExecuteMsg::DisablePairType {
+                pair_type: pair_type.clone(),
+            },
+        )?)
+        .add_attributes(vec![
+            attr("action", "disable_pair_type"),
+            attr("guardian", info.sender),
+            attr("pair_type", pair_type.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Sets the allocation point of the specified pool to zero in the generator set in the factory.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool to deactivate.
+///
+/// ## Executor
+/// Only a guardian can execute this.
+fn deactivate_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let config = load_config_for_guardian(deps.as_ref(), &info.sender)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let generator = query_factory_config(&deps.querier, config.factory)?
+        .generator_address
+        .ok_or(ContractError::GeneratorNotSet {})?;
+
+    Ok(Response::new()
+        .add_message(build_execute_msg(
+            &generator,
+            &GeneratorExecuteMsg::DeactivatePool {
+                lp_token: lp_token.to_string(),
+            },
+        )?)
+        .add_attributes(vec![
+            attr("action", "deactivate_pool"),
+            attr("guardian", info.sender),
+            attr("lp_token", lp_token),
+        ]))
+}
+
+/// ## Description
+/// Pauses fee collection in the Maker set in the factory. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// Only a guardian can execute this.
+fn pause_maker(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = load_config_for_guardian(deps.as_ref(), &info.sender)?;
+
+    let maker = query_factory_config(&deps.querier, config.factory)?
+        .fee_address
+        .ok_or(ContractError::MakerNotSet {})?;
+
+    Ok(Response::new()
+        .add_message(build_execute_msg(&maker, &MakerExecuteMsg::Pause {})?)
+        .add_attributes(vec![
+            attr("action", "pause_maker"),
+            attr("guardian", info.sender),
+        ]))
+}
+
+/// ## Description
+/// Adds or removes guardians. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **add** is an [`Option`] field of type [`Vec<String>`]. These are the guardians to add.
+///
+/// * **remove** is an [`Option`] field of type [`Vec<String>`]. These are the guardians to remove.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_guardians(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(remove) = remove {
+        for guardian in remove {
+            let guardian = addr_validate_to_lower(deps.api, &guardian)?;
+            config.guardians.retain(|addr| *addr != guardian);
+        }
+    }
+
+    if let Some(add) = add {
+        for guardian in add {
+            let guardian = addr_validate_to_lower(deps.api, &guardian)?;
+            if !config.is_guardian(&guardian) {
+                config.guardians.push(guardian);
+            }
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_guardians"))
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                guardians: config.guardians,
+                factory: config.factory,
+            })
+        }
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
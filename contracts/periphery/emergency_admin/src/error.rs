@@ -0,0 +1,22 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes emergency admin contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("At least one pair must be specified")]
+    NoPairs {},
+
+    #[error("The factory has no generator set")]
+    GeneratorNotSet {},
+
+    #[error("The factory has no Maker set")]
+    MakerNotSet {},
+}
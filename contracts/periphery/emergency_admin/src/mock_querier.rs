@@ -0,0 +1,87 @@
+use astroport::factory::{ConfigResponse as FactoryConfigResponse, QueryMsg as FactoryQueryMsg};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, WasmQuery,
+};
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    generator: Option<Addr>,
+    maker: Option<Addr>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "factory" =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Config {} => SystemResult::Ok(
+                        to_binary(&FactoryConfigResponse {
+                            owner: Addr::unchecked("owner"),
+                            pair_configs: vec![],
+                            token_code_id: 0,
+                            fee_address: self.maker.clone(),
+                            generator_address: self.generator.clone(),
+                            whitelist_code_id: 0,
+                            referral_registry: None,
+                            emergency_admin: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                            pairs_count: vec![],
+                        })
+                        .into(),
+                    ),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            generator: None,
+            maker: None,
+        }
+    }
+
+    pub fn set_generator(&mut self, generator: &str) {
+        self.generator = Some(Addr::unchecked(generator));
+    }
+
+    pub fn set_maker(&mut self, maker: &str) {
+        self.maker = Some(Addr::unchecked(maker));
+    }
+}
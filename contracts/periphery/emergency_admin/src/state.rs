@@ -0,0 +1,32 @@
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the emergency admin contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change the guardian set (the Assembly)
+    pub owner: Addr,
+    /// Addresses that are allowed to trigger emergency actions
+    pub guardians: Vec<Addr>,
+    /// The factory contract address
+    pub factory: Addr,
+}
+
+impl Config {
+    /// Returns true if the address is a registered guardian.
+    pub fn is_guardian(&self, addr: &Addr) -> bool {
+        self.guardians.contains(addr)
+    }
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,217 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, OwnedDeps, Response, WasmMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::emergency_admin::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use astroport::factory::{ExecuteMsg as FactoryExecuteMsg, PairType};
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::maker::ExecuteMsg as MakerExecuteMsg;
+use astroport::pair::ExecuteMsg as PairExecuteMsg;
+
+type EmergencyAdminDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn setup() -> EmergencyAdminDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: String::from("assembly"),
+            guardians: vec![String::from("guardian"), String::from("guardian")],
+            factory: String::from("factory"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn wasm_execute_msgs(res: Response) -> Vec<(String, CosmosMsg)> {
+    res.messages
+        .into_iter()
+        .map(|msg| match msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                ref contract_addr,
+                ref funds,
+                ..
+            }) => {
+                // Emergency actions never move funds
+                assert!(funds.is_empty());
+                (contract_addr.clone(), msg.msg)
+            }
+            _ => panic!("Unexpected message"),
+        })
+        .collect()
+}
+
+fn execute_msg(contract_addr: &str, msg: &impl serde::Serialize) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn emergency_actions() {
+    let mut deps = setup();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.guardians, vec![Addr::unchecked("guardian")]);
+
+    let pause_pairs = ExecuteMsg::PausePairs {
+        pairs: vec![String::from("pair0000"), String::from("pair0001")],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("assembly", &[]),
+        pause_pairs.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        pause_pairs,
+    )
+    .unwrap();
+    assert_eq!(
+        wasm_execute_msgs(res),
+        vec![
+            (
+                String::from("pair0000"),
+                execute_msg("pair0000", &PairExecuteMsg::Pause {})
+            ),
+            (
+                String::from("pair0001"),
+                execute_msg("pair0001", &PairExecuteMsg::Pause {})
+            ),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::PausePairs { pairs: vec![] },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoPairs {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::DisablePairType {
+            pair_type: PairType::Xyk {},
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        execute_msg(
+            "factory",
+            &FactoryExecuteMsg::DisablePairType {
+                pair_type: PairType::Xyk {},
+            }
+        )
+    );
+
+    // The generator and the Maker are resolved through the factory
+    let deactivate_pool = ExecuteMsg::DeactivatePool {
+        lp_token: String::from("lp_token"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        deactivate_pool.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::GeneratorNotSet {});
+
+    deps.querier.set_generator("generator");
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        deactivate_pool,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        execute_msg(
+            "generator",
+            &GeneratorExecuteMsg::DeactivatePool {
+                lp_token: String::from("lp_token"),
+            }
+        )
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::PauseMaker {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MakerNotSet {});
+
+    deps.querier.set_maker("maker");
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::PauseMaker {},
+    )
+    .unwrap();
+    assert_eq!(
+        wasm_execute_msgs(res),
+        vec![(
+            String::from("maker"),
+            execute_msg("maker", &MakerExecuteMsg::Pause {})
+        )]
+    );
+}
+
+#[test]
+fn update_guardians() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::UpdateGuardians {
+        add: Some(vec![String::from("new_guardian")]),
+        remove: Some(vec![String::from("guardian")]),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), mock_env(), mock_info("assembly", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.guardians, vec![Addr::unchecked("new_guardian")]);
+
+    // Removed guardians lose their powers right away
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::PauseMaker {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
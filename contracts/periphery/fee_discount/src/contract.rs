@@ -0,0 +1,307 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::constants::BPS_DENOMINATOR;
+use astroport::factory::UpdateAddr;
+use astroport::fee_discount::{
+    ConfigResponse, ExecuteMsg, FeeDiscountResponse, FeeDiscountTier, InstantiateMsg, MigrateMsg,
+    QueryMsg, MAX_TIERS,
+};
+use astroport::querier::query_token_balance;
+use astroport::voting_escrow::{VotingEscrowQueryMsg, VotingPowerResponse};
+use cosmwasm_std::{
+    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw2::set_contract_version;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-fee-discount";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        xastro_token: addr_validate_to_lower(deps.api, &msg.xastro_token)?,
+        voting_escrow: msg
+            .voting_escrow
+            .map(|addr| addr_validate_to_lower(deps.api, &addr))
+            .transpose()?,
+        tiers: msg.tiers,
+    };
+    validate_tiers(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::UpdateConfig { voting_escrow, tiers }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            voting_escrow,
+            tiers,
+        } => update_config(deps, info, voting_escrow, tiers),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **voting_escrow** is an [`Option`] field of type [`UpdateAddr`]. Sets or removes the voting escrow contract.
+///
+/// * **tiers** is an [`Option`] field of type [`Vec<FeeDiscountTier>`]. These are the new discount tiers.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    voting_escrow: Option<UpdateAddr>,
+    tiers: Option<Vec<FeeDiscountTier>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(voting_escrow) = voting_escrow {
+        config.voting_escrow = match voting_escrow {
+            UpdateAddr::Set(addr) => Some(addr_validate_to_lower(deps.api, &addr)?),
+            UpdateAddr::Remove {} => None,
+        };
+    }
+
+    if let Some(tiers) = tiers {
+        config.tiers = tiers;
+    }
+
+    validate_tiers(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Checks that there are at most [`MAX_TIERS`] tiers, that every tier sets at least one threshold and
+/// doesn't waive more than the whole commission, and that vxASTRO thresholds have a voting escrow to query.
+/// ## Params
+/// * **config** is an object of type [`Config`]. This is the configuration to check.
+fn validate_tiers(config: &Config) -> Result<(), ContractError> {
+    if config.tiers.len() > MAX_TIERS {
+        return Err(ContractError::TooManyTiers(MAX_TIERS));
+    }
+
+    for tier in &config.tiers {
+        if tier.min_xastro.is_none() && tier.min_vxastro.is_none() {
+            return Err(ContractError::TierWithoutThreshold {});
+        }
+        if tier.discount_bps > BPS_DENOMINATOR {
+            return Err(ContractError::DiscountTooHigh(BPS_DENOMINATOR));
+        }
+        if tier.min_vxastro.is_some() && config.voting_escrow.is_none() {
+            return Err(ContractError::VotingEscrowNotSet {});
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::FeeDiscount { trader }** Returns the discount a trader is entitled to using a
+/// [`FeeDiscountResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                xastro_token: config.xastro_token,
+                voting_escrow: config.voting_escrow,
+                tiers: config.tiers,
+            })
+        }
+        QueryMsg::FeeDiscount { trader } => to_binary(&query_fee_discount(deps, trader)?),
+    }
+}
+
+/// ## Description
+/// Returns the discount of the best tier a trader qualifies for in a [`FeeDiscountResponse`] object.
+/// A trader qualifies for a tier if their xASTRO balance or their vxASTRO voting power reaches
+/// the respective threshold of the tier.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **trader** is an object of type [`String`]. This is the trader to return the discount for.
+fn query_fee_discount(deps: Deps, trader: String) -> StdResult<FeeDiscountResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let trader = addr_validate_to_lower(deps.api, &trader)?;
+
+    let xastro_balance = if config.tiers.iter().any(|tier| tier.min_xastro.is_some()) {
+        query_token_balance(&deps.querier, config.xastro_token, trader.clone())?
+    } else {
+        Uint128::zero()
+    };
+    let voting_power = match &config.voting_escrow {
+        Some(voting_escrow) if config.tiers.iter().any(|tier| tier.min_vxastro.is_some()) => {
+            query_voting_power(deps, voting_escrow, &trader)?
+        }
+        _ => Uint128::zero(),
+    };
+
+    let discount_bps = config
+        .tiers
+        .iter()
+        .filter(|tier| {
+            tier.min_xastro
+                .map(|min_xastro| xastro_balance >= min_xastro)
+                .unwrap_or(false)
+                || tier
+                    .min_vxastro
+                    .map(|min_vxastro| voting_power >= min_vxastro)
+                    .unwrap_or(false)
+        })
+        .map(|tier| tier.discount_bps)
+        .max()
+        .unwrap_or_default();
+
+    Ok(FeeDiscountResponse { discount_bps })
+}
+
+/// ## Description
+/// Returns the current vxASTRO voting power of a user.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **voting_escrow** is an object of type [`Addr`]. This is the voting escrow contract address.
+///
+/// * **user** is an object of type [`Addr`]. This is the user to return the voting power for.
+fn query_voting_power(deps: Deps, voting_escrow: &Addr, user: &Addr) -> StdResult<Uint128> {
+    let res: VotingPowerResponse = deps.querier.query_wasm_smart(
+        voting_escrow,
+        &VotingEscrowQueryMsg::UserVotingPower {
+            user: user.to_string(),
+        },
+    )?;
+
+    Ok(res.voting_power)
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
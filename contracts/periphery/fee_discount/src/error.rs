@@ -0,0 +1,25 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes fee discount contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("At most {0} discount tiers can be set")]
+    TooManyTiers(usize),
+
+    #[error("Every discount tier must set a minimum xASTRO balance or vxASTRO voting power")]
+    TierWithoutThreshold {},
+
+    #[error("A tier can't waive more than {0} bps of the commission")]
+    DiscountTooHigh(u16),
+
+    #[error("A vxASTRO tier is set, but there is no voting escrow contract")]
+    VotingEscrowNotSet {},
+}
@@ -0,0 +1,104 @@
+use astroport::voting_escrow::{VotingEscrowQueryMsg, VotingPowerResponse};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    voting_escrow: String,
+    // xASTRO balances keyed by the holder address
+    xastro_balances: HashMap<String, Uint128>,
+    // vxASTRO voting power keyed by the holder address
+    voting_power: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if *contract_addr == self.voting_escrow =>
+            {
+                match from_binary(msg).unwrap() {
+                    VotingEscrowQueryMsg::UserVotingPower { user } => {
+                        let voting_power =
+                            self.voting_power.get(&user).copied().unwrap_or_default();
+                        SystemResult::Ok(to_binary(&VotingPowerResponse { voting_power }).into())
+                    }
+                    VotingEscrowQueryMsg::TotalVotingPower {} => {
+                        let voting_power = self.voting_power.values().sum();
+                        SystemResult::Ok(to_binary(&VotingPowerResponse { voting_power }).into())
+                    }
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => match from_binary(msg).unwrap() {
+                Cw20QueryMsg::Balance { address } => {
+                    let balance = self
+                        .xastro_balances
+                        .get(&address)
+                        .copied()
+                        .unwrap_or_default();
+                    SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                }
+                _ => panic!("DO NOT ENTER HERE"),
+            },
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            voting_escrow: String::default(),
+            xastro_balances: HashMap::new(),
+            voting_power: HashMap::new(),
+        }
+    }
+
+    pub fn set_voting_escrow(&mut self, voting_escrow: &str) {
+        self.voting_escrow = voting_escrow.to_string();
+    }
+
+    pub fn set_xastro_balance(&mut self, holder: &str, balance: u128) {
+        self.xastro_balances
+            .insert(holder.to_string(), Uint128::from(balance));
+    }
+
+    pub fn set_voting_power(&mut self, holder: &str, voting_power: u128) {
+        self.voting_power
+            .insert(holder.to_string(), Uint128::from(voting_power));
+    }
+}
@@ -0,0 +1,28 @@
+use astroport::common::OwnershipProposal;
+use astroport::fee_discount::FeeDiscountTier;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the fee discount contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The xASTRO token contract address
+    pub xastro_token: Addr,
+    /// The voting escrow (vxASTRO) contract address
+    pub voting_escrow: Option<Addr>,
+    /// The discount tiers
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,172 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, OwnedDeps, Uint128};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::factory::UpdateAddr;
+use astroport::fee_discount::{
+    ConfigResponse, ExecuteMsg, FeeDiscountResponse, FeeDiscountTier, InstantiateMsg, QueryMsg,
+};
+
+type FeeDiscountDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn tier(min_xastro: Option<u128>, min_vxastro: Option<u128>, discount_bps: u16) -> FeeDiscountTier {
+    FeeDiscountTier {
+        min_xastro: min_xastro.map(Uint128::from),
+        min_vxastro: min_vxastro.map(Uint128::from),
+        discount_bps,
+    }
+}
+
+fn setup() -> FeeDiscountDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_voting_escrow("voting_escrow");
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            xastro_token: String::from("xastro"),
+            voting_escrow: Some(String::from("voting_escrow")),
+            tiers: vec![
+                tier(Some(1_000), Some(500), 1_000),
+                tier(Some(10_000), None, 2_500),
+                tier(None, Some(5_000), 5_000),
+            ],
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn query_discount(deps: &FeeDiscountDeps, trader: &str) -> u16 {
+    let res: FeeDiscountResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeDiscount {
+                trader: trader.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    res.discount_bps
+}
+
+#[test]
+fn fee_discount() {
+    let mut deps = setup();
+
+    assert_eq!(query_discount(&deps, "trader"), 0);
+
+    // Either threshold qualifies a trader for a tier
+    deps.querier.set_xastro_balance("trader", 1_000);
+    assert_eq!(query_discount(&deps, "trader"), 1_000);
+    deps.querier.set_xastro_balance("trader", 0);
+    deps.querier.set_voting_power("trader", 500);
+    assert_eq!(query_discount(&deps, "trader"), 1_000);
+
+    // The best qualifying tier is used
+    deps.querier.set_xastro_balance("trader", 10_000);
+    assert_eq!(query_discount(&deps, "trader"), 2_500);
+    deps.querier.set_voting_power("trader", 5_000);
+    assert_eq!(query_discount(&deps, "trader"), 5_000);
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let update_msg = ExecuteMsg::UpdateConfig {
+        voting_escrow: None,
+        tiers: Some(vec![tier(Some(100), None, 500)]),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        update_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    for (tiers, expected_err) in [
+        (
+            vec![tier(Some(100), None, 500); 11],
+            ContractError::TooManyTiers(10),
+        ),
+        (
+            vec![tier(None, None, 500)],
+            ContractError::TierWithoutThreshold {},
+        ),
+        (
+            vec![tier(Some(100), None, 10_001)],
+            ContractError::DiscountTooHigh(10_000),
+        ),
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateConfig {
+                voting_escrow: None,
+                tiers: Some(tiers),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, expected_err);
+    }
+
+    // vxASTRO tiers need a voting escrow contract
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            voting_escrow: Some(UpdateAddr::Remove {}),
+            tiers: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::VotingEscrowNotSet {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_msg,
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            voting_escrow: Some(UpdateAddr::Remove {}),
+            tiers: None,
+        },
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            xastro_token: Addr::unchecked("xastro"),
+            voting_escrow: None,
+            tiers: vec![tier(Some(100), None, 500)],
+        }
+    );
+
+    deps.querier.set_xastro_balance("trader", 100);
+    deps.querier.set_voting_power("trader", 1_000_000);
+    assert_eq!(query_discount(&deps, "trader"), 500);
+}
@@ -0,0 +1,661 @@
+use crate::error::ContractError;
+use crate::state::{
+    Config, Order, CONFIG, NEXT_ORDER_ID, ORDERS, OWNERSHIP_PROPOSAL, OWNER_ORDERS,
+};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::constants::BPS_DENOMINATOR;
+use astroport::limit_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderResponse,
+    OrdersResponse, QueryMsg, MAX_KEEPER_FEE_BPS,
+};
+use astroport::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, QueryMsg as RouterQueryMsg,
+    SimulateSwapOperationsResponse, SwapOperation, MAX_SWAP_OPERATIONS,
+};
+use astroport::DecimalCheckedOps;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut,
+    Env, MessageInfo, Order as StorageOrder, Response, StdError, StdResult, Storage, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-limit-order";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading orders
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading orders
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.max_keeper_fee_bps > MAX_KEEPER_FEE_BPS {
+        return Err(ContractError::KeeperFeeTooHigh(MAX_KEEPER_FEE_BPS));
+    }
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            router: addr_validate_to_lower(deps.api, &msg.router)?,
+            max_keeper_fee_bps: msg.max_keeper_fee_bps,
+        },
+    )?;
+    NEXT_ORDER_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::CreateOrder {
+///             offer_asset,
+///             operations,
+///             price,
+///             keeper_fee_bps,
+///         }** Creates an order that offers native tokens.
+///
+/// * **ExecuteMsg::CancelOrder { order_id }** Cancels an order and refunds the offer asset.
+///
+/// * **ExecuteMsg::ExecuteOrder { order_id }** Executes an order if its limit price is reached.
+///
+/// * **ExecuteMsg::UpdateConfig { router, max_keeper_fee_bps }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateOrder {
+            offer_asset,
+            operations,
+            price,
+            keeper_fee_bps,
+        } => {
+            offer_asset.info.check(deps.api)?;
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+            offer_asset.assert_sent_native_token_balance(&info)?;
+
+            create_order(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                operations,
+                price,
+                keeper_fee_bps,
+            )
+        }
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+        ExecuteMsg::ExecuteOrder { order_id } => execute_order(deps, info, order_id),
+        ExecuteMsg::UpdateConfig {
+            router,
+            max_keeper_fee_bps,
+        } => update_config(deps, info, router, max_keeper_fee_bps),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then a [`ContractError`] is returned.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message that has to be processed.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateOrder {
+            operations,
+            price,
+            keeper_fee_bps,
+        } => {
+            let owner = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            create_order(
+                deps,
+                env,
+                owner,
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: info.sender,
+                    },
+                    amount: cw20_msg.amount,
+                },
+                operations,
+                price,
+                keeper_fee_bps,
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Creates a new order. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **owner** is an object of type [`Addr`]. This is the address that creates the order.
+///
+/// * **offer_asset** is an object of type [`Asset`]. This is the asset sold in the order.
+///
+/// * **operations** is a vector that contains objects of type [`SwapOperation`]. These are the swap
+/// operations used to execute the order.
+///
+/// * **price** is an object of type [`Decimal`]. This is the minimum amount of ask tokens received per offer token.
+///
+/// * **keeper_fee_bps** is an object of type [`u16`]. This is the part of the offer amount paid to the keeper.
+#[allow(clippy::too_many_arguments)]
+fn create_order(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    offer_asset: Asset,
+    operations: Vec<SwapOperation>,
+    price: Decimal,
+    keeper_fee_bps: u16,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let ask_asset_info = validate_operations(&offer_asset.info, &operations)?;
+
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    if keeper_fee_bps > config.max_keeper_fee_bps {
+        return Err(ContractError::KeeperFeeTooHigh(config.max_keeper_fee_bps));
+    }
+
+    let keeper_fee = offer_asset
+        .amount
+        .multiply_ratio(keeper_fee_bps, BPS_DENOMINATOR);
+    let minimum_receive = price.checked_mul_ceil(offer_asset.amount.checked_sub(keeper_fee)?)?;
+    if minimum_receive.is_zero() {
+        return Err(ContractError::OrderAmountTooLow {});
+    }
+
+    let order_id = NEXT_ORDER_ID.load(deps.storage)?;
+    NEXT_ORDER_ID.save(deps.storage, &(order_id + 1))?;
+
+    let order = Order {
+        order_id,
+        owner,
+        offer_asset,
+        ask_asset_info,
+        operations,
+        price,
+        minimum_receive,
+        keeper_fee,
+        created_at: env.block.time.seconds(),
+    };
+    ORDERS.save(deps.storage, U64Key::new(order_id), &order)?;
+    OWNER_ORDERS.save(deps.storage, (&order.owner, U64Key::new(order_id)), &())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "create_order"),
+        attr("order_id", order_id.to_string()),
+        attr("owner", order.owner),
+        attr("offer_asset", order.offer_asset.to_string()),
+        attr("ask_asset", order.ask_asset_info.to_string()),
+        attr("minimum_receive", minimum_receive),
+    ]))
+}
+
+/// ## Description
+/// Cancels an order and refunds the offer asset to the order owner. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **order_id** is an object of type [`u64`]. This is the order to cancel.
+///
+/// ## Executor
+/// Only the order owner can execute this.
+fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = load_order(deps.as_ref(), order_id)?;
+
+    // Permission check
+    if info.sender != order.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_order(deps.storage, &order);
+
+    Ok(Response::new()
+        .add_message(
+            order
+                .offer_asset
+                .clone()
+                .into_msg(&deps.querier, order.owner.clone())?,
+        )
+        .add_attributes(vec![
+            attr("action", "cancel_order"),
+            attr("order_id", order_id.to_string()),
+            attr("owner", order.owner),
+        ]))
+}
+
+/// ## Description
+/// Executes an order through the router if the simulated return satisfies the order price.
+/// The ask tokens are sent to the order owner and the keeper fee is sent to the sender.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **order_id** is an object of type [`u64`]. This is the order to execute.
+fn execute_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps.as_ref(), order_id)?;
+
+    let swap_asset = Asset {
+        info: order.offer_asset.info.clone(),
+        amount: order.offer_asset.amount.checked_sub(order.keeper_fee)?,
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let swap_amount = match &swap_asset.info {
+        AssetInfo::NativeToken { .. } => {
+            let coin = swap_asset.deduct_tax(&deps.querier)?;
+            let swap_amount = coin.amount;
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.router.to_string(),
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                    operations: order.operations.clone(),
+                    minimum_receive: Some(order.minimum_receive),
+                    to: Some(order.owner.clone()),
+                    max_spread: None,
+                    referral_code: None,
+                })?,
+                funds: vec![coin],
+            }));
+            swap_amount
+        }
+        AssetInfo::Token { contract_addr } => {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: config.router.to_string(),
+                    amount: swap_asset.amount,
+                    msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                        operations: order.operations.clone(),
+                        minimum_receive: Some(order.minimum_receive),
+                        to: Some(order.owner.to_string()),
+                        max_spread: None,
+                        referral_code: None,
+                    })?,
+                })?,
+                funds: vec![],
+            }));
+            swap_asset.amount
+        }
+    };
+
+    let simulation: SimulateSwapOperationsResponse = deps.querier.query_wasm_smart(
+        &config.router,
+        &RouterQueryMsg::SimulateSwapOperations {
+            offer_amount: swap_amount,
+            operations: order.operations.clone(),
+        },
+    )?;
+    if simulation.amount < order.minimum_receive {
+        return Err(ContractError::LimitNotReached {
+            return_amount: simulation.amount.to_string(),
+            minimum_receive: order.minimum_receive.to_string(),
+        });
+    }
+
+    if !order.keeper_fee.is_zero() {
+        messages.push(
+            Asset {
+                info: order.offer_asset.info.clone(),
+                amount: order.keeper_fee,
+            }
+            .into_msg(&deps.querier, info.sender.clone())?,
+        );
+    }
+
+    remove_order(deps.storage, &order);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "execute_order"),
+        attr("order_id", order_id.to_string()),
+        attr("keeper", info.sender),
+        attr("keeper_fee", order.keeper_fee),
+        attr("simulated_return", simulation.amount),
+    ]))
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **router** is an [`Option`] field of type [`String`]. This is the new router contract address.
+///
+/// * **max_keeper_fee_bps** is an [`Option`] field of type [`u16`]. This is the new maximum keeper fee.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    router: Option<String>,
+    max_keeper_fee_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(router) = router {
+        config.router = addr_validate_to_lower(deps.api, &router)?;
+    }
+
+    if let Some(max_keeper_fee_bps) = max_keeper_fee_bps {
+        if max_keeper_fee_bps > MAX_KEEPER_FEE_BPS {
+            return Err(ContractError::KeeperFeeTooHigh(MAX_KEEPER_FEE_BPS));
+        }
+        config.max_keeper_fee_bps = max_keeper_fee_bps;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Checks that the swap operations start with the offer asset and returns the asset they end with.
+/// ## Params
+/// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the asset sold in the order.
+///
+/// * **operations** is a slice that contains objects of type [`SwapOperation`].
+fn validate_operations(
+    offer_asset_info: &AssetInfo,
+    operations: &[SwapOperation],
+) -> Result<AssetInfo, ContractError> {
+    let (first, last) = match (operations.first(), operations.last()) {
+        (Some(first), Some(last)) if operations.len() <= MAX_SWAP_OPERATIONS => (first, last),
+        _ => {
+            return Err(ContractError::InvalidOperations(format!(
+                "an order must use 1 to {} operations",
+                MAX_SWAP_OPERATIONS
+            )))
+        }
+    };
+
+    if !first.get_offer_asset_info().equal(offer_asset_info) {
+        return Err(ContractError::InvalidOperations(String::from(
+            "the first operation must offer the offer asset",
+        )));
+    }
+
+    for pair in operations.windows(2) {
+        if !pair[0]
+            .get_target_asset_info()
+            .equal(&pair[1].get_offer_asset_info())
+        {
+            return Err(ContractError::InvalidOperations(String::from(
+                "every operation must offer the asset returned by the previous one",
+            )));
+        }
+    }
+
+    let ask_asset_info = last.get_target_asset_info();
+    if ask_asset_info.equal(offer_asset_info) {
+        return Err(ContractError::InvalidOperations(String::from(
+            "the ask asset must differ from the offer asset",
+        )));
+    }
+
+    Ok(ask_asset_info)
+}
+
+/// ## Description
+/// Loads an order. Returns a [`ContractError`] if the order doesn't exist.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **order_id** is an object of type [`u64`].
+fn load_order(deps: Deps, order_id: u64) -> Result<Order, ContractError> {
+    ORDERS
+        .may_load(deps.storage, U64Key::new(order_id))?
+        .ok_or(ContractError::OrderNotFound(order_id))
+}
+
+/// ## Description
+/// Removes an order together with its owner index entry.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **order** is an object of type [`Order`]. This is the order to remove.
+fn remove_order(storage: &mut dyn Storage, order: &Order) {
+    ORDERS.remove(storage, U64Key::new(order.order_id));
+    OWNER_ORDERS.remove(storage, (&order.owner, U64Key::new(order.order_id)));
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Order { order_id }** Returns an order using an [`OrderResponse`] object.
+///
+/// * **QueryMsg::Orders { owner, start_after, limit }** Returns open orders using an [`OrdersResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                router: config.router,
+                max_keeper_fee_bps: config.max_keeper_fee_bps,
+            })
+        }
+        QueryMsg::Order { order_id } => to_binary(&order_response(
+            ORDERS.load(deps.storage, U64Key::new(order_id))?,
+        )),
+        QueryMsg::Orders {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(deps, owner, start_after, limit)?),
+    }
+}
+
+/// ## Description
+/// Returns open orders ordered by their identifiers. If an owner is specified, only their orders are returned.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **owner** is an [`Option`] field of type [`String`]. This is the owner whose orders are returned.
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. This is the order identifier to start reading after.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of orders to return.
+fn query_orders(
+    deps: Deps,
+    owner: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    let orders = match owner {
+        Some(owner) => {
+            let owner = addr_validate_to_lower(deps.api, &owner)?;
+            OWNER_ORDERS
+                .prefix(&owner)
+                .range(deps.storage, start, None, StorageOrder::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (key, _) = item?;
+                    ORDERS.load(deps.storage, key.into())
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        None => ORDERS
+            .range(deps.storage, start, None, StorageOrder::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, order)| order))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    Ok(OrdersResponse {
+        orders: orders.into_iter().map(order_response).collect(),
+    })
+}
+
+/// ## Description
+/// Converts a stored order into an [`OrderResponse`] object.
+/// ## Params
+/// * **order** is an object of type [`Order`].
+fn order_response(order: Order) -> OrderResponse {
+    OrderResponse {
+        order_id: order.order_id,
+        owner: order.owner,
+        offer_asset: order.offer_asset,
+        ask_asset_info: order.ask_asset_info,
+        operations: order.operations,
+        price: order.price,
+        minimum_receive: order.minimum_receive,
+        keeper_fee: order.keeper_fee,
+        created_at: order.created_at,
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
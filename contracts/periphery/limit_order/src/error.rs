@@ -0,0 +1,40 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes limit order contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid swap operations: {0}")]
+    InvalidOperations(String),
+
+    #[error("The order price must be greater than zero")]
+    InvalidPrice {},
+
+    #[error("The keeper fee can't be higher than {0} bps")]
+    KeeperFeeTooHigh(u16),
+
+    #[error("The order amount is too low")]
+    OrderAmountTooLow {},
+
+    #[error("Order {0} not found")]
+    OrderNotFound(u64),
+
+    #[error("The limit price isn't reached: the swap returns {return_amount} while the order requires {minimum_receive}")]
+    LimitNotReached {
+        return_amount: String,
+        minimum_receive: String,
+    },
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
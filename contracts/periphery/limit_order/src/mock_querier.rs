@@ -0,0 +1,80 @@
+use astroport::router::{QueryMsg as RouterQueryMsg, SimulateSwapOperationsResponse};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    router: String,
+    // the amount returned by every router swap simulation
+    simulated_return: Uint128,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if *contract_addr == self.router =>
+            {
+                match from_binary(msg).unwrap() {
+                    RouterQueryMsg::SimulateSwapOperations { .. } => SystemResult::Ok(
+                        to_binary(&SimulateSwapOperationsResponse {
+                            amount: self.simulated_return,
+                        })
+                        .into(),
+                    ),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            router: String::default(),
+            simulated_return: Uint128::zero(),
+        }
+    }
+
+    pub fn set_router(&mut self, router: &str) {
+        self.router = router.to_string();
+    }
+
+    pub fn set_simulated_return(&mut self, amount: Uint128) {
+        self.simulated_return = amount;
+    }
+}
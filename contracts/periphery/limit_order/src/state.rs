@@ -0,0 +1,63 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport::common::OwnershipProposal;
+use astroport::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the limit order contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport router contract address used to execute orders
+    pub router: Addr,
+    /// The maximum keeper fee (in bps of the offer amount) that an order can pay
+    pub max_keeper_fee_bps: u16,
+}
+
+/// ## Description
+/// This structure describes an open order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Order {
+    /// The order identifier
+    pub order_id: u64,
+    /// The address that created the order
+    pub owner: Addr,
+    /// The asset sold in the order, including the keeper fee
+    pub offer_asset: Asset,
+    /// The asset bought in the order
+    pub ask_asset_info: AssetInfo,
+    /// The swap operations used to execute the order
+    pub operations: Vec<SwapOperation>,
+    /// The minimum amount of ask tokens received per offer token
+    pub price: Decimal,
+    /// The minimum amount of ask tokens the owner receives when the order is executed
+    pub minimum_receive: Uint128,
+    /// The amount of offer tokens paid to the keeper that executes the order
+    pub keeper_fee: Uint128,
+    /// The timestamp (in seconds) when the order was created
+    pub created_at: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the identifier of the next order
+pub const NEXT_ORDER_ID: Item<u64> = Item::new("next_order_id");
+
+/// ## Description
+/// Stores open orders by their identifiers
+pub const ORDERS: Map<U64Key, Order> = Map::new("orders");
+
+/// ## Description
+/// Stores the identifiers of open orders by their owners
+pub const OWNER_ORDERS: Map<(&Addr, U64Key), ()> = Map::new("owner_orders");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
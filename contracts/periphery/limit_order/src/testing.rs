@@ -0,0 +1,409 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::AssetInfo;
+use astroport::limit_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse,
+    QueryMsg, MAX_KEEPER_FEE_BPS,
+};
+use astroport::router::{Cw20HookMsg as RouterCw20HookMsg, SwapOperation};
+
+type LimitOrderDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn token_info(contract_addr: &str) -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    }
+}
+
+fn astro_ust_operations() -> Vec<SwapOperation> {
+    vec![SwapOperation::AstroSwap {
+        offer_asset_info: token_info("astro"),
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: String::from("uusd"),
+        },
+    }]
+}
+
+fn setup() -> LimitOrderDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_router("router");
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            router: String::from("router"),
+            max_keeper_fee_bps: 200,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn create_order(
+    deps: &mut LimitOrderDeps,
+    token: &str,
+    sender: &str,
+    amount: u128,
+    operations: Vec<SwapOperation>,
+    price: Decimal,
+    keeper_fee_bps: u16,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(sender),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::CreateOrder {
+                operations,
+                price,
+                keeper_fee_bps,
+            })
+            .unwrap(),
+        }),
+    )
+}
+
+fn query_order(deps: &LimitOrderDeps, order_id: u64) -> OrderResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id }).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            router: String::from("router"),
+            max_keeper_fee_bps: MAX_KEEPER_FEE_BPS + 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::KeeperFeeTooHigh(MAX_KEEPER_FEE_BPS));
+
+    let deps = setup();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            router: Addr::unchecked("router"),
+            max_keeper_fee_bps: 200,
+        }
+    );
+}
+
+#[test]
+fn create_and_cancel_order() {
+    let mut deps = setup();
+    let price = Decimal::from_ratio(2u128, 1u128);
+
+    let err = create_order(&mut deps, "astro", "user", 1000, vec![], price, 100).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidOperations(String::from("an order must use 1 to 50 operations"))
+    );
+
+    // The first operation must offer the sent token
+    let err = create_order(
+        &mut deps,
+        "xtoken",
+        "user",
+        1000,
+        astro_ust_operations(),
+        price,
+        100,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidOperations(String::from(
+            "the first operation must offer the offer asset"
+        ))
+    );
+
+    let err = create_order(
+        &mut deps,
+        "astro",
+        "user",
+        1000,
+        astro_ust_operations(),
+        Decimal::zero(),
+        100,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPrice {});
+
+    let err = create_order(
+        &mut deps,
+        "astro",
+        "user",
+        1000,
+        astro_ust_operations(),
+        price,
+        201,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::KeeperFeeTooHigh(200));
+
+    let err = create_order(
+        &mut deps,
+        "astro",
+        "user",
+        0,
+        astro_ust_operations(),
+        price,
+        100,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderAmountTooLow {});
+
+    create_order(
+        &mut deps,
+        "astro",
+        "user",
+        1000,
+        astro_ust_operations(),
+        price,
+        100,
+    )
+    .unwrap();
+    create_order(
+        &mut deps,
+        "astro",
+        "user2",
+        500,
+        astro_ust_operations(),
+        price,
+        0,
+    )
+    .unwrap();
+
+    let order = query_order(&deps, 1);
+    assert_eq!(order.owner, Addr::unchecked("user"));
+    assert_eq!(order.offer_asset.amount, Uint128::from(1000u128));
+    assert_eq!(
+        order.ask_asset_info,
+        AssetInfo::NativeToken {
+            denom: String::from("uusd")
+        }
+    );
+    // 1% of the offer amount goes to the keeper, the rest is swapped at a price of 2
+    assert_eq!(order.keeper_fee, Uint128::from(10u128));
+    assert_eq!(order.minimum_receive, Uint128::from(1980u128));
+
+    let orders: OrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Orders {
+                owner: Some(String::from("user2")),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orders.orders.len(), 1);
+    assert_eq!(orders.orders[0].order_id, 2);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("user"),
+                amount: Uint128::from(1000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let orders: OrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Orders {
+                owner: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orders.orders.len(), 1);
+    assert_eq!(orders.orders[0].order_id, 2);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderNotFound(1));
+}
+
+#[test]
+fn execute_order() {
+    let mut deps = setup();
+
+    create_order(
+        &mut deps,
+        "astro",
+        "user",
+        1000,
+        astro_ust_operations(),
+        Decimal::from_ratio(2u128, 1u128),
+        100,
+    )
+    .unwrap();
+
+    deps.querier.set_simulated_return(Uint128::from(1979u128));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::LimitNotReached {
+            return_amount: String::from("1979"),
+            minimum_receive: String::from("1980"),
+        }
+    );
+
+    deps.querier.set_simulated_return(Uint128::from(2000u128));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("router"),
+                amount: Uint128::from(990u128),
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                    operations: astro_ust_operations(),
+                    minimum_receive: Some(Uint128::from(1980u128)),
+                    to: Some(String::from("user")),
+                    max_spread: None,
+                    referral_code: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("keeper"),
+                amount: Uint128::from(10u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // The order can't be executed twice
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderNotFound(1));
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: Some(String::from("router2")),
+            max_keeper_fee_bps: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: None,
+            max_keeper_fee_bps: Some(MAX_KEEPER_FEE_BPS + 1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::KeeperFeeTooHigh(MAX_KEEPER_FEE_BPS));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            router: Some(String::from("router2")),
+            max_keeper_fee_bps: Some(500),
+        },
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.router, Addr::unchecked("router2"));
+    assert_eq!(config.max_keeper_fee_bps, 500);
+}
@@ -0,0 +1,742 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, MARKETS, OWNERSHIP_PROPOSAL};
+use astroport::asset::{addr_validate_to_lower, pair_info_by_pool, Asset, AssetInfo, PairInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::liquidity_migrator::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LegacyPairInfo,
+    MarketInfo, MigrateMsg, QueryMsg,
+};
+use astroport::pair::{
+    Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+};
+use astroport::querier::query_pair_info;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-liquidity-migrator";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading markets
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading markets
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            factory: addr_validate_to_lower(deps.api, &msg.factory)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::SetMarket { legacy_pair, astroport_pair }** Adds a market or replaces its Astroport pair.
+///
+/// * **ExecuteMsg::RemoveMarket { legacy_lp_token }** Removes a market.
+///
+/// * **ExecuteMsg::Callback(msg)** Handles callbacks described in the [`CallbackMsg`].
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::SetMarket {
+            legacy_pair,
+            astroport_pair,
+        } => set_market(deps, info, legacy_pair, astroport_pair),
+        ExecuteMsg::RemoveMarket { legacy_lp_token } => remove_market(deps, info, legacy_lp_token),
+        ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, *msg),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then a [`ContractError`] is returned,
+/// otherwise it returns a [`Response`] with the specified attributes if the operation was successful
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message that has to be processed.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Migrate {
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        } => {
+            let sender = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            let receiver = receiver
+                .map(|receiver| addr_validate_to_lower(deps.api, &receiver))
+                .transpose()?
+                .unwrap_or(sender);
+
+            migrate_liquidity(
+                deps,
+                env,
+                info.sender,
+                cw20_msg.amount,
+                receiver,
+                slippage_tolerance,
+                auto_stake.unwrap_or(false),
+            )
+        }
+        Cw20HookMsg::MigratePair {
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        } => {
+            let sender = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            let receiver = receiver
+                .map(|receiver| addr_validate_to_lower(deps.api, &receiver))
+                .transpose()?
+                .unwrap_or(sender);
+
+            migrate_pair_liquidity(
+                deps,
+                env,
+                info.sender,
+                cw20_msg.amount,
+                receiver,
+                slippage_tolerance,
+                auto_stake.unwrap_or(false),
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Withdraws the received legacy LP tokens from the legacy pair and schedules the callback that provides
+/// the withdrawn assets to the market's Astroport pair. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **legacy_lp_token** is an object of type [`Addr`]. This is the legacy LP token that was sent.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of legacy LP tokens to migrate.
+///
+/// * **receiver** is an object of type [`Addr`]. This is the receiver of the Astroport LP tokens.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`].
+///
+/// * **auto_stake** is a variable of type [`bool`]. Determines whether the Astroport LP tokens are staked
+/// in the generator.
+fn migrate_liquidity(
+    deps: DepsMut,
+    env: Env,
+    legacy_lp_token: Addr,
+    amount: Uint128,
+    receiver: Addr,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: bool,
+) -> Result<Response, ContractError> {
+    let market = MARKETS
+        .may_load(deps.storage, &legacy_lp_token)?
+        .ok_or_else(|| ContractError::MarketNotFound(legacy_lp_token.to_string()))?;
+
+    let messages = withdraw_and_provide_msgs(
+        deps.as_ref(),
+        &env,
+        &legacy_lp_token,
+        &market.legacy_pair,
+        market.astroport_pair,
+        amount,
+        receiver.clone(),
+        slippage_tolerance,
+        auto_stake,
+    )?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "migrate_liquidity"),
+        attr("legacy_lp_token", legacy_lp_token),
+        attr("amount", amount),
+        attr("receiver", receiver),
+    ]))
+}
+
+/// ## Description
+/// Withdraws the received Astroport LP tokens from their pair and schedules the callback that provides
+/// the withdrawn assets to the pair currently registered in the factory for the same assets. This lets
+/// liquidity follow a market that moved to another pair type. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the Astroport LP token that was sent.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to migrate.
+///
+/// * **receiver** is an object of type [`Addr`]. This is the receiver of the new LP tokens.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`].
+///
+/// * **auto_stake** is a variable of type [`bool`]. Determines whether the new LP tokens are staked
+/// in the generator.
+fn migrate_pair_liquidity(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    amount: Uint128,
+    receiver: Addr,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // The LP token is minted by its pair, which must report it as its own
+    let source_pair = pair_info_by_pool(deps.as_ref(), lp_token.clone())
+        .map_err(|_| ContractError::InvalidLpToken {})?;
+    if source_pair.liquidity_token != lp_token {
+        return Err(ContractError::InvalidLpToken {});
+    }
+
+    let target_pair = query_pair_info(&deps.querier, config.factory, &source_pair.asset_infos)
+        .map_err(|_| ContractError::PairNotRegistered {})?;
+    if target_pair.contract_addr == source_pair.contract_addr {
+        return Err(ContractError::PairNotReplaced {});
+    }
+
+    let target_pair_addr = target_pair.contract_addr.clone();
+    let messages = withdraw_and_provide_msgs(
+        deps.as_ref(),
+        &env,
+        &lp_token,
+        &source_pair.contract_addr,
+        target_pair,
+        amount,
+        receiver.clone(),
+        slippage_tolerance,
+        auto_stake,
+    )?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "migrate_pair_liquidity"),
+        attr("lp_token", lp_token),
+        attr("source_pair", source_pair.contract_addr),
+        attr("target_pair", target_pair_addr),
+        attr("amount", amount),
+        attr("receiver", receiver),
+    ]))
+}
+
+/// ## Description
+/// Returns the messages that withdraw LP tokens from a pair and provide the withdrawn assets to an
+/// Astroport pair in a callback. The contract's balances of the pair assets are recorded beforehand,
+/// so the callback only provides what the withdrawal returned.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token to withdraw.
+///
+/// * **pair** is an object of type [`Addr`]. This is the pair the liquidity is withdrawn from.
+///
+/// * **astroport_pair** is an object of type [`PairInfo`]. This is the pair the liquidity is provided to.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to withdraw.
+///
+/// * **receiver** is an object of type [`Addr`]. This is the receiver of the Astroport LP tokens.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`].
+///
+/// * **auto_stake** is a variable of type [`bool`]. Determines whether the Astroport LP tokens are staked
+/// in the generator.
+#[allow(clippy::too_many_arguments)]
+fn withdraw_and_provide_msgs(
+    deps: Deps,
+    env: &Env,
+    lp_token: &Addr,
+    pair: &Addr,
+    astroport_pair: PairInfo,
+    amount: Uint128,
+    receiver: Addr,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut prev_balances = vec![];
+    for info in astroport_pair.asset_infos.iter() {
+        prev_balances.push(Asset {
+            amount: info.query_pool(&deps.querier, env.contract.address.clone())?,
+            info: info.clone(),
+        });
+    }
+    let prev_balances = [prev_balances[0].clone(), prev_balances[1].clone()];
+
+    Ok(vec![
+        WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount,
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {})?,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        callback_msg(
+            env,
+            CallbackMsg::ProvideLiquidity {
+                astroport_pair,
+                prev_balances,
+                receiver,
+                slippage_tolerance,
+                auto_stake,
+            },
+        )?,
+    ])
+}
+
+/// ## Description
+/// Adds a market for the legacy pair's LP token or replaces its Astroport pair. The Astroport pair must be
+/// registered in the factory and hold the same assets as the legacy pair. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **legacy_pair** is an object of type [`String`]. This is the legacy pair the liquidity is withdrawn from.
+///
+/// * **astroport_pair** is an object of type [`String`]. This is the Astroport pair the liquidity is provided to.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn set_market(
+    deps: DepsMut,
+    info: MessageInfo,
+    legacy_pair: String,
+    astroport_pair: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let legacy_pair = addr_validate_to_lower(deps.api, &legacy_pair)?;
+    let legacy_pair_info: LegacyPairInfo = deps
+        .querier
+        .query_wasm_smart(&legacy_pair, &PairQueryMsg::Pair {})?;
+
+    let astroport_pair = addr_validate_to_lower(deps.api, &astroport_pair)?;
+    let astroport_pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&astroport_pair, &PairQueryMsg::Pair {})?;
+
+    // Only pairs created by the factory can receive liquidity
+    let registered = query_pair_info(
+        &deps.querier,
+        config.factory,
+        &astroport_pair_info.asset_infos,
+    )?;
+    if registered.contract_addr != astroport_pair {
+        return Err(ContractError::PairNotRegistered {});
+    }
+
+    if !same_assets(
+        &legacy_pair_info.asset_infos,
+        &astroport_pair_info.asset_infos,
+    ) {
+        return Err(ContractError::AssetsMismatch {});
+    }
+
+    let legacy_lp_token = legacy_pair_info.liquidity_token;
+    MARKETS.save(
+        deps.storage,
+        &legacy_lp_token,
+        &MarketInfo {
+            legacy_lp_token: legacy_lp_token.clone(),
+            legacy_pair: legacy_pair.clone(),
+            astroport_pair: astroport_pair_info,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_market"),
+        attr("legacy_lp_token", legacy_lp_token),
+        attr("legacy_pair", legacy_pair),
+        attr("astroport_pair", astroport_pair),
+    ]))
+}
+
+/// ## Description
+/// Removes a market. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **legacy_lp_token** is an object of type [`String`]. This is the market's legacy LP token.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn remove_market(
+    deps: DepsMut,
+    info: MessageInfo,
+    legacy_lp_token: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let legacy_lp_token = addr_validate_to_lower(deps.api, &legacy_lp_token)?;
+    if !MARKETS.has(deps.storage, &legacy_lp_token) {
+        return Err(ContractError::MarketNotFound(legacy_lp_token.to_string()));
+    }
+    MARKETS.remove(deps.storage, &legacy_lp_token);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_market"),
+        attr("legacy_lp_token", legacy_lp_token),
+    ]))
+}
+
+/// ## Description
+/// Handles callbacks described in the [`CallbackMsg`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`CallbackMsg`]. This is the callback action.
+///
+/// ## Executor
+/// Callback functions can only be called by this contract.
+fn handle_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    // Callback functions can only be called by this contract
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        CallbackMsg::ProvideLiquidity {
+            astroport_pair,
+            prev_balances,
+            receiver,
+            slippage_tolerance,
+            auto_stake,
+        } => provide_liquidity(
+            deps,
+            env,
+            astroport_pair,
+            prev_balances,
+            receiver,
+            slippage_tolerance,
+            auto_stake,
+        ),
+    }
+}
+
+/// ## Description
+/// Provides the withdrawn assets to the Astroport pair on behalf of the receiver.
+/// The withdrawn amounts are calculated from the contract's balance changes. Native token taxes are
+/// deducted from the provided amounts, so the contract never pays them from its own balance.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **astroport_pair** is an object of type [`PairInfo`]. This is the pair that receives the liquidity.
+///
+/// * **prev_balances** is an array of type [`Asset`]. These are the contract's balances before the withdrawal.
+///
+/// * **receiver** is an object of type [`Addr`]. This is the receiver of the Astroport LP tokens.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`].
+///
+/// * **auto_stake** is a variable of type [`bool`]. Determines whether the Astroport LP tokens are staked
+/// in the generator.
+fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    astroport_pair: PairInfo,
+    prev_balances: [Asset; 2],
+    receiver: Addr,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: bool,
+) -> Result<Response, ContractError> {
+    let mut assets = vec![];
+    for prev_balance in prev_balances.iter() {
+        let received = prev_balance
+            .info
+            .query_pool(&deps.querier, env.contract.address.clone())?
+            .checked_sub(prev_balance.amount)?;
+        let mut asset = Asset {
+            info: prev_balance.info.clone(),
+            amount: received,
+        };
+        if asset.is_native_token() && !asset.amount.is_zero() {
+            asset.amount = asset.deduct_tax(&deps.querier)?.amount;
+        }
+        assets.push(asset);
+    }
+    if assets.iter().all(|asset| asset.amount.is_zero()) {
+        return Err(ContractError::NothingWithdrawn {});
+    }
+    let assets = [assets[0].clone(), assets[1].clone()];
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds = vec![];
+    for asset in assets.iter() {
+        match &asset.info {
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: astroport_pair.contract_addr.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: astroport_pair.contract_addr.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: assets.clone(),
+                slippage_tolerance,
+                auto_stake: Some(auto_stake),
+                receiver: Some(receiver.to_string()),
+            })?,
+            funds,
+        }
+        .into(),
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "provide_liquidity"),
+        attr("astroport_pair", astroport_pair.contract_addr),
+        attr("assets", format!("{}, {}", assets[0], assets[1])),
+        attr("receiver", receiver),
+    ]))
+}
+
+/// ## Description
+/// Returns true if both pairs hold the same assets in any order.
+/// ## Params
+/// * **a** is an array of type [`AssetInfo`].
+///
+/// * **b** is an array of type [`AssetInfo`].
+fn same_assets(a: &[AssetInfo; 2], b: &[AssetInfo; 2]) -> bool {
+    (a[0].equal(&b[0]) && a[1].equal(&b[1])) || (a[0].equal(&b[1]) && a[1].equal(&b[0]))
+}
+
+/// ## Description
+/// Builds a message that executes a callback in this contract.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`CallbackMsg`].
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(Box::new(msg)))?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Market { legacy_lp_token }** Returns a market using a [`MarketInfo`] object.
+///
+/// * **QueryMsg::Markets { start_after, limit }** Returns markets using a vector of [`MarketInfo`] objects.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                factory: config.factory,
+            })
+        }
+        QueryMsg::Market { legacy_lp_token } => {
+            let legacy_lp_token = addr_validate_to_lower(deps.api, &legacy_lp_token)?;
+            to_binary(&MARKETS.load(deps.storage, &legacy_lp_token)?)
+        }
+        QueryMsg::Markets { start_after, limit } => {
+            to_binary(&query_markets(deps, start_after, limit)?)
+        }
+    }
+}
+
+/// ## Description
+/// Returns markets ordered by their legacy LP token addresses.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the legacy LP token after which to start reading.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of markets to return.
+fn query_markets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<MarketInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|token| addr_validate_to_lower(deps.api, &token))
+        .transpose()?
+        .map(|token| Bound::exclusive(token.to_string()));
+
+    MARKETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
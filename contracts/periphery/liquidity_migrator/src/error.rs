@@ -0,0 +1,37 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes liquidity migrator contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The pair is not registered in the factory")]
+    PairNotRegistered {},
+
+    #[error("The legacy pair and the Astroport pair hold different assets")]
+    AssetsMismatch {},
+
+    #[error("The sent token is not an Astroport LP token")]
+    InvalidLpToken {},
+
+    #[error("The pair is still registered in the factory")]
+    PairNotReplaced {},
+
+    #[error("No market accepts {0}")]
+    MarketNotFound(String),
+
+    #[error("The pair returned no liquidity")]
+    NothingWithdrawn {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
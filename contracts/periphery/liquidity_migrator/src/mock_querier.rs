@@ -0,0 +1,173 @@
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::{PairType, QueryMsg as FactoryQueryMsg};
+use astroport::liquidity_migrator::LegacyPairInfo;
+use astroport::pair::QueryMsg as PairQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg, MinterResponse};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    // Astroport pairs keyed by their address
+    pairs: HashMap<String, PairInfo>,
+    // Astroport pairs registered in the factory
+    registered: Vec<String>,
+    // legacy pairs keyed by their address
+    legacy_pairs: HashMap<String, LegacyPairInfo>,
+    // token balances keyed by token address and holder
+    balances: HashMap<(String, String), Uint128>,
+    // minters keyed by token address
+    minters: HashMap<String, String>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if contract_addr == "factory" {
+                    match from_binary(msg).unwrap() {
+                        FactoryQueryMsg::Pair { asset_infos } => {
+                            let pair = self
+                                .registered
+                                .iter()
+                                .filter_map(|pair| self.pairs.get(pair))
+                                .find(|pair| {
+                                    pair.asset_infos[0].equal(&asset_infos[0])
+                                        && pair.asset_infos[1].equal(&asset_infos[1])
+                                });
+                            match pair {
+                                Some(pair) => SystemResult::Ok(to_binary(pair).into()),
+                                None => SystemResult::Err(SystemError::NoSuchContract {
+                                    addr: String::from("pair"),
+                                }),
+                            }
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else if let Some(pair) = self.pairs.get(contract_addr) {
+                    match from_binary(msg).unwrap() {
+                        PairQueryMsg::Pair {} => SystemResult::Ok(to_binary(pair).into()),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else if let Some(pair) = self.legacy_pairs.get(contract_addr) {
+                    match from_binary(msg).unwrap() {
+                        PairQueryMsg::Pair {} => SystemResult::Ok(to_binary(pair).into()),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else {
+                    match from_binary(msg).unwrap() {
+                        Cw20QueryMsg::Balance { address } => {
+                            let balance = self
+                                .balances
+                                .get(&(contract_addr.clone(), address))
+                                .cloned()
+                                .unwrap_or_default();
+                            SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                        }
+                        Cw20QueryMsg::Minter {} => match self.minters.get(contract_addr) {
+                            Some(minter) => SystemResult::Ok(
+                                to_binary(&MinterResponse {
+                                    minter: minter.clone(),
+                                    cap: None,
+                                })
+                                .into(),
+                            ),
+                            None => SystemResult::Err(SystemError::NoSuchContract {
+                                addr: contract_addr.clone(),
+                            }),
+                        },
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            pairs: HashMap::new(),
+            registered: vec![],
+            legacy_pairs: HashMap::new(),
+            balances: HashMap::new(),
+            minters: HashMap::new(),
+        }
+    }
+
+    pub fn add_pair(
+        &mut self,
+        pair: &str,
+        asset_infos: [AssetInfo; 2],
+        lp_token: &str,
+        pair_type: PairType,
+        registered: bool,
+    ) {
+        self.pairs.insert(
+            pair.to_string(),
+            PairInfo {
+                asset_infos,
+                contract_addr: Addr::unchecked(pair),
+                liquidity_token: Addr::unchecked(lp_token),
+                pair_type,
+            },
+        );
+        self.minters.insert(lp_token.to_string(), pair.to_string());
+        if registered {
+            self.registered.push(pair.to_string());
+        }
+    }
+
+    pub fn add_legacy_pair(&mut self, pair: &str, asset_infos: [AssetInfo; 2], lp_token: &str) {
+        self.legacy_pairs.insert(
+            pair.to_string(),
+            LegacyPairInfo {
+                asset_infos,
+                contract_addr: Addr::unchecked(pair),
+                liquidity_token: Addr::unchecked(lp_token),
+            },
+        );
+        self.minters.insert(lp_token.to_string(), pair.to_string());
+    }
+
+    pub fn set_token_balance(&mut self, token: &str, holder: &str, balance: u128) {
+        self.balances.insert(
+            (token.to_string(), holder.to_string()),
+            Uint128::from(balance),
+        );
+    }
+}
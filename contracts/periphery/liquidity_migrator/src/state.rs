@@ -0,0 +1,28 @@
+use astroport::common::OwnershipProposal;
+use astroport::liquidity_migrator::MarketInfo;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the liquidity migrator contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to configure markets
+    pub owner: Addr,
+    /// The factory contract address
+    pub factory: Addr,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores markets keyed by their legacy LP token
+pub const MARKETS: Map<&Addr, MarketInfo> = Map::new("markets");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
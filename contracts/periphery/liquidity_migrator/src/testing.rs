@@ -0,0 +1,463 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::liquidity_migrator::{
+    CallbackMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MarketInfo, QueryMsg,
+};
+use astroport::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+
+type MigratorDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn token_info(contract_addr: &str) -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    }
+}
+
+fn token_asset(contract_addr: &str, amount: u128) -> Asset {
+    Asset {
+        info: token_info(contract_addr),
+        amount: Uint128::from(amount),
+    }
+}
+
+fn wasm_msg(contract_addr: &str, msg: &impl serde::Serialize) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn messages(res: Response) -> Vec<CosmosMsg> {
+    res.messages.into_iter().map(|msg| msg.msg).collect()
+}
+
+fn setup() -> MigratorDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_legacy_pair(
+        "legacy_pair",
+        [token_info("usdc"), token_info("astro")],
+        "legacy_lp",
+    );
+    deps.querier.add_legacy_pair(
+        "legacy_other_pair",
+        [token_info("usdc"), token_info("usdt")],
+        "legacy_other_lp",
+    );
+    deps.querier.add_pair(
+        "astroport_pair",
+        [token_info("astro"), token_info("usdc")],
+        "astroport_lp",
+        PairType::Xyk {},
+        true,
+    );
+    deps.querier.add_pair(
+        "fake_pair",
+        [token_info("astro"), token_info("usdc")],
+        "fake_lp",
+        PairType::Xyk {},
+        false,
+    );
+    // The XYK pair was deregistered after the market moved to a stable pair
+    deps.querier.add_pair(
+        "xyk_pair",
+        [token_info("usdc"), token_info("usdt")],
+        "xyk_lp",
+        PairType::Xyk {},
+        false,
+    );
+    deps.querier.add_pair(
+        "stable_pair",
+        [token_info("usdc"), token_info("usdt")],
+        "stable_lp",
+        PairType::Stable {},
+        true,
+    );
+    deps.querier.add_pair(
+        "orphan_pair",
+        [token_info("astro"), token_info("usdt")],
+        "orphan_lp",
+        PairType::Xyk {},
+        false,
+    );
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            factory: String::from("factory"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn set_market() {
+    let mut deps = setup();
+
+    let set_market = |legacy_pair: &str, astroport_pair: &str| ExecuteMsg::SetMarket {
+        legacy_pair: legacy_pair.to_string(),
+        astroport_pair: astroport_pair.to_string(),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        set_market("legacy_pair", "astroport_pair"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Only pairs created by the factory can receive liquidity
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        set_market("legacy_pair", "fake_pair"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotRegistered {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        set_market("legacy_other_pair", "astroport_pair"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetsMismatch {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        set_market("legacy_pair", "astroport_pair"),
+    )
+    .unwrap();
+
+    let market: MarketInfo = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Market {
+                legacy_lp_token: String::from("legacy_lp"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.legacy_lp_token, Addr::unchecked("legacy_lp"));
+    assert_eq!(market.legacy_pair, Addr::unchecked("legacy_pair"));
+    assert_eq!(
+        market.astroport_pair.contract_addr,
+        Addr::unchecked("astroport_pair")
+    );
+
+    let markets: Vec<MarketInfo> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Markets {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(markets, vec![market]);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveMarket {
+            legacy_lp_token: String::from("legacy_lp"),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveMarket {
+            legacy_lp_token: String::from("legacy_lp"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MarketNotFound(String::from("legacy_lp"))
+    );
+}
+
+#[test]
+fn migrate_liquidity() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetMarket {
+            legacy_pair: String::from("legacy_pair"),
+            astroport_pair: String::from("astroport_pair"),
+        },
+    )
+    .unwrap();
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 10);
+
+    let migrate_msg = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Migrate {
+                slippage_tolerance: Some(Decimal::percent(1)),
+                auto_stake: Some(true),
+                receiver: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    // Only LP tokens with a market can be migrated
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("legacy_other_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MarketNotFound(String::from("legacy_other_lp"))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("legacy_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap();
+    let market: MarketInfo = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Market {
+                legacy_lp_token: String::from("legacy_lp"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let callback = Box::new(CallbackMsg::ProvideLiquidity {
+        astroport_pair: market.astroport_pair,
+        prev_balances: [token_asset("astro", 10), token_asset("usdc", 0)],
+        receiver: Addr::unchecked("user"),
+        slippage_tolerance: Some(Decimal::percent(1)),
+        auto_stake: true,
+    });
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "legacy_lp",
+                &Cw20ExecuteMsg::Send {
+                    contract: String::from("legacy_pair"),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+                }
+            ),
+            wasm_msg(MOCK_CONTRACT_ADDR, &ExecuteMsg::Callback(callback.clone())),
+        ]
+    );
+
+    // Callbacks can only be called by the contract itself
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::Callback(callback.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(callback.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingWithdrawn {});
+
+    // The legacy pair returned liquidity
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 60);
+    deps.querier
+        .set_token_balance("usdc", MOCK_CONTRACT_ADDR, 200);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(callback),
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "astro",
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: String::from("astroport_pair"),
+                    amount: Uint128::from(50u128),
+                    expires: None,
+                }
+            ),
+            wasm_msg(
+                "usdc",
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: String::from("astroport_pair"),
+                    amount: Uint128::from(200u128),
+                    expires: None,
+                }
+            ),
+            wasm_msg(
+                "astroport_pair",
+                &PairExecuteMsg::ProvideLiquidity {
+                    assets: [token_asset("astro", 50), token_asset("usdc", 200)],
+                    slippage_tolerance: Some(Decimal::percent(1)),
+                    auto_stake: Some(true),
+                    receiver: Some(String::from("user")),
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn migrate_pair_liquidity() {
+    let mut deps = setup();
+    deps.querier
+        .set_token_balance("usdt", MOCK_CONTRACT_ADDR, 10);
+
+    let migrate_msg = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::MigratePair {
+                slippage_tolerance: None,
+                auto_stake: Some(true),
+                receiver: Some(String::from("receiver")),
+            })
+            .unwrap(),
+        })
+    };
+
+    // Legacy LP tokens can only be migrated through a market
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("legacy_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLpToken {});
+
+    // The pair is still the one registered in the factory
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astroport_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotReplaced {});
+
+    // No pair is registered for the assets
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("orphan_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotRegistered {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("xyk_lp", &[]),
+        migrate_msg(100),
+    )
+    .unwrap();
+    let callback = Box::new(CallbackMsg::ProvideLiquidity {
+        astroport_pair: PairInfo {
+            asset_infos: [token_info("usdc"), token_info("usdt")],
+            contract_addr: Addr::unchecked("stable_pair"),
+            liquidity_token: Addr::unchecked("stable_lp"),
+            pair_type: PairType::Stable {},
+        },
+        prev_balances: [token_asset("usdc", 0), token_asset("usdt", 10)],
+        receiver: Addr::unchecked("receiver"),
+        slippage_tolerance: None,
+        auto_stake: true,
+    });
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "xyk_lp",
+                &Cw20ExecuteMsg::Send {
+                    contract: String::from("xyk_pair"),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+                }
+            ),
+            wasm_msg(MOCK_CONTRACT_ADDR, &ExecuteMsg::Callback(callback.clone())),
+        ]
+    );
+
+    // The XYK pair returned liquidity
+    deps.querier
+        .set_token_balance("usdc", MOCK_CONTRACT_ADDR, 300);
+    deps.querier
+        .set_token_balance("usdt", MOCK_CONTRACT_ADDR, 310);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(callback),
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res)[2],
+        wasm_msg(
+            "stable_pair",
+            &PairExecuteMsg::ProvideLiquidity {
+                assets: [token_asset("usdc", 300), token_asset("usdt", 300)],
+                slippage_tolerance: None,
+                auto_stake: Some(true),
+                receiver: Some(String::from("receiver")),
+            }
+        )
+    );
+}
@@ -1,9 +1,10 @@
 use crate::error::ContractError;
 use crate::querier::{query_cumulative_prices, query_pair_info, query_prices};
-use crate::state::{Config, PriceCumulativeLast, CONFIG, PRICE_LAST};
+use crate::state::{Config, Observation, PriceCumulativeLast, CONFIG, OBSERVATIONS, PRICE_LAST};
 use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport::common::assert_migration_allowed;
+use astroport::constants::{SECONDS_PER_DAY, TWAP_PRECISION};
 use astroport::oracle::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use astroport::pair::TWAP_PRECISION;
 use astroport::querier::query_token_precision;
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
@@ -18,7 +19,9 @@ const CONTRACT_NAME: &str = "astroport-oracle";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Time between two consecutive TWAP updates.
-pub const PERIOD: u64 = 86400;
+pub const PERIOD: u64 = SECONDS_PER_DAY;
+/// The number of recent spot price observations used to calculate the median price.
+pub const OBSERVATIONS_SIZE: usize = 7;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
@@ -96,7 +99,8 @@ pub fn execute(
 }
 
 /// ## Description
-/// Updates the local TWAP values for the tokens in the target Astroport pool.
+/// Updates the local TWAP values for the tokens in the target Astroport pool
+/// and records a new spot price observation.
 /// Returns a default object of type [`Response`] if the operation was successful,
 /// otherwise returns a [`ContractError`].
 /// ## Params
@@ -107,7 +111,7 @@ pub fn update(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let price_last = PRICE_LAST.load(deps.storage)?;
 
-    let prices = query_cumulative_prices(&deps.querier, config.pair.contract_addr)?;
+    let prices = query_cumulative_prices(&deps.querier, config.pair.contract_addr.clone())?;
     let time_elapsed = env.block.time.seconds() - price_last.block_timestamp_last;
 
     // Ensure that at least one full period has passed since the last update
@@ -141,25 +145,74 @@ pub fn update(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         block_timestamp_last: env.block.time.seconds(),
     };
     PRICE_LAST.save(deps.storage, &prices)?;
+
+    let observation = Observation {
+        price_0: query_spot_price(deps.as_ref(), &config, config.asset_infos[0].clone())?,
+        price_1: query_spot_price(deps.as_ref(), &config, config.asset_infos[1].clone())?,
+        timestamp: env.block.time.seconds(),
+    };
+    let mut observations = OBSERVATIONS.may_load(deps.storage)?.unwrap_or_default();
+    observations.push(observation);
+    if observations.len() > OBSERVATIONS_SIZE {
+        observations.remove(0);
+    }
+    OBSERVATIONS.save(deps.storage, &observations)?;
+
     Ok(Response::default())
 }
 
+/// ## Description
+/// Returns the amount of the other pool asset received for one unit of the specified token.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **token** is an object of type [`AssetInfo`]. This is the token for which we return the spot price.
+fn query_spot_price(deps: Deps, config: &Config, token: AssetInfo) -> StdResult<Uint128> {
+    let p = query_token_precision(&deps.querier, token.clone())?;
+    let one = Uint128::new(10_u128.pow(p.into()));
+
+    Ok(query_prices(
+        &deps.querier,
+        config.pair.contract_addr.clone(),
+        Asset {
+            info: token,
+            amount: one,
+        },
+    )?
+    .return_amount)
+}
+
 /// ## Description
 /// Exposes all the queries available in the contract.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **msg** is an object of type [`QueryMsg`].
 ///
 /// ## Queries
 /// * **QueryMsg::Consult { token, amount }** Validates assets and calculates a new average
 /// amount with updated precision
+///
+/// * **QueryMsg::ConsultMedian { token, amount, max_staleness }** Validates assets and calculates
+/// an amount using the median of the recent spot price observations
+///
+/// * **QueryMsg::LastUpdateTime {}** Returns the timestamp of the latest TWAP update
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Consult { token, amount } => to_binary(&consult(deps, token, amount)?),
+        QueryMsg::ConsultMedian {
+            token,
+            amount,
+            max_staleness,
+        } => to_binary(&consult_median(deps, env, token, amount, max_staleness)?),
+        QueryMsg::LastUpdateTime {} => {
+            to_binary(&PRICE_LAST.load(deps.storage)?.block_timestamp_last)
+        }
     }
 }
 
@@ -208,14 +261,79 @@ fn consult(deps: Deps, token: AssetInfo, amount: Uint128) -> Result<Uint256, Std
 }
 
 /// ## Description
-/// Used for contract migration. Returns the default object of type [`Response`].
+/// Multiplies a token amount by the median of the recent spot price observations and returns the result as a [`Uint256`]
+/// if the operation was successful or returns [`StdError`] on failure.
+/// Fails if there are no observations or the latest observation is older than `max_staleness` seconds.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **token** is an object of type [`AssetInfo`]. This is the token for which we multiply its median price by an amount.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens we multiply the median price by.
+///
+/// * **max_staleness** is an [`Option`] of type [`u64`]. This is the maximum age (in seconds) of the latest observation.
+fn consult_median(
+    deps: Deps,
+    env: Env,
+    token: AssetInfo,
+    amount: Uint128,
+    max_staleness: Option<u64>,
+) -> Result<Uint256, StdError> {
+    let config = CONFIG.load(deps.storage)?;
+    let observations = OBSERVATIONS.may_load(deps.storage)?.unwrap_or_default();
+
+    let last_observation = observations
+        .last()
+        .ok_or_else(|| StdError::generic_err("No price observations"))?;
+    if let Some(max_staleness) = max_staleness {
+        if env.block.time.seconds() - last_observation.timestamp > max_staleness {
+            return Err(StdError::generic_err("Price observations are stale"));
+        }
+    }
+
+    let mut prices: Vec<Uint128> = if config.asset_infos[0].equal(&token) {
+        observations.iter().map(|o| o.price_0).collect()
+    } else if config.asset_infos[1].equal(&token) {
+        observations.iter().map(|o| o.price_1).collect()
+    } else {
+        return Err(StdError::generic_err("Invalid Token"));
+    };
+    prices.sort();
+
+    let mid = prices.len() / 2;
+    let median = if prices.len() % 2 == 0 {
+        let (low, high) = (prices[mid - 1].u128(), prices[mid].u128());
+        Uint128::new(low + (high - low) / 2)
+    } else {
+        prices[mid]
+    };
+
+    let p = query_token_precision(&deps.querier, token)?;
+    let one = Uint128::new(10_u128.pow(p.into()));
+
+    Ok(Uint256::from(median).multiply_ratio(Uint256::from(amount), Uint256::from(one)))
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
 /// * **_msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
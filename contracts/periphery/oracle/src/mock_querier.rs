@@ -1,13 +1,14 @@
-use astroport::asset::{Asset, PairInfo};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::PairType;
 use astroport::factory::QueryMsg::Pair;
-use astroport::pair::CumulativePricesResponse;
-use astroport::pair::QueryMsg::CumulativePrices;
+use astroport::pair::QueryMsg::{CumulativePrices, Simulation};
+use astroport::pair::{CumulativePricesResponse, QueryMsg as PairQueryMsg, SimulationResponse};
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Addr, Coin, OwnedDeps, Querier, QuerierResult,
     QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
 use std::collections::HashMap;
 use terra_cosmwasm::TerraQueryWrapper;
 
@@ -33,6 +34,8 @@ pub struct WasmMockQuerier {
 pub struct TokenQuerier {
     // this lets us iterate over all pairs that match the first string
     pairs: HashMap<String, CumulativePricesResponse>,
+    // spot prices keyed by the offer asset
+    spot_prices: HashMap<String, Uint128>,
 }
 
 impl TokenQuerier {
@@ -90,8 +93,8 @@ impl WasmMockQuerier {
                         ),
                         _ => panic!("DO NOT ENTER HERE"),
                     }
-                } else {
-                    match from_binary(&msg).unwrap() {
+                } else if let Ok(pair_msg) = from_binary::<PairQueryMsg>(&msg) {
+                    match pair_msg {
                         CumulativePrices { .. } => {
                             let balance = match self.token_querier.pairs.get(contract_addr) {
                                 Some(v) => v,
@@ -101,6 +104,39 @@ impl WasmMockQuerier {
                             };
                             SystemResult::Ok(to_binary(&balance).into())
                         }
+                        Simulation { offer_asset } => {
+                            let return_amount = match self
+                                .token_querier
+                                .spot_prices
+                                .get(&offer_asset.info.to_string())
+                            {
+                                Some(v) => *v,
+                                None => {
+                                    return SystemResult::Err(SystemError::Unknown {});
+                                }
+                            };
+                            SystemResult::Ok(
+                                to_binary(&SimulationResponse {
+                                    return_amount,
+                                    spread_amount: Uint128::zero(),
+                                    commission_amount: Uint128::zero(),
+                                })
+                                .into(),
+                            )
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else {
+                    match from_binary(&msg).unwrap() {
+                        Cw20QueryMsg::TokenInfo {} => SystemResult::Ok(
+                            to_binary(&TokenInfoResponse {
+                                name: contract_addr.to_string(),
+                                symbol: contract_addr.to_string(),
+                                decimals: 6,
+                                total_supply: Uint128::zero(),
+                            })
+                            .into(),
+                        ),
                         _ => panic!("DO NOT ENTER HERE"),
                     }
                 }
@@ -127,4 +163,10 @@ impl WasmMockQuerier {
     ) {
         self.token_querier.set(pair, assert, total, price1, price2)
     }
+
+    pub fn set_spot_price(&mut self, offer_asset: AssetInfo, price: u128) {
+        self.token_querier
+            .spot_prices
+            .insert(offer_asset.to_string(), Uint128::from(price));
+    }
 }
@@ -11,6 +11,8 @@ use cw_storage_plus::Item;
 pub const CONFIG: Item<Config> = Item::new("config");
 /// Stores the latest cumulative and average prices at the given key
 pub const PRICE_LAST: Item<PriceCumulativeLast> = Item::new("price_last");
+/// Stores the most recent spot price observations, the oldest observation comes first
+pub const OBSERVATIONS: Item<Vec<Observation>> = Item::new("observations");
 
 /// ## Description
 /// This structure stores the latest cumulative and average token prices for the target pool
@@ -28,6 +30,18 @@ pub struct PriceCumulativeLast {
     pub block_timestamp_last: u64,
 }
 
+/// ## Description
+/// This structure stores a spot price observation for the target pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Observation {
+    /// The amount of asset 1 returned for one unit of asset 0
+    pub price_0: Uint128,
+    /// The amount of asset 0 returned for one unit of asset 1
+    pub price_1: Uint128,
+    /// The timestamp of the observation
+    pub timestamp: u64,
+}
+
 /// ## Description
 /// Global configuration for the contract
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
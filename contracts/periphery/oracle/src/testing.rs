@@ -1,10 +1,11 @@
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, migrate, query, PERIOD};
 use crate::mock_querier::mock_dependencies;
 use astroport::asset::{Asset, AssetInfo};
-use astroport::oracle::{ExecuteMsg, InstantiateMsg};
+use astroport::oracle::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{attr, from_binary, Addr, Uint128};
+use cw2::set_contract_version;
 use std::ops::Mul;
 
 #[test]
@@ -53,8 +54,10 @@ fn oracle_overflow() {
 
     let instantiate_msg = InstantiateMsg {
         factory_contract: factory.to_string(),
-        asset_infos: [astro_asset_info, usdc_asset_info],
+        asset_infos: [astro_asset_info.clone(), usdc_asset_info.clone()],
     };
+    deps.querier.set_spot_price(astro_asset_info, 1_000_000);
+    deps.querier.set_spot_price(usdc_asset_info, 1_000_000);
 
     // Set cumulative price to 192738282u128
     deps.querier.set_cumulative_price(
@@ -75,5 +78,179 @@ fn oracle_overflow() {
         Uint128::from(100u128),
     );
     env.block.time = env.block.time.plus_seconds(86400);
-    execute(deps.as_mut(), env, info, ExecuteMsg::Update {}).unwrap();
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Update {}).unwrap();
+
+    let last_update_time: u64 =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LastUpdateTime {}).unwrap())
+            .unwrap();
+    assert_eq!(last_update_time, env.block.time.seconds());
+}
+
+#[test]
+fn consult_median() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    let mut env = mock_env();
+
+    let astro_asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("astro-token"),
+    };
+    let usdc_asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("usdc-token"),
+    };
+    let assets = [
+        Asset {
+            info: astro_asset_info.clone(),
+            amount: Uint128::zero(),
+        },
+        Asset {
+            info: usdc_asset_info.clone(),
+            amount: Uint128::zero(),
+        },
+    ];
+    deps.querier.set_cumulative_price(
+        Addr::unchecked("pair"),
+        assets,
+        Uint128::zero(),
+        Uint128::zero(),
+        Uint128::zero(),
+    );
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            factory_contract: String::from("factory"),
+            asset_infos: [astro_asset_info.clone(), usdc_asset_info.clone()],
+        },
+    )
+    .unwrap();
+
+    let consult_median = |token: &AssetInfo, max_staleness: Option<u64>| QueryMsg::ConsultMedian {
+        token: token.clone(),
+        amount: Uint128::from(2_000_000u128),
+        max_staleness,
+    };
+
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        consult_median(&astro_asset_info, None),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: No price observations");
+
+    // The third observation is a manipulated spot price
+    for (astro_price, usdc_price) in [
+        (2_000_000, 500_000),
+        (2_100_000, 476_190),
+        (50_000_000, 20_000),
+    ] {
+        deps.querier
+            .set_spot_price(astro_asset_info.clone(), astro_price);
+        deps.querier
+            .set_spot_price(usdc_asset_info.clone(), usdc_price);
+        env.block.time = env.block.time.plus_seconds(PERIOD);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Update {},
+        )
+        .unwrap();
+    }
+
+    // The median ignores the outlier
+    let res: Uint256 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            consult_median(&astro_asset_info, Some(60)),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Uint256::from(4_200_000u128));
+    let res: Uint256 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            consult_median(&usdc_asset_info, None),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Uint256::from(952_380u128));
+
+    // With an even number of observations the two middle prices are averaged
+    deps.querier
+        .set_spot_price(astro_asset_info.clone(), 2_200_000);
+    deps.querier
+        .set_spot_price(usdc_asset_info.clone(), 454_545);
+    env.block.time = env.block.time.plus_seconds(PERIOD);
+    execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Update {}).unwrap();
+    let res: Uint256 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            consult_median(&astro_asset_info, None),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Uint256::from(4_300_000u128));
+
+    // The query fails if the latest observation is too old
+    env.block.time = env.block.time.plus_seconds(61);
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        consult_median(&astro_asset_info, Some(60)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Price observations are stale"
+    );
+
+    let err = query(
+        deps.as_ref(),
+        env,
+        consult_median(
+            &AssetInfo::NativeToken {
+                denom: String::from("uluna"),
+            },
+            None,
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Invalid Token");
+}
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-oracle", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-oracle"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-oracle"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
 }
@@ -48,6 +48,7 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, u64
             minter: owner.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let astro_token_instance = router
@@ -157,6 +158,7 @@ fn instantiate_token(router: &mut TerraApp, owner: Addr, name: String, symbol: S
             minter: owner.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_instance = router
@@ -813,11 +815,11 @@ fn consult2() {
         [
             Asset {
                 info: asset_infos[0].clone(),
-                amount: Uint128::from(1000_u128),
+                amount: Uint128::from(10_000_u128),
             },
             Asset {
                 info: asset_infos[1].clone(),
-                amount: Uint128::from(1000_u128),
+                amount: Uint128::from(10_000_u128),
             },
         ],
     );
@@ -840,8 +842,8 @@ fn consult2() {
         pair_info.contract_addr.clone(),
         astro_token_instance.clone(),
         usdc_token_instance.clone(),
-        Uint128::from(1000_u128),
-        Uint128::from(1000_u128),
+        Uint128::from(10_000_u128),
+        Uint128::from(10_000_u128),
     );
     router.update_block(next_day);
 
@@ -881,8 +883,8 @@ fn consult2() {
 
     // Change pair liquidity
     for (amount1, amount2) in [
-        (Uint128::from(1000_u128), Uint128::from(500_u128)),
-        (Uint128::from(1000_u128), Uint128::from(500_u128)),
+        (Uint128::from(10_000_u128), Uint128::from(5_000_u128)),
+        (Uint128::from(10_000_u128), Uint128::from(5_000_u128)),
     ] {
         change_provide_liquidity(
             &mut router,
@@ -934,8 +936,8 @@ fn consult2() {
 
     // Change pair liquidity
     for (amount1, amount2) in [
-        (Uint128::from(250_u128), Uint128::from(350_u128)),
-        (Uint128::from(250_u128), Uint128::from(350_u128)),
+        (Uint128::from(2_500_u128), Uint128::from(3_500_u128)),
+        (Uint128::from(2_500_u128), Uint128::from(3_500_u128)),
     ] {
         change_provide_liquidity(
             &mut router,
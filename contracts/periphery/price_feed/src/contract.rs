@@ -0,0 +1,437 @@
+use crate::error::ContractError;
+use crate::state::{Config, Feed, CONFIG, FEEDS, OWNERSHIP_PROPOSAL};
+use astroport::asset::{addr_validate_to_lower, AssetInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::oracle::QueryMsg as OracleQueryMsg;
+use astroport::price_feed::{
+    ConfigResponse, ExecuteMsg, FeedResponse, InstantiateMsg, MigrateMsg, PriceResponse, QueryMsg,
+    MAX_FEED_DECIMALS,
+};
+use astroport::querier::query_token_precision;
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    QuerierWrapper, Response, StdError, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-price-feed";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading feeds
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading feeds
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::SetFeed {
+///             symbol,
+///             oracle,
+///             asset_info,
+///             quote_asset_info,
+///             decimals,
+///         }** Creates or replaces the feed of a symbol.
+///
+/// * **ExecuteMsg::RemoveFeed { symbol }** Removes the feed of a symbol.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetFeed {
+            symbol,
+            oracle,
+            asset_info,
+            quote_asset_info,
+            decimals,
+        } => set_feed(
+            deps,
+            info,
+            symbol,
+            oracle,
+            asset_info,
+            quote_asset_info,
+            decimals,
+        ),
+        ExecuteMsg::RemoveFeed { symbol } => remove_feed(deps, info, symbol),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Creates or replaces the feed of a symbol. Both assets are checked against the oracle.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **symbol** is an object of type [`String`]. This is the symbol that the feed is queried by.
+///
+/// * **oracle** is an object of type [`String`]. This is the oracle contract that provides the TWAP.
+///
+/// * **asset_info** is an object of type [`AssetInfo`]. This is the priced asset.
+///
+/// * **quote_asset_info** is an object of type [`AssetInfo`]. This is the asset the price is expressed in.
+///
+/// * **decimals** is an object of type [`u8`]. This is the amount of decimals that prices are reported with.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn set_feed(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+    oracle: String,
+    asset_info: AssetInfo,
+    quote_asset_info: AssetInfo,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if symbol.is_empty() {
+        return Err(ContractError::InvalidSymbol {});
+    }
+    if decimals > MAX_FEED_DECIMALS {
+        return Err(ContractError::DecimalsTooHigh(MAX_FEED_DECIMALS));
+    }
+
+    asset_info.check(deps.api)?;
+    quote_asset_info.check(deps.api)?;
+    if asset_info.equal(&quote_asset_info) {
+        return Err(ContractError::InvalidFeedAssets {});
+    }
+
+    let feed = Feed {
+        oracle: addr_validate_to_lower(deps.api, &oracle)?,
+        asset_precision: query_token_precision(&deps.querier, asset_info.clone())?,
+        quote_precision: query_token_precision(&deps.querier, quote_asset_info.clone())?,
+        asset_info,
+        quote_asset_info,
+        decimals,
+    };
+
+    // The oracle rejects assets that aren't in its pool, so both assets must belong to it
+    query_consult(
+        &deps.querier,
+        &feed.oracle,
+        &feed.asset_info,
+        feed.asset_precision,
+    )?;
+    query_consult(
+        &deps.querier,
+        &feed.oracle,
+        &feed.quote_asset_info,
+        feed.quote_precision,
+    )?;
+
+    FEEDS.save(deps.storage, &symbol, &feed)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_feed"),
+        attr("symbol", symbol),
+        attr("oracle", feed.oracle),
+    ]))
+}
+
+/// ## Description
+/// Removes the feed of a symbol. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **symbol** is an object of type [`String`]. This is the symbol of the feed to remove.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn remove_feed(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !FEEDS.has(deps.storage, &symbol) {
+        return Err(ContractError::FeedNotFound(symbol));
+    }
+    FEEDS.remove(deps.storage, &symbol);
+
+    Ok(Response::new().add_attributes(vec![attr("action", "remove_feed"), attr("symbol", symbol)]))
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Feed { symbol }** Returns the feed of a symbol using a [`FeedResponse`] object.
+///
+/// * **QueryMsg::Feeds { start_after, limit }** Returns feeds using a vector that contains objects of type [`FeedResponse`].
+///
+/// * **QueryMsg::Price { symbol }** Returns the latest price of a symbol using a [`PriceResponse`] object.
+///
+/// * **QueryMsg::Prices { symbols }** Returns the latest prices of several symbols using a vector
+/// that contains objects of type [`PriceResponse`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+            })
+        }
+        QueryMsg::Feed { symbol } => {
+            let feed = load_feed(deps, &symbol)?;
+            to_binary(&feed_response(symbol, feed))
+        }
+        QueryMsg::Feeds { start_after, limit } => {
+            to_binary(&query_feeds(deps, start_after, limit)?)
+        }
+        QueryMsg::Price { symbol } => to_binary(&query_price(deps, symbol)?),
+        QueryMsg::Prices { symbols } => to_binary(
+            &symbols
+                .into_iter()
+                .map(|symbol| query_price(deps, symbol))
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+    }
+}
+
+/// ## Description
+/// Returns the latest price of a symbol. The oracle TWAP for one whole priced token is converted
+/// into whole quote tokens and scaled by the feed decimals.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **symbol** is an object of type [`String`]. This is the symbol to return the price for.
+fn query_price(deps: Deps, symbol: String) -> StdResult<PriceResponse> {
+    let feed = load_feed(deps, &symbol)?;
+
+    let quote_amount = query_consult(
+        &deps.querier,
+        &feed.oracle,
+        &feed.asset_info,
+        feed.asset_precision,
+    )?;
+    let publish_time: u64 = deps
+        .querier
+        .query_wasm_smart(&feed.oracle, &OracleQueryMsg::LastUpdateTime {})?;
+
+    Ok(PriceResponse {
+        symbol,
+        price: quote_amount.multiply_ratio(
+            10u128.pow(feed.decimals.into()),
+            10u128.pow(feed.quote_precision.into()),
+        ),
+        decimals: feed.decimals,
+        publish_time,
+    })
+}
+
+/// ## Description
+/// Returns feeds ordered by their symbols.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the symbol after which to start reading.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of feeds to return.
+fn query_feeds(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FeedResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    FEEDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (symbol, feed) = item?;
+            let symbol = String::from_utf8(symbol)
+                .map_err(|_| StdError::invalid_utf8("Invalid feed symbol"))?;
+            Ok(feed_response(symbol, feed))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Queries an oracle for the amount of the other pool asset that one whole token is worth.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **oracle** is an object of type [`Addr`]. This is the oracle contract to query.
+///
+/// * **asset_info** is an object of type [`AssetInfo`]. This is the asset to price.
+///
+/// * **precision** is an object of type [`u8`]. This is the precision of the asset.
+fn query_consult(
+    querier: &QuerierWrapper,
+    oracle: &Addr,
+    asset_info: &AssetInfo,
+    precision: u8,
+) -> StdResult<Uint128> {
+    querier.query_wasm_smart(
+        oracle,
+        &OracleQueryMsg::Consult {
+            token: asset_info.clone(),
+            amount: Uint128::new(10u128.pow(precision.into())),
+        },
+    )
+}
+
+/// ## Description
+/// Loads the feed of a symbol. Returns a [`StdError`] if the feed doesn't exist.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **symbol** is an object of type [`str`].
+fn load_feed(deps: Deps, symbol: &str) -> StdResult<Feed> {
+    FEEDS
+        .may_load(deps.storage, symbol)?
+        .ok_or_else(|| StdError::generic_err(format!("Feed for {} not found", symbol)))
+}
+
+/// ## Description
+/// Converts a stored feed into a [`FeedResponse`] object.
+/// ## Params
+/// * **symbol** is an object of type [`String`].
+///
+/// * **feed** is an object of type [`Feed`].
+fn feed_response(symbol: String, feed: Feed) -> FeedResponse {
+    FeedResponse {
+        symbol,
+        oracle: feed.oracle,
+        asset_info: feed.asset_info,
+        quote_asset_info: feed.quote_asset_info,
+        decimals: feed.decimals,
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,25 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes price feed contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The symbol must not be empty")]
+    InvalidSymbol {},
+
+    #[error("The priced asset and the quote asset must differ")]
+    InvalidFeedAssets {},
+
+    #[error("A feed can't report prices with more than {0} decimals")]
+    DecimalsTooHigh(u8),
+
+    #[error("Feed for {0} not found")]
+    FeedNotFound(String),
+}
@@ -0,0 +1,116 @@
+use astroport::asset::AssetInfo;
+use astroport::oracle::QueryMsg as OracleQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Decimal, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+/// A mocked oracle that returns fixed TWAPs for its assets
+pub struct MockOracle {
+    /// The pool assets with the amount of the other asset that one base unit is worth
+    pub rates: Vec<(AssetInfo, Decimal)>,
+    pub last_update_time: u64,
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    oracles: HashMap<String, MockOracle>,
+    token_decimals: HashMap<String, u8>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if self.oracles.contains_key(contract_addr) =>
+            {
+                let oracle = &self.oracles[contract_addr];
+                match from_binary(msg).unwrap() {
+                    OracleQueryMsg::Consult { token, amount } => {
+                        match oracle.rates.iter().find(|(info, _)| info.equal(&token)) {
+                            Some((_, rate)) => {
+                                SystemResult::Ok(to_binary(&(amount * *rate)).into())
+                            }
+                            None => SystemResult::Err(SystemError::InvalidRequest {
+                                error: String::from("Invalid Token"),
+                                request: msg.clone(),
+                            }),
+                        }
+                    }
+                    OracleQueryMsg::LastUpdateTime {} => {
+                        SystemResult::Ok(to_binary(&oracle.last_update_time).into())
+                    }
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => match self.token_decimals.get(contract_addr) {
+                        Some(decimals) => SystemResult::Ok(
+                            to_binary(&TokenInfoResponse {
+                                name: String::from("Mock"),
+                                symbol: String::from("MOCK"),
+                                decimals: *decimals,
+                                total_supply: Uint128::zero(),
+                            })
+                            .into(),
+                        ),
+                        None => SystemResult::Err(SystemError::NoSuchContract {
+                            addr: contract_addr.clone(),
+                        }),
+                    },
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            oracles: HashMap::new(),
+            token_decimals: HashMap::new(),
+        }
+    }
+
+    pub fn set_oracle(&mut self, oracle: &str, mock_oracle: MockOracle) {
+        self.oracles.insert(oracle.to_string(), mock_oracle);
+    }
+
+    pub fn set_token_decimals(&mut self, token: &str, decimals: u8) {
+        self.token_decimals.insert(token.to_string(), decimals);
+    }
+}
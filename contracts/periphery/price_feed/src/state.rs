@@ -0,0 +1,44 @@
+use astroport::asset::AssetInfo;
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the price feed contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes a price feed backed by an Astroport oracle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Feed {
+    /// The Astroport oracle contract that provides the TWAP
+    pub oracle: Addr,
+    /// The priced asset
+    pub asset_info: AssetInfo,
+    /// The asset the price is expressed in
+    pub quote_asset_info: AssetInfo,
+    /// The amount of decimals that prices are reported with
+    pub decimals: u8,
+    /// The precision of the priced asset
+    pub asset_precision: u8,
+    /// The precision of the quote asset
+    pub quote_precision: u8,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores feeds by their symbols
+pub const FEEDS: Map<&str, Feed> = Map::new("feeds");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,220 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, Decimal, OwnedDeps, Response, Uint128};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, MockOracle, WasmMockQuerier};
+use astroport::asset::AssetInfo;
+use astroport::price_feed::{
+    ExecuteMsg, FeedResponse, InstantiateMsg, PriceResponse, QueryMsg, MAX_FEED_DECIMALS,
+};
+
+type PriceFeedDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn astro_info() -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked("astro"),
+    }
+}
+
+fn ust_info() -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: String::from("uusd"),
+    }
+}
+
+fn setup() -> PriceFeedDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_token_decimals("astro", 6);
+    deps.querier.set_oracle(
+        "oracle",
+        MockOracle {
+            rates: vec![
+                (astro_info(), Decimal::from_ratio(3u128, 2u128)),
+                (ust_info(), Decimal::from_ratio(1u128, 2u128)),
+            ],
+            last_update_time: 1_600_000_000,
+        },
+    );
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn set_feed(
+    deps: &mut PriceFeedDeps,
+    sender: &str,
+    symbol: &str,
+    asset_info: AssetInfo,
+    quote_asset_info: AssetInfo,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::SetFeed {
+            symbol: String::from(symbol),
+            oracle: String::from("oracle"),
+            asset_info,
+            quote_asset_info,
+            decimals,
+        },
+    )
+}
+
+#[test]
+fn set_feed_validation() {
+    let mut deps = setup();
+
+    let err = set_feed(&mut deps, "user", "ASTRO", astro_info(), ust_info(), 8).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = set_feed(&mut deps, "owner", "", astro_info(), ust_info(), 8).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSymbol {});
+
+    let err = set_feed(
+        &mut deps,
+        "owner",
+        "ASTRO",
+        astro_info(),
+        ust_info(),
+        MAX_FEED_DECIMALS + 1,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DecimalsTooHigh(MAX_FEED_DECIMALS));
+
+    let err = set_feed(&mut deps, "owner", "ASTRO", astro_info(), astro_info(), 8).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFeedAssets {});
+
+    // The oracle doesn't price uluna
+    let err = set_feed(
+        &mut deps,
+        "owner",
+        "ASTRO",
+        astro_info(),
+        AssetInfo::NativeToken {
+            denom: String::from("uluna"),
+        },
+        8,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    set_feed(&mut deps, "owner", "ASTRO", astro_info(), ust_info(), 8).unwrap();
+    set_feed(&mut deps, "owner", "uusd", ust_info(), astro_info(), 18).unwrap();
+
+    let feeds: Vec<FeedResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Feeds {
+                start_after: Some(String::from("ASTRO")),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        feeds,
+        vec![FeedResponse {
+            symbol: String::from("uusd"),
+            oracle: Addr::unchecked("oracle"),
+            asset_info: ust_info(),
+            quote_asset_info: astro_info(),
+            decimals: 18,
+        }]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveFeed {
+            symbol: String::from("LUNA"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FeedNotFound(String::from("LUNA")));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveFeed {
+            symbol: String::from("uusd"),
+        },
+    )
+    .unwrap();
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Feed {
+            symbol: String::from("uusd"),
+        },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn prices() {
+    let mut deps = setup();
+
+    set_feed(&mut deps, "owner", "ASTRO", astro_info(), ust_info(), 8).unwrap();
+    set_feed(&mut deps, "owner", "uusd", ust_info(), astro_info(), 18).unwrap();
+
+    let price: PriceResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Price {
+                symbol: String::from("ASTRO"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        price,
+        PriceResponse {
+            symbol: String::from("ASTRO"),
+            price: Uint128::new(150_000_000),
+            decimals: 8,
+            publish_time: 1_600_000_000,
+        }
+    );
+
+    let prices: Vec<PriceResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Prices {
+                symbols: vec![String::from("uusd"), String::from("ASTRO")],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].price, Uint128::new(500_000_000_000_000_000));
+    assert_eq!(prices[1].price, Uint128::new(150_000_000));
+
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Price {
+            symbol: String::from("LUNA"),
+        },
+    )
+    .unwrap_err();
+}
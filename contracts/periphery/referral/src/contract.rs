@@ -0,0 +1,487 @@
+use crate::error::ContractError;
+use crate::state::{CodeInfo, Config, ASSET_STATS, CODES, CONFIG, OWNERSHIP_PROPOSAL};
+use astroport::asset::{addr_validate_to_lower, Asset, PairInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::pair::QueryMsg as PairQueryMsg;
+use astroport::querier::query_pair_info;
+use astroport::referral::{
+    AssetStats, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ReferralInfoResponse, StatsResponse,
+};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-referral";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The maximum share of the swap commission that can be paid to referrers
+const MAX_COMMISSION_SHARE: u64 = 50;
+/// The minimum length of a referral code
+const MIN_CODE_LENGTH: usize = 3;
+/// The maximum length of a referral code
+const MAX_CODE_LENGTH: usize = 32;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_commission_share(msg.commission_share)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            factory: addr_validate_to_lower(deps.api, &msg.factory)?,
+            commission_share: msg.commission_share,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::RegisterCode { code, fee_address }** Registers a new referral code.
+///
+/// * **ExecuteMsg::UpdateFeeAddress { code, fee_address }** Updates the fee address of a referral code.
+///
+/// * **ExecuteMsg::RecordSwap { code, offer_asset, referral_fee }** Records a referred swap.
+///
+/// * **ExecuteMsg::UpdateConfig { commission_share }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::RegisterCode { code, fee_address } => {
+            register_code(deps, info, code, fee_address)
+        }
+        ExecuteMsg::UpdateFeeAddress { code, fee_address } => {
+            update_fee_address(deps, info, code, fee_address)
+        }
+        ExecuteMsg::RecordSwap {
+            code,
+            offer_asset,
+            referral_fee,
+        } => record_swap(deps, info, code, offer_asset, referral_fee),
+        ExecuteMsg::UpdateConfig { commission_share } => {
+            update_config(deps, info, commission_share)
+        }
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Registers a new referral code. The sender becomes the code owner.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **code** is an object of type [`String`]. This is the referral code to register.
+///
+/// * **fee_address** is an object of type [`String`]. This is the address that receives the referral fees.
+fn register_code(
+    deps: DepsMut,
+    info: MessageInfo,
+    code: String,
+    fee_address: String,
+) -> Result<Response, ContractError> {
+    validate_code(&code)?;
+
+    if CODES.has(deps.storage, &code) {
+        return Err(ContractError::CodeAlreadyExists(code));
+    }
+
+    let fee_address = addr_validate_to_lower(deps.api, &fee_address)?;
+    CODES.save(
+        deps.storage,
+        &code,
+        &CodeInfo {
+            owner: info.sender.clone(),
+            fee_address: fee_address.clone(),
+            swap_count: 0,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_code"),
+        attr("code", code),
+        attr("owner", info.sender),
+        attr("fee_address", fee_address),
+    ]))
+}
+
+/// ## Description
+/// Updates the address that receives the referral fees of a code.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **code** is an object of type [`String`]. This is the referral code to update.
+///
+/// * **fee_address** is an object of type [`String`]. This is the new address that receives the referral fees.
+///
+/// ## Executor
+/// Only the code owner can execute this.
+fn update_fee_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    code: String,
+    fee_address: String,
+) -> Result<Response, ContractError> {
+    let mut code_info = load_code(deps.as_ref(), &code)?;
+
+    // Permission check
+    if info.sender != code_info.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    code_info.fee_address = addr_validate_to_lower(deps.api, &fee_address)?;
+    CODES.save(deps.storage, &code, &code_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_fee_address"),
+        attr("code", code),
+        attr("fee_address", code_info.fee_address),
+    ]))
+}
+
+/// ## Description
+/// Adds a referred swap to the statistics of a code.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **code** is an object of type [`String`]. This is the referral code used in the swap.
+///
+/// * **offer_asset** is an object of type [`Asset`]. This is the asset offered in the swap.
+///
+/// * **referral_fee** is an object of type [`Asset`]. This is the referral fee paid in the swap.
+///
+/// ## Executor
+/// Only pairs registered in the factory can execute this.
+fn record_swap(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    code: String,
+    offer_asset: Asset,
+    referral_fee: Asset,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !is_factory_pair(deps.as_ref(), &config.factory, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut code_info = load_code(deps.as_ref(), &code)?;
+    code_info.swap_count += 1;
+    CODES.save(deps.storage, &code, &code_info)?;
+
+    add_asset_stats(deps.branch(), &code, &offer_asset, true)?;
+    add_asset_stats(deps.branch(), &code, &referral_fee, false)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "record_swap"),
+        attr("code", code),
+        attr("pair", info.sender),
+        attr("offer_asset", offer_asset.to_string()),
+        attr("referral_fee", referral_fee.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Adds an asset amount to the statistics of a referral code.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **code** is an object of type [`str`]. This is the referral code to update.
+///
+/// * **asset** is an object of type [`Asset`]. This is the asset amount to add.
+///
+/// * **is_volume** is an object of type [`bool`]. Whether the amount is added to the volume or to the referral fees.
+fn add_asset_stats(
+    deps: DepsMut,
+    code: &str,
+    asset: &Asset,
+    is_volume: bool,
+) -> Result<(), ContractError> {
+    if asset.amount.is_zero() {
+        return Ok(());
+    }
+
+    let asset_key = asset.info.to_string();
+    let mut stats = ASSET_STATS
+        .may_load(deps.storage, (code, &asset_key))?
+        .unwrap_or_else(|| AssetStats {
+            info: asset.info.clone(),
+            volume: Default::default(),
+            referral_fees: Default::default(),
+        });
+
+    if is_volume {
+        stats.volume = stats.volume.checked_add(asset.amount)?;
+    } else {
+        stats.referral_fees = stats.referral_fees.checked_add(asset.amount)?;
+    }
+    ASSET_STATS.save(deps.storage, (code, &asset_key), &stats)?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **commission_share** is an [`Option`] field of type [`Decimal`]. This is the new share of the
+/// swap commission paid to referrers.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    commission_share: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(commission_share) = commission_share {
+        validate_commission_share(commission_share)?;
+        config.commission_share = commission_share;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Returns whether the address is a pair registered in the factory.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **factory** is an object of type [`Addr`]. This is the Astroport factory contract address.
+///
+/// * **sender** is an object of type [`Addr`]. This is the address to check.
+fn is_factory_pair(deps: Deps, factory: &Addr, sender: &Addr) -> bool {
+    deps.querier
+        .query_wasm_smart::<PairInfo>(sender, &PairQueryMsg::Pair {})
+        .and_then(|pair_info| {
+            query_pair_info(&deps.querier, factory.clone(), &pair_info.asset_infos)
+        })
+        .map(|pair_info| pair_info.contract_addr == *sender)
+        .unwrap_or(false)
+}
+
+/// ## Description
+/// Loads a registered referral code. Returns a [`ContractError`] if the code is not registered.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **code** is an object of type [`str`]. This is the referral code to load.
+fn load_code(deps: Deps, code: &str) -> Result<CodeInfo, ContractError> {
+    CODES
+        .may_load(deps.storage, code)?
+        .ok_or_else(|| ContractError::CodeNotFound(code.to_string()))
+}
+
+/// ## Description
+/// Checks that a referral code is 3-32 characters long and only contains lowercase letters, digits, '-' or '_'.
+/// ## Params
+/// * **code** is an object of type [`str`]. This is the referral code to check.
+fn validate_code(code: &str) -> Result<(), ContractError> {
+    if code.len() < MIN_CODE_LENGTH
+        || code.len() > MAX_CODE_LENGTH
+        || !code
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(ContractError::InvalidCode(code.to_string()));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Checks that the commission share doesn't exceed [`MAX_COMMISSION_SHARE`].
+/// ## Params
+/// * **commission_share** is an object of type [`Decimal`]. This is the commission share to check.
+fn validate_commission_share(commission_share: Decimal) -> Result<(), ContractError> {
+    let max_commission_share = Decimal::percent(MAX_COMMISSION_SHARE);
+    if commission_share > max_commission_share {
+        return Err(ContractError::CommissionShareTooHigh(
+            max_commission_share.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::ReferralInfo { code }** Returns information about a referral code using a
+/// [`ReferralInfoResponse`] object.
+///
+/// * **QueryMsg::Stats { code }** Returns the swap statistics of a referral code using a [`StatsResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                factory: config.factory,
+                commission_share: config.commission_share,
+            })
+        }
+        QueryMsg::ReferralInfo { code } => {
+            let config = CONFIG.load(deps.storage)?;
+            let code_info = CODES.load(deps.storage, &code)?;
+            to_binary(&ReferralInfoResponse {
+                owner: code_info.owner,
+                fee_address: code_info.fee_address,
+                commission_share: config.commission_share,
+            })
+        }
+        QueryMsg::Stats { code } => to_binary(&query_stats(deps, code)?),
+    }
+}
+
+/// ## Description
+/// Returns the swap statistics of a referral code in a [`StatsResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **code** is an object of type [`String`]. This is the referral code to query.
+fn query_stats(deps: Deps, code: String) -> StdResult<StatsResponse> {
+    let code_info = CODES.load(deps.storage, &code)?;
+    let assets = ASSET_STATS
+        .prefix(&code)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, stats)| stats))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(StatsResponse {
+        swap_count: code_info.swap_count,
+        assets,
+    })
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
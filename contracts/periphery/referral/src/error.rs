@@ -0,0 +1,31 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes referral registry contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid referral code: {0}. It must be 3-32 lowercase letters, digits, '-' or '_'")]
+    InvalidCode(String),
+
+    #[error("Referral code {0} is already registered")]
+    CodeAlreadyExists(String),
+
+    #[error("Referral code {0} is not registered")]
+    CodeNotFound(String),
+
+    #[error("The commission share can't be higher than {0}")]
+    CommissionShareTooHigh(String),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
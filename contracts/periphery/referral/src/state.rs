@@ -0,0 +1,46 @@
+use astroport::common::OwnershipProposal;
+use astroport::referral::AssetStats;
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the referral registry contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport factory contract address
+    pub factory: Addr,
+    /// The share of the swap commission paid to the address registered for a referral code
+    pub commission_share: Decimal,
+}
+
+/// ## Description
+/// This structure describes a registered referral code.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CodeInfo {
+    /// The address that registered the code
+    pub owner: Addr,
+    /// The address that receives the referral fees
+    pub fee_address: Addr,
+    /// The amount of referred swaps
+    pub swap_count: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores registered referral codes
+pub const CODES: Map<&str, CodeInfo> = Map::new("codes");
+
+/// ## Description
+/// Stores the swap statistics of referral codes, keyed by code and asset
+pub const ASSET_STATS: Map<(&str, &str), AssetStats> = Map::new("asset_stats");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,268 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, Decimal, OwnedDeps, Uint128};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::referral::{
+    AssetStats, ExecuteMsg, InstantiateMsg, QueryMsg, ReferralInfoResponse, StatsResponse,
+};
+
+type ReferralDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn native_asset(denom: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::from(amount),
+    }
+}
+
+fn pair_info(contract_addr: &str) -> PairInfo {
+    PairInfo {
+        asset_infos: [native_asset("uusd", 0).info, native_asset("uluna", 0).info],
+        contract_addr: Addr::unchecked(contract_addr),
+        liquidity_token: Addr::unchecked("lp_token"),
+        pair_type: PairType::Xyk {},
+    }
+}
+
+fn setup() -> ReferralDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_factory("factory");
+    deps.querier.set_pair(pair_info("pair"), true);
+    // Reports the same assets as a registered pair but isn't registered in the factory itself
+    deps.querier.set_pair(pair_info("fake_pair"), false);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            factory: String::from("factory"),
+            commission_share: Decimal::percent(20),
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("frontend", &[]),
+        ExecuteMsg::RegisterCode {
+            code: String::from("frontend"),
+            fee_address: String::from("frontend_treasury"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn query_referral_info(deps: &ReferralDeps, code: &str) -> ReferralInfoResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ReferralInfo {
+                code: code.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn record_swap_msg(offer_amount: u128, fee_amount: u128) -> ExecuteMsg {
+    ExecuteMsg::RecordSwap {
+        code: String::from("frontend"),
+        offer_asset: native_asset("uusd", offer_amount),
+        referral_fee: native_asset("uluna", fee_amount),
+    }
+}
+
+#[test]
+fn register_code() {
+    let mut deps = setup();
+
+    assert_eq!(
+        query_referral_info(&deps, "frontend"),
+        ReferralInfoResponse {
+            owner: Addr::unchecked("frontend"),
+            fee_address: Addr::unchecked("frontend_treasury"),
+            commission_share: Decimal::percent(20),
+        }
+    );
+
+    for code in ["ab", "Frontend", "front end", &"a".repeat(33)] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &[]),
+            ExecuteMsg::RegisterCode {
+                code: code.to_string(),
+                fee_address: String::from("user"),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidCode(code.to_string()));
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::RegisterCode {
+            code: String::from("frontend"),
+            fee_address: String::from("user"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CodeAlreadyExists(String::from("frontend"))
+    );
+
+    // Only the code owner can redirect its fees
+    let update_msg = ExecuteMsg::UpdateFeeAddress {
+        code: String::from("frontend"),
+        fee_address: String::from("user"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        update_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("frontend", &[]),
+        update_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        query_referral_info(&deps, "frontend").fee_address,
+        Addr::unchecked("user")
+    );
+}
+
+#[test]
+fn record_swap() {
+    let mut deps = setup();
+
+    for sender in ["user", "fake_pair"] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            record_swap_msg(1000, 6),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pair", &[]),
+        ExecuteMsg::RecordSwap {
+            code: String::from("unknown"),
+            offer_asset: native_asset("uusd", 1000),
+            referral_fee: native_asset("uluna", 6),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CodeNotFound(String::from("unknown")));
+
+    for (offer_amount, fee_amount) in [(1000, 6), (500, 3), (10, 0)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pair", &[]),
+            record_swap_msg(offer_amount, fee_amount),
+        )
+        .unwrap();
+    }
+
+    let stats: StatsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Stats {
+                code: String::from("frontend"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            swap_count: 3,
+            assets: vec![
+                AssetStats {
+                    info: native_asset("uluna", 0).info,
+                    volume: Uint128::zero(),
+                    referral_fees: Uint128::from(9u128),
+                },
+                AssetStats {
+                    info: native_asset("uusd", 0).info,
+                    volume: Uint128::from(1510u128),
+                    referral_fees: Uint128::zero(),
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::UpdateConfig {
+            commission_share: Some(Decimal::percent(30)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            commission_share: Some(Decimal::percent(51)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CommissionShareTooHigh(String::from("0.5"))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            commission_share: Some(Decimal::percent(30)),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_referral_info(&deps, "frontend").commission_share,
+        Decimal::percent(30)
+    );
+}
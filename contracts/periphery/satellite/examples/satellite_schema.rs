@@ -0,0 +1,29 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
+
+use astroport::satellite::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, ProposalStatus, QueryMsg, SatelliteAck,
+    SatelliteMsg,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema_with_title(&schema_for!(InstantiateMsg), &out_dir, "InstantiateMsg");
+    export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
+    export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(ProposalStatus),
+        &out_dir,
+        "ProposalStatusResponse",
+    );
+    export_schema_with_title(&schema_for!(SatelliteMsg), &out_dir, "SatelliteMsg");
+    export_schema_with_title(&schema_for!(SatelliteAck), &out_dir, "SatelliteAck");
+}
@@ -0,0 +1,451 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL, PROPOSALS, QUEUED_PROPOSALS};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::satellite::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, ProposalStatus, QueryMsg,
+    QueuedProposalResponse, SatelliteAck,
+};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use std::convert::TryInto;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-satellite";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The maximum limit for reading queued proposals
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading queued proposals
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        main_controller_port: msg.main_controller_port,
+        gov_channel: None,
+        timelock_period: msg.timelock_period,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::UpdateConfig { main_controller_port, gov_channel, timelock_period }** Updates general contract parameters.
+///
+/// * **ExecuteMsg::ExecuteProposal { id, messages }** Executes the messages of a proposal
+/// received from the hub chain. Only the contract itself can call this.
+///
+/// * **ExecuteMsg::ExecuteQueuedProposal { id }** Executes a queued proposal once its timelock has passed.
+///
+/// * **ExecuteMsg::CancelProposal { id }** Removes a queued proposal without executing it.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            main_controller_port,
+            gov_channel,
+            timelock_period,
+        } => update_config(
+            deps,
+            info,
+            main_controller_port,
+            gov_channel,
+            timelock_period,
+        ),
+        ExecuteMsg::ExecuteProposal { id, messages } => execute_proposal(env, info, id, messages),
+        ExecuteMsg::ExecuteQueuedProposal { id } => execute_queued_proposal(deps, env, id),
+        ExecuteMsg::CancelProposal { id } => cancel_proposal(deps, info, id),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Updates general contract parameters. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **main_controller_port** is an [`Option`] field of type [`String`]. This is the IBC port of the Assembly contract on the hub chain.
+///
+/// * **gov_channel** is an [`Option`] field of type [`String`]. This is the IBC channel used to receive proposals from the hub chain.
+///
+/// * **timelock_period** is an [`Option`] field of type [`u64`]. This is the delay (in seconds) between
+/// receiving a proposal and the earliest time it can be executed.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    main_controller_port: Option<String>,
+    gov_channel: Option<String>,
+    timelock_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attributes = vec![attr("action", "update_config")];
+
+    if let Some(main_controller_port) = main_controller_port {
+        attributes.push(attr("main_controller_port", &main_controller_port));
+        config.main_controller_port = main_controller_port;
+    }
+
+    if let Some(gov_channel) = gov_channel {
+        if !gov_channel.starts_with("channel-") {
+            return Err(ContractError::InvalidGovChannel(gov_channel));
+        }
+
+        attributes.push(attr("gov_channel", &gov_channel));
+        config.gov_channel = Some(gov_channel);
+    }
+
+    if let Some(timelock_period) = timelock_period {
+        attributes.push(attr("timelock_period", timelock_period.to_string()));
+        config.timelock_period = timelock_period;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Executes the messages of a proposal received from the hub chain. The result of the execution
+/// is handled in [`reply`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **id** is an object of type [`u64`]. This is the proposal identifier on the hub chain.
+///
+/// * **messages** is a vector of type [`CosmosMsg`]. These are the proposal messages to execute.
+///
+/// ## Executor
+/// Only the contract itself can execute this.
+fn execute_proposal(
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    messages: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "execute_proposal"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Executes the messages of a queued proposal whose timelock has passed. The result of the execution
+/// is handled in [`reply`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **id** is an object of type [`u64`]. This is the proposal identifier on the hub chain.
+fn execute_queued_proposal(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let proposal = QUEUED_PROPOSALS
+        .may_load(deps.storage, U64Key::new(id))?
+        .ok_or(ContractError::ProposalNotQueued(id))?;
+
+    if env.block.time.seconds() < proposal.executable_at {
+        return Err(ContractError::ProposalTimelocked(
+            id,
+            proposal.executable_at,
+        ));
+    }
+
+    QUEUED_PROPOSALS.remove(deps.storage, U64Key::new(id));
+
+    Ok(Response::new()
+        .add_submessage(execute_proposal_msg(&env, id, proposal.messages)?)
+        .add_attributes(vec![
+            attr("action", "execute_queued_proposal"),
+            attr("proposal_id", id.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Removes a queued proposal without executing it. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **id** is an object of type [`u64`]. This is the proposal identifier on the hub chain.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn cancel_proposal(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if QUEUED_PROPOSALS
+        .may_load(deps.storage, U64Key::new(id))?
+        .is_none()
+    {
+        return Err(ContractError::ProposalNotQueued(id));
+    }
+
+    QUEUED_PROPOSALS.remove(deps.storage, U64Key::new(id));
+    PROPOSALS.save(deps.storage, U64Key::new(id), &ProposalStatus::Canceled {})?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_proposal"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Returns a submessage that executes the proposal messages through [`ExecuteMsg::ExecuteProposal`].
+/// The reply identifier is the proposal identifier.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **id** is an object of type [`u64`]. This is the proposal identifier on the hub chain.
+///
+/// * **messages** is a vector of type [`CosmosMsg`]. These are the proposal messages to execute.
+pub(crate) fn execute_proposal_msg(
+    env: &Env,
+    id: u64,
+    messages: Vec<CosmosMsg>,
+) -> StdResult<SubMsg> {
+    Ok(SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::ExecuteProposal { id, messages })?,
+            funds: vec![],
+        },
+        id,
+    ))
+}
+
+/// ## Description
+/// Stores the execution status of a proposal and overwrites the acknowledgement sent back
+/// to the hub chain with the execution result. The reply identifier is the proposal identifier.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`Reply`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let (status, ack) = match msg.result {
+        ContractResult::Ok(_) => (
+            ProposalStatus::Executed {},
+            SatelliteAck::Result(Binary::default()),
+        ),
+        ContractResult::Err(error) => (
+            ProposalStatus::Failed {
+                error: error.clone(),
+            },
+            SatelliteAck::Error(error),
+        ),
+    };
+
+    PROPOSALS.save(deps.storage, U64Key::new(msg.id), &status)?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&ack)?)
+        .add_attributes(vec![
+            attr("action", "proposal_result"),
+            attr("proposal_id", msg.id.to_string()),
+            attr(
+                "status",
+                match status {
+                    ProposalStatus::Executed {} => "executed",
+                    _ => "failed",
+                },
+            ),
+        ]))
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::ProposalStatus { id }** Returns the execution status of a proposal using a [`ProposalStatus`] object.
+///
+/// * **QueryMsg::QueuedProposals { start_after, limit }** Returns proposals that wait for their timelock
+/// to pass using a vector of [`QueuedProposalResponse`] objects.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                main_controller_port: config.main_controller_port,
+                gov_channel: config.gov_channel,
+                timelock_period: config.timelock_period,
+            })
+        }
+        QueryMsg::ProposalStatus { id } => {
+            to_binary(&PROPOSALS.load(deps.storage, U64Key::new(id))?)
+        }
+        QueryMsg::QueuedProposals { start_after, limit } => {
+            to_binary(&query_queued_proposals(deps, start_after, limit)?)
+        }
+    }
+}
+
+/// ## Description
+/// Returns queued proposals in ascending order of their identifiers.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. This is the identifier after which to start reading.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the number of proposals to read.
+fn query_queued_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<QueuedProposalResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    QUEUED_PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, proposal) = item?;
+            let id = u64::from_be_bytes(
+                id.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid proposal key"))?,
+            );
+            Ok(QueuedProposalResponse {
+                id,
+                messages: proposal.messages,
+                executable_at: proposal.executable_at,
+            })
+        })
+        .collect()
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
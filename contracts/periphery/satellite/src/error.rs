@@ -0,0 +1,37 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes satellite contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid IBC channel ordering. Expected {0}")]
+    InvalidChannelOrder(String),
+
+    #[error("Invalid IBC channel version {0}. Expected {1}")]
+    InvalidChannelVersion(String, String),
+
+    #[error("Invalid source port {0}. Expected {1}")]
+    InvalidSourcePort(String, String),
+
+    #[error("Invalid IBC channel {0}")]
+    InvalidGovChannel(String),
+
+    #[error("Governance channel {0} is already established")]
+    ChannelAlreadyEstablished(String),
+
+    #[error("Proposal {0} was already processed")]
+    ProposalAlreadyProcessed(u64),
+
+    #[error("Proposal {0} is not queued")]
+    ProposalNotQueued(u64),
+
+    #[error("Proposal {0} can't be executed before {1}")]
+    ProposalTimelocked(u64, u64),
+}
@@ -0,0 +1,254 @@
+use crate::contract::execute_proposal_msg;
+use crate::error::ContractError;
+use crate::state::{QueuedProposal, CONFIG, PROPOSALS, QUEUED_PROPOSALS};
+use astroport::satellite::{ProposalStatus, SatelliteAck, SatelliteMsg, SATELLITE_IBC_VERSION};
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Binary, Deps, DepsMut, Env, IbcBasicResponse,
+    IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcOrder,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, StdResult,
+};
+use cw_storage_plus::U64Key;
+
+/// ## Description
+/// Validates a new channel. Only unordered channels with the satellite IBC version that are opened
+/// by the Assembly on the hub chain are accepted, and only one governance channel can exist.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelOpenMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    validate_channel(deps.as_ref(), msg.channel(), msg.counterparty_version())
+}
+
+/// ## Description
+/// Stores the newly connected channel as the governance channel.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelConnectMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    validate_channel(deps.as_ref(), channel, msg.counterparty_version())?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.gov_channel = Some(channel.endpoint.channel_id.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        attr("action", "ibc_connect"),
+        attr("channel_id", &channel.endpoint.channel_id),
+    ]))
+}
+
+/// ## Description
+/// Removes the governance channel when it is closed, so that a new one can be opened.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelCloseMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = &msg.channel().endpoint.channel_id;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.gov_channel.as_ref() == Some(channel_id) {
+        config.gov_channel = None;
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        attr("action", "ibc_close"),
+        attr("channel_id", channel_id),
+    ]))
+}
+
+/// ## Description
+/// Handles a packet sent by the Assembly on the hub chain. Proposal messages are executed in a
+/// submessage, so that a failed proposal is acknowledged with an error instead of reverting the
+/// packet. If a timelock period is set, proposals are queued instead and executed later with
+/// [`ExecuteMsg::ExecuteQueuedProposal`](astroport::satellite::ExecuteMsg::ExecuteQueuedProposal).
+/// Invalid packets are acknowledged with an error as well.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcPacketReceiveMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    do_packet_receive(deps, env, msg).or_else(|err| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(ack_error(err.to_string())?)
+            .add_attributes(vec![
+                attr("action", "receive_packet"),
+                attr("error", err.to_string()),
+            ]))
+    })
+}
+
+/// ## Description
+/// Validates the packet source and schedules the execution of the proposal messages.
+/// The acknowledgement is overwritten with the execution result in the contract's `reply`
+/// unless the proposal is queued.
+fn do_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let packet = msg.packet;
+
+    if config.gov_channel.as_ref() != Some(&packet.dest.channel_id) {
+        return Err(ContractError::InvalidGovChannel(packet.dest.channel_id));
+    }
+
+    if packet.src.port_id != config.main_controller_port {
+        return Err(ContractError::InvalidSourcePort(
+            packet.src.port_id,
+            config.main_controller_port,
+        ));
+    }
+
+    match from_binary(&packet.data)? {
+        SatelliteMsg::ExecuteProposal { id, messages } => {
+            // Replay protection
+            if PROPOSALS.may_load(deps.storage, U64Key::new(id))?.is_some() {
+                return Err(ContractError::ProposalAlreadyProcessed(id));
+            }
+
+            if config.timelock_period > 0 {
+                let executable_at = env.block.time.seconds() + config.timelock_period;
+
+                QUEUED_PROPOSALS.save(
+                    deps.storage,
+                    U64Key::new(id),
+                    &QueuedProposal {
+                        messages,
+                        executable_at,
+                    },
+                )?;
+                PROPOSALS.save(
+                    deps.storage,
+                    U64Key::new(id),
+                    &ProposalStatus::Queued { executable_at },
+                )?;
+
+                return Ok(IbcReceiveResponse::new()
+                    .set_ack(ack_success()?)
+                    .add_attributes(vec![
+                        attr("action", "receive_packet"),
+                        attr("proposal_id", id.to_string()),
+                        attr("executable_at", executable_at.to_string()),
+                    ]));
+            }
+
+            Ok(IbcReceiveResponse::new()
+                .set_ack(ack_success()?)
+                .add_submessage(execute_proposal_msg(&env, id, messages)?)
+                .add_attributes(vec![
+                    attr("action", "receive_packet"),
+                    attr("proposal_id", id.to_string()),
+                ]))
+        }
+    }
+}
+
+/// ## Description
+/// The satellite never sends packets, so acknowledgements are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// ## Description
+/// The satellite never sends packets, so timeouts are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// ## Description
+/// Checks the channel ordering, version and counterparty port. Returns a [`ContractError`]
+/// if the channel can't be used to receive proposals.
+fn validate_channel(
+    deps: Deps,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(gov_channel) = config.gov_channel {
+        if gov_channel != channel.endpoint.channel_id {
+            return Err(ContractError::ChannelAlreadyEstablished(gov_channel));
+        }
+    }
+
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidChannelOrder(String::from(
+            "ORDER_UNORDERED",
+        )));
+    }
+
+    for version in [Some(channel.version.as_str()), counterparty_version]
+        .into_iter()
+        .flatten()
+    {
+        if version != SATELLITE_IBC_VERSION {
+            return Err(ContractError::InvalidChannelVersion(
+                version.to_string(),
+                SATELLITE_IBC_VERSION.to_string(),
+            ));
+        }
+    }
+
+    if channel.counterparty_endpoint.port_id != config.main_controller_port {
+        return Err(ContractError::InvalidSourcePort(
+            channel.counterparty_endpoint.port_id.clone(),
+            config.main_controller_port,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns a successful acknowledgement
+fn ack_success() -> StdResult<Binary> {
+    to_binary(&SatelliteAck::Result(Binary::default()))
+}
+
+/// Returns an acknowledgement with the specified error
+fn ack_error(error: String) -> StdResult<Binary> {
+    to_binary(&SatelliteAck::Error(error))
+}
@@ -0,0 +1,48 @@
+use astroport::common::OwnershipProposal;
+use astroport::satellite::ProposalStatus;
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the satellite contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The IBC port of the Assembly contract on the hub chain
+    pub main_controller_port: String,
+    /// The IBC channel used to receive proposals from the hub chain
+    pub gov_channel: Option<String>,
+    /// The delay (in seconds) between receiving a proposal and the earliest time it can be executed
+    #[serde(default)]
+    pub timelock_period: u64,
+}
+
+/// ## Description
+/// This structure describes a proposal that waits for its timelock to pass.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedProposal {
+    /// The messages to execute
+    pub messages: Vec<CosmosMsg>,
+    /// The timestamp (in seconds) after which the proposal can be executed
+    pub executable_at: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// ## Description
+/// Stores the execution status of every proposal received from the hub chain.
+/// Proposals that are already in this map are never executed again
+pub const PROPOSALS: Map<U64Key, ProposalStatus> = Map::new("proposals");
+
+/// ## Description
+/// Stores proposals that wait for their timelock to pass
+pub const QUEUED_PROPOSALS: Map<U64Key, QueuedProposal> = Map::new("queued_proposals");
@@ -0,0 +1,504 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_channel_close_confirm,
+    mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_recv, mock_info,
+    MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, BankMsg, Binary, ContractResult, CosmosMsg, IbcChannelOpenMsg,
+    IbcOrder, OwnedDeps, Reply, ReplyOn, SubMsgExecutionResponse, WasmMsg,
+};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::ibc::{ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_receive};
+use astroport::satellite::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, ProposalStatus, QueryMsg, QueuedProposalResponse,
+    SatelliteAck, SatelliteMsg, SATELLITE_IBC_VERSION,
+};
+
+/// The port used by `mock_ibc_channel` as the counterparty port
+const HUB_PORT: &str = "their_port";
+const GOV_CHANNEL: &str = "channel-0";
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            main_controller_port: String::from(HUB_PORT),
+            timelock_period: 0,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn connect(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(GOV_CHANNEL, IbcOrder::Unordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap();
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_connect_ack(GOV_CHANNEL, IbcOrder::Unordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap();
+}
+
+fn query_config(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> ConfigResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap()
+}
+
+fn proposal_packet(id: u64) -> SatelliteMsg {
+    SatelliteMsg::ExecuteProposal {
+        id,
+        messages: vec![CosmosMsg::Bank(BankMsg::Burn { amount: vec![] })],
+    }
+}
+
+#[test]
+fn channel_handshake() {
+    let mut deps = setup();
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(GOV_CHANNEL, IbcOrder::Ordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidChannelOrder(String::from("ORDER_UNORDERED"))
+    );
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(GOV_CHANNEL, IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidChannelVersion(
+            String::from("ics20-1"),
+            String::from(SATELLITE_IBC_VERSION)
+        )
+    );
+
+    // Only the Assembly on the hub chain can open a channel
+    let mut channel = mock_ibc_channel(GOV_CHANNEL, IbcOrder::Unordered, SATELLITE_IBC_VERSION);
+    channel.counterparty_endpoint.port_id = String::from("wasm.attacker");
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        IbcChannelOpenMsg::new_init(channel),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidSourcePort(String::from("wasm.attacker"), String::from(HUB_PORT))
+    );
+
+    connect(&mut deps);
+    assert_eq!(
+        query_config(&deps).gov_channel,
+        Some(String::from(GOV_CHANNEL))
+    );
+
+    // A second governance channel can't be opened
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init("channel-1", IbcOrder::Unordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChannelAlreadyEstablished(String::from(GOV_CHANNEL))
+    );
+
+    // Closing the governance channel allows a new one to be opened
+    ibc_channel_close(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_close_confirm(GOV_CHANNEL, IbcOrder::Unordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap();
+    assert_eq!(query_config(&deps).gov_channel, None);
+
+    ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init("channel-1", IbcOrder::Unordered, SATELLITE_IBC_VERSION),
+    )
+    .unwrap();
+}
+
+#[test]
+fn receive_proposal() {
+    let mut deps = setup();
+    connect(&mut deps);
+
+    let mut msg = mock_ibc_packet_recv(GOV_CHANNEL, &proposal_packet(1)).unwrap();
+    msg.packet.src.port_id = String::from(HUB_PORT);
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+        SatelliteAck::Result(Binary::default())
+    );
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, 1);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Always);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            msg: to_binary(&ExecuteMsg::ExecuteProposal {
+                id: 1,
+                messages: vec![CosmosMsg::Bank(BankMsg::Burn { amount: vec![] })],
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Proposal messages can only be executed by the contract itself
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteProposal {
+            id: 1,
+            messages: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::ExecuteProposal {
+            id: 1,
+            messages: vec![CosmosMsg::Bank(BankMsg::Burn { amount: vec![] })],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.data.unwrap()).unwrap(),
+        SatelliteAck::Result(Binary::default())
+    );
+
+    let status: ProposalStatus = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProposalStatus { id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(status, ProposalStatus::Executed {});
+
+    // The same proposal can't be executed twice
+    let mut msg = mock_ibc_packet_recv(GOV_CHANNEL, &proposal_packet(1)).unwrap();
+    msg.packet.src.port_id = String::from(HUB_PORT);
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+        SatelliteAck::Error(ContractError::ProposalAlreadyProcessed(1).to_string())
+    );
+}
+
+#[test]
+fn timelocked_proposal() {
+    let mut deps = setup();
+    connect(&mut deps);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            main_controller_port: None,
+            gov_channel: None,
+            timelock_period: Some(86400),
+        },
+    )
+    .unwrap();
+
+    let env = mock_env();
+    let executable_at = env.block.time.seconds() + 86400;
+
+    // Proposals are queued instead of being executed
+    for id in [1, 2] {
+        let mut msg = mock_ibc_packet_recv(GOV_CHANNEL, &proposal_packet(id)).unwrap();
+        msg.packet.src.port_id = String::from(HUB_PORT);
+        let res = ibc_packet_receive(deps.as_mut(), env.clone(), msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+            SatelliteAck::Result(Binary::default())
+        );
+    }
+
+    let status: ProposalStatus = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalStatus { id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(status, ProposalStatus::Queued { executable_at });
+
+    let queued: Vec<QueuedProposalResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueuedProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        queued.iter().map(|p| p.id).collect::<Vec<_>>(),
+        vec![1u64, 2u64]
+    );
+    assert_eq!(queued[0].executable_at, executable_at);
+
+    // A queued proposal can't be received again
+    let mut msg = mock_ibc_packet_recv(GOV_CHANNEL, &proposal_packet(1)).unwrap();
+    msg.packet.src.port_id = String::from(HUB_PORT);
+    let res = ibc_packet_receive(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+        SatelliteAck::Error(ContractError::ProposalAlreadyProcessed(1).to_string())
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedProposal { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalTimelocked(1, executable_at));
+
+    // Only the owner can cancel queued proposals
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::CancelProposal { id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CancelProposal { id: 2 },
+    )
+    .unwrap();
+
+    let status: ProposalStatus = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalStatus { id: 2 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(status, ProposalStatus::Canceled {});
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedProposal { id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotQueued(2));
+
+    // Anyone can execute a proposal once its timelock has passed
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedProposal { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, 1);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Always);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            msg: to_binary(&ExecuteMsg::ExecuteProposal {
+                id: 1,
+                messages: vec![CosmosMsg::Bank(BankMsg::Burn { amount: vec![] })],
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let queued: Vec<QueuedProposalResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::QueuedProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(queued.is_empty());
+}
+
+#[test]
+fn failed_proposal() {
+    let mut deps = setup();
+    connect(&mut deps);
+
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 2,
+            result: ContractResult::Err(String::from("Unauthorized")),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.data.unwrap()).unwrap(),
+        SatelliteAck::Error(String::from("Unauthorized"))
+    );
+
+    let status: ProposalStatus = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ProposalStatus { id: 2 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        status,
+        ProposalStatus::Failed {
+            error: String::from("Unauthorized")
+        }
+    );
+}
+
+#[test]
+fn reject_unauthorized_packets() {
+    let mut deps = setup();
+    connect(&mut deps);
+
+    // Wrong channel
+    let mut msg = mock_ibc_packet_recv("channel-5", &proposal_packet(1)).unwrap();
+    msg.packet.src.port_id = String::from(HUB_PORT);
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+        SatelliteAck::Error(
+            ContractError::InvalidGovChannel(String::from("channel-5")).to_string()
+        )
+    );
+
+    // Wrong source port
+    let msg = mock_ibc_packet_recv(GOV_CHANNEL, &proposal_packet(1)).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        from_binary::<SatelliteAck>(&res.acknowledgement).unwrap(),
+        SatelliteAck::Error(
+            ContractError::InvalidSourcePort(String::from("their-port"), String::from(HUB_PORT))
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        main_controller_port: Some(String::from("wasm.assembly")),
+        gov_channel: Some(String::from("channel-3")),
+        timelock_period: Some(86400),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            main_controller_port: None,
+            gov_channel: Some(String::from("connection-3")),
+            timelock_period: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidGovChannel(String::from("connection-3"))
+    );
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let config = query_config(&deps);
+    assert_eq!(config.main_controller_port, "wasm.assembly");
+    assert_eq!(config.gov_channel, Some(String::from("channel-3")));
+    assert_eq!(config.timelock_period, 86400);
+}
@@ -0,0 +1,550 @@
+use crate::error::ContractError;
+use crate::migration::CONFIGV100;
+use crate::state::{Config, ScheduledMsgs, CONFIG, NEXT_ID, SCHEDULED};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::assert_migration_allowed;
+use astroport::timelock::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ScheduledMsgsResponse,
+};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, U64Key};
+use std::convert::TryInto;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-timelock";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading scheduled batches
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading scheduled batches
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admins = validate_admins(deps.api, &msg.admins)?;
+    validate_threshold(&admins, msg.threshold)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admins,
+            threshold: msg.threshold,
+            execution_delay: msg.execution_delay,
+        },
+    )?;
+    NEXT_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Schedule { msgs }** Schedules messages that can be executed once they're approved
+/// and the execution delay has passed.
+///
+/// * **ExecuteMsg::Approve { id }** Approves scheduled messages.
+///
+/// * **ExecuteMsg::Execute { id }** Dispatches scheduled messages.
+///
+/// * **ExecuteMsg::Cancel { id }** Removes scheduled messages.
+///
+/// * **ExecuteMsg::UpdateAdmins { admins, threshold }** Replaces the admin set and the approval threshold.
+///
+/// * **ExecuteMsg::UpdateExecutionDelay { execution_delay }** Updates the execution delay.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Schedule { msgs } => schedule(deps, env, info, msgs),
+        ExecuteMsg::Approve { id } => approve(deps, env, info, id),
+        ExecuteMsg::Execute { id } => execute_scheduled(deps, env, info, id),
+        ExecuteMsg::Cancel { id } => cancel(deps, info, id),
+        ExecuteMsg::UpdateAdmins { admins, threshold } => {
+            update_admins(deps, env, info, admins, threshold)
+        }
+        ExecuteMsg::UpdateExecutionDelay { execution_delay } => {
+            update_execution_delay(deps, env, info, execution_delay)
+        }
+    }
+}
+
+/// ## Description
+/// Schedules a batch of messages that can be executed once it's approved and the execution delay has passed.
+/// Scheduling counts as the sender's approval. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msgs** is a vector of type [`CosmosMsg`]. These are the messages to schedule.
+///
+/// ## Executor
+/// Only an admin can execute this.
+fn schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if msgs.is_empty() {
+        return Err(ContractError::NoMessages {});
+    }
+
+    let id = NEXT_ID.load(deps.storage)?;
+    NEXT_ID.save(deps.storage, &(id + 1))?;
+
+    let approvals = vec![info.sender.clone()];
+    let executable_after = executable_after(&env, &config, &approvals);
+    SCHEDULED.save(
+        deps.storage,
+        U64Key::new(id),
+        &ScheduledMsgs {
+            msgs,
+            scheduled_by: info.sender.clone(),
+            approvals,
+            executable_after,
+        },
+    )?;
+
+    let mut attributes = vec![
+        attr("action", "schedule"),
+        attr("id", id.to_string()),
+        attr("scheduled_by", info.sender),
+    ];
+    if let Some(executable_after) = executable_after {
+        attributes.push(attr("executable_after", executable_after.to_string()));
+    }
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Approves a batch of scheduled messages. The execution delay starts once the approval threshold is reached.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **id** is an object of type [`u64`]. This is the identifier of the batch.
+///
+/// ## Executor
+/// Only an admin can execute this.
+fn approve(deps: DepsMut, env: Env, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut scheduled = SCHEDULED.load(deps.storage, U64Key::new(id))?;
+    if scheduled.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    scheduled.approvals.push(info.sender.clone());
+
+    // Later approvals don't restart the execution delay
+    if scheduled.executable_after.is_none() {
+        scheduled.executable_after = executable_after(&env, &config, &scheduled.approvals);
+    }
+    SCHEDULED.save(deps.storage, U64Key::new(id), &scheduled)?;
+
+    let mut attributes = vec![
+        attr("action", "approve"),
+        attr("id", id.to_string()),
+        attr("approved_by", info.sender),
+    ];
+    if let Some(executable_after) = scheduled.executable_after {
+        attributes.push(attr("executable_after", executable_after.to_string()));
+    }
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Returns the timestamp after which a batch can be executed if it has enough approvals, otherwise returns [`None`].
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **approvals** is a slice of type [`Addr`]. These are the admins that approved the batch.
+fn executable_after(env: &Env, config: &Config, approvals: &[Addr]) -> Option<u64> {
+    if config.is_approved(approvals) {
+        Some(env.block.time.seconds() + config.execution_delay)
+    } else {
+        None
+    }
+}
+
+/// ## Description
+/// Dispatches a batch of scheduled messages whose execution delay has passed and removes it.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **id** is an object of type [`u64`]. This is the identifier of the batch.
+///
+/// ## Executor
+/// Only an admin can execute this.
+fn execute_scheduled(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let scheduled = SCHEDULED.load(deps.storage, U64Key::new(id))?;
+    let executable_after = scheduled
+        .executable_after
+        .ok_or(ContractError::NotApproved {})?;
+    if env.block.time.seconds() < executable_after {
+        return Err(ContractError::ExecutionDelayNotPassed(executable_after));
+    }
+    SCHEDULED.remove(deps.storage, U64Key::new(id));
+
+    Ok(Response::new()
+        .add_messages(scheduled.msgs)
+        .add_attributes(vec![
+            attr("action", "execute"),
+            attr("id", id.to_string()),
+            attr("executed_by", info.sender),
+        ]))
+}
+
+/// ## Description
+/// Removes a batch of scheduled messages without dispatching them. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **id** is an object of type [`u64`]. This is the identifier of the batch.
+///
+/// ## Executor
+/// Only an admin can execute this.
+fn cancel(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Make sure the batch exists
+    SCHEDULED.load(deps.storage, U64Key::new(id))?;
+    SCHEDULED.remove(deps.storage, U64Key::new(id));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel"),
+        attr("id", id.to_string()),
+        attr("cancelled_by", info.sender),
+    ]))
+}
+
+/// ## Description
+/// Replaces the admin set and the approval threshold. Batches that already reached the previous threshold
+/// keep their execution time. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **admins** is a vector of type [`String`]. These are the new admins.
+///
+/// * **threshold** is an object of type [`u32`]. This is the new amount of approvals needed to execute a batch.
+///
+/// ## Executor
+/// Only the contract itself can execute this.
+fn update_admins(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    admins: Vec<String>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    // Admin changes must go through the execution delay
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let admins = validate_admins(deps.api, &admins)?;
+    validate_threshold(&admins, threshold)?;
+    CONFIG.update::<_, ContractError>(deps.storage, |mut config| {
+        config.admins = admins.clone();
+        config.threshold = threshold;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_admins"),
+        attr(
+            "admins",
+            admins
+                .iter()
+                .map(|admin| admin.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        attr("threshold", threshold.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Updates the execution delay. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **execution_delay** is an object of type [`u64`]. This is the new execution delay in seconds.
+///
+/// ## Executor
+/// Only the contract itself can execute this.
+fn update_execution_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    execution_delay: u64,
+) -> Result<Response, ContractError> {
+    // Delay changes must go through the current execution delay
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    CONFIG.update::<_, ContractError>(deps.storage, |mut config| {
+        config.execution_delay = execution_delay;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_execution_delay"),
+        attr("execution_delay", execution_delay.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Validates the admin addresses. Returns a [`ContractError`] if the list is empty, an address is invalid
+/// or duplicated.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **admins** is a slice of type [`String`]. These are the admin addresses to validate.
+fn validate_admins(api: &dyn Api, admins: &[String]) -> Result<Vec<Addr>, ContractError> {
+    if admins.is_empty() {
+        return Err(ContractError::NoAdmins {});
+    }
+
+    let mut validated: Vec<Addr> = Vec::with_capacity(admins.len());
+    for admin in admins {
+        let admin = addr_validate_to_lower(api, admin)?;
+        if validated.contains(&admin) {
+            return Err(ContractError::DuplicateAdmin(admin.to_string()));
+        }
+        validated.push(admin);
+    }
+
+    Ok(validated)
+}
+
+/// ## Description
+/// Validates the approval threshold. Returns a [`ContractError`] if it's zero or greater than the amount of admins.
+/// ## Params
+/// * **admins** is a slice of type [`Addr`]. These are the validated admin addresses.
+///
+/// * **threshold** is an object of type [`u32`]. This is the amount of approvals needed to execute a batch.
+fn validate_threshold(admins: &[Addr], threshold: u32) -> Result<(), ContractError> {
+    if threshold == 0 || threshold as usize > admins.len() {
+        return Err(ContractError::InvalidThreshold(admins.len()));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Scheduled { id }** Returns a batch of scheduled messages using a [`ScheduledMsgsResponse`] object.
+///
+/// * **QueryMsg::AllScheduled { start_after, limit }** Returns all scheduled batches using a vector
+/// of [`ScheduledMsgsResponse`] objects.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                admins: config.admins,
+                threshold: config.threshold,
+                execution_delay: config.execution_delay,
+            })
+        }
+        QueryMsg::Scheduled { id } => {
+            let scheduled = SCHEDULED.load(deps.storage, U64Key::new(id))?;
+            to_binary(&scheduled_response(id, scheduled))
+        }
+        QueryMsg::AllScheduled { start_after, limit } => {
+            to_binary(&query_all_scheduled(deps, start_after, limit)?)
+        }
+    }
+}
+
+/// ## Description
+/// Returns scheduled batches in ascending order of their identifiers.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. This is the identifier after which to start reading.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the number of batches to read.
+fn query_all_scheduled(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ScheduledMsgsResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    SCHEDULED
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, scheduled) = item?;
+            let id = u64::from_be_bytes(
+                id.as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid scheduled batch key"))?,
+            );
+            Ok(scheduled_response(id, scheduled))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Converts a stored batch into a [`ScheduledMsgsResponse`].
+/// ## Params
+/// * **id** is an object of type [`u64`]. This is the identifier of the batch.
+///
+/// * **scheduled** is an object of type [`ScheduledMsgs`].
+fn scheduled_response(id: u64, scheduled: ScheduledMsgs) -> ScheduledMsgsResponse {
+    ScheduledMsgsResponse {
+        id,
+        msgs: scheduled.msgs,
+        scheduled_by: scheduled.scheduled_by,
+        approvals: scheduled.approvals,
+        executable_after: scheduled.executable_after,
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version. Contracts migrated from version 1.0.0 need a single approval, so
+/// they keep working as before until the admins schedule a higher threshold.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    match contract_version.version.as_ref() {
+        "1.0.0" => {
+            let config_v100 = CONFIGV100.load(deps.storage)?;
+
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    admins: config_v100.admins,
+                    threshold: 1,
+                    execution_delay: config_v100.execution_delay,
+                },
+            )?;
+        }
+        _ => return Err(ContractError::MigrationError {}),
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes timelock contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The admin list can't be empty")]
+    NoAdmins {},
+
+    #[error("Duplicate admin {0}")]
+    DuplicateAdmin(String),
+
+    #[error("The approval threshold must be between 1 and the amount of admins ({0})")]
+    InvalidThreshold(usize),
+
+    #[error("At least one message must be scheduled")]
+    NoMessages {},
+
+    #[error("The admin already approved the scheduled messages")]
+    AlreadyApproved {},
+
+    #[error("The scheduled messages don't have enough approvals")]
+    NotApproved {},
+
+    #[error("The scheduled messages can't be executed before {0}")]
+    ExecutionDelayNotPassed(u64),
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
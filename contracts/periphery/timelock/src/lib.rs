@@ -0,0 +1,7 @@
+pub mod contract;
+pub mod error;
+mod migration;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
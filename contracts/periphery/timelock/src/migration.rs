@@ -0,0 +1,15 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the timelock configuration for version 1.0.0.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV100 {
+    /// Addresses that are allowed to schedule, execute and cancel messages
+    pub admins: Vec<Addr>,
+    /// The delay (in seconds) between scheduling messages and the earliest time they can be executed
+    pub execution_delay: u64,
+}
+
+pub const CONFIGV100: Item<ConfigV100> = Item::new("config");
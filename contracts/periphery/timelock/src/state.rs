@@ -0,0 +1,59 @@
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the timelock contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Addresses that are allowed to schedule, approve, execute and cancel messages
+    pub admins: Vec<Addr>,
+    /// The amount of admin approvals scheduled messages need before the execution delay starts
+    pub threshold: u32,
+    /// The delay (in seconds) between approving messages and the earliest time they can be executed
+    pub execution_delay: u64,
+}
+
+impl Config {
+    /// Returns true if the address is a registered admin.
+    pub fn is_admin(&self, addr: &Addr) -> bool {
+        self.admins.contains(addr)
+    }
+
+    /// Returns true if enough current admins approved a batch.
+    pub fn is_approved(&self, approvals: &[Addr]) -> bool {
+        approvals
+            .iter()
+            .filter(|approval| self.is_admin(approval))
+            .count()
+            >= self.threshold as usize
+    }
+}
+
+/// ## Description
+/// This structure describes a batch of scheduled messages.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledMsgs {
+    /// The messages dispatched when the batch is executed
+    pub msgs: Vec<CosmosMsg>,
+    /// The admin that scheduled the batch
+    pub scheduled_by: Addr,
+    /// The admins that approved the batch. Batches scheduled before approvals were introduced have none
+    #[serde(default)]
+    pub approvals: Vec<Addr>,
+    /// The timestamp (in seconds) after which the batch can be executed. Not set until the batch is approved
+    pub executable_after: Option<u64>,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the identifier assigned to the next scheduled batch
+pub const NEXT_ID: Item<u64> = Item::new("next_id");
+
+/// ## Description
+/// Stores scheduled batches by their identifier
+pub const SCHEDULED: Map<U64Key, ScheduledMsgs> = Map::new("scheduled");
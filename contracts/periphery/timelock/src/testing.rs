@@ -0,0 +1,448 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Env, OwnedDeps, StdError, WasmMsg,
+};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use astroport::timelock::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduledMsgsResponse,
+};
+
+const EXECUTION_DELAY: u64 = 86400;
+
+type TimelockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+fn now() -> u64 {
+    mock_env().block.time.seconds()
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(seconds - env.block.time.seconds());
+    env
+}
+
+fn setup() -> TimelockDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            admins: vec![String::from("alice"), String::from("bob")],
+            threshold: 1,
+            execution_delay: EXECUTION_DELAY,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn send_msg() -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: String::from("treasury"),
+        amount: coins(100, "uusd"),
+    })
+}
+
+fn schedule(
+    deps: &mut TimelockDeps,
+    sender: &str,
+    msgs: Vec<CosmosMsg>,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Schedule { msgs },
+    )
+    .map(|_| ())
+}
+
+fn query_config(deps: &TimelockDeps) -> ConfigResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            admins: vec![],
+            threshold: 1,
+            execution_delay: EXECUTION_DELAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoAdmins {});
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            admins: vec![String::from("alice"), String::from("alice")],
+            threshold: 1,
+            execution_delay: EXECUTION_DELAY,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DuplicateAdmin(String::from("alice")));
+
+    for threshold in [0, 3] {
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                admins: vec![String::from("alice"), String::from("bob")],
+                threshold,
+                execution_delay: EXECUTION_DELAY,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidThreshold(2));
+    }
+
+    let deps = setup();
+    assert_eq!(
+        query_config(&deps),
+        ConfigResponse {
+            admins: vec![Addr::unchecked("alice"), Addr::unchecked("bob")],
+            threshold: 1,
+            execution_delay: EXECUTION_DELAY,
+        }
+    );
+}
+
+#[test]
+fn schedule_and_execute() {
+    let mut deps = setup();
+
+    let err = schedule(&mut deps, "carol", vec![send_msg()]).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = schedule(&mut deps, "alice", vec![]).unwrap_err();
+    assert_eq!(err, ContractError::NoMessages {});
+
+    schedule(&mut deps, "alice", vec![send_msg()]).unwrap();
+
+    let scheduled: ScheduledMsgsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Scheduled { id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        scheduled,
+        ScheduledMsgsResponse {
+            id: 1,
+            msgs: vec![send_msg()],
+            scheduled_by: Addr::unchecked("alice"),
+            approvals: vec![Addr::unchecked("alice")],
+            executable_after: Some(now() + EXECUTION_DELAY),
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY - 1),
+        mock_info("bob", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExecutionDelayNotPassed(now() + EXECUTION_DELAY)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY),
+        mock_info("carol", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Any admin can execute the batch once the delay has passed
+    let res = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY),
+        mock_info("bob", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, send_msg());
+
+    // A batch can only be executed once
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY),
+        mock_info("bob", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::NotFound { .. })));
+}
+
+#[test]
+fn cancel_and_list() {
+    let mut deps = setup();
+
+    for _ in 0..3 {
+        schedule(&mut deps, "alice", vec![send_msg()]).unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        ExecuteMsg::Cancel { id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Cancel { id: 2 },
+    )
+    .unwrap();
+
+    let all: Vec<ScheduledMsgsResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllScheduled {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(all.iter().map(|s| s.id).collect::<Vec<_>>(), vec![1, 3]);
+
+    let all: Vec<ScheduledMsgsResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllScheduled {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(all.iter().map(|s| s.id).collect::<Vec<_>>(), vec![3]);
+}
+
+#[test]
+fn config_changes_go_through_the_timelock() {
+    let mut deps = setup();
+
+    let update_admins = ExecuteMsg::UpdateAdmins {
+        admins: vec![String::from("carol")],
+        threshold: 1,
+    };
+    let update_delay = ExecuteMsg::UpdateExecutionDelay {
+        execution_delay: 3600,
+    };
+
+    // Admins can't change the config directly
+    for msg in [update_admins.clone(), update_delay.clone()] {
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    let self_msg = |msg: &ExecuteMsg| -> CosmosMsg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            msg: to_binary(msg).unwrap(),
+            funds: vec![],
+        })
+    };
+    schedule(
+        &mut deps,
+        "alice",
+        vec![self_msg(&update_admins), self_msg(&update_delay)],
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY),
+        mock_info("alice", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+        vec![self_msg(&update_admins), self_msg(&update_delay)]
+    );
+
+    // The dispatched messages are executed by the contract itself
+    for msg in [update_admins, update_delay] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        query_config(&deps),
+        ConfigResponse {
+            admins: vec![Addr::unchecked("carol")],
+            threshold: 1,
+            execution_delay: 3600,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::UpdateAdmins {
+            admins: vec![],
+            threshold: 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoAdmins {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::UpdateAdmins {
+            admins: vec![String::from("carol")],
+            threshold: 2,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidThreshold(1));
+}
+
+#[test]
+fn approvals_start_the_execution_delay() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            admins: vec![
+                String::from("alice"),
+                String::from("bob"),
+                String::from("carol"),
+            ],
+            threshold: 2,
+            execution_delay: EXECUTION_DELAY,
+        },
+    )
+    .unwrap();
+
+    schedule(&mut deps, "alice", vec![send_msg()]).unwrap();
+
+    let query_scheduled = |deps: &TimelockDeps| -> ScheduledMsgsResponse {
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Scheduled { id: 1 }).unwrap())
+            .unwrap()
+    };
+    assert_eq!(query_scheduled(&deps).executable_after, None);
+
+    // The batch can't be executed with a single approval, whatever the time
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + EXECUTION_DELAY),
+        mock_info("alice", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotApproved {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Approve { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyApproved {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dave", &[]),
+        ExecuteMsg::Approve { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The delay starts with the second approval
+    let approved_at = now() + 100;
+    execute(
+        deps.as_mut(),
+        env_at(approved_at),
+        mock_info("bob", &[]),
+        ExecuteMsg::Approve { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        query_scheduled(&deps),
+        ScheduledMsgsResponse {
+            id: 1,
+            msgs: vec![send_msg()],
+            scheduled_by: Addr::unchecked("alice"),
+            approvals: vec![Addr::unchecked("alice"), Addr::unchecked("bob")],
+            executable_after: Some(approved_at + EXECUTION_DELAY),
+        }
+    );
+
+    // Later approvals don't restart the delay
+    execute(
+        deps.as_mut(),
+        env_at(approved_at + 100),
+        mock_info("carol", &[]),
+        ExecuteMsg::Approve { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        query_scheduled(&deps).executable_after,
+        Some(approved_at + EXECUTION_DELAY)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(approved_at + EXECUTION_DELAY - 1),
+        mock_info("carol", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExecutionDelayNotPassed(approved_at + EXECUTION_DELAY)
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(approved_at + EXECUTION_DELAY),
+        mock_info("carol", &[]),
+        ExecuteMsg::Execute { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, send_msg());
+}
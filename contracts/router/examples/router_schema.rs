@@ -2,8 +2,8 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use astroport::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    SimulateSwapOperationsResponse,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, IbcTransfersResponse, InstantiateMsg, MigrateMsg,
+    QueryMsg, SimulateSwapOperationsResponse,
 };
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
@@ -24,4 +24,9 @@ fn main() {
         &out_dir,
         "SimulateSwapOperationsResponse",
     );
+    export_schema_with_title(
+        &schema_for!(IbcTransfersResponse),
+        &out_dir,
+        "IbcTransfersResponse",
+    );
 }
@@ -1,22 +1,29 @@
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg,
-    WasmQuery,
+    entry_point, from_binary, to_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, IbcMsg, IbcTimeout, MessageInfo, Order, QueryRequest, Response, StdError,
+    StdResult, Uint128, WasmMsg, WasmQuery,
 };
 
 use crate::error::ContractError;
-use crate::operations::execute_swap_operation;
-use crate::state::{Config, CONFIG};
+use crate::operations::{assert_external_adapter, execute_swap_operation};
+use crate::state::{Config, IbcTransfer, CONFIG, IBC_TRANSFERS, LAST_IBC_TRANSFER_ID};
 
-use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::asset::{
+    addr_validate_to_lower, has_duplicate_asset_infos, Asset, AssetInfo, PairInfo,
+};
+use astroport::common::assert_migration_allowed;
+use astroport::ics20::{validate_ibc_channel, validate_ibc_timeout, TransferMsg};
 use astroport::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
-use astroport::querier::query_pair_info;
+use astroport::querier::{query_factory_config, query_pair_info, query_pairs_info};
 use astroport::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation, MAX_SWAP_OPERATIONS,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, IbcTransferResponse, IbcTransfersResponse,
+    InstantiateMsg, MigrateMsg, QueryMsg, QuoteRoutesResponse, RemoteReceiver, RouteQuote,
+    SimulateSwapOperationsResponse, SwapOperation, SwapReceiver, MAX_SWAP_OPERATIONS,
 };
 use cw2::set_contract_version;
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::{Bound, U64Key};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use terra_cosmwasm::{SwapResponse, TerraMsgWrapper, TerraQuerier};
 
@@ -25,6 +32,17 @@ const CONTRACT_NAME: &str = "astroport-router";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum amount of pairs returned by a single factory `Pairs` query
+const PAIRS_PAGE_LIMIT: u32 = 30;
+/// The default amount of route quotes returned by the `QuoteRoutes` query
+const DEFAULT_ROUTE_QUOTES_LIMIT: u32 = 3;
+/// The maximum amount of route quotes returned by the `QuoteRoutes` query
+const MAX_ROUTE_QUOTES_LIMIT: u32 = 10;
+/// The default amount of transfers returned by the `IbcTransfers` query
+const DEFAULT_IBC_TRANSFERS_LIMIT: u32 = 10;
+/// The maximum amount of transfers returned by the `IbcTransfers` query
+const MAX_IBC_TRANSFERS_LIMIT: u32 = 30;
+
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 /// Returns a default object of type [`Response`] if the operation was successful,
@@ -73,10 +91,29 @@ pub fn instantiate(
 /// * **ExecuteMsg::ExecuteSwapOperations {
 ///             operations,
 ///             minimum_receive,
-///             to
+///             to,
+///             max_spread,
+///             referral_code
 ///         }** Performs swap operations with the specified parameters.
 ///
-/// * **ExecuteMsg::ExecuteSwapOperation { operation, to }** Execute a single swap operation.
+/// * **ExecuteMsg::ExecuteSwapOperation {
+///             operation,
+///             to,
+///             max_spread,
+///             referral_code
+///         }** Execute a single swap operation.
+///
+/// * **ExecuteMsg::IbcHookSwap {
+///             offer_asset,
+///             operations,
+///             minimum_receive,
+///             max_spread,
+///             receiver
+///         }** Swaps the tokens received in an ICS-20 transfer and sends the ask tokens to a local or remote receiver.
+///
+/// * **ExecuteMsg::SendIbcTransfer { asset_info, prev_balance, receiver }** Sends the ask tokens of a swap over IBC.
+///
+/// * **ExecuteMsg::RefundIbcTransfer { transfer_id, recipient }** Sends the ask tokens of an expired IBC transfer to a recipient.
 ///
 /// * **ExecuteMsg::AssertMinimumReceive {
 ///             asset_info,
@@ -98,6 +135,7 @@ pub fn execute(
             minimum_receive,
             to,
             max_spread,
+            referral_code,
         } => execute_swap_operations(
             deps,
             env,
@@ -107,12 +145,39 @@ pub fn execute(
             minimum_receive,
             to,
             max_spread,
+            referral_code,
         ),
         ExecuteMsg::ExecuteSwapOperation {
             operation,
             to,
             max_spread,
-        } => execute_swap_operation(deps, env, info, operation, to, max_spread),
+            referral_code,
+        } => execute_swap_operation(deps, env, info, operation, to, max_spread, referral_code),
+        ExecuteMsg::IbcHookSwap {
+            offer_asset,
+            operations,
+            minimum_receive,
+            max_spread,
+            receiver,
+        } => ibc_hook_swap(
+            deps,
+            env,
+            info,
+            offer_asset,
+            operations,
+            minimum_receive,
+            max_spread,
+            receiver,
+        ),
+        ExecuteMsg::SendIbcTransfer {
+            asset_info,
+            prev_balance,
+            receiver,
+        } => send_ibc_transfer(deps, env, info, asset_info, prev_balance, receiver),
+        ExecuteMsg::RefundIbcTransfer {
+            transfer_id,
+            recipient,
+        } => refund_ibc_transfer(deps, env, info, transfer_id, recipient),
         ExecuteMsg::AssertMinimumReceive {
             asset_info,
             prev_balance,
@@ -153,6 +218,7 @@ pub fn receive_cw20(
             minimum_receive,
             to,
             max_spread,
+            referral_code,
         } => {
             let to_addr = if let Some(to_addr) = to {
                 Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
@@ -169,6 +235,7 @@ pub fn receive_cw20(
                 minimum_receive,
                 to_addr,
                 max_spread,
+                referral_code,
             )
         }
     }
@@ -192,6 +259,10 @@ pub fn receive_cw20(
 /// * **minimum_receive** is an object of type [`Option<Uint128>`]. Used to guarantee that the ask amount is above a minimum amount.
 ///
 /// * **to** is an object of type [`Option<Addr>`]. This is the recipient of the ask tokens.
+///
+/// * **max_spread** is an object of type [`Option<Decimal>`]. This is the max spread enforced for each swap.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code passed to every Astroport pair.
 #[allow(clippy::too_many_arguments)]
 pub fn execute_swap_operations(
     deps: DepsMut,
@@ -202,6 +273,7 @@ pub fn execute_swap_operations(
     minimum_receive: Option<Uint128>,
     to: Option<Addr>,
     max_spread: Option<Decimal>,
+    referral_code: Option<String>,
 ) -> Result<Response<TerraMsgWrapper>, ContractError> {
     let operations_len = operations.len();
     if operations_len == 0 {
@@ -239,6 +311,7 @@ pub fn execute_swap_operations(
                         None
                     },
                     max_spread,
+                    referral_code: referral_code.clone(),
                 })?,
             }))
         })
@@ -262,6 +335,310 @@ pub fn execute_swap_operations(
     Ok(Response::new().add_messages(messages))
 }
 
+/// ## Description
+/// Swaps native tokens received in an ICS-20 transfer. The ibc-hooks middleware executes this message
+/// with the funds received in the transfer, so the funds must match the declared offer asset.
+/// The ask tokens are sent to a local address or forwarded to another chain over IBC.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **offer_asset** is an object of type [`Asset`]. This is the asset received in the transfer.
+///
+/// * **operations** is a vector that contains objects of type [`SwapOperation`]. These are all the swap operations to perform.
+///
+/// * **minimum_receive** is an object of type [`Option<Uint128>`]. Used to guarantee that the ask amount is above a minimum amount.
+///
+/// * **max_spread** is an object of type [`Option<Decimal>`]. This is the max spread enforced for each swap.
+///
+/// * **receiver** is an object of type [`SwapReceiver`]. This is the recipient of the ask tokens.
+#[allow(clippy::too_many_arguments)]
+fn ibc_hook_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_asset: Asset,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Option<Uint128>,
+    max_spread: Option<Decimal>,
+    receiver: SwapReceiver,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    // Only native tokens can be received in an ICS-20 transfer
+    match (&offer_asset.info, info.funds.as_slice()) {
+        (AssetInfo::NativeToken { denom }, [coin])
+            if coin.denom == *denom && coin.amount == offer_asset.amount => {}
+        _ => return Err(ContractError::InvalidIbcHookFunds {}),
+    }
+
+    if let Some(operation) = operations.first() {
+        if !operation.get_offer_asset_info().equal(&offer_asset.info) {
+            return Err(ContractError::InvalidOfferAsset {});
+        }
+    }
+
+    match receiver {
+        SwapReceiver::Local { address } => {
+            let to = addr_validate_to_lower(deps.api, &address)?;
+            execute_swap_operations(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                operations,
+                minimum_receive,
+                Some(to),
+                max_spread,
+                None,
+            )
+        }
+        SwapReceiver::Remote(receiver) => {
+            let target_asset_info = operations
+                .last()
+                .ok_or(ContractError::MustProvideOperations {})?
+                .get_target_asset_info();
+
+            validate_remote_receiver(deps.api, &target_asset_info, &receiver)?;
+
+            // The router receives the ask tokens and forwards them once all swaps are done
+            let prev_balance =
+                target_asset_info.query_pool(&deps.querier, env.contract.address.clone())?;
+            let contract_addr = env.contract.address.clone();
+
+            let response = execute_swap_operations(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                operations,
+                minimum_receive,
+                Some(contract_addr.clone()),
+                max_spread,
+                None,
+            )?;
+
+            Ok(response.add_message(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::SendIbcTransfer {
+                    asset_info: target_asset_info,
+                    prev_balance,
+                    receiver,
+                })?,
+            }))
+        }
+    }
+}
+
+/// ## Description
+/// Validates a recipient on another chain. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **asset_info** is an object of type [`AssetInfo`]. This is the asset sent to the recipient.
+///
+/// * **receiver** is an object of type [`RemoteReceiver`]. This is the recipient to validate.
+fn validate_remote_receiver(
+    api: &dyn Api,
+    asset_info: &AssetInfo,
+    receiver: &RemoteReceiver,
+) -> Result<(), ContractError> {
+    validate_ibc_channel(&receiver.channel)?;
+    validate_ibc_timeout(receiver.timeout)?;
+
+    if receiver.address.trim().is_empty() {
+        return Err(ContractError::InvalidIbcRecipient {});
+    }
+
+    if !asset_info.is_native_token() {
+        match &receiver.ics20_contract {
+            Some(ics20_contract) => {
+                addr_validate_to_lower(api, ics20_contract)?;
+            }
+            None => return Err(ContractError::Ics20ContractRequired {}),
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Sends the ask tokens received by the router in a swap to an address on another chain. Native tokens
+/// are sent with an ICS-20 transfer and CW20 tokens are sent through the cw20-ics20 contract.
+/// The transfer is recorded, so it can be refunded if it fails or times out.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **asset_info** is an object of type [`AssetInfo`]. This is the asset to send.
+///
+/// * **prev_balance** is an object of type [`Uint128`]. This is the router's balance before the swap.
+///
+/// * **receiver** is an object of type [`RemoteReceiver`]. This is the recipient on the other chain.
+fn send_ibc_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    receiver: RemoteReceiver,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_remote_receiver(deps.api, &asset_info, &receiver)?;
+
+    let timeout = validate_ibc_timeout(receiver.timeout)?;
+    let amount = asset_info
+        .query_pool(&deps.querier, env.contract.address.clone())?
+        .checked_sub(prev_balance)?;
+
+    let (message, sent): (CosmosMsg<TerraMsgWrapper>, Asset) = match asset_info {
+        AssetInfo::NativeToken { denom } => {
+            let coin = Asset {
+                info: AssetInfo::NativeToken {
+                    denom: denom.clone(),
+                },
+                amount,
+            }
+            .deduct_tax(&deps.querier)?;
+            let sent = Asset {
+                info: AssetInfo::NativeToken { denom },
+                amount: coin.amount,
+            };
+
+            (
+                CosmosMsg::Ibc(IbcMsg::Transfer {
+                    channel_id: receiver.channel.clone(),
+                    to_address: receiver.address.clone(),
+                    amount: coin,
+                    timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout)),
+                }),
+                sent,
+            )
+        }
+        AssetInfo::Token { contract_addr } => (
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: receiver
+                        .ics20_contract
+                        .clone()
+                        .ok_or(ContractError::Ics20ContractRequired {})?,
+                    amount,
+                    msg: to_binary(&TransferMsg {
+                        channel: receiver.channel.clone(),
+                        remote_address: receiver.address.clone(),
+                        timeout: Some(timeout),
+                        memo: None,
+                    })?,
+                })?,
+            }),
+            Asset {
+                info: AssetInfo::Token { contract_addr },
+                amount,
+            },
+        ),
+    };
+
+    let transfer_id = LAST_IBC_TRANSFER_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    LAST_IBC_TRANSFER_ID.save(deps.storage, &transfer_id)?;
+    IBC_TRANSFERS.save(
+        deps.storage,
+        U64Key::new(transfer_id),
+        &IbcTransfer {
+            asset: sent.clone(),
+            channel: receiver.channel.clone(),
+            receiver: receiver.address.clone(),
+            timeout: env.block.time.plus_seconds(timeout).seconds(),
+        },
+    )?;
+
+    Ok(Response::new().add_message(message).add_attributes(vec![
+        ("action", "send_ibc_transfer"),
+        ("transfer_id", transfer_id.to_string().as_str()),
+        ("channel", receiver.channel.as_str()),
+        ("receiver", receiver.address.as_str()),
+        ("amount", sent.amount.to_string().as_str()),
+    ]))
+}
+
+/// ## Description
+/// Sends the ask tokens of an expired IBC transfer to a recipient on this chain. Failed and timed out
+/// transfers are refunded to the router, which has no way to tell who the swapper was, so the factory
+/// owner returns the tokens once the transfer can no longer be received. The transfer record is removed.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **transfer_id** is an object of type [`u64`]. This is the identifier of the transfer to refund.
+///
+/// * **recipient** is an object of type [`String`]. This is the recipient of the refunded tokens.
+///
+/// ## Executor
+/// Only the factory owner can execute this.
+fn refund_ibc_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfer_id: u64,
+    recipient: String,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != query_factory_config(&deps.querier, config.astroport_factory)?.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = addr_validate_to_lower(deps.api, &recipient)?;
+    let transfer = IBC_TRANSFERS
+        .may_load(deps.storage, U64Key::new(transfer_id))?
+        .ok_or(ContractError::IbcTransferNotFound(transfer_id))?;
+
+    if env.block.time.seconds() < transfer.timeout {
+        return Err(ContractError::IbcTransferNotExpired(transfer_id));
+    }
+
+    IBC_TRANSFERS.remove(deps.storage, U64Key::new(transfer_id));
+
+    let message: CosmosMsg<TerraMsgWrapper> = match &transfer.asset.info {
+        AssetInfo::NativeToken { .. } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![transfer.asset.deduct_tax(&deps.querier)?],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: transfer.asset.amount,
+            })?,
+        }),
+    };
+
+    Ok(Response::new().add_message(message).add_attributes(vec![
+        ("action", "refund_ibc_transfer"),
+        ("transfer_id", transfer_id.to_string().as_str()),
+        ("recipient", recipient.as_str()),
+        ("amount", transfer.asset.amount.to_string().as_str()),
+    ]))
+}
+
 /// ## Description
 /// Checks if an ask amount is equal to or above a minimum amount.
 /// Returns a [`ContractError`] on failure, otherwise returns a default object of type [`Response`]
@@ -312,6 +689,13 @@ fn assert_minimum_receive(
 ///             offer_amount,
 ///             operations,
 ///         }** Simulates one or multiple swap operations and returns the end result in a [`SimulateSwapOperationsResponse`] object.
+/// * **QueryMsg::QuoteRoutes {
+///             offer_asset_info,
+///             ask_asset_info,
+///             offer_amount,
+///             limit,
+///         }** Simulates direct and two-hop routes between two assets and returns the best quotes in a [`QuoteRoutesResponse`] object.
+/// * **QueryMsg::IbcTransfers { start_after, limit }** Returns the IBC transfers that were not refunded in an [`IbcTransfersResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
@@ -324,9 +708,66 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             offer_amount,
             operations,
         )?)?),
+        QueryMsg::QuoteRoutes {
+            offer_asset_info,
+            ask_asset_info,
+            offer_amount,
+            limit,
+        } => Ok(to_binary(&quote_routes(
+            deps,
+            offer_asset_info,
+            ask_asset_info,
+            offer_amount,
+            limit,
+        )?)?),
+        QueryMsg::IbcTransfers { start_after, limit } => {
+            Ok(to_binary(&query_ibc_transfers(deps, start_after, limit)?)?)
+        }
     }
 }
 
+/// ## Description
+/// Returns the IBC transfers sent by the router that were not refunded, ordered by their identifiers.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. This is the transfer identifier to start reading after.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of transfers to return.
+fn query_ibc_transfers(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<IbcTransfersResponse> {
+    let limit = limit
+        .unwrap_or(DEFAULT_IBC_TRANSFERS_LIMIT)
+        .min(MAX_IBC_TRANSFERS_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id)));
+
+    let transfers = IBC_TRANSFERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (transfer_id, transfer) = item?;
+            let transfer_id = u64::from_be_bytes(
+                transfer_id
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid transfer key"))?,
+            );
+            Ok(IbcTransferResponse {
+                transfer_id,
+                asset: transfer.asset,
+                channel: transfer.channel,
+                receiver: transfer.receiver,
+                timeout: transfer.timeout,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(IbcTransfersResponse { transfers })
+}
+
 /// ## Description
 /// Returns a [`ContractError`] on failure, otherwise returns general contract settings in a [`ConfigResponse`] object.
 /// ## Params
@@ -341,16 +782,25 @@ pub fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
 /// * **_msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
 
 /// ## Description
@@ -426,40 +876,29 @@ fn simulate_swap_operations(
                     &[offer_asset_info.clone(), ask_asset_info.clone()],
                 )?;
 
-                // Deduct tax
-                if let AssetInfo::NativeToken { denom } = offer_asset_info.clone() {
-                    let asset = Asset {
-                        info: AssetInfo::NativeToken { denom },
-                        amount: offer_amount,
-                    };
-
-                    offer_amount = offer_amount.checked_sub(asset.compute_tax(&deps.querier)?)?;
-                }
-
-                let mut res: SimulationResponse =
-                    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                        contract_addr: pair_info.contract_addr.to_string(),
-                        msg: to_binary(&PairQueryMsg::Simulation {
-                            offer_asset: Asset {
-                                info: offer_asset_info.clone(),
-                                amount: offer_amount,
-                            },
-                        })?,
-                    }))?;
-
-                // Deduct tax
-                if let AssetInfo::NativeToken { denom } = ask_asset_info.clone() {
-                    let asset = Asset {
-                        info: AssetInfo::NativeToken { denom },
-                        amount: res.return_amount,
-                    };
-
-                    res.return_amount = res
-                        .return_amount
-                        .checked_sub(asset.compute_tax(&deps.querier)?)?;
-                }
+                offer_amount = simulate_pair_swap(
+                    deps,
+                    pair_info.contract_addr.to_string(),
+                    offer_asset_info,
+                    ask_asset_info,
+                    offer_amount,
+                )?;
+            }
+            SwapOperation::ExternalSwap {
+                pair_contract,
+                offer_asset_info,
+                ask_asset_info,
+            } => {
+                let pair_contract = addr_validate_to_lower(deps.api, &pair_contract)?;
+                assert_external_adapter(deps, &pair_contract)?;
 
-                offer_amount = res.return_amount;
+                offer_amount = simulate_pair_swap(
+                    deps,
+                    pair_contract.to_string(),
+                    offer_asset_info,
+                    ask_asset_info,
+                    offer_amount,
+                )?;
             }
         }
     }
@@ -469,6 +908,187 @@ fn simulate_swap_operations(
     })
 }
 
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the best quotes for direct and two-hop routes
+/// between two assets in a [`QuoteRoutesResponse`] object. Routes are built from the pairs registered in
+/// the factory. Routes whose simulation fails are skipped.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the asset that is swapped.
+///
+/// * **ask_asset_info** is an object of type [`AssetInfo`]. This is the asset that is received.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of offer assets being swapped.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of quotes to return.
+fn quote_routes(
+    deps: Deps,
+    offer_asset_info: AssetInfo,
+    ask_asset_info: AssetInfo,
+    offer_amount: Uint128,
+    limit: Option<u32>,
+) -> Result<QuoteRoutesResponse, ContractError> {
+    offer_asset_info.check(deps.api)?;
+    ask_asset_info.check(deps.api)?;
+    if offer_asset_info.equal(&ask_asset_info) {
+        return Err(ContractError::SameAssets {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pairs = query_all_pairs(deps, config.astroport_factory)?;
+
+    // Assets that have a pair with the offer or the ask asset
+    let offer_neighbours: Vec<AssetInfo> = pairs
+        .iter()
+        .filter_map(|pair| paired_asset(pair, &offer_asset_info))
+        .collect();
+    let ask_neighbours: Vec<AssetInfo> = pairs
+        .iter()
+        .filter_map(|pair| paired_asset(pair, &ask_asset_info))
+        .collect();
+
+    let swap = |offer: &AssetInfo, ask: &AssetInfo| SwapOperation::AstroSwap {
+        offer_asset_info: offer.clone(),
+        ask_asset_info: ask.clone(),
+    };
+
+    let mut routes: Vec<Vec<SwapOperation>> = vec![];
+    for asset in offer_neighbours {
+        if asset.equal(&ask_asset_info) {
+            routes.push(vec![swap(&offer_asset_info, &ask_asset_info)]);
+        } else if ask_neighbours.iter().any(|ask_pair| ask_pair.equal(&asset)) {
+            routes.push(vec![
+                swap(&offer_asset_info, &asset),
+                swap(&asset, &ask_asset_info),
+            ]);
+        }
+    }
+
+    let mut quotes: Vec<RouteQuote> = routes
+        .into_iter()
+        .filter_map(|operations| {
+            simulate_swap_operations(deps, offer_amount, operations.clone())
+                .ok()
+                .map(|res| RouteQuote {
+                    operations,
+                    return_amount: res.amount,
+                })
+        })
+        .collect();
+
+    quotes.sort_by_key(|quote| Reverse(quote.return_amount));
+    quotes.truncate(
+        limit
+            .unwrap_or(DEFAULT_ROUTE_QUOTES_LIMIT)
+            .min(MAX_ROUTE_QUOTES_LIMIT) as usize,
+    );
+
+    Ok(QuoteRoutesResponse { routes: quotes })
+}
+
+/// ## Description
+/// Returns information about all the pairs registered in the factory.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **factory** is an object of type [`Addr`]. This is the Astroport factory contract address.
+fn query_all_pairs(deps: Deps, factory: Addr) -> StdResult<Vec<PairInfo>> {
+    let mut pairs: Vec<PairInfo> = vec![];
+    let mut start_after = None;
+
+    loop {
+        let page = query_pairs_info(
+            &deps.querier,
+            factory.clone(),
+            start_after,
+            Some(PAIRS_PAGE_LIMIT),
+        )?
+        .pairs;
+        let page_len = page.len();
+        start_after = page.last().map(|pair| pair.asset_infos.clone());
+        pairs.extend(page);
+
+        if page_len < PAIRS_PAGE_LIMIT as usize {
+            return Ok(pairs);
+        }
+    }
+}
+
+/// ## Description
+/// Returns the other asset of a pair if the pair contains the specified asset, otherwise returns [`None`].
+/// ## Params
+/// * **pair** is an object of type [`PairInfo`].
+///
+/// * **asset_info** is an object of type [`AssetInfo`]. This is the asset we look for in the pair.
+fn paired_asset(pair: &PairInfo, asset_info: &AssetInfo) -> Option<AssetInfo> {
+    if pair.asset_infos[0].equal(asset_info) {
+        Some(pair.asset_infos[1].clone())
+    } else if pair.asset_infos[1].equal(asset_info) {
+        Some(pair.asset_infos[0].clone())
+    } else {
+        None
+    }
+}
+
+/// ## Description
+/// Returns the amount of ask assets received for a swap in the specified pair contract, accounting for
+/// the tax charged on the offer and ask assets if they are native tokens.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **pair_contract** is an object of type [`String`]. This is the pair contract in which the swap is simulated.
+///
+/// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the asset that is swapped.
+///
+/// * **ask_asset_info** is an object of type [`AssetInfo`]. This is the asset that is received.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of offer assets being swapped.
+fn simulate_pair_swap(
+    deps: Deps,
+    pair_contract: String,
+    offer_asset_info: AssetInfo,
+    ask_asset_info: AssetInfo,
+    offer_amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let mut offer_amount = offer_amount;
+
+    // Deduct tax
+    if let AssetInfo::NativeToken { denom } = offer_asset_info.clone() {
+        let asset = Asset {
+            info: AssetInfo::NativeToken { denom },
+            amount: offer_amount,
+        };
+
+        offer_amount = offer_amount.checked_sub(asset.compute_tax(&deps.querier)?)?;
+    }
+
+    let mut res: SimulationResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: pair_contract,
+            msg: to_binary(&PairQueryMsg::Simulation {
+                offer_asset: Asset {
+                    info: offer_asset_info,
+                    amount: offer_amount,
+                },
+            })?,
+        }))?;
+
+    // Deduct tax
+    if let AssetInfo::NativeToken { denom } = ask_asset_info {
+        let asset = Asset {
+            info: AssetInfo::NativeToken { denom },
+            amount: res.return_amount,
+        };
+
+        res.return_amount = res
+            .return_amount
+            .checked_sub(asset.compute_tax(&deps.querier)?)?;
+    }
+
+    Ok(res.return_amount)
+}
+
 /// ## Description
 /// Validates swap operations. Returns a [`ContractError`] on failure, otherwise returns [`Ok`].
 /// ## Params
@@ -478,26 +1098,18 @@ fn simulate_swap_operations(
 fn assert_operations(api: &dyn Api, operations: &[SwapOperation]) -> Result<(), ContractError> {
     let mut ask_asset_map: HashMap<String, bool> = HashMap::new();
     for operation in operations.iter() {
-        let (offer_asset, ask_asset) = match operation {
-            SwapOperation::NativeSwap {
-                offer_denom,
-                ask_denom,
-            } => (
-                AssetInfo::NativeToken {
-                    denom: offer_denom.clone(),
-                },
-                AssetInfo::NativeToken {
-                    denom: ask_denom.clone(),
-                },
-            ),
-            SwapOperation::AstroSwap {
-                offer_asset_info,
-                ask_asset_info,
-            } => (offer_asset_info.clone(), ask_asset_info.clone()),
-        };
+        let offer_asset = operation.get_offer_asset_info();
+        let ask_asset = operation.get_target_asset_info();
         offer_asset.check(api)?;
         ask_asset.check(api)?;
 
+        if has_duplicate_asset_infos(&[offer_asset.clone(), ask_asset.clone()]) {
+            return Err(StdError::generic_err(
+                "invalid operations; offer and ask assets must be different",
+            )
+            .into());
+        }
+
         ask_asset_map.remove(&offer_asset.to_string());
         ask_asset_map.insert(ask_asset.to_string(), true);
     }
@@ -624,4 +1236,20 @@ fn test_invalid_operations() {
         )
         .is_err()
     );
+    // Same offer and ask asset errors
+    assert_eq!(
+        true,
+        assert_operations(
+            deps.as_ref().api,
+            &vec![SwapOperation::AstroSwap {
+                offer_asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0001"),
+                },
+                ask_asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0001"),
+                },
+            }]
+        )
+        .is_err()
+    );
 }
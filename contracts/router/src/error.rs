@@ -19,6 +19,30 @@ pub enum ContractError {
 
     #[error("The swap operation limit was exceeded!")]
     SwapLimitExceeded {},
+
+    #[error("Received funds must match the offer asset!")]
+    InvalidIbcHookFunds {},
+
+    #[error("The first swap operation must offer the received asset!")]
+    InvalidOfferAsset {},
+
+    #[error("IBC recipient address must be set!")]
+    InvalidIbcRecipient {},
+
+    #[error("A cw20-ics20 contract must be specified to send CW20 tokens over IBC!")]
+    Ics20ContractRequired {},
+
+    #[error("IBC transfer {0} does not exist!")]
+    IbcTransferNotFound(u64),
+
+    #[error("IBC transfer {0} can only be refunded after its timeout!")]
+    IbcTransferNotExpired(u64),
+
+    #[error("Offer and ask assets must be different!")]
+    SameAssets {},
+
+    #[error("External adapter {0} is not registered or is disabled in the factory!")]
+    ExternalAdapterNotAllowed(String),
 }
 
 impl From<OverflowError> for ContractError {
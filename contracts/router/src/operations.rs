@@ -1,13 +1,16 @@
 use cosmwasm_std::{
-    to_binary, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, WasmMsg,
 };
 
 use crate::error::ContractError;
 use crate::state::{Config, CONFIG};
 
-use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
 use astroport::pair::ExecuteMsg as PairExecuteMsg;
-use astroport::querier::{query_balance, query_pair_info, query_token_balance};
+use astroport::querier::{
+    query_balance, query_external_adapter, query_pair_info, query_token_balance,
+};
 use astroport::router::SwapOperation;
 use cw20::Cw20ExecuteMsg;
 use terra_cosmwasm::{create_swap_msg, create_swap_send_msg, TerraMsgWrapper};
@@ -25,6 +28,10 @@ use terra_cosmwasm::{create_swap_msg, create_swap_send_msg, TerraMsgWrapper};
 /// * **operation** is an object of type [`SwapOperation`]. It's the swap operation to perform (offer/ask assets and the offer asset amount).
 ///
 /// * **to** is an object of type [`Option<String>`]. This is the address that receives the ask assets.
+///
+/// * **max_spread** is an object of type [`Option<Decimal>`]. This is the max spread enforced for the swap.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code passed to Astroport pairs.
 pub fn execute_swap_operation(
     deps: DepsMut,
     env: Env,
@@ -32,6 +39,7 @@ pub fn execute_swap_operation(
     operation: SwapOperation,
     to: Option<String>,
     max_spread: Option<Decimal>,
+    referral_code: Option<String>,
 ) -> Result<Response<TerraMsgWrapper>, ContractError> {
     if env.contract.address != info.sender {
         return Err(ContractError::Unauthorized {});
@@ -83,25 +91,32 @@ pub fn execute_swap_operation(
                 &[offer_asset_info.clone(), ask_asset_info],
             )?;
 
-            let amount = match offer_asset_info.clone() {
-                AssetInfo::NativeToken { denom } => {
-                    query_balance(&deps.querier, env.contract.address, denom)?
-                }
-                AssetInfo::Token { contract_addr } => {
-                    query_token_balance(&deps.querier, contract_addr, env.contract.address)?
-                }
-            };
-            let offer_asset: Asset = Asset {
-                info: offer_asset_info,
-                amount,
-            };
-
-            vec![asset_into_swap_msg(
+            vec![pair_swap_msg(
                 deps,
+                env,
                 pair_info.contract_addr.to_string(),
-                offer_asset,
+                offer_asset_info,
+                max_spread,
+                to,
+                referral_code,
+            )?]
+        }
+        SwapOperation::ExternalSwap {
+            pair_contract,
+            offer_asset_info,
+            ..
+        } => {
+            let pair_contract = addr_validate_to_lower(deps.api, &pair_contract)?;
+            assert_external_adapter(deps.as_ref(), &pair_contract)?;
+
+            vec![pair_swap_msg(
+                deps,
+                env,
+                pair_contract.to_string(),
+                offer_asset_info,
                 max_spread,
                 to,
+                None,
             )?]
         }
     };
@@ -109,6 +124,73 @@ pub fn execute_swap_operation(
     Ok(Response::new().add_messages(messages))
 }
 
+/// ## Description
+/// Checks that an external adapter is registered and enabled in the factory.
+/// Returns a [`ContractError`] if the router may not use the adapter.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **adapter** is an object of type [`Addr`]. This is the adapter contract used for an external swap.
+pub fn assert_external_adapter(deps: Deps, adapter: &Addr) -> Result<(), ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    match query_external_adapter(&deps.querier, config.astroport_factory, adapter) {
+        Ok(adapter_info) if adapter_info.enabled => Ok(()),
+        _ => Err(ContractError::ExternalAdapterNotAllowed(
+            adapter.to_string(),
+        )),
+    }
+}
+
+/// ## Description
+/// Creates a message of type [`CosmosMsg`] that swaps the router's whole balance of the offer asset
+/// in the specified pair contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **pair_contract** is an object of type [`String`]. This is the pair contract in which the swap is performed.
+///
+/// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the asset that is swapped.
+///
+/// * **max_spread** is an object of type [`Option<Decimal>`]. This is the max spread enforced for the swap.
+///
+/// * **to** is an object of type [`Option<String>`]. This is the address that receives the ask assets.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code passed to the pair.
+fn pair_swap_msg(
+    deps: DepsMut,
+    env: Env,
+    pair_contract: String,
+    offer_asset_info: AssetInfo,
+    max_spread: Option<Decimal>,
+    to: Option<String>,
+    referral_code: Option<String>,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    let amount = match offer_asset_info.clone() {
+        AssetInfo::NativeToken { denom } => {
+            query_balance(&deps.querier, env.contract.address, denom)?
+        }
+        AssetInfo::Token { contract_addr } => {
+            query_token_balance(&deps.querier, contract_addr, env.contract.address)?
+        }
+    };
+    let offer_asset: Asset = Asset {
+        info: offer_asset_info,
+        amount,
+    };
+
+    asset_into_swap_msg(
+        deps,
+        pair_contract,
+        offer_asset,
+        max_spread,
+        to,
+        referral_code,
+    )
+}
+
 /// ## Description
 /// Creates a message of type [`CosmosMsg`] representing a swap operation.
 /// Returns a [`CosmosMsg<TerraMsgWrapper>`] with the specified attributes if the operation was successful.
@@ -122,12 +204,15 @@ pub fn execute_swap_operation(
 /// * **max_spread** is an object of type [`Option<Decimal>`]. This is the max spread enforced for the swap.
 ///
 /// * **to** is an object of type [`Option<String>`]. This is the address that receives the ask assets.
+///
+/// * **referral_code** is an object of type [`Option<String>`]. This is the referral code passed to the pair.
 pub fn asset_into_swap_msg(
     deps: DepsMut,
     pair_contract: String,
     offer_asset: Asset,
     max_spread: Option<Decimal>,
     to: Option<String>,
+    referral_code: Option<String>,
 ) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
     match offer_asset.info.clone() {
         AssetInfo::NativeToken { denom } => {
@@ -146,6 +231,7 @@ pub fn asset_into_swap_msg(
                     belief_price: None,
                     max_spread,
                     to,
+                    referral_code,
                 })?,
             }))
         }
@@ -160,6 +246,7 @@ pub fn asset_into_swap_msg(
                     belief_price: None,
                     max_spread,
                     to,
+                    referral_code,
                 })?,
             })?,
         })),
@@ -1,7 +1,8 @@
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use astroport::asset::Asset;
 use cosmwasm_std::Addr;
 
 /// ## Description
@@ -15,3 +16,26 @@ pub struct Config {
     /// The factory contract address
     pub astroport_factory: Addr,
 }
+
+/// ## Description
+/// Stores the IBC transfers sent by the router with their identifiers as keys
+pub const IBC_TRANSFERS: Map<U64Key, IbcTransfer> = Map::new("ibc_transfers");
+
+/// ## Description
+/// Stores the identifier of the last IBC transfer sent by the router
+pub const LAST_IBC_TRANSFER_ID: Item<u64> = Item::new("last_ibc_transfer_id");
+
+/// ## Description
+/// This structure describes an IBC transfer sent by the router. Failed and timed out transfers
+/// are refunded to the router, so the transfer is kept until the factory owner refunds it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransfer {
+    /// The ask tokens sent in the transfer
+    pub asset: Asset,
+    /// The local channel used to send the ask tokens
+    pub channel: String,
+    /// The recipient address on the remote chain
+    pub receiver: String,
+    /// The timestamp in seconds after which the transfer can no longer be received
+    pub timeout: u64,
+}
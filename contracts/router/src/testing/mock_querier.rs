@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use astroport::asset::{Asset, AssetInfo, PairInfo};
-use astroport::factory::PairType;
+use astroport::factory::{
+    ConfigResponse as FactoryConfigResponse, ExternalAdapterInfo, PairType, PairsResponse,
+};
 use astroport::pair::SimulationResponse;
 use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 use terra_cosmwasm::{
@@ -18,8 +20,20 @@ use terra_cosmwasm::{
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    Pair { asset_infos: [AssetInfo; 2] },
-    Simulation { offer_asset: Asset },
+    Config {},
+    Pair {
+        asset_infos: [AssetInfo; 2],
+    },
+    Pairs {
+        start_after: Option<[AssetInfo; 2]>,
+        limit: Option<u32>,
+    },
+    Simulation {
+        offer_asset: Asset,
+    },
+    ExternalAdapter {
+        adapter: String,
+    },
 }
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies.
@@ -100,12 +114,19 @@ pub(crate) fn caps_to_map(caps: &[(&String, &Uint128)]) -> HashMap<String, Uint1
 #[derive(Clone, Default)]
 pub struct AstroportFactoryQuerier {
     pairs: HashMap<String, String>,
+    // external adapters with their enabled flags
+    external_adapters: HashMap<String, bool>,
+    // pairs returned by the factory's pairs query
+    pair_infos: Vec<PairInfo>,
+    // swap rates used in pair simulations, 1:1 by default
+    swap_rates: HashMap<String, Decimal>,
 }
 
 impl AstroportFactoryQuerier {
     pub fn new(pairs: &[(&String, &String)]) -> Self {
         AstroportFactoryQuerier {
             pairs: pairs_to_map(pairs),
+            ..AstroportFactoryQuerier::default()
         }
     }
 }
@@ -187,15 +208,28 @@ impl WasmMockQuerier {
                 {
                     self.handle_cw20(contract_addr, msg)
                 } else {
-                    self.handle_default(msg)
+                    self.handle_default(contract_addr, msg)
                 }
             }
             _ => self.base.handle_query(request),
         }
     }
 
-    fn handle_default(&self, msg: &Binary) -> QuerierResult {
+    fn handle_default(&self, contract_addr: &str, msg: &Binary) -> QuerierResult {
         match from_binary(&msg).unwrap() {
+            QueryMsg::Config {} => {
+                SystemResult::Ok(ContractResult::from(to_binary(&FactoryConfigResponse {
+                    owner: Addr::unchecked("owner"),
+                    pair_configs: vec![],
+                    token_code_id: 0,
+                    fee_address: None,
+                    generator_address: None,
+                    whitelist_code_id: 0,
+                    referral_registry: None,
+                    emergency_admin: None,
+                    pairs_count: vec![],
+                })))
+            }
             QueryMsg::Pair { asset_infos } => {
                 let key = asset_infos[0].to_string() + asset_infos[1].to_string().as_str();
                 match self.astroport_factory_querier.pairs.get(&key) {
@@ -218,9 +252,54 @@ impl WasmMockQuerier {
                     }),
                 }
             }
+            QueryMsg::ExternalAdapter { adapter } => {
+                match self
+                    .astroport_factory_querier
+                    .external_adapters
+                    .get(&adapter)
+                {
+                    Some(enabled) => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&ExternalAdapterInfo {
+                            adapter: Addr::unchecked(&adapter),
+                            venue: String::from("venue"),
+                            enabled: *enabled,
+                        })))
+                    }
+                    None => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "No external adapter exists".to_string(),
+                        request: msg.as_slice().into(),
+                    }),
+                }
+            }
+            QueryMsg::Pairs { start_after, limit } => {
+                let pairs = &self.astroport_factory_querier.pair_infos;
+                let start = match start_after {
+                    Some(asset_infos) => pairs
+                        .iter()
+                        .position(|pair| pair.asset_infos == asset_infos)
+                        .map_or(pairs.len(), |position| position + 1),
+                    None => 0,
+                };
+
+                SystemResult::Ok(ContractResult::from(to_binary(&PairsResponse {
+                    pairs: pairs
+                        .iter()
+                        .skip(start)
+                        .take(limit.unwrap_or(10) as usize)
+                        .cloned()
+                        .collect(),
+                })))
+            }
             QueryMsg::Simulation { offer_asset } => {
+                let rate = self
+                    .astroport_factory_querier
+                    .swap_rates
+                    .get(contract_addr)
+                    .copied()
+                    .unwrap_or_else(Decimal::one);
+
                 SystemResult::Ok(ContractResult::from(to_binary(&SimulationResponse {
-                    return_amount: offer_asset.amount,
+                    return_amount: offer_asset.amount * rate,
                     commission_amount: Uint128::zero(),
                     spread_amount: Uint128::zero(),
                 })))
@@ -304,4 +383,29 @@ impl WasmMockQuerier {
     pub fn with_astroport_pairs(&mut self, pairs: &[(&String, &String)]) {
         self.astroport_factory_querier = AstroportFactoryQuerier::new(pairs);
     }
+
+    // Registers pairs in the factory, every pair swaps at the specified rate
+    pub fn with_registered_pairs(&mut self, pairs: &[(PairInfo, Decimal)]) {
+        for (pair, rate) in pairs {
+            let contract_addr = pair.contract_addr.to_string();
+            for key in [
+                pair.asset_infos[0].to_string() + pair.asset_infos[1].to_string().as_str(),
+                pair.asset_infos[1].to_string() + pair.asset_infos[0].to_string().as_str(),
+            ] {
+                self.astroport_factory_querier
+                    .pairs
+                    .insert(key, contract_addr.clone());
+            }
+            self.astroport_factory_querier
+                .swap_rates
+                .insert(contract_addr, *rate);
+            self.astroport_factory_querier.pair_infos.push(pair.clone());
+        }
+    }
+
+    pub fn with_external_adapter(&mut self, adapter: &str, enabled: bool) {
+        self.astroport_factory_querier
+            .external_adapters
+            .insert(adapter.to_string(), enabled);
+    }
 }
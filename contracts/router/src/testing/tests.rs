@@ -1,19 +1,24 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Coin, Decimal, ReplyOn, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, BankMsg, Coin, Decimal, IbcMsg, IbcTimeout, ReplyOn,
+    SubMsg, Uint128, WasmMsg,
 };
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
 use crate::error::ContractError;
 use crate::testing::mock_querier::mock_dependencies;
 
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
-use astroport::asset::{Asset, AssetInfo};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::ics20::TransferMsg;
 use astroport::pair::ExecuteMsg as PairExecuteMsg;
 use astroport::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation, MAX_SWAP_OPERATIONS,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, IbcTransferResponse, IbcTransfersResponse,
+    InstantiateMsg, MigrateMsg, QueryMsg, QuoteRoutesResponse, RemoteReceiver, RouteQuote,
+    SimulateSwapOperationsResponse, SwapOperation, SwapReceiver, MAX_SWAP_OPERATIONS,
 };
 use terra_cosmwasm::{create_swap_msg, create_swap_send_msg};
 
@@ -55,6 +60,7 @@ fn execute_swap_operations() {
         minimum_receive: None,
         to: None,
         max_spread: None,
+        referral_code: None,
     };
 
     let env = mock_env();
@@ -96,6 +102,7 @@ fn execute_swap_operations() {
         minimum_receive: Some(Uint128::from(1000000u128)),
         to: None,
         max_spread: None,
+        referral_code: None,
     };
 
     let env = mock_env();
@@ -115,6 +122,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -138,6 +146,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -161,6 +170,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -184,6 +194,7 @@ fn execute_swap_operations() {
                         },
                         to: Some(String::from("addr0000")),
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -251,6 +262,7 @@ fn execute_swap_operations() {
             minimum_receive: None,
             to: Some(String::from("addr0002")),
             max_spread: None,
+            referral_code: None,
         })
         .unwrap(),
     });
@@ -272,6 +284,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -295,6 +308,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -318,6 +332,7 @@ fn execute_swap_operations() {
                         },
                         to: None,
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -341,6 +356,7 @@ fn execute_swap_operations() {
                         },
                         to: Some(String::from("addr0002")),
                         max_spread: None,
+                        referral_code: None,
                     })
                     .unwrap(),
                 }
@@ -386,6 +402,7 @@ fn execute_swap_operation() {
         },
         to: None,
         max_spread: None,
+        referral_code: None,
     };
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
@@ -420,6 +437,7 @@ fn execute_swap_operation() {
         },
         to: Some(String::from("addr0000")),
         max_spread: None,
+        referral_code: None,
     };
     let env = mock_env();
     let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
@@ -461,6 +479,7 @@ fn execute_swap_operation() {
         },
         to: Some(String::from("addr0000")),
         max_spread: None,
+        referral_code: None,
     };
     let env = mock_env();
     let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
@@ -484,6 +503,86 @@ fn execute_swap_operation() {
                         belief_price: None,
                         max_spread: None,
                         to: Some(String::from("addr0000")),
+                        referral_code: None,
+                    })
+                    .unwrap()
+                })
+                .unwrap()
+            }
+            .into(),
+            id: 0,
+            gas_limit: None,
+            reply_on: ReplyOn::Never,
+        }]
+    );
+
+    // External pairs must be registered and enabled adapters in the factory
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::ExternalSwap {
+            pair_contract: String::from("externalpair"),
+            offer_asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        },
+        to: Some(String::from("addr0000")),
+        max_spread: None,
+        referral_code: None,
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExternalAdapterNotAllowed(String::from("externalpair"))
+    );
+
+    deps.querier.with_external_adapter("externalpair", false);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExternalAdapterNotAllowed(String::from("externalpair"))
+    );
+
+    deps.querier.with_external_adapter("externalpair", true);
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::ExternalSwap {
+            pair_contract: String::from("externalpair"),
+            offer_asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset"),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        },
+        to: Some(String::from("addr0000")),
+        max_spread: None,
+        referral_code: None,
+    };
+    let env = mock_env();
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: String::from("asset"),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: String::from("externalpair"),
+                    amount: Uint128::new(1000000u128),
+                    msg: to_binary(&PairExecuteMsg::Swap {
+                        offer_asset: Asset {
+                            info: AssetInfo::Token {
+                                contract_addr: Addr::unchecked("asset"),
+                            },
+                            amount: Uint128::new(1000000u128),
+                        },
+                        belief_price: None,
+                        max_spread: None,
+                        to: Some(String::from("addr0000")),
+                        referral_code: None,
                     })
                     .unwrap()
                 })
@@ -691,6 +790,7 @@ fn assert_maximum_receive_swap_operations() {
         minimum_receive: None,
         to: None,
         max_spread: None,
+        referral_code: None,
     };
 
     let env = mock_env();
@@ -699,3 +799,631 @@ fn assert_maximum_receive_swap_operations() {
 
     assert_eq!(res, ContractError::SwapLimitExceeded {});
 }
+
+#[test]
+fn ibc_hook_swap() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        astroport_factory: String::from("astroportfactory"),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    let ibc_denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: ibc_denom.to_string(),
+        },
+        amount: Uint128::new(1000000u128),
+    };
+    let operations = vec![SwapOperation::AstroSwap {
+        offer_asset_info: offer_asset.info.clone(),
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+    }];
+    let funds = [Coin {
+        denom: ibc_denom.to_string(),
+        amount: Uint128::new(1000000u128),
+    }];
+    let remote_receiver = RemoteReceiver {
+        channel: String::from("channel-1"),
+        address: String::from("osmo1receiver"),
+        timeout: None,
+        ics20_contract: None,
+    };
+
+    // The received funds must match the offer asset
+    let msg = ExecuteMsg::IbcHookSwap {
+        offer_asset: offer_asset.clone(),
+        operations: operations.clone(),
+        minimum_receive: None,
+        max_spread: None,
+        receiver: SwapReceiver::Local {
+            address: String::from("addr0000"),
+        },
+    };
+    for funds in [
+        vec![],
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1000000u128),
+        }],
+        vec![Coin {
+            denom: ibc_denom.to_string(),
+            amount: Uint128::new(999999u128),
+        }],
+    ] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ibc_hooks_sender", &funds),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::InvalidIbcHookFunds {});
+    }
+
+    // The first operation must offer the received asset
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ibc_hooks_sender", &funds),
+        ExecuteMsg::IbcHookSwap {
+            offer_asset: offer_asset.clone(),
+            operations: vec![SwapOperation::NativeSwap {
+                offer_denom: "uluna".to_string(),
+                ask_denom: "uusd".to_string(),
+            }],
+            minimum_receive: None,
+            max_spread: None,
+            receiver: SwapReceiver::Local {
+                address: String::from("addr0000"),
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::InvalidOfferAsset {});
+
+    // Local receiver
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ibc_hooks_sender", &funds),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                operation: operations[0].clone(),
+                to: Some(String::from("addr0000")),
+                max_spread: None,
+                referral_code: None,
+            })
+            .unwrap(),
+        })]
+    );
+
+    // CW20 ask assets can only be sent through the cw20-ics20 contract
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ibc_hooks_sender", &funds),
+        ExecuteMsg::IbcHookSwap {
+            offer_asset: offer_asset.clone(),
+            operations: vec![SwapOperation::AstroSwap {
+                offer_asset_info: offer_asset.info.clone(),
+                ask_asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+            }],
+            minimum_receive: None,
+            max_spread: None,
+            receiver: SwapReceiver::Remote(remote_receiver.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Ics20ContractRequired {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ibc_hooks_sender", &funds),
+        ExecuteMsg::IbcHookSwap {
+            offer_asset: offer_asset.clone(),
+            operations: operations.clone(),
+            minimum_receive: None,
+            max_spread: None,
+            receiver: SwapReceiver::Remote(RemoteReceiver {
+                channel: String::from("connection-1"),
+                ..remote_receiver.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res.to_string(),
+        "Generic error: Invalid IBC channel connection-1"
+    );
+
+    // Remote receiver. The router receives the ask assets and forwards them over IBC
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ibc_hooks_sender", &funds),
+        ExecuteMsg::IbcHookSwap {
+            offer_asset,
+            operations: operations.clone(),
+            minimum_receive: None,
+            max_spread: None,
+            receiver: SwapReceiver::Remote(remote_receiver.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: operations[0].clone(),
+                    to: Some(String::from(MOCK_CONTRACT_ADDR)),
+                    max_spread: None,
+                    referral_code: None,
+                })
+                .unwrap(),
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::SendIbcTransfer {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    prev_balance: Uint128::zero(),
+                    receiver: remote_receiver,
+                })
+                .unwrap(),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn send_ibc_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1500000u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(2000000u128),
+        )],
+    )]);
+
+    let receiver = RemoteReceiver {
+        channel: String::from("channel-1"),
+        address: String::from("osmo1receiver"),
+        timeout: Some(300),
+        ics20_contract: Some(String::from("ics20")),
+    };
+    let msg = ExecuteMsg::SendIbcTransfer {
+        asset_info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        prev_balance: Uint128::new(500000u128),
+        receiver: receiver.clone(),
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(IbcMsg::Transfer {
+            channel_id: String::from("channel-1"),
+            to_address: String::from("osmo1receiver"),
+            amount: Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000000u128),
+            },
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(300)),
+        })]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::SendIbcTransfer {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            prev_balance: Uint128::zero(),
+            receiver,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("asset0000"),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("ics20"),
+                amount: Uint128::new(2000000u128),
+                msg: to_binary(&TransferMsg {
+                    channel: String::from("channel-1"),
+                    remote_address: String::from("osmo1receiver"),
+                    timeout: Some(300),
+                    memo: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        })]
+    );
+}
+
+#[test]
+fn refund_ibc_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        astroport_factory: String::from("astroportfactory"),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1000000u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(2000000u128),
+        )],
+    )]);
+
+    let receiver = RemoteReceiver {
+        channel: String::from("channel-1"),
+        address: String::from("osmo1receiver"),
+        timeout: Some(300),
+        ics20_contract: Some(String::from("ics20")),
+    };
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::SendIbcTransfer {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            prev_balance: Uint128::zero(),
+            receiver: receiver.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("transfer_id", "1"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::SendIbcTransfer {
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            prev_balance: Uint128::zero(),
+            receiver,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("transfer_id", "2"));
+
+    let timeout = env.block.time.plus_seconds(300).seconds();
+    let native_transfer = IbcTransferResponse {
+        transfer_id: 1,
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(1000000u128),
+        },
+        channel: String::from("channel-1"),
+        receiver: String::from("osmo1receiver"),
+        timeout,
+    };
+    let token_transfer = IbcTransferResponse {
+        transfer_id: 2,
+        asset: Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(2000000u128),
+        },
+        channel: String::from("channel-1"),
+        receiver: String::from("osmo1receiver"),
+        timeout,
+    };
+    let res: IbcTransfersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IbcTransfers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.transfers,
+        vec![native_transfer.clone(), token_transfer.clone()]
+    );
+
+    let res: IbcTransfersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IbcTransfers {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.transfers, vec![token_transfer]);
+
+    let refund = ExecuteMsg::RefundIbcTransfer {
+        transfer_id: 1,
+        recipient: String::from("user0000"),
+    };
+
+    // Only the factory owner can refund transfers
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        refund.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The transfer can still be received before its timeout
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        refund.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::IbcTransferNotExpired(1));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(300);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        refund.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from("user0000"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000000u128),
+            }],
+        })]
+    );
+
+    // A transfer can only be refunded once
+    let err = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), refund).unwrap_err();
+    assert_eq!(err, ContractError::IbcTransferNotFound(1));
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("owner", &[]),
+        ExecuteMsg::RefundIbcTransfer {
+            transfer_id: 2,
+            recipient: String::from("user0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("asset0000"),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("user0000"),
+                amount: Uint128::new(2000000u128),
+            })
+            .unwrap(),
+        })]
+    );
+
+    let res: IbcTransfersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IbcTransfers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.transfers, vec![]);
+}
+
+#[test]
+fn quote_routes() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        astroport_factory: String::from("astroportfactory"),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let token = |contract_addr: &str| AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    };
+    let pair = |contract_addr: &str, asset_infos: [AssetInfo; 2]| PairInfo {
+        asset_infos,
+        contract_addr: Addr::unchecked(contract_addr),
+        liquidity_token: Addr::unchecked("liquidity"),
+        pair_type: PairType::Xyk {},
+    };
+    let swap = |offer: &str, ask: &str| SwapOperation::AstroSwap {
+        offer_asset_info: token(offer),
+        ask_asset_info: token(ask),
+    };
+
+    deps.querier.with_registered_pairs(&[
+        (
+            pair("pair0000", [token("tokena"), token("tokenb")]),
+            Decimal::one(),
+        ),
+        (
+            pair("pair0001", [token("tokena"), token("tokenc")]),
+            Decimal::percent(200),
+        ),
+        (
+            pair("pair0002", [token("tokenb"), token("tokenc")]),
+            Decimal::one(),
+        ),
+        (
+            pair("pair0003", [token("tokena"), token("tokend")]),
+            Decimal::percent(300),
+        ),
+        (
+            pair("pair0004", [token("tokene"), token("tokenb")]),
+            Decimal::one(),
+        ),
+    ]);
+
+    let quote_msg = |limit: Option<u32>| QueryMsg::QuoteRoutes {
+        offer_asset_info: token("tokena"),
+        ask_asset_info: token("tokenb"),
+        offer_amount: Uint128::new(100),
+        limit,
+    };
+
+    // The two-hop route through tokenc returns more than the direct route
+    let res: QuoteRoutesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), quote_msg(None)).unwrap()).unwrap();
+    assert_eq!(
+        res.routes,
+        vec![
+            RouteQuote {
+                operations: vec![swap("tokena", "tokenc"), swap("tokenc", "tokenb")],
+                return_amount: Uint128::new(200),
+            },
+            RouteQuote {
+                operations: vec![swap("tokena", "tokenb")],
+                return_amount: Uint128::new(100),
+            },
+        ]
+    );
+
+    let res: QuoteRoutesResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), quote_msg(Some(1))).unwrap()).unwrap();
+    assert_eq!(res.routes.len(), 1);
+    assert_eq!(res.routes[0].return_amount, Uint128::new(200));
+
+    // Assets without a common pair have no routes
+    let res: QuoteRoutesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QuoteRoutes {
+                offer_asset_info: token("tokend"),
+                ask_asset_info: token("tokene"),
+                offer_amount: Uint128::new(100),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.routes, vec![]);
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QuoteRoutes {
+            offer_asset_info: token("tokena"),
+            ask_asset_info: token("tokena"),
+            offer_amount: Uint128::new(100),
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SameAssets {});
+}
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-router", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-router"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-router"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
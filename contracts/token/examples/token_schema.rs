@@ -5,8 +5,8 @@ use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
 use astroport::token::InstantiateMsg;
 use cw20::{
-    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, MinterResponse,
-    TokenInfoResponse,
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
+    DownloadLogoResponse, MarketingInfoResponse, MinterResponse, TokenInfoResponse,
 };
 use cw20_base::msg::{ExecuteMsg, QueryMsg};
 
@@ -41,4 +41,14 @@ fn main() {
         &out_dir,
         "AllAccountsResponse",
     );
+    export_schema_with_title(
+        &schema_for!(MarketingInfoResponse),
+        &out_dir,
+        "MarketingInfoResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(DownloadLogoResponse),
+        &out_dir,
+        "DownloadLogoResponse",
+    );
 }
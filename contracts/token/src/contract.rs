@@ -2,10 +2,12 @@ use cosmwasm_std::{
     entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
 };
 
+use astroport::common::assert_migration_allowed;
 use cw2::set_contract_version;
+use cw20::{EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse};
 use cw20_base::contract::{create_accounts, execute as cw20_execute, query as cw20_query};
 use cw20_base::msg::{ExecuteMsg, QueryMsg};
-use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO};
+use cw20_base::state::{MinterData, TokenInfo, LOGO, MARKETING_INFO, TOKEN_INFO};
 use cw20_base::ContractError;
 
 use astroport::asset::addr_validate_to_lower;
@@ -16,6 +18,44 @@ const CONTRACT_NAME: &str = "astroport-token";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum size of an embedded logo in bytes
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+/// ## Description
+/// Returns a [`ContractError`] if the embedded logo is larger than [`LOGO_SIZE_CAP`] or isn't a valid
+/// SVG or PNG image. This mirrors the checks cw20-base runs on `UploadLogo`, which aren't exported.
+/// ## Params
+/// * **logo** is an object of type [`Logo`].
+fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
+    match logo {
+        Logo::Embedded(EmbeddedLogo::Svg(data)) => {
+            // The logo must start with an XML preamble
+            let preamble = data
+                .split_inclusive(|c| *c == b'>')
+                .next()
+                .ok_or(ContractError::InvalidXmlPreamble {})?;
+            if !(preamble.starts_with(b"<?xml ") && preamble.ends_with(b"?>")) {
+                return Err(ContractError::InvalidXmlPreamble {});
+            }
+            if data.len() > LOGO_SIZE_CAP {
+                return Err(ContractError::LogoTooBig {});
+            }
+        }
+        Logo::Embedded(EmbeddedLogo::Png(data)) => {
+            const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+            if data.len() > LOGO_SIZE_CAP {
+                return Err(ContractError::LogoTooBig {});
+            }
+            if !data.starts_with(&PNG_HEADER) {
+                return Err(ContractError::InvalidPngHeader {});
+            }
+        }
+        Logo::Url(_) => {}
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 /// Returns a default object of type [`Response`] if the operation was successful,
@@ -33,7 +73,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // Check valid token info
@@ -45,7 +85,7 @@ pub fn instantiate(
     // Check supply cap
     if let Some(limit) = msg.get_cap() {
         if total_supply > limit {
-            return Err(StdError::generic_err("Initial supply greater than cap"));
+            return Err(StdError::generic_err("Initial supply greater than cap").into());
         }
     }
 
@@ -68,6 +108,31 @@ pub fn instantiate(
 
     TOKEN_INFO.save(deps.storage, &data)?;
 
+    if let Some(marketing) = msg.marketing {
+        let logo = if let Some(logo) = marketing.logo {
+            verify_logo(&logo)?;
+            let logo_info = match &logo {
+                Logo::Url(url) => LogoInfo::Url(url.clone()),
+                Logo::Embedded(_) => LogoInfo::Embedded,
+            };
+            LOGO.save(deps.storage, &logo)?;
+            Some(logo_info)
+        } else {
+            None
+        };
+
+        let data = MarketingInfoResponse {
+            project: marketing.project,
+            description: marketing.description,
+            marketing: marketing
+                .marketing
+                .map(|addr| addr_validate_to_lower(deps.api, &addr))
+                .transpose()?,
+            logo,
+        };
+        MARKETING_INFO.save(deps.storage, &data)?;
+    }
+
     Ok(Response::default())
 }
 
@@ -105,14 +170,23 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
 /// * **_msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
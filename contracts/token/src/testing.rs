@@ -0,0 +1,134 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{attr, Binary};
+use cw2::set_contract_version;
+use cw20::{EmbeddedLogo, Logo, LogoInfo};
+use cw20_base::contract::{query_download_logo, query_marketing_info};
+use cw20_base::ContractError;
+
+use crate::contract::{instantiate, migrate};
+use astroport::token::{InstantiateMarketingInfo, InstantiateMsg, MigrateMsg};
+
+const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+fn instantiate_msg(logo: Logo) -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Astroport".to_string(),
+        symbol: "ASTRO".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+        mint: None,
+        marketing: Some(InstantiateMarketingInfo {
+            project: Some("Astroport".to_string()),
+            description: None,
+            marketing: Some("marketing".to_string()),
+            logo: Some(logo),
+        }),
+    }
+}
+
+#[test]
+fn instantiate_with_embedded_logo() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut png = PNG_HEADER.to_vec();
+    png.extend_from_slice(&[0u8; 16]);
+    let logo = Logo::Embedded(EmbeddedLogo::Png(Binary(png.clone())));
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg(logo),
+    )
+    .unwrap();
+
+    let marketing_info = query_marketing_info(deps.as_ref()).unwrap();
+    assert_eq!(marketing_info.logo, Some(LogoInfo::Embedded));
+
+    let logo = query_download_logo(deps.as_ref()).unwrap();
+    assert_eq!(logo.mime_type, "image/png");
+    assert_eq!(logo.data, Binary(png));
+}
+
+#[test]
+fn instantiate_with_oversized_logo() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut png = PNG_HEADER.to_vec();
+    png.resize(5 * 1024 + 1, 0);
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg(Logo::Embedded(EmbeddedLogo::Png(Binary(png)))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LogoTooBig {});
+
+    let mut svg = b"<?xml version=\"1.0\"?><svg>".to_vec();
+    svg.resize(5 * 1024 + 1, b' ');
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg(Logo::Embedded(EmbeddedLogo::Svg(Binary(svg)))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LogoTooBig {});
+}
+
+#[test]
+fn instantiate_with_invalid_logo_format() {
+    let mut deps = mock_dependencies(&[]);
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg(Logo::Embedded(EmbeddedLogo::Png(Binary(
+            b"not a png image".to_vec(),
+        )))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPngHeader {});
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg(Logo::Embedded(EmbeddedLogo::Svg(Binary(
+            b"<svg></svg>".to_vec(),
+        )))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidXmlPreamble {});
+}
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-token", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-token"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-token"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -0,0 +1,477 @@
+use crate::error::ContractError;
+use crate::state::{Config, Stage, CLAIMS, CONFIG, LATEST_STAGE, OWNERSHIP_PROPOSAL, STAGES};
+use astroport::airdrop::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StageResponse,
+};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use sha2::Digest;
+use std::convert::TryInto;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-airdrop";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            token: addr_validate_to_lower(deps.api, &msg.token)?,
+        },
+    )?;
+    LATEST_STAGE.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::RegisterMerkleRoot { merkle_root, start_timestamp, expiration_timestamp, total_amount }**
+/// Registers a new airdrop stage.
+///
+/// * **ExecuteMsg::Claim { stage, amount, proof, recipient }** Claims an airdrop allocation.
+///
+/// * **ExecuteMsg::Clawback { stage, recipient }** Sends the unclaimed tokens of an expired stage to the recipient.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::RegisterMerkleRoot {
+            merkle_root,
+            start_timestamp,
+            expiration_timestamp,
+            total_amount,
+        } => register_merkle_root(
+            deps,
+            env,
+            info,
+            merkle_root,
+            start_timestamp,
+            expiration_timestamp,
+            total_amount,
+        ),
+        ExecuteMsg::Claim {
+            stage,
+            amount,
+            proof,
+            recipient,
+        } => claim(deps, env, info, stage, amount, proof, recipient),
+        ExecuteMsg::Clawback { stage, recipient } => clawback(deps, env, info, stage, recipient),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Registers a new airdrop stage. Stages are numbered sequentially starting from 1.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **merkle_root** is an object of type [`String`]. This is the hex-encoded merkle root of the stage.
+///
+/// * **start_timestamp** is an object of type [`u64`]. This is the timestamp when claims open.
+///
+/// * **expiration_timestamp** is an object of type [`u64`]. This is the timestamp when claims close.
+///
+/// * **total_amount** is an object of type [`Uint128`]. This is the total amount of tokens distributed in the stage.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn register_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    start_timestamp: u64,
+    expiration_timestamp: u64,
+    total_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if start_timestamp >= expiration_timestamp || expiration_timestamp <= env.block.time.seconds() {
+        return Err(ContractError::InvalidClaimWindow {});
+    }
+
+    let stage = LATEST_STAGE
+        .load(deps.storage)?
+        .checked_add(1)
+        .ok_or_else(|| StdError::generic_err("The maximum number of stages was reached"))?;
+
+    STAGES.save(
+        deps.storage,
+        stage.into(),
+        &Stage {
+            merkle_root: decode_hash(&merkle_root)?,
+            start_timestamp,
+            expiration_timestamp,
+            total_amount,
+            claimed_amount: Uint128::zero(),
+            clawed_back: false,
+        },
+    )?;
+    LATEST_STAGE.save(deps.storage, &stage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_merkle_root"),
+        attr("stage", stage.to_string()),
+        attr("merkle_root", merkle_root),
+        attr("total_amount", total_amount),
+    ]))
+}
+
+/// ## Description
+/// Claims an airdrop allocation after verifying its merkle proof. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **stage_id** is an object of type [`u8`]. This is the airdrop stage.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens allocated to the sender.
+///
+/// * **proof** is a vector of type [`String`]. This is the hex-encoded merkle proof of the allocation.
+///
+/// * **recipient** is an [`Option`] field of type [`String`]. This is the address that receives the tokens.
+fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage_id: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut stage = STAGES
+        .may_load(deps.storage, stage_id.into())?
+        .ok_or(ContractError::StageNotFound(stage_id))?;
+
+    let now = env.block.time.seconds();
+    if now < stage.start_timestamp {
+        return Err(ContractError::StageNotStarted(stage_id));
+    }
+    if now >= stage.expiration_timestamp {
+        return Err(ContractError::StageExpired(stage_id));
+    }
+
+    if CLAIMS
+        .may_load(deps.storage, (stage_id.into(), &info.sender))?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    if !verify_claim(info.sender.as_ref(), amount, &proof, &stage.merkle_root)? {
+        return Err(ContractError::VerificationFailed {});
+    }
+
+    stage.claimed_amount = stage.claimed_amount.checked_add(amount)?;
+    if stage.claimed_amount > stage.total_amount {
+        return Err(ContractError::StageAmountExceeded {});
+    }
+    STAGES.save(deps.storage, stage_id.into(), &stage)?;
+    CLAIMS.save(deps.storage, (stage_id.into(), &info.sender), &true)?;
+
+    let recipient = match recipient {
+        Some(recipient) => addr_validate_to_lower(deps.api, &recipient)?,
+        None => info.sender.clone(),
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&config, recipient.to_string(), amount)?)
+        .add_attributes(vec![
+            attr("action", "claim"),
+            attr("stage", stage_id.to_string()),
+            attr("address", info.sender),
+            attr("recipient", recipient),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// Sends the tokens that weren't claimed in an expired stage to the recipient.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **stage_id** is an object of type [`u8`]. This is the airdrop stage.
+///
+/// * **recipient** is an [`Option`] field of type [`String`]. This is the address that receives the tokens.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage_id: u8,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut stage = STAGES
+        .may_load(deps.storage, stage_id.into())?
+        .ok_or(ContractError::StageNotFound(stage_id))?;
+
+    if env.block.time.seconds() < stage.expiration_timestamp {
+        return Err(ContractError::StageNotExpired(stage_id));
+    }
+    if stage.clawed_back {
+        return Err(ContractError::AlreadyClawedBack {});
+    }
+
+    stage.clawed_back = true;
+    STAGES.save(deps.storage, stage_id.into(), &stage)?;
+
+    let recipient = match recipient {
+        Some(recipient) => addr_validate_to_lower(deps.api, &recipient)?,
+        None => config.owner.clone(),
+    };
+    let amount = stage.total_amount.checked_sub(stage.claimed_amount)?;
+
+    let mut response = Response::new();
+    if !amount.is_zero() {
+        response = response.add_message(transfer_msg(&config, recipient.to_string(), amount)?);
+    }
+
+    Ok(response.add_attributes(vec![
+        attr("action", "clawback"),
+        attr("stage", stage_id.to_string()),
+        attr("recipient", recipient),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Returns a message that transfers the airdropped token.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **recipient** is an object of type [`String`]. This is the address that receives the tokens.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens to transfer.
+fn transfer_msg(config: &Config, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Decodes a hex-encoded SHA-256 hash. Returns a [`ContractError`] if the hash is invalid.
+/// ## Params
+/// * **hash** is an object of type [`str`]. This is the hex-encoded hash.
+fn decode_hash(hash: &str) -> Result<[u8; 32], ContractError> {
+    hex::decode(hash)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ContractError::InvalidHash(hash.to_string()))
+}
+
+/// ## Description
+/// Returns whether the allocation belongs to the merkle tree with the specified root.
+/// A leaf is `sha256(address + amount)` and every pair of nodes is sorted before being hashed.
+/// ## Params
+/// * **address** is an object of type [`str`]. This is the address that claims the allocation.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the allocated amount.
+///
+/// * **proof** is a slice of type [`String`]. This is the hex-encoded merkle proof.
+///
+/// * **merkle_root** is an array of type [`u8`]. This is the merkle root of the stage.
+fn verify_claim(
+    address: &str,
+    amount: Uint128,
+    proof: &[String],
+    merkle_root: &[u8; 32],
+) -> Result<bool, ContractError> {
+    let mut hash: [u8; 32] =
+        sha2::Sha256::digest(format!("{}{}", address, amount).as_bytes()).into();
+
+    for node in proof {
+        let node = decode_hash(node)?;
+        let (first, second) = if hash < node {
+            (hash, node)
+        } else {
+            (node, hash)
+        };
+        hash = sha2::Sha256::digest(&[first, second].concat()).into();
+    }
+
+    Ok(&hash == merkle_root)
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::LatestStage {}** Returns the latest registered stage.
+///
+/// * **QueryMsg::Stage { stage }** Returns information about a stage using a [`StageResponse`] object.
+///
+/// * **QueryMsg::IsClaimed { stage, address }** Returns whether an address claimed its allocation in a stage.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                token: config.token,
+            })
+        }
+        QueryMsg::LatestStage {} => to_binary(&LATEST_STAGE.load(deps.storage)?),
+        QueryMsg::Stage { stage } => {
+            let stage = STAGES.load(deps.storage, stage.into())?;
+            to_binary(&StageResponse {
+                merkle_root: hex::encode(stage.merkle_root),
+                start_timestamp: stage.start_timestamp,
+                expiration_timestamp: stage.expiration_timestamp,
+                total_amount: stage.total_amount,
+                claimed_amount: stage.claimed_amount,
+                clawed_back: stage.clawed_back,
+            })
+        }
+        QueryMsg::IsClaimed { stage, address } => {
+            let address = addr_validate_to_lower(deps.api, &address)?;
+            to_binary(
+                &CLAIMS
+                    .may_load(deps.storage, (stage.into(), &address))?
+                    .unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
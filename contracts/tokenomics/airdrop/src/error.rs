@@ -0,0 +1,49 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes airdrop contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid hex-encoded hash: {0}")]
+    InvalidHash(String),
+
+    #[error("The claim window must start before it expires and can't be in the past")]
+    InvalidClaimWindow {},
+
+    #[error("The airdrop stage {0} doesn't exist")]
+    StageNotFound(u8),
+
+    #[error("The airdrop stage {0} hasn't started yet")]
+    StageNotStarted(u8),
+
+    #[error("The airdrop stage {0} has expired")]
+    StageExpired(u8),
+
+    #[error("The airdrop stage {0} hasn't expired yet")]
+    StageNotExpired(u8),
+
+    #[error("The allocation was already claimed")]
+    AlreadyClaimed {},
+
+    #[error("The merkle proof is invalid")]
+    VerificationFailed {},
+
+    #[error("The claimed amount exceeds the stage's total amount")]
+    StageAmountExceeded {},
+
+    #[error("Unclaimed tokens were already clawed back")]
+    AlreadyClawedBack {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
@@ -0,0 +1,53 @@
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U8Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the airdrop contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to register merkle roots and claw back unclaimed tokens
+    pub owner: Addr,
+    /// The CW20 token that's distributed by the airdrop
+    pub token: Addr,
+}
+
+/// ## Description
+/// This structure describes an airdrop stage.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stage {
+    /// The merkle root of the stage
+    pub merkle_root: [u8; 32],
+    /// The timestamp (in seconds) when claims open
+    pub start_timestamp: u64,
+    /// The timestamp (in seconds) when claims close
+    pub expiration_timestamp: u64,
+    /// The total amount of tokens distributed in the stage
+    pub total_amount: Uint128,
+    /// The amount of tokens claimed so far
+    pub claimed_amount: Uint128,
+    /// Whether the unclaimed tokens were clawed back
+    pub clawed_back: bool,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest registered stage at the given key
+pub const LATEST_STAGE: Item<u8> = Item::new("latest_stage");
+
+/// ## Description
+/// Stores airdrop stages by their number
+pub const STAGES: Map<U8Key, Stage> = Map::new("stages");
+
+/// ## Description
+/// Stores whether an address claimed its allocation, keyed by stage and address
+pub const CLAIMS: Map<(U8Key, &Addr), bool> = Map::new("claims");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
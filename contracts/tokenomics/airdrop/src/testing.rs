@@ -0,0 +1,364 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use sha2::Digest;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use astroport::airdrop::{ExecuteMsg, InstantiateMsg, QueryMsg, StageResponse};
+
+const ALLOCATIONS: [(&str, u128); 3] = [("alice", 100), ("bob", 200), ("carol", 300)];
+const START: u64 = 100;
+const CLAIM_WINDOW: u64 = 86400;
+
+type AirdropDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+fn now() -> u64 {
+    mock_env().block.time.seconds()
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(seconds - env.block.time.seconds());
+    env
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    sha2::Sha256::digest(data).into()
+}
+
+fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a < b {
+        sha256(&[a, b].concat())
+    } else {
+        sha256(&[b, a].concat())
+    }
+}
+
+/// Builds a merkle tree over the allocations and returns its root and the proof of every leaf.
+/// An unpaired node is promoted to the next level as is.
+fn merkle_tree(allocations: &[(&str, u128)]) -> (String, Vec<Vec<String>>) {
+    let mut level: Vec<[u8; 32]> = allocations
+        .iter()
+        .map(|(address, amount)| sha256(format!("{}{}", address, amount).as_bytes()))
+        .collect();
+    let mut positions: Vec<usize> = (0..level.len()).collect();
+    let mut proofs = vec![vec![]; level.len()];
+
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            if let Some(sibling) = level.get(*position ^ 1) {
+                proof.push(hex::encode(sibling));
+            }
+            *position /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(*a, *b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    (hex::encode(level[0]), proofs)
+}
+
+fn register_msg(merkle_root: String) -> ExecuteMsg {
+    ExecuteMsg::RegisterMerkleRoot {
+        merkle_root,
+        start_timestamp: now() + START,
+        expiration_timestamp: now() + START + CLAIM_WINDOW,
+        total_amount: Uint128::from(600u128),
+    }
+}
+
+fn setup() -> (AirdropDeps, Vec<Vec<String>>) {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            token: String::from("astro"),
+        },
+    )
+    .unwrap();
+
+    let (merkle_root, proofs) = merkle_tree(&ALLOCATIONS);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        register_msg(merkle_root),
+    )
+    .unwrap();
+
+    (deps, proofs)
+}
+
+fn claim_msg(stage: u8, amount: u128, proof: &[String]) -> ExecuteMsg {
+    ExecuteMsg::Claim {
+        stage,
+        amount: Uint128::from(amount),
+        proof: proof.to_vec(),
+        recipient: None,
+    }
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("astro"),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: String::from(recipient),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn query_stage(deps: &AirdropDeps, stage: u8) -> StageResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Stage { stage }).unwrap()).unwrap()
+}
+
+#[test]
+fn register_merkle_root() {
+    let (mut deps, _) = setup();
+    let (merkle_root, _) = merkle_tree(&ALLOCATIONS);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        register_msg(merkle_root.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        register_msg(String::from("deadbeef")),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidHash(String::from("deadbeef")));
+
+    for (start_timestamp, expiration_timestamp) in [(now() + 10, now() + 10), (now() - 10, now())] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                start_timestamp,
+                expiration_timestamp,
+                total_amount: Uint128::from(600u128),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidClaimWindow {});
+    }
+
+    // Every registered root opens a new stage
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        register_msg(merkle_root.clone()),
+    )
+    .unwrap();
+    let latest_stage: u8 =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LatestStage {}).unwrap()).unwrap();
+    assert_eq!(latest_stage, 2);
+
+    assert_eq!(
+        query_stage(&deps, 2),
+        StageResponse {
+            merkle_root,
+            start_timestamp: now() + START,
+            expiration_timestamp: now() + START + CLAIM_WINDOW,
+            total_amount: Uint128::from(600u128),
+            claimed_amount: Uint128::zero(),
+            clawed_back: false,
+        }
+    );
+}
+
+#[test]
+fn claim() {
+    let (mut deps, proofs) = setup();
+    let claim_env = env_at(now() + START);
+
+    let err = execute(
+        deps.as_mut(),
+        claim_env.clone(),
+        mock_info("alice", &[]),
+        claim_msg(2, 100, &proofs[0]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StageNotFound(2));
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + START - 1),
+        mock_info("alice", &[]),
+        claim_msg(1, 100, &proofs[0]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StageNotStarted(1));
+
+    // The proof must match both the sender and the amount
+    for (sender, amount) in [("alice", 101), ("bob", 100)] {
+        let err = execute(
+            deps.as_mut(),
+            claim_env.clone(),
+            mock_info(sender, &[]),
+            claim_msg(1, amount, &proofs[0]),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::VerificationFailed {});
+    }
+
+    for (i, (address, amount)) in ALLOCATIONS.iter().enumerate() {
+        let res = execute(
+            deps.as_mut(),
+            claim_env.clone(),
+            mock_info(address, &[]),
+            claim_msg(1, *amount, &proofs[i]),
+        )
+        .unwrap();
+        assert_eq!(res.messages[0].msg, transfer_msg(address, *amount));
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        claim_env.clone(),
+        mock_info("alice", &[]),
+        claim_msg(1, 100, &proofs[0]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyClaimed {});
+
+    let is_claimed: bool = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsClaimed {
+                stage: 1,
+                address: String::from("alice"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(is_claimed);
+    assert_eq!(query_stage(&deps, 1).claimed_amount, Uint128::from(600u128));
+}
+
+#[test]
+fn claim_to_recipient_and_expiry() {
+    let (mut deps, proofs) = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(now() + START),
+        mock_info("bob", &[]),
+        ExecuteMsg::Claim {
+            stage: 1,
+            amount: Uint128::from(200u128),
+            proof: proofs[1].clone(),
+            recipient: Some(String::from("bob_wallet")),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, transfer_msg("bob_wallet", 200));
+
+    let is_claimed: bool = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsClaimed {
+                stage: 1,
+                address: String::from("bob_wallet"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!is_claimed);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + START + CLAIM_WINDOW),
+        mock_info("alice", &[]),
+        claim_msg(1, 100, &proofs[0]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StageExpired(1));
+}
+
+#[test]
+fn clawback() {
+    let (mut deps, proofs) = setup();
+    let expiration = now() + START + CLAIM_WINDOW;
+
+    execute(
+        deps.as_mut(),
+        env_at(now() + START),
+        mock_info("carol", &[]),
+        claim_msg(1, 300, &proofs[2]),
+    )
+    .unwrap();
+
+    let clawback_msg = ExecuteMsg::Clawback {
+        stage: 1,
+        recipient: Some(String::from("treasury")),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(expiration),
+        mock_info("user", &[]),
+        clawback_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(expiration - 1),
+        mock_info("owner", &[]),
+        clawback_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StageNotExpired(1));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(expiration),
+        mock_info("owner", &[]),
+        clawback_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, transfer_msg("treasury", 300));
+    assert!(query_stage(&deps, 1).clawed_back);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(expiration),
+        mock_info("owner", &[]),
+        clawback_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyClawedBack {});
+}
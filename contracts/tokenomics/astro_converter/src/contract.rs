@@ -0,0 +1,377 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL};
+use astroport::asset::{addr_validate_to_lower, validate_native_denom};
+use astroport::astro_converter::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::querier::{query_balance, query_token_balance};
+use cosmwasm_std::{
+    attr, coins, entry_point, from_binary, to_binary, BankMsg, Binary, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-astro-converter";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.window_end < env.block.time.seconds() {
+        return Err(ContractError::InvalidWindowEnd {});
+    }
+
+    validate_native_denom(deps.api, &msg.native_denom)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        astro_token: addr_validate_to_lower(deps.api, &msg.astro_token)?,
+        native_denom: msg.native_denom,
+        window_end: msg.window_end,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::ConvertBack { receiver }** Converts native ASTRO back to CW20 ASTRO.
+///
+/// * **ExecuteMsg::UpdateConfig { window_end }** Updates general contract parameters.
+///
+/// * **ExecuteMsg::BurnLocked {}** Burns the locked CW20 ASTRO once the transition window has ended.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ConvertBack { receiver } => convert_back(deps, env, info, receiver),
+        ExecuteMsg::UpdateConfig { window_end } => update_config(deps, env, info, window_end),
+        ExecuteMsg::BurnLocked {} => burn_locked(deps, env, info),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// CW20 ASTRO is locked in the contract and the same amount of native ASTRO is released to the receiver.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.astro_token {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Convert { receiver } => {
+            let receiver = addr_validate_to_lower(
+                deps.api,
+                &receiver.unwrap_or_else(|| cw20_msg.sender.clone()),
+            )?;
+
+            let available = query_balance(
+                &deps.querier,
+                env.contract.address,
+                config.native_denom.clone(),
+            )?;
+            if available < cw20_msg.amount {
+                return Err(ContractError::InsufficientNativeAstro(available));
+            }
+
+            Ok(Response::new()
+                .add_message(BankMsg::Send {
+                    to_address: receiver.to_string(),
+                    amount: coins(cw20_msg.amount.u128(), config.native_denom),
+                })
+                .add_attributes(vec![
+                    attr("action", "convert"),
+                    attr("sender", cw20_msg.sender),
+                    attr("receiver", receiver),
+                    attr("amount", cw20_msg.amount),
+                ]))
+        }
+    }
+}
+
+/// ## Description
+/// Converts the native ASTRO sent with the message back to CW20 ASTRO. The native ASTRO stays in the
+/// contract and the same amount of locked CW20 ASTRO is released. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **receiver** is an [`Option`] field of type [`String`]. This is the address that receives CW20 ASTRO.
+fn convert_back(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if env.block.time.seconds() > config.window_end {
+        return Err(ContractError::TransitionWindowClosed {});
+    }
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == config.native_denom && !coin.amount.is_zero() => coin.amount,
+        _ => return Err(ContractError::InvalidFunds {}),
+    };
+
+    let receiver = match receiver {
+        Some(receiver) => addr_validate_to_lower(deps.api, &receiver)?,
+        None => info.sender.clone(),
+    };
+
+    let available = query_token_balance(
+        &deps.querier,
+        config.astro_token.clone(),
+        env.contract.address,
+    )?;
+    if available < amount {
+        return Err(ContractError::InsufficientCw20Astro(available));
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: receiver.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "convert_back"),
+            attr("sender", info.sender),
+            attr("receiver", receiver),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// Updates general contract parameters. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **window_end** is an [`Option`] field of type [`u64`]. This is the new end of the transition window.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    window_end: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attributes = vec![attr("action", "update_config")];
+
+    if let Some(window_end) = window_end {
+        if window_end < env.block.time.seconds() {
+            return Err(ContractError::InvalidWindowEnd {});
+        }
+
+        config.window_end = window_end;
+        attributes.push(attr("window_end", window_end.to_string()));
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Burns all the CW20 ASTRO locked in the contract once the transition window has ended.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn burn_locked(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() <= config.window_end {
+        return Err(ContractError::TransitionWindowOpen {});
+    }
+
+    let amount = query_token_balance(
+        &deps.querier,
+        config.astro_token.clone(),
+        env.contract.address,
+    )?;
+    if amount.is_zero() {
+        return Ok(Response::new().add_attribute("action", "burn_locked"));
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![attr("action", "burn_locked"), attr("amount", amount)]))
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                astro_token: config.astro_token,
+                native_denom: config.native_denom,
+                window_end: config.window_end,
+            })
+        }
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
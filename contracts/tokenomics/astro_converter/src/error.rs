@@ -0,0 +1,37 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes ASTRO converter contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The transition window can't end in the past")]
+    InvalidWindowEnd {},
+
+    #[error("The transition window has ended")]
+    TransitionWindowClosed {},
+
+    #[error("The transition window hasn't ended yet")]
+    TransitionWindowOpen {},
+
+    #[error("Only native ASTRO can be converted back to CW20 ASTRO")]
+    InvalidFunds {},
+
+    #[error("Not enough native ASTRO in the converter. Available: {0}")]
+    InsufficientNativeAstro(Uint128),
+
+    #[error("Not enough CW20 ASTRO in the converter. Available: {0}")]
+    InsufficientCw20Astro(Uint128),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
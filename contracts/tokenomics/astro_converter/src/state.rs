@@ -0,0 +1,27 @@
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the ASTRO converter contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The CW20 ASTRO token contract address
+    pub astro_token: Addr,
+    /// The native ASTRO denom
+    pub native_denom: String,
+    /// The timestamp (in seconds) until which native ASTRO can be converted back to CW20 ASTRO
+    pub window_end: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,282 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::astro_converter::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+
+const NATIVE_DENOM: &str = "factory/creator/uastro";
+
+type ConverterDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn setup(native_balance: u128) -> (ConverterDeps, u64) {
+    let mut deps = mock_dependencies(&coins(native_balance, NATIVE_DENOM));
+    let window_end = mock_env().block.time.seconds() + 1000;
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            astro_token: String::from("astro"),
+            native_denom: String::from(NATIVE_DENOM),
+            window_end,
+        },
+    )
+    .unwrap();
+
+    (deps, window_end)
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(seconds - env.block.time.seconds());
+    env
+}
+
+fn convert_msg(sender: &str, amount: u128, receiver: Option<String>) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from(sender),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::Convert { receiver }).unwrap(),
+    })
+}
+
+#[test]
+fn proper_initialization() {
+    let (deps, window_end) = setup(0);
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            astro_token: Addr::unchecked("astro"),
+            native_denom: String::from(NATIVE_DENOM),
+            window_end,
+        }
+    );
+
+    let mut deps = mock_dependencies(&[]);
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            astro_token: String::from("astro"),
+            native_denom: String::from(NATIVE_DENOM),
+            window_end: mock_env().block.time.seconds() - 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWindowEnd {});
+}
+
+#[test]
+fn convert() {
+    let (mut deps, _) = setup(1000);
+
+    // Only CW20 ASTRO can be converted
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_astro", &[]),
+        convert_msg("user", 100, None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        convert_msg("user", 1001, None),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientNativeAstro(Uint128::from(1000u128))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        convert_msg("user", 100, None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("user"),
+            amount: coins(100, NATIVE_DENOM),
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        convert_msg("user", 100, Some(String::from("receiver"))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("receiver"),
+            amount: coins(100, NATIVE_DENOM),
+        })
+    );
+}
+
+#[test]
+fn convert_back() {
+    let (mut deps, window_end) = setup(0);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 500);
+
+    for funds in [
+        vec![],
+        coins(100, "uluna"),
+        vec![coin(100, NATIVE_DENOM), coin(100, "uluna")],
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &funds),
+            ExecuteMsg::ConvertBack { receiver: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFunds {});
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(501, NATIVE_DENOM)),
+        ExecuteMsg::ConvertBack { receiver: None },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientCw20Astro(Uint128::from(500u128))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(500, NATIVE_DENOM)),
+        ExecuteMsg::ConvertBack { receiver: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("user"),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Native ASTRO can't be converted back once the transition window has ended
+    let err = execute(
+        deps.as_mut(),
+        env_at(window_end + 1),
+        mock_info("user", &coins(500, NATIVE_DENOM)),
+        ExecuteMsg::ConvertBack { receiver: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TransitionWindowClosed {});
+}
+
+#[test]
+fn update_config_and_burn_locked() {
+    let (mut deps, window_end) = setup(0);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 700);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            window_end: Some(window_end + 100),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            window_end: Some(mock_env().block.time.seconds() - 1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWindowEnd {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            window_end: Some(window_end + 100),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(window_end + 100),
+        mock_info("owner", &[]),
+        ExecuteMsg::BurnLocked {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TransitionWindowOpen {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(window_end + 101),
+        mock_info("not_owner", &[]),
+        ExecuteMsg::BurnLocked {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(window_end + 101),
+        mock_info("owner", &[]),
+        ExecuteMsg::BurnLocked {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(700u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
@@ -0,0 +1,803 @@
+use crate::error::ContractError;
+use crate::state::{Config, UserInfo, CONFIG, OWNERSHIP_PROPOSAL, STATE, USERS};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::auction::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    StateResponse, UserInfoResponse,
+};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::pair::{ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg};
+use astroport::querier::query_token_balance;
+use astroport::DecimalCheckedOps;
+use cosmwasm_std::{
+    attr, coins, entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-auction";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.init_timestamp < env.block.time.seconds()
+        || msg.deposit_window == 0
+        || msg.withdrawal_window == 0
+        || msg.lp_vesting_duration == 0
+        || msg.stable_denom.is_empty()
+    {
+        return Err(ContractError::InvalidParams {});
+    }
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        astro_token: addr_validate_to_lower(deps.api, &msg.astro_token)?,
+        stable_denom: msg.stable_denom,
+        init_timestamp: msg.init_timestamp,
+        deposit_window: msg.deposit_window,
+        withdrawal_window: msg.withdrawal_window,
+        lp_vesting_duration: msg.lp_vesting_duration,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &StateResponse::default())?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::DepositStable {}** Deposits the stable asset sent along with the message.
+///
+/// * **ExecuteMsg::WithdrawStable { amount }** Withdraws the stable asset.
+///
+/// * **ExecuteMsg::InitPool { pair, slippage_tolerance }** Provides all deposits to the ASTRO-stable pair.
+///
+/// * **ExecuteMsg::ClaimRewards {}** Claims the sender's share of the ASTRO incentives.
+///
+/// * **ExecuteMsg::WithdrawLp { amount }** Withdraws vested LP tokens.
+///
+/// * **ExecuteMsg::Callback(msg)** Handles callbacks described in the [`CallbackMsg`].
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::DepositStable {} => deposit_stable(deps, env, info),
+        ExecuteMsg::WithdrawStable { amount } => withdraw_stable(deps, env, info, amount),
+        ExecuteMsg::InitPool {
+            pair,
+            slippage_tolerance,
+        } => init_pool(deps, env, info, pair, slippage_tolerance),
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, info),
+        ExecuteMsg::WithdrawLp { amount } => withdraw_lp(deps, env, info, amount),
+        ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, msg),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// Only ASTRO is accepted. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.astro_token {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::DepositAstro {} => {
+            if now < config.init_timestamp || now >= config.withdrawal_start() {
+                return Err(ContractError::DepositWindowClosed {});
+            }
+
+            let user = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            let mut user_info = USERS.may_load(deps.storage, &user)?.unwrap_or_default();
+            user_info.astro_deposited = user_info.astro_deposited.checked_add(cw20_msg.amount)?;
+            USERS.save(deps.storage, &user, &user_info)?;
+
+            let state = STATE.update::<_, ContractError>(deps.storage, |mut state| {
+                state.total_astro_deposited =
+                    state.total_astro_deposited.checked_add(cw20_msg.amount)?;
+                Ok(state)
+            })?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "deposit_astro"),
+                attr("user", user),
+                attr("amount", cw20_msg.amount),
+                attr("total_astro_deposited", state.total_astro_deposited),
+            ]))
+        }
+        Cw20HookMsg::IncreaseAstroIncentives {} => {
+            // Incentives are fixed once the auction has finished
+            if now >= config.auction_end() {
+                return Err(ContractError::AuctionFinished {});
+            }
+
+            let state = STATE.update::<_, ContractError>(deps.storage, |mut state| {
+                state.astro_incentives = state.astro_incentives.checked_add(cw20_msg.amount)?;
+                Ok(state)
+            })?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "increase_astro_incentives"),
+                attr("amount", cw20_msg.amount),
+                attr("astro_incentives", state.astro_incentives),
+            ]))
+        }
+    }
+}
+
+/// ## Description
+/// Deposits the stable asset sent along with the message. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+fn deposit_stable(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let now = env.block.time.seconds();
+    if now < config.init_timestamp || now >= config.withdrawal_start() {
+        return Err(ContractError::DepositWindowClosed {});
+    }
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == config.stable_denom && !coin.amount.is_zero() => coin.amount,
+        _ => return Err(ContractError::InvalidFunds {}),
+    };
+
+    let mut user_info = USERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    user_info.stable_deposited = user_info.stable_deposited.checked_add(amount)?;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    let state = STATE.update::<_, ContractError>(deps.storage, |mut state| {
+        state.total_stable_deposited = state.total_stable_deposited.checked_add(amount)?;
+        Ok(state)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deposit_stable"),
+        attr("user", info.sender),
+        attr("amount", amount),
+        attr("total_stable_deposited", state.total_stable_deposited),
+    ]))
+}
+
+/// ## Description
+/// Withdraws the stable asset. Any amount can be withdrawn during the deposit window.
+/// During the withdrawal window, only one withdrawal is allowed and its maximum amount is
+/// limited by [`max_withdrawal_percent`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of the stable asset to withdraw.
+fn withdraw_stable(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let now = env.block.time.seconds();
+    if now >= config.auction_end() {
+        return Err(ContractError::WithdrawalWindowClosed {});
+    }
+
+    let mut user_info = USERS.load(deps.storage, &info.sender)?;
+
+    let max_amount = if now >= config.withdrawal_start() {
+        if user_info.withdrawal_flag {
+            return Err(ContractError::AlreadyWithdrawn {});
+        }
+        user_info.withdrawal_flag = true;
+
+        max_withdrawal_percent(&config, now).checked_mul(user_info.stable_deposited)?
+    } else {
+        user_info.stable_deposited
+    };
+    if amount > max_amount {
+        return Err(ContractError::WithdrawalLimitExceeded(max_amount));
+    }
+
+    user_info.stable_deposited = user_info.stable_deposited.checked_sub(amount)?;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    STATE.update::<_, ContractError>(deps.storage, |mut state| {
+        state.total_stable_deposited = state.total_stable_deposited.checked_sub(amount)?;
+        Ok(state)
+    })?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), config.stable_denom),
+        })
+        .add_attributes(vec![
+            attr("action", "withdraw_stable"),
+            attr("user", info.sender),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// Provides all ASTRO and stable deposits to the ASTRO-stable pair, which sets the initial ASTRO
+/// price. The minted LP tokens are then vested to participants. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the ASTRO-stable pair contract address.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`]. This is the slippage tolerance used
+/// when providing liquidity.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn init_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+    slippage_tolerance: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() < config.auction_end() {
+        return Err(ContractError::AuctionNotFinished {});
+    }
+
+    if state.pair.is_some() {
+        return Err(ContractError::PoolAlreadyInitialized {});
+    }
+
+    if state.total_astro_deposited.is_zero() || state.total_stable_deposited.is_zero() {
+        return Err(ContractError::NoDeposits {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair, &PairQueryMsg::Pair {})?;
+
+    let astro_info = AssetInfo::Token {
+        contract_addr: config.astro_token.clone(),
+    };
+    let stable_info = AssetInfo::NativeToken {
+        denom: config.stable_denom.clone(),
+    };
+    if !pair_info.asset_infos.contains(&astro_info) || !pair_info.asset_infos.contains(&stable_info)
+    {
+        return Err(ContractError::InvalidPair {});
+    }
+
+    let assets = [
+        Asset {
+            info: astro_info,
+            amount: state.total_astro_deposited,
+        },
+        Asset {
+            info: stable_info,
+            amount: state.total_stable_deposited,
+        },
+    ];
+
+    let prev_lp_balance = query_token_balance(
+        &deps.querier,
+        pair_info.liquidity_token.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: pair.to_string(),
+                amount: state.total_astro_deposited,
+                expires: None,
+            })?,
+            funds: vec![],
+        })
+        .add_message(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: assets.clone(),
+                slippage_tolerance,
+                auto_stake: None,
+                receiver: None,
+            })?,
+            funds: coins(state.total_stable_deposited.u128(), config.stable_denom),
+        })
+        .add_message(callback_msg(
+            &env,
+            CallbackMsg::UpdateStateOnPoolInit {
+                pair: pair.clone(),
+                lp_token: pair_info.liquidity_token,
+                prev_lp_balance,
+            },
+        )?)
+        .add_attributes(vec![
+            attr("action", "init_pool"),
+            attr("pair", pair),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+        ]))
+}
+
+/// ## Description
+/// Claims the sender's share of the ASTRO incentives. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+fn claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    if state.lp_token.is_none() {
+        return Err(ContractError::PoolNotInitialized {});
+    }
+
+    let mut user_info = USERS.load(deps.storage, &info.sender)?;
+    if user_info.astro_incentives_claimed {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let astro_incentives = calculate_astro_incentives(&state, &user_info);
+    user_info.astro_incentives_claimed = true;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !astro_incentives.is_zero() {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: config.astro_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount: astro_incentives,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "claim_rewards"),
+        attr("user", info.sender),
+        attr("astro_incentives", astro_incentives),
+    ]))
+}
+
+/// ## Description
+/// Withdraws vested LP tokens. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an [`Option`] field of type [`Uint128`]. This is the amount of LP tokens to withdraw.
+/// Everything that has vested is withdrawn if it isn't specified.
+fn withdraw_lp(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    let lp_token = state
+        .lp_token
+        .clone()
+        .ok_or(ContractError::PoolNotInitialized {})?;
+
+    let mut user_info = USERS.load(deps.storage, &info.sender)?;
+    let withdrawable = withdrawable_lp_shares(&config, &state, &user_info, &env)?;
+    let amount = amount.unwrap_or(withdrawable);
+
+    if amount > withdrawable {
+        return Err(ContractError::LpWithdrawalLimitExceeded(withdrawable));
+    }
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    user_info.lp_withdrawn = user_info.lp_withdrawn.checked_add(amount)?;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "withdraw_lp"),
+            attr("user", info.sender),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// Handles callbacks described in [`CallbackMsg`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`CallbackMsg`]. This is the callback action.
+///
+/// ## Executor
+/// Callback functions can only be called by this contract.
+fn handle_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    // Callback functions can only be called by this contract
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        CallbackMsg::UpdateStateOnPoolInit {
+            pair,
+            lp_token,
+            prev_lp_balance,
+        } => update_state_on_pool_init(deps, env, pair, lp_token, prev_lp_balance),
+    }
+}
+
+/// ## Description
+/// Saves the amount of LP tokens minted for the auction deposits and starts LP vesting.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **pair** is an object of type [`Addr`]. This is the ASTRO-stable pair contract address.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token contract address.
+///
+/// * **prev_lp_balance** is an object of type [`Uint128`]. This is the contract's LP token balance
+/// before liquidity was provided.
+fn update_state_on_pool_init(
+    deps: DepsMut,
+    env: Env,
+    pair: Addr,
+    lp_token: Addr,
+    prev_lp_balance: Uint128,
+) -> Result<Response, ContractError> {
+    let lp_shares_minted = query_token_balance(
+        &deps.querier,
+        lp_token.clone(),
+        env.contract.address.clone(),
+    )?
+    .checked_sub(prev_lp_balance)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    state.pair = Some(pair);
+    state.lp_token = Some(lp_token);
+    state.lp_shares_minted = lp_shares_minted;
+    state.pool_init_timestamp = env.block.time.seconds();
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_state_on_pool_init"),
+        attr("lp_shares_minted", lp_shares_minted),
+    ]))
+}
+
+/// ## Description
+/// Returns a message that executes a [`CallbackMsg`] on this contract.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`CallbackMsg`].
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Returns the maximum share of a stable deposit that can be withdrawn at the specified time.
+/// Everything can be withdrawn during the deposit window. During the first half of the withdrawal
+/// window up to 50% can be withdrawn, and the limit then decreases linearly to zero.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **now** is an object of type [`u64`]. This is the current timestamp in seconds.
+fn max_withdrawal_percent(config: &Config, now: u64) -> Decimal {
+    if now < config.withdrawal_start() {
+        return Decimal::one();
+    }
+
+    let elapsed = now - config.withdrawal_start();
+    if elapsed <= config.withdrawal_window / 2 {
+        Decimal::percent(50)
+    } else {
+        Decimal::from_ratio(
+            config.withdrawal_window.saturating_sub(elapsed),
+            config.withdrawal_window,
+        )
+    }
+}
+
+/// ## Description
+/// Returns the user's share of the LP tokens minted for the auction deposits. Both sides of the
+/// pool hold equal value at the initial price, so half of the LP tokens is distributed among ASTRO
+/// depositors and the other half among stable depositors, proportionally to their deposits.
+/// ## Params
+/// * **state** is an object of type [`StateResponse`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn calculate_lp_shares(state: &StateResponse, user_info: &UserInfo) -> Uint128 {
+    if state.total_astro_deposited.is_zero() || state.total_stable_deposited.is_zero() {
+        return Uint128::zero();
+    }
+
+    state.lp_shares_minted.multiply_ratio(
+        user_info.astro_deposited,
+        state.total_astro_deposited.u128() * 2,
+    ) + state.lp_shares_minted.multiply_ratio(
+        user_info.stable_deposited,
+        state.total_stable_deposited.u128() * 2,
+    )
+}
+
+/// ## Description
+/// Returns the user's share of the ASTRO incentives, which is proportional to their share of LP tokens.
+/// ## Params
+/// * **state** is an object of type [`StateResponse`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn calculate_astro_incentives(state: &StateResponse, user_info: &UserInfo) -> Uint128 {
+    if state.lp_shares_minted.is_zero() {
+        return Uint128::zero();
+    }
+
+    state.astro_incentives.multiply_ratio(
+        calculate_lp_shares(state, user_info),
+        state.lp_shares_minted,
+    )
+}
+
+/// ## Description
+/// Returns the amount of the user's LP tokens that have vested but were not withdrawn yet.
+/// LP tokens vest linearly over `lp_vesting_duration` seconds after the pool is initialized.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **state** is an object of type [`StateResponse`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+///
+/// * **env** is an object of type [`Env`].
+fn withdrawable_lp_shares(
+    config: &Config,
+    state: &StateResponse,
+    user_info: &UserInfo,
+    env: &Env,
+) -> StdResult<Uint128> {
+    if state.lp_token.is_none() {
+        return Ok(Uint128::zero());
+    }
+
+    let elapsed = env
+        .block
+        .time
+        .seconds()
+        .saturating_sub(state.pool_init_timestamp)
+        .min(config.lp_vesting_duration);
+    let vested =
+        calculate_lp_shares(state, user_info).multiply_ratio(elapsed, config.lp_vesting_duration);
+
+    Ok(vested.checked_sub(user_info.lp_withdrawn)?)
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::State {}** Returns the global auction state using a [`StateResponse`] object.
+///
+/// * **QueryMsg::UserInfo { address }** Returns information about a participant using a [`UserInfoResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                astro_token: config.astro_token,
+                stable_denom: config.stable_denom,
+                init_timestamp: config.init_timestamp,
+                deposit_window: config.deposit_window,
+                withdrawal_window: config.withdrawal_window,
+                lp_vesting_duration: config.lp_vesting_duration,
+            })
+        }
+        QueryMsg::State {} => to_binary(&STATE.load(deps.storage)?),
+        QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, env, address)?),
+    }
+}
+
+/// ## Description
+/// Returns information about a participant using a [`UserInfoResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **address** is an object of type [`String`]. This is the participant whose information is returned.
+fn query_user_info(deps: Deps, env: Env, address: String) -> StdResult<UserInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let user = addr_validate_to_lower(deps.api, &address)?;
+    let user_info = USERS.may_load(deps.storage, &user)?.unwrap_or_default();
+
+    Ok(UserInfoResponse {
+        astro_deposited: user_info.astro_deposited,
+        stable_deposited: user_info.stable_deposited,
+        withdrawal_flag: user_info.withdrawal_flag,
+        lp_shares: calculate_lp_shares(&state, &user_info),
+        withdrawable_lp_shares: withdrawable_lp_shares(&config, &state, &user_info, &env)?,
+        lp_withdrawn: user_info.lp_withdrawn,
+        astro_incentives: calculate_astro_incentives(&state, &user_info),
+        astro_incentives_claimed: user_info.astro_incentives_claimed,
+    })
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,64 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes auction contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid auction parameters")]
+    InvalidParams {},
+
+    #[error("Exactly one coin of the stable asset must be sent")]
+    InvalidFunds {},
+
+    #[error("The deposit window is closed")]
+    DepositWindowClosed {},
+
+    #[error("The withdrawal window is closed")]
+    WithdrawalWindowClosed {},
+
+    #[error("Only one withdrawal is allowed during the withdrawal window")]
+    AlreadyWithdrawn {},
+
+    #[error("Amount exceeds the maximum allowed withdrawal: {0}")]
+    WithdrawalLimitExceeded(Uint128),
+
+    #[error("The auction has already finished")]
+    AuctionFinished {},
+
+    #[error("The auction hasn't finished yet")]
+    AuctionNotFinished {},
+
+    #[error("Both ASTRO and the stable asset must be deposited to initialize the pool")]
+    NoDeposits {},
+
+    #[error("The pair must consist of ASTRO and the stable asset")]
+    InvalidPair {},
+
+    #[error("The pool was already initialized")]
+    PoolAlreadyInitialized {},
+
+    #[error("The pool hasn't been initialized yet")]
+    PoolNotInitialized {},
+
+    #[error("ASTRO incentives were already claimed")]
+    AlreadyClaimed {},
+
+    #[error("No LP tokens have vested yet")]
+    NothingToWithdraw {},
+
+    #[error("Amount exceeds the withdrawable LP tokens: {0}")]
+    LpWithdrawalLimitExceeded(Uint128),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
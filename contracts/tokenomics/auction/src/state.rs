@@ -0,0 +1,70 @@
+use astroport::auction::StateResponse;
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the auction contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The native stable asset that's paired with ASTRO
+    pub stable_denom: String,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds
+    pub withdrawal_window: u64,
+    /// The duration (in seconds) over which participants' LP tokens vest after the pool is initialized
+    pub lp_vesting_duration: u64,
+}
+
+impl Config {
+    /// Returns the timestamp (in seconds) when the withdrawal window starts.
+    pub fn withdrawal_start(&self) -> u64 {
+        self.init_timestamp + self.deposit_window
+    }
+
+    /// Returns the timestamp (in seconds) when both auction windows are closed.
+    pub fn auction_end(&self) -> u64 {
+        self.withdrawal_start() + self.withdrawal_window
+    }
+}
+
+/// ## Description
+/// This structure describes a participant's deposits and claims.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UserInfo {
+    /// The amount of deposited ASTRO
+    pub astro_deposited: Uint128,
+    /// The amount of the deposited stable asset
+    pub stable_deposited: Uint128,
+    /// Whether the stable asset was withdrawn during the withdrawal window
+    pub withdrawal_flag: bool,
+    /// The amount of LP tokens already withdrawn
+    pub lp_withdrawn: Uint128,
+    /// Whether the ASTRO incentives were claimed
+    pub astro_incentives_claimed: bool,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the global auction state at the given key
+pub const STATE: Item<StateResponse> = Item::new("state");
+
+/// ## Description
+/// Stores participants' deposits by their address
+pub const USERS: Map<&Addr, UserInfo> = Map::new("users");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
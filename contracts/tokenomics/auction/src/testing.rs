@@ -0,0 +1,679 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{native_asset_info, token_asset_info, Asset, PairInfo};
+use astroport::auction::{
+    CallbackMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse, UserInfoResponse,
+};
+use astroport::factory::PairType;
+use astroport::pair::ExecuteMsg as PairExecuteMsg;
+
+const DEPOSIT_WINDOW: u64 = 5 * 86400;
+const WITHDRAWAL_WINDOW: u64 = 2 * 86400;
+const LP_VESTING_DURATION: u64 = 90 * 86400;
+
+type AuctionDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn now() -> u64 {
+    mock_env().block.time.seconds()
+}
+
+fn auction_end() -> u64 {
+    now() + DEPOSIT_WINDOW + WITHDRAWAL_WINDOW
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(seconds - env.block.time.seconds());
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: String::from("owner"),
+        astro_token: String::from("astro"),
+        stable_denom: String::from("uusd"),
+        init_timestamp: now(),
+        deposit_window: DEPOSIT_WINDOW,
+        withdrawal_window: WITHDRAWAL_WINDOW,
+        lp_vesting_duration: LP_VESTING_DURATION,
+    }
+}
+
+fn setup() -> AuctionDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    deps
+}
+
+fn astro_hook(
+    deps: &mut AuctionDeps,
+    env: Env,
+    sender: &str,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(sender),
+            amount: Uint128::from(amount),
+            msg: to_binary(&msg).unwrap(),
+        }),
+    )
+    .map(|_| ())
+}
+
+fn deposit_stable(deps: &mut AuctionDeps, env: Env, user: &str, amount: u128) {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(user, &coins(amount, "uusd")),
+        ExecuteMsg::DepositStable {},
+    )
+    .unwrap();
+}
+
+fn withdraw_stable(
+    deps: &mut AuctionDeps,
+    env: Env,
+    user: &str,
+    amount: u128,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(user, &[]),
+        ExecuteMsg::WithdrawStable {
+            amount: Uint128::from(amount),
+        },
+    )
+    .map(|_| ())
+}
+
+fn query_state(deps: &AuctionDeps) -> StateResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap()
+}
+
+fn query_user_info(deps: &AuctionDeps, env: Env, user: &str) -> UserInfoResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::UserInfo {
+                address: String::from(user),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn cw20_msg(token: &str, msg: &Cw20ExecuteMsg) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from(token),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn astro_pair() -> PairInfo {
+    PairInfo {
+        asset_infos: [
+            native_asset_info(String::from("uusd")),
+            token_asset_info(Addr::unchecked("astro")),
+        ],
+        contract_addr: Addr::unchecked("astro_pair"),
+        liquidity_token: Addr::unchecked("astro_lp_token"),
+        pair_type: PairType::Xyk {},
+    }
+}
+
+/// Runs an auction in which "alice" deposits 300 ASTRO and 100 UST, "bob" deposits 100 ASTRO and
+/// 300 UST, and the pool is initialized with 1000 LP tokens minted for the deposits.
+fn setup_initialized_pool() -> AuctionDeps {
+    let mut deps = setup();
+
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "owner",
+        400,
+        Cw20HookMsg::IncreaseAstroIncentives {},
+    )
+    .unwrap();
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "alice",
+        300,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "bob",
+        100,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+    deposit_stable(&mut deps, mock_env(), "alice", 100);
+    deposit_stable(&mut deps, mock_env(), "bob", 300);
+
+    deps.querier.set_pair(astro_pair());
+    execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitPool {
+            pair: String::from("astro_pair"),
+            slippage_tolerance: None,
+        },
+    )
+    .unwrap();
+
+    deps.querier
+        .set_token_balance("astro_lp_token", MOCK_CONTRACT_ADDR, 1000);
+    execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::UpdateStateOnPoolInit {
+            pair: Addr::unchecked("astro_pair"),
+            lp_token: Addr::unchecked("astro_lp_token"),
+            prev_lp_balance: Uint128::zero(),
+        }),
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    for msg in [
+        InstantiateMsg {
+            init_timestamp: now() - 1,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            deposit_window: 0,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            lp_vesting_duration: 0,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            stable_denom: String::new(),
+            ..instantiate_msg()
+        },
+    ] {
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidParams {});
+    }
+
+    let deps = setup();
+    assert_eq!(query_state(&deps), StateResponse::default());
+}
+
+#[test]
+fn deposits() {
+    let mut deps = setup();
+
+    // Only ASTRO is accepted
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random_token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::DepositAstro {}).unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    for funds in [
+        vec![],
+        coins(100, "uluna"),
+        vec![coin(100, "uluna"), coin(100, "uusd")],
+        coins(0, "uusd"),
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &funds),
+            ExecuteMsg::DepositStable {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFunds {});
+    }
+
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "user",
+        100,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "user",
+        50,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+    deposit_stable(&mut deps, mock_env(), "user", 200);
+
+    let withdrawal_start = env_at(now() + DEPOSIT_WINDOW);
+    let err = astro_hook(
+        &mut deps,
+        withdrawal_start.clone(),
+        "user",
+        100,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowClosed {});
+    let err = execute(
+        deps.as_mut(),
+        withdrawal_start,
+        mock_info("user", &coins(100, "uusd")),
+        ExecuteMsg::DepositStable {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowClosed {});
+
+    let state = query_state(&deps);
+    assert_eq!(state.total_astro_deposited, Uint128::from(150u128));
+    assert_eq!(state.total_stable_deposited, Uint128::from(200u128));
+
+    let user_info = query_user_info(&deps, mock_env(), "user");
+    assert_eq!(user_info.astro_deposited, Uint128::from(150u128));
+    assert_eq!(user_info.stable_deposited, Uint128::from(200u128));
+    assert_eq!(user_info.lp_shares, Uint128::zero());
+}
+
+#[test]
+fn withdraw_stable_deposits() {
+    let mut deps = setup();
+    deposit_stable(&mut deps, mock_env(), "user", 100);
+
+    // Any amount can be withdrawn during the deposit window
+    let err = withdraw_stable(&mut deps, mock_env(), "user", 101).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(100u128))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawStable {
+            amount: Uint128::from(50u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("user"),
+            amount: coins(50, "uusd"),
+        })
+    );
+
+    // Up to 50% can be withdrawn during the first half of the withdrawal window
+    let withdrawal_start = now() + DEPOSIT_WINDOW;
+    let err = withdraw_stable(&mut deps, env_at(withdrawal_start), "user", 26).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(25u128))
+    );
+
+    // Then the limit decreases linearly
+    let env = env_at(withdrawal_start + WITHDRAWAL_WINDOW * 3 / 4);
+    let err = withdraw_stable(&mut deps, env.clone(), "user", 13).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(12u128))
+    );
+    withdraw_stable(&mut deps, env.clone(), "user", 12).unwrap();
+
+    // Only one withdrawal is allowed during the withdrawal window
+    let err = withdraw_stable(&mut deps, env, "user", 1).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyWithdrawn {});
+
+    let err = withdraw_stable(&mut deps, env_at(auction_end()), "user", 1).unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalWindowClosed {});
+
+    assert_eq!(
+        query_state(&deps).total_stable_deposited,
+        Uint128::from(38u128)
+    );
+}
+
+#[test]
+fn init_pool() {
+    let mut deps = setup();
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "user",
+        100,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+
+    let mut pair = astro_pair();
+    deps.querier.set_pair(pair.clone());
+
+    let init_msg = ExecuteMsg::InitPool {
+        pair: String::from("astro_pair"),
+        slippage_tolerance: None,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("user", &[]),
+        init_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end() - 1),
+        mock_info("owner", &[]),
+        init_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionNotFinished {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        init_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoDeposits {});
+
+    deposit_stable(&mut deps, mock_env(), "user", 200);
+
+    pair.asset_infos[0] = native_asset_info(String::from("uluna"));
+    pair.contract_addr = Addr::unchecked("luna_pair");
+    deps.querier.set_pair(pair);
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitPool {
+            pair: String::from("luna_pair"),
+            slippage_tolerance: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPair {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        init_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            cw20_msg(
+                "astro",
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: String::from("astro_pair"),
+                    amount: Uint128::from(100u128),
+                    expires: None,
+                }
+            ),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("astro_pair"),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset {
+                            info: token_asset_info(Addr::unchecked("astro")),
+                            amount: Uint128::from(100u128),
+                        },
+                        Asset {
+                            info: native_asset_info(String::from("uusd")),
+                            amount: Uint128::from(200u128),
+                        },
+                    ],
+                    slippage_tolerance: None,
+                    auto_stake: None,
+                    receiver: None,
+                })
+                .unwrap(),
+                funds: coins(200, "uusd"),
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::UpdateStateOnPoolInit {
+                    pair: Addr::unchecked("astro_pair"),
+                    lp_token: Addr::unchecked("astro_lp_token"),
+                    prev_lp_balance: Uint128::zero(),
+                }))
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // Callbacks can only be executed by the contract itself
+    let callback = ExecuteMsg::Callback(CallbackMsg::UpdateStateOnPoolInit {
+        pair: Addr::unchecked("astro_pair"),
+        lp_token: Addr::unchecked("astro_lp_token"),
+        prev_lp_balance: Uint128::zero(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        callback.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    deps.querier
+        .set_token_balance("astro_lp_token", MOCK_CONTRACT_ADDR, 141);
+    execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        callback,
+    )
+    .unwrap();
+
+    let state = query_state(&deps);
+    assert_eq!(state.pair, Some(Addr::unchecked("astro_pair")));
+    assert_eq!(state.lp_token, Some(Addr::unchecked("astro_lp_token")));
+    assert_eq!(state.lp_shares_minted, Uint128::from(141u128));
+    assert_eq!(state.pool_init_timestamp, auction_end());
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("owner", &[]),
+        init_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolAlreadyInitialized {});
+}
+
+#[test]
+fn claim_rewards_and_withdraw_lp() {
+    let mut deps = setup();
+    astro_hook(
+        &mut deps,
+        mock_env(),
+        "alice",
+        300,
+        Cw20HookMsg::DepositAstro {},
+    )
+    .unwrap();
+
+    // Nothing can be claimed before the pool is initialized
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolNotInitialized {});
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawLp { amount: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolNotInitialized {});
+
+    let mut deps = setup_initialized_pool();
+
+    // Alice holds 3/4 of the ASTRO side and 1/4 of the stable side, so she receives half of the LP
+    // tokens and half of the incentives, same as Bob
+    for user in ["alice", "bob"] {
+        let user_info = query_user_info(&deps, env_at(auction_end()), user);
+        assert_eq!(user_info.lp_shares, Uint128::from(500u128));
+        assert_eq!(user_info.astro_incentives, Uint128::from(200u128));
+        assert_eq!(user_info.withdrawable_lp_shares, Uint128::zero());
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_msg(
+            "astro",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("alice"),
+                amount: Uint128::from(200u128),
+            }
+        )
+    );
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyClaimed {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(auction_end()),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawLp { amount: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+
+    // LP tokens vest linearly
+    let env = env_at(auction_end() + LP_VESTING_DURATION / 5);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawLp {
+            amount: Some(Uint128::from(101u128)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::LpWithdrawalLimitExceeded(Uint128::from(100u128))
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawLp {
+            amount: Some(Uint128::from(40u128)),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_msg(
+            "astro_lp_token",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("alice"),
+                amount: Uint128::from(40u128),
+            }
+        )
+    );
+
+    let user_info = query_user_info(&deps, env, "alice");
+    assert_eq!(user_info.lp_withdrawn, Uint128::from(40u128));
+    assert_eq!(user_info.withdrawable_lp_shares, Uint128::from(60u128));
+    assert!(user_info.astro_incentives_claimed);
+
+    // Everything is withdrawable once vesting ends
+    let env = env_at(auction_end() + 2 * LP_VESTING_DURATION);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawLp { amount: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_msg(
+            "astro_lp_token",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("alice"),
+                amount: Uint128::from(460u128),
+            }
+        )
+    );
+    assert_eq!(
+        query_user_info(&deps, env, "alice").withdrawable_lp_shares,
+        Uint128::zero()
+    );
+}
@@ -0,0 +1,637 @@
+use crate::error::ContractError;
+use crate::state::{
+    Config, BRIBES, CLAIMS, CONFIG, NEXT_BRIBE_ID, OWNERSHIP_PROPOSAL, POOL_BRIBES,
+};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
+use astroport::bribes::{
+    BribeInfo, ConfigResponse, ControllerQueryMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, PoolVotesResponse, QueryMsg, MAX_BRIBES_PER_PERIOD,
+};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U64Key;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-bribes";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        generator_controller: addr_validate_to_lower(deps.api, &msg.generator_controller)?,
+        claim_window: msg.claim_window,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_BRIBE_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::DepositBribe { lp_token, period }** Deposits the sent native tokens as a bribe.
+///
+/// * **ExecuteMsg::ClaimBribes { lp_token, period }** Claims the sender's share of a pool's bribes.
+///
+/// * **ExecuteMsg::RecoverBribe { bribe_id }** Sends the unclaimed part of an expired bribe back to the briber.
+///
+/// * **ExecuteMsg::UpdateConfig { generator_controller, claim_window }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::DepositBribe { lp_token, period } => {
+            let funds: Vec<_> = info
+                .funds
+                .iter()
+                .filter(|coin| !coin.amount.is_zero())
+                .collect();
+            if funds.len() != 1 {
+                return Err(ContractError::InvalidFunds {});
+            }
+
+            let reward = Asset {
+                info: AssetInfo::NativeToken {
+                    denom: funds[0].denom.clone(),
+                },
+                amount: funds[0].amount,
+            };
+            deposit_bribe(deps, info.sender, lp_token, period, reward)
+        }
+        ExecuteMsg::ClaimBribes { lp_token, period } => {
+            claim_bribes(deps, env, info, lp_token, period)
+        }
+        ExecuteMsg::RecoverBribe { bribe_id } => recover_bribe(deps, env, info, bribe_id),
+        ExecuteMsg::UpdateConfig {
+            generator_controller,
+            claim_window,
+        } => update_config(deps, info, generator_controller, claim_window),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then a [`ContractError`] is returned.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message that has to be processed.
+fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::DepositBribe { lp_token, period } => {
+            if cw20_msg.amount.is_zero() {
+                return Err(ContractError::InvalidFunds {});
+            }
+
+            let briber = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            let reward = Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            };
+            deposit_bribe(deps, briber, lp_token, period, reward)
+        }
+    }
+}
+
+/// ## Description
+/// Deposits a bribe for the voters of a pool in a period that isn't tuned yet. Deposits of the same
+/// token by the same briber are merged into one bribe. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **briber** is an object of type [`Addr`]. This is the address that deposits the bribe.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the bribed pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period of the bribe.
+///
+/// * **reward** is an object of type [`Asset`]. This is the deposited bribe.
+fn deposit_bribe(
+    deps: DepsMut,
+    briber: Addr,
+    lp_token: String,
+    period: u64,
+    reward: Asset,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let votes = query_pool_votes(deps.as_ref(), &config, &lp_token, period)?;
+    if votes.tuned_at.is_some() {
+        return Err(ContractError::PeriodAlreadyTuned(period));
+    }
+
+    let mut bribe_ids = POOL_BRIBES
+        .may_load(deps.storage, (&lp_token, U64Key::new(period)))?
+        .unwrap_or_default();
+
+    let mut existing_bribe = None;
+    for bribe_id in &bribe_ids {
+        let bribe = BRIBES.load(deps.storage, U64Key::new(*bribe_id))?;
+        if bribe.briber == briber && bribe.reward.info == reward.info {
+            existing_bribe = Some(bribe);
+            break;
+        }
+    }
+
+    let bribe = match existing_bribe {
+        Some(mut bribe) => {
+            bribe.reward.amount = bribe.reward.amount.checked_add(reward.amount)?;
+            bribe
+        }
+        None => {
+            if bribe_ids.len() >= MAX_BRIBES_PER_PERIOD {
+                return Err(ContractError::TooManyBribes(MAX_BRIBES_PER_PERIOD));
+            }
+
+            let bribe_id = NEXT_BRIBE_ID.load(deps.storage)?;
+            NEXT_BRIBE_ID.save(deps.storage, &(bribe_id + 1))?;
+            bribe_ids.push(bribe_id);
+            POOL_BRIBES.save(deps.storage, (&lp_token, U64Key::new(period)), &bribe_ids)?;
+
+            BribeInfo {
+                id: bribe_id,
+                briber,
+                lp_token: lp_token.clone(),
+                period,
+                reward: reward.clone(),
+                claimed: Uint128::zero(),
+                recovered: false,
+            }
+        }
+    };
+    BRIBES.save(deps.storage, U64Key::new(bribe.id), &bribe)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deposit_bribe"),
+        attr("bribe_id", bribe.id.to_string()),
+        attr("briber", bribe.briber),
+        attr("lp_token", lp_token),
+        attr("period", period.to_string()),
+        attr("reward", reward.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Claims the sender's share of all bribes deposited for a pool in a tuned period. The share of every
+/// bribe is proportional to the sender's vote weight for the pool. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool the sender voted for.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period to claim bribes for.
+fn claim_bribes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    period: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let votes = query_pool_votes(deps.as_ref(), &config, &lp_token, period)?;
+    let tuned_at = votes
+        .tuned_at
+        .ok_or(ContractError::PeriodNotTuned(period))?;
+    if env.block.time.seconds() >= tuned_at + config.claim_window {
+        return Err(ContractError::ClaimWindowClosed(period));
+    }
+
+    if CLAIMS.has(deps.storage, (&lp_token, U64Key::new(period), &info.sender)) {
+        return Err(ContractError::AlreadyClaimed(period));
+    }
+
+    let user_weight = query_user_vote(deps.as_ref(), &config, &info.sender, &lp_token, period)?;
+    if user_weight.is_zero() {
+        return Err(ContractError::NoVotes(period));
+    }
+
+    let mut messages = vec![];
+    let bribe_ids = POOL_BRIBES
+        .may_load(deps.storage, (&lp_token, U64Key::new(period)))?
+        .unwrap_or_default();
+    for bribe_id in bribe_ids {
+        let mut bribe = BRIBES.load(deps.storage, U64Key::new(bribe_id))?;
+        let share = bribe
+            .reward
+            .amount
+            .multiply_ratio(user_weight, votes.total_weight);
+        if share.is_zero() {
+            continue;
+        }
+
+        bribe.claimed = bribe.claimed.checked_add(share)?;
+        BRIBES.save(deps.storage, U64Key::new(bribe_id), &bribe)?;
+
+        messages.push(
+            Asset {
+                info: bribe.reward.info,
+                amount: share,
+            }
+            .into_msg(&deps.querier, info.sender.clone())?,
+        );
+    }
+
+    CLAIMS.save(
+        deps.storage,
+        (&lp_token, U64Key::new(period), &info.sender),
+        &(),
+    )?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "claim_bribes"),
+        attr("user", info.sender),
+        attr("lp_token", lp_token),
+        attr("period", period.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Sends the unclaimed part of a bribe back to the briber once the claim window of its period is closed.
+/// Bribes for a pool that received no votes can be recovered as soon as the period is tuned.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **bribe_id** is an object of type [`u64`]. This is the identifier of the bribe to recover.
+///
+/// ## Executor
+/// Only the briber can execute this.
+fn recover_bribe(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bribe_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut bribe = BRIBES
+        .may_load(deps.storage, U64Key::new(bribe_id))?
+        .ok_or(ContractError::BribeNotFound(bribe_id))?;
+
+    // Permission check
+    if info.sender != bribe.briber {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let votes = query_pool_votes(deps.as_ref(), &config, &bribe.lp_token, bribe.period)?;
+    let expired = match votes.tuned_at {
+        Some(tuned_at) => {
+            votes.total_weight.is_zero()
+                || env.block.time.seconds() >= tuned_at + config.claim_window
+        }
+        None => false,
+    };
+    if !expired {
+        return Err(ContractError::BribeNotExpired(bribe_id));
+    }
+
+    let unclaimed = bribe.reward.amount.checked_sub(bribe.claimed)?;
+    if bribe.recovered || unclaimed.is_zero() {
+        return Err(ContractError::NothingToRecover(bribe_id));
+    }
+
+    bribe.recovered = true;
+    BRIBES.save(deps.storage, U64Key::new(bribe_id), &bribe)?;
+
+    let message = Asset {
+        info: bribe.reward.info,
+        amount: unclaimed,
+    }
+    .into_msg(&deps.querier, bribe.briber.clone())?;
+
+    Ok(Response::new().add_message(message).add_attributes(vec![
+        attr("action", "recover_bribe"),
+        attr("bribe_id", bribe_id.to_string()),
+        attr("briber", bribe.briber),
+        attr("amount", unclaimed),
+    ]))
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **generator_controller** is an [`Option`] field of type [`String`]. This is the new generator controller contract.
+///
+/// * **claim_window** is an [`Option`] field of type [`u64`]. This is the new claim window (in seconds).
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    generator_controller: Option<String>,
+    claim_window: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(generator_controller) = generator_controller {
+        config.generator_controller = addr_validate_to_lower(deps.api, &generator_controller)?;
+    }
+    if let Some(claim_window) = claim_window {
+        config.claim_window = claim_window;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Returns the votes of a pool in a period from the generator controller.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period.
+fn query_pool_votes(
+    deps: Deps,
+    config: &Config,
+    lp_token: &Addr,
+    period: u64,
+) -> StdResult<PoolVotesResponse> {
+    deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::PoolVotes {
+            lp_token: lp_token.to_string(),
+            period,
+        },
+    )
+}
+
+/// ## Description
+/// Returns the vote weight a user gave to a pool in a period from the generator controller.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **user** is an object of type [`Addr`]. This is the voter.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period.
+fn query_user_vote(
+    deps: Deps,
+    config: &Config,
+    user: &Addr,
+    lp_token: &Addr,
+    period: u64,
+) -> StdResult<Uint128> {
+    deps.querier.query_wasm_smart(
+        &config.generator_controller,
+        &ControllerQueryMsg::UserVote {
+            user: user.to_string(),
+            lp_token: lp_token.to_string(),
+            period,
+        },
+    )
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Bribe { bribe_id }** Returns a bribe using a [`BribeInfo`] object.
+///
+/// * **QueryMsg::PoolBribes { lp_token, period }** Returns all bribes deposited for a pool in a period.
+///
+/// * **QueryMsg::Claimable { lp_token, period, user }** Returns the bribes a user can claim for a pool in a period.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                generator_controller: config.generator_controller,
+                claim_window: config.claim_window,
+            })
+        }
+        QueryMsg::Bribe { bribe_id } => {
+            to_binary(&BRIBES.load(deps.storage, U64Key::new(bribe_id))?)
+        }
+        QueryMsg::PoolBribes { lp_token, period } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            to_binary(&query_pool_bribes(deps, &lp_token, period)?)
+        }
+        QueryMsg::Claimable {
+            lp_token,
+            period,
+            user,
+        } => to_binary(&query_claimable(deps, env, lp_token, period, user)?),
+    }
+}
+
+/// ## Description
+/// Returns all bribes deposited for a pool in a period.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period.
+fn query_pool_bribes(deps: Deps, lp_token: &Addr, period: u64) -> StdResult<Vec<BribeInfo>> {
+    POOL_BRIBES
+        .may_load(deps.storage, (lp_token, U64Key::new(period)))?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|bribe_id| BRIBES.load(deps.storage, U64Key::new(bribe_id)))
+        .collect()
+}
+
+/// ## Description
+/// Returns the bribes a user can claim for a pool in a period. Nothing is claimable before the period
+/// is tuned, after the claim window is closed or once the user has claimed.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period.
+///
+/// * **user** is an object of type [`String`]. This is the voter.
+fn query_claimable(
+    deps: Deps,
+    env: Env,
+    lp_token: String,
+    period: u64,
+    user: String,
+) -> StdResult<Vec<Asset>> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    let votes = query_pool_votes(deps, &config, &lp_token, period)?;
+    let claimable = match votes.tuned_at {
+        Some(tuned_at) => {
+            env.block.time.seconds() < tuned_at + config.claim_window
+                && !CLAIMS.has(deps.storage, (&lp_token, U64Key::new(period), &user))
+        }
+        None => false,
+    };
+    if !claimable {
+        return Ok(vec![]);
+    }
+
+    let user_weight = query_user_vote(deps, &config, &user, &lp_token, period)?;
+    if user_weight.is_zero() {
+        return Ok(vec![]);
+    }
+
+    Ok(query_pool_bribes(deps, &lp_token, period)?
+        .into_iter()
+        .map(|bribe| Asset {
+            amount: bribe
+                .reward
+                .amount
+                .multiply_ratio(user_weight, votes.total_weight),
+            info: bribe.reward.info,
+        })
+        .filter(|asset| !asset.amount.is_zero())
+        .collect())
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes bribes contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Bribes must be deposited in exactly one non-zero native token or a CW20 token")]
+    InvalidFunds {},
+
+    #[error("Period {0} is already tuned")]
+    PeriodAlreadyTuned(u64),
+
+    #[error("Period {0} is not tuned yet")]
+    PeriodNotTuned(u64),
+
+    #[error("A pool can't have more than {0} bribes in one period")]
+    TooManyBribes(usize),
+
+    #[error("The claim window for period {0} is closed")]
+    ClaimWindowClosed(u64),
+
+    #[error("Bribes for period {0} are already claimed")]
+    AlreadyClaimed(u64),
+
+    #[error("The user didn't vote for the pool in period {0}")]
+    NoVotes(u64),
+
+    #[error("Bribe {0} not found")]
+    BribeNotFound(u64),
+
+    #[error("Bribe {0} can't be recovered until its claim window is closed")]
+    BribeNotExpired(u64),
+
+    #[error("Bribe {0} has nothing left to recover")]
+    NothingToRecover(u64),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
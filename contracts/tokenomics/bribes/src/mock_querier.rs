@@ -0,0 +1,117 @@
+use astroport::bribes::{ControllerQueryMsg, PoolVotesResponse};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    controller: String,
+    // pool votes keyed by LP token and period
+    pool_votes: HashMap<(String, u64), PoolVotesResponse>,
+    // user vote weights keyed by user, LP token and period
+    user_votes: HashMap<(String, String, u64), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if *contract_addr == self.controller =>
+            {
+                match from_binary(msg).unwrap() {
+                    ControllerQueryMsg::PoolVotes { lp_token, period } => {
+                        let votes = self.pool_votes.get(&(lp_token, period)).cloned().unwrap_or(
+                            PoolVotesResponse {
+                                total_weight: Uint128::zero(),
+                                tuned_at: None,
+                            },
+                        );
+                        SystemResult::Ok(to_binary(&votes).into())
+                    }
+                    ControllerQueryMsg::UserVote {
+                        user,
+                        lp_token,
+                        period,
+                    } => {
+                        let weight = self
+                            .user_votes
+                            .get(&(user, lp_token, period))
+                            .cloned()
+                            .unwrap_or_default();
+                        SystemResult::Ok(to_binary(&weight).into())
+                    }
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            controller: String::default(),
+            pool_votes: HashMap::new(),
+            user_votes: HashMap::new(),
+        }
+    }
+
+    pub fn set_controller(&mut self, controller: &str) {
+        self.controller = controller.to_string();
+    }
+
+    pub fn set_pool_votes(
+        &mut self,
+        lp_token: &str,
+        period: u64,
+        total_weight: u128,
+        tuned_at: Option<u64>,
+    ) {
+        self.pool_votes.insert(
+            (lp_token.to_string(), period),
+            PoolVotesResponse {
+                total_weight: Uint128::from(total_weight),
+                tuned_at,
+            },
+        );
+    }
+
+    pub fn set_user_vote(&mut self, user: &str, lp_token: &str, period: u64, weight: u128) {
+        self.user_votes.insert(
+            (user.to_string(), lp_token.to_string(), period),
+            Uint128::from(weight),
+        );
+    }
+}
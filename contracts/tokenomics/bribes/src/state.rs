@@ -0,0 +1,42 @@
+use astroport::bribes::BribeInfo;
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the bribes contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The generator controller contract that vxASTRO holders vote in
+    pub generator_controller: Addr,
+    /// The amount of seconds after a period is tuned during which voters can claim bribes
+    pub claim_window: u64,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores bribes keyed by their identifier
+pub const BRIBES: Map<U64Key, BribeInfo> = Map::new("bribes");
+
+/// ## Description
+/// Stores the identifiers of the bribes deposited for a pool keyed by LP token and period
+pub const POOL_BRIBES: Map<(&Addr, U64Key), Vec<u64>> = Map::new("pool_bribes");
+
+/// ## Description
+/// Stores the voters that claimed their bribes keyed by LP token, period and voter
+pub const CLAIMS: Map<(&Addr, U64Key, &Addr), ()> = Map::new("claims");
+
+/// ## Description
+/// Stores the identifier assigned to the next bribe
+pub const NEXT_BRIBE_ID: Item<u64> = Item::new("next_bribe_id");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
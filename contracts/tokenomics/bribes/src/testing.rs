@@ -0,0 +1,352 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{Asset, AssetInfo};
+use astroport::bribes::{BribeInfo, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+type BribesDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+const CLAIM_WINDOW: u64 = 1000;
+
+fn token_asset(contract_addr: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked(contract_addr),
+        },
+        amount: Uint128::from(amount),
+    }
+}
+
+fn env_at(offset: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(offset);
+    env
+}
+
+fn setup() -> BribesDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_controller("controller");
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            generator_controller: String::from("controller"),
+            claim_window: CLAIM_WINDOW,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn deposit_cw20_bribe(
+    deps: &mut BribesDeps,
+    token: &str,
+    briber: &str,
+    amount: u128,
+    period: u64,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: briber.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::DepositBribe {
+                lp_token: String::from("lp_token"),
+                period,
+            })
+            .unwrap(),
+        }),
+    )
+    .map(|_| ())
+}
+
+fn query_pool_bribes(deps: &BribesDeps, period: u64) -> Vec<BribeInfo> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PoolBribes {
+                lp_token: String::from("lp_token"),
+                period,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn cw20_transfer(token: &str, recipient: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn deposit_bribe() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::DepositBribe {
+        lp_token: String::from("lp_token"),
+        period: 1,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[coin(100, "uusd"), coin(100, "uluna")]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[coin(100, "uusd")]),
+        msg,
+    )
+    .unwrap();
+
+    // Deposits of the same token by the same briber are merged
+    deposit_cw20_bribe(&mut deps, "token", "briber", 1000, 1).unwrap();
+    deposit_cw20_bribe(&mut deps, "token", "briber", 500, 1).unwrap();
+    deposit_cw20_bribe(&mut deps, "token", "other_briber", 300, 1).unwrap();
+
+    let bribes = query_pool_bribes(&deps, 1);
+    assert_eq!(
+        bribes
+            .iter()
+            .map(|bribe| (bribe.id, bribe.briber.as_str(), bribe.reward.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                1,
+                "briber",
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: String::from("uusd"),
+                    },
+                    amount: Uint128::from(100u128),
+                }
+            ),
+            (2, "briber", token_asset("token", 1500)),
+            (3, "other_briber", token_asset("token", 300)),
+        ]
+    );
+    assert!(query_pool_bribes(&deps, 2).is_empty());
+
+    // Bribes can't be added once the period is tuned
+    deps.querier
+        .set_pool_votes("lp_token", 1, 100, Some(mock_env().block.time.seconds()));
+    let err = deposit_cw20_bribe(&mut deps, "token", "briber", 1000, 1).unwrap_err();
+    assert_eq!(err, ContractError::PeriodAlreadyTuned(1));
+}
+
+#[test]
+fn claim_bribes() {
+    let mut deps = setup();
+
+    deposit_cw20_bribe(&mut deps, "token", "briber", 1000, 1).unwrap();
+    deposit_cw20_bribe(&mut deps, "other_token", "other_briber", 400, 1).unwrap();
+    deps.querier.set_user_vote("alice", "lp_token", 1, 30);
+    deps.querier.set_user_vote("bob", "lp_token", 1, 10);
+
+    let claim_msg = ExecuteMsg::ClaimBribes {
+        lp_token: String::from("lp_token"),
+        period: 1,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        claim_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PeriodNotTuned(1));
+
+    // Other voters of the pool hold the remaining half of the votes
+    deps.querier
+        .set_pool_votes("lp_token", 1, 80, Some(mock_env().block.time.seconds()));
+
+    let claimable: Vec<Asset> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Claimable {
+                lp_token: String::from("lp_token"),
+                period: 1,
+                user: String::from("alice"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        claimable,
+        vec![token_asset("token", 375), token_asset("other_token", 150)]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        claim_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            cw20_transfer("token", "alice", 375),
+            cw20_transfer("other_token", "alice", 150),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        claim_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyClaimed(1));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        claim_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoVotes(1));
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(CLAIM_WINDOW),
+        mock_info("bob", &[]),
+        claim_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ClaimWindowClosed(1));
+
+    let bribes = query_pool_bribes(&deps, 1);
+    assert_eq!(bribes[0].claimed, Uint128::from(375u128));
+    assert_eq!(bribes[1].claimed, Uint128::from(150u128));
+}
+
+#[test]
+fn recover_bribe() {
+    let mut deps = setup();
+
+    deposit_cw20_bribe(&mut deps, "token", "briber", 1000, 1).unwrap();
+    deposit_cw20_bribe(&mut deps, "token", "briber", 700, 2).unwrap();
+    deps.querier.set_user_vote("alice", "lp_token", 1, 25);
+
+    let recover_msg = ExecuteMsg::RecoverBribe { bribe_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[]),
+        recover_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BribeNotExpired(1));
+
+    deps.querier
+        .set_pool_votes("lp_token", 1, 100, Some(mock_env().block.time.seconds()));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimBribes {
+            lp_token: String::from("lp_token"),
+            period: 1,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(CLAIM_WINDOW - 1),
+        mock_info("briber", &[]),
+        recover_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BribeNotExpired(1));
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(CLAIM_WINDOW),
+        mock_info("alice", &[]),
+        recover_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(CLAIM_WINDOW),
+        mock_info("briber", &[]),
+        recover_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, cw20_transfer("token", "briber", 750));
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(CLAIM_WINDOW),
+        mock_info("briber", &[]),
+        recover_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingToRecover(1));
+
+    // Bribes for a pool without votes can be recovered right after the period is tuned
+    deps.querier
+        .set_pool_votes("lp_token", 2, 0, Some(mock_env().block.time.seconds()));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[]),
+        ExecuteMsg::RecoverBribe { bribe_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, cw20_transfer("token", "briber", 700));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber", &[]),
+        ExecuteMsg::RecoverBribe { bribe_id: 3 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BribeNotFound(3));
+}
@@ -3,7 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use astroport::generator::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use astroport::asset::AssetInfo;
+use astroport::generator::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PendingTokenResponse,
+    PoolAprInputsResponse, PoolInfoResponse, PoolLengthResponse, PoolListResponse, QueryMsg,
+    RewardInfoResponse, StakerResponse,
+};
+use cosmwasm_std::Uint128;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -15,4 +21,58 @@ fn main() {
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(Cw20HookMsg), &out_dir, "Cw20HookMsg");
+    export_schema_with_title(
+        &schema_for!(PoolLengthResponse),
+        &out_dir,
+        "ActivePoolLengthResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(PoolLengthResponse),
+        &out_dir,
+        "PoolLengthResponse",
+    );
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "DepositResponse");
+    export_schema_with_title(
+        &schema_for!(PendingTokenResponse),
+        &out_dir,
+        "PendingTokenResponse",
+    );
+    export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(RewardInfoResponse),
+        &out_dir,
+        "RewardInfoResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Uint128),
+        &out_dir,
+        "OrphanProxyRewardsResponse",
+    );
+    export_schema_with_title(&schema_for!(PoolInfoResponse), &out_dir, "PoolInfoResponse");
+    export_schema_with_title(
+        &schema_for!(Uint128),
+        &out_dir,
+        "SimulateFutureRewardResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<StakerResponse>),
+        &out_dir,
+        "PoolStakersResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<AssetInfo>),
+        &out_dir,
+        "BlockedListTokensResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(PoolAprInputsResponse),
+        &out_dir,
+        "PoolAprInputsResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(Vec<PoolListResponse>),
+        &out_dir,
+        "PoolListResponse",
+    );
 }
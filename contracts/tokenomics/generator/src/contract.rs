@@ -1,45 +1,76 @@
 use cosmwasm_std::{
-    attr, entry_point, from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128, Uint64,
-    WasmMsg,
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, QuerierWrapper, Reply, ReplyOn, Response, StdError, StdResult, Storage,
+    SubMsg, Uint128, Uint64, WasmMsg,
 };
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse};
 use std::collections::HashSet;
+use std::slice;
+use std::str::FromStr;
 
 use crate::error::ContractError;
 use crate::migration;
 use crate::state::{
-    update_user_balance, Config, ExecuteOnReply, UserInfo, CONFIG, DEFAULT_LIMIT, MAX_LIMIT,
-    OWNERSHIP_PROPOSAL, POOL_INFO, TMP_USER_ACTION, USER_INFO,
+    accrued_rewards, addr_from_key, load_pool, split_user_info_key, update_user_balance, user_info,
+    Config, ExecuteOnReply, UserInfo, UsersMigration, VestingClaim, ALT_REWARD_TOKENS, CONFIG,
+    DEFAULT_LIMIT, DEFAULT_MIGRATION_LIMIT, EMISSION_SCHEDULE, KEEPER_TIP_BUDGET, MAX_ACTIVE_POOLS,
+    MAX_BOOST_MULTIPLIER_PERCENT, MAX_EMISSION_EPOCHS, MAX_LIMIT, MAX_MIGRATION_LIMIT,
+    MAX_REWARD_PROXIES, MAX_SCHEDULED_SETS, OPERATORS, ORPHAN_STAKES, OWNERSHIP_PROPOSAL,
+    POOL_INFO, POOL_LENGTH, SCHEDULED_SETS, SNAPSHOTS_START_BLOCK, TMP_USER_ACTION,
+    TMP_VESTING_CLAIM, UNPAID_ASTRO, USERS_MIGRATION, VE_BOOST_BASE_PERCENT,
 };
 use astroport::asset::{
-    addr_validate_to_lower, pair_info_by_pool, token_asset_info, AssetInfo, PairInfo,
+    addr_validate_to_lower, native_asset_info, pair_info_by_pool, token_asset_info, Asset,
+    AssetInfo, PairInfo,
 };
 
-use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
-use astroport::factory::{PairConfig, PairType};
-use astroport::generator::PoolInfo;
-use astroport::generator::StakerResponse;
-use astroport::querier::query_token_balance;
+use astroport::attr_builder::AttrBuilder;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::constants::MAX_ALLOC_POINT;
+use astroport::factory::{PairConfig, PairType, UpdateAddr};
+use astroport::generator::{
+    AltRewardInfo, BoostProvider, BoostQueryMsg, BoostResponse, EmissionEpoch, KeeperIncentive,
+    PoolInfo, RewardProxyInfo, ScheduledSet,
+};
+use astroport::generator::{
+    EmissionRateResponse, PoolPendingTokenResponse, StakedPoolResponse, StakerResponse,
+    UserInfoResponse,
+};
+use astroport::querier::{query_fee_info, query_token_balance};
+use astroport::simulation::{
+    calculate_generator_rewards, calculate_pending_rewards, calculate_rewards_per_share,
+};
+use astroport::voting_escrow::{VotingEscrowQueryMsg, VotingPowerResponse};
 use astroport::DecimalCheckedOps;
 use astroport::{
     factory::{ConfigResponse as FactoryConfigResponse, QueryMsg as FactoryQueryMsg},
     generator::{
         ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PendingTokenResponse,
-        PoolInfoResponse, PoolLengthResponse, QueryMsg, RewardInfoResponse,
+        PoolAprInputsResponse, PoolInfoResponse, PoolLengthResponse, PoolListResponse, QueryMsg,
+        RewardInfoResponse,
     },
     generator_proxy::{
-        Cw20HookMsg as ProxyCw20HookMsg, ExecuteMsg as ProxyExecuteMsg, QueryMsg as ProxyQueryMsg,
+        ConfigResponse as ProxyConfigResponse, Cw20HookMsg as ProxyCw20HookMsg,
+        ExecuteMsg as ProxyExecuteMsg, QueryMsg as ProxyQueryMsg,
     },
+    pair::{PoolResponse as PairPoolResponse, QueryMsg as PairQueryMsg},
     vesting::ExecuteMsg as VestingExecuteMsg,
 };
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 use cw_storage_plus::{Bound, PrimaryKey};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-generator";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// A `reply` call code ID used for proxy reward updates that don't resume the pending user action.
+const PROXY_REWARDS_UPDATE_REPLY_ID: u64 = 1;
+/// A `reply` call code ID used for the last proxy reward update, which resumes the pending user action.
+const PROCESS_USER_ACTION_REPLY_ID: u64 = 2;
+/// A `reply` call code ID used for ASTRO claims from the vesting contract.
+const VESTING_CLAIM_REPLY_ID: u64 = 3;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`] struct.
@@ -55,7 +86,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -79,6 +110,10 @@ pub fn instantiate(
         vesting_contract: addr_validate_to_lower(deps.api, &msg.vesting_contract)?,
         active_pools: vec![],
         blocked_list_tokens: vec![],
+        keeper_incentive: None,
+        boost_provider: None,
+        voting_escrow: None,
+        external_lp_tokens: vec![],
     };
 
     if let Some(generator_controller) = msg.generator_controller {
@@ -91,6 +126,8 @@ pub fn instantiate(
 
     CONFIG.save(deps.storage, &config)?;
     TMP_USER_ACTION.save(deps.storage, &None)?;
+    SNAPSHOTS_START_BLOCK.save(deps.storage, &env.block.height)?;
+    POOL_LENGTH.save(deps.storage, &0)?;
 
     Ok(Response::default())
 }
@@ -120,7 +157,7 @@ pub fn instantiate(
 ///             has_asset_rewards,
 ///         }** Update the given pool's has_asset_rewards parameter.
 ///
-/// * **ExecuteMsg::ClaimRewards { lp_token }** Updates reward and returns it to user.
+/// * **ExecuteMsg::ClaimRewards { lp_tokens }** Sends the sender's pending rewards without changing their staked amounts.
 ///
 /// * **ExecuteMsg::Withdraw { lp_token, amount }** Withdraw LP tokens from the Generator.
 ///
@@ -133,7 +170,10 @@ pub fn instantiate(
 /// * **ExecuteMsg::SendOrphanProxyReward {
 ///             recipient,
 ///             lp_token,
-///         }** Sends orphan proxy rewards to another address.
+///         }** Sends the orphan rewards of every reward proxy to another address.
+///
+/// * **ExecuteMsg::AddRewardProxy { lp_token, proxy }** Adds a reward proxy that pays extra rewards
+/// to the stakers of a generator without holding their LP tokens.
 ///
 /// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
 /// it depending on the received template.
@@ -150,10 +190,71 @@ pub fn instantiate(
 /// can call this.
 ///
 /// * **ExecuteMsg::DeactivatePool { lp_token }** Sets the allocation point to zero for specified
-/// LP token.
+/// LP token. Only the factory or the factory's emergency admin can execute this.
 ///
 /// * **ExecuteMsg::DeactivatePools { pair_types }** Sets the allocation point to zero for each pool
 /// by the pair type
+///
+/// * **ExecuteMsg::RemovePool { lp_token }** Removes an inactive generator without stakers.
+///
+/// * **ExecuteMsg::UpdatePoolRewards { lp_token }** Updates the accrued rewards of a specific generator
+/// and tips the sender if it was stale.
+///
+/// * **ExecuteMsg::MassUpdatePools {}** Updates the accrued rewards of all active generators
+/// and tips the sender for the stale ones.
+///
+/// * **ExecuteMsg::SetKeeperIncentive { keeper_incentive }** Sets the keeper incentive settings.
+///
+/// * **ExecuteMsg::SweepNativeTokens { recipient }** Sends all native coins held by the Generator to the recipient.
+///
+/// * **ExecuteMsg::ApproveOperator { operator, lp_token }** Allows an operator to claim the sender's rewards
+/// from a specific generator.
+///
+/// * **ExecuteMsg::RevokeOperator { operator, lp_token }** Revokes an operator's approval to claim the sender's
+/// rewards from a specific generator.
+///
+/// * **ExecuteMsg::ClaimRewardsFor { user, lp_token }** Claims a user's rewards from a specific generator on
+/// behalf of the user. Only an approved operator can call this.
+///
+/// * **ExecuteMsg::SetAltReward {
+///             lp_token,
+///             reward_token,
+///             tokens_per_block,
+///         }** Sets a generator's alternative base reward token and emission rate.
+///
+/// * **ExecuteMsg::ScheduleSet {
+///             lp_token,
+///             alloc_point,
+///             effective_block,
+///         }** Schedules a generator's allocation points to change at a future block.
+///
+/// * **ExecuteMsg::CancelScheduledSet { lp_token, effective_block }** Cancels a scheduled allocation point change.
+///
+/// * **ExecuteMsg::AddEmissionEpoch {
+///             start_block,
+///             tokens_per_block,
+///         }** Appends an epoch with a new ASTRO emission rate to the emission schedule.
+///
+/// * **ExecuteMsg::CancelEmissionEpoch { start_block }** Removes an epoch that hasn't started yet from the
+/// emission schedule.
+///
+/// * **ExecuteMsg::SetBoostProvider {
+///             boost_provider,
+///             max_multiplier,
+///         }** Sets or removes the boost provider and the maximum boost multiplier.
+///
+/// * **ExecuteMsg::SetVotingEscrow { voting_escrow }** Sets or removes the voting escrow contract used for
+/// vxASTRO boosts.
+///
+/// * **ExecuteMsg::CheckpointUserBoost { lp_token, user }** Recomputes a staker's boost: the larger of the
+/// boost provider multiplier and the vxASTRO boost from the staker's current voting power. Anyone can execute this.
+///
+/// * **ExecuteMsg::SendOrphanStake {
+///             recipient,
+///             lp_token,
+///         }** Sends a generator's orphan stake to another address.
+///
+/// * **ExecuteMsg::MigrateUsers { limit }** Migrates the next batch of stakes stored before V1.3.0.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     mut deps: DepsMut,
@@ -161,12 +262,24 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // None of the handlers accept native coins, so they would be stuck in the contract
+    if !info.funds.is_empty() {
+        return Err(ContractError::NonPayable {});
+    }
+
     match msg {
         ExecuteMsg::DeactivatePools { pair_types } => deactivate_pools(deps, env, pair_types),
         ExecuteMsg::DeactivatePool { lp_token } => {
+            apply_scheduled_changes(deps.branch(), &env)?;
             let cfg = CONFIG.load(deps.storage)?;
             if info.sender != cfg.factory {
-                return Err(ContractError::Unauthorized {});
+                // The factory's emergency admin can also zero an allocation
+                let factory_cfg: FactoryConfigResponse = deps
+                    .querier
+                    .query_wasm_smart(cfg.factory.clone(), &FactoryQueryMsg::Config {})?;
+                if Some(&info.sender) != factory_cfg.emergency_admin.as_ref() {
+                    return Err(ContractError::Unauthorized {});
+                }
             }
             let lp_token_addr = addr_validate_to_lower(deps.api, &lp_token)?;
             let active_pools: Vec<Addr> =
@@ -177,9 +290,16 @@ pub fn execute(
         ExecuteMsg::UpdateTokensBlockedlist { add, remove } => {
             update_tokens_blockedlist(deps, env, info, add, remove)
         }
+        ExecuteMsg::UpdateExternalLpTokens { add, remove } => {
+            update_external_lp_tokens(deps, info, add, remove)
+        }
+        ExecuteMsg::RemovePool { lp_token } => remove_pool(deps, env, info, lp_token),
         ExecuteMsg::MoveToProxy { lp_token, proxy } => {
             move_to_proxy(deps, env, info, lp_token, proxy)
         }
+        ExecuteMsg::AddRewardProxy { lp_token, proxy } => {
+            add_reward_proxy(deps, env, info, lp_token, proxy)
+        }
         ExecuteMsg::UpdateAllowedProxies { add, remove } => {
             update_allowed_proxies(deps, info, add, remove)
         }
@@ -192,7 +312,7 @@ pub fn execute(
         ExecuteMsg::UpdatePool {
             lp_token,
             has_asset_rewards,
-        } => execute_update_pool(deps, info, lp_token, has_asset_rewards),
+        } => execute_update_pool(deps, env, info, lp_token, has_asset_rewards),
         ExecuteMsg::ClaimRewards { lp_tokens } => {
             let mut lp_tokens_addr: Vec<Addr> = vec![];
             for lp_token in &lp_tokens {
@@ -202,7 +322,7 @@ pub fn execute(
             update_rewards_and_execute(
                 deps,
                 env,
-                None,
+                Some(lp_tokens_addr.clone()),
                 ExecuteOnReply::ClaimRewards {
                     lp_tokens: lp_tokens_addr,
                     account: info.sender,
@@ -210,12 +330,15 @@ pub fn execute(
             )
         }
         ExecuteMsg::Withdraw { lp_token, amount } => {
+            if amount.is_zero() {
+                return Err(ContractError::ZeroAmount {});
+            }
             let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
 
             update_rewards_and_execute(
                 deps,
                 env,
-                Some(lp_token.clone()),
+                Some(vec![lp_token.clone()]),
                 ExecuteOnReply::Withdraw {
                     lp_token,
                     account: info.sender,
@@ -230,7 +353,11 @@ pub fn execute(
         ExecuteMsg::SendOrphanProxyReward {
             recipient,
             lp_token,
-        } => send_orphan_proxy_rewards(deps, info, recipient, lp_token),
+        } => send_orphan_proxy_rewards(deps, env, info, recipient, lp_token),
+        ExecuteMsg::SendOrphanStake {
+            recipient,
+            lp_token,
+        } => send_orphan_stake(deps, info, recipient, lp_token),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::SetTokensPerBlock { amount } => {
             let cfg = CONFIG.load(deps.storage)?;
@@ -245,6 +372,116 @@ pub fn execute(
                 ExecuteOnReply::SetTokensPerBlock { amount },
             )
         }
+        ExecuteMsg::UpdatePoolRewards { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+            update_rewards_and_execute(
+                deps,
+                env,
+                Some(vec![lp_token.clone()]),
+                ExecuteOnReply::UpdatePools {
+                    lp_tokens: vec![lp_token],
+                    keeper: info.sender,
+                },
+            )
+        }
+        ExecuteMsg::MassUpdatePools {} => {
+            let cfg = CONFIG.load(deps.storage)?;
+            let lp_tokens = cfg.active_pools.into_iter().map(|pool| pool.0).collect();
+
+            update_rewards_and_execute(
+                deps,
+                env,
+                None,
+                ExecuteOnReply::UpdatePools {
+                    lp_tokens,
+                    keeper: info.sender,
+                },
+            )
+        }
+        ExecuteMsg::SetKeeperIncentive { keeper_incentive } => {
+            set_keeper_incentive(deps, info, keeper_incentive)
+        }
+        ExecuteMsg::SweepNativeTokens { recipient } => {
+            sweep_native_tokens(deps, env, info, recipient)
+        }
+        ExecuteMsg::ApproveOperator { operator, lp_token } => {
+            approve_operator(deps, info, operator, lp_token)
+        }
+        ExecuteMsg::RevokeOperator { operator, lp_token } => {
+            revoke_operator(deps, info, operator, lp_token)
+        }
+        ExecuteMsg::ClaimRewardsFor { user, lp_token } => {
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+            if !OPERATORS.has(deps.storage, (&user, &lp_token, &info.sender)) {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            update_rewards_and_execute(
+                deps,
+                env,
+                Some(vec![lp_token.clone()]),
+                ExecuteOnReply::ClaimRewards {
+                    lp_tokens: vec![lp_token],
+                    account: user,
+                },
+            )
+        }
+        ExecuteMsg::SetAltReward {
+            lp_token,
+            reward_token,
+            tokens_per_block,
+        } => set_alt_reward(deps, env, info, lp_token, reward_token, tokens_per_block),
+        ExecuteMsg::ScheduleSet {
+            lp_token,
+            alloc_point,
+            effective_block,
+        } => schedule_set(deps, env, info, lp_token, alloc_point, effective_block),
+        ExecuteMsg::CancelScheduledSet {
+            lp_token,
+            effective_block,
+        } => cancel_scheduled_set(deps, env, info, lp_token, effective_block),
+        ExecuteMsg::AddEmissionEpoch {
+            start_block,
+            tokens_per_block,
+        } => add_emission_epoch(deps, env, info, start_block, tokens_per_block),
+        ExecuteMsg::CancelEmissionEpoch { start_block } => {
+            cancel_emission_epoch(deps, env, info, start_block)
+        }
+        ExecuteMsg::SetBoostProvider {
+            boost_provider,
+            max_multiplier,
+        } => set_boost_provider(deps, info, boost_provider, max_multiplier),
+        ExecuteMsg::SetVotingEscrow { voting_escrow } => {
+            set_voting_escrow(deps, info, voting_escrow)
+        }
+        ExecuteMsg::CheckpointUserBoost { lp_token, user } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            let user = addr_validate_to_lower(deps.api, &user)?;
+
+            if user_info()
+                .may_load(deps.storage, (&lp_token, &user))?
+                .is_none()
+            {
+                return Err(ContractError::NoStakedLpTokens {
+                    user: user.to_string(),
+                    lp_token: lp_token.to_string(),
+                });
+            }
+
+            update_rewards_and_execute(
+                deps,
+                env,
+                Some(vec![lp_token.clone()]),
+                ExecuteOnReply::CheckpointBoost {
+                    lp_token,
+                    account: user,
+                },
+            )
+        }
+        ExecuteMsg::MigrateUsers { limit } => migrate_users(deps, env, info, limit),
         ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
             let config: Config = CONFIG.load(deps.storage)?;
 
@@ -286,18 +523,15 @@ fn deactivate_pools(
     env: Env,
     pair_types: Vec<PairType>,
 ) -> Result<Response, ContractError> {
+    apply_scheduled_changes(deps.branch(), &env)?;
     let mut cfg = CONFIG.load(deps.storage)?;
 
     // Check for duplicate pair types
     let mut uniq: HashSet<String> = HashSet::new();
-    if !pair_types
-        .clone()
-        .into_iter()
-        .all(|a| uniq.insert(a.to_string()))
-    {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Duplicate of pair type!",
-        )));
+    if let Some(pair_type) = pair_types.iter().find(|a| !uniq.insert(a.to_string())) {
+        return Err(ContractError::PairTypeDuplicate {
+            pair_type: pair_type.to_string(),
+        });
     }
 
     let blacklisted_pair_types: Vec<PairType> = deps.querier.query_wasm_smart(
@@ -308,10 +542,9 @@ fn deactivate_pools(
     // checks if each pair type is blacklisted
     for pair_type in pair_types.clone() {
         if !blacklisted_pair_types.contains(&pair_type) {
-            return Err(ContractError::Std(StdError::generic_err(format!(
-                "Pair type ({}) is not blacklisted!",
-                pair_type
-            ))));
+            return Err(ContractError::PairTypeNotBlacklisted {
+                pair_type: pair_type.to_string(),
+            });
         }
     }
 
@@ -321,8 +554,8 @@ fn deactivate_pools(
     // find active pools with blacklisted pair type
     for pool in &mut cfg.active_pools {
         if !pool.1.is_zero() {
-            let pair_info = pair_info_by_pool(deps.as_ref(), pool.0.clone())?;
-            if pair_types.contains(&pair_info.pair_type) {
+            let pair_info = query_lp_pair(deps.as_ref(), &cfg.external_lp_tokens, &pool.0)?;
+            if pair_info.is_some_and(|pair_info| pair_types.contains(&pair_info.pair_type)) {
                 // recalculate total allocation point before resetting the allocation point of pool
                 cfg.total_alloc_point = cfg.total_alloc_point.checked_sub(pool.1)?;
                 // sets allocation point to zero for each pool with blacklisted pair type
@@ -335,6 +568,120 @@ fn deactivate_pools(
     Ok(Response::new().add_attribute("action", "deactivate_pools"))
 }
 
+/// ## Description
+/// Removes a generator that has no allocation points and no stakers, so it no longer takes space in the
+/// generator list. The generator's remaining alternative reward budget is sent to the owner. Historical state
+/// stays available. Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator is removed.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn remove_pool(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    apply_scheduled_changes(deps.branch(), &env)?;
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
+
+    // A pending change would activate the generator after it was removed
+    let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+    if !get_alloc_point(&cfg.active_pools, &lp_token).is_zero()
+        || scheduled_sets
+            .iter()
+            .any(|scheduled_set| scheduled_set.lp_token == lp_token)
+    {
+        return Err(ContractError::PoolIsActive {
+            lp_token: lp_token.to_string(),
+        });
+    }
+
+    // Stakes are removed once they are fully withdrawn
+    if user_info()
+        .prefix(&lp_token)
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(ContractError::PoolHasStakers {
+            lp_token: lp_token.to_string(),
+        });
+    }
+
+    if pool
+        .reward_proxies
+        .iter()
+        .any(|reward_proxy| !reward_proxy.orphan_rewards.is_zero())
+    {
+        return Err(ContractError::PoolHasOrphanRewards {
+            lp_token: lp_token.to_string(),
+        });
+    }
+
+    if ORPHAN_STAKES.has(deps.storage, &lp_token) {
+        return Err(ContractError::PoolHasOrphanStake {
+            lp_token: lp_token.to_string(),
+        });
+    }
+
+    // Deactivated generators keep an entry with zero allocation points
+    cfg.active_pools.retain(|(addr, _)| addr != &lp_token);
+    cfg.total_alloc_point = cfg
+        .active_pools
+        .iter()
+        .map(|(_, alloc_point)| alloc_point)
+        .sum();
+    CONFIG.save(deps.storage, &cfg)?;
+
+    POOL_INFO.remove(deps.storage, &lp_token, env.block.height)?;
+    POOL_LENGTH.update(deps.storage, |length| -> StdResult<_> {
+        Ok(length.saturating_sub(1))
+    })?;
+
+    let mut messages = vec![];
+    if let Some(alt_reward) = &pool.alt_reward {
+        let count = ALT_REWARD_TOKENS.load(deps.storage, &alt_reward.reward_token)?;
+        if count > 1 {
+            ALT_REWARD_TOKENS.save(deps.storage, &alt_reward.reward_token, &(count - 1))?;
+        } else {
+            ALT_REWARD_TOKENS.remove(deps.storage, &alt_reward.reward_token);
+        }
+
+        if !alt_reward.budget.is_zero() {
+            messages.push(WasmMsg::Execute {
+                contract_addr: alt_reward.reward_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: cfg.owner.to_string(),
+                    amount: alt_reward.budget,
+                })?,
+                funds: vec![],
+            });
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "remove_pool"),
+        attr("lp_token", lp_token),
+    ]))
+}
+
 /// Add or remove tokens to and from the blocked list. Returns a [`ContractError`] on failure.
 fn update_tokens_blockedlist(
     mut deps: DepsMut,
@@ -344,11 +691,10 @@ fn update_tokens_blockedlist(
     remove: Option<Vec<AssetInfo>>,
 ) -> Result<Response, ContractError> {
     if add.is_none() && remove.is_none() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Need to provide add or remove parameters",
-        )));
+        return Err(ContractError::EmptyListUpdate {});
     }
 
+    apply_scheduled_changes(deps.branch(), &env)?;
     let mut cfg = CONFIG.load(deps.storage)?;
 
     // Permission check
@@ -363,10 +709,8 @@ fn update_tokens_blockedlist(
                 .blocked_list_tokens
                 .iter()
                 .position(|x| *x == asset_info)
-                .ok_or_else(|| {
-                    StdError::generic_err(
-                        "Can't remove token. It is not found in the blocked list.",
-                    )
+                .ok_or_else(|| ContractError::TokenNotBlocked {
+                    token: asset_info.to_string(),
                 })?;
             cfg.blocked_list_tokens.remove(index);
         }
@@ -389,8 +733,10 @@ fn update_tokens_blockedlist(
 
                 // Find active pools with blacklisted tokens
                 for pool in &mut cfg.active_pools {
-                    let pair_info = pair_info_by_pool(deps.as_ref(), pool.0.clone())?;
-                    if pair_info.asset_infos.contains(&asset_info) {
+                    let pair_info = query_lp_pair(deps.as_ref(), &cfg.external_lp_tokens, &pool.0)?;
+                    if pair_info
+                        .is_some_and(|pair_info| pair_info.asset_infos.contains(&asset_info))
+                    {
                         // Recalculate total allocation points before resetting the pool allocation points
                         cfg.total_alloc_point = cfg.total_alloc_point.checked_sub(pool.1)?;
                         // Sets allocation points to zero for each pool with blacklisted tokens
@@ -477,14 +823,27 @@ pub fn execute_setup_pools(
     info: MessageInfo,
     pools: Vec<(String, Uint128)>,
 ) -> Result<Response, ContractError> {
+    // Due changes must be applied first, otherwise they would override the new allocations
+    apply_scheduled_changes(deps.branch(), &env)?;
     let mut cfg = CONFIG.load(deps.storage)?;
     if info.sender != cfg.owner && Some(info.sender) != cfg.generator_controller {
         return Err(ContractError::Unauthorized {});
     }
 
-    let pools_set: HashSet<String> = pools.clone().into_iter().map(|pc| pc.0).collect();
-    if pools_set.len() != pools.len() {
-        return Err(ContractError::PoolDuplicate {});
+    let mut pools_set: HashSet<&String> = HashSet::new();
+    if let Some((lp_token, _)) = pools
+        .iter()
+        .find(|(lp_token, _)| !pools_set.insert(lp_token))
+    {
+        return Err(ContractError::PoolDuplicate {
+            lp_token: lp_token.clone(),
+        });
+    }
+
+    if pools.len() > MAX_ACTIVE_POOLS {
+        return Err(ContractError::TooManyPools {
+            max: MAX_ACTIVE_POOLS,
+        });
     }
 
     let mut setup_pools: Vec<(Addr, Uint128)> = vec![];
@@ -495,42 +854,54 @@ pub fn execute_setup_pools(
     )?;
 
     for (addr, alloc_point) in pools {
+        if alloc_point > Uint128::from(MAX_ALLOC_POINT) {
+            return Err(ContractError::AllocPointTooHigh {
+                max: MAX_ALLOC_POINT.into(),
+            });
+        }
+
         let pool_addr = addr_validate_to_lower(deps.api, &addr)?;
-        let pair_info = pair_info_by_pool(deps.as_ref(), pool_addr.clone())?;
-
-        // check if assets in the blocked list
-        for asset in pair_info.asset_infos.clone() {
-            if cfg.blocked_list_tokens.contains(&asset) {
-                return Err(ContractError::Std(StdError::generic_err(format!(
-                    "Token {} is blocked!",
-                    asset
-                ))));
+
+        // External LP tokens are allowed by the owner and don't belong to a registered pair
+        if !cfg.external_lp_tokens.contains(&pool_addr) {
+            let pair_info = pair_info_by_pool(deps.as_ref(), pool_addr.clone())?;
+
+            // check if assets in the blocked list
+            for asset in pair_info.asset_infos.clone() {
+                if cfg.blocked_list_tokens.contains(&asset) {
+                    return Err(ContractError::TokenBlocked {
+                        token: asset.to_string(),
+                    });
+                }
             }
-        }
 
-        // check if pair type is blacklisted
-        if blacklisted_pair_types.contains(&pair_info.pair_type) {
-            return Err(ContractError::Std(StdError::generic_err(format!(
-                "Pair type ({}) is blacklisted!",
-                pair_info.pair_type
-            ))));
-        }
+            // check if pair type is blacklisted
+            if blacklisted_pair_types.contains(&pair_info.pair_type) {
+                return Err(ContractError::PairTypeBlacklisted {
+                    pair_type: pair_info.pair_type.to_string(),
+                });
+            }
 
-        // If a pair gets deregistered from the factory, we should raise error.
-        let _: PairInfo = deps
-            .querier
-            .query_wasm_smart(
-                cfg.factory.clone(),
-                &FactoryQueryMsg::Pair {
-                    asset_infos: pair_info.asset_infos.clone(),
-                },
-            )
-            .map_err(|_| {
-                ContractError::Std(StdError::generic_err(format!(
-                    "The pair aren't registered: {}-{}",
-                    pair_info.asset_infos[0], pair_info.asset_infos[1]
-                )))
-            })?;
+            // If a pair gets deregistered from the factory, we should raise error.
+            let registered_pair: PairInfo = deps
+                .querier
+                .query_wasm_smart(
+                    cfg.factory.clone(),
+                    &FactoryQueryMsg::Pair {
+                        asset_infos: pair_info.asset_infos.clone(),
+                    },
+                )
+                .map_err(|_| ContractError::PairNotRegistered {
+                    lp_token: pool_addr.to_string(),
+                })?;
+
+            // A token that only names a registered pair as its minter is not that pair's LP token
+            if registered_pair.liquidity_token != pool_addr {
+                return Err(ContractError::PairNotRegistered {
+                    lp_token: pool_addr.to_string(),
+                });
+            }
+        }
 
         setup_pools.push((pool_addr, alloc_point));
     }
@@ -572,6 +943,7 @@ pub fn execute_setup_pools(
 /// Can only be called by the owner.
 pub fn execute_update_pool(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     lp_token: String,
     has_asset_rewards: bool,
@@ -583,11 +955,11 @@ pub fn execute_update_pool(
         return Err(ContractError::Unauthorized {});
     }
 
-    let mut pool_info = POOL_INFO.load(deps.storage, &lp_token_addr)?;
+    let mut pool_info = load_pool(deps.storage, &lp_token_addr)?;
 
     pool_info.has_asset_rewards = has_asset_rewards;
 
-    POOL_INFO.save(deps.storage, &lp_token_addr, &pool_info)?;
+    POOL_INFO.save(deps.storage, &lp_token_addr, &pool_info, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_pool")
@@ -596,62 +968,56 @@ pub fn execute_update_pool(
 }
 
 /// ## Description
-/// Updates the amount of accrued rewards for a specific generator (if specified in input parameters), otherwise updates rewards for
-/// all pools that are in [`POOL_INFO`]. Returns a [`ContractError`] on failure, otherwise returns a [`Response`] object with
-/// the specified attributes.
+/// Updates the proxy rewards of specific generators (if specified in input parameters), otherwise updates proxy rewards for
+/// all active generators, then performs the pending user action. Returns a [`ContractError`] on failure, otherwise returns
+/// a [`Response`] object with the specified attributes.
 ///
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **update_single_pool** is an [`Option`] field object of type [`Addr`]. This indicates whether a single generator
-/// should be updated and if yes, which one.
+/// * **update_pools** is an [`Option`] field object of type [`Vec<Addr>`]. These are the LP tokens whose generators
+/// should be updated. All active generators are updated if not specified.
 ///
 /// * **on_reply** is an object of type [`ExecuteOnReply`]. This is the action to be performed on reply.
 fn update_rewards_and_execute(
-    mut deps: DepsMut,
+    deps: DepsMut,
     env: Env,
-    update_single_pool: Option<Addr>,
+    update_pools: Option<Vec<Addr>>,
     on_reply: ExecuteOnReply,
 ) -> Result<Response, ContractError> {
     TMP_USER_ACTION.update(deps.storage, |v| {
         if v.is_some() {
-            Err(StdError::generic_err("Repetitive reply definition!"))
+            Err(ContractError::RepeatedReply {})
         } else {
             Ok(Some(on_reply))
         }
     })?;
 
-    let mut pools: Vec<(Addr, PoolInfo)> = vec![];
-    match update_single_pool {
-        Some(lp_token) => {
-            let pool = POOL_INFO.load(deps.storage, &lp_token)?;
-            pools = vec![(lp_token, pool)];
-        }
+    let lp_tokens = match update_pools {
+        Some(lp_tokens) => lp_tokens,
         None => {
             let config = CONFIG.load(deps.storage)?;
-
-            for (lp_token, _) in config.active_pools {
-                pools.push((lp_token.clone(), POOL_INFO.load(deps.storage, &lp_token)?))
-            }
+            config.active_pools.into_iter().map(|pool| pool.0).collect()
         }
-    }
+    };
 
     let mut messages: Vec<SubMsg> = vec![];
-    for (lp_token, mut pool) in pools {
-        if let Some(reward_proxy) = pool.reward_proxy.clone() {
-            messages.append(&mut get_proxy_rewards(
-                deps.branch(),
-                &lp_token,
-                &mut pool,
-                &reward_proxy,
-            )?);
+    let mut updated_proxies: HashSet<Addr> = HashSet::new();
+    for lp_token in &lp_tokens {
+        let pool = load_pool(deps.storage, lp_token)?;
+        for reward_proxy in pool.reward_proxies {
+            // A generator listed several times is only updated once
+            if updated_proxies.insert(reward_proxy.proxy.clone()) {
+                messages.append(&mut get_proxy_rewards(deps.as_ref(), &reward_proxy.proxy)?);
+            }
         }
     }
 
     if let Some(last) = messages.last_mut() {
-        last.reply_on = ReplyOn::Success;
+        last.id = PROCESS_USER_ACTION_REPLY_ID;
+        last.reply_on = ReplyOn::Always;
         Ok(Response::new().add_submessages(messages))
     } else {
         process_after_update(deps, env)
@@ -659,39 +1025,27 @@ fn update_rewards_and_execute(
 }
 
 /// ## Description
-/// Fetches accrued proxy rewards. Snapshots the old amount of rewards that are still unclaimed. Returns a [`ContractError`]
+/// Fetches accrued proxy rewards. The proxy reward balance before the update is tracked in
+/// [`PoolInfo`], so only the pending rewards are queried. Returns a [`ContractError`]
 /// on failure, otherwise returns a vector that contains objects of type [`SubMsg`].
 ///
 /// ## Params
-/// * **deps** is an object of type [`DepsMut`].
-///
-/// * **lp_token** is an object of type [`Addr`]. This is the LP token for which we fetch the latest amount of accrued proxy rewards.
-///
-/// * **pool** is an object of type [`PoolInfo`]. This is the generator associated with the `lp_token`.
+/// * **deps** is an object of type [`Deps`].
 ///
 /// * **reward_proxy** is an object of type [`Addr`]. This is the address of the dual rewards proxy for the target LP/generator.
-fn get_proxy_rewards(
-    deps: DepsMut,
-    lp_token: &Addr,
-    pool: &mut PoolInfo,
-    reward_proxy: &Addr,
-) -> Result<Vec<SubMsg>, ContractError> {
-    let reward_amount: Uint128 = deps
-        .querier
-        .query_wasm_smart(reward_proxy, &ProxyQueryMsg::Reward {})?;
-
-    pool.proxy_reward_balance_before_update = reward_amount;
-    POOL_INFO.save(deps.storage, lp_token, pool)?;
-
+fn get_proxy_rewards(deps: Deps, reward_proxy: &Addr) -> Result<Vec<SubMsg>, ContractError> {
     let msg = ProxyQueryMsg::PendingToken {};
     let res: Uint128 = deps.querier.query_wasm_smart(reward_proxy, &msg)?;
 
     Ok(if !res.is_zero() {
-        vec![SubMsg::new(WasmMsg::Execute {
-            contract_addr: reward_proxy.to_string(),
-            funds: vec![],
-            msg: to_binary(&ProxyExecuteMsg::UpdateRewards {})?,
-        })]
+        vec![SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: reward_proxy.to_string(),
+                funds: vec![],
+                msg: to_binary(&ProxyExecuteMsg::UpdateRewards {})?,
+            },
+            PROXY_REWARDS_UPDATE_REPLY_ID,
+        )]
     } else {
         vec![]
     })
@@ -699,15 +1053,94 @@ fn get_proxy_rewards(
 
 /// ## Description
 /// The entry point to the contract for processing replies from submessages.
+/// Replies from proxy `UpdateRewards` calls carry no data. The last call resumes the pending user
+/// action even if it failed. ASTRO claimed from vesting goes straight to the recipient, so the
+/// Generator never receives it; the amount actually claimed is read from the vesting contract's own event.
 /// # Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`Reply`].
+/// * **msg** is an object of type [`Reply`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, _msg: Reply) -> Result<Response, ContractError> {
-    process_after_update(deps, env)
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // A failing proxy must not freeze the LP tokens staked through it. Its reward balance
+    // doesn't change, so the generator simply accrues no new proxy rewards for this update
+    match msg.id {
+        VESTING_CLAIM_REPLY_ID => process_vesting_claim(deps, msg),
+        PROXY_REWARDS_UPDATE_REPLY_ID => {
+            Ok(Response::new().add_attribute("action", "proxy_rewards_update_failed"))
+        }
+        PROCESS_USER_ACTION_REPLY_ID => {
+            let update_failed = msg.result.is_err();
+            let response = process_after_update(deps, env)?;
+            Ok(if update_failed {
+                response.add_attribute("action", "proxy_rewards_update_failed")
+            } else {
+                response
+            })
+        }
+        _ => Err(ContractError::Std(StdError::generic_err(
+            "unknown reply id",
+        ))),
+    }
+}
+
+/// ## Description
+/// Records the result of the ASTRO claim stored in [`TMP_VESTING_CLAIM`]. The claimed amount is the
+/// `claimed_amount` attribute of the event emitted by the vesting contract itself; if the vesting contract
+/// doesn't report it, the full amount is considered claimed. ASTRO that wasn't paid, because the claim
+/// failed or the vesting contract sent less than requested, is added to the recipient's next claim,
+/// so a failed claim doesn't revert the user action. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the claimed and unpaid amounts.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **msg** is an object of type [`Reply`]. This is the reply of the vesting `Claim` call.
+fn process_vesting_claim(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let claim = TMP_VESTING_CLAIM.load(deps.storage)?;
+    TMP_VESTING_CLAIM.remove(deps.storage);
+
+    let (action, claimed) = match msg.result.into_result() {
+        Ok(res) => {
+            // Only the vesting contract's own event reports the claim. Contracts called by the
+            // vesting contract (e.g. the ASTRO token) may emit attributes with the same keys.
+            // The name of the contract address attribute differs between chains
+            let cfg = CONFIG.load(deps.storage)?;
+            let claimed = res
+                .events
+                .iter()
+                .filter(|event| {
+                    event.ty == "wasm"
+                        && event.attributes.iter().any(|attr| {
+                            matches!(
+                                attr.key.as_str(),
+                                "_contract_addr" | "_contract_address" | "contract_address"
+                            ) && cfg.vesting_contract == attr.value
+                        })
+                })
+                .flat_map(|event| event.attributes.iter())
+                .find(|attr| attr.key == "claimed_amount")
+                .map(|attr| Uint128::from_str(&attr.value))
+                .transpose()?
+                .unwrap_or(claim.amount);
+            ("vesting_claim", claimed)
+        }
+        Err(_) => ("vesting_claim_failed", Uint128::zero()),
+    };
+
+    let unpaid = claim.amount.saturating_sub(claimed);
+    if !unpaid.is_zero() {
+        UNPAID_ASTRO.update(deps.storage, &claim.recipient, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(unpaid)?)
+        })?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("recipient", claim.recipient)
+        .add_attribute("claimed_astro", claimed)
+        .add_attribute("unpaid_astro", unpaid))
 }
 
 /// ## Description
@@ -717,31 +1150,45 @@ pub fn reply(deps: DepsMut, env: Env, _msg: Reply) -> Result<Response, ContractE
 /// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **env** is an object of type [`Env`].
-fn process_after_update(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    match TMP_USER_ACTION.load(deps.storage)? {
+fn process_after_update(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let response = match TMP_USER_ACTION.load(deps.storage)? {
         Some(action) => {
             TMP_USER_ACTION.save(deps.storage, &None)?;
             match action {
                 ExecuteOnReply::ClaimRewards { lp_tokens, account } => {
-                    claim_rewards(deps, env, lp_tokens, account)
+                    claim_rewards(deps.branch(), env.clone(), lp_tokens, account)
                 }
                 ExecuteOnReply::Deposit {
                     lp_token,
                     account,
                     amount,
-                } => deposit(deps, env, lp_token, account, amount),
+                } => deposit(deps.branch(), env.clone(), lp_token, account, amount),
                 ExecuteOnReply::Withdraw {
                     lp_token,
                     account,
                     amount,
-                } => withdraw(deps, env, lp_token, account, amount),
+                } => withdraw(deps.branch(), env.clone(), lp_token, account, amount),
+                ExecuteOnReply::CheckpointBoost { lp_token, account } => {
+                    checkpoint_user_boost(deps.branch(), env.clone(), lp_token, account)
+                }
                 ExecuteOnReply::SetTokensPerBlock { amount } => {
-                    set_tokens_per_block(deps, env, amount)
+                    set_tokens_per_block(deps.branch(), env.clone(), amount)
                 }
-            }
+                ExecuteOnReply::UpdatePools { lp_tokens, keeper } => {
+                    update_pools(deps.branch(), env.clone(), lp_tokens, keeper)
+                }
+                ExecuteOnReply::MoveToProxy { lp_token, proxy } => {
+                    migrate_proxy(deps.branch(), env.clone(), lp_token, proxy)
+                }
+            }?
         }
-        None => Ok(Response::default()),
-    }
+        None => Response::default(),
+    };
+
+    // The action accounts its generators first, as a deposit is already in the LP token balance
+    apply_scheduled_changes(deps, &env)?;
+
+    Ok(response)
 }
 
 /// ## Description
@@ -772,12 +1219,16 @@ pub fn deactivate_pool(deps: DepsMut, lp_token: Addr) -> Result<Response, Contra
 /// # Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
+/// * **env** is an object of type [`Env`].
+///
 /// * **amount** is the object of type [`Uint128`]. Sets a new count of tokens per block.
 fn set_tokens_per_block(
     mut deps: DepsMut,
     env: Env,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    // Generators activated by a due change aren't active yet, but accrued rewards at the previous rate as well
+    apply_scheduled_changes(deps.branch(), &env)?;
     let mut cfg = CONFIG.load(deps.storage)?;
 
     let pools: Vec<Addr> = cfg.active_pools.iter().map(|pool| pool.0.clone()).collect();
@@ -808,58 +1259,1006 @@ pub fn mass_update_pools(
     lp_tokens: &[Addr],
 ) -> Result<(), ContractError> {
     for lp_token in lp_tokens {
-        let mut pool = POOL_INFO.load(deps.storage, lp_token)?;
+        let mut pool = load_pool(deps.storage, lp_token)?;
         accumulate_rewards_per_share(deps.branch(), env, lp_token, &mut pool, cfg, None)?;
-        POOL_INFO.save(deps.storage, lp_token, &pool)?;
+        POOL_INFO.save(deps.storage, lp_token, &pool, env.block.height)?;
     }
 
     Ok(())
 }
 
 /// ## Description
-/// Updates the amount of accrued rewards for a specific generator. Returns a [`ContractError`] on
-/// failure, otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// Updates the amount of accrued rewards for the specified generators and tips the keeper for every
+/// stale generator with a non-zero allocation. The tip is capped by [`KeeperIncentive`]'s `max_tip` and
+/// by the [`KEEPER_TIP_BUDGET`] it is paid from. Returns a [`ContractError`] on failure, otherwise returns a
+/// [`Response`] with the specified attributes if the operation was successful.
 /// # Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **lp_token** is the object of type [`Addr`]. Sets the liquidity pool to be updated.
+/// * **lp_tokens** is a vector of type [`Addr`]. These are the LP tokens whose generators we update.
 ///
-/// * **account** is the object of type [`Addr`].
-pub fn claim_rewards(
+/// * **keeper** is an object of type [`Addr`]. This is the address that triggered the update.
+fn update_pools(
     mut deps: DepsMut,
     env: Env,
     lp_tokens: Vec<Addr>,
-    account: Addr,
+    keeper: Addr,
 ) -> Result<Response, ContractError> {
-    let response = Response::default();
-
     let cfg = CONFIG.load(deps.storage)?;
 
+    // Staleness must be checked before the update resets the last reward block
+    let mut stale_pools = 0u128;
+    if let Some(keeper_incentive) = &cfg.keeper_incentive {
+        for lp_token in &lp_tokens {
+            let pool = load_pool(deps.storage, lp_token)?;
+            if !get_alloc_point(&cfg.active_pools, lp_token).is_zero()
+                && env.block.height
+                    >= pool
+                        .last_reward_block
+                        .u64()
+                        .saturating_add(keeper_incentive.staleness_threshold)
+            {
+                stale_pools += 1;
+            }
+        }
+    }
+
     mass_update_pools(deps.branch(), &env, &cfg, &lp_tokens)?;
 
-    let mut send_rewards_msg: Vec<WasmMsg> = vec![];
-    for lp_token in &lp_tokens {
-        let pool = POOL_INFO.load(deps.storage, lp_token)?;
+    // Tips come out of a separate budget, so they never take ASTRO emitted to stakers
+    let tip_budget = KEEPER_TIP_BUDGET
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let tip = cfg
+        .keeper_incentive
+        .map(|keeper_incentive| {
+            keeper_incentive
+                .tip_per_pool
+                .checked_mul(Uint128::from(stale_pools))
+                .map(|tip| tip.min(keeper_incentive.max_tip).min(tip_budget))
+        })
+        .transpose()?
+        .unwrap_or_default();
 
-        let user = USER_INFO.load(deps.storage, (lp_token, &account))?;
+    let mut response = Response::new().add_attributes(vec![
+        attr("action", "update_pools"),
+        attr("keeper", keeper.as_str()),
+        attr("keeper_tip", tip),
+    ]);
 
-        send_rewards_msg.append(&mut send_pending_rewards(&cfg, &pool, &user, &account)?);
+    if !tip.is_zero() {
+        KEEPER_TIP_BUDGET.save(deps.storage, &tip_budget.checked_sub(tip)?)?;
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: cfg.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: keeper.to_string(),
+                amount: tip,
+            })?,
+            funds: vec![],
+        });
     }
 
-    Ok(response
-        .add_attribute("action", "claim_rewards")
-        .add_messages(send_rewards_msg))
+    Ok(response)
 }
 
 /// ## Description
-/// Accrues the amount of rewards distributed for each staked LP token in a specific generator.
-/// Also update reward variables for the given generator.
+/// Sets the ASTRO tip paid to whoever updates stale generators. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
 /// # Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **env** is an object of type [`Env`].
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **keeper_incentive** is an [`Option`] field object of type [`KeeperIncentive`]. `None` disables keeper tips.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn set_keeper_incentive(
+    deps: DepsMut,
+    info: MessageInfo,
+    keeper_incentive: Option<KeeperIncentive>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(keeper_incentive) = &keeper_incentive {
+        if keeper_incentive.staleness_threshold == 0 {
+            return Err(ContractError::InvalidStalenessThreshold {});
+        }
+    }
+
+    cfg.keeper_incentive = keeper_incentive;
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_attribute("action", "set_keeper_incentive"))
+}
+
+/// ## Description
+/// Sets or removes the boost provider and the maximum multiplier it can apply. Existing boosts keep
+/// their last checkpointed value until the staker deposits, withdraws or is checkpointed.
+/// Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **boost_provider** is an object of type [`UpdateAddr`]. This sets or removes the boost provider contract.
+///
+/// * **max_multiplier** is an object of type [`Decimal`]. This is the maximum multiplier applied to a staker's LP amount.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn set_boost_provider(
+    deps: DepsMut,
+    info: MessageInfo,
+    boost_provider: UpdateAddr,
+    max_multiplier: Decimal,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    cfg.boost_provider = match boost_provider {
+        UpdateAddr::Set(contract) => {
+            let max = Decimal::percent(MAX_BOOST_MULTIPLIER_PERCENT);
+            if max_multiplier < Decimal::one() || max_multiplier > max {
+                return Err(ContractError::InvalidBoostMultiplier { max });
+            }
+
+            Some(BoostProvider {
+                contract: addr_validate_to_lower(deps.api, &contract)?,
+                max_multiplier,
+            })
+        }
+        UpdateAddr::Remove {} => None,
+    };
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_attribute("action", "set_boost_provider"))
+}
+
+/// ## Description
+/// Sets or removes the voting escrow contract whose vxASTRO voting power boosts ASTRO rewards. Existing
+/// boosts keep their last checkpointed value until the staker deposits, withdraws or is checkpointed.
+/// Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **voting_escrow** is an object of type [`UpdateAddr`]. This sets or removes the voting escrow contract.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn set_voting_escrow(
+    deps: DepsMut,
+    info: MessageInfo,
+    voting_escrow: UpdateAddr,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    cfg.voting_escrow = match voting_escrow {
+        UpdateAddr::Set(contract) => Some(addr_validate_to_lower(deps.api, &contract)?),
+        UpdateAddr::Remove {} => None,
+    };
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_attribute("action", "set_voting_escrow"))
+}
+
+/// ## Description
+/// Returns the extra stake credited to a staker's LP amount. This is the larger of the bonuses granted by
+/// the boost provider and by the staker's vxASTRO voting power. Staking must never fail because of
+/// either contract, so a failed query is treated as no boost.
+/// # Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **cfg** is an object of type [`Config`]. This is the contract config.
+///
+/// * **user** is an object of type [`Addr`]. This is the staker whose boost we query.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens staked by the user.
+///
+/// * **lp_supply** is an object of type [`Uint128`]. This is the total amount of LP tokens staked in
+/// the generator, including the user's amount.
+fn query_boost_bonus(
+    querier: &QuerierWrapper,
+    cfg: &Config,
+    user: &Addr,
+    amount: Uint128,
+    lp_supply: Uint128,
+) -> Uint128 {
+    let provider_bonus = cfg
+        .boost_provider
+        .as_ref()
+        .and_then(|boost_provider| {
+            querier
+                .query_wasm_smart::<BoostResponse>(
+                    &boost_provider.contract,
+                    &BoostQueryMsg::Boost {
+                        user: user.to_string(),
+                    },
+                )
+                .ok()
+                .map(|res| res.multiplier.min(boost_provider.max_multiplier))
+        })
+        .filter(|multiplier| *multiplier > Decimal::one())
+        .and_then(|multiplier| (multiplier - Decimal::one()).checked_mul_floor(amount).ok())
+        .unwrap_or_default();
+
+    let ve_bonus = cfg
+        .voting_escrow
+        .as_ref()
+        .map(|voting_escrow| query_ve_boost_bonus(querier, voting_escrow, user, amount, lp_supply))
+        .unwrap_or_default();
+
+    provider_bonus.max(ve_bonus)
+}
+
+/// ## Description
+/// Returns the extra stake credited to a staker's LP amount by their vxASTRO voting power. Without
+/// vxASTRO, only [`VE_BOOST_BASE_PERCENT`] of the amount counts. The remaining share is unlocked in
+/// proportion to the staker's share of the total voting power:
+/// `min(base * amount + (1 - base) * lp_supply * voting_power / total_voting_power, amount)`.
+/// The result is scaled so that the unboosted amount counts in full, which caps the multiplier at 2.5.
+/// # Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **voting_escrow** is an object of type [`Addr`]. This is the voting escrow contract.
+///
+/// * **user** is an object of type [`Addr`]. This is the staker whose boost we query.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens staked by the user.
+///
+/// * **lp_supply** is an object of type [`Uint128`]. This is the total amount of LP tokens staked in
+/// the generator, including the user's amount.
+fn query_ve_boost_bonus(
+    querier: &QuerierWrapper,
+    voting_escrow: &Addr,
+    user: &Addr,
+    amount: Uint128,
+    lp_supply: Uint128,
+) -> Uint128 {
+    let query_voting_power = |msg: &VotingEscrowQueryMsg| {
+        querier
+            .query_wasm_smart::<VotingPowerResponse>(voting_escrow, msg)
+            .map(|res| res.voting_power)
+    };
+
+    let total_voting_power = match query_voting_power(&VotingEscrowQueryMsg::TotalVotingPower {}) {
+        Ok(total_voting_power) if !total_voting_power.is_zero() => total_voting_power,
+        _ => return Uint128::zero(),
+    };
+    let voting_power = query_voting_power(&VotingEscrowQueryMsg::UserVotingPower {
+        user: user.to_string(),
+    })
+    .unwrap_or_default()
+    .min(total_voting_power);
+
+    // The boosted share of the amount is credited on top of the amount itself
+    amount
+        .min(lp_supply.multiply_ratio(voting_power, total_voting_power))
+        .multiply_ratio(100 - VE_BOOST_BASE_PERCENT, VE_BOOST_BASE_PERCENT)
+}
+
+/// ## Description
+/// Sends all native coins held by the Generator to the recipient. Native coins sent to the Generator before
+/// it started rejecting them are stuck, as no handler uses them. Terra taxes are deducted from the sent
+/// amounts. Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **recipient** is an [`Option`] field object of type [`String`]. This is the recipient of the coins.
+/// Defaults to the owner.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn sweep_native_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = recipient
+        .map(|recipient| addr_validate_to_lower(deps.api, &recipient))
+        .transpose()?
+        .unwrap_or(cfg.owner);
+
+    let mut messages = vec![];
+    for coin in deps.querier.query_all_balances(env.contract.address)? {
+        if coin.amount.is_zero() {
+            continue;
+        }
+
+        messages.push(
+            Asset {
+                info: native_asset_info(coin.denom),
+                amount: coin.amount,
+            }
+            .into_msg(&deps.querier, recipient.clone())?,
+        );
+    }
+
+    if messages.is_empty() {
+        return Err(ContractError::NothingToSweep {});
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "sweep_native_tokens"),
+        attr("recipient", recipient),
+    ]))
+}
+
+/// ## Description
+/// Sets a generator's alternative base reward, which is emitted from its own funded budget independently
+/// from ASTRO. Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator emits the reward.
+///
+/// * **reward_token** is an object of type [`String`]. This is the CW20 token emitted as the reward.
+///
+/// * **tokens_per_block** is an object of type [`Uint128`]. This is the amount of reward tokens distributed per block.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn set_alt_reward(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    reward_token: String,
+    tokens_per_block: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let reward_token = addr_validate_to_lower(deps.api, &reward_token)?;
+
+    // The generator's LP token balances are used as staked amounts, so they can't hold LP tokens as rewards
+    if POOL_INFO.may_load(deps.storage, &reward_token)?.is_some() {
+        return Err(ContractError::InvalidAltRewardToken {});
+    }
+
+    // Accrue the rewards emitted at the previous rate
+    mass_update_pools(deps.branch(), &env, &cfg, slice::from_ref(&lp_token))?;
+
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    match &mut pool.alt_reward {
+        Some(alt_reward) => {
+            // Accrued rewards are tracked per share, so they must stay in the same token
+            if alt_reward.reward_token != reward_token {
+                return Err(ContractError::AltRewardTokenChange {});
+            }
+            alt_reward.tokens_per_block = tokens_per_block;
+        }
+        None => {
+            pool.alt_reward = Some(AltRewardInfo {
+                reward_token: reward_token.clone(),
+                tokens_per_block,
+                budget: Uint128::zero(),
+            });
+            ALT_REWARD_TOKENS.update::<_, StdError>(deps.storage, &reward_token, |count| {
+                Ok(count.unwrap_or_default() + 1)
+            })?;
+        }
+    }
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_alt_reward"),
+        attr("lp_token", lp_token),
+        attr("reward_token", reward_token),
+        attr("tokens_per_block", tokens_per_block),
+    ]))
+}
+
+/// ## Description
+/// Adds tokens to the budget of a generator's alternative base reward. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **reward_token** is an object of type [`Addr`]. This is the token that was sent.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator emits the reward.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens that was sent.
+fn fund_alt_reward(
+    mut deps: DepsMut,
+    env: Env,
+    reward_token: Addr,
+    lp_token: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    // Accrue the rewards emitted before the budget is increased, as emissions stop when it runs out
+    mass_update_pools(deps.branch(), &env, &cfg, slice::from_ref(&lp_token))?;
+
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    match &mut pool.alt_reward {
+        Some(alt_reward) if alt_reward.reward_token == reward_token => {
+            alt_reward.budget = alt_reward.budget.checked_add(amount)?;
+        }
+        _ => {
+            return Err(ContractError::AltRewardNotSet {
+                lp_token: lp_token.to_string(),
+            })
+        }
+    }
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "fund_alt_reward"),
+        attr("lp_token", lp_token),
+        attr("reward_token", reward_token),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Adds ASTRO to the budget that keeper tips are paid from. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **token** is an object of type [`Addr`]. This is the token that was sent.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens that was sent.
+fn fund_keeper_tips(
+    deps: DepsMut,
+    token: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if token != cfg.astro_token {
+        return Err(ContractError::InvalidKeeperTipToken {});
+    }
+
+    let budget = KEEPER_TIP_BUDGET
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    KEEPER_TIP_BUDGET.save(deps.storage, &budget)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "fund_keeper_tips"),
+        attr("amount", amount),
+        attr("keeper_tip_budget", budget),
+    ]))
+}
+
+/// ## Description
+/// Schedules a generator's allocation points to change at a future block. A change scheduled for the same
+/// generator and block is replaced. Returns a [`ContractError`] on failure, otherwise returns a [`Response`]
+/// with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator allocation points change.
+///
+/// * **alloc_point** is an object of type [`Uint128`]. These are the new allocation points.
+///
+/// * **effective_block** is a variable of type [`u64`]. This is the block from which the new allocation points are used.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn schedule_set(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    alloc_point: Uint128,
+    effective_block: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if alloc_point > Uint128::from(MAX_ALLOC_POINT) {
+        return Err(ContractError::AllocPointTooHigh {
+            max: MAX_ALLOC_POINT.into(),
+        });
+    }
+
+    if effective_block <= env.block.height {
+        return Err(ContractError::InvalidEffectiveBlock {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    load_pool(deps.storage, &lp_token)?;
+
+    if let Some(pair_info) = query_lp_pair(deps.as_ref(), &cfg.external_lp_tokens, &lp_token)? {
+        if let Some(asset) = pair_info
+            .asset_infos
+            .iter()
+            .find(|asset| cfg.blocked_list_tokens.contains(asset))
+        {
+            return Err(ContractError::TokenBlocked {
+                token: asset.to_string(),
+            });
+        }
+    }
+
+    let mut scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+    scheduled_sets.retain(|scheduled_set| {
+        scheduled_set.lp_token != lp_token || scheduled_set.effective_block != effective_block
+    });
+
+    if scheduled_sets.len() >= MAX_SCHEDULED_SETS {
+        return Err(ContractError::TooManyScheduledSets {
+            max: MAX_SCHEDULED_SETS,
+        });
+    }
+
+    // Every generator that may become active must fit among the active generators
+    let mut pools: HashSet<&Addr> = cfg.active_pools.iter().map(|(addr, _)| addr).collect();
+    pools.extend(
+        scheduled_sets
+            .iter()
+            .map(|scheduled_set| &scheduled_set.lp_token),
+    );
+    pools.insert(&lp_token);
+    if pools.len() > MAX_ACTIVE_POOLS {
+        return Err(ContractError::TooManyPools {
+            max: MAX_ACTIVE_POOLS,
+        });
+    }
+
+    // Changes for the same block are applied in the order they were scheduled
+    let index = scheduled_sets
+        .iter()
+        .position(|scheduled_set| scheduled_set.effective_block > effective_block)
+        .unwrap_or(scheduled_sets.len());
+    scheduled_sets.insert(
+        index,
+        ScheduledSet {
+            lp_token: lp_token.clone(),
+            alloc_point,
+            effective_block,
+        },
+    );
+    SCHEDULED_SETS.save(deps.storage, &scheduled_sets)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "schedule_set"),
+        attr("lp_token", lp_token),
+        attr("alloc_point", alloc_point),
+        attr("effective_block", effective_block.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Cancels a scheduled allocation point change before its effective block. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator allocation points were to change.
+///
+/// * **effective_block** is a variable of type [`u64`]. This is the block from which the new allocation points were to be used.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn cancel_scheduled_set(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    effective_block: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // A due change may already be accounted for in rewards, so it can't be cancelled anymore
+    if effective_block <= env.block.height {
+        return Err(ContractError::InvalidEffectiveBlock {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let mut scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+
+    let index = scheduled_sets
+        .iter()
+        .position(|scheduled_set| {
+            scheduled_set.lp_token == lp_token && scheduled_set.effective_block == effective_block
+        })
+        .ok_or_else(|| ContractError::ScheduledSetNotFound {
+            lp_token: lp_token.to_string(),
+            effective_block,
+        })?;
+    scheduled_sets.remove(index);
+    SCHEDULED_SETS.save(deps.storage, &scheduled_sets)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_scheduled_set"),
+        attr("lp_token", lp_token),
+        attr("effective_block", effective_block.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Appends an epoch to the ASTRO emission schedule. Returns a [`ContractError`] on failure, otherwise returns
+/// a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **start_block** is a variable of type [`u64`]. This is the block from which the epoch's emission rate is used.
+///
+/// * **tokens_per_block** is an object of type [`Uint128`]. This is the amount of ASTRO distributed per block
+/// during the epoch.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn add_emission_epoch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_block: u64,
+    tokens_per_block: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    // Epochs can only be appended, so the schedule stays ordered by start block
+    let last_start_block = emission_schedule.last().map_or(env.block.height, |epoch| {
+        epoch.start_block.max(env.block.height)
+    });
+    if start_block <= last_start_block {
+        return Err(ContractError::InvalidEpochStartBlock {});
+    }
+
+    if emission_schedule.len() >= MAX_EMISSION_EPOCHS {
+        return Err(ContractError::TooManyEmissionEpochs {
+            max: MAX_EMISSION_EPOCHS,
+        });
+    }
+
+    emission_schedule.push(EmissionEpoch {
+        start_block,
+        tokens_per_block,
+    });
+    EMISSION_SCHEDULE.save(deps.storage, &emission_schedule)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_emission_epoch"),
+        attr("start_block", start_block.to_string()),
+        attr("tokens_per_block", tokens_per_block),
+    ]))
+}
+
+/// ## Description
+/// Removes an emission epoch before its start block. Returns a [`ContractError`] on failure, otherwise returns
+/// a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **start_block** is a variable of type [`u64`]. This is the start block of the epoch to remove.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn cancel_emission_epoch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_block: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // A started epoch may already be accounted for in rewards, so it can't be cancelled anymore
+    if start_block <= env.block.height {
+        return Err(ContractError::InvalidEpochStartBlock {});
+    }
+
+    let mut emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    let index = emission_schedule
+        .iter()
+        .position(|epoch| epoch.start_block == start_block)
+        .ok_or(ContractError::EmissionEpochNotFound { start_block })?;
+    emission_schedule.remove(index);
+    EMISSION_SCHEDULE.save(deps.storage, &emission_schedule)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_emission_epoch"),
+        attr("start_block", start_block.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Applies the scheduled allocation point changes and emission epochs whose block was reached. All affected
+/// generators are updated first, so the rewards accrued before every change are accounted at the previous
+/// allocations and emission rate. Generators holding blocked tokens are not activated. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+fn apply_scheduled_changes(mut deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    let mut scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+    let due = scheduled_sets
+        .iter()
+        .take_while(|scheduled_set| scheduled_set.effective_block <= env.block.height)
+        .count();
+    let mut emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let due_epochs = emission_schedule
+        .iter()
+        .take_while(|epoch| epoch.start_block <= env.block.height)
+        .count();
+    if due == 0 && due_epochs == 0 {
+        return Ok(());
+    }
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    let mut lp_tokens: Vec<Addr> = cfg.active_pools.iter().map(|pool| pool.0.clone()).collect();
+    for scheduled_set in &scheduled_sets[..due] {
+        if !lp_tokens.contains(&scheduled_set.lp_token) {
+            lp_tokens.push(scheduled_set.lp_token.clone());
+        }
+    }
+    mass_update_pools(deps.branch(), env, &cfg, &lp_tokens)?;
+
+    for scheduled_set in scheduled_sets.drain(..due) {
+        let mut alloc_point = scheduled_set.alloc_point;
+        if !alloc_point.is_zero() {
+            let pair_info = query_lp_pair(
+                deps.as_ref(),
+                &cfg.external_lp_tokens,
+                &scheduled_set.lp_token,
+            )?;
+            if pair_info.is_some_and(|pair_info| {
+                pair_info
+                    .asset_infos
+                    .iter()
+                    .any(|asset| cfg.blocked_list_tokens.contains(asset))
+            }) {
+                alloc_point = Uint128::zero();
+            }
+        }
+
+        cfg.total_alloc_point =
+            set_alloc_point(&mut cfg.active_pools, &scheduled_set.lp_token, alloc_point);
+    }
+
+    if let Some(epoch) = emission_schedule.drain(..due_epochs).next_back() {
+        cfg.tokens_per_block = epoch.tokens_per_block;
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+    SCHEDULED_SETS.save(deps.storage, &scheduled_sets)?;
+    EMISSION_SCHEDULE.save(deps.storage, &emission_schedule)?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Allows an operator to claim the sender's rewards from a specific generator. Returns a [`ContractError`] on
+/// failure, otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **operator** is an object of type [`String`]. This is the address allowed to claim the sender's rewards.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator rewards the operator can claim.
+fn approve_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    if operator == info.sender {
+        return Err(ContractError::CannotApproveSelf {});
+    }
+
+    // Only generators can have operators
+    load_pool(deps.storage, &lp_token)?;
+
+    OPERATORS.save(
+        deps.storage,
+        (&info.sender, &lp_token, &operator),
+        &Empty {},
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_operator"),
+        attr("user", info.sender),
+        attr("lp_token", lp_token),
+        attr("operator", operator),
+    ]))
+}
+
+/// ## Description
+/// Revokes an operator's approval to claim the sender's rewards from a specific generator. Returns a
+/// [`ContractError`] on failure, otherwise returns a [`Response`] with the specified attributes if the
+/// operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **operator** is an object of type [`String`]. This is the operator whose approval is revoked.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator rewards the operator can no longer claim.
+fn revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let key = (&info.sender, &lp_token, &operator);
+    if !OPERATORS.has(deps.storage, key) {
+        return Err(ContractError::OperatorNotApproved {});
+    }
+    OPERATORS.remove(deps.storage, key);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_operator"),
+        attr("user", info.sender),
+        attr("lp_token", lp_token),
+        attr("operator", operator),
+    ]))
+}
+
+/// ## Description
+/// Updates the amount of accrued rewards for specific generators and sends the user's pending ASTRO,
+/// proxy, alternative and pair rewards. The user's staked amount doesn't change. Returns a [`ContractError`] on
+/// failure, otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is the object of type [`Addr`]. Sets the liquidity pool to be updated.
+///
+/// * **account** is the object of type [`Addr`].
+pub fn claim_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    lp_tokens: Vec<Addr>,
+    account: Addr,
+) -> Result<Response, ContractError> {
+    let response = Response::default();
+
+    let cfg = CONFIG.load(deps.storage)?;
+
+    mass_update_pools(deps.branch(), &env, &cfg, &lp_tokens)?;
+
+    // ASTRO from all generators is claimed from vesting at once
+    let mut pending_rewards = Uint128::zero();
+    let mut send_rewards_msg: Vec<WasmMsg> = vec![];
+    for lp_token in &lp_tokens {
+        let mut pool = load_pool(deps.storage, lp_token)?;
+
+        let user = user_info().load(deps.storage, (lp_token, &account))?;
+
+        let (pool_rewards, mut other_rewards_msgs) =
+            pending_rewards_msgs(&mut pool, &user, &account)?;
+        pending_rewards = pending_rewards.checked_add(pool_rewards)?;
+        send_rewards_msg.append(&mut other_rewards_msgs);
+        send_rewards_msg.append(&mut build_claim_pools_asset_reward_messages(
+            deps.as_ref(),
+            &env,
+            lp_token,
+            &pool,
+            &account,
+            user.amount,
+            Uint128::zero(),
+        )?);
+
+        // Settle the claimed rewards without touching the stake
+        let (amount, boost_bonus) = (user.amount, user.boost_bonus);
+        let user = update_user_balance(user, &pool, amount, boost_bonus)?;
+
+        POOL_INFO.save(deps.storage, lp_token, &pool, env.block.height)?;
+        user_info().save(deps.storage, (lp_token, &account), &user, env.block.height)?;
+    }
+
+    let claim_msg = claim_astro_msg(deps.storage, &cfg, pending_rewards, &account)?;
+
+    Ok(response
+        .add_attribute("action", "claim_rewards")
+        .add_submessages(claim_msg)
+        .add_messages(send_rewards_msg))
+}
+
+/// ## Description
+/// Accrues the amount of rewards distributed for each staked LP token in a specific generator.
+/// Also update reward variables for the given generator.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
 ///
 /// * **lp_token** is an object of type [`Addr`]. This is the LP token whose rewards per share we update.
 ///
@@ -869,6 +2268,8 @@ pub fn claim_rewards(
 ///
 /// * **deposited** is an [`Option`] field object of type [`Uint128`]. This is the total amount of LP
 /// tokens deposited in the target generator.
+///
+/// Returns the amount of LP tokens staked in the generator before the deposit.
 pub fn accumulate_rewards_per_share(
     deps: DepsMut,
     env: &Env,
@@ -876,62 +2277,165 @@ pub fn accumulate_rewards_per_share(
     pool: &mut PoolInfo,
     cfg: &Config,
     deposited: Option<Uint128>,
-) -> StdResult<()> {
-    let lp_supply: Uint128;
+) -> Result<Uint128, ContractError> {
+    let mut lp_supply = query_lp_supply(deps.as_ref(), env, lp_token, pool)?;
 
-    match &pool.reward_proxy {
-        Some(proxy) => {
-            lp_supply = deps
+    if pool.reward_proxies.is_empty() {
+        if let Some(amount) = deposited {
+            // On deposit, the contract's LP token balance is already increased, so we need to subtract the
+            lp_supply = lp_supply.checked_sub(amount)?;
+        }
+    } else if !lp_supply.is_zero() {
+        for reward_proxy in pool.reward_proxies.iter_mut() {
+            let reward_amount: Uint128 = deps
                 .querier
-                .query_wasm_smart(proxy, &ProxyQueryMsg::Deposit {})?;
-
-            if !lp_supply.is_zero() {
-                let reward_amount: Uint128 = deps
-                    .querier
-                    .query_wasm_smart(proxy, &ProxyQueryMsg::Reward {})?;
-
-                let token_rewards =
-                    reward_amount.checked_sub(pool.proxy_reward_balance_before_update)?;
-
-                let share = Decimal::from_ratio(token_rewards, lp_supply);
-                pool.accumulated_proxy_rewards_per_share = pool
-                    .accumulated_proxy_rewards_per_share
-                    .checked_add(share)?;
-                pool.proxy_reward_balance_before_update = reward_amount;
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::Reward {})?;
+
+            // The balance only decreases when the generator sends proxy rewards, which is deducted from
+            // the tracked balance, so anything above it has been accrued since the previous update
+            let token_rewards =
+                reward_amount.saturating_sub(reward_proxy.reward_balance_before_update);
+
+            // Track the proxy reward rate since the previous update
+            if env.block.height > pool.last_reward_block.u64() {
+                reward_proxy.reward_per_block = token_rewards
+                    .checked_div(Uint128::from(
+                        env.block.height - pool.last_reward_block.u64(),
+                    ))
+                    .map_err(StdError::from)?;
             }
-        }
-        None => {
-            let res: BalanceResponse = deps.querier.query_wasm_smart(
+
+            reward_proxy.accumulated_rewards_per_share = accumulate_share(
+                reward_proxy.accumulated_rewards_per_share,
+                token_rewards,
+                lp_supply,
                 lp_token,
-                &cw20::Cw20QueryMsg::Balance {
-                    address: env.contract.address.to_string(),
-                },
             )?;
-
-            if let Some(amount) = deposited {
-                // On deposit, the contract's LP token balance is already increased, so we need to subtract the
-                lp_supply = res.balance.checked_sub(amount)?;
-            } else {
-                lp_supply = res.balance;
-            }
+            reward_proxy.reward_balance_before_update = reward_amount;
         }
-    };
+    }
 
     if env.block.height > pool.last_reward_block.u64() {
         if !lp_supply.is_zero() {
-            let alloc_point = get_alloc_point(&cfg.active_pools, lp_token);
+            let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+            let emission_schedule = EMISSION_SCHEDULE
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+
+            let token_rewards = calculate_rewards(
+                env,
+                lp_token,
+                pool,
+                cfg,
+                &scheduled_sets,
+                &emission_schedule,
+            )?;
+
+            // Boosts only apply to ASTRO rewards
+            pool.accumulated_rewards_per_share = accumulate_share(
+                pool.accumulated_rewards_per_share,
+                token_rewards,
+                lp_supply.checked_add(pool.total_boost_bonus)?,
+                lp_token,
+            )?;
 
-            let token_rewards = calculate_rewards(env, pool, &alloc_point, cfg)?;
+            if let Some(alt_reward) = &mut pool.alt_reward {
+                let alt_rewards =
+                    calculate_alt_rewards(env, alt_reward, pool.last_reward_block.u64())?;
+                alt_reward.budget = alt_reward.budget.checked_sub(alt_rewards)?;
 
-            let share = Decimal::from_ratio(token_rewards, lp_supply);
-            pool.accumulated_rewards_per_share =
-                pool.accumulated_rewards_per_share.checked_add(share)?;
+                pool.accumulated_alt_rewards_per_share = accumulate_share(
+                    pool.accumulated_alt_rewards_per_share,
+                    alt_rewards,
+                    lp_supply,
+                    lp_token,
+                )?;
+            }
         }
 
         pool.last_reward_block = Uint64::from(env.block.height);
     }
 
-    Ok(())
+    Ok(lp_supply)
+}
+
+/// ## Description
+/// Returns the amount of LP tokens staked in a generator, excluding its orphan stake which doesn't earn rewards.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+///
+/// * **pool** is an object of type [`PoolInfo`]. This is the generator associated with the `lp_token`.
+fn query_lp_supply(
+    deps: Deps,
+    env: &Env,
+    lp_token: &Addr,
+    pool: &PoolInfo,
+) -> Result<Uint128, ContractError> {
+    let staked: Uint128 = match pool.staking_proxy() {
+        Some(proxy) => deps
+            .querier
+            .query_wasm_smart(proxy, &ProxyQueryMsg::Deposit {})?,
+        None => query_token_balance(
+            &deps.querier,
+            lp_token.clone(),
+            env.contract.address.clone(),
+        )?,
+    };
+    let orphan_stake = ORPHAN_STAKES
+        .may_load(deps.storage, lp_token)?
+        .unwrap_or_default();
+
+    Ok(staked.saturating_sub(orphan_stake))
+}
+
+/// ## Description
+/// Adds the rewards per staked LP token to the accumulated rewards per share of a generator.
+/// Returns a [`ContractError`] if the result doesn't fit in a [`Decimal`].
+/// # Params
+/// * **acc_per_share** is an object of type [`Decimal`]. These are the generator's accumulated rewards per share.
+///
+/// * **rewards** is an object of type [`Uint128`]. These are the rewards accrued since the last update.
+///
+/// * **lp_supply** is an object of type [`Uint128`]. This is the total amount of LP tokens staked.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the generator's LP token.
+fn accumulate_share(
+    acc_per_share: Decimal,
+    rewards: Uint128,
+    lp_supply: Uint128,
+    lp_token: &Addr,
+) -> Result<Decimal, ContractError> {
+    calculate_rewards_per_share(rewards, lp_supply)
+        .ok()
+        .and_then(|share| acc_per_share.checked_add(share).ok())
+        .ok_or_else(|| ContractError::RewardsPerShareOverflow {
+            lp_token: lp_token.to_string(),
+        })
+}
+
+/// ## Description
+/// Returns the amount of alternative base rewards emitted since the last reward update, capped by the remaining budget.
+/// # Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **alt_reward** is an object of type [`AltRewardInfo`]. This is the generator's alternative base reward.
+///
+/// * **last_reward_block** is a variable of type [`u64`]. This is the generator's last reward update block.
+fn calculate_alt_rewards(
+    env: &Env,
+    alt_reward: &AltRewardInfo,
+    last_reward_block: u64,
+) -> StdResult<Uint128> {
+    let n_blocks = Uint128::from(env.block.height).checked_sub(last_reward_block.into())?;
+
+    Ok(alt_reward
+        .tokens_per_block
+        .checked_mul(n_blocks)?
+        .min(alt_reward.budget))
 }
 
 /// ## Description
@@ -955,6 +2459,23 @@ fn receive_cw20(
     let amount = cw20_msg.amount;
     let lp_token = info.sender;
 
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // Staked tokens are keyed by the account, so it must be a valid address
+    let account = match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit {} => addr_validate_to_lower(deps.api, &cw20_msg.sender)?,
+        Cw20HookMsg::DepositFor(beneficiary) => {
+            addr_validate_to_lower(deps.api, beneficiary.as_str())?
+        }
+        // The sent token is a reward token rather than an LP token
+        Cw20HookMsg::FundAltReward { lp_token: target } => {
+            return fund_alt_reward(deps, env, lp_token, target, amount)
+        }
+        Cw20HookMsg::FundKeeperTips {} => return fund_keeper_tips(deps, lp_token, amount),
+    };
+
     let cfg = CONFIG.load(deps.storage)?;
 
     if POOL_INFO.may_load(deps.storage, &lp_token)?.is_none() {
@@ -965,35 +2486,26 @@ fn receive_cw20(
         create_pool(deps.branch(), &env, &lp_token, &cfg, &factory_cfg)?;
     }
 
-    match from_binary(&cw20_msg.msg)? {
-        Cw20HookMsg::Deposit {} => update_rewards_and_execute(
-            deps,
-            env,
-            Some(lp_token.clone()),
-            ExecuteOnReply::Deposit {
-                lp_token,
-                account: Addr::unchecked(cw20_msg.sender),
-                amount,
-            },
-        ),
-        Cw20HookMsg::DepositFor(beneficiary) => update_rewards_and_execute(
-            deps,
-            env,
-            Some(lp_token.clone()),
-            ExecuteOnReply::Deposit {
-                lp_token,
-                account: beneficiary,
-                amount,
-            },
-        ),
-    }
+    update_rewards_and_execute(
+        deps,
+        env,
+        Some(vec![lp_token.clone()]),
+        ExecuteOnReply::Deposit {
+            lp_token,
+            account,
+            amount,
+        },
+    )
 }
 
 /// ## Description
-/// Distributes pending proxy rewards for a specific staker.
+/// Distributes pending ASTRO, proxy and alternative rewards for a specific staker. The proxy rewards sent
+/// are deducted from the proxy reward balance tracked in the generator.
 /// Returns a [`ContractError`] on failure, otherwise returns a vector that
 /// contains objects of type [`SubMsg`].
 /// # Params
+/// * **storage** is an object of type [`Storage`].
+///
 /// * **cfg** is an object of type [`Config`].
 ///
 /// * **pool** is an object of type [`PoolInfo`]. This is the generator where the staker is staked.
@@ -1002,42 +2514,60 @@ fn receive_cw20(
 ///
 /// * **to** is an object of type [`Addr`]. This is the address that will receive the proxy rewards.
 pub fn send_pending_rewards(
+    storage: &mut dyn Storage,
     cfg: &Config,
-    pool: &PoolInfo,
+    pool: &mut PoolInfo,
     user: &UserInfo,
     to: &Addr,
-) -> Result<Vec<WasmMsg>, ContractError> {
+) -> Result<Vec<SubMsg>, ContractError> {
+    let (pending_rewards, other_rewards_msgs) = pending_rewards_msgs(pool, user, to)?;
+
+    let mut messages = claim_astro_msg(storage, cfg, pending_rewards, to)?;
+    messages.extend(other_rewards_msgs.into_iter().map(SubMsg::new));
+
+    Ok(messages)
+}
+
+/// ## Description
+/// Returns the amount of pending ASTRO rewards of a specific staker, together with the messages that send
+/// their pending rewards from every reward proxy and alternative rewards. The proxy rewards sent are deducted
+/// from the reward balance tracked for each proxy in the generator.
+/// # Params
+/// * **pool** is an object of type [`PoolInfo`]. This is the generator where the staker is staked.
+///
+/// * **user** is an object of type [`UserInfo`]. This is the staker for which we claim accrued rewards.
+///
+/// * **to** is an object of type [`Addr`]. This is the address that will receive the rewards.
+fn pending_rewards_msgs(
+    pool: &mut PoolInfo,
+    user: &UserInfo,
+    to: &Addr,
+) -> Result<(Uint128, Vec<WasmMsg>), ContractError> {
     if user.amount.is_zero() {
-        return Ok(vec![]);
+        return Ok((Uint128::zero(), vec![]));
     }
 
     let mut messages = vec![];
 
-    let pending_rewards = pool
-        .accumulated_rewards_per_share
-        .checked_mul(user.amount)?
-        .checked_sub(user.reward_debt)?;
-
-    if !pending_rewards.is_zero() {
-        messages.push(WasmMsg::Execute {
-            contract_addr: cfg.vesting_contract.to_string(),
-            msg: to_binary(&VestingExecuteMsg::Claim {
-                recipient: Some(to.to_string()),
-                amount: Some(pending_rewards),
-            })?,
-            funds: vec![],
-        });
-    }
+    let pending_rewards = accrued_rewards(
+        pool.accumulated_rewards_per_share,
+        user.boosted_amount()?,
+        user.reward_debt,
+    )?;
 
-    if let Some(proxy) = &pool.reward_proxy {
-        let pending_proxy_rewards = pool
-            .accumulated_proxy_rewards_per_share
-            .checked_mul(user.amount)?
-            .checked_sub(user.reward_debt_proxy)?;
+    for (index, reward_proxy) in pool.reward_proxies.iter_mut().enumerate() {
+        let pending_proxy_rewards = accrued_rewards(
+            reward_proxy.accumulated_rewards_per_share,
+            user.amount,
+            user.proxy_reward_debt(index),
+        )?;
 
         if !pending_proxy_rewards.is_zero() {
+            reward_proxy.reward_balance_before_update = reward_proxy
+                .reward_balance_before_update
+                .saturating_sub(pending_proxy_rewards);
             messages.push(WasmMsg::Execute {
-                contract_addr: proxy.to_string(),
+                contract_addr: reward_proxy.proxy.to_string(),
                 funds: vec![],
                 msg: to_binary(&ProxyExecuteMsg::SendRewards {
                     account: to.to_string(),
@@ -1047,7 +2577,71 @@ pub fn send_pending_rewards(
         }
     }
 
-    Ok(messages)
+    if let Some(alt_reward) = &pool.alt_reward {
+        let pending_alt_rewards = accrued_rewards(
+            pool.accumulated_alt_rewards_per_share,
+            user.amount,
+            user.reward_debt_alt,
+        )?;
+
+        if !pending_alt_rewards.is_zero() {
+            messages.push(WasmMsg::Execute {
+                contract_addr: alt_reward.reward_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: to.to_string(),
+                    amount: pending_alt_rewards,
+                })?,
+                funds: vec![],
+            });
+        }
+    }
+
+    Ok((pending_rewards, messages))
+}
+
+/// ## Description
+/// Returns the message that sends ASTRO rewards from the vesting contract, together with the ASTRO
+/// the recipient wasn't paid before. Returns no message if there is nothing to send.
+/// # Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **cfg** is an object of type [`Config`].
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of ASTRO to send.
+///
+/// * **to** is an object of type [`Addr`]. This is the address that will receive the ASTRO.
+fn claim_astro_msg(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    amount: Uint128,
+    to: &Addr,
+) -> StdResult<Vec<SubMsg>> {
+    let amount = amount.checked_add(UNPAID_ASTRO.may_load(storage, to)?.unwrap_or_default())?;
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    UNPAID_ASTRO.remove(storage, to);
+    TMP_VESTING_CLAIM.save(
+        storage,
+        &VestingClaim {
+            recipient: to.clone(),
+            amount,
+        },
+    )?;
+
+    // A failed claim must not revert the deposit, withdrawal or claim that sends it
+    Ok(vec![SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: cfg.vesting_contract.to_string(),
+            msg: to_binary(&VestingExecuteMsg::Claim {
+                recipient: Some(to.to_string()),
+                amount: Some(amount),
+            })?,
+            funds: vec![],
+        },
+        VESTING_CLAIM_REPLY_ID,
+    )])
 }
 
 /// ## Description
@@ -1070,14 +2664,14 @@ pub fn deposit(
     beneficiary: Addr,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let user = USER_INFO
+    let user = user_info()
         .load(deps.storage, (&lp_token, &beneficiary))
         .unwrap_or_default();
 
     let cfg = CONFIG.load(deps.storage)?;
-    let mut pool = POOL_INFO.load(deps.storage, &lp_token)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
 
-    accumulate_rewards_per_share(
+    let lp_supply = accumulate_rewards_per_share(
         deps.branch(),
         &env,
         &lp_token,
@@ -1087,21 +2681,21 @@ pub fn deposit(
     )?;
 
     // Send pending rewards (if any) to the depositor
-    let send_rewards_msg = send_pending_rewards(&cfg, &pool, &user, &beneficiary)?;
+    let send_rewards_msg =
+        send_pending_rewards(deps.storage, &cfg, &mut pool, &user, &beneficiary)?;
 
     // If a reward proxy is set - send LP tokens to the proxy
-    let transfer_msg = if !amount.is_zero() && pool.reward_proxy.is_some() {
-        vec![WasmMsg::Execute {
+    let transfer_msg = match pool.staking_proxy() {
+        Some(proxy) if !amount.is_zero() => vec![WasmMsg::Execute {
             contract_addr: lp_token.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Send {
-                contract: pool.reward_proxy.clone().unwrap().to_string(),
+                contract: proxy.to_string(),
                 msg: to_binary(&ProxyCw20HookMsg::Deposit {})?,
                 amount,
             })?,
             funds: vec![],
-        }]
-    } else {
-        vec![]
+        }],
+        _ => vec![],
     };
 
     let reward_msg = build_claim_pools_asset_reward_messages(
@@ -1116,17 +2710,38 @@ pub fn deposit(
 
     // Update user's LP token balance
     let updated_amount = user.amount.checked_add(amount)?;
-    let user = update_user_balance(user, &pool, updated_amount)?;
+    let boost_bonus = query_boost_bonus(
+        &deps.querier,
+        &cfg,
+        &beneficiary,
+        updated_amount,
+        lp_supply.checked_add(amount)?,
+    );
+    pool.total_boost_bonus = pool
+        .total_boost_bonus
+        .checked_sub(user.boost_bonus)?
+        .checked_add(boost_bonus)?;
+    let user = update_user_balance(user, &pool, updated_amount, boost_bonus)?;
+
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+    user_info().save(
+        deps.storage,
+        (&lp_token, &beneficiary),
+        &user,
+        env.block.height,
+    )?;
 
-    POOL_INFO.save(deps.storage, &lp_token, &pool)?;
-    USER_INFO.save(deps.storage, (&lp_token, &beneficiary), &user)?;
+    let attributes = AttrBuilder::new("deposit")
+        .receiver(beneficiary.as_str())
+        .attr("lp_token", lp_token.as_str())
+        .attr("amount", amount);
 
     Ok(Response::new()
-        .add_messages(send_rewards_msg)
+        .add_submessages(send_rewards_msg)
         .add_messages(transfer_msg)
         .add_messages(reward_msg)
-        .add_attribute("action", "deposit")
-        .add_attribute("amount", amount))
+        .add_event(attributes.event("generator"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -1149,24 +2764,28 @@ pub fn withdraw(
     account: Addr,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let user = USER_INFO
+    let user = user_info()
         .load(deps.storage, (&lp_token, &account))
         .unwrap_or_default();
     if user.amount < amount {
-        return Err(ContractError::BalanceTooSmall {});
+        return Err(ContractError::BalanceTooSmall {
+            requested: amount,
+            available: user.amount,
+        });
     }
 
     let cfg = CONFIG.load(deps.storage)?;
-    let mut pool = POOL_INFO.load(deps.storage, &lp_token)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
 
-    accumulate_rewards_per_share(deps.branch(), &env, &lp_token, &mut pool, &cfg, None)?;
+    let lp_supply =
+        accumulate_rewards_per_share(deps.branch(), &env, &lp_token, &mut pool, &cfg, None)?;
 
     // Send pending rewards to the user
-    let send_rewards_msg = send_pending_rewards(&cfg, &pool, &user, &account)?;
+    let send_rewards_msg = send_pending_rewards(deps.storage, &cfg, &mut pool, &user, &account)?;
 
     // Instantiate the transfer call for the LP token
     let transfer_msg = if !amount.is_zero() {
-        vec![match &pool.reward_proxy {
+        vec![match pool.staking_proxy() {
             Some(proxy) => WasmMsg::Execute {
                 contract_addr: proxy.to_string(),
                 funds: vec![],
@@ -1200,22 +2819,90 @@ pub fn withdraw(
 
     // Update user's balance
     let updated_amount = user.amount.checked_sub(amount)?;
-    let user = update_user_balance(user, &pool, updated_amount)?;
+    let boost_bonus = query_boost_bonus(
+        &deps.querier,
+        &cfg,
+        &account,
+        updated_amount,
+        lp_supply.saturating_sub(amount),
+    );
+    pool.total_boost_bonus = pool
+        .total_boost_bonus
+        .checked_sub(user.boost_bonus)?
+        .checked_add(boost_bonus)?;
+    let user = update_user_balance(user, &pool, updated_amount, boost_bonus)?;
 
-    POOL_INFO.save(deps.storage, &lp_token, &pool)?;
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
 
     if !user.amount.is_zero() {
-        USER_INFO.save(deps.storage, (&lp_token, &account), &user)?;
+        user_info().save(deps.storage, (&lp_token, &account), &user, env.block.height)?;
     } else {
-        USER_INFO.remove(deps.storage, (&lp_token, &account));
+        user_info().remove(deps.storage, (&lp_token, &account), env.block.height)?;
     }
 
+    let attributes = AttrBuilder::new("withdraw")
+        .sender(account.as_str())
+        .receiver(account.as_str())
+        .attr("lp_token", lp_token.as_str())
+        .attr("amount", amount);
+
     Ok(Response::new()
-        .add_messages(send_rewards_msg)
+        .add_submessages(send_rewards_msg)
         .add_messages(transfer_msg)
         .add_messages(reward_msg)
-        .add_attribute("action", "withdraw")
-        .add_attribute("amount", amount))
+        .add_event(attributes.event("generator"))
+        .add_attributes(attributes.build()))
+}
+
+/// ## Description
+/// Recomputes a staker's boost in a specific generator. The rewards accrued with the previous boost are
+/// sent to the staker first, then the boost and the reward debts are updated. The stake doesn't change.
+/// Returns a [`ContractError`] on failure, otherwise returns a [`Response`] with the specified attributes
+/// if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token staked by the user.
+///
+/// * **account** is an object of type [`Addr`]. This is the staker whose boost we update.
+fn checkpoint_user_boost(
+    mut deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    account: Addr,
+) -> Result<Response, ContractError> {
+    let user = user_info().load(deps.storage, (&lp_token, &account))?;
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+
+    let lp_supply =
+        accumulate_rewards_per_share(deps.branch(), &env, &lp_token, &mut pool, &cfg, None)?;
+
+    // Rewards accrued with the previous boost are settled before it changes
+    let send_rewards_msg = send_pending_rewards(deps.storage, &cfg, &mut pool, &user, &account)?;
+
+    let boost_bonus = query_boost_bonus(&deps.querier, &cfg, &account, user.amount, lp_supply);
+    pool.total_boost_bonus = pool
+        .total_boost_bonus
+        .checked_sub(user.boost_bonus)?
+        .checked_add(boost_bonus)?;
+    let amount = user.amount;
+    let user = update_user_balance(user, &pool, amount, boost_bonus)?;
+
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+    user_info().save(deps.storage, (&lp_token, &account), &user, env.block.height)?;
+
+    Ok(Response::new()
+        .add_submessages(send_rewards_msg)
+        .add_attributes(vec![
+            attr("action", "checkpoint_user_boost"),
+            attr("lp_token", lp_token),
+            attr("user", account),
+            attr("boost_bonus", boost_bonus),
+        ]))
 }
 
 /// ## Description
@@ -1230,18 +2917,10 @@ pub fn build_claim_pools_asset_reward_messages(
     deposit: Uint128,
 ) -> Result<Vec<WasmMsg>, ContractError> {
     Ok(if pool.has_asset_rewards {
-        let total_share = match &pool.reward_proxy {
-            Some(proxy) => deps
-                .querier
-                .query_wasm_smart(proxy, &ProxyQueryMsg::Deposit {})?,
-            None => {
-                query_token_balance(
-                    &deps.querier,
-                    lp_token.clone(),
-                    env.contract.address.clone(),
-                )? - deposit
-            }
-        };
+        let mut total_share = query_lp_supply(deps, env, lp_token, pool)?;
+        if pool.reward_proxies.is_empty() {
+            total_share = total_share.checked_sub(deposit)?;
+        }
 
         let minter_response: MinterResponse = deps
             .querier
@@ -1277,24 +2956,37 @@ pub fn build_claim_pools_asset_reward_messages(
 /// * **lp_token** is an object of type [`String`]. This is the LP token to withdraw.
 pub fn emergency_withdraw(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     lp_token: String,
 ) -> Result<Response, ContractError> {
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
 
-    let mut pool = POOL_INFO.load(deps.storage, &lp_token)?;
-    let user = USER_INFO.load(deps.storage, (&lp_token, &info.sender))?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    let user = user_info().load(deps.storage, (&lp_token, &info.sender))?;
 
-    pool.orphan_proxy_rewards = pool.orphan_proxy_rewards.checked_add(
-        pool.accumulated_proxy_rewards_per_share
-            .checked_mul(user.amount)?
-            .saturating_sub(user.reward_debt_proxy),
-    )?;
+    for (index, reward_proxy) in pool.reward_proxies.iter_mut().enumerate() {
+        reward_proxy.orphan_rewards = reward_proxy.orphan_rewards.checked_add(accrued_rewards(
+            reward_proxy.accumulated_rewards_per_share,
+            user.amount,
+            user.proxy_reward_debt(index),
+        )?)?;
+    }
+
+    // Forfeited alternative rewards are returned to the budget
+    if let Some(alt_reward) = &mut pool.alt_reward {
+        alt_reward.budget = alt_reward.budget.checked_add(accrued_rewards(
+            pool.accumulated_alt_rewards_per_share,
+            user.amount,
+            user.reward_debt_alt,
+        )?)?;
+    }
+
+    pool.total_boost_bonus = pool.total_boost_bonus.checked_sub(user.boost_bonus)?;
 
     // Instantiate the transfer call for the LP token
     let transfer_msg: WasmMsg;
-    if let Some(proxy) = &pool.reward_proxy {
+    if let Some(proxy) = pool.staking_proxy() {
         transfer_msg = WasmMsg::Execute {
             contract_addr: proxy.to_string(),
             msg: to_binary(&ProxyExecuteMsg::EmergencyWithdraw {
@@ -1315,13 +3007,19 @@ pub fn emergency_withdraw(
     }
 
     // Change the user's balance
-    USER_INFO.remove(deps.storage, (&lp_token, &info.sender));
-    POOL_INFO.save(deps.storage, &lp_token, &pool)?;
+    user_info().remove(deps.storage, (&lp_token, &info.sender), env.block.height)?;
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+
+    let attributes = AttrBuilder::new("emergency_withdraw")
+        .sender(info.sender.as_str())
+        .receiver(info.sender.as_str())
+        .attr("lp_token", lp_token.as_str())
+        .attr("amount", user.amount);
 
     Ok(Response::new()
         .add_message(transfer_msg)
-        .add_attribute("action", "emergency_withdraw")
-        .add_attribute("amount", user.amount))
+        .add_event(attributes.event("generator"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
@@ -1359,7 +3057,7 @@ fn set_allowed_reward_proxies(
 }
 
 /// ## Description
-/// Sends orphaned proxy rewards (which are left behind by emergency withdrawals) to another address.
+/// Sends the orphaned rewards of every reward proxy (which are left behind by emergency withdrawals) to another address.
 /// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
 /// attributes if the operation was successful.
 /// # Params
@@ -1372,6 +3070,7 @@ fn set_allowed_reward_proxies(
 /// * **lp_token** is an object of type [`String`]. This is the LP token whose orphaned rewards we send out.
 fn send_orphan_proxy_rewards(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     lp_token: String,
@@ -1385,38 +3084,164 @@ fn send_orphan_proxy_rewards(
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
     let recipient = addr_validate_to_lower(deps.api, &recipient)?;
 
-    let mut pool = POOL_INFO.load(deps.storage, &lp_token)?;
-    let proxy = match &pool.reward_proxy {
-        Some(proxy) => proxy.clone(),
-        None => return Err(ContractError::PoolDoesNotHaveAdditionalRewards {}),
-    };
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    if pool.reward_proxies.is_empty() {
+        return Err(ContractError::PoolDoesNotHaveAdditionalRewards {
+            lp_token: lp_token.to_string(),
+        });
+    }
 
-    let amount = pool.orphan_proxy_rewards;
-    if amount.is_zero() {
-        return Err(ContractError::OrphanRewardsTooSmall {});
+    let mut response = Response::new()
+        .add_attribute("action", "send_orphan_rewards")
+        .add_attribute("recipient", recipient.to_string())
+        .add_attribute("lp_token", lp_token.to_string());
+    let mut sent = false;
+    for reward_proxy in pool.reward_proxies.iter_mut() {
+        let amount = reward_proxy.orphan_rewards;
+        if amount.is_zero() {
+            continue;
+        }
+
+        reward_proxy.orphan_rewards = Uint128::zero();
+        reward_proxy.reward_balance_before_update = reward_proxy
+            .reward_balance_before_update
+            .saturating_sub(amount);
+        response = response
+            .add_message(WasmMsg::Execute {
+                contract_addr: reward_proxy.proxy.to_string(),
+                funds: vec![],
+                msg: to_binary(&ProxyExecuteMsg::SendRewards {
+                    account: recipient.to_string(),
+                    amount,
+                })?,
+            })
+            .add_attribute("proxy", reward_proxy.proxy.to_string())
+            .add_attribute("amount", amount);
+        sent = true;
     }
 
-    pool.orphan_proxy_rewards = Uint128::zero();
-    POOL_INFO.save(deps.storage, &lp_token, &pool)?;
+    if !sent {
+        return Err(ContractError::OrphanRewardsTooSmall {
+            lp_token: lp_token.to_string(),
+        });
+    }
 
-    Ok(Response::new()
-        .add_message(WasmMsg::Execute {
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+
+    Ok(response)
+}
+
+/// ## Description
+/// Sends the orphan stake of a generator to the recipient. The orphan stake doesn't earn rewards,
+/// so the generator's reward accounting doesn't change.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **recipient** is an object of type [`String`]. This is the recipient of the orphan stake.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose orphan stake we send out.
+fn send_orphan_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let recipient = addr_validate_to_lower(deps.api, &recipient)?;
+
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let amount = ORPHAN_STAKES
+        .may_load(deps.storage, &lp_token)?
+        .ok_or_else(|| ContractError::OrphanStakeNotFound {
+            lp_token: lp_token.to_string(),
+        })?;
+    ORPHAN_STAKES.remove(deps.storage, &lp_token);
+
+    let transfer_msg = match pool.staking_proxy() {
+        Some(proxy) => WasmMsg::Execute {
             contract_addr: proxy.to_string(),
             funds: vec![],
-            msg: to_binary(&ProxyExecuteMsg::SendRewards {
+            msg: to_binary(&ProxyExecuteMsg::Withdraw {
                 account: recipient.to_string(),
                 amount,
             })?,
-        })
-        .add_attribute("action", "send_orphan_rewards")
+        },
+        None => WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        },
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "send_orphan_stake")
         .add_attribute("recipient", recipient)
         .add_attribute("lp_token", lp_token.to_string())
         .add_attribute("amount", amount))
 }
 
 /// ## Description
-/// Sets the reward proxy contract for a specifi generator. Returns a [`ContractError`] on failure, otherwise
-/// returns a [`Response`] with the specified attributes if the operation was successful.
+/// Migrates the next batch of stakes stored before V1.3.0. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// # Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of stakes to migrate.
+fn migrate_users(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    apply_scheduled_changes(deps.branch(), &env)?;
+    let cfg = CONFIG.load(deps.storage)?;
+
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    let progress = USERS_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::UsersAlreadyMigrated {})?;
+    let limit = limit
+        .unwrap_or(DEFAULT_MIGRATION_LIMIT)
+        .min(MAX_MIGRATION_LIMIT) as usize;
+
+    let last_key = migration::migrate_users(deps.branch(), &env, &cfg, progress.last_key, limit)?;
+    let finished = last_key.is_none();
+    if finished {
+        USERS_MIGRATION.remove(deps.storage);
+    } else {
+        USERS_MIGRATION.save(deps.storage, &UsersMigration { last_key })?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_users")
+        .add_attribute("finished", finished.to_string()))
+}
+
+/// ## Description
+/// Sets the reward proxy contract that stakes the LP tokens of a specific generator. If the generator already has
+/// such a proxy, its rewards are updated first and the generator is moved to the new proxy afterwards. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
 fn move_to_proxy(
     mut deps: DepsMut,
     env: Env,
@@ -1435,49 +3260,258 @@ fn move_to_proxy(
     }
 
     if !cfg.allowed_reward_proxies.contains(&proxy_addr) {
-        return Err(ContractError::RewardProxyNotAllowed {});
+        return Err(ContractError::RewardProxyNotAllowed {
+            proxy: proxy_addr.to_string(),
+        });
+    }
+
+    if POOL_INFO.may_load(deps.storage, &lp_addr)?.is_none() {
+        let factory_cfg: FactoryConfigResponse = deps
+            .querier
+            .query_wasm_smart(cfg.factory.clone(), &FactoryQueryMsg::Config {})?;
+
+        create_pool(deps.branch(), &env, &lp_addr, &cfg, &factory_cfg)?;
+    }
+
+    let mut pool_info = load_pool(deps.storage, &lp_addr)?;
+    if pool_info
+        .reward_proxies
+        .iter()
+        .any(|reward_proxy| reward_proxy.proxy == proxy_addr)
+    {
+        return Err(ContractError::PoolAlreadyHasRewardProxyContract {
+            lp_token: lp_addr.to_string(),
+            proxy: proxy_addr.to_string(),
+        });
+    }
+
+    if let Some(reward_proxy) = pool_info.staking_proxy() {
+        // Stakers keep accruing the same token, so the proxy rewards per share stay valid
+        let proxy_cfg: ProxyConfigResponse = deps
+            .querier
+            .query_wasm_smart(&proxy_addr, &ProxyQueryMsg::Config {})?;
+        let reward_token: Addr = deps
+            .querier
+            .query_wasm_smart(reward_proxy, &ProxyQueryMsg::RewardInfo {})?;
+        let new_reward_token: Addr = deps
+            .querier
+            .query_wasm_smart(&proxy_addr, &ProxyQueryMsg::RewardInfo {})?;
+        if proxy_cfg.generator_contract_addr != env.contract.address.as_str()
+            || proxy_cfg.lp_token_addr != lp_addr.as_str()
+            || new_reward_token != reward_token
+        {
+            return Err(ContractError::IncompatibleRewardProxy {
+                proxy: proxy_addr.to_string(),
+                lp_token: lp_addr.to_string(),
+            });
+        }
+
+        return update_rewards_and_execute(
+            deps,
+            env,
+            Some(vec![lp_addr.clone()]),
+            ExecuteOnReply::MoveToProxy {
+                lp_token: lp_addr,
+                proxy: proxy_addr,
+            },
+        );
+    }
+    pool_info.reward_proxies = vec![RewardProxyInfo::new(proxy_addr.clone())];
+
+    let res: BalanceResponse = deps.querier.query_wasm_smart(
+        lp_addr.clone(),
+        &cw20::Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+
+    let messages = if !res.balance.is_zero() {
+        vec![WasmMsg::Execute {
+            contract_addr: lp_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: proxy_addr.to_string(),
+                msg: to_binary(&ProxyCw20HookMsg::Deposit {})?,
+                amount: res.balance,
+            })?,
+            funds: vec![],
+        }]
+    } else {
+        vec![]
+    };
+
+    POOL_INFO.save(deps.storage, &lp_addr, &pool_info, env.block.height)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![attr("action", "move_to_proxy"), attr("proxy", proxy)]))
+}
+
+/// ## Description
+/// Moves a generator from the reward proxy staking its LP tokens to a new one after the generator's rewards were updated.
+/// The undistributed proxy rewards (including orphan rewards) are sent to the new proxy, and the staked LP tokens
+/// are withdrawn from the current proxy and deposited in the new one. Returns a [`ContractError`] on failure,
+/// otherwise returns a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token whose generator changes its proxy.
+///
+/// * **proxy** is an object of type [`Addr`]. This is the new reward proxy contract.
+fn migrate_proxy(
+    mut deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    proxy: Addr,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    let old_proxy = match pool.staking_proxy() {
+        Some(old_proxy) => old_proxy.clone(),
+        None => {
+            return Err(ContractError::PoolDoesNotHaveAdditionalRewards {
+                lp_token: lp_token.to_string(),
+            })
+        }
+    };
+
+    accumulate_rewards_per_share(deps.branch(), &env, &lp_token, &mut pool, &cfg, None)?;
+
+    let lp_amount: Uint128 = deps
+        .querier
+        .query_wasm_smart(&old_proxy, &ProxyQueryMsg::Deposit {})?;
+
+    // The messages run in order, so the LP tokens are back in the Generator before they're sent
+    let mut messages = vec![];
+    let proxy_reward_balance = pool.reward_proxies[0].reward_balance_before_update;
+    if !proxy_reward_balance.is_zero() {
+        messages.push(WasmMsg::Execute {
+            contract_addr: old_proxy.to_string(),
+            msg: to_binary(&ProxyExecuteMsg::SendRewards {
+                account: proxy.to_string(),
+                amount: proxy_reward_balance,
+            })?,
+            funds: vec![],
+        });
+    }
+    if !lp_amount.is_zero() {
+        messages.push(WasmMsg::Execute {
+            contract_addr: old_proxy.to_string(),
+            msg: to_binary(&ProxyExecuteMsg::Withdraw {
+                account: env.contract.address.to_string(),
+                amount: lp_amount,
+            })?,
+            funds: vec![],
+        });
+        messages.push(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: proxy.to_string(),
+                msg: to_binary(&ProxyCw20HookMsg::Deposit {})?,
+                amount: lp_amount,
+            })?,
+            funds: vec![],
+        });
+    }
+
+    pool.reward_proxies[0].proxy = proxy.clone();
+    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "move_to_proxy"),
+        attr("lp_token", lp_token),
+        attr("previous_proxy", old_proxy),
+        attr("proxy", proxy),
+    ]))
+}
+
+/// ## Description
+/// Adds a reward proxy that pays extra rewards to the stakers of a specific generator without holding their
+/// LP tokens. Stakers accrue its rewards from now on. Returns a [`ContractError`] on failure, otherwise returns
+/// a [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the generator.
+///
+/// * **proxy** is an object of type [`String`]. This is the reward proxy contract to add.
+///
+/// ##Executor
+/// Only the owner can execute this.
+fn add_reward_proxy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    proxy: String,
+) -> Result<Response, ContractError> {
+    let lp_addr = addr_validate_to_lower(deps.api, &lp_token)?;
+    let proxy_addr = addr_validate_to_lower(deps.api, &proxy)?;
+
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !cfg.allowed_reward_proxies.contains(&proxy_addr) {
+        return Err(ContractError::RewardProxyNotAllowed {
+            proxy: proxy_addr.to_string(),
+        });
     }
 
-    if POOL_INFO.may_load(deps.storage, &lp_addr)?.is_none() {
-        let factory_cfg: FactoryConfigResponse = deps
-            .querier
-            .query_wasm_smart(cfg.factory.clone(), &FactoryQueryMsg::Config {})?;
-
-        create_pool(deps.branch(), &env, &lp_addr, &cfg, &factory_cfg)?;
+    let mut pool_info = load_pool(deps.storage, &lp_addr)?;
+    if pool_info.staking_proxy().is_none() {
+        return Err(ContractError::NoStakingRewardProxy {
+            lp_token: lp_addr.to_string(),
+        });
     }
 
-    let mut pool_info = POOL_INFO.load(deps.storage, &lp_addr.clone())?;
-    if pool_info.reward_proxy.is_some() {
-        return Err(ContractError::PoolAlreadyHasRewardProxyContract {});
+    if pool_info
+        .reward_proxies
+        .iter()
+        .any(|reward_proxy| reward_proxy.proxy == proxy_addr)
+    {
+        return Err(ContractError::PoolAlreadyHasRewardProxyContract {
+            lp_token: lp_addr.to_string(),
+            proxy: proxy_addr.to_string(),
+        });
     }
-    pool_info.reward_proxy = Some(proxy_addr);
 
-    let res: BalanceResponse = deps.querier.query_wasm_smart(
-        lp_addr.clone(),
-        &cw20::Cw20QueryMsg::Balance {
-            address: env.contract.address.to_string(),
-        },
-    )?;
+    if pool_info.reward_proxies.len() >= MAX_REWARD_PROXIES {
+        return Err(ContractError::TooManyRewardProxies {
+            max: MAX_REWARD_PROXIES,
+        });
+    }
 
-    let messages = if !res.balance.is_zero() {
-        vec![WasmMsg::Execute {
-            contract_addr: lp_addr.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Send {
-                contract: pool_info.reward_proxy.clone().unwrap().to_string(),
-                msg: to_binary(&ProxyCw20HookMsg::Deposit {})?,
-                amount: res.balance,
-            })?,
-            funds: vec![],
-        }]
-    } else {
-        vec![]
-    };
+    let proxy_cfg: ProxyConfigResponse = deps
+        .querier
+        .query_wasm_smart(&proxy_addr, &ProxyQueryMsg::Config {})?;
+    if proxy_cfg.generator_contract_addr != env.contract.address.as_str()
+        || proxy_cfg.lp_token_addr != lp_addr.as_str()
+    {
+        return Err(ContractError::RewardProxyMismatch {
+            proxy: proxy_addr.to_string(),
+            lp_token: lp_addr.to_string(),
+        });
+    }
 
-    POOL_INFO.save(deps.storage, &lp_addr, &pool_info)?;
+    pool_info
+        .reward_proxies
+        .push(RewardProxyInfo::new(proxy_addr.clone()));
+    POOL_INFO.save(deps.storage, &lp_addr, &pool_info, env.block.height)?;
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attributes(vec![attr("action", "move_to_proxy"), attr("proxy", proxy)]))
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_reward_proxy"),
+        attr("lp_token", lp_addr),
+        attr("proxy", proxy_addr),
+    ]))
 }
 
 /// Add or remove proxy contracts to and from the proxy contract whitelist. Returns a [`ContractError`] on failure.
@@ -1488,9 +3522,7 @@ fn update_allowed_proxies(
     remove: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
     if add.is_none() && remove.is_none() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Need to provide add or remove parameters",
-        )));
+        return Err(ContractError::EmptyListUpdate {});
     }
 
     let mut cfg = CONFIG.load(deps.storage)?;
@@ -1507,10 +3539,8 @@ fn update_allowed_proxies(
                 .allowed_reward_proxies
                 .iter()
                 .position(|x| *x.as_str() == remove_proxy.as_str().to_lowercase())
-                .ok_or_else(|| {
-                    StdError::generic_err(
-                        "Can't remove proxy contract. It is not found in allowed list.",
-                    )
+                .ok_or_else(|| ContractError::RewardProxyNotFound {
+                    proxy: remove_proxy.clone(),
                 })?;
             cfg.allowed_reward_proxies.remove(index);
         }
@@ -1530,6 +3560,66 @@ fn update_allowed_proxies(
     Ok(Response::default().add_attribute("action", "update_allowed_proxies"))
 }
 
+/// Add or remove LP tokens that can be set up in generators without a pair registered in the factory.
+/// LP tokens whose generators are active or scheduled can't be removed. Returns a [`ContractError`] on failure.
+fn update_external_lp_tokens(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    if add.is_none() && remove.is_none() {
+        return Err(ContractError::EmptyListUpdate {});
+    }
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Remove LP tokens
+    if let Some(lp_tokens) = remove {
+        let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+
+        for lp_token in lp_tokens {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            let index = cfg
+                .external_lp_tokens
+                .iter()
+                .position(|x| *x == lp_token)
+                .ok_or_else(|| ContractError::ExternalLpTokenNotFound {
+                    lp_token: lp_token.to_string(),
+                })?;
+
+            // Active and scheduled generators are checked against the factory once they aren't external
+            if cfg.active_pools.iter().any(|(pool, _)| *pool == lp_token)
+                || scheduled_sets.iter().any(|set| set.lp_token == lp_token)
+            {
+                return Err(ContractError::ExternalLpTokenInUse {
+                    lp_token: lp_token.to_string(),
+                });
+            }
+
+            cfg.external_lp_tokens.remove(index);
+        }
+    }
+
+    // Add LP tokens
+    if let Some(lp_tokens) = add {
+        for lp_token in lp_tokens {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            if !cfg.external_lp_tokens.contains(&lp_token) {
+                cfg.external_lp_tokens.push(lp_token);
+            }
+        }
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+    Ok(Response::default().add_attribute("action", "update_external_lp_tokens"))
+}
+
 /// ## Description
 /// Exposes all the queries available in the contract.
 /// ## Params
@@ -1544,9 +3634,15 @@ fn update_allowed_proxies(
 ///
 /// * **QueryMsg::Deposit { lp_token, user }** Returns the amount of LP tokens staked by a user in a specific generator.
 ///
+/// * **QueryMsg::UserInfo { lp_token, user }** Returns the staking information of a user in a specific generator
+/// using a [`UserInfoResponse`] object.
+///
 /// * **QueryMsg::PendingToken { lp_token, user }** Returns the amount of pending rewards a user earned using
 /// a [`PendingTokenResponse`] object.
 ///
+/// * **QueryMsg::PendingTokenList { user, lp_tokens }** Returns the amount of pending rewards a user earned in
+/// multiple generators using a vector of [`PoolPendingTokenResponse`] objects.
+///
 /// * **QueryMsg::Config {}** Returns the Generator contract configuration using a [`ConfigResponse`] object.
 ///
 /// * **QueryMsg::RewardInfo { lp_token }** Returns reward information about a specific generator
@@ -1554,10 +3650,44 @@ fn update_allowed_proxies(
 ///
 /// * **QueryMsg::OrphanProxyRewards { lp_token }** Returns the amount of orphaned proxy rewards for a specific generator.
 ///
+/// * **QueryMsg::OrphanStake { lp_token }** Returns the orphan stake of a specific generator.
+///
+/// * **QueryMsg::UnpaidAstro { user }** Returns the ASTRO rewards that couldn't be claimed from vesting for a user.
+///
 /// * **QueryMsg::PoolInfo { lp_token }** Returns general information about a generator using a [`PoolInfoResponse`] object.
 ///
 /// * **QueryMsg::SimulateFutureReward { lp_token, future_block }** Returns the amount of token rewards a generator will
 /// distribute up to a future block.
+///
+/// * **QueryMsg::PoolAprInputs { lp_token }** Returns everything needed to compute a generator's APR using a
+/// [`PoolAprInputsResponse`] object.
+///
+/// * **QueryMsg::DepositAt { lp_token, user, block }** Returns the amount of LP tokens a user had staked in a
+/// generator at the beginning of a past block.
+///
+/// * **QueryMsg::PoolInfoAt { lp_token, block }** Returns the stored state of a generator at the beginning of a
+/// past block using a [`PoolInfo`] object.
+///
+/// * **QueryMsg::EmissionRate {}** Returns the amount of ASTRO distributed per block right now using an
+/// [`EmissionRateResponse`] object.
+///
+/// * **QueryMsg::OperatorApproved { user, lp_token, operator }** Returns whether an operator can claim a user's
+/// rewards from a specific generator.
+///
+/// * **QueryMsg::AltReward { lp_token }** Returns a generator's alternative base reward settings using an
+/// [`Option<AltRewardInfo>`] object.
+///
+/// * **QueryMsg::PendingAltReward { lp_token, user }** Returns the amount of alternative base rewards a staker
+/// can claim from a specific generator.
+///
+/// * **QueryMsg::ScheduledSets {}** Returns the pending allocation point changes using a vector of
+/// [`ScheduledSet`] objects.
+///
+/// * **QueryMsg::EmissionSchedule {}** Returns the emission epochs that weren't applied yet using a vector of
+/// [`EmissionEpoch`] objects.
+///
+/// * **QueryMsg::PoolList { start_after, limit }** Returns all the generators ever created using a vector of
+/// [`PoolListResponse`] objects.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
@@ -1566,14 +3696,36 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         QueryMsg::Deposit { lp_token, user } => {
             Ok(to_binary(&query_deposit(deps, lp_token, user)?)?)
         }
+        QueryMsg::UserInfo { lp_token, user } => {
+            Ok(to_binary(&query_user_info(deps, lp_token, user)?)?)
+        }
         QueryMsg::PendingToken { lp_token, user } => {
             Ok(to_binary(&pending_token(deps, env, lp_token, user)?)?)
         }
+        QueryMsg::PendingTokenList { user, lp_tokens } => {
+            Ok(to_binary(&pending_token_list(deps, env, user, lp_tokens)?)?)
+        }
         QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
         QueryMsg::RewardInfo { lp_token } => Ok(to_binary(&query_reward_info(deps, lp_token)?)?),
         QueryMsg::OrphanProxyRewards { lp_token } => {
             Ok(to_binary(&query_orphan_proxy_rewards(deps, lp_token)?)?)
         }
+        QueryMsg::UnpaidAstro { user } => {
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            Ok(to_binary(
+                &UNPAID_ASTRO
+                    .may_load(deps.storage, &user)?
+                    .unwrap_or_default(),
+            )?)
+        }
+        QueryMsg::OrphanStake { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            Ok(to_binary(
+                &ORPHAN_STAKES
+                    .may_load(deps.storage, &lp_token)?
+                    .unwrap_or_default(),
+            )?)
+        }
         QueryMsg::PoolInfo { lp_token } => Ok(to_binary(&query_pool_info(deps, env, lp_token)?)?),
         QueryMsg::SimulateFutureReward {
             lp_token,
@@ -1594,8 +3746,171 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             start_after,
             limit,
         )?)?),
+        QueryMsg::StakedPools {
+            user,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_staked_pools(
+            deps,
+            user,
+            start_after,
+            limit,
+        )?)?),
         QueryMsg::BlockedListTokens {} => Ok(to_binary(&query_blocked_list_tokens(deps)?)?),
+        QueryMsg::PoolAprInputs { lp_token } => {
+            Ok(to_binary(&query_pool_apr_inputs(deps, env, lp_token)?)?)
+        }
+        QueryMsg::DepositAt {
+            lp_token,
+            user,
+            block,
+        } => Ok(to_binary(&query_deposit_at(
+            deps, env, lp_token, user, block,
+        )?)?),
+        QueryMsg::PoolInfoAt { lp_token, block } => {
+            Ok(to_binary(&query_pool_info_at(deps, env, lp_token, block)?)?)
+        }
+        QueryMsg::EmissionRate {} => Ok(to_binary(&query_emission_rate(deps, env)?)?),
+        QueryMsg::OperatorApproved {
+            user,
+            lp_token,
+            operator,
+        } => Ok(to_binary(&query_operator_approved(
+            deps, user, lp_token, operator,
+        )?)?),
+        QueryMsg::AltReward { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            Ok(to_binary(&load_pool(deps.storage, &lp_token)?.alt_reward)?)
+        }
+        QueryMsg::PendingAltReward { lp_token, user } => Ok(to_binary(&query_pending_alt_reward(
+            deps, env, lp_token, user,
+        )?)?),
+        QueryMsg::ScheduledSets {} => Ok(to_binary(
+            &SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::EmissionSchedule {} => Ok(to_binary(
+            &EMISSION_SCHEDULE
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::PoolList { start_after, limit } => {
+            Ok(to_binary(&query_pool_list(deps, start_after, limit)?)?)
+        }
+    }
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the stored state of all the generators ever created,
+/// ordered by LP token.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an object of type [`Option<String>`]. This is an optional field
+/// that specifies whether the function should return a list of generators starting from a
+/// specific LP token onward.
+///
+/// * **limit** is an object of type [`Option<u32>`]. This is the max amount of generators
+/// to return.
+fn query_pool_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<PoolListResponse>, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|start| addr_validate_to_lower(deps.api, &start))
+        .transpose()?
+        .map(|start| Bound::exclusive(start.to_string()));
+
+    let mut pools: Vec<PoolListResponse> = vec![];
+    for item in POOL_INFO
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let (key, pool) = item?;
+        let lp_token = addr_from_key(deps.api, key)?;
+        pools.push(PoolListResponse {
+            alloc_point: get_alloc_point(&cfg.active_pools, &lp_token),
+            lp_token,
+            last_reward_block: pool.last_reward_block.u64(),
+            reward_proxies: pool.reward_proxies,
+            has_asset_rewards: pool.has_asset_rewards,
+            accumulated_rewards_per_share: pool.accumulated_rewards_per_share,
+            accumulated_alt_rewards_per_share: pool.accumulated_alt_rewards_per_share,
+        });
+    }
+
+    Ok(pools)
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the amount of alternative base rewards a staker
+/// can claim from a specific generator.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token staked by the user.
+///
+/// * **user** is an object of type [`String`]. This is the user whose pending rewards we calculate.
+fn query_pending_alt_reward(
+    deps: Deps,
+    env: Env,
+    lp_token: String,
+    user: String,
+) -> Result<Uint128, ContractError> {
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let alt_reward = match &pool.alt_reward {
+        Some(alt_reward) => alt_reward,
+        None => return Ok(Uint128::zero()),
+    };
+    let user_info = user_info()
+        .may_load(deps.storage, (&lp_token, &user))?
+        .unwrap_or_default();
+
+    let lp_supply = query_lp_supply(deps, &env, &lp_token, &pool)?;
+
+    let mut alt_rewards = Uint128::zero();
+    if env.block.height > pool.last_reward_block.u64() && !lp_supply.is_zero() {
+        alt_rewards = calculate_alt_rewards(&env, alt_reward, pool.last_reward_block.u64())?;
     }
+
+    Ok(calculate_pending_rewards(
+        pool.accumulated_alt_rewards_per_share,
+        alt_rewards,
+        lp_supply,
+        user_info.amount,
+        user_info.reward_debt_alt,
+    )?)
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns whether an operator can claim a user's rewards
+/// from a specific generator.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **user** is an object of type [`String`]. This is the user whose rewards are claimed.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the generator.
+///
+/// * **operator** is an object of type [`String`]. This is the operator address.
+fn query_operator_approved(
+    deps: Deps,
+    user: String,
+    lp_token: String,
+    operator: String,
+) -> Result<bool, ContractError> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+
+    Ok(OPERATORS.has(deps.storage, (&user, &lp_token, &operator)))
 }
 
 /// ## Description
@@ -1610,9 +3925,7 @@ fn query_blocked_list_tokens(deps: Deps) -> Result<Vec<AssetInfo>, ContractError
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 pub fn pool_length(deps: Deps) -> Result<PoolLengthResponse, ContractError> {
-    let length = POOL_INFO
-        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .count();
+    let length = POOL_LENGTH.load(deps.storage)? as usize;
     Ok(PoolLengthResponse { length })
 }
 
@@ -1627,21 +3940,146 @@ pub fn active_pool_length(deps: Deps) -> Result<PoolLengthResponse, ContractErro
 }
 
 /// ## Description
-/// Returns a [`ContractError`] on failure, otherwise returns the amount of LP tokens a user staked in a specific generator.
+/// Returns a [`ContractError`] on failure, otherwise returns the amount of LP tokens a user staked in a specific generator.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token for which we query the user's balance for.
+///
+/// * **user** is an object of type [`String`]. This is the user whose balance we query.
+pub fn query_deposit(deps: Deps, lp_token: String, user: String) -> Result<Uint128, ContractError> {
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    let user_info = user_info()
+        .load(deps.storage, (&lp_token, &user))
+        .unwrap_or_default();
+    Ok(user_info.amount)
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the staking information of a user in a specific generator
+/// using a [`UserInfoResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token for which we query the user's information.
+///
+/// * **user** is an object of type [`String`]. This is the user whose information we query.
+pub fn query_user_info(
+    deps: Deps,
+    lp_token: String,
+    user: String,
+) -> Result<UserInfoResponse, ContractError> {
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    let user_info = user_info()
+        .may_load(deps.storage, (&lp_token, &user))?
+        .unwrap_or_default();
+    let reward_proxies = POOL_INFO
+        .may_load(deps.storage, &lp_token)?
+        .map(|pool| pool.reward_proxies)
+        .unwrap_or_default();
+    Ok(UserInfoResponse {
+        amount: user_info.amount,
+        reward_debt: user_info.reward_debt,
+        reward_debt_proxy: reward_proxies
+            .into_iter()
+            .enumerate()
+            .map(|(index, reward_proxy)| (reward_proxy.proxy, user_info.proxy_reward_debt(index)))
+            .collect(),
+        boost_bonus: user_info.boost_bonus,
+    })
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the amount of LP tokens a user had staked in a generator
+/// at the beginning of a past block.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token for which we query the user's balance for.
+///
+/// * **user** is an object of type [`String`]. This is the user whose balance we query.
+///
+/// * **block** is an object of type [`u64`]. This is the block at which we read the balance.
+pub fn query_deposit_at(
+    deps: Deps,
+    env: Env,
+    lp_token: String,
+    user: String,
+    block: u64,
+) -> Result<Uint128, ContractError> {
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    assert_snapshot_available(deps, &env, block)?;
+
+    let user_info = user_info()
+        .may_load_at_height(deps.storage, (&lp_token, &user), block)?
+        .unwrap_or_default();
+    Ok(user_info.amount)
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the stored state of a generator at the beginning of a
+/// past block in a [`PoolInfo`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator we query.
+///
+/// * **block** is an object of type [`u64`]. This is the block at which we read the generator state.
+fn query_pool_info_at(
+    deps: Deps,
+    env: Env,
+    lp_token: String,
+    block: u64,
+) -> Result<PoolInfo, ContractError> {
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    assert_snapshot_available(deps, &env, block)?;
+
+    POOL_INFO
+        .may_load_at_height(deps.storage, &lp_token, block)?
+        .ok_or(ContractError::PoolNotFound {
+            lp_token: lp_token.to_string(),
+        })
+}
+
+/// ## Description
+/// Returns a [`ContractError`] if the historical state at the specified block is not available.
+/// Snapshots start with the block in which the contract was instantiated or upgraded to track them,
+/// and can't be read for blocks that haven't started yet.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **block** is an object of type [`u64`]. This is the block at which we want to read the state.
+fn assert_snapshot_available(deps: Deps, env: &Env, block: u64) -> Result<(), ContractError> {
+    let start_block = SNAPSHOTS_START_BLOCK.load(deps.storage)?;
+    if block < start_block || block > env.block.height {
+        return Err(ContractError::SnapshotNotAvailable { block });
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns a [`ContractError`] if the stakes stored before V1.3.0 are not migrated yet. Until then stakes
+/// may be missing from the depositor index of [`user_info`] or be stored under invalid addresses.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
-///
-/// * **lp_token** is an object of type [`String`]. This is the LP token for which we query the user's balance for.
-///
-/// * **user** is an object of type [`String`]. This is the user whose balance we query.
-pub fn query_deposit(deps: Deps, lp_token: String, user: String) -> Result<Uint128, ContractError> {
-    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
-    let user = addr_validate_to_lower(deps.api, &user)?;
+fn assert_users_migrated(deps: Deps) -> Result<(), ContractError> {
+    if USERS_MIGRATION.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::UsersMigrationPending {});
+    }
 
-    let user_info = USER_INFO
-        .load(deps.storage, (&lp_token, &user))
-        .unwrap_or_default();
-    Ok(user_info.amount)
+    Ok(())
 }
 
 /// ## Description
@@ -1667,62 +4105,139 @@ pub fn pending_token(
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
     let user = addr_validate_to_lower(deps.api, &user)?;
 
-    let pool = POOL_INFO.load(deps.storage, &lp_token)?;
-    let user_info = USER_INFO
-        .load(deps.storage, (&lp_token, &user))
+    compute_pending_token(deps, &env, &cfg, &lp_token, &user)
+}
+
+/// ## Description
+/// Calculates and returns the pending token rewards for a specific user in multiple generators.
+/// If `lp_tokens` is not specified, the rewards are calculated for all the generators in which the user has funds.
+/// Returns a [`ContractError`] on failure, otherwise returns a vector of [`PoolPendingTokenResponse`] objects.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **user** is an object of type [`String`]. This is the user for which we fetch the amount of pending token rewards.
+///
+/// * **lp_tokens** is an [`Option`] of type [`Vec<String>`]. These are the LP tokens whose generators we query.
+pub fn pending_token_list(
+    deps: Deps,
+    env: Env,
+    user: String,
+    lp_tokens: Option<Vec<String>>,
+) -> Result<Vec<PoolPendingTokenResponse>, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let user = addr_validate_to_lower(deps.api, &user)?;
+
+    let lp_tokens = match lp_tokens {
+        Some(lp_tokens) => lp_tokens
+            .iter()
+            .map(|lp_token| addr_validate_to_lower(deps.api, lp_token))
+            .collect::<StdResult<Vec<_>>>()?,
+        None => {
+            assert_users_migrated(deps)?;
+            user_info()
+                .idx
+                .user
+                .prefix(user.as_bytes().to_vec())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .map(|key| {
+                    let (lp_token, _) = split_user_info_key(&key)?;
+                    addr_from_key(deps.api, lp_token.to_vec())
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        }
+    };
+
+    lp_tokens
+        .into_iter()
+        .map(|lp_token| {
+            let res = compute_pending_token(deps, &env, &cfg, &lp_token, &user)?;
+            Ok(PoolPendingTokenResponse {
+                lp_token: lp_token.to_string(),
+                pending: res.pending,
+                pending_on_proxy: res.pending_on_proxy,
+            })
+        })
+        .collect()
+}
+
+/// ## Description
+/// Calculates and returns the pending token rewards for a specific user in a [`PendingTokenResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **cfg** is an object of type [`Config`]. This is the Generator contract configuration.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token staked by the user whose pending rewards we calculate.
+///
+/// * **user** is an object of type [`Addr`]. This is the user for which we fetch the amount of pending token rewards.
+fn compute_pending_token(
+    deps: Deps,
+    env: &Env,
+    cfg: &Config,
+    lp_token: &Addr,
+    user: &Addr,
+) -> Result<PendingTokenResponse, ContractError> {
+    let pool = load_pool(deps.storage, lp_token)?;
+    let user_info = user_info()
+        .load(deps.storage, (lp_token, user))
         .unwrap_or_default();
 
     let mut pending_on_proxy = None;
 
-    let lp_supply: Uint128;
+    let lp_supply = query_lp_supply(deps, env, lp_token, &pool)?;
 
-    match &pool.reward_proxy {
-        Some(proxy) => {
-            lp_supply = deps
+    if !pool.reward_proxies.is_empty() && !lp_supply.is_zero() {
+        let mut pending_proxy_rewards = vec![];
+        for (index, reward_proxy) in pool.reward_proxies.iter().enumerate() {
+            let res: Option<Uint128> = deps
                 .querier
-                .query_wasm_smart(proxy, &ProxyQueryMsg::Deposit {})?;
-
-            if !lp_supply.is_zero() {
-                let res: Option<Uint128> = deps
-                    .querier
-                    .query_wasm_smart(proxy, &ProxyQueryMsg::PendingToken {})?;
-
-                let mut acc_per_share_on_proxy = pool.accumulated_proxy_rewards_per_share;
-                if let Some(token_rewards) = res {
-                    let share = Decimal::from_ratio(token_rewards, lp_supply);
-                    acc_per_share_on_proxy = pool
-                        .accumulated_proxy_rewards_per_share
-                        .checked_add(share)?;
-                }
-
-                pending_on_proxy = Some(
-                    acc_per_share_on_proxy
-                        .checked_mul(user_info.amount)?
-                        .checked_sub(user_info.reward_debt_proxy)?,
-                );
-            }
-        }
-        None => {
-            lp_supply = query_token_balance(
-                &deps.querier,
-                lp_token.clone(),
-                env.contract.address.clone(),
-            )?;
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::PendingToken {})?;
+            let reward_token: Addr = deps
+                .querier
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::RewardInfo {})?;
+
+            pending_proxy_rewards.push(Asset {
+                info: token_asset_info(reward_token),
+                amount: calculate_pending_rewards(
+                    reward_proxy.accumulated_rewards_per_share,
+                    res.unwrap_or_default(),
+                    lp_supply,
+                    user_info.amount,
+                    user_info.proxy_reward_debt(index),
+                )?,
+            });
         }
+
+        pending_on_proxy = Some(pending_proxy_rewards);
     }
 
-    let mut acc_per_share = pool.accumulated_rewards_per_share;
+    let mut token_rewards = Uint128::zero();
     if env.block.height > pool.last_reward_block.u64() && !lp_supply.is_zero() {
-        let alloc_point = get_alloc_point(&cfg.active_pools, &lp_token);
-
-        let token_rewards = calculate_rewards(&env, &pool, &alloc_point, &cfg)?;
-        let share = Decimal::from_ratio(token_rewards, lp_supply);
-        acc_per_share = pool.accumulated_rewards_per_share.checked_add(share)?;
+        let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+        let emission_schedule = EMISSION_SCHEDULE
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        token_rewards = calculate_rewards(
+            env,
+            lp_token,
+            &pool,
+            cfg,
+            &scheduled_sets,
+            &emission_schedule,
+        )?;
     }
 
-    let pending = acc_per_share
-        .checked_mul(user_info.amount)?
-        .checked_sub(user_info.reward_debt)?;
+    let pending = calculate_pending_rewards(
+        pool.accumulated_rewards_per_share,
+        token_rewards,
+        lp_supply.checked_add(pool.total_boost_bonus)?,
+        user_info.boosted_amount()?,
+        user_info.reward_debt,
+    )?;
 
     Ok(PendingTokenResponse {
         pending,
@@ -1751,6 +4266,13 @@ fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
         generator_controller: config.generator_controller,
         active_pools: config.active_pools,
         blocked_list_tokens: config.blocked_list_tokens,
+        keeper_incentive: config.keeper_incentive,
+        keeper_tip_budget: KEEPER_TIP_BUDGET
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        boost_provider: config.boost_provider,
+        voting_escrow: config.voting_escrow,
+        external_lp_tokens: config.external_lp_tokens,
     })
 }
 
@@ -1766,38 +4288,47 @@ fn query_reward_info(deps: Deps, lp_token: String) -> Result<RewardInfoResponse,
 
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
 
-    let pool = POOL_INFO.load(deps.storage, &lp_token)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
 
-    let proxy_reward_token = match pool.reward_proxy {
-        Some(proxy) => {
-            let res: Addr = deps
-                .querier
-                .query_wasm_smart(&proxy, &ProxyQueryMsg::RewardInfo {})?;
-            Some(res)
-        }
-        None => None,
-    };
+    let proxy_reward_tokens = pool
+        .reward_proxies
+        .iter()
+        .map(|reward_proxy| {
+            deps.querier
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::RewardInfo {})
+        })
+        .collect::<StdResult<Vec<Addr>>>()?;
 
     Ok(RewardInfoResponse {
         base_reward_token: config.astro_token,
-        proxy_reward_token,
+        proxy_reward_tokens,
     })
 }
 
-/// Returns a [`ContractError`] on failure, otherwise returns the amount of orphaned proxy rewards for a specific generator.
+/// Returns a [`ContractError`] on failure, otherwise returns the amount of orphaned rewards of every reward proxy
+/// of a specific generator.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 ///
 /// * **lp_token** is an object of type [`String`]. This is the LP token whose generator we query for orphaned rewards.
-fn query_orphan_proxy_rewards(deps: Deps, lp_token: String) -> Result<Uint128, ContractError> {
+fn query_orphan_proxy_rewards(
+    deps: Deps,
+    lp_token: String,
+) -> Result<Vec<(Addr, Uint128)>, ContractError> {
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
 
-    let pool = POOL_INFO.load(deps.storage, &lp_token)?;
-    if pool.reward_proxy.is_none() {
-        return Err(ContractError::PoolDoesNotHaveAdditionalRewards {});
+    let pool = load_pool(deps.storage, &lp_token)?;
+    if pool.reward_proxies.is_empty() {
+        return Err(ContractError::PoolDoesNotHaveAdditionalRewards {
+            lp_token: lp_token.to_string(),
+        });
     }
 
-    Ok(pool.orphan_proxy_rewards)
+    Ok(pool
+        .reward_proxies
+        .into_iter()
+        .map(|reward_proxy| (reward_proxy.proxy, reward_proxy.orphan_rewards))
+        .collect())
 }
 
 /// ## Description
@@ -1817,53 +4348,50 @@ fn query_pool_info(
     let config = CONFIG.load(deps.storage)?;
 
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
-    let pool = POOL_INFO.load(deps.storage, &lp_token)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
 
-    let lp_supply: Uint128;
-    let mut pending_on_proxy = None;
+    let lp_supply = query_lp_supply(deps, &env, &lp_token, &pool)?;
+    let mut pending_proxy_rewards = vec![];
     let mut pending_astro_rewards = Uint128::zero();
 
-    // If proxy rewards are live for this LP token, calculate its pending proxy rewards
-    match &pool.reward_proxy {
-        Some(proxy) => {
-            lp_supply = deps
-                .querier
-                .query_wasm_smart(proxy, &ProxyQueryMsg::Deposit {})?;
-
-            // If LP tokens are staked via a proxy contract, fetch current pending proxy rewards
-            if !lp_supply.is_zero() {
-                let res: Uint128 = deps
-                    .querier
-                    .query_wasm_smart(proxy, &ProxyQueryMsg::PendingToken {})?;
-
-                if !res.is_zero() {
-                    pending_on_proxy = Some(res);
-                }
-            }
-        }
-        None => {
-            lp_supply = query_token_balance(
-                &deps.querier,
-                lp_token.clone(),
-                env.contract.address.clone(),
-            )?;
-        }
+    // If LP tokens are staked, fetch the current pending rewards of every reward proxy
+    for reward_proxy in &pool.reward_proxies {
+        let pending = if !lp_supply.is_zero() {
+            deps.querier
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::PendingToken {})?
+        } else {
+            Uint128::zero()
+        };
+        pending_proxy_rewards.push((reward_proxy.proxy.clone(), pending));
     }
 
     let alloc_point = get_alloc_point(&config.active_pools, &lp_token);
 
+    let emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
     // Calculate pending ASTRO rewards
     if env.block.height > pool.last_reward_block.u64() && !lp_supply.is_zero() {
-        pending_astro_rewards = calculate_rewards(&env, &pool, &alloc_point, &config)?;
+        let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+        pending_astro_rewards = calculate_rewards(
+            &env,
+            &lp_token,
+            &pool,
+            &config,
+            &scheduled_sets,
+            &emission_schedule,
+        )?;
     }
 
     // Calculate ASTRO tokens being distributed per block to this LP token pool
     let astro_tokens_per_block: Uint128;
-    astro_tokens_per_block = config
-        .tokens_per_block
-        .checked_mul(alloc_point)?
-        .checked_div(config.total_alloc_point)
-        .unwrap_or_else(|_| Uint128::zero());
+    astro_tokens_per_block = calculate_generator_rewards(
+        Uint128::from(1u8),
+        tokens_per_block_at(&config, &emission_schedule, env.block.height),
+        alloc_point,
+        config.total_alloc_point,
+    )?;
 
     Ok(PoolInfoResponse {
         alloc_point,
@@ -1872,18 +4400,101 @@ fn query_pool_info(
         current_block: env.block.height,
         accumulated_rewards_per_share: pool.accumulated_rewards_per_share,
         pending_astro_rewards,
-        reward_proxy: pool.reward_proxy,
-        pending_proxy_rewards: pending_on_proxy,
-        accumulated_proxy_rewards_per_share: pool.accumulated_proxy_rewards_per_share,
-        proxy_reward_balance_before_update: pool.proxy_reward_balance_before_update,
-        orphan_proxy_rewards: pool.orphan_proxy_rewards,
+        reward_proxies: pool.reward_proxies,
+        pending_proxy_rewards,
         lp_supply,
     })
 }
 
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the generator's emissions together with the
+/// pair's reserves and fee rate using a [`PoolAprInputsResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token whose generator we query.
+fn query_pool_apr_inputs(
+    deps: Deps,
+    env: Env,
+    lp_token: String,
+) -> Result<PoolAprInputsResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool_info = query_pool_info(deps, env.clone(), lp_token.clone())?;
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let pair_info =
+        query_lp_pair(deps, &config.external_lp_tokens, &lp_token)?.ok_or_else(|| {
+            ContractError::PairNotRegistered {
+                lp_token: lp_token.to_string(),
+            }
+        })?;
+    let pair_pool: PairPoolResponse = deps
+        .querier
+        .query_wasm_smart(&pair_info.contract_addr, &PairQueryMsg::Pool {})?;
+    let fee_info = query_fee_info(&deps.querier, config.factory, pair_info.pair_type)?;
+
+    let proxy_rewards_per_block = pool
+        .reward_proxies
+        .iter()
+        .map(|reward_proxy| {
+            let reward_token: Addr = deps
+                .querier
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::RewardInfo {})?;
+            Ok((reward_token, reward_proxy.reward_per_block))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolAprInputsResponse {
+        lp_token,
+        pair: pair_info.contract_addr,
+        astro_tokens_per_block: pool_info.astro_tokens_per_block,
+        proxy_rewards_per_block,
+        lp_staked: pool_info.lp_supply,
+        lp_total_supply: pair_pool.total_share,
+        assets: pair_pool.assets,
+        total_fee_rate: fee_info.total_fee_rate,
+        current_block: env.block.height,
+    })
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns the amount of ASTRO distributed per block at the
+/// current block using an [`EmissionRateResponse`] object. Nothing is distributed before the start block
+/// or when no generator is active. Emission epochs that started but weren't applied yet are taken into account.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+fn query_emission_rate(deps: Deps, env: Env) -> Result<EmissionRateResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let configured_tokens_per_block =
+        tokens_per_block_at(&config, &emission_schedule, env.block.height);
+
+    let tokens_per_block =
+        if env.block.height < config.start_block.u64() || config.total_alloc_point.is_zero() {
+            Uint128::zero()
+        } else {
+            configured_tokens_per_block
+        };
+
+    Ok(EmissionRateResponse {
+        tokens_per_block,
+        configured_tokens_per_block,
+        start_block: config.start_block,
+        current_block: env.block.height,
+    })
+}
+
 /// ## Description
 /// Returns a [`ContractError`] on failure, otherwise returns the total amount of ASTRO tokens distributed for
-/// a specific generator up to a certain block in the future.
+/// a specific generator up to a certain block in the future. Scheduled allocation point changes and emission
+/// epochs are taken into account.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 ///
@@ -1899,16 +4510,19 @@ pub fn query_simulate_future_reward(
     let cfg = CONFIG.load(deps.storage)?;
 
     let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
-    let alloc_point = get_alloc_point(&cfg.active_pools, &lp_token);
-    let n_blocks = Uint128::from(future_block)
-        .checked_sub(env.block.height.into())
-        .unwrap_or_else(|_| Uint128::zero());
+    let scheduled_sets = SCHEDULED_SETS.may_load(deps.storage)?.unwrap_or_default();
+    let emission_schedule = EMISSION_SCHEDULE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
 
-    let simulated_reward = n_blocks
-        .checked_mul(cfg.tokens_per_block)?
-        .checked_mul(alloc_point)?
-        .checked_div(cfg.total_alloc_point)
-        .unwrap_or_else(|_| Uint128::zero());
+    let simulated_reward = calculate_rewards_between(
+        &lp_token,
+        env.block.height,
+        future_block.max(env.block.height),
+        &cfg,
+        &scheduled_sets,
+        &emission_schedule,
+    )?;
 
     Ok(simulated_reward)
 }
@@ -1934,63 +4548,239 @@ pub fn query_list_of_stakers(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> Result<Vec<StakerResponse>, ContractError> {
+    assert_users_migrated(deps)?;
+
     let lp_addr = addr_validate_to_lower(deps.api, lp_token.as_str())?;
     let mut active_stakers: Vec<StakerResponse> = vec![];
 
-    if POOL_INFO.has(deps.storage, &lp_addr) {
+    if POOL_INFO.may_load(deps.storage, &lp_addr)?.is_some() {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
         let start = start_after
-            .map(|start| start.joined_key())
-            .map(Bound::Exclusive);
+            .map(|start| addr_validate_to_lower(deps.api, &start))
+            .transpose()?
+            .map(|start| Bound::exclusive(start.to_string()));
 
-        active_stakers = USER_INFO
+        // Stakes are removed once they are fully withdrawn, so every entry is an active staker
+        for item in user_info()
             .prefix(&lp_addr)
             .range(deps.storage, start, None, Order::Ascending)
-            .filter_map(|stakers| {
-                stakers
-                    .ok()
-                    .map(|staker| StakerResponse {
-                        account: String::from_utf8(staker.0).unwrap(),
-                        amount: staker.1.amount,
-                    })
-                    .filter(|active_staker| !active_staker.amount.is_zero())
-            })
             .take(limit)
-            .collect();
+        {
+            let (key, user) = item?;
+            active_stakers.push(StakerResponse {
+                account: addr_from_key(deps.api, key)?.to_string(),
+                amount: user.amount,
+            });
+        }
     }
 
     Ok(active_stakers)
 }
 
+/// ## Description
+/// Returns a [`ContractError`] on failure, otherwise returns a list of generators in which
+/// a specific account currently has funds.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **user** is an object of type [`String`]. This is the account whose stakes we query.
+///
+/// * **start_after** is an object of type [`Option<String>`]. This is an optional field
+/// that specifies whether the function should return a list of generators starting from a
+/// specific LP token onward.
+///
+/// * **limit** is an object of type [`Option<u32>`]. This is the max amount of generators
+/// to return.
+pub fn query_staked_pools(
+    deps: Deps,
+    user: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<StakedPoolResponse>, ContractError> {
+    assert_users_migrated(deps)?;
+
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|start| addr_validate_to_lower(deps.api, &start))
+        .transpose()?
+        .map(|start| Bound::exclusive((&start, &user).joined_key()));
+
+    let mut staked_pools: Vec<StakedPoolResponse> = vec![];
+    for item in user_info()
+        .idx
+        .user
+        .prefix(user.as_bytes().to_vec())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let (key, stake) = item?;
+        let (lp_token, _) = split_user_info_key(&key)?;
+        staked_pools.push(StakedPoolResponse {
+            lp_token: addr_from_key(deps.api, lp_token.to_vec())?.to_string(),
+            amount: stake.amount,
+        });
+    }
+
+    Ok(staked_pools)
+}
+
 /// ## Description
 /// Calculates and returns the amount of accrued rewards since the last reward checkpoint for a specific generator.
+/// Scheduled allocation point changes and emission epochs that are due but not applied yet split the period,
+/// so every part is rewarded at the allocations and emission rate in force at the time.
 /// ## Params
 /// * **env** is an object of type [`Env`].
 ///
-/// * **pool** is an object of type [`PoolInfo`]. This is the generator for which we calculate accrued rewards.
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
 ///
-/// * **alloc_point** is the object of type [`Uint64`].
+/// * **pool** is an object of type [`PoolInfo`]. This is the generator for which we calculate accrued rewards.
 ///
 /// * **cfg** is an object of type [`Config`]. This is the Generator contract configuration.
+///
+/// * **scheduled_sets** is a slice of type [`ScheduledSet`]. These are the pending allocation point changes.
+///
+/// * **emission_schedule** is a slice of type [`EmissionEpoch`]. These are the pending emission epochs.
 pub fn calculate_rewards(
     env: &Env,
+    lp_token: &Addr,
     pool: &PoolInfo,
-    alloc_point: &Uint128,
     cfg: &Config,
+    scheduled_sets: &[ScheduledSet],
+    emission_schedule: &[EmissionEpoch],
 ) -> StdResult<Uint128> {
-    let n_blocks = Uint128::from(env.block.height).checked_sub(pool.last_reward_block.into())?;
-
-    let r;
-    if !cfg.total_alloc_point.is_zero() {
-        r = n_blocks
-            .checked_mul(cfg.tokens_per_block)?
-            .checked_mul(*alloc_point)?
-            .checked_div(cfg.total_alloc_point)?;
-    } else {
-        r = Uint128::zero();
+    calculate_rewards_between(
+        lp_token,
+        pool.last_reward_block.u64(),
+        env.block.height,
+        cfg,
+        scheduled_sets,
+        emission_schedule,
+    )
+}
+
+/// ## Description
+/// Calculates and returns the amount of rewards a specific generator accrues between two blocks, taking into account
+/// the scheduled allocation point changes and emission epochs up to the end block.
+/// ## Params
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+///
+/// * **from** is a variable of type [`u64`]. This is the block from which rewards are calculated.
+///
+/// * **to** is a variable of type [`u64`]. This is the block up to which rewards are calculated.
+///
+/// * **cfg** is an object of type [`Config`]. This is the Generator contract configuration.
+///
+/// * **scheduled_sets** is a slice of type [`ScheduledSet`]. These are the pending allocation point changes.
+///
+/// * **emission_schedule** is a slice of type [`EmissionEpoch`]. These are the pending emission epochs.
+fn calculate_rewards_between(
+    lp_token: &Addr,
+    mut from: u64,
+    to: u64,
+    cfg: &Config,
+    scheduled_sets: &[ScheduledSet],
+    emission_schedule: &[EmissionEpoch],
+) -> StdResult<Uint128> {
+    let mut active_pools = cfg.active_pools.clone();
+    let mut total_alloc_point = cfg.total_alloc_point;
+    let mut tokens_per_block = cfg.tokens_per_block;
+    let mut rewards = Uint128::zero();
+
+    let mut scheduled_sets = scheduled_sets
+        .iter()
+        .take_while(|scheduled_set| scheduled_set.effective_block <= to)
+        .peekable();
+    let mut epochs = emission_schedule
+        .iter()
+        .take_while(|epoch| epoch.start_block <= to)
+        .peekable();
+
+    loop {
+        let next_set = scheduled_sets
+            .peek()
+            .map(|scheduled_set| scheduled_set.effective_block);
+        let next_epoch = epochs.peek().map(|epoch| epoch.start_block);
+        let block = match next_set.into_iter().chain(next_epoch).min() {
+            Some(block) => block,
+            None => break,
+        };
+
+        if block > from {
+            rewards = rewards.checked_add(calculate_generator_rewards(
+                Uint128::from(block - from),
+                tokens_per_block,
+                get_alloc_point(&active_pools, lp_token),
+                total_alloc_point,
+            )?)?;
+            from = block;
+        }
+
+        // Allocation point changes are applied before an epoch starting at the same block
+        if next_set == Some(block) {
+            if let Some(scheduled_set) = scheduled_sets.next() {
+                total_alloc_point = set_alloc_point(
+                    &mut active_pools,
+                    &scheduled_set.lp_token,
+                    scheduled_set.alloc_point,
+                );
+            }
+        } else if let Some(epoch) = epochs.next() {
+            tokens_per_block = epoch.tokens_per_block;
+        }
+    }
+
+    let n_blocks = Uint128::from(to).checked_sub(from.into())?;
+
+    Ok(rewards.checked_add(calculate_generator_rewards(
+        n_blocks,
+        tokens_per_block,
+        get_alloc_point(&active_pools, lp_token),
+        total_alloc_point,
+    )?)?)
+}
+
+/// ## Description
+/// Returns the amount of ASTRO distributed per block at a specific block according to the configured rate
+/// and the pending emission epochs.
+/// ## Params
+/// * **cfg** is an object of type [`Config`]. This is the Generator contract configuration.
+///
+/// * **emission_schedule** is a slice of type [`EmissionEpoch`]. These are the pending emission epochs.
+///
+/// * **block** is a variable of type [`u64`]. This is the block for which we return the emission rate.
+fn tokens_per_block_at(cfg: &Config, emission_schedule: &[EmissionEpoch], block: u64) -> Uint128 {
+    emission_schedule
+        .iter()
+        .take_while(|epoch| epoch.start_block <= block)
+        .last()
+        .map_or(cfg.tokens_per_block, |epoch| epoch.tokens_per_block)
+}
+
+/// ## Description
+/// Sets the allocation points of a generator in the list of active generators and returns the new total
+/// allocation points. A generator that isn't active is only added if it gets allocation points.
+/// ## Params
+/// * **pools** is a vector of set that contains LP token address and allocation point.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+///
+/// * **alloc_point** is an object of type [`Uint128`]. These are the new allocation points.
+fn set_alloc_point(
+    pools: &mut Vec<(Addr, Uint128)>,
+    lp_token: &Addr,
+    alloc_point: Uint128,
+) -> Uint128 {
+    match pools.iter_mut().find(|(addr, _)| addr == lp_token) {
+        Some(pool) => pool.1 = alloc_point,
+        None => {
+            if !alloc_point.is_zero() {
+                pools.push((lp_token.clone(), alloc_point));
+            }
+        }
     }
 
-    Ok(r)
+    pools.iter().map(|(_, alloc_point)| alloc_point).sum()
 }
 
 /// ## Description
@@ -2012,7 +4802,27 @@ pub fn get_alloc_point(pools: &[(Addr, Uint128)], lp_token: &Addr) -> Uint128 {
 }
 
 /// ## Description
-/// Creates pool if it is allowed in the factory.
+/// Returns the pair whose liquidity token is `lp_token`, or [`None`] if `lp_token` is an external LP token.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **external_lp_tokens** are the LP tokens allowed without a pair registered in the factory.
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token whose pair we query.
+fn query_lp_pair(
+    deps: Deps,
+    external_lp_tokens: &[Addr],
+    lp_token: &Addr,
+) -> StdResult<Option<PairInfo>> {
+    if external_lp_tokens.contains(lp_token) {
+        return Ok(None);
+    }
+
+    pair_info_by_pool(deps, lp_token.clone()).map(Some)
+}
+
+/// ## Description
+/// Creates pool if it is allowed in the factory. Pools of external LP tokens are not checked against the factory.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -2026,21 +4836,32 @@ pub fn create_pool(
     cfg: &Config,
     factory_cfg: &FactoryConfigResponse,
 ) -> Result<PoolInfo, ContractError> {
-    let pair_info = pair_info_by_pool(deps.as_ref(), lp_token.clone())?;
+    // The Generator's alternative reward balances would be counted as staked LP tokens
+    if ALT_REWARD_TOKENS.has(deps.storage, lp_token) {
+        return Err(ContractError::LpTokenIsAltReward {
+            lp_token: lp_token.to_string(),
+        });
+    }
 
-    let mut pair_config: Option<PairConfig> = None;
-    for factory_pair_config in &factory_cfg.pair_configs {
-        if factory_pair_config.pair_type == pair_info.pair_type {
-            pair_config = Some(factory_pair_config.clone());
+    if let Some(pair_info) = query_lp_pair(deps.as_ref(), &cfg.external_lp_tokens, lp_token)? {
+        let mut pair_config: Option<PairConfig> = None;
+        for factory_pair_config in &factory_cfg.pair_configs {
+            if factory_pair_config.pair_type == pair_info.pair_type {
+                pair_config = Some(factory_pair_config.clone());
+            }
         }
-    }
 
-    if let Some(pair_config) = pair_config {
-        if pair_config.is_disabled || pair_config.is_generator_disabled {
-            return Err(ContractError::GeneratorIsDisabled {});
+        if let Some(pair_config) = pair_config {
+            if pair_config.is_disabled || pair_config.is_generator_disabled {
+                return Err(ContractError::GeneratorIsDisabled {
+                    lp_token: lp_token.to_string(),
+                });
+            }
+        } else {
+            return Err(ContractError::PairNotRegistered {
+                lp_token: lp_token.to_string(),
+            });
         }
-    } else {
-        return Err(ContractError::PairNotRegistered {});
     }
 
     POOL_INFO.save(
@@ -2049,104 +4870,137 @@ pub fn create_pool(
         &PoolInfo {
             last_reward_block: cfg.start_block.max(Uint64::from(env.block.height)),
             accumulated_rewards_per_share: Decimal::zero(),
-            reward_proxy: None,
-            accumulated_proxy_rewards_per_share: Decimal::zero(),
-            proxy_reward_balance_before_update: Uint128::zero(),
-            orphan_proxy_rewards: Uint128::zero(),
+            reward_proxies: vec![],
             has_asset_rewards: false,
+            alt_reward: None,
+            accumulated_alt_rewards_per_share: Decimal::zero(),
+            total_boost_bonus: Uint128::zero(),
         },
+        env.block.height,
     )?;
+    POOL_LENGTH.update(deps.storage, |length| -> StdResult<_> { Ok(length + 1) })?;
 
-    Ok(POOL_INFO.load(deps.storage, lp_token)?)
+    load_pool(deps.storage, lp_token)
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    let contract_version = get_contract_version(deps.storage)?;
-
-    match contract_version.contract.as_ref() {
-        "astroport-generator" => match contract_version.version.as_ref() {
-            "1.0.0" => {
-                let msg: migration::MigrationMsgV120 = from_binary(&msg.params)?;
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-                let mut active_pools: Vec<(Addr, Uint64)> = vec![];
+    match contract_version.version.as_ref() {
+        "1.0.0" => {
+            let msg: migration::MigrationMsgV120 = from_binary(&msg.params)?;
 
-                let keys = POOL_INFO
-                    .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending {})
-                    .map(|v| String::from_utf8(v).map_err(StdError::from))
-                    .collect::<Result<Vec<String>, StdError>>()?;
+            let mut active_pools: Vec<(Addr, Uint64)> = vec![];
 
-                for key in keys {
-                    let pool_info_v100 = migration::POOL_INFOV100
-                        .load(deps.storage, &Addr::unchecked(key.clone()))?;
-
-                    if !pool_info_v100.alloc_point.is_zero() {
-                        active_pools.push((Addr::unchecked(&key), pool_info_v100.alloc_point));
-                    }
+            for lp_token in migration::pool_keys(deps.as_ref())? {
+                let pool_info_v100 = migration::POOL_INFOV100.load(deps.storage, &lp_token)?;
 
-                    let pool_info = PoolInfo {
-                        has_asset_rewards: false,
-                        accumulated_proxy_rewards_per_share: pool_info_v100
-                            .accumulated_proxy_rewards_per_share,
-                        accumulated_rewards_per_share: pool_info_v100.accumulated_rewards_per_share,
-                        last_reward_block: pool_info_v100.last_reward_block,
-                        orphan_proxy_rewards: pool_info_v100.orphan_proxy_rewards,
-                        proxy_reward_balance_before_update: pool_info_v100
-                            .proxy_reward_balance_before_update,
-                        reward_proxy: pool_info_v100.reward_proxy,
-                    };
-                    POOL_INFO.save(deps.storage, &Addr::unchecked(key), &pool_info)?;
+                if !pool_info_v100.alloc_point.is_zero() {
+                    active_pools.push((lp_token.clone(), pool_info_v100.alloc_point));
                 }
 
-                migration::migrate_configs_to_v120(&mut deps, active_pools, msg)?
+                let pool_info = PoolInfo {
+                    has_asset_rewards: false,
+                    accumulated_rewards_per_share: pool_info_v100.accumulated_rewards_per_share,
+                    last_reward_block: pool_info_v100.last_reward_block,
+                    reward_proxies: migration::legacy_reward_proxies(
+                        pool_info_v100.reward_proxy,
+                        pool_info_v100.accumulated_proxy_rewards_per_share,
+                        pool_info_v100.proxy_reward_balance_before_update,
+                        pool_info_v100.orphan_proxy_rewards,
+                    ),
+                    alt_reward: None,
+                    accumulated_alt_rewards_per_share: Decimal::zero(),
+                    total_boost_bonus: Uint128::zero(),
+                };
+                // Only the format changes, so no history is written
+                migration::POOL_INFO_PRIMARY.save(deps.storage, &lp_token, &pool_info)?;
             }
-            "1.1.0" => {
-                let msg: migration::MigrationMsgV120 = from_binary(&msg.params)?;
 
-                let mut active_pools: Vec<(Addr, Uint64)> = vec![];
-
-                let keys = POOL_INFO
-                    .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending {})
-                    .map(|v| String::from_utf8(v).map_err(StdError::from))
-                    .collect::<Result<Vec<String>, StdError>>()?;
+            migration::migrate_configs_to_v120(&mut deps, active_pools, msg)?;
+            // Stakes are migrated in batches with ExecuteMsg::MigrateUsers
+            USERS_MIGRATION.save(deps.storage, &UsersMigration::default())?;
+        }
+        "1.1.0" => {
+            let msg: migration::MigrationMsgV120 = from_binary(&msg.params)?;
 
-                for key in keys {
-                    let pool_info_v110 = migration::POOL_INFOV110
-                        .load(deps.storage, &Addr::unchecked(key.clone()))?;
+            let mut active_pools: Vec<(Addr, Uint64)> = vec![];
 
-                    if !pool_info_v110.alloc_point.is_zero() {
-                        active_pools.push((Addr::unchecked(&key), pool_info_v110.alloc_point));
-                    }
+            for lp_token in migration::pool_keys(deps.as_ref())? {
+                let pool_info_v110 = migration::POOL_INFOV110.load(deps.storage, &lp_token)?;
 
-                    let pool_info = PoolInfo {
-                        has_asset_rewards: pool_info_v110.has_asset_rewards,
-                        accumulated_proxy_rewards_per_share: pool_info_v110
-                            .accumulated_proxy_rewards_per_share,
-                        accumulated_rewards_per_share: pool_info_v110.accumulated_rewards_per_share,
-                        last_reward_block: pool_info_v110.last_reward_block,
-                        orphan_proxy_rewards: pool_info_v110.orphan_proxy_rewards,
-                        proxy_reward_balance_before_update: pool_info_v110
-                            .proxy_reward_balance_before_update,
-                        reward_proxy: pool_info_v110.reward_proxy,
-                    };
-                    POOL_INFO.save(deps.storage, &Addr::unchecked(key), &pool_info)?;
+                if !pool_info_v110.alloc_point.is_zero() {
+                    active_pools.push((lp_token.clone(), pool_info_v110.alloc_point));
                 }
 
-                migration::migrate_configs_to_v120(&mut deps, active_pools, msg)?
+                let pool_info = PoolInfo {
+                    has_asset_rewards: pool_info_v110.has_asset_rewards,
+                    accumulated_rewards_per_share: pool_info_v110.accumulated_rewards_per_share,
+                    last_reward_block: pool_info_v110.last_reward_block,
+                    reward_proxies: migration::legacy_reward_proxies(
+                        pool_info_v110.reward_proxy,
+                        pool_info_v110.accumulated_proxy_rewards_per_share,
+                        pool_info_v110.proxy_reward_balance_before_update,
+                        pool_info_v110.orphan_proxy_rewards,
+                    ),
+                    alt_reward: None,
+                    accumulated_alt_rewards_per_share: Decimal::zero(),
+                    total_boost_bonus: Uint128::zero(),
+                };
+                // Only the format changes, so no history is written
+                migration::POOL_INFO_PRIMARY.save(deps.storage, &lp_token, &pool_info)?;
             }
-            _ => return Err(ContractError::MigrationError {}),
-        },
+
+            migration::migrate_configs_to_v120(&mut deps, active_pools, msg)?;
+            // Stakes are migrated in batches with ExecuteMsg::MigrateUsers
+            USERS_MIGRATION.save(deps.storage, &UsersMigration::default())?;
+        }
+        "1.2.0" => {
+            for lp_token in migration::pool_keys(deps.as_ref())? {
+                let pool_info_v120 = migration::POOL_INFOV120.load(deps.storage, &lp_token)?;
+
+                let pool_info = PoolInfo {
+                    has_asset_rewards: pool_info_v120.has_asset_rewards,
+                    accumulated_rewards_per_share: pool_info_v120.accumulated_rewards_per_share,
+                    last_reward_block: pool_info_v120.last_reward_block,
+                    reward_proxies: migration::legacy_reward_proxies(
+                        pool_info_v120.reward_proxy,
+                        pool_info_v120.accumulated_proxy_rewards_per_share,
+                        pool_info_v120.proxy_reward_balance_before_update,
+                        pool_info_v120.orphan_proxy_rewards,
+                    ),
+                    alt_reward: None,
+                    accumulated_alt_rewards_per_share: Decimal::zero(),
+                    total_boost_bonus: Uint128::zero(),
+                };
+                // Only the format changes, so no history is written
+                migration::POOL_INFO_PRIMARY.save(deps.storage, &lp_token, &pool_info)?;
+            }
+
+            // Stakes are migrated in batches with ExecuteMsg::MigrateUsers
+            USERS_MIGRATION.save(deps.storage, &UsersMigration::default())?;
+        }
         _ => return Err(ContractError::MigrationError {}),
     };
 
+    migration::sync_proxy_reward_balances(&mut deps, &env)?;
+    migration::count_pools(&mut deps)?;
+
+    // Historical state is only tracked from this upgrade onwards
+    if SNAPSHOTS_START_BLOCK.may_load(deps.storage)?.is_none() {
+        SNAPSHOTS_START_BLOCK.save(deps.storage, &env.block.height)?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
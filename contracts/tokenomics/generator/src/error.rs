@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 /// This enum describes generator contract errors!
@@ -10,38 +10,184 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Insufficient balance in contract to process claim")]
-    BalanceTooSmall {},
+    #[error("Insufficient balance in contract to process claim: requested {requested}, available {available}")]
+    BalanceTooSmall {
+        requested: Uint128,
+        available: Uint128,
+    },
 
     #[error("Pool with the LP token already exists!")]
     TokenPoolAlreadyExists {},
 
-    #[error("Reward proxy not allowed!")]
-    RewardProxyNotAllowed {},
+    #[error("Reward proxy {proxy} not allowed!")]
+    RewardProxyNotAllowed { proxy: String },
 
-    #[error("Pool doesn't have additional rewards!")]
-    PoolDoesNotHaveAdditionalRewards {},
+    #[error("Can't remove proxy contract {proxy}. It is not found in allowed list.")]
+    RewardProxyNotFound { proxy: String },
 
-    #[error("Insufficient amount of orphan rewards!")]
-    OrphanRewardsTooSmall {},
+    #[error("Pool {lp_token} doesn't have additional rewards!")]
+    PoolDoesNotHaveAdditionalRewards { lp_token: String },
+
+    #[error("Insufficient amount of orphan rewards for {lp_token}!")]
+    OrphanRewardsTooSmall { lp_token: String },
 
     #[error("Contract can't be migrated!")]
     MigrationError {},
 
-    #[error("The pool already has a reward proxy contract!")]
-    PoolAlreadyHasRewardProxyContract {},
+    #[error("The pool {lp_token} already has the reward proxy contract {proxy}!")]
+    PoolAlreadyHasRewardProxyContract { lp_token: String, proxy: String },
+
+    #[error("Generator is disabled for {lp_token}!")]
+    GeneratorIsDisabled { lp_token: String },
+
+    #[error("Generator for {lp_token} doesn't exist!")]
+    PoolNotFound { lp_token: String },
+
+    #[error("Duplicate of pool {lp_token}")]
+    PoolDuplicate { lp_token: String },
+
+    #[error("Pair of {lp_token} is not registered in factory!")]
+    PairNotRegistered { lp_token: String },
+
+    #[error("Duplicate of pair type {pair_type}!")]
+    PairTypeDuplicate { pair_type: String },
 
-    #[error("Generator is disabled!")]
-    GeneratorIsDisabled {},
+    #[error("Pair type ({pair_type}) is blacklisted!")]
+    PairTypeBlacklisted { pair_type: String },
 
-    #[error("Duplicate of pool")]
-    PoolDuplicate {},
+    #[error("Pair type ({pair_type}) is not blacklisted!")]
+    PairTypeNotBlacklisted { pair_type: String },
 
-    #[error("Pair is not registered in factory!")]
-    PairNotRegistered {},
+    #[error("Token {token} is blocked!")]
+    TokenBlocked { token: String },
+
+    #[error("Can't remove token {token}. It is not found in the blocked list.")]
+    TokenNotBlocked { token: String },
+
+    #[error("Need to provide add or remove parameters")]
+    EmptyListUpdate {},
+
+    #[error("Repetitive reply definition!")]
+    RepeatedReply {},
+
+    #[error("Amount must be greater than zero! Use ClaimRewards to claim rewards")]
+    ZeroAmount {},
 
     #[error("ASTRO or Terra native assets (UST, LUNA etc) cannot be blocked!")]
     AssetCannotBeBlocked {},
+
+    #[error("Allocation point can't be greater than {max}!")]
+    AllocPointTooHigh { max: Uint128 },
+
+    #[error("The keeper staleness threshold must be greater than zero!")]
+    InvalidStalenessThreshold {},
+
+    #[error("Keeper tips can only be funded with ASTRO!")]
+    InvalidKeeperTipToken {},
+
+    #[error("Accumulated rewards per share overflowed for {lp_token}!")]
+    RewardsPerShareOverflow { lp_token: String },
+
+    #[error("Staker rewards overflowed!")]
+    StakerRewardsOverflow {},
+
+    #[error("The Generator doesn't accept native coins!")]
+    NonPayable {},
+
+    #[error("There are no native coins to sweep!")]
+    NothingToSweep {},
+
+    #[error("There can't be more than {max} active generators!")]
+    TooManyPools { max: usize },
+
+    #[error("Historical state is not available for block {block}!")]
+    SnapshotNotAvailable { block: u64 },
+
+    #[error("You can't approve yourself as an operator!")]
+    CannotApproveSelf {},
+
+    #[error("The operator is not approved!")]
+    OperatorNotApproved {},
+
+    #[error("The generator for {lp_token} doesn't have an alternative reward!")]
+    AltRewardNotSet { lp_token: String },
+
+    #[error("The alternative reward token of a generator can't be changed!")]
+    AltRewardTokenChange {},
+
+    #[error("Generator LP tokens can't be used as alternative reward tokens!")]
+    InvalidAltRewardToken {},
+
+    #[error(
+        "{lp_token} is the alternative reward token of a generator, so it can't get a generator!"
+    )]
+    LpTokenIsAltReward { lp_token: String },
+
+    #[error("The effective block must be in the future!")]
+    InvalidEffectiveBlock {},
+
+    #[error("There can't be more than {max} scheduled allocation point changes!")]
+    TooManyScheduledSets { max: usize },
+
+    #[error("No allocation point change is scheduled for {lp_token} at block {effective_block}!")]
+    ScheduledSetNotFound {
+        lp_token: String,
+        effective_block: u64,
+    },
+
+    #[error("Emission epochs must start in the future, after the last scheduled epoch!")]
+    InvalidEpochStartBlock {},
+
+    #[error("There can't be more than {max} scheduled emission epochs!")]
+    TooManyEmissionEpochs { max: usize },
+
+    #[error("No emission epoch is scheduled to start at block {start_block}!")]
+    EmissionEpochNotFound { start_block: u64 },
+
+    #[error("Generator for {lp_token} is active or has scheduled allocation point changes!")]
+    PoolIsActive { lp_token: String },
+
+    #[error("Generator for {lp_token} still has stakers!")]
+    PoolHasStakers { lp_token: String },
+
+    #[error("Orphan proxy rewards of {lp_token} must be sent before the generator is removed!")]
+    PoolHasOrphanRewards { lp_token: String },
+
+    #[error("The reward proxy {proxy} doesn't stake {lp_token} for the same reward token as the current proxy!")]
+    IncompatibleRewardProxy { proxy: String, lp_token: String },
+
+    #[error("The maximum boost multiplier must be between 1 and {max}!")]
+    InvalidBoostMultiplier { max: Decimal },
+
+    #[error("{user} has no LP tokens staked in the generator for {lp_token}!")]
+    NoStakedLpTokens { user: String, lp_token: String },
+
+    #[error("All stakes are already migrated!")]
+    UsersAlreadyMigrated {},
+
+    #[error("Stakes are being migrated, try again once the migration is finished!")]
+    UsersMigrationPending {},
+
+    #[error("The generator for {lp_token} doesn't have an orphan stake!")]
+    OrphanStakeNotFound { lp_token: String },
+
+    #[error("The orphan stake of {lp_token} must be sent before the generator is removed!")]
+    PoolHasOrphanStake { lp_token: String },
+
+    #[error("Can't remove LP token {lp_token}. It is not found in the external LP tokens.")]
+    ExternalLpTokenNotFound { lp_token: String },
+
+    #[error("Can't remove LP token {lp_token} from the external LP tokens while its generator is active or scheduled!")]
+    ExternalLpTokenInUse { lp_token: String },
+
+    #[error("The generator for {lp_token} must have a reward proxy staking its LP tokens before other reward proxies are added!")]
+    NoStakingRewardProxy { lp_token: String },
+
+    #[error("A generator can't have more than {max} reward proxies!")]
+    TooManyRewardProxies { max: usize },
+
+    #[error("The reward proxy {proxy} doesn't pay rewards for {lp_token} in this Generator!")]
+    RewardProxyMismatch { proxy: String, lp_token: String },
 }
 
 impl From<OverflowError> for ContractError {
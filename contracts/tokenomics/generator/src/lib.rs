@@ -2,3 +2,9 @@ pub mod contract;
 pub mod error;
 mod migration;
 pub mod state;
+
+#[cfg(test)]
+mod testing;
+
+#[cfg(test)]
+mod mock_querier;
@@ -1,8 +1,17 @@
-use crate::state::{Config, CONFIG};
+use crate::contract::accumulate_rewards_per_share;
+use crate::error::ContractError;
+use crate::state::{
+    addr_from_key, split_user_info_key, user_info, Config, UserInfo, CONFIG, ORPHAN_STAKES,
+    POOL_INFO, POOL_LENGTH,
+};
 use astroport::asset::{addr_validate_to_lower, AssetInfo};
+use astroport::generator::{PoolInfo, RewardProxyInfo};
+use astroport::generator_proxy::QueryMsg as ProxyQueryMsg;
 
-use cosmwasm_std::{Addr, Decimal, DepsMut, StdError, Uint128, Uint64};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Binary, Decimal, Deps, DepsMut, Env, Order, StdError, StdResult, Uint128, Uint64,
+};
+use cw_storage_plus::{Bound, Index, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +59,48 @@ pub struct PoolInfoV110 {
 /// Stores the contract config(V1.1.0) at the given key
 pub const POOL_INFOV110: Map<&Addr, PoolInfoV110> = Map::new("pool_info");
 
+/// This structure stores the parameters for a generator (in the upgraded version v1.2.0 of the Generator contract).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfoV120 {
+    /// Accumulated amount of reward per share unit. Used for reward calculations
+    pub last_reward_block: Uint64,
+    pub accumulated_rewards_per_share: Decimal,
+    /// the reward proxy contract
+    pub reward_proxy: Option<Addr>,
+    pub accumulated_proxy_rewards_per_share: Decimal,
+    /// for calculation of new proxy rewards
+    pub proxy_reward_balance_before_update: Uint128,
+    /// the orphan proxy rewards which are left by emergency withdrawals
+    pub orphan_proxy_rewards: Uint128,
+    /// The pool has assets giving additional rewards
+    pub has_asset_rewards: bool,
+}
+
+/// Stores the contract config(V1.2.0) at the given key
+pub const POOL_INFOV120: Map<&Addr, PoolInfoV120> = Map::new("pool_info");
+
+/// Converts the only reward proxy a generator could have before V1.3.0 to the list of its reward proxies.
+pub fn legacy_reward_proxies(
+    reward_proxy: Option<Addr>,
+    accumulated_proxy_rewards_per_share: Decimal,
+    proxy_reward_balance_before_update: Uint128,
+    orphan_proxy_rewards: Uint128,
+) -> Vec<RewardProxyInfo> {
+    reward_proxy
+        .map(|proxy| RewardProxyInfo {
+            proxy,
+            accumulated_rewards_per_share: accumulated_proxy_rewards_per_share,
+            reward_balance_before_update: proxy_reward_balance_before_update,
+            orphan_rewards: orphan_proxy_rewards,
+            reward_per_block: Uint128::zero(),
+        })
+        .into_iter()
+        .collect()
+}
+
+/// A plain view of the primary [`POOL_INFO`] storage. Writing it doesn't record any history
+pub const POOL_INFO_PRIMARY: Map<&Addr, PoolInfo> = Map::new("pool_info");
+
 /// This structure describes the main control config of generator.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigV100 {
@@ -110,6 +161,10 @@ pub fn migrate_configs_to_v120(
         active_pools: pools,
         blocked_list_tokens: vec![],
         guardian: None,
+        keeper_incentive: None,
+        boost_provider: None,
+        voting_escrow: None,
+        external_lp_tokens: vec![],
     };
 
     if let Some(generator_controller) = msg.generator_controller {
@@ -128,3 +183,154 @@ pub fn migrate_configs_to_v120(
 
     Ok(())
 }
+
+/// Returns the LP tokens of all generators.
+pub fn pool_keys(deps: Deps) -> StdResult<Vec<Addr>> {
+    POOL_INFO
+        .prefix(())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| addr_from_key(deps.api, key))
+        .collect()
+}
+
+/// A plain view of the primary [`user_info`] storage, which can be iterated across generators from any stake.
+const USER_INFO_KEYS: Map<(&Addr, &Addr), UserInfo> = Map::new("user_info");
+
+/// Migrates the next batch of stakes stored before V1.3.0, in storage order after `last_key`. Before V1.3.0
+/// the `DepositFor` hook stored stakes under the beneficiary address without validating it. Stakes stored
+/// under a non-normalized address are merged into the normalized one. Stakes stored under an invalid address,
+/// which no account can ever withdraw, become the generator's orphan stake and stop earning rewards.
+/// Remaining stakes are added to the depositor index of [`user_info`], which was a plain map before V1.3.0,
+/// and fully withdrawn stakes are removed, so staker queries don't need to skip them.
+///
+/// Returns the primary key of the last migrated stake, or [`None`] if all stakes are migrated.
+pub fn migrate_users(
+    mut deps: DepsMut,
+    env: &Env,
+    cfg: &Config,
+    last_key: Option<Binary>,
+    limit: usize,
+) -> Result<Option<Binary>, ContractError> {
+    let start = last_key.map(|key| Bound::exclusive(key.to_vec()));
+    let stakes = USER_INFO_KEYS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(Vec<u8>, UserInfo)>>>()?;
+
+    for (pk, user) in &stakes {
+        let (lp_token, key) = split_user_info_key(pk)?;
+        let lp_token =
+            Addr::unchecked(String::from_utf8(lp_token.to_vec()).map_err(StdError::from)?);
+        let key = String::from_utf8(key.to_vec()).map_err(StdError::from)?;
+
+        if user.amount.is_zero() {
+            // Fully withdrawn stakes were kept before V1.3.0. They don't hold anything, so no history is written
+            user_info().idx.user.remove(deps.storage, pk, user)?;
+            USER_INFO_KEYS.remove(deps.storage, (&lp_token, &Addr::unchecked(key)));
+            continue;
+        }
+
+        let account = addr_validate_to_lower(deps.api, &key.to_lowercase()).ok();
+        if matches!(&account, Some(account) if account.as_str() == key) {
+            // The stake itself doesn't change, so only its depositor index is written
+            user_info().idx.user.save(deps.storage, pk, user)?;
+            continue;
+        }
+
+        // The stake is removed under the key it was stored with
+        user_info().remove(
+            deps.storage,
+            (&lp_token, &Addr::unchecked(key)),
+            env.block.height,
+        )?;
+
+        match account {
+            Some(account) => {
+                let mut merged = user_info()
+                    .may_load(deps.storage, (&lp_token, &account))?
+                    .unwrap_or_default();
+                merged.amount = merged.amount.checked_add(user.amount)?;
+                merged.reward_debt = merged.reward_debt.checked_add(user.reward_debt)?;
+                for (index, reward_debt_proxy) in user.reward_debt_proxy.iter().enumerate() {
+                    match merged.reward_debt_proxy.get_mut(index) {
+                        Some(merged_debt) => {
+                            *merged_debt = merged_debt.checked_add(*reward_debt_proxy)?
+                        }
+                        None => merged.reward_debt_proxy.push(*reward_debt_proxy),
+                    }
+                }
+                merged.reward_debt_alt =
+                    merged.reward_debt_alt.checked_add(user.reward_debt_alt)?;
+                merged.boost_bonus = merged.boost_bonus.checked_add(user.boost_bonus)?;
+                user_info().save(
+                    deps.storage,
+                    (&lp_token, &account),
+                    &merged,
+                    env.block.height,
+                )?;
+            }
+            None if !user.amount.is_zero() => {
+                // Rewards are accrued with the orphan stake still staked, so other stakers don't receive them
+                if let Some(mut pool) = POOL_INFO.may_load(deps.storage, &lp_token)? {
+                    accumulate_rewards_per_share(
+                        deps.branch(),
+                        env,
+                        &lp_token,
+                        &mut pool,
+                        cfg,
+                        None,
+                    )?;
+                    pool.total_boost_bonus =
+                        pool.total_boost_bonus.checked_sub(user.boost_bonus)?;
+                    POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+                }
+
+                ORPHAN_STAKES.update(deps.storage, &lp_token, |orphan_stake| -> StdResult<_> {
+                    Ok(orphan_stake.unwrap_or_default().checked_add(user.amount)?)
+                })?;
+            }
+            None => {}
+        }
+    }
+
+    Ok(if stakes.len() < limit {
+        None
+    } else {
+        stakes.last().map(|(pk, _)| Binary::from(pk.as_slice()))
+    })
+}
+
+/// Syncs the proxy reward balances tracked by generators. Before V1.3.0 the balance was queried before
+/// every proxy rewards update, so proxy rewards sent since the last update were not deducted from it.
+/// Generators are only written when their balance changes.
+pub fn sync_proxy_reward_balances(deps: &mut DepsMut, env: &Env) -> Result<(), StdError> {
+    for lp_token in pool_keys(deps.as_ref())? {
+        let mut pool = POOL_INFO.load(deps.storage, &lp_token)?;
+        let mut changed = false;
+        for reward_proxy in pool.reward_proxies.iter_mut() {
+            let balance = deps
+                .querier
+                .query_wasm_smart(&reward_proxy.proxy, &ProxyQueryMsg::Reward {})?;
+            if reward_proxy.reward_balance_before_update != balance {
+                reward_proxy.reward_balance_before_update = balance;
+                changed = true;
+            }
+        }
+
+        if changed {
+            POOL_INFO.save(deps.storage, &lp_token, &pool, env.block.height)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Stores the amount of generators, which was computed by iterating over [`POOL_INFO`] before V1.3.0.
+pub fn count_pools(deps: &mut DepsMut) -> Result<(), StdError> {
+    if POOL_LENGTH.may_load(deps.storage)?.is_none() {
+        let length = pool_keys(deps.as_ref())?.len() as u64;
+        POOL_LENGTH.save(deps.storage, &length)?;
+    }
+
+    Ok(())
+}
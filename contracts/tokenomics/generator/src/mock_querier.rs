@@ -0,0 +1,142 @@
+use astroport::factory::{
+    ConfigResponse as FactoryConfigResponse, PairType, QueryMsg as FactoryQueryMsg,
+};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use std::collections::HashMap;
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies.
+/// This uses the Astroport CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_querier: TokenQuerier,
+}
+
+#[derive(Clone, Default)]
+pub struct TokenQuerier {
+    // This lets us iterate over all balances that match the first string
+    balances: HashMap<String, HashMap<String, Uint128>>,
+}
+
+impl TokenQuerier {
+    pub fn new(balances: &[(&String, &[(&String, &Uint128)])]) -> Self {
+        TokenQuerier {
+            balances: balances_to_map(balances),
+        }
+    }
+}
+
+pub(crate) fn balances_to_map(
+    balances: &[(&String, &[(&String, &Uint128)])],
+) -> HashMap<String, HashMap<String, Uint128>> {
+    let mut balances_map: HashMap<String, HashMap<String, Uint128>> = HashMap::new();
+    for (contract_addr, balances) in balances.iter() {
+        let mut contract_balances_map: HashMap<String, Uint128> = HashMap::new();
+        for (addr, balance) in balances.iter() {
+            contract_balances_map.insert(addr.to_string(), **balance);
+        }
+
+        balances_map.insert(contract_addr.to_string(), contract_balances_map);
+    }
+    balances_map
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        // MockQuerier doesn't support Custom, so we ignore it completely
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "factory" =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::BlacklistedPairTypes {} => {
+                        SystemResult::Ok(to_binary(&Vec::<PairType>::new()).into())
+                    }
+                    FactoryQueryMsg::Config {} => SystemResult::Ok(
+                        to_binary(&FactoryConfigResponse {
+                            owner: Addr::unchecked("owner"),
+                            pair_configs: vec![],
+                            token_code_id: 0,
+                            fee_address: None,
+                            generator_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                            whitelist_code_id: 0,
+                            referral_registry: None,
+                            emergency_admin: None,
+                            pairs_count: vec![],
+                        })
+                        .into(),
+                    ),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_querier
+                            .balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .cloned()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                    }
+                    // Mocked tokens are not minted by pairs
+                    Cw20QueryMsg::Minter {} => SystemResult::Err(SystemError::InvalidRequest {
+                        error: format!("{} has no minter", contract_addr),
+                        request: msg.clone(),
+                    }),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_querier: TokenQuerier::default(),
+        }
+    }
+
+    // Configure the token balances
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_querier = TokenQuerier::new(balances);
+    }
+}
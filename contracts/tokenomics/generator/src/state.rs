@@ -1,11 +1,16 @@
+use crate::error::ContractError;
 use astroport::asset::AssetInfo;
 use astroport::common::OwnershipProposal;
-use astroport::generator::PoolInfo;
+use astroport::generator::{BoostProvider, EmissionEpoch, KeeperIncentive, PoolInfo, ScheduledSet};
 use astroport::DecimalCheckedOps;
-use cosmwasm_std::{Addr, StdResult, Uint128, Uint64};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Api, Binary, Decimal, Empty, StdError, StdResult, Storage, Uint128, Uint64,
+};
+use cw_storage_plus::{
+    Index, IndexList, IndexedSnapshotMap, Item, Map, MultiIndex, SnapshotMap, Strategy,
+};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// This structure stores the outstanding amount of token rewards that a user accrued.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -14,8 +19,52 @@ pub struct UserInfo {
     pub amount: Uint128,
     /// The amount of ASTRO rewards a user already received or is not eligible for; used for proper reward calculation
     pub reward_debt: Uint128,
-    /// Proxy reward amount a user already received or is not eligible for; used for proper reward calculation
-    pub reward_debt_proxy: Uint128,
+    /// Proxy reward amounts a user already received or is not eligible for, one for each reward proxy of the
+    /// generator; used for proper reward calculation. Stakes saved before V1.3.0 hold a single amount for the
+    /// generator's only proxy
+    #[serde(deserialize_with = "deserialize_reward_debt_proxy")]
+    pub reward_debt_proxy: Vec<Uint128>,
+    /// Alternative base reward amount a user already received or is not eligible for; used for proper reward calculation
+    #[serde(default)]
+    pub reward_debt_alt: Uint128,
+    /// The extra stake credited by the user's boost at the last checkpoint; counted for ASTRO rewards only
+    #[serde(default)]
+    pub boost_bonus: Uint128,
+}
+
+impl UserInfo {
+    /// Returns the stake used for ASTRO rewards: the LP amount plus the boost bonus.
+    pub fn boosted_amount(&self) -> StdResult<Uint128> {
+        Ok(self.amount.checked_add(self.boost_bonus)?)
+    }
+
+    /// Returns the reward amount the user already received or is not eligible for from the reward proxy
+    /// at the specified position in the generator's proxy list.
+    pub fn proxy_reward_debt(&self, index: usize) -> Uint128 {
+        self.reward_debt_proxy
+            .get(index)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The stored proxy reward debt of a user: a single amount before V1.3.0, one amount per reward proxy since then.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RewardDebtProxy {
+    Single(Uint128),
+    PerProxy(Vec<Uint128>),
+}
+
+/// Deserializes the proxy reward debts of a user from either stored format.
+fn deserialize_reward_debt_proxy<'de, D>(deserializer: D) -> Result<Vec<Uint128>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match RewardDebtProxy::deserialize(deserializer)? {
+        RewardDebtProxy::Single(debt) => vec![debt],
+        RewardDebtProxy::PerProxy(debts) => debts,
+    })
 }
 
 /// This structure stores the core parameters for the Generator contract.
@@ -45,6 +94,17 @@ pub struct Config {
     pub blocked_list_tokens: Vec<AssetInfo>,
     /// The guardian address which can add or remove tokens from blacklist
     pub guardian: Option<Addr>,
+    /// The ASTRO tip paid to whoever updates stale generators
+    pub keeper_incentive: Option<KeeperIncentive>,
+    /// The boost provider that returns per-user multipliers for ASTRO rewards
+    #[serde(default)]
+    pub boost_provider: Option<BoostProvider>,
+    /// The voting escrow contract whose vxASTRO voting power boosts ASTRO rewards
+    #[serde(default)]
+    pub voting_escrow: Option<Addr>,
+    /// LP tokens that can be set up in generators without a pair registered in the factory
+    #[serde(default)]
+    pub external_lp_tokens: Vec<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -74,36 +134,224 @@ pub enum ExecuteOnReply {
         /// The amount of tokens to withdraw
         amount: Uint128,
     },
+    /// Settles a staker's rewards and recomputes their boost without changing the stake
+    CheckpointBoost {
+        /// The LP token staked by the user
+        lp_token: Addr,
+        /// The staker whose boost is updated
+        account: Addr,
+    },
     /// Sets a new amount of ASTRO to distribute per block between all active generators
     SetTokensPerBlock {
         /// The new amount of ASTRO to distribute per block
         amount: Uint128,
     },
+    /// Moves a generator's LP tokens and undistributed proxy rewards to another reward proxy
+    MoveToProxy {
+        /// The LP token whose generator changes its proxy
+        lp_token: Addr,
+        /// The new reward proxy contract
+        proxy: Addr,
+    },
+    /// Updates the accrued rewards of generators and tips the keeper for the stale ones
+    UpdatePools {
+        /// The list of LP tokens whose generators we update
+        lp_tokens: Vec<Addr>,
+        /// The address that triggered the update
+        keeper: Addr,
+    },
 }
 
 /// Stores the contract config at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
+/// The checkpoint strategy used by [`POOL_INFO`] and [`user_info`].
+/// [`Strategy::EveryBlock`] keeps the state at the beginning of every block in which it changed.
+pub const SNAPSHOT_STRATEGY: Strategy = Strategy::EveryBlock;
+/// Stores the first block for which historical generator and staker state is available
+pub const SNAPSHOTS_START_BLOCK: Item<u64> = Item::new("snapshots_start_block");
 /// This is a map that contains information about all generators.
 ///
 /// The first key is the address of a LP token, the second key is an object of type [`PoolInfo`].
-pub const POOL_INFO: Map<&Addr, PoolInfo> = Map::new("pool_info");
+/// Every change is checkpointed, so the state can also be read at a past block.
+pub const POOL_INFO: SnapshotMap<&Addr, PoolInfo> = SnapshotMap::new(
+    "pool_info",
+    "pool_info__checkpoints",
+    "pool_info__changelog",
+    SNAPSHOT_STRATEGY,
+);
 pub const TMP_USER_ACTION: Item<Option<ExecuteOnReply>> = Item::new("tmp_user_action");
 
-/// This is a map that contains information about all stakers.
+/// This structure stores an ASTRO claim from the vesting contract until its reply is processed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingClaim {
+    /// The address that receives the ASTRO
+    pub recipient: Addr,
+    /// The amount of ASTRO claimed
+    pub amount: Uint128,
+}
+
+/// Stores the ASTRO claim sent to the vesting contract until its reply is processed
+pub const TMP_VESTING_CLAIM: Item<VestingClaim> = Item::new("tmp_vesting_claim");
+
+/// Stores the ASTRO rewards that couldn't be claimed from the vesting contract, keyed by recipient.
+/// They are added to the recipient's next claim
+pub const UNPAID_ASTRO: Map<&Addr, Uint128> = Map::new("unpaid_astro");
+
+/// This structure holds the secondary indexes of [`user_info`].
+pub struct UserInfoIndexes<'a> {
+    /// Indexes stakes by the depositor address. The index key is the raw depositor key followed by the primary key
+    pub user: MultiIndex<'a, (Vec<u8>, Vec<u8>), UserInfo>,
+}
+
+impl<'a> IndexList<UserInfo> for UserInfoIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<UserInfo>> + '_> {
+        let v: Vec<&dyn Index<UserInfo>> = vec![&self.user];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Returns a map that contains information about all stakers.
 ///
 /// The first key is an LP token address, the second key is a depositor address.
-pub const USER_INFO: Map<(&Addr, &Addr), UserInfo> = Map::new("user_info");
+/// Stakes can also be iterated by depositor using the `user` index. Every change is checkpointed,
+/// so stakes can also be read at a past block.
+pub fn user_info<'a>() -> IndexedSnapshotMap<'a, (&'a Addr, &'a Addr), UserInfo, UserInfoIndexes<'a>>
+{
+    let indexes = UserInfoIndexes {
+        user: MultiIndex::new(
+            |_, pk| {
+                let user = split_user_info_key(&pk)
+                    .map(|(_, user)| user.to_vec())
+                    .unwrap_or_default();
+                (user, pk)
+            },
+            "user_info",
+            "user_info__user",
+        ),
+    };
+    IndexedSnapshotMap::new(
+        "user_info",
+        "user_info__checkpoints",
+        "user_info__changelog",
+        SNAPSHOT_STRATEGY,
+        indexes,
+    )
+}
 
 /// ## Pagination settings
-/// The maximum amount of users that can be read at once from [`USER_INFO`]
+/// The maximum amount of users that can be read at once from [`user_info`]
 pub const MAX_LIMIT: u32 = 30;
 
-/// The default amount of users to read from [`USER_INFO`]
+/// The default amount of users to read from [`user_info`]
 pub const DEFAULT_LIMIT: u32 = 10;
 
+/// The maximum amount of active generators. All active generators are updated whenever ASTRO emissions change,
+/// so this bounds the gas used by those updates
+pub const MAX_ACTIVE_POOLS: usize = 100;
+
+/// The maximum number of reward proxies a generator can have
+pub const MAX_REWARD_PROXIES: usize = 3;
+
+/// The maximum amount of pending allocation point changes. Every reward calculation goes through them
+pub const MAX_SCHEDULED_SETS: usize = 20;
+
+/// Stores the allocation point changes that weren't applied yet, ordered by their effective block
+pub const SCHEDULED_SETS: Item<Vec<ScheduledSet>> = Item::new("scheduled_sets");
+
+/// The maximum amount of pending emission epochs. Every reward calculation goes through them
+pub const MAX_EMISSION_EPOCHS: usize = 20;
+
+/// Stores the emission epochs that weren't applied yet, ordered by their start block
+pub const EMISSION_SCHEDULE: Item<Vec<EmissionEpoch>> = Item::new("emission_schedule");
+
+/// The highest maximum boost multiplier (in percent) the owner can configure
+pub const MAX_BOOST_MULTIPLIER_PERCENT: u64 = 250;
+
+/// The share (in percent) of a staker's LP amount that counts without any vxASTRO. The vxASTRO boost can
+/// raise it up to the full amount, which results in a multiplier of 2.5
+pub const VE_BOOST_BASE_PERCENT: u128 = 40;
+
+/// This structure stores the progress of migrating the stakes stored before V1.3.0.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UsersMigration {
+    /// The primary key of the last migrated stake in [`user_info`]
+    pub last_key: Option<Binary>,
+}
+
+/// Stores the progress of migrating the stakes stored before V1.3.0. It is removed once all stakes are migrated
+pub const USERS_MIGRATION: Item<UsersMigration> = Item::new("users_migration");
+
+/// The default amount of stakes migrated at once
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 50;
+
+/// The maximum amount of stakes migrated at once, which bounds the gas used by a migration transaction
+pub const MAX_MIGRATION_LIMIT: u32 = 200;
+
+/// Stores the LP tokens that were staked under invalid addresses before V1.3.0, keyed by LP token.
+/// Nobody can withdraw them, so they don't earn rewards and the owner can send them to another address
+pub const ORPHAN_STAKES: Map<&Addr, Uint128> = Map::new("orphan_stakes");
+
+/// Stores the ASTRO held by the Generator to pay keeper tips. Tips are never claimed from the vesting
+/// allocation, so they don't reduce the ASTRO emitted to stakers
+pub const KEEPER_TIP_BUDGET: Item<Uint128> = Item::new("keeper_tip_budget");
+
+/// Stores the amount of generators that emit each alternative reward token, keyed by reward token.
+/// The Generator's LP token balances are used as staked amounts, so these tokens can't get a generator
+pub const ALT_REWARD_TOKENS: Map<&Addr, u64> = Map::new("alt_reward_tokens");
+
+/// Stores the amount of existing generators, so it can be read without iterating over [`POOL_INFO`]
+pub const POOL_LENGTH: Item<u64> = Item::new("pool_length");
+
 /// Contains a proposal to change contract ownership.
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
 
+/// Stores the operators allowed to claim a user's rewards from a generator.
+///
+/// The key is a tuple of the user address, the LP token address and the operator address.
+pub const OPERATORS: Map<(&Addr, &Addr, &Addr), Empty> = Map::new("operators");
+
+/// Loads the generator of the specified LP token.
+/// Returns a [`ContractError::PoolNotFound`] if the LP token doesn't have a generator.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+pub fn load_pool(storage: &dyn Storage, lp_token: &Addr) -> Result<PoolInfo, ContractError> {
+    POOL_INFO
+        .may_load(storage, lp_token)?
+        .ok_or_else(|| ContractError::PoolNotFound {
+            lp_token: lp_token.to_string(),
+        })
+}
+
+/// Deserializes an address stored as a [`POOL_INFO`] or [`user_info`] key.
+/// Returns a [`StdError`] if the key is not a valid address.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **key** is a vector of type [`u8`]. This is the raw storage key.
+pub fn addr_from_key(api: &dyn Api, key: Vec<u8>) -> StdResult<Addr> {
+    let addr = String::from_utf8(key).map_err(StdError::from)?;
+    api.addr_validate(&addr)
+}
+
+/// Splits a [`user_info`] primary key into the raw LP token and depositor keys.
+/// Returns a [`StdError`] if the key is malformed.
+/// ## Params
+/// * **pk** is a slice of type [`u8`]. This is the raw primary key.
+pub fn split_user_info_key(pk: &[u8]) -> StdResult<(&[u8], &[u8])> {
+    if pk.len() < 2 {
+        return Err(StdError::generic_err("Invalid staker key"));
+    }
+
+    let lp_token_len = u16::from_be_bytes([pk[0], pk[1]]) as usize;
+    if pk.len() < 2 + lp_token_len {
+        return Err(StdError::generic_err("Invalid staker key"));
+    }
+
+    Ok(pk[2..].split_at(lp_token_len))
+}
+
 /// Update user balance.
 /// ## Params
 /// * **user** is an object of type [`UserInfo`].
@@ -111,24 +359,66 @@ pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_pro
 /// * **pool** is an object of type [`PoolInfo`].
 ///
 /// * **amount** is an object of type [`Uint128`].
+///
+/// * **boost_bonus** is an object of type [`Uint128`]. This is the extra stake credited by the user's boost.
 pub fn update_user_balance(
     mut user: UserInfo,
     pool: &PoolInfo,
     amount: Uint128,
-) -> StdResult<UserInfo> {
+    boost_bonus: Uint128,
+) -> Result<UserInfo, ContractError> {
     user.amount = amount;
+    user.boost_bonus = boost_bonus;
 
     if !pool.accumulated_rewards_per_share.is_zero() {
         user.reward_debt = pool
             .accumulated_rewards_per_share
-            .checked_mul(user.amount)?;
+            .checked_mul_ceil(user.boosted_amount()?)
+            .map_err(|_| ContractError::StakerRewardsOverflow {})?;
     };
 
-    if !pool.accumulated_proxy_rewards_per_share.is_zero() {
-        user.reward_debt_proxy = pool
-            .accumulated_proxy_rewards_per_share
-            .checked_mul(user.amount)?;
+    if user.reward_debt_proxy.len() < pool.reward_proxies.len() {
+        user.reward_debt_proxy
+            .resize(pool.reward_proxies.len(), Uint128::zero());
+    }
+
+    for (reward_debt_proxy, reward_proxy) in user
+        .reward_debt_proxy
+        .iter_mut()
+        .zip(pool.reward_proxies.iter())
+    {
+        if !reward_proxy.accumulated_rewards_per_share.is_zero() {
+            *reward_debt_proxy = reward_proxy
+                .accumulated_rewards_per_share
+                .checked_mul_ceil(user.amount)
+                .map_err(|_| ContractError::StakerRewardsOverflow {})?;
+        };
+    }
+
+    if !pool.accumulated_alt_rewards_per_share.is_zero() {
+        user.reward_debt_alt = pool
+            .accumulated_alt_rewards_per_share
+            .checked_mul_ceil(user.amount)
+            .map_err(|_| ContractError::StakerRewardsOverflow {})?;
     };
 
     Ok(user)
 }
+
+/// Returns the rewards accrued by a staker since staking, rounded down.
+/// ## Params
+/// * **acc_per_share** is an object of type [`Decimal`]. These are the generator's accumulated rewards per LP token.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens staked.
+///
+/// * **reward_debt** is an object of type [`Uint128`]. This is the staker's reward debt.
+pub fn accrued_rewards(
+    acc_per_share: Decimal,
+    amount: Uint128,
+    reward_debt: Uint128,
+) -> Result<Uint128, ContractError> {
+    Ok(acc_per_share
+        .checked_mul_floor(amount)
+        .map_err(|_| ContractError::StakerRewardsOverflow {})?
+        .saturating_sub(reward_debt))
+}
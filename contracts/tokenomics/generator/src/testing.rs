@@ -0,0 +1,498 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, ContractResult, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
+    Reply, SubMsgExecutionResponse, Uint128, Uint64, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::migration::{PoolInfoV120, POOL_INFOV120};
+use crate::mock_querier::mock_dependencies;
+use crate::state::{user_info, ExecuteOnReply, CONFIG, TMP_USER_ACTION};
+use astroport::generator::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolInfoResponse, QueryMsg,
+    StakedPoolResponse, StakerResponse,
+};
+
+/// A stake in the format stored by V1.2.0.
+#[derive(Serialize, Deserialize)]
+struct UserInfoV120 {
+    amount: Uint128,
+    reward_debt: Uint128,
+    reward_debt_proxy: Uint128,
+}
+
+fn instantiate_generator(deps: DepsMut, env: Env) {
+    instantiate(
+        deps,
+        env,
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            factory: "factory".to_string(),
+            generator_controller: None,
+            guardian: None,
+            astro_token: "astro".to_string(),
+            tokens_per_block: Uint128::new(10),
+            start_block: Uint64::new(0),
+            allowed_reward_proxies: vec![],
+            vesting_contract: "vesting".to_string(),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn migrate_users_in_batches() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let lp_token = Addr::unchecked("lptoken");
+
+    instantiate_generator(deps.as_mut(), env.clone());
+
+    let mut cfg = CONFIG.load(&deps.storage).unwrap();
+    cfg.active_pools = vec![(lp_token.clone(), Uint128::new(100))];
+    cfg.total_alloc_point = Uint128::new(100);
+    CONFIG.save(&mut deps.storage, &cfg).unwrap();
+    POOL_INFOV120
+        .save(
+            &mut deps.storage,
+            &lp_token,
+            &PoolInfoV120 {
+                last_reward_block: Uint64::new(env.block.height),
+                accumulated_rewards_per_share: Decimal::zero(),
+                reward_proxy: None,
+                accumulated_proxy_rewards_per_share: Decimal::zero(),
+                proxy_reward_balance_before_update: Uint128::zero(),
+                orphan_proxy_rewards: Uint128::zero(),
+                has_asset_rewards: false,
+            },
+        )
+        .unwrap();
+
+    // Stakes stored by V1.2.0 under unvalidated keys
+    let stakes: Map<(&Addr, &Addr), UserInfoV120> = Map::new("user_info");
+    for (user, amount) in [
+        ("user1", 100),
+        ("User1", 50),
+        ("ab", 30),
+        ("user2", 20),
+        ("user3", 0),
+    ] {
+        stakes
+            .save(
+                &mut deps.storage,
+                (&lp_token, &Addr::unchecked(user)),
+                &UserInfoV120 {
+                    amount: Uint128::new(amount),
+                    reward_debt: Uint128::zero(),
+                    reward_debt_proxy: Uint128::new(amount),
+                },
+            )
+            .unwrap();
+    }
+    deps.querier.with_token_balances(&[(
+        &lp_token.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::new(200))],
+    )]);
+    set_contract_version(&mut deps.storage, "astroport-generator", "1.2.0").unwrap();
+
+    migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            params: to_binary(&Empty {}).unwrap(),
+        },
+    )
+    .unwrap();
+
+    let staked_pools = QueryMsg::StakedPools {
+        user: "user1".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let err = query(deps.as_ref(), env.clone(), staked_pools.clone()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Stakes are being migrated, try again once the migration is finished!"
+    );
+
+    let migrate_users = |limit| ExecuteMsg::MigrateUsers { limit: Some(limit) };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        migrate_users(2),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    // Stakes are migrated in storage order, so uppercase keys come first
+    for finished in ["false", "false", "true"] {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            migrate_users(2),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[1].value, finished);
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        migrate_users(2),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "All stakes are already migrated!");
+
+    let deposit = |deps: Deps, user: &str| -> Uint128 {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::Deposit {
+                    lp_token: lp_token.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(deposit(deps.as_ref(), "user1"), Uint128::new(150));
+    assert_eq!(deposit(deps.as_ref(), "user2"), Uint128::new(20));
+
+    // The single proxy reward debt of V1.2.0 becomes the debt for the first reward proxy
+    let user1 = user_info()
+        .load(&deps.storage, (&lp_token, &Addr::unchecked("user1")))
+        .unwrap();
+    assert_eq!(user1.reward_debt_proxy, vec![Uint128::new(150)]);
+
+    let res: Vec<StakedPoolResponse> =
+        from_binary(&query(deps.as_ref(), env.clone(), staked_pools).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        vec![StakedPoolResponse {
+            lp_token: lp_token.to_string(),
+            amount: Uint128::new(150),
+        }]
+    );
+
+    // Fully withdrawn stakes are removed
+    let res: Vec<StakerResponse> = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PoolStakers {
+                lp_token: lp_token.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        vec![
+            StakerResponse {
+                account: "user1".to_string(),
+                amount: Uint128::new(150),
+            },
+            StakerResponse {
+                account: "user2".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]
+    );
+
+    let orphan_stake: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrphanStake {
+                lp_token: lp_token.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orphan_stake, Uint128::new(30));
+
+    // The orphan stake doesn't earn rewards
+    let pool_info: PoolInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PoolInfo {
+                lp_token: lp_token.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool_info.lp_supply, Uint128::new(170));
+
+    let send_orphan_stake = ExecuteMsg::SendOrphanStake {
+        recipient: "recipient".to_string(),
+        lp_token: lp_token.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        send_orphan_stake.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        send_orphan_stake.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::new(30),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("owner", &[]),
+        send_orphan_stake,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The generator for lptoken doesn't have an orphan stake!"
+    );
+}
+
+#[test]
+fn external_lp_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let lp_token = Addr::unchecked("lptoken");
+
+    instantiate_generator(deps.as_mut(), env.clone());
+
+    let setup_pools = |pools: Vec<(String, Uint128)>| ExecuteMsg::SetupPools { pools };
+    let update_external_lp_tokens = |add: Option<Vec<String>>, remove: Option<Vec<String>>| {
+        ExecuteMsg::UpdateExternalLpTokens { add, remove }
+    };
+    let config = |deps: Deps| -> ConfigResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::Config {}).unwrap()).unwrap()
+    };
+
+    // The LP token is not minted by a pair registered in the factory
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        setup_pools(vec![(lp_token.to_string(), Uint128::new(100))]),
+    )
+    .unwrap_err();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        update_external_lp_tokens(Some(vec![lp_token.to_string()]), None),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        update_external_lp_tokens(Some(vec![lp_token.to_string()]), None),
+    )
+    .unwrap();
+    assert_eq!(
+        config(deps.as_ref()).external_lp_tokens,
+        vec![lp_token.clone()]
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        setup_pools(vec![(lp_token.to_string(), Uint128::new(100))]),
+    )
+    .unwrap();
+    assert_eq!(
+        config(deps.as_ref()).active_pools,
+        vec![(lp_token.clone(), Uint128::new(100))]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        update_external_lp_tokens(None, Some(vec![lp_token.to_string()])),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Can't remove LP token lptoken from the external LP tokens while its generator is active or scheduled!"
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        update_external_lp_tokens(None, Some(vec!["other".to_string()])),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Can't remove LP token other. It is not found in the external LP tokens."
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        setup_pools(vec![]),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("owner", &[]),
+        update_external_lp_tokens(None, Some(vec![lp_token.to_string()])),
+    )
+    .unwrap();
+    assert!(config(deps.as_ref()).external_lp_tokens.is_empty());
+}
+
+#[test]
+fn alt_reward_tokens_cannot_get_generators() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let lp_token = Addr::unchecked("lptoken");
+    let reward_token = Addr::unchecked("rewardtoken");
+
+    instantiate_generator(deps.as_mut(), env.clone());
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::UpdateExternalLpTokens {
+            add: Some(vec![lp_token.to_string(), reward_token.to_string()]),
+            remove: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::SetupPools {
+            pools: vec![(lp_token.to_string(), Uint128::new(100))],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::SetAltReward {
+            lp_token: lp_token.to_string(),
+            reward_token: reward_token.to_string(),
+            tokens_per_block: Uint128::new(10),
+        },
+    )
+    .unwrap();
+
+    let setup_both_pools = ExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_token.to_string(), Uint128::new(50)),
+            (reward_token.to_string(), Uint128::new(50)),
+        ],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        setup_both_pools.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "rewardtoken is the alternative reward token of a generator, so it can't get a generator!"
+    );
+
+    // The reward token can get a generator once no generator emits it
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::SetupPools { pools: vec![] },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::RemovePool {
+            lp_token: lp_token.to_string(),
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), env, owner, setup_both_pools).unwrap();
+}
+
+#[test]
+fn unknown_reply_id_is_rejected() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+
+    instantiate_generator(deps.as_mut(), env.clone());
+
+    let action = ExecuteOnReply::ClaimRewards {
+        lp_tokens: vec![],
+        account: Addr::unchecked("user"),
+    };
+    TMP_USER_ACTION
+        .save(&mut deps.storage, &Some(action.clone()))
+        .unwrap();
+
+    let err = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: 100,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: unknown reply id");
+
+    // The pending user action is left for the reply it belongs to
+    assert_eq!(TMP_USER_ACTION.load(&deps.storage).unwrap(), Some(action));
+}
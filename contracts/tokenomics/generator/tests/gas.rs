@@ -0,0 +1,393 @@
+//! Storage gas benchmarks for the generator reward loops.
+//!
+//! The generator is stored with metered storage (see [`astroport_test::gas`]) and every
+//! benchmark compares the gas used by a single transaction with a recorded baseline. If a change
+//! is expected to alter the gas usage, update the baselines with the values reported by the
+//! failing assertions. A mass update of [`MAX_ACTIVE_POOLS`] pools must also stay below
+//! [`MAX_ACTIVE_POOLS_GAS_LIMIT`], as that cap only holds while a full update fits in a block.
+use astroport::asset::token_asset_info;
+use astroport::factory::PairType;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::generator_proxy::InstantiateMsg as ProxyInstantiateMsg;
+use astroport_generator::state::MAX_ACTIVE_POOLS;
+use astroport_test::gas::measure_gas;
+use astroport_test::{Protocol, ProtocolBuilder};
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ExecuteMsg;
+use mirror_protocol::staking::{
+    ExecuteMsg as MirrorExecuteMsg, InstantiateMsg as MirrorInstantiateMsg,
+};
+use terra_multi_test::{ContractWrapper, Executor};
+
+const USER: &str = "user";
+
+/// Gas usage may exceed a recorded baseline by up to this percentage
+const TOLERANCE_PERCENT: u64 = 10;
+
+/// Recorded gas usage for a generator with the specified amount of pools
+struct Baseline {
+    pools: usize,
+    deposit: u64,
+    withdraw: u64,
+    mass_update: u64,
+    claim_rewards: u64,
+}
+
+const BASELINES: [Baseline; 3] = [
+    Baseline {
+        pools: 1,
+        deposit: 33_859,
+        withdraw: 30_245,
+        mass_update: 39_303,
+        claim_rewards: 29_681,
+    },
+    Baseline {
+        pools: 10,
+        deposit: 34_933,
+        withdraw: 30_743,
+        mass_update: 162_360,
+        claim_rewards: 177_551,
+    },
+    Baseline {
+        pools: 50,
+        deposit: 40_021,
+        withdraw: 33_248,
+        mass_update: 709_444,
+        claim_rewards: 834_776,
+    },
+];
+
+/// Recorded gas usage for a generator in which every pool has a reward proxy
+struct ProxyBaseline {
+    pools: usize,
+    deposit: u64,
+    mass_update: u64,
+}
+
+const PROXY_BASELINES: [ProxyBaseline; 2] = [
+    ProxyBaseline {
+        pools: 1,
+        deposit: 43_863,
+        mass_update: 49_349,
+    },
+    ProxyBaseline {
+        pools: 10,
+        deposit: 45_747,
+        mass_update: 266_891,
+    },
+];
+
+/// The maximum amount of gas that updating a single pool may add to a mass update
+const MASS_UPDATE_GAS_PER_POOL: u64 = 15_000;
+
+/// The maximum amount of gas a mass update of [`MAX_ACTIVE_POOLS`] pools may use
+const MAX_ACTIVE_POOLS_GAS_LIMIT: u64 = 1_550_000;
+
+#[test]
+fn gas_usage() {
+    for baseline in &BASELINES {
+        let (mut protocol, pools) = setup(baseline.pools, false);
+        let user = Addr::unchecked(USER);
+        let lp_token = pools[0].1.clone();
+
+        let (_, gas) = measure_gas(|| protocol.deposit(&user, &lp_token, 100).unwrap());
+        assert_within_baseline("deposit", baseline.pools, gas, baseline.deposit);
+
+        protocol.advance_blocks(10);
+        let (_, gas) = measure_gas(|| {
+            protocol
+                .app
+                .execute_contract(
+                    user.clone(),
+                    protocol.generator(),
+                    &GeneratorExecuteMsg::Withdraw {
+                        lp_token: lp_token.to_string(),
+                        amount: Uint128::new(50),
+                    },
+                    &[],
+                )
+                .unwrap()
+        });
+        assert_within_baseline("withdraw", baseline.pools, gas, baseline.withdraw);
+
+        protocol.advance_blocks(10);
+        let gas = set_tokens_per_block(&mut protocol);
+        assert_within_baseline("mass_update", baseline.pools, gas, baseline.mass_update);
+
+        protocol.advance_blocks(10);
+        let (_, gas) = measure_gas(|| {
+            protocol
+                .app
+                .execute_contract(
+                    user.clone(),
+                    protocol.generator(),
+                    &GeneratorExecuteMsg::ClaimRewards {
+                        lp_tokens: pools.iter().map(|(_, lp)| lp.to_string()).collect(),
+                    },
+                    &[],
+                )
+                .unwrap()
+        });
+        assert_within_baseline("claim_rewards", baseline.pools, gas, baseline.claim_rewards);
+    }
+}
+
+#[test]
+fn gas_usage_with_proxies() {
+    for baseline in &PROXY_BASELINES {
+        let (mut protocol, pools) = setup(baseline.pools, true);
+        let user = Addr::unchecked(USER);
+
+        let (_, gas) = measure_gas(|| protocol.deposit(&user, &pools[0].1, 100).unwrap());
+        assert_within_baseline("proxy deposit", baseline.pools, gas, baseline.deposit);
+
+        protocol.advance_blocks(10);
+        let gas = set_tokens_per_block(&mut protocol);
+        assert_within_baseline(
+            "proxy mass_update",
+            baseline.pools,
+            gas,
+            baseline.mass_update,
+        );
+    }
+}
+
+#[test]
+fn mass_update_gas_scales_linearly() {
+    let (mut protocol, _) = setup(10, false);
+    let gas_10_pools = set_tokens_per_block(&mut protocol);
+
+    let (mut protocol, _) = setup(50, false);
+    let gas_50_pools = set_tokens_per_block(&mut protocol);
+
+    let gas_per_pool = (gas_50_pools - gas_10_pools) / 40;
+    assert!(
+        gas_per_pool <= MASS_UPDATE_GAS_PER_POOL,
+        "Updating a single pool uses {} gas, the limit is {}",
+        gas_per_pool,
+        MASS_UPDATE_GAS_PER_POOL
+    );
+}
+
+#[test]
+fn mass_update_of_max_active_pools_fits_gas_limit() {
+    let (mut protocol, _) = setup(MAX_ACTIVE_POOLS, false);
+    let gas_used = set_tokens_per_block(&mut protocol);
+
+    assert!(
+        gas_used < MAX_ACTIVE_POOLS_GAS_LIMIT,
+        "Updating {} pools used {} gas, the limit is {}",
+        MAX_ACTIVE_POOLS,
+        gas_used,
+        MAX_ACTIVE_POOLS_GAS_LIMIT
+    );
+}
+
+fn assert_within_baseline(action: &str, pools: usize, gas: u64, baseline: u64) {
+    assert!(
+        gas <= baseline + baseline * TOLERANCE_PERCENT / 100,
+        "{} with {} pools used {} gas, the baseline is {}",
+        action,
+        pools,
+        gas,
+        baseline
+    );
+}
+
+/// Changes the amount of ASTRO emitted per block, which updates every active pool.
+/// Returns the amount of gas used.
+fn set_tokens_per_block(protocol: &mut Protocol) -> u64 {
+    let owner = protocol.owner.clone();
+    let generator = protocol.generator();
+
+    let (_, gas) = measure_gas(|| {
+        protocol
+            .app
+            .execute_contract(
+                owner,
+                generator,
+                &GeneratorExecuteMsg::SetTokensPerBlock {
+                    amount: Uint128::new(5_000000),
+                },
+                &[],
+            )
+            .unwrap()
+    });
+
+    gas
+}
+
+/// Instantiates a generator with `n_pools` ASTRO pairs that have equal allocation points. If
+/// `with_proxies` is set, each pool gets a Mirror reward proxy. The user stakes 100 LP tokens in
+/// every pool and then 10 blocks pass. Returns the protocol and a list of (pair, LP token) tuples.
+fn setup(n_pools: usize, with_proxies: bool) -> (Protocol, Vec<(Addr, Addr)>) {
+    let mut protocol = ProtocolBuilder::new()
+        .with_generator(10_000000)
+        .with_gas_metering()
+        .build();
+    let user = Addr::unchecked(USER);
+    let astro_token = protocol.astro_token.clone();
+
+    let pools: Vec<(Addr, Addr)> = (0..n_pools)
+        .map(|i| {
+            let token = protocol.create_token(&token_symbol(i));
+            let pair_info = protocol.create_pair(
+                PairType::Xyk {},
+                [
+                    token_asset_info(astro_token.clone()),
+                    token_asset_info(token),
+                ],
+                None,
+            );
+
+            (pair_info.contract_addr, pair_info.liquidity_token)
+        })
+        .collect();
+
+    let alloc_points: Vec<(&Addr, u128)> = pools.iter().map(|(_, lp)| (lp, 100)).collect();
+    protocol.setup_pools(&alloc_points);
+
+    if with_proxies {
+        let proxies = instantiate_mirror_proxies(&mut protocol, &pools);
+        let owner = protocol.owner.clone();
+        let generator = protocol.generator();
+
+        protocol
+            .app
+            .execute_contract(
+                owner.clone(),
+                generator.clone(),
+                &GeneratorExecuteMsg::SetAllowedRewardProxies {
+                    proxies: proxies.iter().map(|proxy| proxy.to_string()).collect(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        for ((_, lp_token), proxy) in pools.iter().zip(proxies) {
+            protocol
+                .app
+                .execute_contract(
+                    owner.clone(),
+                    generator.clone(),
+                    &GeneratorExecuteMsg::MoveToProxy {
+                        lp_token: lp_token.to_string(),
+                        proxy: proxy.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+    }
+
+    for (pair, lp_token) in &pools {
+        // The pair is the LP token minter
+        protocol
+            .app
+            .execute_contract(
+                pair.clone(),
+                lp_token.clone(),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: user.to_string(),
+                    amount: Uint128::new(1_000),
+                },
+                &[],
+            )
+            .unwrap();
+        protocol.deposit(&user, lp_token, 100).unwrap();
+    }
+
+    protocol.advance_blocks(10);
+
+    (protocol, pools)
+}
+
+/// Instantiates Mirror staking with every pair registered as an asset and a reward proxy for
+/// each pool. Returns the proxy addresses.
+fn instantiate_mirror_proxies(protocol: &mut Protocol, pools: &[(Addr, Addr)]) -> Vec<Addr> {
+    let owner = protocol.owner.clone();
+    let mirror_token = protocol.create_token("MIR");
+
+    let mirror_staking_code_id =
+        protocol
+            .app
+            .store_code(Box::new(ContractWrapper::new_with_empty(
+                mirror_staking::contract::execute,
+                mirror_staking::contract::instantiate,
+                mirror_staking::contract::query,
+            )));
+    let mirror_staking = protocol
+        .app
+        .instantiate_contract(
+            mirror_staking_code_id,
+            owner.clone(),
+            &MirrorInstantiateMsg {
+                base_denom: String::from("uusd"),
+                mint_contract: String::from(MOCK_CONTRACT_ADDR),
+                mirror_token: mirror_token.to_string(),
+                oracle_contract: String::from(MOCK_CONTRACT_ADDR),
+                owner: owner.to_string(),
+                premium_min_update_interval: 0,
+                short_reward_contract: String::from(MOCK_CONTRACT_ADDR),
+                terraswap_factory: String::from(MOCK_CONTRACT_ADDR),
+            },
+            &[],
+            "Mirror staking",
+            None,
+        )
+        .unwrap();
+
+    let proxy_code_id = protocol
+        .app
+        .store_code(Box::new(ContractWrapper::new_with_empty(
+            astroport_generator_proxy_to_mirror::contract::execute,
+            astroport_generator_proxy_to_mirror::contract::instantiate,
+            astroport_generator_proxy_to_mirror::contract::query,
+        )));
+
+    pools
+        .iter()
+        .map(|(pair, lp_token)| {
+            protocol
+                .app
+                .execute_contract(
+                    owner.clone(),
+                    mirror_staking.clone(),
+                    &MirrorExecuteMsg::RegisterAsset {
+                        asset_token: pair.to_string(),
+                        staking_token: lp_token.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            protocol
+                .app
+                .instantiate_contract(
+                    proxy_code_id,
+                    owner.clone(),
+                    &ProxyInstantiateMsg {
+                        generator_contract_addr: protocol.generator().to_string(),
+                        pair_addr: pair.to_string(),
+                        lp_token_addr: lp_token.to_string(),
+                        reward_contract_addr: mirror_staking.to_string(),
+                        reward_token_addr: mirror_token.to_string(),
+                    },
+                    &[],
+                    "Proxy",
+                    None,
+                )
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Returns a unique token symbol for each index (token symbols can only contain letters).
+fn token_symbol(i: usize) -> String {
+    format!(
+        "TOK{}{}",
+        (b'A' + (i / 26) as u8) as char,
+        (b'A' + (i % 26) as u8) as char
+    )
+}
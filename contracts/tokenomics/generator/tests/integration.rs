@@ -1,37 +1,53 @@
-use astroport::asset::{native_asset_info, token_asset_info, AssetInfo, PairInfo};
-use astroport::generator::{ExecuteMsg, PoolLengthResponse, QueryMsg, StakerResponse};
+use astroport::asset::{native_asset_info, token_asset_info, Asset, AssetInfo, PairInfo};
+use astroport::generator::{
+    AltRewardInfo, BoostProvider, BoostQueryMsg, BoostResponse, EmissionEpoch,
+    EmissionRateResponse, ExecuteMsg, PoolInfo, PoolLengthResponse, PoolListResponse,
+    PoolPendingTokenResponse, QueryMsg, ScheduledSet, StakedPoolResponse, StakerResponse,
+    UserInfoResponse,
+};
 
 use astroport::{
     factory::{
         ConfigResponse as FactoryConfigResponse, ExecuteMsg as FactoryExecuteMsg,
         InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType, QueryMsg as FactoryQueryMsg,
+        UpdateAddr,
     },
     generator::{
         ConfigResponse, Cw20HookMsg as GeneratorHookMsg, ExecuteMsg as GeneratorExecuteMsg,
-        InstantiateMsg as GeneratorInstantiateMsg, PendingTokenResponse, PoolInfoResponse,
-        QueryMsg as GeneratorQueryMsg,
+        InstantiateMsg as GeneratorInstantiateMsg, KeeperIncentive, PendingTokenResponse,
+        PoolAprInputsResponse, PoolInfoResponse, QueryMsg as GeneratorQueryMsg, RewardInfoResponse,
+    },
+    generator_proxy::{
+        ExecuteMsg as ProxyExecuteMsg, InstantiateMsg as ProxyInstantiateMsg,
+        QueryMsg as ProxyQueryMsg,
     },
-    generator_proxy::InstantiateMsg as ProxyInstantiateMsg,
     token::InstantiateMsg as TokenInstantiateMsg,
     vesting::{
-        Cw20HookMsg as VestingHookMsg, InstantiateMsg as VestingInstantiateMsg, VestingAccount,
-        VestingSchedule, VestingSchedulePoint,
+        Cw20HookMsg as VestingHookMsg, ExecuteMsg as VestingExecuteMsg,
+        InstantiateMsg as VestingInstantiateMsg, VestingAccount, VestingSchedule,
+        VestingSchedulePoint,
     },
+    voting_escrow::{VotingEscrowQueryMsg, VotingPowerResponse},
 };
 
 use astroport::pair::StablePoolParams;
+use astroport_generator_proxy_to_mirror::error::ContractError as ProxyContractError;
+use astroport_test::contracts::{
+    store_factory_code, store_generator_code, store_pair_code, store_pair_stable_code,
+    store_token_code, store_vesting_code,
+};
+use astroport_test::mock_app;
 use cosmwasm_std::{
-    testing::{mock_env, MockApi, MockStorage, MOCK_CONTRACT_ADDR},
-    to_binary, Addr, Binary, StdResult, Uint128, Uint64,
+    testing::MOCK_CONTRACT_ADDR, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Uint128, Uint64, WasmMsg,
 };
-use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse};
+use cw_storage_plus::{Item, Map};
 use mirror_protocol::staking::{
     Cw20HookMsg as MirrorStakingHookMsg, ExecuteMsg as MirrorExecuteMsg,
     InstantiateMsg as MirrorInstantiateMsg,
 };
-use terra_multi_test::{
-    next_block, AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock,
-};
+use terra_multi_test::{next_block, ContractWrapper, Executor, TerraApp};
 
 const OWNER: &str = "owner";
 const USER1: &str = "user1";
@@ -57,7 +73,7 @@ fn proper_deposit_and_withdraw() {
 
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -132,6 +148,27 @@ fn proper_deposit_and_withdraw() {
     check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 10);
     check_token_balance(&mut app, &lp_eur_usd, &generator_instance, 10);
 
+    let query_user_info = |app: &TerraApp, lp_token: &Addr| -> UserInfoResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::UserInfo {
+                    lp_token: lp_token.to_string(),
+                    user: USER1.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        query_user_info(&app, &lp_cny_eur),
+        UserInfoResponse {
+            amount: Uint128::new(10),
+            reward_debt: Uint128::zero(),
+            reward_debt_proxy: vec![],
+            boost_bonus: Uint128::zero(),
+        }
+    );
+
     check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
     check_pending_rewards(&mut app, &generator_instance, &lp_eur_usd, USER1, (0, None));
 
@@ -174,6 +211,11 @@ fn proper_deposit_and_withdraw() {
     check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 0);
     check_token_balance(&mut app, &lp_eur_usd, &generator_instance, 0);
 
+    assert_eq!(
+        query_user_info(&app, &lp_cny_eur),
+        UserInfoResponse::default()
+    );
+
     check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
 
     check_pending_rewards(&mut app, &generator_instance, &lp_eur_usd, USER1, (0, None));
@@ -194,7 +236,7 @@ fn set_tokens_per_block() {
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
 
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
     let factory_instance =
         instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
@@ -240,7 +282,7 @@ fn update_config() {
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
 
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
     let factory_instance =
         instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
@@ -301,7 +343,7 @@ fn update_owner() {
     let mut app = mock_app();
 
     let token_code_id = store_token_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -392,7 +434,7 @@ fn disabling_pool() {
 
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -448,7 +490,10 @@ fn disabling_pool() {
     let resp = app
         .execute_contract(user1.clone(), lp_eur_usdt.clone(), &msg, &[])
         .unwrap_err();
-    assert_eq!(resp.to_string(), "Generator is disabled!");
+    assert_eq!(
+        resp.to_string(),
+        format!("Generator is disabled for {}!", lp_eur_usdt)
+    );
 
     // Enable generator
     let msg = FactoryExecuteMsg::UpdatePairConfig {
@@ -495,7 +540,7 @@ fn generator_without_reward_proxies() {
 
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -596,7 +641,8 @@ fn generator_without_reward_proxies() {
         app.execute_contract(user2.clone(), generator_instance.clone(), &msg, &[])
             .unwrap_err()
             .to_string(),
-        "Insufficient balance in contract to process claim".to_string()
+        "Insufficient balance in contract to process claim: requested 1000000, available 0"
+            .to_string()
     );
 
     // User can't emergency withdraw if they didn't deposit
@@ -755,7 +801,8 @@ fn generator_without_reward_proxies() {
         app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
             .unwrap_err()
             .to_string(),
-        "Insufficient balance in contract to process claim".to_string(),
+        "Insufficient balance in contract to process claim: requested 1000000, available 0"
+            .to_string(),
     );
 
     // User2 withdraw and get rewards
@@ -825,7 +872,7 @@ fn generator_with_mirror_reward_proxy() {
 
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -915,7 +962,7 @@ fn generator_with_mirror_reward_proxy() {
         )
         .unwrap_err()
         .to_string(),
-        String::from("Reward proxy not allowed!")
+        format!("Reward proxy {} not allowed!", proxy_to_mirror_instance)
     );
 
     let msg = GeneratorExecuteMsg::SetAllowedRewardProxies {
@@ -999,7 +1046,8 @@ fn generator_with_mirror_reward_proxy() {
         app.execute_contract(user2.clone(), generator_instance.clone(), &msg, &[])
             .unwrap_err()
             .to_string(),
-        "Insufficient balance in contract to process claim".to_string()
+        "Insufficient balance in contract to process claim: requested 1000000, available 0"
+            .to_string()
     );
 
     // User can't emergency withdraw if they didn't deposit previously
@@ -1214,7 +1262,8 @@ fn generator_with_mirror_reward_proxy() {
         app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
             .unwrap_err()
             .to_string(),
-        "Insufficient balance in contract to process claim".to_string(),
+        "Insufficient balance in contract to process claim: requested 1000000, available 0"
+            .to_string(),
     );
 
     check_token_balance(
@@ -1229,11 +1278,14 @@ fn generator_with_mirror_reward_proxy() {
     let msg = GeneratorQueryMsg::OrphanProxyRewards {
         lp_token: lp_cny_eur.to_string(),
     };
-    let orphan_rewards: Uint128 = app
+    let orphan_rewards: Vec<(Addr, Uint128)> = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg)
         .unwrap();
-    assert_eq!(orphan_rewards, Uint128::new(50_000000));
+    assert_eq!(
+        orphan_rewards,
+        vec![(proxy_to_mirror_instance.clone(), Uint128::new(50_000000))]
+    );
 
     // Owner sends orphaned proxy rewards
     let msg = GeneratorExecuteMsg::SendOrphanProxyReward {
@@ -1262,7 +1314,7 @@ fn generator_with_mirror_reward_proxy() {
         app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
             .unwrap_err()
             .to_string(),
-        "Insufficient amount of orphan rewards!"
+        format!("Insufficient amount of orphan rewards for {}!", lp_cny_eur)
     );
 
     // User2 withdraws and gets rewards
@@ -1348,7 +1400,7 @@ fn update_allowed_proxies() {
     let owner = Addr::unchecked(OWNER);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
     let allowed_proxies = Some(vec![
         "proxy1".to_string(),
         "proxy2".to_string(),
@@ -1376,10 +1428,7 @@ fn update_allowed_proxies() {
     let err = app
         .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
-    assert_eq!(
-        "Generic error: Need to provide add or remove parameters",
-        err.to_string()
-    );
+    assert_eq!("Need to provide add or remove parameters", err.to_string());
 
     let msg = ExecuteMsg::UpdateAllowedProxies {
         add: Some(vec!["proxy5".to_string(), "proxy6".to_string()]),
@@ -1413,7 +1462,7 @@ fn update_allowed_proxies() {
         .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
     assert_eq!(
-        "Generic error: Can't remove proxy contract. It is not found in allowed list.",
+        "Can't remove proxy contract proxy1. It is not found in allowed list.",
         err.to_string()
     );
 
@@ -1447,7 +1496,7 @@ fn move_to_proxy() {
     let user1 = Addr::unchecked(USER1);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -1494,7 +1543,7 @@ fn move_to_proxy() {
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(None, reps.reward_proxy);
+    assert_eq!(reps.reward_proxies, vec![]);
 
     let (mirror_token_instance, mirror_staking_instance) =
         instantiate_mirror_protocol(&mut app, token_code_id, &pair_cny_eur, &lp_cny_eur);
@@ -1519,7 +1568,10 @@ fn move_to_proxy() {
     let err = app
         .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
-    assert_eq!("Reward proxy not allowed!", err.to_string());
+    assert_eq!(
+        format!("Reward proxy {} not allowed!", proxy_to_mirror_instance),
+        err.to_string()
+    );
 
     let msg = GeneratorExecuteMsg::SetAllowedRewardProxies {
         proxies: vec![proxy_to_mirror_instance.to_string()],
@@ -1544,7 +1596,13 @@ fn move_to_proxy() {
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Some(Addr::unchecked("contract #10")), reps.reward_proxy);
+    assert_eq!(
+        vec![Addr::unchecked("contract #10")],
+        reps.reward_proxies
+            .into_iter()
+            .map(|reward_proxy| reward_proxy.proxy)
+            .collect::<Vec<_>>()
+    );
 
     // Mint tokens, so user can deposit
     mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
@@ -1605,9 +1663,111 @@ fn move_to_proxy() {
         .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
     assert_eq!(
-        "The pool already has a reward proxy contract!",
+        format!(
+            "The pool {} already has the reward proxy contract {}!",
+            lp_cny_eur, proxy_to_mirror_instance
+        ),
+        err.to_string()
+    );
+
+    // A generator can only move to a proxy that pays the same reward token
+    let astro_proxy_instance = instantiate_proxy(
+        &mut app,
+        proxy_code_id,
+        &generator_instance,
+        &pair_cny_eur,
+        &lp_cny_eur,
+        &mirror_staking_instance,
+        &astro_token_instance,
+    );
+    let new_proxy_instance = instantiate_proxy(
+        &mut app,
+        proxy_code_id,
+        &generator_instance,
+        &pair_cny_eur,
+        &lp_cny_eur,
+        &mirror_staking_instance,
+        &mirror_token_instance,
+    );
+
+    let msg = GeneratorExecuteMsg::SetAllowedRewardProxies {
+        proxies: vec![
+            proxy_to_mirror_instance.to_string(),
+            astro_proxy_instance.to_string(),
+            new_proxy_instance.to_string(),
+        ],
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = ExecuteMsg::MoveToProxy {
+        lp_token: lp_cny_eur.to_string(),
+        proxy: astro_proxy_instance.to_string(),
+    };
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        format!(
+            "The reward proxy {} doesn't stake {} for the same reward token as the current proxy!",
+            astro_proxy_instance, lp_cny_eur
+        ),
         err.to_string()
     );
+
+    // The staked LP tokens and the undistributed proxy rewards move to the new proxy
+    let msg = ExecuteMsg::MoveToProxy {
+        lp_token: lp_cny_eur.to_string(),
+        proxy: new_proxy_instance.to_string(),
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(
+        vec![new_proxy_instance.clone()],
+        reps.reward_proxies
+            .into_iter()
+            .map(|reward_proxy| reward_proxy.proxy)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(reps.lp_supply, Uint128::new(10));
+
+    check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 0);
+    check_token_balance(&mut app, &lp_cny_eur, &mirror_staking_instance, 10);
+    check_token_balance(
+        &mut app,
+        &mirror_token_instance,
+        &proxy_to_mirror_instance,
+        0,
+    );
+    check_token_balance(
+        &mut app,
+        &mirror_token_instance,
+        &new_proxy_instance,
+        50_000000,
+    );
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (10_000000, Some(50_000000)),
+    );
+
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_cny_eur.to_string(),
+        amount: Uint128::new(10),
+    };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    check_token_balance(&mut app, &lp_cny_eur, &user1, 10);
+    check_token_balance(&mut app, &mirror_token_instance, &user1, 50_000000);
 }
 
 #[test]
@@ -1621,7 +1781,7 @@ fn query_all_stakers() {
     let user5 = Addr::unchecked(USER5);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let factory_instance =
         instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
@@ -1757,29 +1917,20 @@ fn query_all_stakers() {
 }
 
 #[test]
-fn query_pagination_stakers() {
+fn query_staked_pools() {
     let mut app = mock_app();
 
     let user1 = Addr::unchecked(USER1);
-    let user2 = Addr::unchecked(USER2);
-    let user3 = Addr::unchecked(USER3);
-    let user4 = Addr::unchecked(USER4);
-    let user5 = Addr::unchecked(USER5);
-    let user6 = Addr::unchecked(USER6);
-    let user7 = Addr::unchecked(USER7);
-    let user8 = Addr::unchecked(USER8);
-    let user9 = Addr::unchecked(USER9);
-
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let factory_instance =
         instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
-    let cny_eur_token_code_id = store_token_code(&mut app);
-    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
-    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, token_code_id, "USD", None);
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
 
     let (pair_cny_eur, lp_cny_eur) = create_pair(
         &mut app,
@@ -1787,379 +1938,653 @@ fn query_pagination_stakers() {
         None,
         None,
         [
-            AssetInfo::Token {
-                contract_addr: cny_token.clone(),
-            },
-            AssetInfo::Token {
-                contract_addr: eur_token.clone(),
-            },
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
         ],
     );
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
-
     let generator_instance =
         instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
 
     register_lp_tokens_in_generator(
         &mut app,
         &generator_instance,
-        vec![PoolWithProxy {
-            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
-            proxy: None,
-        }],
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::new(50)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_usd.to_string(), Uint128::new(50)),
+                proxy: None,
+            },
+        ],
     );
 
-    for user in [
-        user1, user2, user3, user4, user5, user6, user7, user8, user9,
-    ] {
-        mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user, 10);
-    }
+    let res: PoolLengthResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &QueryMsg::PoolLength {})
+        .unwrap();
+    assert_eq!(res.length, 2);
+
+    let query_pool_list = |app: &TerraApp, start_after: Option<String>, limit: Option<u32>| {
+        let res: Vec<PoolListResponse> = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::PoolList { start_after, limit },
+            )
+            .unwrap();
+        res.into_iter()
+            .map(|pool| (pool.lp_token, pool.alloc_point))
+            .collect::<Vec<_>>()
+    };
 
-    for user in [
-        USER1, USER2, USER3, USER4, USER5, USER6, USER7, USER8, USER9,
-    ] {
-        deposit_lp_tokens_to_generator(&mut app, &generator_instance, user, &[(&lp_cny_eur, 10)]);
-    }
+    let mut expected_pools = vec![
+        (lp_cny_eur.clone(), Uint128::new(50)),
+        (lp_eur_usd.clone(), Uint128::new(50)),
+    ];
+    expected_pools.sort();
+    assert_eq!(query_pool_list(&app, None, None), expected_pools);
+    assert_eq!(
+        query_pool_list(&app, Some(expected_pools[0].0.to_string()), Some(1)),
+        expected_pools[1..]
+    );
 
-    check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 90);
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
+    mint_tokens(&mut app, pair_eur_usd.clone(), &lp_eur_usd, &user1, 20);
+    mint_tokens(
+        &mut app,
+        pair_eur_usd,
+        &lp_eur_usd,
+        &Addr::unchecked(USER2),
+        30,
+    );
 
-    // Get the first two stakers
-    let msg_cny_eur = QueryMsg::PoolStakers {
-        lp_token: lp_cny_eur.to_string(),
-        start_after: None,
-        limit: Some(2),
+    deposit_lp_tokens_to_generator(
+        &mut app,
+        &generator_instance,
+        USER1,
+        &[(&lp_cny_eur, 10), (&lp_eur_usd, 20)],
+    );
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER2, &[(&lp_eur_usd, 30)]);
+
+    let query_staked_pools = |app: &TerraApp, start_after: Option<String>, limit: Option<u32>| {
+        let res: Vec<StakedPoolResponse> = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::StakedPools {
+                    user: USER1.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        res
     };
 
-    let reps: Vec<StakerResponse> = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
+    let mut expected = vec![
+        StakedPoolResponse {
+            lp_token: lp_cny_eur.to_string(),
+            amount: Uint128::new(10),
+        },
+        StakedPoolResponse {
+            lp_token: lp_eur_usd.to_string(),
+            amount: Uint128::new(20),
+        },
+    ];
+    expected.sort_by(|a, b| a.lp_token.cmp(&b.lp_token));
+    assert_eq!(query_staked_pools(&app, None, None), expected);
 
-    // check count of users
-    assert_eq!(reps.len(), 2 as usize);
+    // Paginate by LP token
+    assert_eq!(query_staked_pools(&app, None, Some(1)), expected[..1]);
+    assert_eq!(
+        query_staked_pools(&app, Some(expected[0].lp_token.clone()), None),
+        expected[1..]
+    );
 
+    // Pending rewards from several generators can be queried at once
+    app.update_block(|bi| next_block(bi));
+    let query_pending_token_list = |app: &TerraApp, lp_tokens: Option<Vec<String>>| {
+        let res: Vec<PoolPendingTokenResponse> = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::PendingTokenList {
+                    user: USER1.to_string(),
+                    lp_tokens,
+                },
+            )
+            .unwrap();
+        res
+    };
+    let expected_pending: Vec<PoolPendingTokenResponse> = expected
+        .iter()
+        .map(|pool| {
+            let res: PendingTokenResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &generator_instance,
+                    &QueryMsg::PendingToken {
+                        lp_token: pool.lp_token.clone(),
+                        user: USER1.to_string(),
+                    },
+                )
+                .unwrap();
+            PoolPendingTokenResponse {
+                lp_token: pool.lp_token.clone(),
+                pending: res.pending,
+                pending_on_proxy: res.pending_on_proxy,
+            }
+        })
+        .collect();
+    assert!(!expected_pending[0].pending.is_zero());
+    assert_eq!(query_pending_token_list(&app, None), expected_pending);
     assert_eq!(
-        vec![
-            StakerResponse {
-                account: "user1".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user2".to_string(),
-                amount: Uint128::new(10)
-            },
-        ],
-        reps
+        query_pending_token_list(&app, Some(vec![expected[1].lp_token.clone()])),
+        expected_pending[1..]
     );
 
-    // Get the next seven stakers
-    let msg_cny_eur = QueryMsg::PoolStakers {
+    // Generators without funds are skipped
+    let msg = GeneratorExecuteMsg::Withdraw {
         lp_token: lp_cny_eur.to_string(),
-        start_after: Some("user2".to_string()),
-        limit: Some(7),
+        amount: Uint128::new(10),
     };
-
-    let reps: Vec<StakerResponse> = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+    app.execute_contract(user1, generator_instance.clone(), &msg, &[])
         .unwrap();
 
     assert_eq!(
-        vec![
-            StakerResponse {
-                account: "user3".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user4".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user5".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user6".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user7".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user8".to_string(),
-                amount: Uint128::new(10)
-            },
-            StakerResponse {
-                account: "user9".to_string(),
-                amount: Uint128::new(10)
-            },
-        ],
-        reps
+        query_staked_pools(&app, None, None),
+        vec![StakedPoolResponse {
+            lp_token: lp_eur_usd.to_string(),
+            amount: Uint128::new(20),
+        }]
     );
 }
 
 #[test]
-fn update_tokens_blocked_list() {
+fn query_historical_state() {
     let mut app = mock_app();
 
-    let owner = Addr::unchecked(OWNER);
     let user1 = Addr::unchecked(USER1);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
-
-    let astro_token_instance =
-        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let pair_code_id = store_pair_code(&mut app);
 
     let factory_instance =
         instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
-    let generator_instance =
-        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
-
-    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
     let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
-    let ukr_token = instantiate_token(&mut app, token_code_id, "UKR", None);
-    let msi_token = instantiate_token(&mut app, token_code_id, "MSI", None);
+    let usd_token = instantiate_token(&mut app, token_code_id, "USD", None);
 
-    let (_, lp_cny_eur) = create_pair(
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
         &mut app,
         &factory_instance,
         None,
         None,
-        [
-            AssetInfo::Token {
-                contract_addr: cny_token.clone(),
-            },
-            AssetInfo::Token {
-                contract_addr: eur_token.clone(),
-            },
-        ],
+        [token_asset_info(eur_token), token_asset_info(usd_token)],
     );
 
-    let (_, lp_cny_ukr) = create_pair(
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+    let start_block = app.block_info().height;
+
+    app.update_block(|bi| next_block(bi));
+    register_lp_tokens_in_generator(
         &mut app,
-        &factory_instance,
-        None,
-        None,
-        [
-            AssetInfo::Token {
-                contract_addr: cny_token.clone(),
-            },
-            AssetInfo::Token {
-                contract_addr: ukr_token.clone(),
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_eur_usd.to_string(), Uint128::new(50)),
+            proxy: None,
+        }],
+    );
+
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &user1, 30);
+
+    app.update_block(|bi| next_block(bi));
+    let first_deposit_block = app.block_info().height;
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_eur_usd, 10)]);
+
+    app.update_block(|bi| next_block(bi));
+    let second_deposit_block = app.block_info().height;
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_eur_usd, 20)]);
+
+    app.update_block(|bi| next_block(bi));
+    let withdraw_block = app.block_info().height;
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_eur_usd.to_string(),
+        amount: Uint128::new(30),
+    };
+    app.execute_contract(user1, generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    app.update_block(|bi| next_block(bi));
+
+    let query_deposit_at = |app: &TerraApp, block: u64| -> StdResult<Uint128> {
+        app.wrap().query_wasm_smart(
+            &generator_instance,
+            &QueryMsg::DepositAt {
+                lp_token: lp_eur_usd.to_string(),
+                user: USER1.to_string(),
+                block,
             },
-        ],
+        )
+    };
+
+    // The state is read at the beginning of the block
+    assert_eq!(
+        query_deposit_at(&app, first_deposit_block).unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_deposit_at(&app, second_deposit_block).unwrap(),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        query_deposit_at(&app, withdraw_block).unwrap(),
+        Uint128::new(30)
+    );
+    assert_eq!(
+        query_deposit_at(&app, withdraw_block + 1).unwrap(),
+        Uint128::zero()
     );
 
-    let (_, lp_eur_msi) = create_pair(
+    // Blocks before the generator tracked snapshots and future blocks are rejected
+    let err = query_deposit_at(&app, start_block - 1).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Historical state is not available for block"));
+    query_deposit_at(&app, withdraw_block + 2).unwrap_err();
+
+    let query_pool_info_at = |app: &TerraApp, block: u64| -> StdResult<PoolInfo> {
+        app.wrap().query_wasm_smart(
+            &generator_instance,
+            &QueryMsg::PoolInfoAt {
+                lp_token: lp_eur_usd.to_string(),
+                block,
+            },
+        )
+    };
+
+    // The generator didn't exist yet
+    query_pool_info_at(&app, start_block).unwrap_err();
+
+    let pool_info = query_pool_info_at(&app, second_deposit_block).unwrap();
+    assert_eq!(pool_info.last_reward_block.u64(), first_deposit_block);
+    let pool_info = query_pool_info_at(&app, withdraw_block).unwrap();
+    assert_eq!(pool_info.last_reward_block.u64(), second_deposit_block);
+}
+
+#[test]
+fn zero_amount_deposit_and_withdraw() {
+    let mut app = mock_app();
+
+    let user1 = Addr::unchecked(USER1);
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, token_code_id, "USD", None);
+
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
         &mut app,
         &factory_instance,
         None,
         None,
-        [
-            AssetInfo::Token {
-                contract_addr: eur_token.clone(),
-            },
-            AssetInfo::Token {
-                contract_addr: msi_token.clone(),
-            },
-        ],
+        [token_asset_info(eur_token), token_asset_info(usd_token)],
     );
 
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
     register_lp_tokens_in_generator(
         &mut app,
         &generator_instance,
-        vec![
-            PoolWithProxy {
-                pool: (lp_cny_eur.to_string(), Uint128::new(100)),
-                proxy: None,
-            },
-            PoolWithProxy {
-                pool: (lp_cny_ukr.to_string(), Uint128::new(100)),
-                proxy: None,
-            },
-            PoolWithProxy {
-                pool: (lp_eur_msi.to_string(), Uint128::new(100)),
-                proxy: None,
-            },
-        ],
+        vec![PoolWithProxy {
+            pool: (lp_eur_usd.to_string(), Uint128::new(50)),
+            proxy: None,
+        }],
     );
 
-    let msg = ExecuteMsg::UpdateTokensBlockedlist {
-        add: None,
-        remove: None,
-    };
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_eur_usd, 10)]);
 
+    // The LP token contract rejects zero-amount sends, so the hook is called directly
+    let msg = GeneratorExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: USER1.to_string(),
+        amount: Uint128::zero(),
+        msg: to_binary(&GeneratorHookMsg::Deposit {}).unwrap(),
+    });
     let err = app
-        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .execute_contract(lp_eur_usd.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
     assert_eq!(
-        "Generic error: Need to provide add or remove parameters",
-        err.to_string()
+        err.to_string(),
+        "Amount must be greater than zero! Use ClaimRewards to claim rewards"
     );
 
-    let msg = ExecuteMsg::UpdateTokensBlockedlist {
-        add: Some(vec![native_asset_info("uusd".to_string())]),
-        remove: None,
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_eur_usd.to_string(),
+        amount: Uint128::zero(),
     };
-
     let err = app
         .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
-    assert_eq!("Unauthorized", err.to_string());
-
-    let err = app
-        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap_err();
     assert_eq!(
-        "ASTRO or Terra native assets (UST, LUNA etc) cannot be blocked!",
-        err.to_string()
+        err.to_string(),
+        "Amount must be greater than zero! Use ClaimRewards to claim rewards"
     );
 
-    let msg = ExecuteMsg::UpdateTokensBlockedlist {
-        add: Some(vec![token_asset_info(cny_token.clone())]),
-        remove: None,
+    // Rewards are claimed without touching the stake
+    app.update_block(|bi| next_block(bi));
+    let msg = GeneratorExecuteMsg::ClaimRewards {
+        lp_tokens: vec![lp_eur_usd.to_string()],
     };
-
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
+    check_pending_rewards(&mut app, &generator_instance, &lp_eur_usd, USER1, (0, None));
 
-    // check if we cannot change the allocation point for blocked token
-    let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![
-            (lp_cny_eur.to_string(), Uint128::from(60u32)),
-            (lp_cny_ukr.to_string(), Uint128::from(40u32)),
-            (lp_eur_msi.to_string(), Uint128::from(140u32)),
-        ],
-    };
-    let err = app
-        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap_err();
-
-    assert_eq!(
-        format!("Generic error: Token {} is blocked!", cny_token.to_string()),
-        err.to_string()
-    );
-
-    // Change pool alloc points
-    let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![(lp_eur_msi.to_string(), Uint128::from(140u32))],
-    };
-
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+    // Claimed rewards can't be claimed again
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_eur.to_string(),
-    };
-
-    // Check if alloc point is equal to 0
-    let reps: PoolInfoResponse = app
+    let deposit: Uint128 = app
         .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .query_wasm_smart(
+            &generator_instance,
+            &QueryMsg::Deposit {
+                lp_token: lp_eur_usd.to_string(),
+                user: USER1.to_string(),
+            },
+        )
         .unwrap();
-    assert_eq!(Uint128::zero(), reps.alloc_point);
+    assert_eq!(deposit, Uint128::new(10));
+}
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_ukr.to_string(),
-    };
+#[test]
+fn claim_rewards_from_multiple_generators() {
+    let mut app = mock_app();
 
-    // Check if alloc point is equal to 0
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::zero(), reps.alloc_point);
+    let user1 = Addr::unchecked(USER1);
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_eur_msi.to_string(),
-    };
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
-    // Check if alloc point is equal to 140
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::new(140), reps.alloc_point);
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
-    let msg = ExecuteMsg::UpdateTokensBlockedlist {
-        add: None,
-        remove: Some(vec![native_asset_info("eur".to_string())]),
-    };
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, token_code_id, "USD", None);
 
-    let err = app
-        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap_err();
-    assert_eq!(
-        "Generic error: Can't remove token. It is not found in the blocked list.",
-        err.to_string()
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
     );
 
-    let msg = ExecuteMsg::UpdateTokensBlockedlist {
-        add: None,
-        remove: Some(vec![token_asset_info(cny_token)]),
-    };
-
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap();
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
 
-    // Change pool alloc points
-    let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![
-            (lp_cny_eur.to_string(), Uint128::from(60u32)),
-            (lp_cny_ukr.to_string(), Uint128::from(40u32)),
-            (lp_eur_msi.to_string(), Uint128::from(140u32)),
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::new(60)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_usd.to_string(), Uint128::new(40)),
+                proxy: None,
+            },
         ],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &user1, 10);
+    deposit_lp_tokens_to_generator(
+        &mut app,
+        &generator_instance,
+        USER1,
+        &[(&lp_cny_eur, 10), (&lp_eur_usd, 10)],
+    );
+
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (6_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        USER1,
+        (4_000000, None),
+    );
+
+    let msg = GeneratorExecuteMsg::ClaimRewards {
+        lp_tokens: vec![lp_cny_eur.to_string(), lp_eur_usd.to_string()],
     };
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+    let res = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
+    // The ASTRO of both generators is claimed from vesting in a single message
+    let vesting_claims = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "action" && attr.value == "claim")
+        .count();
+    assert_eq!(vesting_claims, 1);
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
+    check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
+    check_pending_rewards(&mut app, &generator_instance, &lp_eur_usd, USER1, (0, None));
+
+    // The stakes are untouched
+    check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 10);
+    check_token_balance(&mut app, &lp_eur_usd, &generator_instance, 10);
+}
+
+#[test]
+fn query_pagination_stakers() {
+    let mut app = mock_app();
+
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+    let user3 = Addr::unchecked(USER3);
+    let user4 = Addr::unchecked(USER4);
+    let user5 = Addr::unchecked(USER5);
+    let user6 = Addr::unchecked(USER6);
+    let user7 = Addr::unchecked(USER7);
+    let user8 = Addr::unchecked(USER8);
+    let user9 = Addr::unchecked(USER9);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            AssetInfo::Token {
+                contract_addr: cny_token.clone(),
+            },
+            AssetInfo::Token {
+                contract_addr: eur_token.clone(),
+            },
+        ],
+    );
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    for user in [
+        user1, user2, user3, user4, user5, user6, user7, user8, user9,
+    ] {
+        mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user, 10);
+    }
+
+    for user in [
+        USER1, USER2, USER3, USER4, USER5, USER6, USER7, USER8, USER9,
+    ] {
+        deposit_lp_tokens_to_generator(&mut app, &generator_instance, user, &[(&lp_cny_eur, 10)]);
+    }
+
+    check_token_balance(&mut app, &lp_cny_eur, &generator_instance, 90);
+
+    // Get the first two stakers
+    let msg_cny_eur = QueryMsg::PoolStakers {
         lp_token: lp_cny_eur.to_string(),
+        start_after: None,
+        limit: Some(2),
     };
 
-    // Check if alloc point is equal to 60
-    let reps: PoolInfoResponse = app
+    let reps: Vec<StakerResponse> = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(60), reps.alloc_point);
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_ukr.to_string(),
-    };
+    // check count of users
+    assert_eq!(reps.len(), 2 as usize);
 
-    // Check if alloc point is equal to 40
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::new(40), reps.alloc_point);
+    assert_eq!(
+        vec![
+            StakerResponse {
+                account: "user1".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user2".to_string(),
+                amount: Uint128::new(10)
+            },
+        ],
+        reps
+    );
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_eur_msi.to_string(),
+    // Get the next seven stakers
+    let msg_cny_eur = QueryMsg::PoolStakers {
+        lp_token: lp_cny_eur.to_string(),
+        start_after: Some("user2".to_string()),
+        limit: Some(7),
     };
 
-    // Check if alloc point is equal to 140
-    let reps: PoolInfoResponse = app
+    let reps: Vec<StakerResponse> = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(140), reps.alloc_point);
+
+    assert_eq!(
+        vec![
+            StakerResponse {
+                account: "user3".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user4".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user5".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user6".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user7".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user8".to_string(),
+                amount: Uint128::new(10)
+            },
+            StakerResponse {
+                account: "user9".to_string(),
+                amount: Uint128::new(10)
+            },
+        ],
+        reps
+    );
 }
 
 #[test]
-fn setup_pools() {
+fn update_tokens_blocked_list() {
     let mut app = mock_app();
 
     let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
@@ -2170,23 +2595,10 @@ fn setup_pools() {
     let generator_instance =
         instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
 
-    // add generator to factory
-    let msg = FactoryExecuteMsg::UpdateConfig {
-        token_code_id: None,
-        fee_address: None,
-        generator_address: Some(generator_instance.to_string()),
-        whitelist_code_id: None,
-    };
-
-    app.execute_contract(Addr::unchecked(OWNER), factory_instance.clone(), &msg, &[])
-        .unwrap();
-
-    let res: FactoryConfigResponse = app
-        .wrap()
-        .query_wasm_smart(&factory_instance.clone(), &FactoryQueryMsg::Config {})
-        .unwrap();
-
-    assert_eq!(res.generator_address, Some(generator_instance.clone()));
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let ukr_token = instantiate_token(&mut app, token_code_id, "UKR", None);
+    let msi_token = instantiate_token(&mut app, token_code_id, "MSI", None);
 
     let (_, lp_cny_eur) = create_pair(
         &mut app,
@@ -2194,41 +2606,41 @@ fn setup_pools() {
         None,
         None,
         [
-            AssetInfo::NativeToken {
-                denom: "cny".to_string(),
+            AssetInfo::Token {
+                contract_addr: cny_token.clone(),
             },
-            AssetInfo::NativeToken {
-                denom: "eur".to_string(),
+            AssetInfo::Token {
+                contract_addr: eur_token.clone(),
             },
         ],
     );
 
-    let (_, lp_cny_uusd) = create_pair(
+    let (_, lp_cny_ukr) = create_pair(
         &mut app,
         &factory_instance,
         None,
         None,
         [
-            AssetInfo::NativeToken {
-                denom: "cny".to_string(),
+            AssetInfo::Token {
+                contract_addr: cny_token.clone(),
             },
-            AssetInfo::NativeToken {
-                denom: "uusd".to_string(),
+            AssetInfo::Token {
+                contract_addr: ukr_token.clone(),
             },
         ],
     );
 
-    let (_, lp_eur_uusd) = create_pair(
+    let (_, lp_eur_msi) = create_pair(
         &mut app,
         &factory_instance,
         None,
         None,
         [
-            AssetInfo::NativeToken {
-                denom: "eur".to_string(),
+            AssetInfo::Token {
+                contract_addr: eur_token.clone(),
             },
-            AssetInfo::NativeToken {
-                denom: "uusd".to_string(),
+            AssetInfo::Token {
+                contract_addr: msi_token.clone(),
             },
         ],
     );
@@ -2242,79 +2654,69 @@ fn setup_pools() {
                 proxy: None,
             },
             PoolWithProxy {
-                pool: (lp_cny_uusd.to_string(), Uint128::new(100)),
+                pool: (lp_cny_ukr.to_string(), Uint128::new(100)),
                 proxy: None,
             },
             PoolWithProxy {
-                pool: (lp_eur_uusd.to_string(), Uint128::new(100)),
+                pool: (lp_eur_msi.to_string(), Uint128::new(100)),
                 proxy: None,
             },
         ],
     );
 
-    // deregister pair and set the allocation point to zero for pool
-    app.execute_contract(
-        Addr::unchecked(OWNER),
-        factory_instance.clone(),
-        &FactoryExecuteMsg::Deregister {
-            asset_infos: [
-                AssetInfo::NativeToken {
-                    denom: "cny".to_string(),
-                },
-                AssetInfo::NativeToken {
-                    denom: "eur".to_string(),
-                },
-            ],
-        },
-        &[],
-    )
-    .unwrap();
+    let msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: None,
+        remove: None,
+    };
 
-    // Check if the allocation point for lp_cny_eur is equal to zero
-    let res: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            generator_instance.to_owned(),
-            &GeneratorQueryMsg::PoolInfo {
-                lp_token: lp_cny_eur.to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::zero(), res.alloc_point);
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!("Need to provide add or remove parameters", err.to_string());
 
-    // Check pool length
-    let res: PoolLengthResponse = app
-        .wrap()
-        .query_wasm_smart(
-            generator_instance.to_owned(),
-            &GeneratorQueryMsg::ActivePoolLength {},
-        )
+    let msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: Some(vec![native_asset_info("uusd".to_string())]),
+        remove: None,
+    };
+
+    let err = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!("Unauthorized", err.to_string());
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        "ASTRO or Terra native assets (UST, LUNA etc) cannot be blocked!",
+        err.to_string()
+    );
+
+    let msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: Some(vec![token_asset_info(cny_token.clone())]),
+        remove: None,
+    };
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
-    assert_eq!(3, res.length);
 
-    // Change pool alloc points
+    // check if we cannot change the allocation point for blocked token
     let msg = GeneratorExecuteMsg::SetupPools {
         pools: vec![
             (lp_cny_eur.to_string(), Uint128::from(60u32)),
-            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
-            (lp_cny_uusd.to_string(), Uint128::from(140u32)),
+            (lp_cny_ukr.to_string(), Uint128::from(40u32)),
+            (lp_eur_msi.to_string(), Uint128::from(140u32)),
         ],
     };
-
     let err = app
         .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap_err();
-    assert_eq!(
-        "Generic error: The pair aren't registered: cny-eur",
-        err.to_string()
-    );
+
+    assert_eq!(format!("Token {} is blocked!", cny_token), err.to_string());
 
     // Change pool alloc points
     let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![
-            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
-            (lp_cny_uusd.to_string(), Uint128::from(140u32)),
-        ],
+        pools: vec![(lp_eur_msi.to_string(), Uint128::from(140u32))],
     };
 
     app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
@@ -2332,32 +2734,54 @@ fn setup_pools() {
     assert_eq!(Uint128::zero(), reps.alloc_point);
 
     let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_uusd.to_string(),
+        lp_token: lp_cny_ukr.to_string(),
     };
 
-    // Check if alloc point is equal to 140
+    // Check if alloc point is equal to 0
     let reps: PoolInfoResponse = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(140), reps.alloc_point);
+    assert_eq!(Uint128::zero(), reps.alloc_point);
 
     let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_eur_uusd.to_string(),
+        lp_token: lp_eur_msi.to_string(),
     };
 
-    // Check if alloc point is equal to 40
+    // Check if alloc point is equal to 140
     let reps: PoolInfoResponse = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(40), reps.alloc_point);
+    assert_eq!(Uint128::new(140), reps.alloc_point);
+
+    let msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: None,
+        remove: Some(vec![native_asset_info("eur".to_string())]),
+    };
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        "Can't remove token eur. It is not found in the blocked list.",
+        err.to_string()
+    );
+
+    let msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: None,
+        remove: Some(vec![token_asset_info(cny_token)]),
+    };
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
 
     // Change pool alloc points
     let msg = GeneratorExecuteMsg::SetupPools {
         pools: vec![
-            (lp_eur_uusd.to_string(), Uint128::from(80u32)),
-            (lp_cny_uusd.to_string(), Uint128::from(80u32)),
+            (lp_cny_eur.to_string(), Uint128::from(60u32)),
+            (lp_cny_ukr.to_string(), Uint128::from(40u32)),
+            (lp_eur_msi.to_string(), Uint128::from(140u32)),
         ],
     };
     app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
@@ -2367,57 +2791,50 @@ fn setup_pools() {
         lp_token: lp_cny_eur.to_string(),
     };
 
-    // Check if alloc point is equal to 0
+    // Check if alloc point is equal to 60
     let reps: PoolInfoResponse = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::zero(), reps.alloc_point);
+    assert_eq!(Uint128::new(60), reps.alloc_point);
 
     let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_uusd.to_string(),
+        lp_token: lp_cny_ukr.to_string(),
     };
 
-    // Check if alloc point is equal to 80
+    // Check if alloc point is equal to 40
     let reps: PoolInfoResponse = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(80), reps.alloc_point);
+    assert_eq!(Uint128::new(40), reps.alloc_point);
 
     let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_eur_uusd.to_string(),
+        lp_token: lp_eur_msi.to_string(),
     };
 
-    // Check if alloc point is equal to 80
+    // Check if alloc point is equal to 140
     let reps: PoolInfoResponse = app
         .wrap()
         .query_wasm_smart(&generator_instance, &msg_cny_eur)
         .unwrap();
-    assert_eq!(Uint128::new(80), reps.alloc_point);
+    assert_eq!(Uint128::new(140), reps.alloc_point);
 }
 
 #[test]
-fn deactivate_pools_by_pair_types() {
+fn setup_pools() {
     let mut app = mock_app();
 
     let owner = Addr::unchecked(OWNER);
-    let user1 = Addr::unchecked(USER1);
     let token_code_id = store_token_code(&mut app);
     let factory_code_id = store_factory_code(&mut app);
-    let pair_code_id = store_pair_code_id(&mut app);
-    let pair_stable_code_id = store_pair_stable_code_id(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
     let astro_token_instance =
         instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
 
-    let factory_instance = instantiate_factory(
-        &mut app,
-        factory_code_id,
-        token_code_id,
-        pair_code_id,
-        Some(pair_stable_code_id),
-    );
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
     let generator_instance =
         instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
@@ -2428,6 +2845,7 @@ fn deactivate_pools_by_pair_types() {
         fee_address: None,
         generator_address: Some(generator_instance.to_string()),
         whitelist_code_id: None,
+        referral_registry: None,
     };
 
     app.execute_contract(Addr::unchecked(OWNER), factory_instance.clone(), &msg, &[])
@@ -2440,22 +2858,55 @@ fn deactivate_pools_by_pair_types() {
 
     assert_eq!(res.generator_address, Some(generator_instance.clone()));
 
-    let (_, lp_cny_uusd) = create_pair(
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
         &mut app,
         &factory_instance,
-        Some(PairType::Stable {}),
-        Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+        None,
+        None,
         [
             AssetInfo::NativeToken {
                 denom: "cny".to_string(),
             },
             AssetInfo::NativeToken {
-                denom: "uusd".to_string(),
+                denom: "eur".to_string(),
             },
         ],
     );
 
-    let (_, lp_cny_eur) = create_pair(
+    // A fake LP token that is minted by a registered pair can't be added
+    let fake_lp_token = app
+        .instantiate_contract(
+            token_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("FAKE"),
+                symbol: String::from("FAKE"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: pair_cny_eur.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("FAKE"),
+            None,
+        )
+        .unwrap();
+
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![(fake_lp_token.to_string(), Uint128::from(100u32))],
+    };
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        format!("Pair of {} is not registered in factory!", fake_lp_token),
+        err.to_string()
+    );
+
+    let (_, lp_cny_uusd) = create_pair(
         &mut app,
         &factory_instance,
         None,
@@ -2465,7 +2916,7 @@ fn deactivate_pools_by_pair_types() {
                 denom: "cny".to_string(),
             },
             AssetInfo::NativeToken {
-                denom: "eur".to_string(),
+                denom: "uusd".to_string(),
             },
         ],
     );
@@ -2504,54 +2955,76 @@ fn deactivate_pools_by_pair_types() {
         ],
     );
 
-    // try to deactivate pools for not blacklisted pair types
-    let msg = GeneratorExecuteMsg::DeactivatePools {
-        pair_types: vec![PairType::Xyk {}, PairType::Stable {}],
-    };
-    let err = app
-        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap_err();
-    assert_eq!(
-        format!(
-            "Generic error: Pair type ({}) is not blacklisted!",
-            PairType::Xyk {}
-        ),
-        err.to_string()
-    );
-
-    // Add stable pair type to blacklist
-    let msg = FactoryExecuteMsg::UpdatePairConfig {
-        config: PairConfig {
-            code_id: pair_stable_code_id,
-            pair_type: PairType::Stable {},
-            total_fee_bps: 100,
-            maker_fee_bps: 10,
-            is_disabled: false,
-            is_generator_disabled: true,
+    // deregister pair and set the allocation point to zero for pool
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory_instance.clone(),
+        &FactoryExecuteMsg::Deregister {
+            asset_infos: [
+                AssetInfo::NativeToken {
+                    denom: "cny".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "eur".to_string(),
+                },
+            ],
         },
-    };
+        &[],
+    )
+    .unwrap();
 
-    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
+    // Check if the allocation point for lp_cny_eur is equal to zero
+    let res: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            generator_instance.to_owned(),
+            &GeneratorQueryMsg::PoolInfo {
+                lp_token: lp_cny_eur.to_string(),
+            },
+        )
         .unwrap();
+    assert_eq!(Uint128::zero(), res.alloc_point);
 
-    // check if we add stable pair type to blacklist
-    let res: Vec<PairType> = app
+    // Check pool length
+    let res: PoolLengthResponse = app
         .wrap()
         .query_wasm_smart(
-            &factory_instance.clone(),
-            &FactoryQueryMsg::BlacklistedPairTypes {},
+            generator_instance.to_owned(),
+            &GeneratorQueryMsg::ActivePoolLength {},
         )
         .unwrap();
-    assert_eq!(res, vec![PairType::Stable {}]);
+    assert_eq!(3, res.length);
 
-    let msg = GeneratorExecuteMsg::DeactivatePools {
-        pair_types: vec![PairType::Stable {}],
+    // Change pool alloc points
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_cny_eur.to_string(), Uint128::from(60u32)),
+            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
+            (lp_cny_uusd.to_string(), Uint128::from(140u32)),
+        ],
     };
-    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        format!("Pair of {} is not registered in factory!", lp_cny_eur),
+        err.to_string()
+    );
+
+    // Change pool alloc points
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
+            (lp_cny_uusd.to_string(), Uint128::from(140u32)),
+        ],
+    };
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
 
     let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_uusd.to_string(),
+        lp_token: lp_cny_eur.to_string(),
     };
 
     // Check if alloc point is equal to 0
@@ -2561,7 +3034,235 @@ fn deactivate_pools_by_pair_types() {
         .unwrap();
     assert_eq!(Uint128::zero(), reps.alloc_point);
 
-    // try to change alloc point for blacklisted pool by pair type
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 140
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(140), reps.alloc_point);
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_eur_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 40
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(40), reps.alloc_point);
+
+    // Change pool alloc points
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_eur_uusd.to_string(), Uint128::from(80u32)),
+            (lp_cny_uusd.to_string(), Uint128::from(80u32)),
+        ],
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_eur.to_string(),
+    };
+
+    // Check if alloc point is equal to 0
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::zero(), reps.alloc_point);
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 80
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(80), reps.alloc_point);
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_eur_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 80
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(80), reps.alloc_point);
+}
+
+#[test]
+fn deactivate_pools_by_pair_types() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+    let pair_stable_code_id = store_pair_stable_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+
+    let factory_instance = instantiate_factory(
+        &mut app,
+        factory_code_id,
+        token_code_id,
+        pair_code_id,
+        Some(pair_stable_code_id),
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    // add generator to factory
+    let msg = FactoryExecuteMsg::UpdateConfig {
+        token_code_id: None,
+        fee_address: None,
+        generator_address: Some(generator_instance.to_string()),
+        whitelist_code_id: None,
+        referral_registry: None,
+    };
+
+    app.execute_contract(Addr::unchecked(OWNER), factory_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let res: FactoryConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_instance.clone(), &FactoryQueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(res.generator_address, Some(generator_instance.clone()));
+
+    let (_, lp_cny_uusd) = create_pair(
+        &mut app,
+        &factory_instance,
+        Some(PairType::Stable {}),
+        Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+        [
+            AssetInfo::NativeToken {
+                denom: "cny".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+        ],
+    );
+
+    let (_, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            AssetInfo::NativeToken {
+                denom: "cny".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "eur".to_string(),
+            },
+        ],
+    );
+
+    let (_, lp_eur_uusd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            AssetInfo::NativeToken {
+                denom: "eur".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+        ],
+    );
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_cny_uusd.to_string(), Uint128::new(100)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_uusd.to_string(), Uint128::new(100)),
+                proxy: None,
+            },
+        ],
+    );
+
+    // try to deactivate pools for not blacklisted pair types
+    let msg = GeneratorExecuteMsg::DeactivatePools {
+        pair_types: vec![PairType::Xyk {}, PairType::Stable {}],
+    };
+    let err = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        format!("Pair type ({}) is not blacklisted!", PairType::Xyk {}),
+        err.to_string()
+    );
+
+    // Add stable pair type to blacklist
+    let msg = FactoryExecuteMsg::UpdatePairConfig {
+        config: PairConfig {
+            code_id: pair_stable_code_id,
+            pair_type: PairType::Stable {},
+            total_fee_bps: 100,
+            maker_fee_bps: 10,
+            is_disabled: false,
+            is_generator_disabled: true,
+        },
+    };
+
+    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
+        .unwrap();
+
+    // check if we add stable pair type to blacklist
+    let res: Vec<PairType> = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_instance.clone(),
+            &FactoryQueryMsg::BlacklistedPairTypes {},
+        )
+        .unwrap();
+    assert_eq!(res, vec![PairType::Stable {}]);
+
+    let msg = GeneratorExecuteMsg::DeactivatePools {
+        pair_types: vec![PairType::Stable {}],
+    };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 0
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::zero(), reps.alloc_point);
+
+    // try to change alloc point for blacklisted pool by pair type
     let msg = GeneratorExecuteMsg::SetupPools {
         pools: vec![
             (lp_cny_eur.to_string(), Uint128::from(60u32)),
@@ -2570,190 +3271,2977 @@ fn deactivate_pools_by_pair_types() {
         ],
     };
 
-    let err = app
-        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap_err();
-    assert_eq!(
-        "Generic error: Pair type (stable) is blacklisted!",
-        err.to_string()
-    );
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!("Pair type (stable) is blacklisted!", err.to_string());
+
+    // Change pool alloc points
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_cny_eur.to_string(), Uint128::from(60u32)),
+            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
+        ],
+    };
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_eur.to_string(),
+    };
+
+    // Check if alloc point is equal to 60
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(60), reps.alloc_point);
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 0
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::zero(), reps.alloc_point);
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_eur_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 40
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::new(40), reps.alloc_point);
+
+    // remove stable pair type from blacklist
+    let msg = FactoryExecuteMsg::UpdatePairConfig {
+        config: PairConfig {
+            code_id: pair_stable_code_id,
+            pair_type: PairType::Stable {},
+            total_fee_bps: 100,
+            maker_fee_bps: 10,
+            is_disabled: false,
+            is_generator_disabled: false,
+        },
+    };
+
+    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
+        .unwrap();
+
+    // check if we remove stable pair type from blacklist
+    let res: Vec<PairType> = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_instance.clone(),
+            &FactoryQueryMsg::BlacklistedPairTypes {},
+        )
+        .unwrap();
+    assert_eq!(res, vec![]);
+
+    // Change pool alloc points
+    let msg = GeneratorExecuteMsg::SetupPools {
+        pools: vec![
+            (lp_eur_uusd.to_string(), Uint128::from(80u32)),
+            (lp_cny_uusd.to_string(), Uint128::from(80u32)),
+        ],
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg_cny_eur = QueryMsg::PoolInfo {
+        lp_token: lp_cny_eur.to_string(),
+    };
+
+    // Check if alloc point is equal to 0
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+        .unwrap();
+    assert_eq!(Uint128::zero(), reps.alloc_point);
+
+    let msg_cny_uusd = QueryMsg::PoolInfo {
+        lp_token: lp_cny_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 80
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_cny_uusd)
+        .unwrap();
+    assert_eq!(Uint128::new(80), reps.alloc_point);
+
+    let msg_eur_uusd = QueryMsg::PoolInfo {
+        lp_token: lp_eur_uusd.to_string(),
+    };
+
+    // Check if alloc point is equal to 80
+    let reps: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &msg_eur_uusd)
+        .unwrap();
+    assert_eq!(Uint128::new(80), reps.alloc_point);
+}
+
+#[test]
+fn keeper_incentive() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let keeper = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (_, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (_, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_usd.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+        ],
+    );
+
+    let keeper_incentive = KeeperIncentive {
+        tip_per_pool: Uint128::new(100),
+        max_tip: Uint128::new(150),
+        staleness_threshold: 5,
+    };
+    let msg = GeneratorExecuteMsg::SetKeeperIncentive {
+        keeper_incentive: Some(keeper_incentive.clone()),
+    };
+
+    let err = app
+        .execute_contract(keeper.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let res: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(res.keeper_incentive, Some(keeper_incentive));
+    assert_eq!(res.keeper_tip_budget, Uint128::zero());
+
+    // Only ASTRO can fund keeper tips
+    mint_tokens(&mut app, owner.clone(), &cny_token, &owner, 1000);
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            cny_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: generator_instance.to_string(),
+                amount: Uint128::new(1000),
+                msg: to_binary(&GeneratorHookMsg::FundKeeperTips {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Keeper tips can only be funded with ASTRO!"
+    );
+
+    // Tips are paid from the budget, not from the vesting allocation of stakers
+    mint_tokens(&mut app, owner.clone(), &astro_token_instance, &owner, 300);
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: generator_instance.to_string(),
+            amount: Uint128::new(300),
+            msg: to_binary(&GeneratorHookMsg::FundKeeperTips {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Updating fresh generators is allowed but isn't tipped
+    for _ in 0..4 {
+        app.update_block(|bi| next_block(bi));
+    }
+    app.execute_contract(
+        keeper.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &keeper, 0);
+
+    // A single stale generator
+    for _ in 0..5 {
+        app.update_block(|bi| next_block(bi));
+    }
+    app.execute_contract(
+        keeper.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::UpdatePoolRewards {
+            lp_token: lp_cny_eur.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &keeper, 100);
+
+    // Both generators are stale, but the tip is capped
+    for _ in 0..5 {
+        app.update_block(|bi| next_block(bi));
+    }
+    app.execute_contract(
+        keeper.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &keeper, 250);
+    check_token_balance(&mut app, &astro_token_instance, &generator_instance, 50);
+
+    // The tip is also capped by the remaining budget
+    for _ in 0..5 {
+        app.update_block(|bi| next_block(bi));
+    }
+    app.execute_contract(
+        keeper.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &keeper, 300);
+
+    let res: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(res.keeper_tip_budget, Uint128::zero());
+
+    // Keeper tips can be disabled
+    app.execute_contract(
+        owner,
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::SetKeeperIncentive {
+            keeper_incentive: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    for _ in 0..5 {
+        app.update_block(|bi| next_block(bi));
+    }
+    app.execute_contract(
+        keeper.clone(),
+        generator_instance,
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &keeper, 300);
+}
+
+#[test]
+fn pool_apr_inputs() {
+    let mut app = mock_app();
+
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (_, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    // Nothing is distributed until a generator is active
+    let res: EmissionRateResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::EmissionRate {})
+        .unwrap();
+    assert_eq!(res.tokens_per_block, Uint128::zero());
+    assert_eq!(res.configured_tokens_per_block, Uint128::new(10_000000));
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_usd.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+        ],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 6)]);
+
+    let res: PoolAprInputsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &GeneratorQueryMsg::PoolAprInputs {
+                lp_token: lp_cny_eur.to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.lp_token, lp_cny_eur);
+    assert_eq!(res.pair, pair_cny_eur);
+    // Half of the 10 ASTRO emitted per block goes to this generator
+    assert_eq!(res.astro_tokens_per_block, Uint128::new(5_000000));
+    assert_eq!(res.proxy_rewards_per_block, vec![]);
+    assert_eq!(res.lp_staked, Uint128::new(6));
+    assert_eq!(res.lp_total_supply, Uint128::new(10));
+    assert_eq!(res.assets[0].info, token_asset_info(cny_token));
+    assert_eq!(res.assets[1].info, token_asset_info(eur_token));
+    assert_eq!(res.total_fee_rate, Decimal::percent(1));
+    assert_eq!(res.current_block, app.block_info().height);
+
+    let res: EmissionRateResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::EmissionRate {})
+        .unwrap();
+    assert_eq!(
+        res,
+        EmissionRateResponse {
+            tokens_per_block: Uint128::new(10_000000),
+            configured_tokens_per_block: Uint128::new(10_000000),
+            start_block: Uint64::from(app.block_info().height),
+            current_block: app.block_info().height,
+        }
+    );
+}
+
+#[test]
+fn native_funds() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    let funds = vec![Coin {
+        denom: "uluna".to_string(),
+        amount: Uint128::new(100),
+    }];
+    app.init_bank_balance(&user1, funds.clone()).unwrap();
+
+    // Native coins are rejected by every handler
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &GeneratorExecuteMsg::MassUpdatePools {},
+            &funds,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The Generator doesn't accept native coins!"
+    );
+
+    let msg = GeneratorExecuteMsg::SweepNativeTokens {
+        recipient: Some(USER2.to_string()),
+    };
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "There are no native coins to sweep!");
+
+    // Coins sent before the Generator started rejecting them are stuck
+    app.init_bank_balance(&generator_instance, funds).unwrap();
+
+    let err = app
+        .execute_contract(user1, generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    app.execute_contract(owner, generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let balance = app
+        .wrap()
+        .query_balance(&generator_instance, "uluna")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+    let balance = app.wrap().query_balance(USER2, "uluna").unwrap();
+    assert_eq!(balance.amount, Uint128::new(100));
+}
+
+#[test]
+fn operator_claims_rewards() {
+    let mut app = mock_app();
+
+    let user1 = Addr::unchecked(USER1);
+    let operator = Addr::unchecked(USER2);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+
+    app.update_block(|bi| next_block(bi));
+
+    let claim_msg = GeneratorExecuteMsg::ClaimRewardsFor {
+        user: USER1.to_string(),
+        lp_token: lp_cny_eur.to_string(),
+    };
+    let query_approved = |app: &TerraApp| -> bool {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &GeneratorQueryMsg::OperatorApproved {
+                    user: USER1.to_string(),
+                    lp_token: lp_cny_eur.to_string(),
+                    operator: USER2.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let err = app
+        .execute_contract(
+            operator.clone(),
+            generator_instance.clone(),
+            &claim_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &GeneratorExecuteMsg::ApproveOperator {
+                operator: USER1.to_string(),
+                lp_token: lp_cny_eur.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "You can't approve yourself as an operator!"
+    );
+
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::ApproveOperator {
+            operator: USER2.to_string(),
+            lp_token: lp_cny_eur.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(query_approved(&app));
+
+    // Rewards are sent to the user, not the operator
+    app.execute_contract(
+        operator.clone(),
+        generator_instance.clone(),
+        &claim_msg,
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
+    check_token_balance(&mut app, &astro_token_instance, &operator, 0);
+    check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
+
+    let revoke_msg = GeneratorExecuteMsg::RevokeOperator {
+        operator: USER2.to_string(),
+        lp_token: lp_cny_eur.to_string(),
+    };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &revoke_msg, &[])
+        .unwrap();
+    assert!(!query_approved(&app));
+
+    let err = app
+        .execute_contract(user1, generator_instance.clone(), &revoke_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "The operator is not approved!");
+
+    app.update_block(|bi| next_block(bi));
+    let err = app
+        .execute_contract(operator, generator_instance.clone(), &claim_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+}
+
+#[test]
+fn alternative_base_rewards() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let alt_token_instance = instantiate_token(&mut app, token_code_id, "ALT", None);
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (_, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    // Only the CNY-EUR generator gets ASTRO
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    let set_alt_reward = |reward_token: &Addr| GeneratorExecuteMsg::SetAltReward {
+        lp_token: lp_eur_usd.to_string(),
+        reward_token: reward_token.to_string(),
+        tokens_per_block: Uint128::new(100),
+    };
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &set_alt_reward(&alt_token_instance),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Generator for {} doesn't exist!", lp_eur_usd)
+    );
+
+    // The EUR-USD generator is created by the first deposit
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_eur_usd, 10)]);
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &set_alt_reward(&alt_token_instance),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &set_alt_reward(&lp_cny_eur),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generator LP tokens can't be used as alternative reward tokens!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &set_alt_reward(&alt_token_instance),
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &set_alt_reward(&cny_token),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The alternative reward token of a generator can't be changed!"
+    );
+
+    let fund = |app: &mut TerraApp, token: &Addr, amount: u128| {
+        mint_tokens(app, owner.clone(), token, &owner, amount);
+        app.execute_contract(
+            owner.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: generator_instance.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&GeneratorHookMsg::FundAltReward {
+                    lp_token: lp_eur_usd.to_string(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+    };
+    let query_pending_alt = |app: &TerraApp| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &GeneratorQueryMsg::PendingAltReward {
+                    lp_token: lp_eur_usd.to_string(),
+                    user: USER1.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let err = fund(&mut app, &cny_token, 100).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "The generator for {} doesn't have an alternative reward!",
+            lp_eur_usd
+        )
+    );
+
+    fund(&mut app, &alt_token_instance, 250).unwrap();
+
+    app.update_block(|bi| next_block(bi));
+    assert_eq!(query_pending_alt(&app), Uint128::new(100));
+    check_pending_rewards(&mut app, &generator_instance, &lp_eur_usd, USER1, (0, None));
+
+    // Emissions stop when the budget runs out
+    app.update_block(|bi| next_block(bi));
+    app.update_block(|bi| next_block(bi));
+    assert_eq!(query_pending_alt(&app), Uint128::new(250));
+
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::ClaimRewards {
+            lp_tokens: vec![lp_eur_usd.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    check_token_balance(&mut app, &alt_token_instance, &user1, 250);
+    check_token_balance(&mut app, &astro_token_instance, &user1, 0);
+    assert_eq!(query_pending_alt(&app), Uint128::zero());
+
+    let res: Option<AltRewardInfo> = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &GeneratorQueryMsg::AltReward {
+                lp_token: lp_eur_usd.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Some(AltRewardInfo {
+            reward_token: alt_token_instance.clone(),
+            tokens_per_block: Uint128::new(100),
+            budget: Uint128::zero(),
+        })
+    );
+
+    // Blocks without budget aren't paid out by later funding
+    app.update_block(|bi| next_block(bi));
+    fund(&mut app, &alt_token_instance, 100).unwrap();
+    assert_eq!(query_pending_alt(&app), Uint128::zero());
+
+    app.update_block(|bi| next_block(bi));
+    assert_eq!(query_pending_alt(&app), Uint128::new(100));
+
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_eur_usd.to_string(),
+        amount: Uint128::new(10),
+    };
+    app.execute_contract(user1.clone(), generator_instance, &msg, &[])
+        .unwrap();
+    check_token_balance(&mut app, &alt_token_instance, &user1, 350);
+    check_token_balance(&mut app, &lp_eur_usd, &user1, 10);
+}
+
+#[test]
+fn scheduled_alloc_points() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    // Only the CNY-EUR generator gets ASTRO
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    // The EUR-USD generator is created by the first deposit
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &user1, 10);
+    deposit_lp_tokens_to_generator(
+        &mut app,
+        &generator_instance,
+        USER1,
+        &[(&lp_cny_eur, 10), (&lp_eur_usd, 10)],
+    );
+
+    let current_block = app.block_info().height;
+    let schedule_set = |effective_block: u64| GeneratorExecuteMsg::ScheduleSet {
+        lp_token: lp_eur_usd.to_string(),
+        alloc_point: Uint128::new(100),
+        effective_block,
+    };
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &schedule_set(current_block + 2),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &schedule_set(current_block),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The effective block must be in the future!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &schedule_set(current_block + 2),
+        &[],
+    )
+    .unwrap();
+
+    let scheduled_sets: Vec<ScheduledSet> = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::ScheduledSets {})
+        .unwrap();
+    assert_eq!(
+        scheduled_sets,
+        vec![ScheduledSet {
+            lp_token: lp_eur_usd.clone(),
+            alloc_point: Uint128::new(100),
+            effective_block: current_block + 2,
+        }]
+    );
+
+    // No transaction is needed at the effective block
+    for _ in 0..4 {
+        app.update_block(|bi| next_block(bi));
+    }
+
+    // CNY-EUR gets all 10 ASTRO per block for 2 blocks, then half of them for 2 blocks
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (30_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        USER1,
+        (10_000000, None),
+    );
+
+    // The change is applied during the next pool update
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.active_pools,
+        vec![
+            (lp_cny_eur.clone(), Uint128::new(100)),
+            (lp_eur_usd.clone(), Uint128::new(100)),
+        ]
+    );
+    assert_eq!(config.total_alloc_point, Uint128::new(200));
+
+    let scheduled_sets: Vec<ScheduledSet> = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::ScheduledSets {})
+        .unwrap();
+    assert!(scheduled_sets.is_empty());
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (30_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        USER1,
+        (10_000000, None),
+    );
+
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        USER1,
+        (15_000000, None),
+    );
+
+    // Scheduled changes can be cancelled until they're applied
+    let current_block = app.block_info().height;
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &schedule_set(current_block + 10),
+        &[],
+    )
+    .unwrap();
+
+    let cancel_scheduled_set = GeneratorExecuteMsg::CancelScheduledSet {
+        lp_token: lp_eur_usd.to_string(),
+        effective_block: current_block + 10,
+    };
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &cancel_scheduled_set,
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner,
+            generator_instance.clone(),
+            &cancel_scheduled_set,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "No allocation point change is scheduled for {} at block {}!",
+            lp_eur_usd,
+            current_block + 10
+        )
+    );
+}
+
+#[test]
+fn set_tokens_per_block_applies_due_scheduled_sets() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (pair_eur_usd, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &owner, 10);
+    mint_tokens(&mut app, pair_eur_usd, &lp_eur_usd, &owner, 10);
+    deposit_lp_tokens_to_generator(
+        &mut app,
+        &generator_instance,
+        OWNER,
+        &[(&lp_cny_eur, 10), (&lp_eur_usd, 10)],
+    );
+
+    // The EUR-USD generator is activated at the effective block
+    let current_block = app.block_info().height;
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::ScheduleSet {
+            lp_token: lp_eur_usd.to_string(),
+            alloc_point: Uint128::new(100),
+            effective_block: current_block + 2,
+        },
+        &[],
+    )
+    .unwrap();
+
+    for _ in 0..4 {
+        app.update_block(|bi| next_block(bi));
+    }
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::SetTokensPerBlock {
+            amount: Uint128::new(20_000000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Both generators accrued their past rewards at the previous rate
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        OWNER,
+        (30_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        OWNER,
+        (10_000000, None),
+    );
+
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        OWNER,
+        (40_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_eur_usd,
+        OWNER,
+        (20_000000, None),
+    );
+}
+
+#[test]
+fn emission_schedule() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::from(100u32)),
+            proxy: None,
+        }],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+
+    let current_block = app.block_info().height;
+    let add_emission_epoch =
+        |start_block: u64, tokens_per_block: u128| GeneratorExecuteMsg::AddEmissionEpoch {
+            start_block,
+            tokens_per_block: Uint128::new(tokens_per_block),
+        };
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &add_emission_epoch(current_block + 2, 5_000000),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &add_emission_epoch(current_block, 5_000000),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Emission epochs must start in the future, after the last scheduled epoch!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &add_emission_epoch(current_block + 2, 5_000000),
+        &[],
+    )
+    .unwrap();
+
+    // Epochs can only be appended
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &add_emission_epoch(current_block + 2, 2_000000),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Emission epochs must start in the future, after the last scheduled epoch!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &add_emission_epoch(current_block + 4, 0),
+        &[],
+    )
+    .unwrap();
+
+    let emission_schedule: Vec<EmissionEpoch> = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::EmissionSchedule {})
+        .unwrap();
+    assert_eq!(
+        emission_schedule,
+        vec![
+            EmissionEpoch {
+                start_block: current_block + 2,
+                tokens_per_block: Uint128::new(5_000000),
+            },
+            EmissionEpoch {
+                start_block: current_block + 4,
+                tokens_per_block: Uint128::zero(),
+            },
+        ]
+    );
+
+    let simulated_reward: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &GeneratorQueryMsg::SimulateFutureReward {
+                lp_token: lp_cny_eur.to_string(),
+                future_block: current_block + 6,
+            },
+        )
+        .unwrap();
+    assert_eq!(simulated_reward, Uint128::new(30_000000));
+
+    // No transaction is needed when an epoch starts
+    for _ in 0..3 {
+        app.update_block(|bi| next_block(bi));
+    }
+
+    let res: EmissionRateResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::EmissionRate {})
+        .unwrap();
+    assert_eq!(res.tokens_per_block, Uint128::new(5_000000));
+    assert_eq!(res.configured_tokens_per_block, Uint128::new(5_000000));
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (25_000000, None),
+    );
+
+    for _ in 0..3 {
+        app.update_block(|bi| next_block(bi));
+    }
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (30_000000, None),
+    );
+
+    // Started epochs are applied during the next pool update
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::MassUpdatePools {},
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.tokens_per_block, Uint128::zero());
+
+    let emission_schedule: Vec<EmissionEpoch> = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::EmissionSchedule {})
+        .unwrap();
+    assert!(emission_schedule.is_empty());
+
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (30_000000, None),
+    );
+
+    // Epochs can be cancelled until they start
+    let current_block = app.block_info().height;
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &add_emission_epoch(current_block + 10, 10_000000),
+        &[],
+    )
+    .unwrap();
+
+    let cancel_emission_epoch = GeneratorExecuteMsg::CancelEmissionEpoch {
+        start_block: current_block + 10,
+    };
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &cancel_emission_epoch,
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner,
+            generator_instance.clone(),
+            &cancel_emission_epoch,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "No emission epoch is scheduled to start at block {}!",
+            current_block + 10
+        )
+    );
+}
+
+#[test]
+fn remove_pool() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_eur_token_code_id = store_token_code(&mut app);
+
+    let cny_token = instantiate_token(&mut app, cny_eur_token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, cny_eur_token_code_id, "EUR", None);
+    let usd_token = instantiate_token(&mut app, cny_eur_token_code_id, "USD", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let (_, lp_eur_usd) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(eur_token.clone()),
+            token_asset_info(usd_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![
+            PoolWithProxy {
+                pool: (lp_cny_eur.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+            PoolWithProxy {
+                pool: (lp_eur_usd.to_string(), Uint128::from(50u32)),
+                proxy: None,
+            },
+        ],
+    );
+
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+
+    let remove_pool = GeneratorExecuteMsg::RemovePool {
+        lp_token: lp_cny_eur.to_string(),
+    };
+
+    let err = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &remove_pool, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &remove_pool, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generator for {} is active or has scheduled allocation point changes!",
+            lp_cny_eur
+        )
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::SetupPools {
+            pools: vec![(lp_eur_usd.to_string(), Uint128::from(50u32))],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(owner.clone(), generator_instance.clone(), &remove_pool, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Generator for {} still has stakers!", lp_cny_eur)
+    );
+
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::Withdraw {
+            lp_token: lp_cny_eur.to_string(),
+            amount: Uint128::new(10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(owner.clone(), generator_instance.clone(), &remove_pool, &[])
+        .unwrap();
+
+    let res: PoolLengthResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &QueryMsg::PoolLength {})
+        .unwrap();
+    assert_eq!(res.length, 1);
+
+    let pools: Vec<PoolListResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &QueryMsg::PoolList {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pools
+            .into_iter()
+            .map(|pool| pool.lp_token)
+            .collect::<Vec<_>>(),
+        vec![lp_eur_usd.clone()]
+    );
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.active_pools,
+        vec![(lp_eur_usd.clone(), Uint128::from(50u32))]
+    );
+    assert_eq!(config.total_alloc_point, Uint128::from(50u32));
+
+    // A new deposit creates the generator again
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+
+    let res: PoolLengthResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &QueryMsg::PoolLength {})
+        .unwrap();
+    assert_eq!(res.length, 2);
+}
+
+#[test]
+fn boosted_rewards() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
+    );
+
+    let boost_provider_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        boost_provider_execute,
+        boost_provider_instantiate,
+        boost_provider_query,
+    )));
+    let boost_provider_instance = app
+        .instantiate_contract(
+            boost_provider_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "Boost provider",
+            None,
+        )
+        .unwrap();
+
+    let set_boost_provider = |max_multiplier: Decimal| GeneratorExecuteMsg::SetBoostProvider {
+        boost_provider: UpdateAddr::Set(boost_provider_instance.to_string()),
+        max_multiplier,
+    };
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &set_boost_provider(Decimal::percent(200)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &set_boost_provider(Decimal::percent(300)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The maximum boost multiplier must be between 1 and 2.5!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &set_boost_provider(Decimal::percent(200)),
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.boost_provider,
+        Some(BoostProvider {
+            contract: boost_provider_instance.clone(),
+            max_multiplier: Decimal::percent(200),
+        })
+    );
+
+    // User1's multiplier is capped at 2x, User2 has no boost
+    app.execute_contract(
+        user1.clone(),
+        boost_provider_instance.clone(),
+        &BoostResponse {
+            multiplier: Decimal::percent(300),
+        },
+        &[],
+    )
+    .unwrap();
+
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user2, 30);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER2, &[(&lp_cny_eur, 30)]);
+
+    let query_boost_bonus = |app: &TerraApp, user: &str| {
+        let res: UserInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::UserInfo {
+                    lp_token: lp_cny_eur.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap();
+        res.boost_bonus
+    };
+    assert_eq!(query_boost_bonus(&app, USER1), Uint128::new(10));
+    assert_eq!(query_boost_bonus(&app, USER2), Uint128::zero());
+
+    // User1 stakes 20 boosted LP tokens out of 50
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (4_000000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER2,
+        (6_000000, None),
+    );
+
+    // The boost is kept until User1 is checkpointed
+    app.execute_contract(
+        user1.clone(),
+        boost_provider_instance,
+        &BoostResponse {
+            multiplier: Decimal::one(),
+        },
+        &[],
+    )
+    .unwrap();
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (4_000000, None),
+    );
+
+    // Anyone can checkpoint a staker, pending rewards are sent to the staker
+    let res = app
+        .execute_contract(
+            user2.clone(),
+            generator_instance.clone(),
+            &GeneratorExecuteMsg::CheckpointUserBoost {
+                lp_token: lp_cny_eur.to_string(),
+                user: USER1.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    // The stake is left as is, no withdrawal is executed on behalf of the staker
+    let actions = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "action")
+        .map(|attr| attr.value.as_str())
+        .collect::<Vec<_>>();
+    assert!(actions.contains(&"checkpoint_user_boost"));
+    assert!(!actions.contains(&"withdraw"));
+    assert_eq!(query_boost_bonus(&app, USER1), Uint128::zero());
+    check_token_balance(&mut app, &astro_token_instance, &user1, 4_000000);
+    check_token_balance(&mut app, &lp_cny_eur, &user1, 0);
+    check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
+
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (2_500000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER2,
+        (13_500000, None),
+    );
+
+    let err = app
+        .execute_contract(
+            user2,
+            generator_instance.clone(),
+            &GeneratorExecuteMsg::CheckpointUserBoost {
+                lp_token: lp_cny_eur.to_string(),
+                user: USER3.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "{} has no LP tokens staked in the generator for {}!",
+            USER3, lp_cny_eur
+        )
+    );
+}
+
+const BOOST_MULTIPLIERS: Map<&Addr, Decimal> = Map::new("boost_multipliers");
+
+fn boost_provider_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn boost_provider_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: BoostResponse,
+) -> StdResult<Response> {
+    BOOST_MULTIPLIERS.save(deps.storage, &info.sender, &msg.multiplier)?;
+    Ok(Response::new())
+}
+
+fn boost_provider_query(deps: Deps, _env: Env, msg: BoostQueryMsg) -> StdResult<Binary> {
+    match msg {
+        BoostQueryMsg::Boost { user } => {
+            let multiplier = BOOST_MULTIPLIERS
+                .may_load(deps.storage, &Addr::unchecked(user))?
+                .unwrap_or_else(Decimal::one);
+            to_binary(&BoostResponse { multiplier })
+        }
+    }
+}
+
+#[test]
+fn vxastro_boosted_rewards() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
+    );
+
+    let voting_escrow_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        voting_escrow_execute,
+        boost_provider_instantiate,
+        voting_escrow_query,
+    )));
+    let voting_escrow_instance = app
+        .instantiate_contract(
+            voting_escrow_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "Voting escrow",
+            None,
+        )
+        .unwrap();
+
+    let set_voting_escrow = GeneratorExecuteMsg::SetVotingEscrow {
+        voting_escrow: UpdateAddr::Set(voting_escrow_instance.to_string()),
+    };
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &set_voting_escrow,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &set_voting_escrow,
+        &[],
+    )
+    .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.voting_escrow, Some(voting_escrow_instance.clone()));
+
+    let set_voting_power = |app: &mut TerraApp, user: &Addr, voting_power: u128| {
+        app.execute_contract(
+            user.clone(),
+            voting_escrow_instance.clone(),
+            &VotingPowerResponse {
+                voting_power: Uint128::new(voting_power),
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // User1 holds all vxASTRO, User2 has none
+    set_voting_power(&mut app, &user1, 100);
+
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 20);
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user2, 30);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 20)]);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER2, &[(&lp_cny_eur, 30)]);
+
+    let query_boost_bonus = |app: &TerraApp, user: &str| {
+        let res: UserInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &QueryMsg::UserInfo {
+                    lp_token: lp_cny_eur.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap();
+        res.boost_bonus
+    };
+    // User1's position counts 2.5 times
+    assert_eq!(query_boost_bonus(&app, USER1), Uint128::new(30));
+    assert_eq!(query_boost_bonus(&app, USER2), Uint128::zero());
+
+    // User1 stakes 50 virtual LP tokens out of 80
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (6_250000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER2,
+        (3_750000, None),
+    );
+
+    // User2 locks ASTRO. Their position is re-boosted once it's checkpointed
+    set_voting_power(&mut app, &user2, 300);
+    assert_eq!(query_boost_bonus(&app, USER2), Uint128::zero());
+
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::CheckpointUserBoost {
+            lp_token: lp_cny_eur.to_string(),
+            user: USER2.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_boost_bonus(&app, USER2), Uint128::new(45));
+    check_token_balance(&mut app, &astro_token_instance, &user2, 3_750000);
+
+    // User1 keeps the boost from their last checkpoint: 50 virtual LP tokens out of 125
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (10_250000, None),
+    );
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER2,
+        (6_000000, None),
+    );
+
+    // Boosts are recomputed on withdrawal, User1 now holds a quarter of the voting power
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::Withdraw {
+            lp_token: lp_cny_eur.to_string(),
+            amount: Uint128::new(10),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_boost_bonus(&app, USER1), Uint128::new(15));
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_250000);
+
+    // User1's lock expires. The boost is kept until User1 re-checkpoints their position
+    set_voting_power(&mut app, &user1, 0);
+    app.update_block(|bi| next_block(bi));
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur,
+        USER1,
+        (2_500000, None),
+    );
+
+    app.execute_contract(
+        user1.clone(),
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::CheckpointUserBoost {
+            lp_token: lp_cny_eur.to_string(),
+            user: USER1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_boost_bonus(&app, USER1), Uint128::zero());
+    assert_eq!(query_boost_bonus(&app, USER2), Uint128::new(45));
+    check_token_balance(&mut app, &astro_token_instance, &user1, 12_750000);
+    check_pending_rewards(&mut app, &generator_instance, &lp_cny_eur, USER1, (0, None));
+}
+
+const VOTING_POWER: Map<&Addr, Uint128> = Map::new("voting_power");
+
+fn voting_escrow_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: VotingPowerResponse,
+) -> StdResult<Response> {
+    VOTING_POWER.save(deps.storage, &info.sender, &msg.voting_power)?;
+    Ok(Response::new())
+}
+
+fn voting_escrow_query(deps: Deps, _env: Env, msg: VotingEscrowQueryMsg) -> StdResult<Binary> {
+    match msg {
+        VotingEscrowQueryMsg::UserVotingPower { user } => {
+            let voting_power = VOTING_POWER
+                .may_load(deps.storage, &Addr::unchecked(user))?
+                .unwrap_or_default();
+            to_binary(&VotingPowerResponse { voting_power })
+        }
+        VotingEscrowQueryMsg::TotalVotingPower {} => {
+            let voting_power = VOTING_POWER
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, voting_power)| voting_power))
+                .sum::<StdResult<Uint128>>()?;
+            to_binary(&VotingPowerResponse { voting_power })
+        }
+    }
+}
+
+#[test]
+fn failing_proxy_rewards_update() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    let (mirror_token_instance, mirror_staking_instance) =
+        instantiate_mirror_protocol(&mut app, token_code_id, &pair_cny_eur, &lp_cny_eur);
+
+    // The proxy works as usual, except that its rewards can't be updated
+    let failing_proxy_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        failing_proxy_execute,
+        astroport_generator_proxy_to_mirror::contract::instantiate,
+        astroport_generator_proxy_to_mirror::contract::query,
+    )));
+    let proxy_instance = instantiate_proxy(
+        &mut app,
+        failing_proxy_code_id,
+        &generator_instance,
+        &pair_cny_eur,
+        &lp_cny_eur,
+        &mirror_staking_instance,
+        &mirror_token_instance,
+    );
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
+    );
+
+    let msg = GeneratorExecuteMsg::SetAllowedRewardProxies {
+        proxies: vec![proxy_instance.to_string()],
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = GeneratorExecuteMsg::MoveToProxy {
+        lp_token: lp_cny_eur.to_string(),
+        proxy: proxy_instance.to_string(),
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 5)]);
+
+    app.update_block(|bi| next_block(bi));
+
+    // The proxy has pending rewards, so the next deposit tries to update them
+    mint_tokens(
+        &mut app,
+        owner.clone(),
+        &mirror_token_instance,
+        &owner,
+        50_000000,
+    );
+    let msg = Cw20ExecuteMsg::Send {
+        contract: mirror_staking_instance.to_string(),
+        msg: to_binary(&MirrorStakingHookMsg::DepositReward {
+            rewards: vec![(pair_cny_eur.to_string(), Uint128::new(50_000000))],
+        })
+        .unwrap(),
+        amount: Uint128::new(50_000000),
+    };
+    app.execute_contract(owner, mirror_token_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = Cw20ExecuteMsg::Send {
+        contract: generator_instance.to_string(),
+        msg: to_binary(&GeneratorHookMsg::Deposit {}).unwrap(),
+        amount: Uint128::new(5),
+    };
+    let res = app
+        .execute_contract(user1, lp_cny_eur.clone(), &msg, &[])
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "proxy_rewards_update_failed")));
+
+    // The deposit went through without any new proxy rewards
+    check_token_balance(&mut app, &lp_cny_eur, &mirror_staking_instance, 10);
+    check_token_balance(&mut app, &mirror_token_instance, &proxy_instance, 0);
+}
+
+fn failing_proxy_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ProxyExecuteMsg,
+) -> Result<Response, ProxyContractError> {
+    if let ProxyExecuteMsg::UpdateRewards {} = msg {
+        return Err(StdError::generic_err("Rewards can't be updated").into());
+    }
+
+    astroport_generator_proxy_to_mirror::contract::execute(deps, env, info, msg)
+}
+
+#[test]
+fn multiple_reward_proxies() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+    let bonus_token = instantiate_token(&mut app, token_code_id, "BONUS", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
+
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
+
+    let (mirror_token_instance, mirror_staking_instance) =
+        instantiate_mirror_protocol(&mut app, token_code_id, &pair_cny_eur, &lp_cny_eur);
+    let proxy_code_id = store_proxy_code(&mut app);
+    let mirror_proxy = instantiate_proxy(
+        &mut app,
+        proxy_code_id,
+        &generator_instance,
+        &pair_cny_eur,
+        &lp_cny_eur,
+        &mirror_staking_instance,
+        &mirror_token_instance,
+    );
+
+    // The reward-only proxies pay BONUS tokens without holding LP tokens
+    let reward_proxy_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        reward_proxy_execute,
+        reward_proxy_instantiate,
+        reward_proxy_query,
+    )));
+    let mut instantiate_reward_proxy = |lp_token: &Addr| {
+        instantiate_proxy(
+            &mut app,
+            reward_proxy_code_id,
+            &generator_instance,
+            &pair_cny_eur,
+            lp_token,
+            &generator_instance,
+            &bonus_token,
+        )
+    };
+    let bonus_proxy = instantiate_reward_proxy(&lp_cny_eur);
+    let mismatched_proxy = instantiate_reward_proxy(&eur_token);
+    let extra_proxy = instantiate_reward_proxy(&lp_cny_eur);
+    let excess_proxy = instantiate_reward_proxy(&lp_cny_eur);
+
+    let msg = GeneratorExecuteMsg::SetAllowedRewardProxies {
+        proxies: vec![
+            mirror_proxy.to_string(),
+            bonus_proxy.to_string(),
+            mismatched_proxy.to_string(),
+            extra_proxy.to_string(),
+            excess_proxy.to_string(),
+        ],
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
+    );
+
+    let add_reward_proxy = |proxy: &Addr| GeneratorExecuteMsg::AddRewardProxy {
+        lp_token: lp_cny_eur.to_string(),
+        proxy: proxy.to_string(),
+    };
+
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            generator_instance.clone(),
+            &add_reward_proxy(&bonus_proxy),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    // The LP tokens must be staked with a proxy first
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &add_reward_proxy(&bonus_proxy),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "The generator for {} must have a reward proxy staking its LP tokens before other reward proxies are added!",
+            lp_cny_eur
+        )
+    );
+
+    let msg = GeneratorExecuteMsg::MoveToProxy {
+        lp_token: lp_cny_eur.to_string(),
+        proxy: mirror_proxy.to_string(),
+    };
+    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &add_reward_proxy(&mirror_proxy),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "The pool {} already has the reward proxy contract {}!",
+            lp_cny_eur, mirror_proxy
+        )
+    );
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            generator_instance.clone(),
+            &add_reward_proxy(&mismatched_proxy),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "The reward proxy {} doesn't pay rewards for {} in this Generator!",
+            mismatched_proxy, lp_cny_eur
+        )
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &add_reward_proxy(&bonus_proxy),
+        &[],
+    )
+    .unwrap();
+
+    let res: RewardInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &GeneratorQueryMsg::RewardInfo {
+                lp_token: lp_cny_eur.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.proxy_reward_tokens,
+        vec![mirror_token_instance.clone(), bonus_token.clone()]
+    );
+
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user1, 10);
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user2, 30);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER2, &[(&lp_cny_eur, 30)]);
+
+    // Only the first proxy holds the LP tokens
+    check_token_balance(&mut app, &lp_cny_eur, &mirror_staking_instance, 40);
+    check_token_balance(&mut app, &lp_cny_eur, &bonus_proxy, 0);
+
+    app.update_block(|bi| next_block(bi));
+
+    // Both proxies receive 40 reward tokens for the stakers
+    mint_tokens(
+        &mut app,
+        owner.clone(),
+        &mirror_token_instance,
+        &owner,
+        40_000000,
+    );
+    let msg = Cw20ExecuteMsg::Send {
+        contract: mirror_staking_instance.to_string(),
+        msg: to_binary(&MirrorStakingHookMsg::DepositReward {
+            rewards: vec![(pair_cny_eur.to_string(), Uint128::new(40_000000))],
+        })
+        .unwrap(),
+        amount: Uint128::new(40_000000),
+    };
+    app.execute_contract(owner.clone(), mirror_token_instance.clone(), &msg, &[])
+        .unwrap();
+
+    mint_tokens(&mut app, owner.clone(), &bonus_token, &owner, 40_000000);
+    let msg = Cw20ExecuteMsg::Send {
+        contract: bonus_proxy.to_string(),
+        msg: to_binary(&Empty {}).unwrap(),
+        amount: Uint128::new(40_000000),
+    };
+    app.execute_contract(owner.clone(), bonus_token.clone(), &msg, &[])
+        .unwrap();
+
+    let query_pending_on_proxy = |app: &TerraApp, user: &str| {
+        let res: PendingTokenResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &GeneratorQueryMsg::PendingToken {
+                    lp_token: lp_cny_eur.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap();
+        res.pending_on_proxy
+    };
+    let proxy_rewards = |amount: u128| {
+        Some(vec![
+            Asset {
+                info: token_asset_info(mirror_token_instance.clone()),
+                amount: Uint128::new(amount),
+            },
+            Asset {
+                info: token_asset_info(bonus_token.clone()),
+                amount: Uint128::new(amount),
+            },
+        ])
+    };
+    assert_eq!(
+        query_pending_on_proxy(&app, USER1),
+        proxy_rewards(10_000000)
+    );
+    assert_eq!(
+        query_pending_on_proxy(&app, USER2),
+        proxy_rewards(30_000000)
+    );
+
+    // A withdrawal settles the rewards of every proxy
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_cny_eur.to_string(),
+        amount: Uint128::new(10),
+    };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    check_token_balance(&mut app, &mirror_token_instance, &user1, 10_000000);
+    check_token_balance(&mut app, &bonus_token, &user1, 10_000000);
+    check_token_balance(&mut app, &lp_cny_eur, &user1, 10);
+    assert_eq!(
+        query_pending_on_proxy(&app, USER2),
+        proxy_rewards(30_000000)
+    );
+
+    let res: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &QueryMsg::UserInfo {
+                lp_token: lp_cny_eur.to_string(),
+                user: USER2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.reward_debt_proxy,
+        vec![
+            (mirror_proxy.clone(), Uint128::zero()),
+            (bonus_proxy.clone(), Uint128::zero())
+        ]
+    );
+
+    // A deposit settles them as well
+    mint_tokens(&mut app, pair_cny_eur.clone(), &lp_cny_eur, &user2, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER2, &[(&lp_cny_eur, 10)]);
+
+    check_token_balance(&mut app, &mirror_token_instance, &user2, 30_000000);
+    check_token_balance(&mut app, &bonus_token, &user2, 30_000000);
+    check_token_balance(&mut app, &bonus_token, &bonus_proxy, 0);
+    assert_eq!(query_pending_on_proxy(&app, USER2), proxy_rewards(0));
+
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &add_reward_proxy(&extra_proxy),
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner,
+            generator_instance.clone(),
+            &add_reward_proxy(&excess_proxy),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "A generator can't have more than 3 reward proxies!"
+    );
+}
+
+const REWARD_PROXY_CONFIG: Item<ProxyInstantiateMsg> = Item::new("config");
+const REWARD_PROXY_PENDING: Item<Uint128> = Item::new("pending");
+
+fn reward_proxy_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ProxyInstantiateMsg,
+) -> StdResult<Response> {
+    REWARD_PROXY_CONFIG.save(deps.storage, &msg)?;
+    REWARD_PROXY_PENDING.save(deps.storage, &Uint128::zero())?;
+    Ok(Response::new())
+}
+
+fn reward_proxy_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ProxyExecuteMsg,
+) -> StdResult<Response> {
+    let cfg = REWARD_PROXY_CONFIG.load(deps.storage)?;
+    match msg {
+        // Funded rewards stay pending until the Generator updates them
+        ProxyExecuteMsg::Receive(msg) => {
+            REWARD_PROXY_PENDING.update(deps.storage, |pending| -> StdResult<_> {
+                Ok(pending.checked_add(msg.amount)?)
+            })?;
+            Ok(Response::new())
+        }
+        ProxyExecuteMsg::UpdateRewards {} => {
+            REWARD_PROXY_PENDING.save(deps.storage, &Uint128::zero())?;
+            Ok(Response::new())
+        }
+        ProxyExecuteMsg::SendRewards { account, amount } => {
+            if info.sender != cfg.generator_contract_addr {
+                return Err(StdError::generic_err("Unauthorized"));
+            }
+
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: cfg.reward_token_addr,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: account,
+                    amount,
+                })?,
+                funds: vec![],
+            }))
+        }
+        _ => Err(StdError::generic_err("The proxy doesn't hold LP tokens")),
+    }
+}
+
+fn reward_proxy_query(deps: Deps, env: Env, msg: ProxyQueryMsg) -> StdResult<Binary> {
+    let cfg = REWARD_PROXY_CONFIG.load(deps.storage)?;
+    let pending = REWARD_PROXY_PENDING.load(deps.storage)?;
+    match msg {
+        ProxyQueryMsg::Config {} => to_binary(&cfg),
+        ProxyQueryMsg::Deposit {} => to_binary(&Uint128::zero()),
+        ProxyQueryMsg::Reward {} => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                &cfg.reward_token_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            to_binary(&res.balance.checked_sub(pending)?)
+        }
+        ProxyQueryMsg::PendingToken {} => to_binary(&pending),
+        ProxyQueryMsg::RewardInfo {} => to_binary(&Addr::unchecked(cfg.reward_token_addr)),
+    }
+}
 
-    // Change pool alloc points
-    let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![
-            (lp_cny_eur.to_string(), Uint128::from(60u32)),
-            (lp_eur_uusd.to_string(), Uint128::from(40u32)),
-        ],
-    };
+#[test]
+fn failing_vesting_claim() {
+    let mut app = mock_app();
 
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
-        .unwrap();
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_eur.to_string(),
-    };
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
 
-    // Check if alloc point is equal to 60
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::new(60), reps.alloc_point);
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_cny_uusd.to_string(),
-    };
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
 
-    // Check if alloc point is equal to 0
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::zero(), reps.alloc_point);
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
+    );
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
-        lp_token: lp_eur_uusd.to_string(),
-    };
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
 
-    // Check if alloc point is equal to 40
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
-        .unwrap();
-    assert_eq!(Uint128::new(40), reps.alloc_point);
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
+    );
 
-    // remove stable pair type from blacklist
-    let msg = FactoryExecuteMsg::UpdatePairConfig {
-        config: PairConfig {
-            code_id: pair_stable_code_id,
-            pair_type: PairType::Stable {},
-            total_fee_bps: 100,
-            maker_fee_bps: 10,
-            is_disabled: false,
-            is_generator_disabled: false,
-        },
-    };
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
 
-    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
-        .unwrap();
+    app.update_block(|bi| next_block(bi));
 
-    // check if we remove stable pair type from blacklist
-    let res: Vec<PairType> = app
-        .wrap()
-        .query_wasm_smart(
-            &factory_instance.clone(),
-            &FactoryQueryMsg::BlacklistedPairTypes {},
+    // Every claim from this vesting contract fails
+    let failing_vesting_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        failing_vesting_execute,
+        failing_vesting_instantiate,
+        failing_vesting_query,
+    )));
+    let failing_vesting_instance = app
+        .instantiate_contract(
+            failing_vesting_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "Failing vesting",
+            None,
         )
         .unwrap();
-    assert_eq!(res, vec![]);
 
-    // Change pool alloc points
-    let msg = GeneratorExecuteMsg::SetupPools {
-        pools: vec![
-            (lp_eur_uusd.to_string(), Uint128::from(80u32)),
-            (lp_cny_uusd.to_string(), Uint128::from(80u32)),
-        ],
-    };
-    app.execute_contract(owner.clone(), generator_instance.clone(), &msg, &[])
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
         .unwrap();
+    let set_vesting = |vesting: &Addr| GeneratorExecuteMsg::UpdateConfig {
+        vesting_contract: Some(vesting.to_string()),
+        generator_controller: None,
+        guardian: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &set_vesting(&failing_vesting_instance),
+        &[],
+    )
+    .unwrap();
 
-    let msg_cny_eur = QueryMsg::PoolInfo {
+    // The withdrawal goes through and the ASTRO is kept for the next claim
+    let msg = GeneratorExecuteMsg::Withdraw {
         lp_token: lp_cny_eur.to_string(),
+        amount: Uint128::new(10),
     };
-
-    // Check if alloc point is equal to 0
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_eur)
+    let res = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
-    assert_eq!(Uint128::zero(), reps.alloc_point);
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "vesting_claim_failed")));
 
-    let msg_cny_uusd = QueryMsg::PoolInfo {
-        lp_token: lp_cny_uusd.to_string(),
+    check_token_balance(&mut app, &lp_cny_eur, &user1, 10);
+    check_token_balance(&mut app, &astro_token_instance, &user1, 0);
+
+    let unpaid_astro = |app: &mut TerraApp| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &GeneratorQueryMsg::UnpaidAstro {
+                    user: USER1.to_string(),
+                },
+            )
+            .unwrap()
     };
+    assert_eq!(unpaid_astro(&mut app), Uint128::new(10_000000));
 
-    // Check if alloc point is equal to 80
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_cny_uusd)
+    // Claims keep failing until the vesting contract works again
+    let msg = GeneratorExecuteMsg::ClaimRewards { lp_tokens: vec![] };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
-    assert_eq!(Uint128::new(80), reps.alloc_point);
+    assert_eq!(unpaid_astro(&mut app), Uint128::new(10_000000));
 
-    let msg_eur_uusd = QueryMsg::PoolInfo {
-        lp_token: lp_eur_uusd.to_string(),
-    };
+    app.execute_contract(
+        owner,
+        generator_instance.clone(),
+        &set_vesting(&config.vesting_contract),
+        &[],
+    )
+    .unwrap();
 
-    // Check if alloc point is equal to 80
-    let reps: PoolInfoResponse = app
-        .wrap()
-        .query_wasm_smart(&generator_instance, &msg_eur_uusd)
+    let res = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
         .unwrap();
-    assert_eq!(Uint128::new(80), reps.alloc_point);
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "claimed_astro" && attr.value == "10000000")));
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
+    assert_eq!(unpaid_astro(&mut app), Uint128::zero());
 }
 
-fn mock_app() -> TerraApp {
-    let env = mock_env();
-    let api = MockApi::default();
-    let bank = BankKeeper::new();
-    let storage = MockStorage::new();
-    let custom = TerraMock::luna_ust_case();
-
-    AppBuilder::new()
-        .with_api(api)
-        .with_block(env.block)
-        .with_bank(bank)
-        .with_storage(storage)
-        .with_custom(custom)
-        .build()
+fn failing_vesting_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
 }
 
-fn store_token_code(app: &mut TerraApp) -> u64 {
-    let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_token::contract::execute,
-        astroport_token::contract::instantiate,
-        astroport_token::contract::query,
-    ));
+fn failing_vesting_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: VestingExecuteMsg,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("Vesting is paused"))
+}
 
-    app.store_code(astro_token_contract)
+fn failing_vesting_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("Vesting is paused"))
 }
 
-fn store_factory_code(app: &mut TerraApp) -> u64 {
-    let factory_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            astroport_factory::contract::execute,
-            astroport_factory::contract::instantiate,
-            astroport_factory::contract::query,
-        )
-        .with_reply_empty(astroport_factory::contract::reply),
+#[test]
+fn partial_vesting_claim() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+    let factory_instance =
+        instantiate_factory(&mut app, factory_code_id, token_code_id, pair_code_id, None);
+
+    let cny_token = instantiate_token(&mut app, token_code_id, "CNY", None);
+    let eur_token = instantiate_token(&mut app, token_code_id, "EUR", None);
+
+    let (pair_cny_eur, lp_cny_eur) = create_pair(
+        &mut app,
+        &factory_instance,
+        None,
+        None,
+        [
+            token_asset_info(cny_token.clone()),
+            token_asset_info(eur_token.clone()),
+        ],
     );
 
-    app.store_code(factory_contract)
-}
+    let generator_instance =
+        instantiate_generator(&mut app, &factory_instance, &astro_token_instance, None);
 
-fn store_pair_code_id(app: &mut TerraApp) -> u64 {
-    let pair_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            astroport_pair::contract::execute,
-            astroport_pair::contract::instantiate,
-            astroport_pair::contract::query,
-        )
-        .with_reply_empty(astroport_pair::contract::reply),
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        vec![PoolWithProxy {
+            pool: (lp_cny_eur.to_string(), Uint128::new(100)),
+            proxy: None,
+        }],
     );
 
-    app.store_code(pair_contract)
-}
+    mint_tokens(&mut app, pair_cny_eur, &lp_cny_eur, &user1, 10);
+    deposit_lp_tokens_to_generator(&mut app, &generator_instance, USER1, &[(&lp_cny_eur, 10)]);
 
-fn store_pair_stable_code_id(app: &mut TerraApp) -> u64 {
-    let pair_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            astroport_pair_stable::contract::execute,
-            astroport_pair_stable::contract::instantiate,
-            astroport_pair_stable::contract::query,
+    app.update_block(|bi| next_block(bi));
+
+    // This vesting contract only pays half of every claim
+    let partial_vesting_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        partial_vesting_execute,
+        partial_vesting_instantiate,
+        failing_vesting_query,
+    )));
+    let partial_vesting_instance = app
+        .instantiate_contract(
+            partial_vesting_code_id,
+            owner.clone(),
+            &astro_token_instance,
+            &[],
+            "Partial vesting",
+            None,
         )
-        .with_reply_empty(astroport_pair_stable::contract::reply),
+        .unwrap();
+    mint_tokens(
+        &mut app,
+        owner.clone(),
+        &astro_token_instance,
+        &partial_vesting_instance,
+        100_000000,
     );
 
-    app.store_code(pair_contract)
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&generator_instance, &GeneratorQueryMsg::Config {})
+        .unwrap();
+    let set_vesting = |vesting: &Addr| GeneratorExecuteMsg::UpdateConfig {
+        vesting_contract: Some(vesting.to_string()),
+        generator_controller: None,
+        guardian: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        generator_instance.clone(),
+        &set_vesting(&partial_vesting_instance),
+        &[],
+    )
+    .unwrap();
+
+    // Only the amount reported by the vesting contract is considered paid
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_cny_eur.to_string(),
+        amount: Uint128::new(10),
+    };
+    let res = app
+        .execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "claimed_astro" && attr.value == "5000000")));
+
+    check_token_balance(&mut app, &lp_cny_eur, &user1, 10);
+    check_token_balance(&mut app, &astro_token_instance, &user1, 5_000000);
+
+    let unpaid_astro = |app: &mut TerraApp| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &GeneratorQueryMsg::UnpaidAstro {
+                    user: USER1.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(unpaid_astro(&mut app), Uint128::new(5_000000));
+
+    // The rest is paid once the vesting contract pays in full again
+    app.execute_contract(
+        owner,
+        generator_instance.clone(),
+        &set_vesting(&config.vesting_contract),
+        &[],
+    )
+    .unwrap();
+
+    let msg = GeneratorExecuteMsg::ClaimRewards { lp_tokens: vec![] };
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+    check_token_balance(&mut app, &astro_token_instance, &user1, 10_000000);
+    assert_eq!(unpaid_astro(&mut app), Uint128::zero());
+}
+
+const PARTIAL_VESTING_TOKEN: Item<Addr> = Item::new("token");
+
+fn partial_vesting_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Addr,
+) -> StdResult<Response> {
+    PARTIAL_VESTING_TOKEN.save(deps.storage, &msg)?;
+    Ok(Response::default())
+}
+
+fn partial_vesting_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: VestingExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        VestingExecuteMsg::Claim { recipient, amount } => {
+            let claimed = amount.unwrap_or_default() / Uint128::new(2);
+
+            Ok(Response::new()
+                .add_message(WasmMsg::Execute {
+                    contract_addr: PARTIAL_VESTING_TOKEN.load(deps.storage)?.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.unwrap_or_else(|| info.sender.to_string()),
+                        amount: claimed,
+                    })?,
+                    funds: vec![],
+                })
+                .add_attribute("action", "claim")
+                .add_attribute("claimed_amount", claimed))
+        }
+        _ => Err(StdError::generic_err("Not supported")),
+    }
 }
 
 fn instantiate_token(
@@ -2773,6 +6261,7 @@ fn instantiate_token(
             minter: String::from(OWNER),
             cap: cap.map(|v| Uint128::from(v)),
         }),
+        marketing: None,
     };
 
     app.instantiate_contract(token_code_id, Addr::unchecked(OWNER), &msg, &[], name, None)
@@ -2831,13 +6320,8 @@ fn instantiate_generator(
     allowed_proxies: Option<Vec<String>>,
 ) -> Addr {
     // Vesting
-    let vesting_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_vesting::contract::execute,
-        astroport_vesting::contract::instantiate,
-        astroport_vesting::contract::query,
-    ));
     let owner = Addr::unchecked(OWNER);
-    let vesting_code_id = app.store_code(vesting_contract);
+    let vesting_code_id = store_vesting_code(&mut app);
 
     let init_msg = VestingInstantiateMsg {
         owner: owner.to_string(),
@@ -2864,16 +6348,7 @@ fn instantiate_generator(
     );
 
     // Generator
-    let generator_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            astroport_generator::contract::execute,
-            astroport_generator::contract::instantiate,
-            astroport_generator::contract::query,
-        )
-        .with_reply_empty(astroport_generator::contract::reply),
-    );
-
-    let generator_code_id = app.store_code(generator_contract);
+    let generator_code_id = store_generator_code(&mut app);
 
     let init_msg = GeneratorInstantiateMsg {
         owner: owner.to_string(),
@@ -3104,10 +6579,14 @@ fn check_pending_rewards(
         .query_wasm_smart(generator_instance.to_owned(), &msg)
         .unwrap();
     assert_eq!(
-        (res.pending, res.pending_on_proxy),
+        (
+            res.pending,
+            res.pending_on_proxy
+                .map(|assets| assets.into_iter().map(|asset| asset.amount).collect())
+        ),
         (
             Uint128::from(expected.0),
-            expected.1.map(|v| Uint128::from(v))
+            expected.1.map(|v| vec![Uint128::from(v)])
         )
     );
 }
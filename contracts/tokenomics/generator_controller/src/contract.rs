@@ -0,0 +1,562 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL, POOL_VOTES, TUNE_INFO, USER_VOTES};
+use astroport::asset::addr_validate_to_lower;
+use astroport::bribes::PoolVotesResponse;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::constants::BPS_DENOMINATOR;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::generator_controller::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TuneInfo, UserVotesResponse,
+    MAX_POOLS_LIMIT, TOTAL_ALLOC_POINTS,
+};
+use astroport::voting_escrow::{VotingEscrowQueryMsg, VotingPowerResponse};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::U64Key;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-generator-controller";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`]. The first voting
+/// period starts at the current block time.
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.period_duration == 0 {
+        return Err(ContractError::InvalidPeriodDuration {});
+    }
+    validate_pools_limit(msg.pools_limit)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        generator: addr_validate_to_lower(deps.api, &msg.generator)?,
+        voting_escrow: addr_validate_to_lower(deps.api, &msg.voting_escrow)?,
+        start_time: env.block.time.seconds(),
+        period_duration: msg.period_duration,
+        pools_limit: msg.pools_limit,
+        whitelist: vec![],
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Vote { votes }** Splits the sender's vxASTRO voting power between pools for the current period.
+///
+/// * **ExecuteMsg::TunePools {}** Replaces the Generator's allocation points with the votes of the last finished period.
+///
+/// * **ExecuteMsg::UpdateWhitelist { add, remove }** Adds or removes pools that can be voted for.
+///
+/// * **ExecuteMsg::UpdateConfig { voting_escrow, pools_limit }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Vote { votes } => vote(deps, env, info, votes),
+        ExecuteMsg::TunePools {} => tune_pools(deps, env),
+        ExecuteMsg::UpdateWhitelist { add, remove } => update_whitelist(deps, info, add, remove),
+        ExecuteMsg::UpdateConfig {
+            voting_escrow,
+            pools_limit,
+        } => update_config(deps, info, voting_escrow, pools_limit),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Splits the sender's current vxASTRO voting power between whitelisted pools for the current period.
+/// Votes the sender already cast in the period are replaced. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **votes** is a vector of LP tokens and the share (in bps) of the voting power each one gets.
+fn vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<(String, u16)>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let period = current_period(&config, &env);
+
+    let mut new_votes: Vec<(Addr, u16)> = vec![];
+    let mut total_bps = 0u32;
+    for (lp_token, bps) in votes {
+        let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+        if !config.whitelist.contains(&lp_token) {
+            return Err(ContractError::PoolNotWhitelisted(lp_token.to_string()));
+        }
+        if new_votes.iter().any(|(voted, _)| *voted == lp_token) {
+            return Err(ContractError::DuplicatedPool(lp_token.to_string()));
+        }
+        if bps == 0 {
+            return Err(ContractError::InvalidVotes {});
+        }
+
+        total_bps += bps as u32;
+        new_votes.push((lp_token, bps));
+    }
+    if total_bps > BPS_DENOMINATOR as u32 {
+        return Err(ContractError::InvalidVotes {});
+    }
+
+    let voting_power = deps
+        .querier
+        .query_wasm_smart::<VotingPowerResponse>(
+            &config.voting_escrow,
+            &VotingEscrowQueryMsg::UserVotingPower {
+                user: info.sender.to_string(),
+            },
+        )?
+        .voting_power;
+    if voting_power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    // Remove the votes the sender already cast in this period
+    if let Some(previous) =
+        USER_VOTES.may_load(deps.storage, (&info.sender, U64Key::new(period)))?
+    {
+        for (lp_token, bps) in previous.votes {
+            let weight = vote_weight(previous.voting_power, bps);
+            POOL_VOTES.update::<_, ContractError>(
+                deps.storage,
+                (&lp_token, U64Key::new(period)),
+                |total| Ok(total.unwrap_or_default().checked_sub(weight)?),
+            )?;
+        }
+    }
+
+    for (lp_token, bps) in &new_votes {
+        let weight = vote_weight(voting_power, *bps);
+        POOL_VOTES.update::<_, ContractError>(
+            deps.storage,
+            (lp_token, U64Key::new(period)),
+            |total| Ok(total.unwrap_or_default().checked_add(weight)?),
+        )?;
+    }
+
+    if new_votes.is_empty() {
+        USER_VOTES.remove(deps.storage, (&info.sender, U64Key::new(period)));
+    } else {
+        USER_VOTES.save(
+            deps.storage,
+            (&info.sender, U64Key::new(period)),
+            &UserVotesResponse {
+                voting_power,
+                votes: new_votes,
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "vote"),
+        attr("voter", info.sender),
+        attr("period", period.to_string()),
+        attr("voting_power", voting_power),
+    ]))
+}
+
+/// ## Description
+/// Replaces the Generator's allocation points with the votes of the last finished period. The
+/// `pools_limit` most voted whitelisted pools get their share of [`TOTAL_ALLOC_POINTS`], all other pools
+/// stop receiving ASTRO emissions. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+fn tune_pools(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let period = current_period(&config, &env)
+        .checked_sub(1)
+        .ok_or(ContractError::NoFinishedPeriod {})?;
+    if TUNE_INFO.has(deps.storage, U64Key::new(period)) {
+        return Err(ContractError::PeriodAlreadyTuned(period));
+    }
+
+    let mut pool_votes = vec![];
+    for lp_token in &config.whitelist {
+        let weight = POOL_VOTES
+            .may_load(deps.storage, (lp_token, U64Key::new(period)))?
+            .unwrap_or_default();
+        if !weight.is_zero() {
+            pool_votes.push((lp_token.clone(), weight));
+        }
+    }
+
+    // Ties are broken by LP token address so that the result doesn't depend on the whitelist order
+    pool_votes.sort_by(|(a_lp_token, a_weight), (b_lp_token, b_weight)| {
+        b_weight
+            .cmp(a_weight)
+            .then_with(|| a_lp_token.cmp(b_lp_token))
+    });
+    pool_votes.truncate(config.pools_limit as usize);
+
+    let total_weight: Uint128 = pool_votes.iter().map(|(_, weight)| weight).sum();
+    if total_weight.is_zero() {
+        return Err(ContractError::NoVotes(period));
+    }
+
+    let pools: Vec<(Addr, Uint128)> = pool_votes
+        .into_iter()
+        .map(|(lp_token, weight)| {
+            (
+                lp_token,
+                weight.multiply_ratio(TOTAL_ALLOC_POINTS, total_weight),
+            )
+        })
+        .filter(|(_, alloc_point)| !alloc_point.is_zero())
+        .collect();
+
+    TUNE_INFO.save(
+        deps.storage,
+        U64Key::new(period),
+        &TuneInfo {
+            tuned_at: env.block.time.seconds(),
+            pools: pools.clone(),
+        },
+    )?;
+
+    // The Generator updates all pools before it replaces their allocation points
+    let setup_pools_msg = WasmMsg::Execute {
+        contract_addr: config.generator.to_string(),
+        msg: to_binary(&GeneratorExecuteMsg::SetupPools {
+            pools: pools
+                .into_iter()
+                .map(|(lp_token, alloc_point)| (lp_token.to_string(), alloc_point))
+                .collect(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(setup_pools_msg)
+        .add_attributes(vec![
+            attr("action", "tune_pools"),
+            attr("period", period.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Adds or removes pools that can be voted for. Votes already cast for a removed pool are ignored when
+/// tuning. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **add** is an [`Option`] field of type [`Vec<String>`]. These are the LP tokens of the pools to add.
+///
+/// * **remove** is an [`Option`] field of type [`Vec<String>`]. These are the LP tokens of the pools to remove.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for lp_token in remove.unwrap_or_default() {
+        let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+        let index = config
+            .whitelist
+            .iter()
+            .position(|whitelisted| *whitelisted == lp_token)
+            .ok_or_else(|| ContractError::PoolNotWhitelisted(lp_token.to_string()))?;
+        config.whitelist.remove(index);
+    }
+
+    for lp_token in add.unwrap_or_default() {
+        let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+        if config.whitelist.contains(&lp_token) {
+            return Err(ContractError::PoolAlreadyWhitelisted(lp_token.to_string()));
+        }
+        config.whitelist.push(lp_token);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_whitelist"))
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **voting_escrow** is an [`Option`] field of type [`String`]. This is the new voting escrow contract.
+///
+/// * **pools_limit** is an [`Option`] field of type [`u64`]. This is the new maximum amount of tuned pools.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    voting_escrow: Option<String>,
+    pools_limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(voting_escrow) = voting_escrow {
+        config.voting_escrow = addr_validate_to_lower(deps.api, &voting_escrow)?;
+    }
+    if let Some(pools_limit) = pools_limit {
+        validate_pools_limit(pools_limit)?;
+        config.pools_limit = pools_limit;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Returns a [`ContractError`] if the maximum amount of tuned pools is zero or higher than [`MAX_POOLS_LIMIT`].
+/// ## Params
+/// * **pools_limit** is an object of type [`u64`]. This is the maximum amount of tuned pools.
+fn validate_pools_limit(pools_limit: u64) -> Result<(), ContractError> {
+    if pools_limit == 0 || pools_limit > MAX_POOLS_LIMIT {
+        return Err(ContractError::InvalidPoolsLimit(MAX_POOLS_LIMIT));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the current voting period.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **env** is an object of type [`Env`].
+fn current_period(config: &Config, env: &Env) -> u64 {
+    env.block.time.seconds().saturating_sub(config.start_time) / config.period_duration
+}
+
+/// ## Description
+/// Returns the vote weight a share of the voting power adds to a pool.
+/// ## Params
+/// * **voting_power** is an object of type [`Uint128`]. This is the voting power of the voter.
+///
+/// * **bps** is an object of type [`u16`]. This is the share (in bps) of the voting power given to the pool.
+fn vote_weight(voting_power: Uint128, bps: u16) -> Uint128 {
+    voting_power.multiply_ratio(bps, BPS_DENOMINATOR)
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::CurrentPeriod {}** Returns the current voting period.
+///
+/// * **QueryMsg::UserVotes { user }** Returns the votes of a user in the current period.
+///
+/// * **QueryMsg::UserVote { user, lp_token, period }** Returns the vote weight a user gave to a pool in a period.
+///
+/// * **QueryMsg::PoolVotes { lp_token, period }** Returns the votes of a pool in a period.
+///
+/// * **QueryMsg::TuneInfo { period }** Returns the result of tuning a period.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                generator: config.generator,
+                voting_escrow: config.voting_escrow,
+                start_time: config.start_time,
+                period_duration: config.period_duration,
+                pools_limit: config.pools_limit,
+                whitelist: config.whitelist,
+            })
+        }
+        QueryMsg::CurrentPeriod {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&current_period(&config, &env))
+        }
+        QueryMsg::UserVotes { user } => {
+            let config = CONFIG.load(deps.storage)?;
+            let period = current_period(&config, &env);
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            to_binary(
+                &USER_VOTES
+                    .may_load(deps.storage, (&user, U64Key::new(period)))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::UserVote {
+            user,
+            lp_token,
+            period,
+        } => to_binary(&query_user_vote(deps, user, lp_token, period)?),
+        QueryMsg::PoolVotes { lp_token, period } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            to_binary(&PoolVotesResponse {
+                total_weight: POOL_VOTES
+                    .may_load(deps.storage, (&lp_token, U64Key::new(period)))?
+                    .unwrap_or_default(),
+                tuned_at: TUNE_INFO
+                    .may_load(deps.storage, U64Key::new(period))?
+                    .map(|tune_info| tune_info.tuned_at),
+            })
+        }
+        QueryMsg::TuneInfo { period } => {
+            to_binary(&TUNE_INFO.load(deps.storage, U64Key::new(period))?)
+        }
+    }
+}
+
+/// ## Description
+/// Returns the vote weight a user gave to a pool in a period. The weights of all voters add up to the
+/// total weight of the pool, which lets the bribes contract split bribes between voters.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **user** is an object of type [`String`]. This is the voter.
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool.
+///
+/// * **period** is an object of type [`u64`]. This is the voting period.
+fn query_user_vote(deps: Deps, user: String, lp_token: String, period: u64) -> StdResult<Uint128> {
+    let user = addr_validate_to_lower(deps.api, &user)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let weight = USER_VOTES
+        .may_load(deps.storage, (&user, U64Key::new(period)))?
+        .and_then(|user_votes| {
+            user_votes
+                .votes
+                .into_iter()
+                .find(|(voted, _)| *voted == lp_token)
+                .map(|(_, bps)| vote_weight(user_votes.voting_power, bps))
+        })
+        .unwrap_or_default();
+
+    Ok(weight)
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,53 @@
+use astroport::constants::BPS_DENOMINATOR;
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes generator controller contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The period duration must be greater than zero")]
+    InvalidPeriodDuration {},
+
+    #[error("The pools limit must be between 1 and {0}")]
+    InvalidPoolsLimit(u64),
+
+    #[error("The sender has no vxASTRO voting power")]
+    NoVotingPower {},
+
+    #[error(
+        "Every vote must be greater than zero and all votes must sum up to at most {} bps",
+        BPS_DENOMINATOR
+    )]
+    InvalidVotes {},
+
+    #[error("Pool {0} is not whitelisted")]
+    PoolNotWhitelisted(String),
+
+    #[error("Pool {0} is duplicated")]
+    DuplicatedPool(String),
+
+    #[error("Pool {0} is already whitelisted")]
+    PoolAlreadyWhitelisted(String),
+
+    #[error("No voting period has finished yet")]
+    NoFinishedPeriod {},
+
+    #[error("Period {0} is already tuned")]
+    PeriodAlreadyTuned(u64),
+
+    #[error("No whitelisted pool received votes in period {0}")]
+    NoVotes(u64),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
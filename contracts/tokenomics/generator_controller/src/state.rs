@@ -0,0 +1,46 @@
+use astroport::common::OwnershipProposal;
+use astroport::generator_controller::{TuneInfo, UserVotesResponse};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the generator controller contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Generator contract whose allocation points are tuned
+    pub generator: Addr,
+    /// The voting escrow (vxASTRO) contract
+    pub voting_escrow: Addr,
+    /// The timestamp (in seconds) when the first voting period started
+    pub start_time: u64,
+    /// The duration of a voting period (in seconds)
+    pub period_duration: u64,
+    /// The maximum amount of pools that receive ASTRO emissions after a tune
+    pub pools_limit: u64,
+    /// The pools that can be voted for
+    pub whitelist: Vec<Addr>,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the votes of users keyed by voter and period
+pub const USER_VOTES: Map<(&Addr, U64Key), UserVotesResponse> = Map::new("user_votes");
+
+/// ## Description
+/// Stores the total vote weight of pools keyed by LP token and period
+pub const POOL_VOTES: Map<(&Addr, U64Key), Uint128> = Map::new("pool_votes");
+
+/// ## Description
+/// Stores the result of tuning a period keyed by period
+pub const TUNE_INFO: Map<U64Key, TuneInfo> = Map::new("tune_info");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,419 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::bribes::PoolVotesResponse;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::generator_controller::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, TuneInfo, UserVotesResponse,
+    MAX_POOLS_LIMIT,
+};
+
+type ControllerDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+const PERIOD_DURATION: u64 = 1000;
+
+fn env_at(offset: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(offset);
+    env
+}
+
+fn instantiate_msg(period_duration: u64, pools_limit: u64) -> InstantiateMsg {
+    InstantiateMsg {
+        owner: String::from("owner"),
+        generator: String::from("generator"),
+        voting_escrow: String::from("voting_escrow"),
+        period_duration,
+        pools_limit,
+    }
+}
+
+fn setup() -> ControllerDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_voting_escrow("voting_escrow");
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        instantiate_msg(PERIOD_DURATION, 2),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateWhitelist {
+            add: Some(vec![
+                String::from("lp_a"),
+                String::from("lp_b"),
+                String::from("lp_c"),
+            ]),
+            remove: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn cast_votes(
+    deps: &mut ControllerDeps,
+    env: Env,
+    voter: &str,
+    votes: &[(&str, u16)],
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(voter, &[]),
+        ExecuteMsg::Vote {
+            votes: votes
+                .iter()
+                .map(|(lp_token, bps)| (lp_token.to_string(), *bps))
+                .collect(),
+        },
+    )
+    .map(|_| ())
+}
+
+fn query_pool_votes(deps: &ControllerDeps, lp_token: &str, period: u64) -> PoolVotesResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PoolVotes {
+                lp_token: lp_token.to_string(),
+                period,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn query_user_vote(deps: &ControllerDeps, user: &str, lp_token: &str, period: u64) -> Uint128 {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserVote {
+                user: user.to_string(),
+                lp_token: lp_token.to_string(),
+                period,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        instantiate_msg(0, 2),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPeriodDuration {});
+
+    for pools_limit in [0, MAX_POOLS_LIMIT + 1] {
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            instantiate_msg(PERIOD_DURATION, pools_limit),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidPoolsLimit(MAX_POOLS_LIMIT));
+    }
+
+    let deps = setup();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner"),
+            generator: Addr::unchecked("generator"),
+            voting_escrow: Addr::unchecked("voting_escrow"),
+            start_time: mock_env().block.time.seconds(),
+            period_duration: PERIOD_DURATION,
+            pools_limit: 2,
+            whitelist: vec![
+                Addr::unchecked("lp_a"),
+                Addr::unchecked("lp_b"),
+                Addr::unchecked("lp_c"),
+            ],
+        }
+    );
+
+    let period: u64 = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(2 * PERIOD_DURATION + 1),
+            QueryMsg::CurrentPeriod {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(period, 2);
+}
+
+#[test]
+fn update_whitelist() {
+    let mut deps = setup();
+
+    let update_whitelist = |add: &[&str], remove: &[&str]| ExecuteMsg::UpdateWhitelist {
+        add: Some(add.iter().map(|lp_token| lp_token.to_string()).collect()),
+        remove: Some(remove.iter().map(|lp_token| lp_token.to_string()).collect()),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        update_whitelist(&["lp_d"], &[]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_whitelist(&["lp_a"], &[]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PoolAlreadyWhitelisted(String::from("lp_a"))
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_whitelist(&[], &["lp_d"]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolNotWhitelisted(String::from("lp_d")));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_whitelist(&["lp_d"], &["lp_a"]),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.whitelist,
+        vec![
+            Addr::unchecked("lp_b"),
+            Addr::unchecked("lp_c"),
+            Addr::unchecked("lp_d"),
+        ]
+    );
+}
+
+#[test]
+fn vote() {
+    let mut deps = setup();
+    deps.querier.set_voting_power("alice", 1000);
+    deps.querier.set_voting_power("bob", 500);
+
+    let err = cast_votes(&mut deps, mock_env(), "alice", &[("lp_x", 5000)]).unwrap_err();
+    assert_eq!(err, ContractError::PoolNotWhitelisted(String::from("lp_x")));
+
+    let err = cast_votes(
+        &mut deps,
+        mock_env(),
+        "alice",
+        &[("lp_a", 5000), ("lp_a", 5000)],
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DuplicatedPool(String::from("lp_a")));
+
+    let err = cast_votes(&mut deps, mock_env(), "alice", &[("lp_a", 0)]).unwrap_err();
+    assert_eq!(err, ContractError::InvalidVotes {});
+
+    let err = cast_votes(
+        &mut deps,
+        mock_env(),
+        "alice",
+        &[("lp_a", 6000), ("lp_b", 5000)],
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidVotes {});
+
+    let err = cast_votes(&mut deps, mock_env(), "carol", &[("lp_a", 5000)]).unwrap_err();
+    assert_eq!(err, ContractError::NoVotingPower {});
+
+    cast_votes(
+        &mut deps,
+        mock_env(),
+        "alice",
+        &[("lp_a", 6000), ("lp_b", 4000)],
+    )
+    .unwrap();
+    cast_votes(&mut deps, mock_env(), "bob", &[("lp_a", 5000)]).unwrap();
+    assert_eq!(query_pool_votes(&deps, "lp_a", 0).total_weight.u128(), 850);
+    assert_eq!(query_pool_votes(&deps, "lp_b", 0).total_weight.u128(), 400);
+
+    // Voting again replaces the previous votes using the current voting power
+    deps.querier.set_voting_power("alice", 2000);
+    cast_votes(&mut deps, mock_env(), "alice", &[("lp_c", 10000)]).unwrap();
+    assert_eq!(query_pool_votes(&deps, "lp_a", 0).total_weight.u128(), 250);
+    assert_eq!(query_pool_votes(&deps, "lp_b", 0).total_weight.u128(), 0);
+    assert_eq!(query_pool_votes(&deps, "lp_c", 0).total_weight.u128(), 2000);
+    assert_eq!(query_user_vote(&deps, "alice", "lp_a", 0).u128(), 0);
+    assert_eq!(query_user_vote(&deps, "alice", "lp_c", 0).u128(), 2000);
+    assert_eq!(query_user_vote(&deps, "bob", "lp_a", 0).u128(), 250);
+
+    let user_votes: UserVotesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserVotes {
+                user: String::from("alice"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        user_votes,
+        UserVotesResponse {
+            voting_power: Uint128::from(2000u128),
+            votes: vec![(Addr::unchecked("lp_c"), 10000)],
+        }
+    );
+
+    // Votes only count for the period they were cast in
+    cast_votes(
+        &mut deps,
+        env_at(PERIOD_DURATION),
+        "bob",
+        &[("lp_b", 10000)],
+    )
+    .unwrap();
+    assert_eq!(query_pool_votes(&deps, "lp_a", 0).total_weight.u128(), 250);
+    assert_eq!(query_pool_votes(&deps, "lp_b", 1).total_weight.u128(), 500);
+
+    // Alice didn't vote in the current period yet
+    let user_votes: UserVotesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(PERIOD_DURATION),
+            QueryMsg::UserVotes {
+                user: String::from("alice"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(user_votes, UserVotesResponse::default());
+}
+
+#[test]
+fn tune_pools() {
+    let mut deps = setup();
+    deps.querier.set_voting_power("alice", 2000);
+    deps.querier.set_voting_power("bob", 500);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::TunePools {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoFinishedPeriod {});
+
+    cast_votes(&mut deps, mock_env(), "alice", &[("lp_c", 10000)]).unwrap();
+    cast_votes(
+        &mut deps,
+        mock_env(),
+        "bob",
+        &[("lp_b", 5000), ("lp_a", 5000)],
+    )
+    .unwrap();
+
+    // Only the two most voted pools get emissions, the tie is broken by address
+    let tune_env = env_at(PERIOD_DURATION);
+    let res = execute(
+        deps.as_mut(),
+        tune_env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::TunePools {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("generator"),
+            msg: to_binary(&GeneratorExecuteMsg::SetupPools {
+                pools: vec![
+                    (String::from("lp_c"), Uint128::from(888_888u128)),
+                    (String::from("lp_a"), Uint128::from(111_111u128)),
+                ],
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let tune_info: TuneInfo =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TuneInfo { period: 0 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        tune_info,
+        TuneInfo {
+            tuned_at: tune_env.block.time.seconds(),
+            pools: vec![
+                (Addr::unchecked("lp_c"), Uint128::from(888_888u128)),
+                (Addr::unchecked("lp_a"), Uint128::from(111_111u128)),
+            ],
+        }
+    );
+    assert_eq!(
+        query_pool_votes(&deps, "lp_b", 0),
+        PoolVotesResponse {
+            total_weight: Uint128::from(250u128),
+            tuned_at: Some(tune_env.block.time.seconds()),
+        }
+    );
+    assert_eq!(query_pool_votes(&deps, "lp_b", 1).tuned_at, None);
+
+    let err = execute(
+        deps.as_mut(),
+        tune_env,
+        mock_info("keeper", &[]),
+        ExecuteMsg::TunePools {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PeriodAlreadyTuned(0));
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(2 * PERIOD_DURATION),
+        mock_info("keeper", &[]),
+        ExecuteMsg::TunePools {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoVotes(1));
+}
@@ -0,0 +1,51 @@
+//! Messages of the Anchor LP staking contract that are used by the proxy.
+
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure describes the CW20 hook messages of the Anchor LP staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bonds the LP tokens sent along with the message
+    Bond {},
+}
+
+/// ## Description
+/// This structure describes the execute messages of the Anchor LP staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Unbonds LP tokens and sends them back to the staker. Pending rewards are left untouched
+    Unbond { amount: Uint128 },
+    /// Sends all pending ANC rewards to the staker
+    Withdraw {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the Anchor LP staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns information about a staker using a [`StakerInfoResponse`] object
+    StakerInfo {
+        staker: String,
+        block_height: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This structure describes the response to a [`QueryMsg::StakerInfo`] query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfoResponse {
+    /// The staker address
+    pub staker: String,
+    /// The global reward index at the time of the last staker update
+    pub reward_index: Decimal,
+    /// The amount of bonded LP tokens
+    pub bond_amount: Uint128,
+    /// The amount of ANC that can be withdrawn
+    pub pending_reward: Uint128,
+}
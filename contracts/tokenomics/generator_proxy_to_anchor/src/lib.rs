@@ -0,0 +1,4 @@
+pub mod anchor;
+pub mod contract;
+pub mod error;
+pub mod state;
@@ -0,0 +1,25 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+/// ## Description
+/// This structure holds the main parameters for the generator_proxy_to_anchor contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The generator contract address
+    pub generator_contract_addr: Addr,
+    /// The Astroport pair contract address
+    pub pair_addr: Addr,
+    /// The contract address for the Astroport ANC LP token
+    pub lp_token_addr: Addr,
+    /// The Anchor LP staking contract address
+    pub reward_contract_addr: Addr,
+    /// The ANC token contract address
+    pub reward_token_addr: Addr,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
@@ -0,0 +1,454 @@
+use astroport::asset::{token_asset_info, Asset};
+use astroport::factory::PairType;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::generator_proxy::{
+    Cw20HookMsg as ProxyHookMsg, ExecuteMsg as ProxyExecuteMsg,
+    InstantiateMsg as ProxyInstantiateMsg, QueryMsg as ProxyQueryMsg,
+};
+use astroport_generator_proxy_to_anchor::anchor::StakerInfoResponse;
+use astroport_test::{Protocol, ProtocolBuilder};
+use cosmwasm_std::{to_binary, Addr, Uint128};
+use cw20::Cw20ExecuteMsg;
+use terra_multi_test::{ContractWrapper, Executor};
+
+/// The amount of ANC the mocked Anchor staking contract emits per block
+const ANC_PER_BLOCK: u128 = 1_000000;
+
+/// A minimal Anchor LP staking contract. It bonds a single LP token and emits a fixed amount
+/// of ANC per block to its stakers, paying it out of the ANC it holds.
+mod mock_anchor_staking {
+    use astroport_generator_proxy_to_anchor::anchor::{Cw20HookMsg, QueryMsg, StakerInfoResponse};
+    use cosmwasm_std::{
+        from_binary, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+        StdError, StdResult, Uint128, WasmMsg,
+    };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct InstantiateMsg {
+        pub staking_token: String,
+        pub anchor_token: String,
+        pub reward_per_block: Uint128,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ExecuteMsg {
+        Receive(Cw20ReceiveMsg),
+        Unbond { amount: Uint128 },
+        Withdraw {},
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        staking_token: Addr,
+        anchor_token: Addr,
+        reward_per_block: Uint128,
+    }
+
+    #[derive(Serialize, Deserialize, Default)]
+    struct StakerInfo {
+        bond_amount: Uint128,
+        pending_reward: Uint128,
+        last_height: u64,
+    }
+
+    const CONFIG: Item<Config> = Item::new("config");
+    const STAKERS: Map<&Addr, StakerInfo> = Map::new("stakers");
+
+    fn accrue(cfg: &Config, staker: &mut StakerInfo, height: u64) {
+        if !staker.bond_amount.is_zero() && height > staker.last_height {
+            let blocks = (height - staker.last_height) as u128;
+            staker.pending_reward += Uint128::from(cfg.reward_per_block.u128() * blocks);
+        }
+        staker.last_height = height;
+    }
+
+    pub fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                staking_token: deps.api.addr_validate(&msg.staking_token)?,
+                anchor_token: deps.api.addr_validate(&msg.anchor_token)?,
+                reward_per_block: msg.reward_per_block,
+            },
+        )?;
+
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        let cfg = CONFIG.load(deps.storage)?;
+
+        let (staker_addr, amount) = match &msg {
+            ExecuteMsg::Receive(cw20_msg) => {
+                let Cw20HookMsg::Bond {} = from_binary(&cw20_msg.msg)?;
+                if info.sender != cfg.staking_token {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                (deps.api.addr_validate(&cw20_msg.sender)?, cw20_msg.amount)
+            }
+            _ => (info.sender, Uint128::zero()),
+        };
+
+        let mut staker = STAKERS
+            .may_load(deps.storage, &staker_addr)?
+            .unwrap_or_default();
+        accrue(&cfg, &mut staker, env.block.height);
+
+        let transfer = match msg {
+            ExecuteMsg::Receive(_) => {
+                staker.bond_amount += amount;
+                None
+            }
+            ExecuteMsg::Unbond { amount } => {
+                staker.bond_amount = staker.bond_amount.checked_sub(amount)?;
+                Some((cfg.staking_token, amount))
+            }
+            ExecuteMsg::Withdraw {} => {
+                let amount = std::mem::take(&mut staker.pending_reward);
+                Some((cfg.anchor_token, amount))
+            }
+        };
+        STAKERS.save(deps.storage, &staker_addr, &staker)?;
+
+        let mut response = Response::new();
+        if let Some((token, amount)) = transfer.filter(|(_, amount)| !amount.is_zero()) {
+            response = response.add_message(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: staker_addr.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            });
+        }
+
+        Ok(response)
+    }
+
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        let cfg = CONFIG.load(deps.storage)?;
+
+        match msg {
+            QueryMsg::StakerInfo {
+                staker,
+                block_height,
+            } => {
+                let staker_addr = deps.api.addr_validate(&staker)?;
+                let mut info = STAKERS
+                    .may_load(deps.storage, &staker_addr)?
+                    .unwrap_or_default();
+                accrue(&cfg, &mut info, block_height.unwrap_or(env.block.height));
+
+                to_binary(&StakerInfoResponse {
+                    staker,
+                    reward_index: Decimal::zero(),
+                    bond_amount: info.bond_amount,
+                    pending_reward: info.pending_reward,
+                })
+            }
+        }
+    }
+}
+
+struct AnchorSetup {
+    lp_token: Addr,
+    anc_token: Addr,
+    anchor_staking: Addr,
+    proxy: Addr,
+}
+
+fn setup(protocol: &mut Protocol) -> AnchorSetup {
+    let anc_token = protocol.create_token("ANC");
+    let ust_token = protocol.create_token("UST");
+    let pair = protocol.create_pair(
+        PairType::Xyk {},
+        [
+            token_asset_info(anc_token.clone()),
+            token_asset_info(ust_token.clone()),
+        ],
+        None,
+    );
+    let lp_token = pair.liquidity_token.clone();
+
+    let anchor_staking_code_id =
+        protocol
+            .app
+            .store_code(Box::new(ContractWrapper::new_with_empty(
+                mock_anchor_staking::execute,
+                mock_anchor_staking::instantiate,
+                mock_anchor_staking::query,
+            )));
+    let anchor_staking = protocol
+        .app
+        .instantiate_contract(
+            anchor_staking_code_id,
+            protocol.owner.clone(),
+            &mock_anchor_staking::InstantiateMsg {
+                staking_token: lp_token.to_string(),
+                anchor_token: anc_token.to_string(),
+                reward_per_block: Uint128::from(ANC_PER_BLOCK),
+            },
+            &[],
+            "Anchor LP staking",
+            None,
+        )
+        .unwrap();
+    protocol.mint_tokens(&anc_token, &anchor_staking, 1_000_000_000000);
+
+    let proxy_code_id = protocol
+        .app
+        .store_code(Box::new(ContractWrapper::new_with_empty(
+            astroport_generator_proxy_to_anchor::contract::execute,
+            astroport_generator_proxy_to_anchor::contract::instantiate,
+            astroport_generator_proxy_to_anchor::contract::query,
+        )));
+    let proxy = protocol
+        .app
+        .instantiate_contract(
+            proxy_code_id,
+            protocol.owner.clone(),
+            &ProxyInstantiateMsg {
+                generator_contract_addr: protocol.generator().to_string(),
+                pair_addr: pair.contract_addr.to_string(),
+                lp_token_addr: lp_token.to_string(),
+                reward_contract_addr: anchor_staking.to_string(),
+                reward_token_addr: anc_token.to_string(),
+            },
+            &[],
+            "Generator proxy to Anchor",
+            None,
+        )
+        .unwrap();
+
+    protocol.setup_pools(&[(&lp_token, 100)]);
+    let owner = protocol.owner.clone();
+    let generator = protocol.generator();
+    protocol
+        .app
+        .execute_contract(
+            owner.clone(),
+            generator.clone(),
+            &GeneratorExecuteMsg::SetAllowedRewardProxies {
+                proxies: vec![proxy.to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+    protocol
+        .app
+        .execute_contract(
+            owner,
+            generator,
+            &GeneratorExecuteMsg::MoveToProxy {
+                lp_token: lp_token.to_string(),
+                proxy: proxy.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Provide liquidity so that the user holds 100 LP tokens
+    let user = Addr::unchecked("user");
+    for token in [&anc_token, &ust_token] {
+        protocol.mint_tokens(token, &user, 100_000000);
+    }
+    protocol
+        .provide_liquidity(
+            &user,
+            &pair.contract_addr,
+            [
+                Asset {
+                    info: pair.asset_infos[0].clone(),
+                    amount: Uint128::from(100_000000u128),
+                },
+                Asset {
+                    info: pair.asset_infos[1].clone(),
+                    amount: Uint128::from(100_000000u128),
+                },
+            ],
+        )
+        .unwrap();
+
+    AnchorSetup {
+        lp_token,
+        anc_token,
+        anchor_staking,
+        proxy,
+    }
+}
+
+fn query_staker_info(protocol: &Protocol, setup: &AnchorSetup) -> StakerInfoResponse {
+    protocol
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.anchor_staking,
+            &astroport_generator_proxy_to_anchor::anchor::QueryMsg::StakerInfo {
+                staker: setup.proxy.to_string(),
+                block_height: None,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn only_generator_can_use_proxy() {
+    let mut protocol = ProtocolBuilder::new().with_generator(10_000000).build();
+    let setup = setup(&mut protocol);
+    let user = Addr::unchecked("user");
+
+    // LP tokens can only be staked through the generator
+    let err = protocol
+        .app
+        .execute_contract(
+            user.clone(),
+            setup.lp_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: setup.proxy.to_string(),
+                amount: Uint128::from(10u128),
+                msg: to_binary(&ProxyHookMsg::Deposit {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    for msg in [
+        ProxyExecuteMsg::SendRewards {
+            account: user.to_string(),
+            amount: Uint128::from(10u128),
+        },
+        ProxyExecuteMsg::Withdraw {
+            account: user.to_string(),
+            amount: Uint128::from(10u128),
+        },
+        ProxyExecuteMsg::EmergencyWithdraw {
+            account: user.to_string(),
+            amount: Uint128::from(10u128),
+        },
+    ] {
+        let err = protocol
+            .app
+            .execute_contract(user.clone(), setup.proxy.clone(), &msg, &[])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+}
+
+#[test]
+fn stake_claim_and_withdraw() {
+    let mut protocol = ProtocolBuilder::new().with_generator(10_000000).build();
+    let setup = setup(&mut protocol);
+    let user = Addr::unchecked("user");
+
+    protocol
+        .deposit(&user, &setup.lp_token, 100_000000)
+        .unwrap();
+
+    // The LP tokens are bonded in Anchor on behalf of the proxy
+    let generator = protocol.generator();
+    assert_eq!(protocol.token_balance(&setup.lp_token, &generator), 0);
+    assert_eq!(
+        protocol.token_balance(&setup.lp_token, &setup.anchor_staking),
+        100_000000
+    );
+    let deposit: Uint128 = protocol
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.proxy, &ProxyQueryMsg::Deposit {})
+        .unwrap();
+    assert_eq!(deposit.u128(), 100_000000);
+
+    protocol.advance_blocks(10);
+
+    let pending: Uint128 = protocol
+        .app
+        .wrap()
+        .query_wasm_smart(&setup.proxy, &ProxyQueryMsg::PendingToken {})
+        .unwrap();
+    assert_eq!(pending.u128(), 10 * ANC_PER_BLOCK);
+    assert_eq!(
+        protocol
+            .pending_token(&setup.lp_token, &user)
+            .pending_on_proxy,
+        Some(vec![Asset {
+            info: token_asset_info(setup.anc_token.clone()),
+            amount: Uint128::from(10 * ANC_PER_BLOCK),
+        }])
+    );
+
+    // Withdrawing claims ANC from Anchor and forwards it to the user with SendRewards
+    protocol
+        .app
+        .execute_contract(
+            user.clone(),
+            generator.clone(),
+            &GeneratorExecuteMsg::Withdraw {
+                lp_token: setup.lp_token.to_string(),
+                amount: Uint128::from(50_000000u128),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        protocol.token_balance(&setup.anc_token, &user),
+        10 * ANC_PER_BLOCK
+    );
+    assert_eq!(protocol.token_balance(&setup.lp_token, &user), 50_000000);
+    assert_eq!(protocol.token_balance(&setup.lp_token, &setup.proxy), 0);
+    assert_eq!(
+        query_staker_info(&protocol, &setup).bond_amount.u128(),
+        50_000000
+    );
+
+    // Rewards keep accruing for the remaining stake
+    protocol.advance_blocks(5);
+    assert_eq!(
+        protocol
+            .pending_token(&setup.lp_token, &user)
+            .pending_on_proxy,
+        Some(vec![Asset {
+            info: token_asset_info(setup.anc_token.clone()),
+            amount: Uint128::from(5 * ANC_PER_BLOCK),
+        }])
+    );
+
+    // An emergency withdrawal returns all LP tokens and leaves ANC behind
+    protocol
+        .app
+        .execute_contract(
+            user.clone(),
+            generator,
+            &GeneratorExecuteMsg::EmergencyWithdraw {
+                lp_token: setup.lp_token.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        protocol.token_balance(&setup.anc_token, &user),
+        10 * ANC_PER_BLOCK
+    );
+    assert_eq!(protocol.token_balance(&setup.lp_token, &user), 100_000000);
+    assert_eq!(
+        protocol.token_balance(&setup.lp_token, &setup.anchor_staking),
+        0
+    );
+    let staker_info = query_staker_info(&protocol, &setup);
+    assert_eq!(staker_info.bond_amount.u128(), 0);
+    assert_eq!(staker_info.pending_reward.u128(), 5 * ANC_PER_BLOCK);
+}
@@ -3,7 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use astroport::generator_proxy::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use astroport::generator_proxy::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use cosmwasm_std::{Addr, Uint128};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,4 +19,9 @@ fn main() {
     export_schema_with_title(&schema_for!(Cw20HookMsg), &out_dir, "Cw20HookMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "DepositResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "RewardResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "PendingTokenResponse");
+    export_schema_with_title(&schema_for!(Addr), &out_dir, "RewardInfoResponse");
 }
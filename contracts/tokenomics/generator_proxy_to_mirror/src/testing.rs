@@ -0,0 +1,40 @@
+use crate::contract::migrate;
+use astroport::generator_proxy::MigrateMsg;
+use cosmwasm_std::attr;
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cw2::set_contract_version;
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(
+        deps.as_mut().storage,
+        "astroport-generator-proxy-to-mirror",
+        "1.0.0",
+    )
+    .unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr(
+                "previous_contract_name",
+                "astroport-generator-proxy-to-mirror"
+            ),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-generator-proxy-to-mirror"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
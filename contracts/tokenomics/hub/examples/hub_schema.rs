@@ -0,0 +1,30 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
+use cosmwasm_std::Uint128;
+
+use astroport::hub::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, HubAck, HubPacket, InstantiateMsg, MigrateMsg,
+    OutpostInfo, QueryMsg,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema_with_title(&schema_for!(InstantiateMsg), &out_dir, "InstantiateMsg");
+    export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
+    export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(Cw20HookMsg), &out_dir, "Cw20HookMsg");
+    export_schema_with_title(&schema_for!(ConfigResponse), &out_dir, "ConfigResponse");
+    export_schema_with_title(&schema_for!(Vec<OutpostInfo>), &out_dir, "OutpostsResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "UserStakeResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "OutpostBalanceResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "OutpostAstroResponse");
+    export_schema_with_title(&schema_for!(HubPacket), &out_dir, "HubPacket");
+    export_schema_with_title(&schema_for!(HubAck), &out_dir, "HubAck");
+}
@@ -0,0 +1,475 @@
+use crate::error::ContractError;
+use crate::state::{
+    Config, Outpost, PendingAction, CONFIG, OUTPOSTS, OUTPOST_ASTRO, OUTPOST_BALANCES,
+    OWNERSHIP_PROPOSAL, PENDING_ACTION, STAKES,
+};
+use astroport::asset::addr_validate_to_lower;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::hub::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, HubAck, InstantiateMsg, MigrateMsg, OutpostInfo,
+    QueryMsg,
+};
+use astroport::ics20::{
+    build_ics20_transfer_msg, validate_ibc_channel, validate_ibc_timeout, TransferMsg,
+};
+use astroport::querier::query_token_balance;
+use astroport::staking::{ConfigResponse as StakingConfigResponse, QueryMsg as StakingQueryMsg};
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Binary, ContractResult, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-hub";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The reply identifier of staking and unstaking submessages
+pub const STAKING_REPLY_ID: u64 = 1;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let staking_contract = addr_validate_to_lower(deps.api, &msg.staking_contract)?;
+    let staking_config: StakingConfigResponse = deps
+        .querier
+        .query_wasm_smart(&staking_contract, &StakingQueryMsg::Config {})?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        astro_token: addr_validate_to_lower(deps.api, &msg.astro_token)?,
+        xastro_token: staking_config.share_token_addr,
+        staking_contract,
+        ics20_contract: addr_validate_to_lower(deps.api, &msg.ics20_contract)?,
+        ics20_timeout: validate_ibc_timeout(msg.ics20_timeout)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Credits ASTRO received through the cw20-ics20 contract to an outpost.
+///
+/// * **ExecuteMsg::UpdateConfig { ics20_contract, ics20_timeout }** Updates general contract parameters.
+///
+/// * **ExecuteMsg::UpdateOutposts { add, remove }** Adds or removes outposts.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::UpdateConfig {
+            ics20_contract,
+            ics20_timeout,
+        } => update_config(deps, info, ics20_contract, ics20_timeout),
+        ExecuteMsg::UpdateOutposts { add, remove } => update_outposts(deps, info, add, remove),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and credits the received ASTRO to the outpost
+/// specified in the hook message. Only ASTRO sent by the cw20-ics20 contract is accepted.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.astro_token || cw20_msg.sender != config.ics20_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::OutpostTransfer { channel } => {
+            if OUTPOSTS.may_load(deps.storage, &channel)?.is_none() {
+                return Err(ContractError::UnknownOutpost(channel));
+            }
+
+            OUTPOST_ASTRO.update::<_, StdError>(deps.storage, &channel, |amount| {
+                Ok(amount.unwrap_or_default().checked_add(cw20_msg.amount)?)
+            })?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "outpost_transfer"),
+                attr("channel", channel),
+                attr("amount", cw20_msg.amount),
+            ]))
+        }
+    }
+}
+
+/// ## Description
+/// Updates general contract parameters. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **ics20_contract** is an [`Option`] field of type [`String`]. This is the cw20-ics20 contract used to send ASTRO back to outposts.
+///
+/// * **ics20_timeout** is an [`Option`] field of type [`u64`]. This is the IBC packet timeout in seconds for ASTRO sent back to outposts.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    ics20_contract: Option<String>,
+    ics20_timeout: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attributes = vec![attr("action", "update_config")];
+
+    if let Some(ics20_contract) = ics20_contract {
+        config.ics20_contract = addr_validate_to_lower(deps.api, &ics20_contract)?;
+        attributes.push(attr("ics20_contract", ics20_contract));
+    }
+
+    if let Some(ics20_timeout) = ics20_timeout {
+        config.ics20_timeout = validate_ibc_timeout(Some(ics20_timeout))?;
+        attributes.push(attr("ics20_timeout", ics20_timeout.to_string()));
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Adds or removes outposts that are allowed to stake through the hub. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **add** is an [`Option`] field of type [`Vec<OutpostInfo>`]. These are the outposts to add or update.
+///
+/// * **remove** is an [`Option`] field of type [`Vec<String>`]. These are the hub channels of the outposts to remove.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_outposts(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<OutpostInfo>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for channel in remove.unwrap_or_default() {
+        OUTPOSTS.remove(deps.storage, &channel);
+    }
+
+    for outpost in add.unwrap_or_default() {
+        validate_ibc_channel(&outpost.channel)?;
+        validate_ibc_channel(&outpost.ics20_channel)?;
+
+        OUTPOSTS.save(
+            deps.storage,
+            &outpost.channel,
+            &Outpost {
+                port: outpost.port,
+                ics20_channel: outpost.ics20_channel,
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_outposts"))
+}
+
+/// ## Description
+/// Credits or debits the remote user's stake once ASTRO is staked or xASTRO is unstaked, and
+/// overwrites the acknowledgement sent back to the outpost with the result. Unstaked ASTRO is
+/// sent back to the user on the outpost chain through the cw20-ics20 contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`Reply`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let action = PENDING_ACTION.load(deps.storage)?;
+    PENDING_ACTION.remove(deps.storage);
+
+    if let ContractResult::Err(error) = msg.result {
+        // The ASTRO wasn't staked, so the outpost can use it for another stake
+        if let PendingAction::Stake {
+            channel, amount, ..
+        } = action
+        {
+            OUTPOST_ASTRO.update::<_, StdError>(deps.storage, &channel, |outpost_astro| {
+                Ok(outpost_astro.unwrap_or_default().checked_add(amount)?)
+            })?;
+        }
+
+        return Ok(Response::new()
+            .set_data(to_binary(&HubAck::Error(error.clone()))?)
+            .add_attributes(vec![attr("action", "staking_failed"), attr("error", error)]));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    match action {
+        PendingAction::Stake {
+            channel,
+            receiver,
+            xastro_balance,
+            ..
+        } => {
+            let minted =
+                query_token_balance(&deps.querier, config.xastro_token, env.contract.address)?
+                    .checked_sub(xastro_balance)?;
+
+            STAKES.update::<_, StdError>(deps.storage, (&channel, &receiver), |stake| {
+                Ok(stake.unwrap_or_default().checked_add(minted)?)
+            })?;
+            OUTPOST_BALANCES.update::<_, StdError>(deps.storage, &channel, |balance| {
+                Ok(balance.unwrap_or_default().checked_add(minted)?)
+            })?;
+
+            Ok(Response::new()
+                .set_data(to_binary(&HubAck::Result(to_binary(&minted)?))?)
+                .add_attributes(vec![
+                    attr("action", "stake"),
+                    attr("channel", channel),
+                    attr("receiver", receiver),
+                    attr("xastro_amount", minted),
+                ]))
+        }
+        PendingAction::Unstake {
+            channel,
+            receiver,
+            amount,
+            astro_balance,
+        } => {
+            let returned = query_token_balance(
+                &deps.querier,
+                config.astro_token.clone(),
+                env.contract.address,
+            )?
+            .checked_sub(astro_balance)?;
+
+            STAKES.update::<_, StdError>(deps.storage, (&channel, &receiver), |stake| {
+                Ok(stake.unwrap_or_default().checked_sub(amount)?)
+            })?;
+            OUTPOST_BALANCES.update::<_, StdError>(deps.storage, &channel, |balance| {
+                Ok(balance.unwrap_or_default().checked_sub(amount)?)
+            })?;
+
+            let outpost = OUTPOSTS.load(deps.storage, &channel)?;
+            let transfer_msg = build_ics20_transfer_msg(
+                &config.astro_token,
+                &config.ics20_contract,
+                returned,
+                &TransferMsg {
+                    channel: outpost.ics20_channel,
+                    remote_address: receiver.clone(),
+                    timeout: Some(config.ics20_timeout),
+                    memo: None,
+                },
+            )?;
+
+            Ok(Response::new()
+                .add_message(transfer_msg)
+                .set_data(to_binary(&HubAck::Result(to_binary(&returned)?))?)
+                .add_attributes(vec![
+                    attr("action", "unstake"),
+                    attr("channel", channel),
+                    attr("receiver", receiver),
+                    attr("xastro_amount", amount),
+                    attr("astro_amount", returned),
+                ]))
+        }
+    }
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Outposts {}** Returns a vector of registered outposts using [`OutpostInfo`] objects.
+///
+/// * **QueryMsg::UserStake { channel, user }** Returns the amount of xASTRO staked on behalf of a remote user.
+///
+/// * **QueryMsg::OutpostBalance { channel }** Returns the total amount of xASTRO staked on behalf of an outpost's users.
+///
+/// * **QueryMsg::OutpostAstro { channel }** Returns the amount of ASTRO received from an outpost that isn't staked yet.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Outposts {} => to_binary(&query_outposts(deps)?),
+        QueryMsg::UserStake { channel, user } => to_binary(
+            &STAKES
+                .may_load(deps.storage, (&channel, &user))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::OutpostBalance { channel } => to_binary(
+            &OUTPOST_BALANCES
+                .may_load(deps.storage, &channel)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::OutpostAstro { channel } => to_binary(
+            &OUTPOST_ASTRO
+                .may_load(deps.storage, &channel)?
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// ## Description
+/// Returns the contract configuration using a [`ConfigResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner,
+        astro_token: config.astro_token,
+        xastro_token: config.xastro_token,
+        staking_contract: config.staking_contract,
+        ics20_contract: config.ics20_contract,
+        ics20_timeout: config.ics20_timeout,
+    })
+}
+
+/// ## Description
+/// Returns a vector of registered outposts using [`OutpostInfo`] objects.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+fn query_outposts(deps: Deps) -> StdResult<Vec<OutpostInfo>> {
+    OUTPOSTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (channel, outpost) = item?;
+            Ok(OutpostInfo {
+                channel: String::from_utf8(channel)?,
+                port: outpost.port,
+                ics20_channel: outpost.ics20_channel,
+            })
+        })
+        .collect()
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,40 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes hub contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid IBC channel ordering. Expected {0}")]
+    InvalidChannelOrder(String),
+
+    #[error("Invalid IBC channel version {0}. Expected {1}")]
+    InvalidChannelVersion(String, String),
+
+    #[error("Unknown outpost channel {0}")]
+    UnknownOutpost(String),
+
+    #[error("Invalid source port {0}. Expected {1}")]
+    InvalidSourcePort(String, String),
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Not enough ASTRO received from the outpost. Available: {0}")]
+    InsufficientAstro(Uint128),
+
+    #[error("Not enough xASTRO staked. Available: {0}")]
+    InsufficientStake(Uint128),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
@@ -0,0 +1,262 @@
+use crate::contract::STAKING_REPLY_ID;
+use crate::error::ContractError;
+use crate::state::{PendingAction, CONFIG, OUTPOSTS, OUTPOST_ASTRO, PENDING_ACTION, STAKES};
+use astroport::hub::{HubAck, HubPacket, HUB_IBC_VERSION};
+use astroport::querier::query_token_balance;
+use astroport::staking::Cw20HookMsg as StakingHookMsg;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, DepsMut, Env, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcOrder, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, SubMsg, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+/// ## Description
+/// Validates a new channel. Only unordered channels with the hub IBC version are accepted.
+/// Packets are only processed on channels registered as outposts by the owner.
+/// ## Params
+/// * **_deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelOpenMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())
+}
+
+/// ## Description
+/// Validates the newly connected channel.
+/// ## Params
+/// * **_deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelConnectMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    validate_channel(channel, msg.counterparty_version())?;
+
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        attr("action", "ibc_connect"),
+        attr("channel_id", &channel.endpoint.channel_id),
+    ]))
+}
+
+/// ## Description
+/// Handles a closed channel. Stakes made through the channel stay on the hub and can be
+/// unstaked once the outpost is registered on a new channel by the owner.
+/// ## Params
+/// * **_deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcChannelCloseMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new().add_attributes(vec![
+        attr("action", "ibc_close"),
+        attr("channel_id", &msg.channel().endpoint.channel_id),
+    ]))
+}
+
+/// ## Description
+/// Handles a packet sent by an outpost. Staking and unstaking are executed in a submessage,
+/// so that a failure is acknowledged with an error instead of reverting the packet.
+/// Invalid packets are acknowledged with an error as well.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`IbcPacketReceiveMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    do_packet_receive(deps, env, msg).or_else(|err| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(to_binary(&HubAck::Error(err.to_string()))?)
+            .add_attributes(vec![
+                attr("action", "receive_packet"),
+                attr("error", err.to_string()),
+            ]))
+    })
+}
+
+/// ## Description
+/// Validates the packet source and schedules staking or unstaking on behalf of the remote user.
+/// The acknowledgement is set with the result in the contract's `reply`.
+fn do_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let packet = msg.packet;
+    let channel = packet.dest.channel_id;
+
+    let outpost = OUTPOSTS
+        .may_load(deps.storage, &channel)?
+        .ok_or_else(|| ContractError::UnknownOutpost(channel.clone()))?;
+
+    if packet.src.port_id != outpost.port {
+        return Err(ContractError::InvalidSourcePort(
+            packet.src.port_id,
+            outpost.port,
+        ));
+    }
+
+    let (action, token, amount, hook_msg) = match from_binary(&packet.data)? {
+        HubPacket::Stake { receiver, amount } => {
+            if amount.is_zero() {
+                return Err(ContractError::ZeroAmount {});
+            }
+
+            // The ASTRO must have been transferred to the hub and credited to this outpost before
+            // the packet is received. Other outposts' ASTRO can't be staked
+            let outpost_astro = OUTPOST_ASTRO
+                .may_load(deps.storage, &channel)?
+                .unwrap_or_default();
+            if outpost_astro < amount {
+                return Err(ContractError::InsufficientAstro(outpost_astro));
+            }
+            OUTPOST_ASTRO.save(deps.storage, &channel, &(outpost_astro - amount))?;
+
+            let xastro_balance =
+                query_token_balance(&deps.querier, config.xastro_token, env.contract.address)?;
+            PENDING_ACTION.save(
+                deps.storage,
+                &PendingAction::Stake {
+                    channel: channel.clone(),
+                    receiver,
+                    amount,
+                    xastro_balance,
+                },
+            )?;
+
+            (
+                "stake",
+                config.astro_token,
+                amount,
+                StakingHookMsg::Enter {},
+            )
+        }
+        HubPacket::Unstake { receiver, amount } => {
+            if amount.is_zero() {
+                return Err(ContractError::ZeroAmount {});
+            }
+
+            let stake = STAKES
+                .may_load(deps.storage, (&channel, &receiver))?
+                .unwrap_or_default();
+            if stake < amount {
+                return Err(ContractError::InsufficientStake(stake));
+            }
+
+            let astro_balance =
+                query_token_balance(&deps.querier, config.astro_token, env.contract.address)?;
+            PENDING_ACTION.save(
+                deps.storage,
+                &PendingAction::Unstake {
+                    channel: channel.clone(),
+                    receiver,
+                    amount,
+                    astro_balance,
+                },
+            )?;
+
+            (
+                "unstake",
+                config.xastro_token,
+                amount,
+                StakingHookMsg::Leave {},
+            )
+        }
+    };
+
+    Ok(IbcReceiveResponse::new()
+        .add_submessage(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: config.staking_contract.to_string(),
+                    amount,
+                    msg: to_binary(&hook_msg)?,
+                })?,
+                funds: vec![],
+            },
+            STAKING_REPLY_ID,
+        ))
+        .add_attributes(vec![
+            attr("action", "receive_packet"),
+            attr("packet_action", action),
+            attr("channel", channel),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// The hub never sends packets, so acknowledgements are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// ## Description
+/// The hub never sends packets, so timeouts are ignored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// ## Description
+/// Checks the channel ordering and version. Returns a [`ContractError`]
+/// if the channel can't be used to communicate with outposts.
+fn validate_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidChannelOrder(String::from(
+            "ORDER_UNORDERED",
+        )));
+    }
+
+    for version in [Some(channel.version.as_str()), counterparty_version]
+        .into_iter()
+        .flatten()
+    {
+        if version != HUB_IBC_VERSION {
+            return Err(ContractError::InvalidChannelVersion(
+                version.to_string(),
+                HUB_IBC_VERSION.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
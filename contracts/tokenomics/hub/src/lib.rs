@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod error;
+pub mod ibc;
+pub mod state;
+
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
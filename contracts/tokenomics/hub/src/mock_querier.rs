@@ -0,0 +1,99 @@
+use astroport::staking::{ConfigResponse as StakingConfigResponse, QueryMsg as StakingQueryMsg};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_querier: TokenQuerier,
+}
+
+#[derive(Clone, Default)]
+pub struct TokenQuerier {
+    // this lets us iterate over all token balances keyed by token address and holder
+    balances: HashMap<(String, String), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if contract_addr == "staking" {
+                    match from_binary(msg).unwrap() {
+                        StakingQueryMsg::Config {} => SystemResult::Ok(
+                            to_binary(&StakingConfigResponse {
+                                deposit_token_addr: Addr::unchecked("astro"),
+                                share_token_addr: Addr::unchecked("xastro"),
+                                native_astro: None,
+                            })
+                            .into(),
+                        ),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else {
+                    match from_binary(msg).unwrap() {
+                        Cw20QueryMsg::Balance { address } => {
+                            let balance = self
+                                .token_querier
+                                .balances
+                                .get(&(contract_addr.clone(), address))
+                                .cloned()
+                                .unwrap_or_default();
+                            SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_querier: TokenQuerier::default(),
+        }
+    }
+
+    pub fn set_token_balance(&mut self, token: &str, holder: &str, balance: u128) {
+        self.token_querier.balances.insert(
+            (token.to_string(), holder.to_string()),
+            Uint128::from(balance),
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the hub contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The xASTRO token contract address
+    pub xastro_token: Addr,
+    /// The xASTRO staking contract address
+    pub staking_contract: Addr,
+    /// The cw20-ics20 contract used to send ASTRO back to outposts
+    pub ics20_contract: Addr,
+    /// The IBC packet timeout in seconds for ASTRO sent back to outposts
+    pub ics20_timeout: u64,
+}
+
+/// ## Description
+/// This structure describes an outpost that is allowed to stake through the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Outpost {
+    /// The IBC port of the outpost contract
+    pub port: String,
+    /// The cw20-ics20 channel used to send ASTRO to the outpost chain
+    pub ics20_channel: String,
+}
+
+/// ## Description
+/// This enum describes the staking actions that are waiting for a reply.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingAction {
+    /// ASTRO is being staked. Holds the staked amount and the hub's xASTRO balance before staking
+    Stake {
+        channel: String,
+        receiver: String,
+        amount: Uint128,
+        xastro_balance: Uint128,
+    },
+    /// xASTRO is being unstaked. Holds the hub's ASTRO balance before unstaking
+    Unstake {
+        channel: String,
+        receiver: String,
+        amount: Uint128,
+        astro_balance: Uint128,
+    },
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// ## Description
+/// Stores outposts using their hub channel as the key
+pub const OUTPOSTS: Map<&str, Outpost> = Map::new("outposts");
+
+/// ## Description
+/// Stores the amount of xASTRO staked on behalf of remote users.
+/// The key is the hub channel of the outpost and the user address on the outpost chain
+pub const STAKES: Map<(&str, &str), Uint128> = Map::new("stakes");
+
+/// ## Description
+/// Stores the total amount of xASTRO staked on behalf of each outpost's users
+pub const OUTPOST_BALANCES: Map<&str, Uint128> = Map::new("outpost_balances");
+
+/// ## Description
+/// Stores the amount of ASTRO received from each outpost that isn't staked yet.
+/// Stake packets can only use the ASTRO credited to their own outpost
+pub const OUTPOST_ASTRO: Map<&str, Uint128> = Map::new("outpost_astro");
+
+/// ## Description
+/// Stores the staking action that is waiting for a reply
+pub const PENDING_ACTION: Item<PendingAction> = Item::new("pending_action");
@@ -0,0 +1,625 @@
+use cosmwasm_std::testing::{
+    mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_recv,
+    mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, ContractResult, CosmosMsg, IbcOrder, OwnedDeps, Reply,
+    SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query, reply, STAKING_REPLY_ID};
+use crate::error::ContractError;
+use crate::ibc::{ibc_channel_connect, ibc_channel_open, ibc_packet_receive};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::hub::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, HubAck, HubPacket, InstantiateMsg, OutpostInfo,
+    QueryMsg, HUB_IBC_VERSION,
+};
+use astroport::ics20::TransferMsg;
+use astroport::staking::Cw20HookMsg as StakingHookMsg;
+
+/// The port used by `mock_ibc_packet_recv` as the source port
+const OUTPOST_PORT: &str = "their-port";
+const OUTPOST_CHANNEL: &str = "channel-0";
+const ICS20_CHANNEL: &str = "channel-1";
+
+type HubDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn setup() -> HubDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            astro_token: String::from("astro"),
+            staking_contract: String::from("staking"),
+            ics20_contract: String::from("ics20"),
+            ics20_timeout: None,
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOutposts {
+            add: Some(vec![OutpostInfo {
+                channel: String::from(OUTPOST_CHANNEL),
+                port: String::from(OUTPOST_PORT),
+                ics20_channel: String::from(ICS20_CHANNEL),
+            }]),
+            remove: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn outpost_transfer(
+    deps: &mut HubDeps,
+    token: &str,
+    sender: &str,
+    channel: &str,
+    amount: u128,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(sender),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::OutpostTransfer {
+                channel: String::from(channel),
+            })
+            .unwrap(),
+        }),
+    )
+    .map(|_| ())
+}
+
+fn receive(deps: &mut HubDeps, channel: &str, packet: &HubPacket) -> (Vec<CosmosMsg>, HubAck) {
+    let msg = mock_ibc_packet_recv(channel, packet).unwrap();
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+
+    let ack = if res.acknowledgement.is_empty() {
+        HubAck::Result(Default::default())
+    } else {
+        from_binary(&res.acknowledgement).unwrap()
+    };
+
+    (res.messages.into_iter().map(|m| m.msg).collect(), ack)
+}
+
+fn reply_ok(deps: &mut HubDeps) -> (Vec<CosmosMsg>, HubAck) {
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: STAKING_REPLY_ID,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    (
+        res.messages.into_iter().map(|m| m.msg).collect(),
+        from_binary(&res.data.unwrap()).unwrap(),
+    )
+}
+
+fn query_user_stake(deps: &HubDeps, user: &str) -> Uint128 {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserStake {
+                channel: String::from(OUTPOST_CHANNEL),
+                user: String::from(user),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn query_outpost_astro(deps: &HubDeps, channel: &str) -> Uint128 {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OutpostAstro {
+                channel: String::from(channel),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn query_outpost_balance(deps: &HubDeps) -> Uint128 {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OutpostBalance {
+                channel: String::from(OUTPOST_CHANNEL),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let deps = setup();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, "owner");
+    assert_eq!(config.astro_token, "astro");
+    assert_eq!(config.xastro_token, "xastro");
+    assert_eq!(config.staking_contract, "staking");
+    assert_eq!(config.ics20_contract, "ics20");
+    assert_eq!(config.ics20_timeout, 600);
+
+    let outposts: Vec<OutpostInfo> =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Outposts {}).unwrap()).unwrap();
+    assert_eq!(
+        outposts,
+        vec![OutpostInfo {
+            channel: String::from(OUTPOST_CHANNEL),
+            port: String::from(OUTPOST_PORT),
+            ics20_channel: String::from(ICS20_CHANNEL),
+        }]
+    );
+}
+
+#[test]
+fn update_outposts() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        ExecuteMsg::UpdateOutposts {
+            add: None,
+            remove: Some(vec![String::from(OUTPOST_CHANNEL)]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOutposts {
+            add: Some(vec![OutpostInfo {
+                channel: String::from("channel-2"),
+                port: String::from(OUTPOST_PORT),
+                ics20_channel: String::from("connection-2"),
+            }]),
+            remove: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid IBC channel connection-2"
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOutposts {
+            add: None,
+            remove: Some(vec![String::from(OUTPOST_CHANNEL)]),
+        },
+    )
+    .unwrap();
+
+    let outposts: Vec<OutpostInfo> =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Outposts {}).unwrap()).unwrap();
+    assert!(outposts.is_empty());
+
+    // Packets from removed outposts are rejected
+    let (messages, ack) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Stake {
+            receiver: String::from("user"),
+            amount: Uint128::from(100u128),
+        },
+    );
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::UnknownOutpost(String::from(OUTPOST_CHANNEL)).to_string())
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        ics20_contract: Some(String::from("new_ics20")),
+        ics20_timeout: Some(1200),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            ics20_contract: None,
+            ics20_timeout: Some(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: IBC timeout must be between 60 and 86400 seconds"
+    );
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.ics20_contract, "new_ics20");
+    assert_eq!(config.ics20_timeout, 1200);
+}
+
+#[test]
+fn channel_handshake() {
+    let mut deps = setup();
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(OUTPOST_CHANNEL, IbcOrder::Ordered, HUB_IBC_VERSION),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidChannelOrder(String::from("ORDER_UNORDERED"))
+    );
+
+    let err = ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(OUTPOST_CHANNEL, IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidChannelVersion(
+            String::from("ics20-1"),
+            String::from(HUB_IBC_VERSION)
+        )
+    );
+
+    ibc_channel_open(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_open_init(OUTPOST_CHANNEL, IbcOrder::Unordered, HUB_IBC_VERSION),
+    )
+    .unwrap();
+    ibc_channel_connect(
+        deps.as_mut(),
+        mock_env(),
+        mock_ibc_channel_connect_ack(OUTPOST_CHANNEL, IbcOrder::Unordered, HUB_IBC_VERSION),
+    )
+    .unwrap();
+}
+
+#[test]
+fn stake_and_unstake() {
+    let mut deps = setup();
+
+    // The ASTRO hasn't arrived through the cw20-ics20 contract yet
+    let packet = HubPacket::Stake {
+        receiver: String::from("user"),
+        amount: Uint128::from(100u128),
+    };
+    let (messages, ack) = receive(&mut deps, OUTPOST_CHANNEL, &packet);
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::InsufficientAstro(Uint128::zero()).to_string())
+    );
+
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+    outpost_transfer(&mut deps, "astro", "ics20", OUTPOST_CHANNEL, 100).unwrap();
+    let (messages, _) = receive(&mut deps, OUTPOST_CHANNEL, &packet);
+    assert_eq!(
+        messages,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("staking"),
+                amount: Uint128::from(100u128),
+                msg: to_binary(&StakingHookMsg::Enter {}).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert_eq!(query_outpost_astro(&deps, OUTPOST_CHANNEL), Uint128::zero());
+
+    // The staking contract mints 80 xASTRO
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 0);
+    deps.querier
+        .set_token_balance("xastro", MOCK_CONTRACT_ADDR, 80);
+    let (messages, ack) = reply_ok(&mut deps);
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Result(to_binary(&Uint128::from(80u128)).unwrap())
+    );
+    assert_eq!(query_user_stake(&deps, "user"), Uint128::from(80u128));
+    assert_eq!(query_outpost_balance(&deps), Uint128::from(80u128));
+
+    // Users can't unstake more than they staked
+    let (messages, ack) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Unstake {
+            receiver: String::from("user"),
+            amount: Uint128::from(81u128),
+        },
+    );
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::InsufficientStake(Uint128::from(80u128)).to_string())
+    );
+
+    let (messages, _) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Unstake {
+            receiver: String::from("user"),
+            amount: Uint128::from(30u128),
+        },
+    );
+    assert_eq!(
+        messages,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("xastro"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("staking"),
+                amount: Uint128::from(30u128),
+                msg: to_binary(&StakingHookMsg::Leave {}).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // The staking contract returns 40 ASTRO which are sent back to the user on the outpost chain
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 40);
+    let (messages, ack) = reply_ok(&mut deps);
+    assert_eq!(
+        ack,
+        HubAck::Result(to_binary(&Uint128::from(40u128)).unwrap())
+    );
+    assert_eq!(
+        messages,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("ics20"),
+                amount: Uint128::from(40u128),
+                msg: to_binary(&TransferMsg {
+                    channel: String::from(ICS20_CHANNEL),
+                    remote_address: String::from("user"),
+                    timeout: Some(600),
+                    memo: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert_eq!(query_user_stake(&deps, "user"), Uint128::from(50u128));
+    assert_eq!(query_outpost_balance(&deps), Uint128::from(50u128));
+}
+
+#[test]
+fn failed_staking() {
+    let mut deps = setup();
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+    outpost_transfer(&mut deps, "astro", "ics20", OUTPOST_CHANNEL, 100).unwrap();
+
+    receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Stake {
+            receiver: String::from("user"),
+            amount: Uint128::from(100u128),
+        },
+    );
+
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: STAKING_REPLY_ID,
+            result: ContractResult::Err(String::from("Staking failed")),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<HubAck>(&res.data.unwrap()).unwrap(),
+        HubAck::Error(String::from("Staking failed"))
+    );
+    assert_eq!(query_user_stake(&deps, "user"), Uint128::zero());
+    assert_eq!(query_outpost_balance(&deps), Uint128::zero());
+
+    // The ASTRO can be staked again
+    assert_eq!(
+        query_outpost_astro(&deps, OUTPOST_CHANNEL),
+        Uint128::from(100u128)
+    );
+}
+
+#[test]
+fn stake_only_outpost_astro() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOutposts {
+            add: Some(vec![OutpostInfo {
+                channel: String::from("channel-2"),
+                port: String::from(OUTPOST_PORT),
+                ics20_channel: String::from("channel-3"),
+            }]),
+            remove: None,
+        },
+    )
+    .unwrap();
+
+    // Only ASTRO sent by the cw20-ics20 contract to a registered outpost is credited
+    let err = outpost_transfer(&mut deps, "xastro", "ics20", OUTPOST_CHANNEL, 100).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = outpost_transfer(&mut deps, "astro", "user", OUTPOST_CHANNEL, 100).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = outpost_transfer(&mut deps, "astro", "ics20", "channel-5", 100).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnknownOutpost(String::from("channel-5"))
+    );
+
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+    outpost_transfer(&mut deps, "astro", "ics20", OUTPOST_CHANNEL, 100).unwrap();
+    assert_eq!(
+        query_outpost_astro(&deps, OUTPOST_CHANNEL),
+        Uint128::from(100u128)
+    );
+
+    // The other outpost can't stake the ASTRO even though the hub holds it
+    let (messages, ack) = receive(
+        &mut deps,
+        "channel-2",
+        &HubPacket::Stake {
+            receiver: String::from("attacker"),
+            amount: Uint128::from(100u128),
+        },
+    );
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::InsufficientAstro(Uint128::zero()).to_string())
+    );
+
+    // The outpost can't stake more than it sent
+    let (messages, _) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Stake {
+            receiver: String::from("user"),
+            amount: Uint128::from(60u128),
+        },
+    );
+    assert_eq!(messages.len(), 1);
+    deps.querier
+        .set_token_balance("xastro", MOCK_CONTRACT_ADDR, 60);
+    reply_ok(&mut deps);
+    assert_eq!(
+        query_outpost_astro(&deps, OUTPOST_CHANNEL),
+        Uint128::from(40u128)
+    );
+
+    let (messages, ack) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Stake {
+            receiver: String::from("user"),
+            amount: Uint128::from(50u128),
+        },
+    );
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::InsufficientAstro(Uint128::from(40u128)).to_string())
+    );
+}
+
+#[test]
+fn reject_invalid_packets() {
+    let mut deps = setup();
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+
+    let packet = HubPacket::Stake {
+        receiver: String::from("user"),
+        amount: Uint128::from(100u128),
+    };
+
+    // Unknown channel
+    let (messages, ack) = receive(&mut deps, "channel-5", &packet);
+    assert!(messages.is_empty());
+    assert_eq!(
+        ack,
+        HubAck::Error(ContractError::UnknownOutpost(String::from("channel-5")).to_string())
+    );
+
+    // Wrong source port
+    let mut msg = mock_ibc_packet_recv(OUTPOST_CHANNEL, &packet).unwrap();
+    msg.packet.src.port_id = String::from("wasm.attacker");
+    let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        from_binary::<HubAck>(&res.acknowledgement).unwrap(),
+        HubAck::Error(
+            ContractError::InvalidSourcePort(
+                String::from("wasm.attacker"),
+                String::from(OUTPOST_PORT)
+            )
+            .to_string()
+        )
+    );
+
+    // Zero amount
+    let (messages, ack) = receive(
+        &mut deps,
+        OUTPOST_CHANNEL,
+        &HubPacket::Stake {
+            receiver: String::from("user"),
+            amount: Uint128::zero(),
+        },
+    );
+    assert!(messages.is_empty());
+    assert_eq!(ack, HubAck::Error(ContractError::ZeroAmount {}.to_string()));
+}
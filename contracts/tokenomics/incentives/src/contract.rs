@@ -0,0 +1,908 @@
+use crate::error::ContractError;
+use crate::state::{Config, PoolInfo, UserInfo, CONFIG, OWNERSHIP_PROPOSAL, POOLS, USERS};
+use astroport::asset::{addr_validate_to_lower, pair_info_by_pool, Asset, AssetInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::incentives::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, IncentivizationFeeInfo, InputSchedule, InstantiateMsg,
+    MigrateMsg, PoolInfoResponse, QueryMsg, RewardSchedule, MAX_REWARD_TOKENS,
+    MAX_SCHEDULES_PER_REWARD,
+};
+use astroport::querier::query_pair_info;
+use astroport::simulation::calculate_rewards_per_share;
+use astroport::DecimalCheckedOps;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-incentives";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        factory: addr_validate_to_lower(deps.api, &msg.factory)?,
+        incentivization_fee_info: msg
+            .incentivization_fee_info
+            .map(|fee_info| validate_fee_info(deps.as_ref(), fee_info))
+            .transpose()?,
+        blocked_tokens: vec![],
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::Withdraw { lp_token, amount }** Withdraws staked LP tokens along with all pending rewards.
+///
+/// * **ExecuteMsg::ClaimRewards { lp_tokens }** Claims pending rewards from the specified pools.
+///
+/// * **ExecuteMsg::Incentivize { lp_token, schedule }** Attaches a reward schedule to a pool.
+///
+/// * **ExecuteMsg::RemoveRewardFromPool { lp_token, reward, receiver }** Removes a reward token from a pool.
+///
+/// * **ExecuteMsg::UpdateTokensBlockedlist { add, remove }** Adds or removes tokens from the blocked list.
+///
+/// * **ExecuteMsg::UpdateConfig { incentivization_fee_info }** Updates the contract configuration.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Withdraw { lp_token, amount } => withdraw(deps, env, info, lp_token, amount),
+        ExecuteMsg::ClaimRewards { lp_tokens } => claim_rewards(deps, env, info, lp_tokens),
+        ExecuteMsg::Incentivize { lp_token, schedule } => {
+            incentivize(deps, env, info, lp_token, schedule)
+        }
+        ExecuteMsg::RemoveRewardFromPool {
+            lp_token,
+            reward,
+            receiver,
+        } => remove_reward_from_pool(deps, env, info, lp_token, reward, receiver),
+        ExecuteMsg::UpdateTokensBlockedlist { add, remove } => {
+            update_tokens_blockedlist(deps, info, add, remove)
+        }
+        ExecuteMsg::UpdateConfig {
+            incentivization_fee_info,
+        } => update_config(deps, info, incentivization_fee_info),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then a [`ContractError`] is returned.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message that has to be processed.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit { recipient } => {
+            let recipient = addr_validate_to_lower(
+                deps.api,
+                &recipient.unwrap_or_else(|| cw20_msg.sender.clone()),
+            )?;
+            deposit(deps, env, info.sender, recipient, cw20_msg.amount)
+        }
+    }
+}
+
+/// ## Description
+/// Stakes LP tokens for the recipient. Pending rewards of the recipient are paid out first.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token that was sent.
+///
+/// * **recipient** is an object of type [`Addr`]. This is the address that the LP tokens are staked for.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to stake.
+fn deposit(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut pool = load_or_create_pool(deps.as_ref(), &env, &config, &lp_token)?;
+    update_pool(&mut pool, env.block.time.seconds())?;
+
+    let mut user = USERS
+        .may_load(deps.storage, (&lp_token, &recipient))?
+        .unwrap_or_default();
+    let messages = claim_user_rewards(deps.as_ref(), &config, &pool, &mut user, &recipient)?;
+
+    user.amount = user.amount.checked_add(amount)?;
+    pool.total_lp = pool.total_lp.checked_add(amount)?;
+
+    USERS.save(deps.storage, (&lp_token, &recipient), &user)?;
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "deposit"),
+        attr("lp_token", lp_token),
+        attr("recipient", recipient),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Withdraws staked LP tokens along with all pending rewards. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token to withdraw.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to withdraw.
+fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+    let mut user = USERS
+        .may_load(deps.storage, (&lp_token, &info.sender))?
+        .unwrap_or_default();
+    if user.amount < amount {
+        return Err(ContractError::AmountExceedsBalance {});
+    }
+
+    let mut pool = POOLS.load(deps.storage, &lp_token)?;
+    update_pool(&mut pool, env.block.time.seconds())?;
+
+    let mut messages = claim_user_rewards(deps.as_ref(), &config, &pool, &mut user, &info.sender)?;
+
+    user.amount = user.amount.checked_sub(amount)?;
+    pool.total_lp = pool.total_lp.checked_sub(amount)?;
+
+    if user.amount.is_zero() {
+        USERS.remove(deps.storage, (&lp_token, &info.sender));
+    } else {
+        USERS.save(deps.storage, (&lp_token, &info.sender), &user)?;
+    }
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "withdraw"),
+        attr("lp_token", lp_token),
+        attr("user", info.sender),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Claims pending rewards from the specified pools. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_tokens** is a vector that contains objects of type [`String`]. These are the LP tokens whose
+/// pools to claim rewards from.
+fn claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_tokens: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut lp_addrs: Vec<Addr> = vec![];
+    for lp_token in &lp_tokens {
+        let lp_token = addr_validate_to_lower(deps.api, lp_token)?;
+        if !lp_addrs.contains(&lp_token) {
+            lp_addrs.push(lp_token);
+        }
+    }
+
+    let mut messages = vec![];
+    for lp_token in lp_addrs {
+        let mut pool = POOLS.load(deps.storage, &lp_token)?;
+        update_pool(&mut pool, env.block.time.seconds())?;
+
+        let mut user = USERS
+            .may_load(deps.storage, (&lp_token, &info.sender))?
+            .unwrap_or_default();
+        messages.extend(claim_user_rewards(
+            deps.as_ref(),
+            &config,
+            &pool,
+            &mut user,
+            &info.sender,
+        )?);
+
+        if !user.amount.is_zero() {
+            USERS.save(deps.storage, (&lp_token, &info.sender), &user)?;
+        }
+        POOLS.save(deps.storage, &lp_token, &pool)?;
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "claim_rewards"),
+        attr("user", info.sender),
+    ]))
+}
+
+/// ## Description
+/// Attaches a reward schedule to a pool. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool to incentivize.
+///
+/// * **schedule** is an object of type [`InputSchedule`]. This is the reward schedule to attach.
+fn incentivize(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    schedule: InputSchedule,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let now = env.block.time.seconds();
+
+    schedule.reward.info.check(deps.api)?;
+    if schedule.reward.info
+        == (AssetInfo::Token {
+            contract_addr: lp_token.clone(),
+        })
+    {
+        return Err(ContractError::LpTokenAsReward {});
+    }
+    if config.blocked_tokens.contains(&schedule.reward.info) {
+        return Err(ContractError::BlockedToken(
+            schedule.reward.info.to_string(),
+        ));
+    }
+    if schedule.start_ts < now || schedule.end_ts <= schedule.start_ts {
+        return Err(ContractError::InvalidSchedulePeriod {});
+    }
+
+    let rps = Decimal::from_ratio(schedule.reward.amount, schedule.end_ts - schedule.start_ts);
+    if rps.is_zero() {
+        return Err(ContractError::RewardAmountTooLow {});
+    }
+
+    let mut pool = load_or_create_pool(deps.as_ref(), &env, &config, &lp_token)?;
+    update_pool(&mut pool, now)?;
+
+    // Adding a new reward token to a pool costs the incentivization fee
+    let reward_schedules = pool
+        .schedules
+        .iter()
+        .filter(|s| s.reward == schedule.reward.info)
+        .count();
+    let is_new_reward = reward_schedules == 0;
+    if is_new_reward {
+        let active_rewards = active_reward_tokens(&pool);
+        if active_rewards.len() >= MAX_REWARD_TOKENS {
+            return Err(ContractError::TooManyRewardTokens(MAX_REWARD_TOKENS));
+        }
+    } else if reward_schedules >= MAX_SCHEDULES_PER_REWARD {
+        // Extending an active reward token is free, so the amount of its schedules is capped instead
+        return Err(ContractError::TooManySchedules(MAX_SCHEDULES_PER_REWARD));
+    }
+    let fee = match &config.incentivization_fee_info {
+        Some(fee_info) if is_new_reward && info.sender != config.owner => Some(fee_info.clone()),
+        _ => None,
+    };
+
+    // Check that exactly the reward (if it's native) and the fee were sent
+    let mut expected_funds: Vec<Coin> = vec![];
+    if let AssetInfo::NativeToken { denom } = &schedule.reward.info {
+        add_coin(&mut expected_funds, denom, schedule.reward.amount)?;
+    }
+    if let Some(fee_info) = &fee {
+        add_coin(
+            &mut expected_funds,
+            &fee_info.fee.denom,
+            fee_info.fee.amount,
+        )?;
+    }
+    if !funds_match(&info.funds, &expected_funds) {
+        let expected = expected_funds
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(ContractError::InvalidFunds(expected));
+    }
+
+    let mut messages = vec![];
+    if let AssetInfo::Token { contract_addr } = &schedule.reward.info {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: info.sender.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: schedule.reward.amount,
+            })?,
+            funds: vec![],
+        }));
+    }
+    if let Some(fee_info) = fee {
+        messages.push(
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: fee_info.fee.denom,
+                },
+                amount: fee_info.fee.amount,
+            }
+            .into_msg(&deps.querier, fee_info.fee_receiver)?,
+        );
+    }
+
+    pool.schedules.push(RewardSchedule {
+        reward: schedule.reward.info.clone(),
+        rps,
+        start_ts: schedule.start_ts,
+        end_ts: schedule.end_ts,
+    });
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "incentivize"),
+        attr("lp_token", lp_token),
+        attr("reward", schedule.reward.to_string()),
+        attr("start_ts", schedule.start_ts.to_string()),
+        attr("end_ts", schedule.end_ts.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Removes all schedules of a reward token from a pool and sends the undistributed rewards to the receiver.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token of the pool.
+///
+/// * **reward** is an object of type [`AssetInfo`]. This is the reward token to remove.
+///
+/// * **receiver** is an object of type [`String`]. This is the address that receives the undistributed rewards.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn remove_reward_from_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    reward: AssetInfo,
+    receiver: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let receiver = addr_validate_to_lower(deps.api, &receiver)?;
+    let now = env.block.time.seconds();
+
+    let mut pool = POOLS.load(deps.storage, &lp_token)?;
+    update_pool(&mut pool, now)?;
+
+    let (removed, schedules): (Vec<_>, Vec<_>) =
+        pool.schedules.into_iter().partition(|s| s.reward == reward);
+    if removed.is_empty() {
+        return Err(ContractError::RewardNotFound(reward.to_string()));
+    }
+    pool.schedules = schedules;
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    let mut undistributed = Uint128::zero();
+    for schedule in removed {
+        undistributed =
+            undistributed.checked_add(schedule.rps.checked_mul_floor(Uint128::from(
+                schedule.end_ts - schedule.start_ts.max(now),
+            ))?)?;
+    }
+
+    let mut messages = vec![];
+    if !undistributed.is_zero() {
+        messages.push(
+            Asset {
+                info: reward.clone(),
+                amount: undistributed,
+            }
+            .into_msg(&deps.querier, receiver.clone())?,
+        );
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "remove_reward_from_pool"),
+        attr("lp_token", lp_token),
+        attr("reward", reward.to_string()),
+        attr("receiver", receiver),
+        attr("undistributed", undistributed),
+    ]))
+}
+
+/// ## Description
+/// Adds or removes tokens from the blocked list. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **add** is an [`Option`] field of type [`Vec<AssetInfo>`]. These are the tokens to block.
+///
+/// * **remove** is an [`Option`] field of type [`Vec<AssetInfo>`]. These are the tokens to unblock.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_tokens_blockedlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<AssetInfo>>,
+    remove: Option<Vec<AssetInfo>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(remove) = remove {
+        config
+            .blocked_tokens
+            .retain(|token| !remove.contains(token));
+    }
+    for token in add.unwrap_or_default() {
+        token.check(deps.api)?;
+        if !config.blocked_tokens.contains(&token) {
+            config.blocked_tokens.push(token);
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_tokens_blockedlist"))
+}
+
+/// ## Description
+/// Updates the contract configuration. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **incentivization_fee_info** is an [`Option`] field of type [`IncentivizationFeeInfo`]. This is the new
+/// fee charged for adding a new reward token to a pool.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    incentivization_fee_info: Option<IncentivizationFeeInfo>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(fee_info) = incentivization_fee_info {
+        config.incentivization_fee_info = Some(validate_fee_info(deps.as_ref(), fee_info)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Loads a pool. If the pool doesn't exist yet, checks that the LP token belongs to a pair registered in
+/// the factory and returns an empty pool. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the LP token of the pool.
+fn load_or_create_pool(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    lp_token: &Addr,
+) -> Result<PoolInfo, ContractError> {
+    if let Some(pool) = POOLS.may_load(deps.storage, lp_token)? {
+        return Ok(pool);
+    }
+
+    let is_factory_lp = pair_info_by_pool(deps, lp_token.clone())
+        .and_then(|pair_info| {
+            query_pair_info(
+                &deps.querier,
+                config.factory.clone(),
+                &pair_info.asset_infos,
+            )
+        })
+        .map(|pair_info| pair_info.liquidity_token == *lp_token)
+        .unwrap_or(false);
+    if !is_factory_lp {
+        return Err(ContractError::InvalidLpToken(lp_token.to_string()));
+    }
+
+    Ok(PoolInfo {
+        last_update_ts: env.block.time.seconds(),
+        ..PoolInfo::default()
+    })
+}
+
+/// ## Description
+/// Accrues the rewards distributed since the latest update to the pool's reward indices and drops
+/// finished schedules.
+/// ## Params
+/// * **pool** is an object of type [`PoolInfo`]. This is the pool to update.
+///
+/// * **now** is an object of type [`u64`]. This is the current timestamp (in seconds).
+pub(crate) fn update_pool(pool: &mut PoolInfo, now: u64) -> StdResult<()> {
+    if now <= pool.last_update_ts {
+        return Ok(());
+    }
+
+    // Rewards distributed while nothing is staked stay in the contract
+    if !pool.total_lp.is_zero() {
+        for schedule in &pool.schedules {
+            let from = pool.last_update_ts.max(schedule.start_ts);
+            let to = now.min(schedule.end_ts);
+            if to <= from {
+                continue;
+            }
+
+            let distributed = schedule.rps.checked_mul_floor(Uint128::from(to - from))?;
+            let index_increase = calculate_rewards_per_share(distributed, pool.total_lp)?;
+            match pool
+                .reward_indices
+                .iter_mut()
+                .find(|(reward, _)| *reward == schedule.reward)
+            {
+                Some((_, index)) => *index = index.checked_add(index_increase)?,
+                None => pool
+                    .reward_indices
+                    .push((schedule.reward.clone(), index_increase)),
+            }
+        }
+    }
+
+    pool.last_update_ts = now;
+    pool.schedules.retain(|schedule| schedule.end_ts > now);
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the pending rewards of a user in an up-to-date pool.
+/// ## Params
+/// * **pool** is an object of type [`PoolInfo`].
+///
+/// * **user** is an object of type [`UserInfo`].
+pub(crate) fn pending_rewards(pool: &PoolInfo, user: &UserInfo) -> StdResult<Vec<Asset>> {
+    let mut rewards = vec![];
+    for (reward, index) in &pool.reward_indices {
+        let last_index = user
+            .last_indices
+            .iter()
+            .find(|(last_reward, _)| last_reward == reward)
+            .map(|(_, last_index)| *last_index)
+            .unwrap_or_else(Decimal::zero);
+        // Reward indices only grow, so the user's last index never exceeds the pool's one
+        let amount = (*index - last_index).checked_mul_floor(user.amount)?;
+
+        if !amount.is_zero() {
+            rewards.push(Asset {
+                info: reward.clone(),
+                amount,
+            });
+        }
+    }
+
+    Ok(rewards)
+}
+
+/// ## Description
+/// Pays out the pending rewards of a user in an up-to-date pool and moves the user's indices to the
+/// pool's current ones. Blocked tokens are skipped.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **pool** is an object of type [`PoolInfo`].
+///
+/// * **user** is an object of type [`UserInfo`]. This is the user whose rewards are paid out.
+///
+/// * **recipient** is an object of type [`Addr`]. This is the address that receives the rewards.
+fn claim_user_rewards(
+    deps: Deps,
+    config: &Config,
+    pool: &PoolInfo,
+    user: &mut UserInfo,
+    recipient: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let messages = pending_rewards(pool, user)?
+        .into_iter()
+        .filter(|reward| !config.blocked_tokens.contains(&reward.info))
+        .map(|reward| reward.into_msg(&deps.querier, recipient.clone()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    user.last_indices = pool.reward_indices.clone();
+
+    Ok(messages)
+}
+
+/// ## Description
+/// Returns the reward tokens that have unfinished schedules in a pool.
+/// ## Params
+/// * **pool** is an object of type [`PoolInfo`].
+fn active_reward_tokens(pool: &PoolInfo) -> Vec<&AssetInfo> {
+    let mut rewards: Vec<&AssetInfo> = vec![];
+    for schedule in &pool.schedules {
+        if !rewards.contains(&&schedule.reward) {
+            rewards.push(&schedule.reward);
+        }
+    }
+    rewards
+}
+
+/// ## Description
+/// Adds a coin amount to a list of coins, merging amounts of the same denom.
+/// ## Params
+/// * **coins** is a vector that contains objects of type [`Coin`].
+///
+/// * **denom** is an object of type [`str`]. This is the denom of the coin to add.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount to add.
+fn add_coin(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) -> StdResult<()> {
+    match coins.iter_mut().find(|coin| coin.denom == denom) {
+        Some(coin) => coin.amount = coin.amount.checked_add(amount)?,
+        None => coins.push(Coin {
+            denom: denom.to_string(),
+            amount,
+        }),
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns whether the sent funds contain exactly the expected coins.
+/// ## Params
+/// * **funds** is a slice that contains objects of type [`Coin`]. These are the sent funds.
+///
+/// * **expected** is a slice that contains objects of type [`Coin`]. These are the expected funds.
+fn funds_match(funds: &[Coin], expected: &[Coin]) -> bool {
+    let sent: Vec<&Coin> = funds.iter().filter(|coin| !coin.amount.is_zero()).collect();
+    sent.len() == expected.len() && expected.iter().all(|coin| sent.contains(&coin))
+}
+
+/// ## Description
+/// Validates the fee receiver of an [`IncentivizationFeeInfo`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **fee_info** is an object of type [`IncentivizationFeeInfo`]. This is the fee to validate.
+fn validate_fee_info(
+    deps: Deps,
+    fee_info: IncentivizationFeeInfo,
+) -> StdResult<IncentivizationFeeInfo> {
+    Ok(IncentivizationFeeInfo {
+        fee_receiver: addr_validate_to_lower(deps.api, fee_info.fee_receiver.as_str())?,
+        fee: fee_info.fee,
+    })
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Deposit { lp_token, user }** Returns the amount of LP tokens staked by a user.
+///
+/// * **QueryMsg::PendingRewards { lp_token, user }** Returns the pending rewards of a user in a pool.
+///
+/// * **QueryMsg::PoolInfo { lp_token }** Returns information about a pool using a [`PoolInfoResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                factory: config.factory,
+                incentivization_fee_info: config.incentivization_fee_info,
+                blocked_tokens: config.blocked_tokens,
+            })
+        }
+        QueryMsg::Deposit { lp_token, user } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            let user_info = USERS
+                .may_load(deps.storage, (&lp_token, &user))?
+                .unwrap_or_default();
+            to_binary(&user_info.amount)
+        }
+        QueryMsg::PendingRewards { lp_token, user } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            let user = addr_validate_to_lower(deps.api, &user)?;
+            let mut pool = POOLS.load(deps.storage, &lp_token)?;
+            update_pool(&mut pool, env.block.time.seconds())?;
+            let user_info = USERS
+                .may_load(deps.storage, (&lp_token, &user))?
+                .unwrap_or_default();
+            to_binary(&pending_rewards(&pool, &user_info)?)
+        }
+        QueryMsg::PoolInfo { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            let mut pool = POOLS.load(deps.storage, &lp_token)?;
+            update_pool(&mut pool, env.block.time.seconds())?;
+            to_binary(&PoolInfoResponse {
+                total_lp: pool.total_lp,
+                last_update_ts: pool.last_update_ts,
+                schedules: pool.schedules,
+                reward_indices: pool.reward_indices,
+            })
+        }
+    }
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
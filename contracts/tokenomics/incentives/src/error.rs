@@ -0,0 +1,49 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes incentives contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{0} is not an LP token of a pair registered in the factory")]
+    InvalidLpToken(String),
+
+    #[error("The schedule must start in the future and end after it starts")]
+    InvalidSchedulePeriod {},
+
+    #[error("The reward amount is too low to be distributed over the schedule")]
+    RewardAmountTooLow {},
+
+    #[error("Token {0} is blocked")]
+    BlockedToken(String),
+
+    #[error("A pool can't distribute more than {0} reward tokens at the same time")]
+    TooManyRewardTokens(usize),
+
+    #[error("A pool can't have more than {0} unfinished schedules of the same reward token")]
+    TooManySchedules(usize),
+
+    #[error("The sent funds don't match the reward and the incentivization fee. Expected: {0}")]
+    InvalidFunds(String),
+
+    #[error("Reward {0} is not distributed in the pool")]
+    RewardNotFound(String),
+
+    #[error("Insufficient staked amount")]
+    AmountExceedsBalance {},
+
+    #[error("The LP token can't be a reward token")]
+    LpTokenAsReward {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
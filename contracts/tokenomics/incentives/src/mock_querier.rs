@@ -0,0 +1,131 @@
+use astroport::asset::PairInfo;
+use astroport::factory::QueryMsg as FactoryQueryMsg;
+use astroport::pair::QueryMsg as PairQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, WasmQuery,
+};
+use cw20::{Cw20QueryMsg, MinterResponse};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    factory: String,
+    // pairs keyed by their contract address
+    pairs: HashMap<String, PairInfo>,
+    // pair addresses keyed by the address of their LP token
+    minters: HashMap<String, String>,
+    // the subset of pairs that are registered in the factory
+    factory_pairs: Vec<PairInfo>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if *contract_addr == self.factory =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Pair { asset_infos } => {
+                        match self
+                            .factory_pairs
+                            .iter()
+                            .find(|pair_info| pair_info.asset_infos == asset_infos)
+                        {
+                            Some(pair_info) => SystemResult::Ok(to_binary(pair_info).into()),
+                            None => SystemResult::Err(SystemError::InvalidRequest {
+                                error: String::from("Pair not found"),
+                                request: msg.clone(),
+                            }),
+                        }
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if self.minters.contains_key(contract_addr) =>
+            {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Minter {} => SystemResult::Ok(
+                        to_binary(&MinterResponse {
+                            minter: self.minters[contract_addr].clone(),
+                            cap: None,
+                        })
+                        .into(),
+                    ),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::Pair {} => match self.pairs.get(contract_addr) {
+                        Some(pair_info) => SystemResult::Ok(to_binary(pair_info).into()),
+                        None => SystemResult::Err(SystemError::NoSuchContract {
+                            addr: contract_addr.clone(),
+                        }),
+                    },
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            factory: String::default(),
+            pairs: HashMap::new(),
+            minters: HashMap::new(),
+            factory_pairs: vec![],
+        }
+    }
+
+    pub fn set_factory(&mut self, factory: &str) {
+        self.factory = factory.to_string();
+    }
+
+    // Adds a pair together with its LP token. Registered pairs are also returned by the factory
+    pub fn set_pair(&mut self, pair_info: PairInfo, registered: bool) {
+        self.minters.insert(
+            pair_info.liquidity_token.to_string(),
+            pair_info.contract_addr.to_string(),
+        );
+        self.pairs
+            .insert(pair_info.contract_addr.to_string(), pair_info.clone());
+        if registered {
+            self.factory_pairs.push(pair_info);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use astroport::asset::AssetInfo;
+use astroport::common::OwnershipProposal;
+use astroport::incentives::{IncentivizationFeeInfo, RewardSchedule};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the incentives contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport factory contract address
+    pub factory: Addr,
+    /// The fee charged for adding a new reward token to a pool
+    pub incentivization_fee_info: Option<IncentivizationFeeInfo>,
+    /// Tokens that can't be distributed as rewards
+    pub blocked_tokens: Vec<AssetInfo>,
+}
+
+/// ## Description
+/// This structure describes the reward state of a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PoolInfo {
+    /// The total amount of staked LP tokens
+    pub total_lp: Uint128,
+    /// The timestamp (in seconds) of the latest reward update
+    pub last_update_ts: u64,
+    /// Reward schedules that aren't finished yet
+    pub schedules: Vec<RewardSchedule>,
+    /// The amount of rewards accumulated per staked LP token for every reward token.
+    /// Indices only grow, so entries are never removed
+    pub reward_indices: Vec<(AssetInfo, Decimal)>,
+}
+
+/// ## Description
+/// This structure describes a user's stake in a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UserInfo {
+    /// The amount of staked LP tokens
+    pub amount: Uint128,
+    /// The reward indices of the pool when the user's rewards were last paid out
+    pub last_indices: Vec<(AssetInfo, Decimal)>,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores pools keyed by their LP token
+pub const POOLS: Map<&Addr, PoolInfo> = Map::new("pools");
+
+/// ## Description
+/// Stores user stakes keyed by LP token and user
+pub const USERS: Map<(&Addr, &Addr), UserInfo> = Map::new("users");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,454 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Env, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::incentives::{
+    Cw20HookMsg, ExecuteMsg, IncentivizationFeeInfo, InputSchedule, InstantiateMsg, QueryMsg,
+    MAX_REWARD_TOKENS, MAX_SCHEDULES_PER_REWARD,
+};
+
+type IncentivesDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn token_asset(contract_addr: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked(contract_addr),
+        },
+        amount: Uint128::from(amount),
+    }
+}
+
+fn pair_info(contract_addr: &str, liquidity_token: &str) -> PairInfo {
+    PairInfo {
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: String::from("uusd"),
+            },
+            AssetInfo::NativeToken {
+                denom: String::from("uluna"),
+            },
+        ],
+        contract_addr: Addr::unchecked(contract_addr),
+        liquidity_token: Addr::unchecked(liquidity_token),
+        pair_type: PairType::Xyk {},
+    }
+}
+
+fn env_at(offset: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(offset);
+    env
+}
+
+fn setup() -> IncentivesDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.set_factory("factory");
+    deps.querier.set_pair(pair_info("pair", "lp_token"), true);
+    // Reports the same assets as a registered pair but isn't registered in the factory itself
+    deps.querier
+        .set_pair(pair_info("fake_pair", "fake_lp_token"), false);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            factory: String::from("factory"),
+            incentivization_fee_info: Some(IncentivizationFeeInfo {
+                fee_receiver: Addr::unchecked("fee_receiver"),
+                fee: coin(1000, "uusd"),
+            }),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn incentivize_msg(lp_token: &str, reward: Asset, start: u64, end: u64) -> ExecuteMsg {
+    let start_ts = mock_env().block.time.seconds() + start;
+    ExecuteMsg::Incentivize {
+        lp_token: lp_token.to_string(),
+        schedule: InputSchedule {
+            reward,
+            start_ts,
+            end_ts: start_ts + end - start,
+        },
+    }
+}
+
+fn deposit(deps: &mut IncentivesDeps, offset: u64, user: &str, amount: u128) -> Response {
+    execute(
+        deps.as_mut(),
+        env_at(offset),
+        mock_info("lp_token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: user.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Deposit { recipient: None }).unwrap(),
+        }),
+    )
+    .unwrap()
+}
+
+fn query_pending_rewards(deps: &IncentivesDeps, offset: u64, user: &str) -> Vec<Asset> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(offset),
+            QueryMsg::PendingRewards {
+                lp_token: String::from("lp_token"),
+                user: user.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn cw20_transfer(token: &str, recipient: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn incentivize() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("fake_lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLpToken(String::from("fake_lp_token"))
+    );
+
+    let mut past_schedule = incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100);
+    if let ExecuteMsg::Incentivize { schedule, .. } = &mut past_schedule {
+        schedule.start_ts -= 1;
+    }
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        past_schedule,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedulePeriod {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("lp_token", 1000), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LpTokenAsReward {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 0), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RewardAmountTooLow {});
+
+    // Adding a new reward token costs the fee unless the sender is the owner
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds(String::from("1000uusd")));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("reward"),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: String::from("owner"),
+                recipient: mock_env().contract.address.to_string(),
+                amount: Uint128::from(1000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Extending an active reward token is free
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 50, 150),
+    )
+    .unwrap();
+
+    for i in 1..MAX_REWARD_TOKENS {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            incentivize_msg(
+                "lp_token",
+                token_asset(&format!("reward{}", i), 1000),
+                0,
+                100,
+            ),
+        )
+        .unwrap();
+    }
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("one_too_many", 1000), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TooManyRewardTokens(MAX_REWARD_TOKENS));
+
+    // Schedules of an active reward token are capped as well
+    for _ in 2..MAX_SCHEDULES_PER_REWARD {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &[]),
+            incentivize_msg("lp_token", token_asset("reward", 1000), 50, 150),
+        )
+        .unwrap();
+    }
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 50, 150),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TooManySchedules(MAX_SCHEDULES_PER_REWARD)
+    );
+
+    // Other reward tokens can still be extended
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        incentivize_msg("lp_token", token_asset("reward1", 1000), 50, 150),
+    )
+    .unwrap();
+}
+
+#[test]
+fn deposit_and_claim() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap();
+
+    deposit(&mut deps, 0, "alice", 100);
+    assert_eq!(
+        query_pending_rewards(&deps, 50, "alice"),
+        vec![token_asset("reward", 500)]
+    );
+
+    let res = deposit(&mut deps, 50, "bob", 300);
+    assert!(res.messages.is_empty());
+
+    // The second half of the rewards is split 1:3
+    assert_eq!(
+        query_pending_rewards(&deps, 200, "alice"),
+        vec![token_asset("reward", 625)]
+    );
+    assert_eq!(
+        query_pending_rewards(&deps, 200, "bob"),
+        vec![token_asset("reward", 375)]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(200),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimRewards {
+            lp_tokens: vec![String::from("lp_token")],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages[0].msg, cw20_transfer("reward", "alice", 625));
+    assert!(query_pending_rewards(&deps, 200, "alice").is_empty());
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(200),
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw {
+            lp_token: String::from("lp_token"),
+            amount: Uint128::from(301u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AmountExceedsBalance {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(200),
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw {
+            lp_token: String::from("lp_token"),
+            amount: Uint128::from(300u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+        vec![
+            cw20_transfer("reward", "bob", 375),
+            cw20_transfer("lp_token", "bob", 300),
+        ]
+    );
+}
+
+#[test]
+fn remove_reward_from_pool() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap();
+    deposit(&mut deps, 0, "alice", 100);
+
+    let remove_msg = ExecuteMsg::RemoveRewardFromPool {
+        lp_token: String::from("lp_token"),
+        reward: token_asset("reward", 0).info,
+        receiver: String::from("receiver"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env_at(40),
+        mock_info("user", &[]),
+        remove_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(40),
+        mock_info("owner", &[]),
+        remove_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_transfer("reward", "receiver", 600)
+    );
+
+    // Rewards accrued before the removal stay claimable
+    assert_eq!(
+        query_pending_rewards(&deps, 100, "alice"),
+        vec![token_asset("reward", 400)]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(50),
+        mock_info("owner", &[]),
+        remove_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RewardNotFound(String::from("reward")));
+}
+
+#[test]
+fn blocked_tokens() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap();
+    deposit(&mut deps, 0, "alice", 100);
+
+    let block_msg = ExecuteMsg::UpdateTokensBlockedlist {
+        add: Some(vec![token_asset("reward", 0).info]),
+        remove: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        block_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        block_msg,
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        incentivize_msg("lp_token", token_asset("reward", 1000), 0, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BlockedToken(String::from("reward")));
+
+    // Blocked rewards aren't paid out
+    let res = execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimRewards {
+            lp_tokens: vec![String::from("lp_token")],
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+}
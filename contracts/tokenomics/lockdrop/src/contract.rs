@@ -0,0 +1,1208 @@
+use crate::error::ContractError;
+use crate::state::{Config, LockupInfo, CONFIG, LOCKUPS, OWNERSHIP_PROPOSAL, POOLS, STATE};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
+use astroport::lockdrop::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupInfoResponse,
+    MigrateMsg, MigrationInfo, PoolInfo, QueryMsg, StateResponse, UserInfoResponse, WEEK,
+};
+use astroport::pair::{
+    Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+};
+use astroport::querier::query_token_balance;
+use astroport::DecimalCheckedOps;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use std::convert::TryInto;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-lockdrop";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.init_timestamp < env.block.time.seconds()
+        || msg.deposit_window == 0
+        || msg.withdrawal_window == 0
+        || msg.migration_window == 0
+        || msg.min_lock_duration == 0
+        || msg.max_lock_duration < msg.min_lock_duration
+        || msg.weekly_divider == 0
+    {
+        return Err(ContractError::InvalidParams {});
+    }
+
+    let config = Config {
+        owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+        astro_token: addr_validate_to_lower(deps.api, &msg.astro_token)?,
+        generator: msg
+            .generator
+            .map(|generator| addr_validate_to_lower(deps.api, &generator))
+            .transpose()?,
+        init_timestamp: msg.init_timestamp,
+        deposit_window: msg.deposit_window,
+        withdrawal_window: msg.withdrawal_window,
+        migration_window: msg.migration_window,
+        min_lock_duration: msg.min_lock_duration,
+        max_lock_duration: msg.max_lock_duration,
+        weekly_multiplier: msg.weekly_multiplier,
+        weekly_divider: msg.weekly_divider,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &StateResponse::default())?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::UpdateConfig { generator }** Updates general contract parameters.
+///
+/// * **ExecuteMsg::InitializePool { lp_token, pair, incentives_share }** Adds an LP token that can be locked.
+///
+/// * **ExecuteMsg::WithdrawFromLockup { lp_token, duration, amount }** Withdraws LP tokens from a lockup.
+///
+/// * **ExecuteMsg::MigrateLiquidity { lp_token, astroport_pair, slippage_tolerance }** Migrates a pool's
+/// liquidity to an Astroport pair.
+///
+/// * **ExecuteMsg::WithdrawUnmigrated { lp_token, duration }** Withdraws a lockup in a pool whose liquidity
+/// wasn't migrated in time.
+///
+/// * **ExecuteMsg::ClaimRewardsAndUnlock { lp_token, duration }** Claims ASTRO rewards and unlocks
+/// expired lockups.
+///
+/// * **ExecuteMsg::Callback(msg)** Handles callbacks described in the [`CallbackMsg`].
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { generator } => update_config(deps, info, generator),
+        ExecuteMsg::InitializePool {
+            lp_token,
+            pair,
+            incentives_share,
+        } => initialize_pool(deps, env, info, lp_token, pair, incentives_share),
+        ExecuteMsg::WithdrawFromLockup {
+            lp_token,
+            duration,
+            amount,
+        } => withdraw_from_lockup(deps, env, info, lp_token, duration, amount),
+        ExecuteMsg::MigrateLiquidity {
+            lp_token,
+            astroport_pair,
+            slippage_tolerance,
+        } => migrate_liquidity(
+            deps,
+            env,
+            info,
+            lp_token,
+            astroport_pair,
+            slippage_tolerance,
+        ),
+        ExecuteMsg::WithdrawUnmigrated { lp_token, duration } => {
+            withdraw_unmigrated(deps, env, info, lp_token, duration)
+        }
+        ExecuteMsg::ClaimRewardsAndUnlock { lp_token, duration } => {
+            claim_rewards_and_unlock(deps, env, info, lp_token, duration)
+        }
+        ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, *msg),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is an object of type [`Cw20ReceiveMsg`]. This is the CW20 message to process.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::IncreaseLockup { duration } => {
+            let user = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            increase_lockup(deps, env, info.sender, user, duration, cw20_msg.amount)
+        }
+        Cw20HookMsg::IncreaseAstroIncentives {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            if info.sender != config.astro_token {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            // Rewards are fixed once the lockdrop has finished
+            if env.block.time.seconds() >= config.lockdrop_end() {
+                return Err(ContractError::LockdropFinished {});
+            }
+
+            let state = STATE.update::<_, StdError>(deps.storage, |mut state| {
+                state.total_astro_incentives += cw20_msg.amount;
+                Ok(state)
+            })?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "increase_astro_incentives"),
+                attr("amount", cw20_msg.amount),
+                attr("total_astro_incentives", state.total_astro_incentives),
+            ]))
+        }
+    }
+}
+
+/// ## Description
+/// Locks LP tokens for the specified number of weeks. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the locked LP token.
+///
+/// * **user** is an object of type [`Addr`]. This is the owner of the lockup.
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to lock.
+fn increase_lockup(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    user: Addr,
+    duration: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let now = env.block.time.seconds();
+    if now < config.init_timestamp || now >= config.withdrawal_start() {
+        return Err(ContractError::DepositWindowClosed {});
+    }
+
+    if duration < config.min_lock_duration || duration > config.max_lock_duration {
+        return Err(ContractError::InvalidLockDuration(
+            config.min_lock_duration,
+            config.max_lock_duration,
+        ));
+    }
+
+    // Only LP tokens of initialized pools can be locked
+    let mut pool = POOLS
+        .may_load(deps.storage, &lp_token)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let mut lockup = LOCKUPS
+        .may_load(deps.storage, (&lp_token, &user, duration.into()))?
+        .unwrap_or_default();
+    let prev_weighted_amount = weighted_amount(&config, duration, lockup.lp_locked)?;
+
+    // The pool takes a share of the incentives once it has lockups
+    if pool.total_lp_locked.is_zero() {
+        STATE.update::<_, StdError>(deps.storage, |mut state| {
+            state.total_incentives_share += pool.incentives_share;
+            Ok(state)
+        })?;
+    }
+
+    lockup.lp_locked = lockup.lp_locked.checked_add(amount)?;
+    pool.total_lp_locked = pool.total_lp_locked.checked_add(amount)?;
+    pool.weighted_amount = pool
+        .weighted_amount
+        .checked_sub(prev_weighted_amount)?
+        .checked_add(weighted_amount(&config, duration, lockup.lp_locked)?)?;
+
+    LOCKUPS.save(deps.storage, (&lp_token, &user, duration.into()), &lockup)?;
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "increase_lockup"),
+        attr("lp_token", lp_token),
+        attr("user", user),
+        attr("duration", duration.to_string()),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Updates general contract parameters. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **generator** is an [`Option`] field of type [`String`]. This is the new generator contract address.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    generator: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attributes = vec![attr("action", "update_config")];
+
+    if let Some(generator) = generator {
+        let generator = addr_validate_to_lower(deps.api, &generator)?;
+        attributes.push(attr("generator", &generator));
+        config.generator = Some(generator);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Adds an LP token that can be locked in the lockdrop. Pools can only be added before the
+/// deposit window closes. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the LP token that can be locked.
+///
+/// * **pair** is an object of type [`String`]. This is the pair that issued the LP token.
+///
+/// * **incentives_share** is an object of type [`u64`]. This is the pool's share of the ASTRO incentives.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn initialize_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    pair: String,
+    incentives_share: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() >= config.withdrawal_start() {
+        return Err(ContractError::DepositWindowClosed {});
+    }
+
+    if incentives_share == 0 {
+        return Err(ContractError::InvalidIncentivesShare {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    if POOLS.has(deps.storage, &lp_token) {
+        return Err(ContractError::PoolAlreadyExists {});
+    }
+
+    let pool = PoolInfo {
+        pair: addr_validate_to_lower(deps.api, &pair)?,
+        incentives_share,
+        total_lp_locked: Uint128::zero(),
+        weighted_amount: Uint128::zero(),
+        migration_info: None,
+    };
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "initialize_pool"),
+        attr("lp_token", lp_token),
+        attr("pair", pool.pair),
+        attr("incentives_share", incentives_share.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Withdraws LP tokens from a lockup. Any amount can be withdrawn during the deposit window.
+/// During the withdrawal window, only one withdrawal is allowed and its maximum amount is
+/// limited by [`max_withdrawal_percent`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the locked LP token.
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to withdraw.
+fn withdraw_from_lockup(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    duration: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let now = env.block.time.seconds();
+    if now >= config.lockdrop_end() {
+        return Err(ContractError::WithdrawalWindowClosed {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let mut pool = POOLS.load(deps.storage, &lp_token)?;
+    let mut lockup = LOCKUPS.load(deps.storage, (&lp_token, &info.sender, duration.into()))?;
+
+    let max_amount = if now >= config.withdrawal_start() {
+        if lockup.withdrawal_flag {
+            return Err(ContractError::AlreadyWithdrawn {});
+        }
+        lockup.withdrawal_flag = true;
+
+        max_withdrawal_percent(&config, now).checked_mul(lockup.lp_locked)?
+    } else {
+        lockup.lp_locked
+    };
+    if amount > max_amount {
+        return Err(ContractError::WithdrawalLimitExceeded(max_amount));
+    }
+
+    let prev_weighted_amount = weighted_amount(&config, duration, lockup.lp_locked)?;
+
+    lockup.lp_locked = lockup.lp_locked.checked_sub(amount)?;
+    pool.total_lp_locked = pool.total_lp_locked.checked_sub(amount)?;
+    pool.weighted_amount = pool
+        .weighted_amount
+        .checked_sub(prev_weighted_amount)?
+        .checked_add(weighted_amount(&config, duration, lockup.lp_locked)?)?;
+
+    // Pools without lockups don't take a share of the incentives
+    if pool.total_lp_locked.is_zero() {
+        STATE.update::<_, StdError>(deps.storage, |mut state| {
+            state.total_incentives_share -= pool.incentives_share;
+            Ok(state)
+        })?;
+    }
+
+    if lockup.lp_locked.is_zero() {
+        LOCKUPS.remove(deps.storage, (&lp_token, &info.sender, duration.into()));
+    } else {
+        LOCKUPS.save(
+            deps.storage,
+            (&lp_token, &info.sender, duration.into()),
+            &lockup,
+        )?;
+    }
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "withdraw_from_lockup"),
+            attr("lp_token", lp_token),
+            attr("user", info.sender),
+            attr("duration", duration.to_string()),
+            attr("amount", amount),
+        ]))
+}
+
+/// ## Description
+/// Withdraws the pool's liquidity from the original pair and provides it to an Astroport pair.
+/// The received Astroport LP tokens are distributed among lockups when they unlock.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the locked LP token.
+///
+/// * **astroport_pair** is an object of type [`String`]. This is the Astroport pair that receives the liquidity.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`]. This is the slippage tolerance used
+/// when providing liquidity.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn migrate_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    astroport_pair: String,
+    slippage_tolerance: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+    if now < config.lockdrop_end() {
+        return Err(ContractError::LockdropNotFinished {});
+    }
+
+    // Users may already be withdrawing the LP tokens of pools that weren't migrated in time
+    if now >= config.migration_deadline() {
+        return Err(ContractError::MigrationWindowClosed {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let pool = POOLS.load(deps.storage, &lp_token)?;
+    if pool.migration_info.is_some() {
+        return Err(ContractError::PoolAlreadyMigrated {});
+    }
+    if pool.total_lp_locked.is_zero() {
+        return Err(ContractError::NothingToMigrate {});
+    }
+
+    let astroport_pair = addr_validate_to_lower(deps.api, &astroport_pair)?;
+    let astroport_pair: PairInfo = deps
+        .querier
+        .query_wasm_smart(&astroport_pair, &PairQueryMsg::Pair {})?;
+
+    let query_asset = |info: &AssetInfo| -> StdResult<Asset> {
+        Ok(Asset {
+            info: info.clone(),
+            amount: info.query_pool(&deps.querier, env.contract.address.clone())?,
+        })
+    };
+    let prev_balances = [
+        query_asset(&astroport_pair.asset_infos[0])?,
+        query_asset(&astroport_pair.asset_infos[1])?,
+    ];
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pool.pair.to_string(),
+                amount: pool.total_lp_locked,
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {})?,
+            })?,
+            funds: vec![],
+        })
+        .add_message(callback_msg(
+            &env,
+            CallbackMsg::ProvideLiquidity {
+                lp_token: lp_token.clone(),
+                astroport_pair: astroport_pair.clone(),
+                prev_balances,
+                slippage_tolerance,
+            },
+        )?)
+        .add_attributes(vec![
+            attr("action", "migrate_liquidity"),
+            attr("lp_token", lp_token),
+            attr("astroport_pair", astroport_pair.contract_addr),
+            attr("amount", pool.total_lp_locked),
+        ]))
+}
+
+/// ## Description
+/// Withdraws the original LP tokens of a lockup in a pool whose liquidity wasn't migrated by the
+/// migration deadline, together with the lockup's ASTRO rewards. The pool's totals don't change, so
+/// the other lockups keep their share of the incentives. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the locked LP token.
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+fn withdraw_unmigrated(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    if env.block.time.seconds() < config.migration_deadline() {
+        return Err(ContractError::MigrationWindowOpen {});
+    }
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let pool = POOLS.load(deps.storage, &lp_token)?;
+    if pool.migration_info.is_some() {
+        return Err(ContractError::PoolAlreadyMigrated {});
+    }
+    let lockup = LOCKUPS.load(deps.storage, (&lp_token, &info.sender, duration.into()))?;
+
+    let mut messages: Vec<CosmosMsg> = vec![WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount: lockup.lp_locked,
+        })?,
+        funds: vec![],
+    }
+    .into()];
+
+    let astro_rewards = if lockup.astro_claimed {
+        Uint128::zero()
+    } else {
+        calculate_astro_rewards(&config, &state, &pool, duration, lockup.lp_locked)?
+    };
+    if !astro_rewards.is_zero() {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: config.astro_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount: astro_rewards,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    LOCKUPS.remove(deps.storage, (&lp_token, &info.sender, duration.into()));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "withdraw_unmigrated"),
+        attr("lp_token", lp_token),
+        attr("user", info.sender),
+        attr("duration", duration.to_string()),
+        attr("amount", lockup.lp_locked),
+        attr("astro_rewards", astro_rewards),
+    ]))
+}
+
+/// ## Description
+/// Claims the lockup's ASTRO rewards if they weren't claimed yet. Once the lockup expires, its share
+/// of the pool's Astroport LP tokens is staked in the generator on behalf of the user (or sent to the
+/// user if no generator is set) and the lockup is removed. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **lp_token** is an object of type [`String`]. This is the locked LP token.
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+fn claim_rewards_and_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: String,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+    let pool = POOLS.load(deps.storage, &lp_token)?;
+    let migration_info = pool
+        .migration_info
+        .clone()
+        .ok_or(ContractError::PoolNotMigrated {})?;
+    let mut lockup = LOCKUPS.load(deps.storage, (&lp_token, &info.sender, duration.into()))?;
+
+    let is_expired = env.block.time.seconds() >= unlock_timestamp(&config, duration);
+    if lockup.astro_claimed && !is_expired {
+        return Err(ContractError::LockupNotExpired {});
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes = vec![
+        attr("action", "claim_rewards_and_unlock"),
+        attr("lp_token", &lp_token),
+        attr("user", &info.sender),
+        attr("duration", duration.to_string()),
+    ];
+
+    if !lockup.astro_claimed {
+        let astro_rewards =
+            calculate_astro_rewards(&config, &state, &pool, duration, lockup.lp_locked)?;
+        if !astro_rewards.is_zero() {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: config.astro_token.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: info.sender.to_string(),
+                        amount: astro_rewards,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+        lockup.astro_claimed = true;
+        attributes.push(attr("astro_rewards", astro_rewards));
+    }
+
+    if is_expired {
+        let lp_amount = migration_info
+            .astroport_lp_amount
+            .multiply_ratio(lockup.lp_locked, pool.total_lp_locked);
+
+        if !lp_amount.is_zero() {
+            let msg = match &config.generator {
+                Some(generator) => Cw20ExecuteMsg::Send {
+                    contract: generator.to_string(),
+                    amount: lp_amount,
+                    msg: to_binary(&GeneratorHookMsg::DepositFor(info.sender.clone()))?,
+                },
+                None => Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount: lp_amount,
+                },
+            };
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: migration_info.astroport_lp_token.to_string(),
+                    msg: to_binary(&msg)?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+
+        LOCKUPS.remove(deps.storage, (&lp_token, &info.sender, duration.into()));
+        attributes.push(attr("astroport_lp_unlocked", lp_amount));
+    } else {
+        LOCKUPS.save(
+            deps.storage,
+            (&lp_token, &info.sender, duration.into()),
+            &lockup,
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Handles callbacks described in [`CallbackMsg`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`CallbackMsg`]. This is the callback action.
+///
+/// ## Executor
+/// Callback functions can only be called by this contract.
+fn handle_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    // Callback functions can only be called by this contract
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        CallbackMsg::ProvideLiquidity {
+            lp_token,
+            astroport_pair,
+            prev_balances,
+            slippage_tolerance,
+        } => provide_liquidity(
+            deps,
+            env,
+            lp_token,
+            astroport_pair,
+            prev_balances,
+            slippage_tolerance,
+        ),
+        CallbackMsg::UpdatePoolOnMigration {
+            lp_token,
+            astroport_pair,
+            prev_lp_balance,
+        } => update_pool_on_migration(deps, env, lp_token, astroport_pair, prev_lp_balance),
+    }
+}
+
+/// ## Description
+/// Provides the assets withdrawn from the original pair to the Astroport pair.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the locked LP token.
+///
+/// * **astroport_pair** is an object of type [`PairInfo`]. This is the Astroport pair that receives the liquidity.
+///
+/// * **prev_balances** is an array of type [`Asset`]. These are the contract's balances of the pair assets
+/// before the liquidity was withdrawn.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`].
+fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    astroport_pair: PairInfo,
+    prev_balances: [Asset; 2],
+    slippage_tolerance: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds = vec![];
+
+    let received_asset = |prev: &Asset| -> StdResult<Asset> {
+        let balance = prev
+            .info
+            .query_pool(&deps.querier, env.contract.address.clone())?;
+        Ok(Asset {
+            info: prev.info.clone(),
+            amount: balance.checked_sub(prev.amount)?,
+        })
+    };
+    let assets = [
+        received_asset(&prev_balances[0])?,
+        received_asset(&prev_balances[1])?,
+    ];
+
+    for asset in assets.iter() {
+        match &asset.info {
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: astroport_pair.contract_addr.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    let prev_lp_balance = query_token_balance(
+        &deps.querier,
+        astroport_pair.liquidity_token.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: astroport_pair.contract_addr.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: assets.clone(),
+                slippage_tolerance,
+                auto_stake: None,
+                receiver: None,
+            })?,
+            funds,
+        }
+        .into(),
+    );
+    messages.push(callback_msg(
+        &env,
+        CallbackMsg::UpdatePoolOnMigration {
+            lp_token,
+            astroport_pair,
+            prev_lp_balance,
+        },
+    )?);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "provide_liquidity"),
+        attr("assets", format!("{}, {}", assets[0], assets[1])),
+    ]))
+}
+
+/// ## Description
+/// Saves the amount of Astroport LP tokens received for the pool's liquidity.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **lp_token** is an object of type [`Addr`]. This is the locked LP token.
+///
+/// * **astroport_pair** is an object of type [`PairInfo`]. This is the Astroport pair that received the liquidity.
+///
+/// * **prev_lp_balance** is an object of type [`Uint128`]. This is the contract's Astroport LP token balance
+/// before liquidity was provided.
+fn update_pool_on_migration(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    astroport_pair: PairInfo,
+    prev_lp_balance: Uint128,
+) -> Result<Response, ContractError> {
+    let astroport_lp_amount = query_token_balance(
+        &deps.querier,
+        astroport_pair.liquidity_token.clone(),
+        env.contract.address,
+    )?
+    .checked_sub(prev_lp_balance)?;
+
+    POOLS.update::<_, StdError>(deps.storage, &lp_token, |pool| {
+        let mut pool = pool.ok_or_else(|| StdError::not_found("PoolInfo"))?;
+        pool.migration_info = Some(MigrationInfo {
+            astroport_pair: astroport_pair.contract_addr,
+            astroport_lp_token: astroport_pair.liquidity_token,
+            astroport_lp_amount,
+        });
+        Ok(pool)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_pool_on_migration"),
+        attr("lp_token", lp_token),
+        attr("astroport_lp_amount", astroport_lp_amount),
+    ]))
+}
+
+/// ## Description
+/// Returns a message that executes a [`CallbackMsg`] on this contract.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`CallbackMsg`].
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(Box::new(msg)))?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Returns the lockup weight for the specified duration:
+/// `1 + (duration - 1) * weekly_multiplier / weekly_divider`.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+fn calculate_weight(config: &Config, duration: u64) -> Decimal {
+    Decimal::one()
+        + Decimal::from_ratio(
+            config.weekly_multiplier * (duration - 1),
+            config.weekly_divider,
+        )
+}
+
+/// ## Description
+/// Returns the weighted amount (amount × duration weight) of a lockup.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of locked LP tokens.
+fn weighted_amount(config: &Config, duration: u64, amount: Uint128) -> StdResult<Uint128> {
+    Ok(calculate_weight(config, duration).checked_mul(amount)?)
+}
+
+/// ## Description
+/// Returns the maximum share of a lockup that can be withdrawn at the specified time.
+/// Everything can be withdrawn during the deposit window. During the first half of the withdrawal
+/// window up to 50% can be withdrawn, and the limit then decreases linearly to zero.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **now** is an object of type [`u64`]. This is the current timestamp in seconds.
+fn max_withdrawal_percent(config: &Config, now: u64) -> Decimal {
+    if now < config.withdrawal_start() {
+        return Decimal::one();
+    }
+
+    let elapsed = now - config.withdrawal_start();
+    if elapsed <= config.withdrawal_window / 2 {
+        Decimal::percent(50)
+    } else {
+        Decimal::from_ratio(
+            config.withdrawal_window.saturating_sub(elapsed),
+            config.withdrawal_window,
+        )
+    }
+}
+
+/// ## Description
+/// Returns the timestamp (in seconds) when a lockup with the specified duration expires.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+fn unlock_timestamp(config: &Config, duration: u64) -> u64 {
+    config.lockdrop_end() + duration * WEEK
+}
+
+/// ## Description
+/// Returns the amount of ASTRO allocated to a lockup. The pool's share of the incentives is
+/// distributed among its lockups proportionally to their weighted amounts.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+///
+/// * **state** is an object of type [`StateResponse`].
+///
+/// * **pool** is an object of type [`PoolInfo`].
+///
+/// * **duration** is an object of type [`u64`]. This is the lock duration in weeks.
+///
+/// * **lp_locked** is an object of type [`Uint128`]. This is the amount of locked LP tokens.
+fn calculate_astro_rewards(
+    config: &Config,
+    state: &StateResponse,
+    pool: &PoolInfo,
+    duration: u64,
+    lp_locked: Uint128,
+) -> StdResult<Uint128> {
+    if state.total_incentives_share == 0 || pool.weighted_amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let pool_incentives = state
+        .total_astro_incentives
+        .multiply_ratio(pool.incentives_share, state.total_incentives_share);
+
+    Ok(pool_incentives.multiply_ratio(
+        weighted_amount(config, duration, lp_locked)?,
+        pool.weighted_amount,
+    ))
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::State {}** Returns the global lockdrop state using a [`StateResponse`] object.
+///
+/// * **QueryMsg::Pool { lp_token }** Returns information about a pool using a [`PoolInfo`] object.
+///
+/// * **QueryMsg::UserInfo { address }** Returns all lockups of a user using a [`UserInfoResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                astro_token: config.astro_token,
+                generator: config.generator,
+                init_timestamp: config.init_timestamp,
+                deposit_window: config.deposit_window,
+                withdrawal_window: config.withdrawal_window,
+                migration_window: config.migration_window,
+                min_lock_duration: config.min_lock_duration,
+                max_lock_duration: config.max_lock_duration,
+                weekly_multiplier: config.weekly_multiplier,
+                weekly_divider: config.weekly_divider,
+            })
+        }
+        QueryMsg::State {} => to_binary(&STATE.load(deps.storage)?),
+        QueryMsg::Pool { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+            to_binary(&POOLS.load(deps.storage, &lp_token)?)
+        }
+        QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, env, address)?),
+    }
+}
+
+/// ## Description
+/// Returns all lockups of a user using a [`UserInfoResponse`] object.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **address** is an object of type [`String`]. This is the user whose lockups are returned.
+fn query_user_info(deps: Deps, env: Env, address: String) -> StdResult<UserInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let user = addr_validate_to_lower(deps.api, &address)?;
+    let lockdrop_finished = env.block.time.seconds() >= config.lockdrop_end();
+
+    let mut lockups = vec![];
+    for item in POOLS.range(deps.storage, None, None, Order::Ascending) {
+        let (lp_token, pool) = item?;
+        let lp_token = Addr::unchecked(String::from_utf8(lp_token)?);
+
+        for item in
+            LOCKUPS
+                .prefix((&lp_token, &user))
+                .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (duration, lockup): (Vec<u8>, LockupInfo) = item?;
+            let duration = u64::from_be_bytes(
+                duration
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| StdError::generic_err("Invalid lockup duration key"))?,
+            );
+
+            let astro_rewards = if lockdrop_finished {
+                calculate_astro_rewards(&config, &state, &pool, duration, lockup.lp_locked)?
+            } else {
+                Uint128::zero()
+            };
+
+            lockups.push(LockupInfoResponse {
+                lp_token: lp_token.clone(),
+                duration,
+                lp_locked: lockup.lp_locked,
+                astro_rewards,
+                astro_claimed: lockup.astro_claimed,
+                unlock_timestamp: unlock_timestamp(&config, duration),
+                astroport_lp_amount: pool.migration_info.as_ref().map(|info| {
+                    info.astroport_lp_amount
+                        .multiply_ratio(lockup.lp_locked, pool.total_lp_locked)
+                }),
+            });
+        }
+    }
+
+    Ok(UserInfoResponse { lockups })
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
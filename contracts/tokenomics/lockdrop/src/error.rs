@@ -0,0 +1,67 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes lockdrop contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid lockdrop parameters")]
+    InvalidParams {},
+
+    #[error("Lock duration must be between {0} and {1} weeks")]
+    InvalidLockDuration(u64, u64),
+
+    #[error("The pool's incentives share must be greater than zero")]
+    InvalidIncentivesShare {},
+
+    #[error("The pool already exists")]
+    PoolAlreadyExists {},
+
+    #[error("The deposit window is closed")]
+    DepositWindowClosed {},
+
+    #[error("The withdrawal window is closed")]
+    WithdrawalWindowClosed {},
+
+    #[error("Only one withdrawal is allowed during the withdrawal window")]
+    AlreadyWithdrawn {},
+
+    #[error("Amount exceeds the maximum allowed withdrawal: {0}")]
+    WithdrawalLimitExceeded(Uint128),
+
+    #[error("The lockdrop has already finished")]
+    LockdropFinished {},
+
+    #[error("The lockdrop hasn't finished yet")]
+    LockdropNotFinished {},
+
+    #[error("The pool's liquidity was already migrated")]
+    PoolAlreadyMigrated {},
+
+    #[error("The pool's liquidity hasn't been migrated yet")]
+    PoolNotMigrated {},
+
+    #[error("The pool has no liquidity to migrate")]
+    NothingToMigrate {},
+
+    #[error("The migration window is closed")]
+    MigrationWindowClosed {},
+
+    #[error("The migration window is still open")]
+    MigrationWindowOpen {},
+
+    #[error("The lockup hasn't expired yet")]
+    LockupNotExpired {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
@@ -0,0 +1,102 @@
+use astroport::asset::PairInfo;
+use astroport::pair::QueryMsg as PairQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_querier: TokenQuerier,
+    pairs: HashMap<String, PairInfo>,
+}
+
+#[derive(Clone, Default)]
+pub struct TokenQuerier {
+    // this lets us iterate over all token balances keyed by token address and holder
+    balances: HashMap<(String, String), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Ok(PairQueryMsg::Pair {}) = from_binary(msg) {
+                    let pair_info = self.pairs.get(contract_addr).expect("Pair not found");
+                    return SystemResult::Ok(to_binary(pair_info).into());
+                }
+
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_querier
+                            .balances
+                            .get(&(contract_addr.clone(), address))
+                            .cloned()
+                            .unwrap_or_default();
+                        SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_querier: TokenQuerier::default(),
+            pairs: HashMap::new(),
+        }
+    }
+
+    pub fn set_token_balance(&mut self, token: &str, holder: &str, balance: u128) {
+        self.token_querier.balances.insert(
+            (token.to_string(), holder.to_string()),
+            Uint128::from(balance),
+        );
+    }
+
+    pub fn set_native_balance(&mut self, holder: &str, balance: &[Coin]) {
+        self.base.update_balance(holder, balance.to_vec());
+    }
+
+    pub fn set_pair(&mut self, pair_info: PairInfo) {
+        self.pairs
+            .insert(pair_info.contract_addr.to_string(), pair_info);
+    }
+}
@@ -0,0 +1,83 @@
+use astroport::common::OwnershipProposal;
+use astroport::lockdrop::{PoolInfo, StateResponse};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the lockdrop contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The generator contract in which unlocked Astroport LP tokens are staked on behalf of users
+    pub generator: Option<Addr>,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds
+    pub withdrawal_window: u64,
+    /// The period in seconds after the lockdrop ends during which the owner can migrate the pools' liquidity
+    pub migration_window: u64,
+    /// The minimum lock duration in weeks
+    pub min_lock_duration: u64,
+    /// The maximum lock duration in weeks
+    pub max_lock_duration: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_multiplier: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_divider: u64,
+}
+
+impl Config {
+    /// Returns the timestamp (in seconds) when the withdrawal window starts.
+    pub fn withdrawal_start(&self) -> u64 {
+        self.init_timestamp + self.deposit_window
+    }
+
+    /// Returns the timestamp (in seconds) when both lockdrop windows are closed.
+    pub fn lockdrop_end(&self) -> u64 {
+        self.withdrawal_start() + self.withdrawal_window
+    }
+
+    /// Returns the timestamp (in seconds) after which the pools' liquidity can't be migrated anymore.
+    pub fn migration_deadline(&self) -> u64 {
+        self.lockdrop_end() + self.migration_window
+    }
+}
+
+/// ## Description
+/// This structure describes a user's lockup in a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LockupInfo {
+    /// The amount of locked LP tokens
+    pub lp_locked: Uint128,
+    /// Whether the ASTRO rewards were claimed
+    pub astro_claimed: bool,
+    /// Whether LP tokens were withdrawn during the withdrawal window
+    pub withdrawal_flag: bool,
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the global lockdrop state at the given key
+pub const STATE: Item<StateResponse> = Item::new("state");
+
+/// ## Description
+/// Stores pools by their LP token address
+pub const POOLS: Map<&Addr, PoolInfo> = Map::new("pools");
+
+/// ## Description
+/// Stores lockups by LP token address, user address and lock duration
+pub const LOCKUPS: Map<(&Addr, &Addr, U64Key), LockupInfo> = Map::new("lockups");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -0,0 +1,838 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, Coin, CosmosMsg, Env, OwnedDeps, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{native_asset_info, token_asset_info, Asset, PairInfo};
+use astroport::factory::PairType;
+use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
+use astroport::lockdrop::{
+    CallbackMsg, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrationInfo, PoolInfo, QueryMsg,
+    StateResponse, UserInfoResponse, WEEK,
+};
+use astroport::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+
+const DEPOSIT_WINDOW: u64 = 5 * 86400;
+const WITHDRAWAL_WINDOW: u64 = 2 * 86400;
+const MIGRATION_WINDOW: u64 = 14 * 86400;
+
+type LockdropDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn now() -> u64 {
+    mock_env().block.time.seconds()
+}
+
+fn lockdrop_end() -> u64 {
+    now() + DEPOSIT_WINDOW + WITHDRAWAL_WINDOW
+}
+
+fn migration_deadline() -> u64 {
+    lockdrop_end() + MIGRATION_WINDOW
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(seconds - env.block.time.seconds());
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: String::from("owner"),
+        astro_token: String::from("astro"),
+        generator: Some(String::from("generator")),
+        init_timestamp: now(),
+        deposit_window: DEPOSIT_WINDOW,
+        withdrawal_window: WITHDRAWAL_WINDOW,
+        migration_window: MIGRATION_WINDOW,
+        min_lock_duration: 1,
+        max_lock_duration: 52,
+        weekly_multiplier: 1,
+        weekly_divider: 51,
+    }
+}
+
+fn setup() -> LockdropDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitializePool {
+            lp_token: String::from("lp_token"),
+            pair: String::from("legacy_pair"),
+            incentives_share: 100,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn lock(deps: &mut LockdropDeps, env: Env, user: &str, amount: u128, duration: u64) {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("lp_token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(user),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::IncreaseLockup { duration }).unwrap(),
+        }),
+    )
+    .unwrap();
+}
+
+fn withdraw(
+    deps: &mut LockdropDeps,
+    env: Env,
+    user: &str,
+    amount: u128,
+    duration: u64,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(user, &[]),
+        ExecuteMsg::WithdrawFromLockup {
+            lp_token: String::from("lp_token"),
+            duration,
+            amount: Uint128::from(amount),
+        },
+    )
+    .map(|_| ())
+}
+
+fn query_pool(deps: &LockdropDeps) -> PoolInfo {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pool {
+                lp_token: String::from("lp_token"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn callback(deps: &mut LockdropDeps, env: Env, msg: CallbackMsg) -> Vec<CosmosMsg> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(Box::new(msg)),
+    )
+    .unwrap()
+    .messages
+    .into_iter()
+    .map(|msg| msg.msg)
+    .collect()
+}
+
+fn cw20_msg(token: &str, msg: &Cw20ExecuteMsg) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from(token),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    for msg in [
+        InstantiateMsg {
+            init_timestamp: now() - 1,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            deposit_window: 0,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            migration_window: 0,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            max_lock_duration: 0,
+            ..instantiate_msg()
+        },
+        InstantiateMsg {
+            weekly_divider: 0,
+            ..instantiate_msg()
+        },
+    ] {
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidParams {});
+    }
+
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitializePool {
+            lp_token: String::from("lp_token"),
+            pair: String::from("legacy_pair"),
+            incentives_share: 100,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolAlreadyExists {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + DEPOSIT_WINDOW),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitializePool {
+            lp_token: String::from("other_lp_token"),
+            pair: String::from("other_pair"),
+            incentives_share: 100,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowClosed {});
+
+    // The pool doesn't take a share of the incentives until it has lockups
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(state.total_incentives_share, 0);
+}
+
+#[test]
+fn incentives_share_of_pools_without_lockups() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitializePool {
+            lp_token: String::from("other_lp_token"),
+            pair: String::from("other_pair"),
+            incentives_share: 300,
+        },
+    )
+    .unwrap();
+
+    let total_incentives_share = |deps: &LockdropDeps| -> u64 {
+        from_binary::<StateResponse>(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap())
+            .unwrap()
+            .total_incentives_share
+    };
+
+    lock(&mut deps, mock_env(), "user", 100, 1);
+    lock(&mut deps, mock_env(), "user", 100, 52);
+    assert_eq!(total_incentives_share(&deps), 100);
+
+    // The pool stops taking a share once all its LP tokens are withdrawn
+    withdraw(&mut deps, mock_env(), "user", 100, 1).unwrap();
+    assert_eq!(total_incentives_share(&deps), 100);
+    withdraw(&mut deps, mock_env(), "user", 100, 52).unwrap();
+    assert_eq!(total_incentives_share(&deps), 0);
+
+    lock(&mut deps, mock_env(), "user", 100, 1);
+    assert_eq!(total_incentives_share(&deps), 100);
+
+    // The pool without lockups doesn't dilute the ASTRO of the other pool
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(3000u128),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let user_info: UserInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(lockdrop_end()),
+            QueryMsg::UserInfo {
+                address: String::from("user"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(user_info.lockups[0].astro_rewards, Uint128::from(3000u128));
+}
+
+#[test]
+fn increase_lockup() {
+    let mut deps = setup();
+
+    let lock_msg = |duration| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::IncreaseLockup { duration }).unwrap(),
+        })
+    };
+
+    // Only LP tokens of initialized pools can be locked
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random_token", &[]),
+        lock_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    for duration in [0, 53] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lp_token", &[]),
+            lock_msg(duration),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidLockDuration(1, 52));
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(now() + DEPOSIT_WINDOW),
+        mock_info("lp_token", &[]),
+        lock_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DepositWindowClosed {});
+
+    // A 52 week lockup weighs twice as much as a 1 week lockup
+    lock(&mut deps, mock_env(), "user", 100, 1);
+    lock(&mut deps, mock_env(), "user", 100, 52);
+    lock(&mut deps, mock_env(), "user", 50, 52);
+
+    let pool = query_pool(&deps);
+    assert_eq!(pool.total_lp_locked, Uint128::from(250u128));
+    assert_eq!(pool.weighted_amount, Uint128::from(400u128));
+
+    let user_info: UserInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserInfo {
+                address: String::from("user"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        user_info
+            .lockups
+            .iter()
+            .map(|lockup| (
+                lockup.duration,
+                lockup.lp_locked.u128(),
+                lockup.unlock_timestamp
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (1, 100, lockdrop_end() + WEEK),
+            (52, 150, lockdrop_end() + 52 * WEEK)
+        ]
+    );
+}
+
+#[test]
+fn withdraw_from_lockup() {
+    let mut deps = setup();
+    lock(&mut deps, mock_env(), "user", 100, 10);
+
+    // Any amount can be withdrawn during the deposit window
+    let err = withdraw(&mut deps, mock_env(), "user", 101, 10).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(100u128))
+    );
+    withdraw(&mut deps, mock_env(), "user", 20, 10).unwrap();
+    withdraw(&mut deps, mock_env(), "user", 30, 10).unwrap();
+
+    // Up to 50% can be withdrawn during the first half of the withdrawal window
+    let withdrawal_start = now() + DEPOSIT_WINDOW;
+    let err = withdraw(&mut deps, env_at(withdrawal_start), "user", 26, 10).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(25u128))
+    );
+
+    // Then the limit decreases linearly
+    let env = env_at(withdrawal_start + WITHDRAWAL_WINDOW * 3 / 4);
+    let err = withdraw(&mut deps, env.clone(), "user", 13, 10).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawalLimitExceeded(Uint128::from(12u128))
+    );
+    withdraw(&mut deps, env.clone(), "user", 12, 10).unwrap();
+
+    // Only one withdrawal is allowed during the withdrawal window
+    let err = withdraw(&mut deps, env, "user", 1, 10).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyWithdrawn {});
+
+    let err = withdraw(&mut deps, env_at(lockdrop_end()), "user", 1, 10).unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalWindowClosed {});
+
+    let pool = query_pool(&deps);
+    assert_eq!(pool.total_lp_locked, Uint128::from(38u128));
+}
+
+#[test]
+fn migrate_liquidity() {
+    let mut deps = setup();
+    lock(&mut deps, mock_env(), "user", 100, 1);
+
+    let astroport_pair = PairInfo {
+        asset_infos: [
+            native_asset_info(String::from("uusd")),
+            token_asset_info(Addr::unchecked("anc")),
+        ],
+        contract_addr: Addr::unchecked("astroport_pair"),
+        liquidity_token: Addr::unchecked("astroport_lp_token"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier.set_pair(astroport_pair.clone());
+
+    let migrate_msg = ExecuteMsg::MigrateLiquidity {
+        lp_token: String::from("lp_token"),
+        astroport_pair: String::from("astroport_pair"),
+        slippage_tolerance: None,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("user", &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end() - 1),
+        mock_info("owner", &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LockdropNotFinished {});
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(migration_deadline()),
+        mock_info("owner", &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MigrationWindowClosed {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("owner", &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap();
+    let prev_balances = [
+        Asset {
+            info: native_asset_info(String::from("uusd")),
+            amount: Uint128::zero(),
+        },
+        Asset {
+            info: token_asset_info(Addr::unchecked("anc")),
+            amount: Uint128::zero(),
+        },
+    ];
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_msg(
+            "lp_token",
+            &Cw20ExecuteMsg::Send {
+                contract: String::from("legacy_pair"),
+                amount: Uint128::from(100u128),
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+            }
+        )
+    );
+    let provide_callback = CallbackMsg::ProvideLiquidity {
+        lp_token: Addr::unchecked("lp_token"),
+        astroport_pair: astroport_pair.clone(),
+        prev_balances: prev_balances.clone(),
+        slippage_tolerance: None,
+    };
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            msg: to_binary(&ExecuteMsg::Callback(Box::new(provide_callback.clone()))).unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Callbacks can only be executed by the contract itself
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("owner", &[]),
+        ExecuteMsg::Callback(Box::new(provide_callback.clone())),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Emulate the liquidity withdrawn from the legacy pair
+    deps.querier
+        .set_native_balance(MOCK_CONTRACT_ADDR, &coins(1000, "uusd"));
+    deps.querier
+        .set_token_balance("anc", MOCK_CONTRACT_ADDR, 500);
+
+    let messages = callback(&mut deps, env_at(lockdrop_end()), provide_callback);
+    let assets = [
+        Asset {
+            info: native_asset_info(String::from("uusd")),
+            amount: Uint128::from(1000u128),
+        },
+        Asset {
+            info: token_asset_info(Addr::unchecked("anc")),
+            amount: Uint128::from(500u128),
+        },
+    ];
+    assert_eq!(
+        messages,
+        vec![
+            cw20_msg(
+                "anc",
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: String::from("astroport_pair"),
+                    amount: Uint128::from(500u128),
+                    expires: None,
+                }
+            ),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("astroport_pair"),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets,
+                    slippage_tolerance: None,
+                    auto_stake: None,
+                    receiver: None,
+                })
+                .unwrap(),
+                funds: vec![Coin {
+                    denom: String::from("uusd"),
+                    amount: Uint128::from(1000u128),
+                }],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                msg: to_binary(&ExecuteMsg::Callback(Box::new(
+                    CallbackMsg::UpdatePoolOnMigration {
+                        lp_token: Addr::unchecked("lp_token"),
+                        astroport_pair: astroport_pair.clone(),
+                        prev_lp_balance: Uint128::zero(),
+                    }
+                )))
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    deps.querier
+        .set_token_balance("astroport_lp_token", MOCK_CONTRACT_ADDR, 700);
+    callback(
+        &mut deps,
+        env_at(lockdrop_end()),
+        CallbackMsg::UpdatePoolOnMigration {
+            lp_token: Addr::unchecked("lp_token"),
+            astroport_pair,
+            prev_lp_balance: Uint128::zero(),
+        },
+    );
+
+    assert_eq!(
+        query_pool(&deps).migration_info,
+        Some(MigrationInfo {
+            astroport_pair: Addr::unchecked("astroport_pair"),
+            astroport_lp_token: Addr::unchecked("astroport_lp_token"),
+            astroport_lp_amount: Uint128::from(700u128),
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("owner", &[]),
+        migrate_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolAlreadyMigrated {});
+
+    // Lockups in migrated pools are unlocked as Astroport LP tokens only
+    let err = execute(
+        deps.as_mut(),
+        env_at(migration_deadline()),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawUnmigrated {
+            lp_token: String::from("lp_token"),
+            duration: 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolAlreadyMigrated {});
+}
+
+#[test]
+fn claim_rewards_and_unlock() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(3000u128),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    lock(&mut deps, mock_env(), "user1", 100, 1);
+    lock(&mut deps, mock_env(), "user2", 100, 52);
+
+    let claim_msg = |duration| ExecuteMsg::ClaimRewardsAndUnlock {
+        lp_token: String::from("lp_token"),
+        duration,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("user1", &[]),
+        claim_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PoolNotMigrated {});
+
+    // Emulate the migration of the pool's liquidity
+    deps.querier
+        .set_token_balance("astroport_lp_token", MOCK_CONTRACT_ADDR, 300);
+    callback(
+        &mut deps,
+        env_at(lockdrop_end()),
+        CallbackMsg::UpdatePoolOnMigration {
+            lp_token: Addr::unchecked("lp_token"),
+            astroport_pair: PairInfo {
+                asset_infos: [
+                    native_asset_info(String::from("uusd")),
+                    token_asset_info(Addr::unchecked("anc")),
+                ],
+                contract_addr: Addr::unchecked("astroport_pair"),
+                liquidity_token: Addr::unchecked("astroport_lp_token"),
+                pair_type: PairType::Xyk {},
+            },
+            prev_lp_balance: Uint128::zero(),
+        },
+    );
+
+    // ASTRO is distributed proportionally to amount × duration weight
+    let res = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("user2", &[]),
+        claim_msg(52),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        cw20_msg(
+            "astro",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("user2"),
+                amount: Uint128::from(2000u128),
+            }
+        )
+    );
+    assert_eq!(res.messages.len(), 1);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end() + WEEK),
+        mock_info("user2", &[]),
+        claim_msg(52),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LockupNotExpired {});
+
+    // Expired lockups are unlocked and staked in the generator on behalf of the user
+    let res = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end() + WEEK),
+        mock_info("user1", &[]),
+        claim_msg(1),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            cw20_msg(
+                "astro",
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("user1"),
+                    amount: Uint128::from(1000u128),
+                }
+            ),
+            cw20_msg(
+                "astroport_lp_token",
+                &Cw20ExecuteMsg::Send {
+                    contract: String::from("generator"),
+                    amount: Uint128::from(150u128),
+                    msg: to_binary(&GeneratorHookMsg::DepositFor(Addr::unchecked("user1")))
+                        .unwrap(),
+                }
+            ),
+        ]
+    );
+
+    // The lockup is removed once unlocked
+    execute(
+        deps.as_mut(),
+        env_at(lockdrop_end() + WEEK),
+        mock_info("user1", &[]),
+        claim_msg(1),
+    )
+    .unwrap_err();
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(lockdrop_end()),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(3000u128),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LockdropFinished {});
+}
+
+#[test]
+fn withdraw_unmigrated() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(3000u128),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    lock(&mut deps, mock_env(), "user1", 100, 1);
+    lock(&mut deps, mock_env(), "user2", 100, 52);
+
+    let withdraw_msg = |duration| ExecuteMsg::WithdrawUnmigrated {
+        lp_token: String::from("lp_token"),
+        duration,
+    };
+
+    // The owner can still migrate the pool
+    let err = execute(
+        deps.as_mut(),
+        env_at(migration_deadline() - 1),
+        mock_info("user1", &[]),
+        withdraw_msg(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MigrationWindowOpen {});
+
+    // The pool wasn't migrated in time, so users get their LP tokens and ASTRO back
+    let res = execute(
+        deps.as_mut(),
+        env_at(migration_deadline()),
+        mock_info("user2", &[]),
+        withdraw_msg(52),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            cw20_msg(
+                "lp_token",
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("user2"),
+                    amount: Uint128::from(100u128),
+                }
+            ),
+            cw20_msg(
+                "astro",
+                &Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("user2"),
+                    amount: Uint128::from(2000u128),
+                }
+            ),
+        ]
+    );
+
+    // The other lockups keep their share
+    let res = execute(
+        deps.as_mut(),
+        env_at(migration_deadline()),
+        mock_info("user1", &[]),
+        withdraw_msg(1),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        cw20_msg(
+            "astro",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("user1"),
+                amount: Uint128::from(1000u128),
+            }
+        )
+    );
+
+    // The lockup is removed once withdrawn
+    execute(
+        deps.as_mut(),
+        env_at(migration_deadline()),
+        mock_info("user1", &[]),
+        withdraw_msg(1),
+    )
+    .unwrap_err();
+}
@@ -1,29 +1,34 @@
 use crate::error::ContractError;
-use crate::state::{Config, BRIDGES, CONFIG, OWNERSHIP_PROPOSAL};
+use crate::state::{Config, BRIDGES, CONFIG, OWNERSHIP_PROPOSAL, PAUSED};
 use std::cmp::min;
 
 use crate::migration;
 use crate::utils::{
-    build_distribute_msg, build_swap_msg, get_pool, try_build_swap_msg, validate_bridge,
+    astro_asset_info, build_convert_astro_msg, build_distribute_msg, build_ibc_transfer_msg,
+    build_staking_fees_msg, build_swap_msg, get_pool, validate_bridge, validate_ibc_bridge,
     BRIDGES_EXECUTION_MAX_DEPTH, BRIDGES_INITIAL_DEPTH,
 };
 use astroport::asset::{
-    addr_validate_to_lower, native_asset_info, token_asset, token_asset_info, Asset, AssetInfo,
-    PairInfo, ULUNA_DENOM, UUSD_DENOM,
+    addr_validate_to_lower, native_asset_info, token_asset_info, Asset, AssetInfo, PairInfo,
+    ULUNA_DENOM, UUSD_DENOM,
+};
+use astroport::attr_builder::AttrBuilder;
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
 };
-use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
 use astroport::factory::UpdateAddr;
 use astroport::maker::{
-    AssetWithLimit, BalancesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
-    QueryMsg,
+    AssetWithLimit, BalancesResponse, CollectConversion, ConfigResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg, SimulateCollectResponse, UpdateIbcBridge,
 };
 use astroport::pair::QueryMsg as PairQueryMsg;
+use astroport::querier::{query_factory_config, simulate};
 use cosmwasm_std::{
     attr, entry_point, to_binary, Addr, Attribute, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
     MessageInfo, Order, QueryRequest, Response, StdError, StdResult, SubMsg, Uint128, Uint64,
     WasmMsg, WasmQuery,
 };
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 use cw20::Cw20ExecuteMsg;
 use std::collections::{HashMap, HashSet};
 
@@ -78,6 +83,11 @@ pub fn instantiate(
         Decimal::percent(DEFAULT_MAX_SPREAD)
     };
 
+    let ibc_bridge = msg
+        .ibc_bridge
+        .map(|params| validate_ibc_bridge(deps.api, params))
+        .transpose()?;
+
     let cfg = Config {
         owner: addr_validate_to_lower(deps.api, &msg.owner)?,
         astro_token_contract: addr_validate_to_lower(deps.api, &msg.astro_token_contract)?,
@@ -91,6 +101,9 @@ pub fn instantiate(
         governance_contract,
         governance_percent,
         max_spread,
+        ibc_bridge,
+        native_astro: None,
+        notify_staking: false,
     };
 
     CONFIG.save(deps.storage, &cfg)?;
@@ -119,6 +132,8 @@ pub fn instantiate(
 ///             governance_contract,
 ///             governance_percent,
 ///             max_spread,
+///             ibc_bridge,
+///             notify_staking,
 ///         }** Updates general contract settings stores in the [`Config`].
 ///
 /// * **ExecuteMsg::UpdateBridges { add, remove }** Adds or removes bridge assets used to swap fee tokens to ASTRO.
@@ -134,6 +149,10 @@ pub fn instantiate(
 /// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
 ///
 /// * **ExecuteMsg::EnableRewards** Enables collected ASTRO (pre Maker upgrade) to be distributed to xASTRO stakers.
+///
+/// * **ExecuteMsg::Pause {}** Pauses fee collection.
+///
+/// * **ExecuteMsg::Unpause {}** Resumes fee collection.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -149,6 +168,8 @@ pub fn execute(
             governance_contract,
             governance_percent,
             max_spread,
+            ibc_bridge,
+            notify_staking,
         } => update_config(
             deps,
             info,
@@ -157,6 +178,8 @@ pub fn execute(
             governance_contract,
             governance_percent,
             max_spread,
+            ibc_bridge,
+            notify_staking,
         ),
         ExecuteMsg::UpdateBridges { add, remove } => update_bridges(deps, info, add, remove),
         ExecuteMsg::SwapBridgeAssets { assets, depth } => {
@@ -219,6 +242,8 @@ pub fn execute(
             CONFIG.save(deps.storage, &config)?;
             Ok(Response::default())
         }
+        ExecuteMsg::Pause {} => pause(deps, info),
+        ExecuteMsg::Unpause {} => unpause(deps, info),
     }
 }
 
@@ -237,9 +262,13 @@ fn collect(
     env: Env,
     assets: Vec<AssetWithLimit>,
 ) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
     let mut cfg = CONFIG.load(deps.storage)?;
 
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+    let astro = astro_asset_info(&cfg);
 
     // Check for duplicate assets
     let mut uniq = HashSet::new();
@@ -251,15 +280,27 @@ fn collect(
         return Err(ContractError::DuplicatedAsset {});
     }
 
+    // CW20 ASTRO is converted instead of swapped once the contract has been switched to native ASTRO
+    let cw20_astro = token_asset_info(cfg.astro_token_contract.clone());
+
     // Swap all non ASTRO tokens
-    let (mut response, bridge_assets) = swap_assets(
+    let (mut response, bridge_assets, swapped_assets) = swap_assets(
         deps.as_ref(),
         env.clone(),
         &cfg,
-        assets.into_iter().filter(|a| a.info.ne(&astro)).collect(),
+        assets
+            .into_iter()
+            .filter(|a| a.info.ne(&astro) && a.info.ne(&cw20_astro))
+            .collect(),
         true,
     )?;
 
+    if let Some(convert_msg) =
+        build_convert_astro_msg(deps.as_ref(), &cfg, env.contract.address.clone())?
+    {
+        response.messages.push(convert_msg);
+    }
+
     // If no swap messages - send ASTRO directly to x/vxASTRO stakers
     if response.messages.is_empty() {
         let (mut distribute_msg, attributes) = distribute(deps, env, &mut cfg)?;
@@ -275,19 +316,25 @@ fn collect(
         )?);
     }
 
-    Ok(response.add_attribute("action", "collect"))
+    let attributes = AttrBuilder::new("collect").assets("swap", &swapped_assets);
+
+    Ok(response
+        .add_event(attributes.event("maker"))
+        .add_attributes(attributes.build()))
 }
 
 /// ## Description
-/// This enum describes available token types that can be used as a SwapTarget.
-enum SwapTarget {
-    Astro(SubMsg),
-    Bridge { asset: AssetInfo, msg: SubMsg },
+/// This structure describes the next swap of a fee token on its route to ASTRO.
+struct SwapHop {
+    /// The pool used for the swap
+    pool: PairInfo,
+    /// The bridge asset received from the swap, or `None` if the swap returns ASTRO
+    bridge: Option<AssetInfo>,
 }
 
 /// ## Description
 /// Swap all non ASTRO tokens to ASTRO. Returns a [`ContractError`] on failure, otherwise returns
-/// a [`Response`] object if the operation was successful.
+/// a [`Response`] object, the bridge assets and the list of swapped assets if the operation was successful.
 /// # Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -304,12 +351,13 @@ fn swap_assets(
     cfg: &Config,
     assets: Vec<AssetWithLimit>,
     with_validation: bool,
-) -> Result<(Response, Vec<AssetInfo>), ContractError> {
+) -> Result<(Response, Vec<AssetInfo>, Vec<Asset>), ContractError> {
     let mut response = Response::default();
     let mut bridge_assets = HashMap::new();
+    let mut swapped_assets = vec![];
 
     // For default bridges we always need these two pools, hence the check
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+    let astro = astro_asset_info(cfg);
     let uusd = native_asset_info(UUSD_DENOM.to_string());
     let uluna = native_asset_info(ULUNA_DENOM.to_string());
 
@@ -329,143 +377,142 @@ fn swap_assets(
         }
 
         if !balance.is_zero() {
-            let swap_msg = if with_validation {
-                swap(deps, cfg, a.info, balance)?
+            swapped_assets.push(Asset {
+                info: a.info.clone(),
+                amount: balance,
+            });
+
+            let hop = if with_validation {
+                swap_hop(deps, cfg, a.info.clone())?
             } else {
-                swap_no_validate(deps, cfg, a.info, balance)?
+                swap_hop_no_validate(deps, cfg, a.info.clone())?
             };
 
-            match swap_msg {
-                SwapTarget::Astro(msg) => {
-                    response.messages.push(msg);
-                }
-                SwapTarget::Bridge { asset, msg } => {
-                    response.messages.push(msg);
-                    bridge_assets.insert(asset.to_string(), asset);
-                }
+            response
+                .messages
+                .push(build_swap_msg(deps, cfg, hop.pool, a.info, balance)?);
+            if let Some(asset) = hop.bridge {
+                bridge_assets.insert(asset.to_string(), asset);
             }
         }
     }
 
-    Ok((response, bridge_assets.into_values().collect()))
+    Ok((
+        response,
+        bridge_assets.into_values().collect(),
+        swapped_assets,
+    ))
 }
 
 /// ## Description
-/// Checks if all required pools and bridges exists and performs a swap operation to ASTRO.
-/// Returns a [`ContractError`] on failure, otherwise returns a vector that contains objects
-/// of type [`SubMsg`] if the operation was successful.
+/// Checks if all required pools and bridges exists and returns the next swap of a fee token on its route to ASTRO.
+/// Returns a [`ContractError`] on failure, otherwise returns a [`SwapHop`] object if the operation was successful.
 /// # Params
-/// * **deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`Deps`].
 ///
 /// * **cfg** is an object of type [`Config`]. This is the Maker contract configuration.
 ///
 /// * **from_token** is an object of type [`AssetInfo`]. This is the token to swap to ASTRO.
-///
-/// * **amount_in** is an object of type [`Uint128`]. This is the amount of fee tokens to swap.
-fn swap(
-    deps: Deps,
-    cfg: &Config,
-    from_token: AssetInfo,
-    amount_in: Uint128,
-) -> Result<SwapTarget, ContractError> {
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+fn swap_hop(deps: Deps, cfg: &Config, from_token: AssetInfo) -> Result<SwapHop, ContractError> {
+    let astro = astro_asset_info(cfg);
     let uusd = native_asset_info(UUSD_DENOM.to_string());
     let uluna = native_asset_info(ULUNA_DENOM.to_string());
 
     // 1. If from_token is UST, only swap to ASTRO is possible
     if from_token.eq(&uusd) {
-        let swap_to_astro = try_build_swap_msg(deps, cfg, from_token, astro, amount_in)?;
-        return Ok(SwapTarget::Astro(swap_to_astro));
+        let pool = get_pool(deps, cfg, from_token, astro)?;
+        return Ok(SwapHop { pool, bridge: None });
     }
 
     // 2. Check if bridge tokens exist
     let bridge_token = BRIDGES.load(deps.storage, from_token.to_string());
     if let Ok(asset) = bridge_token {
-        let bridge_pool = validate_bridge(
+        let pool = validate_bridge(
             deps,
             cfg,
-            from_token.clone(),
+            from_token,
             asset.clone(),
             astro,
             BRIDGES_INITIAL_DEPTH,
         )?;
-
-        let msg = build_swap_msg(deps, cfg, bridge_pool, from_token, amount_in)?;
-        return Ok(SwapTarget::Bridge { asset, msg });
+        return Ok(SwapHop {
+            pool,
+            bridge: Some(asset),
+        });
     }
 
     // 3. Check for a pair with UST
-    if from_token.ne(&uusd) {
-        let swap_to_uusd =
-            try_build_swap_msg(deps, cfg, from_token.clone(), uusd.clone(), amount_in);
-        if let Ok(msg) = swap_to_uusd {
-            return Ok(SwapTarget::Bridge { asset: uusd, msg });
-        }
+    if let Ok(pool) = get_pool(deps, cfg, from_token.clone(), uusd.clone()) {
+        return Ok(SwapHop {
+            pool,
+            bridge: Some(uusd),
+        });
     }
 
     // 4. Check for a pair with LUNA
-    if from_token.ne(&uusd) && from_token.ne(&uluna) {
-        let swap_to_uluna =
-            try_build_swap_msg(deps, cfg, from_token.clone(), uluna.clone(), amount_in);
-        if let Ok(msg) = swap_to_uluna {
-            return Ok(SwapTarget::Bridge { asset: uluna, msg });
+    if from_token.ne(&uluna) {
+        if let Ok(pool) = get_pool(deps, cfg, from_token.clone(), uluna.clone()) {
+            return Ok(SwapHop {
+                pool,
+                bridge: Some(uluna),
+            });
         }
     }
 
     // 5. Check for a direct pair with ASTRO
-    let swap_to_astro = try_build_swap_msg(deps, cfg, from_token.clone(), astro, amount_in);
-    if let Ok(msg) = swap_to_astro {
-        return Ok(SwapTarget::Astro(msg));
+    if let Ok(pool) = get_pool(deps, cfg, from_token.clone(), astro) {
+        return Ok(SwapHop { pool, bridge: None });
     }
 
     Err(ContractError::CannotSwap(from_token))
 }
 
 /// ## Description
-/// Performs a swap operation to ASTRO without additional checks. Returns a [`ContractError`] on failure,
-/// otherwise returns a vector that contains objects of type [`SubMsg`] if the operation
-/// was successful.
+/// Returns the next swap of a fee token on its route to ASTRO without additional checks.
+/// Returns a [`ContractError`] on failure, otherwise returns a [`SwapHop`] object if the operation was successful.
 /// # Params
-/// * **deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`Deps`].
 ///
 /// * **cfg** is an object of type [`Config`]. This is the Maker contract configuration.
 ///
 /// * **from_token** is an object of type [`AssetInfo`]. This is the token to swap to ASTRO.
-///
-/// * **amount_in** is an object of type [`Uint128`]. This is the amount of tokens to swap.
-fn swap_no_validate(
+fn swap_hop_no_validate(
     deps: Deps,
     cfg: &Config,
     from_token: AssetInfo,
-    amount_in: Uint128,
-) -> Result<SwapTarget, ContractError> {
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+) -> Result<SwapHop, ContractError> {
+    let astro = astro_asset_info(cfg);
     let uusd = native_asset_info(UUSD_DENOM.to_string());
     let uluna = native_asset_info(ULUNA_DENOM.to_string());
 
     // LUNA should be swapped to UST
     if from_token.eq(&uluna) {
-        let msg = try_build_swap_msg(deps, cfg, from_token, uusd.clone(), amount_in)?;
-        return Ok(SwapTarget::Bridge { asset: uusd, msg });
+        let pool = get_pool(deps, cfg, from_token, uusd.clone())?;
+        return Ok(SwapHop {
+            pool,
+            bridge: Some(uusd),
+        });
     }
 
     // UST should be swapped to ASTRO
     if from_token.eq(&uusd) {
-        let swap_to_astro = try_build_swap_msg(deps, cfg, from_token, astro, amount_in)?;
-        return Ok(SwapTarget::Astro(swap_to_astro));
+        let pool = get_pool(deps, cfg, from_token, astro)?;
+        return Ok(SwapHop { pool, bridge: None });
     }
 
     // Check if next level bridge exists
     let bridge_token = BRIDGES.load(deps.storage, from_token.to_string());
     if let Ok(asset) = bridge_token {
-        let msg = try_build_swap_msg(deps, cfg, from_token, asset.clone(), amount_in)?;
-        return Ok(SwapTarget::Bridge { asset, msg });
+        let pool = get_pool(deps, cfg, from_token, asset.clone())?;
+        return Ok(SwapHop {
+            pool,
+            bridge: Some(asset),
+        });
     }
 
     // Check for a direct swap to ASTRO
-    let swap_to_astro = try_build_swap_msg(deps, cfg, from_token.clone(), astro, amount_in);
-    if let Ok(msg) = swap_to_astro {
-        return Ok(SwapTarget::Astro(msg));
+    if let Ok(pool) = get_pool(deps, cfg, from_token.clone(), astro) {
+        return Ok(SwapHop { pool, bridge: None });
     }
 
     Err(ContractError::CannotSwap(from_token))
@@ -516,7 +563,8 @@ fn swap_bridge_assets(
         })
         .collect();
 
-    let (response, bridge_assets) = swap_assets(deps.as_ref(), env.clone(), &cfg, bridges, false)?;
+    let (response, bridge_assets, _) =
+        swap_assets(deps.as_ref(), env.clone(), &cfg, bridges, false)?;
 
     // There should always be some messages, if there are none - something went wrong
     if response.messages.is_empty() {
@@ -576,7 +624,7 @@ fn distribute(
     let mut result = vec![];
     let mut attributes = vec![];
 
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+    let astro = astro_asset_info(cfg);
 
     let mut amount = astro.query_pool(&deps.querier, env.contract.address.clone())?;
     if amount.is_zero() {
@@ -621,19 +669,47 @@ fn distribute(
         CONFIG.save(deps.storage, cfg)?;
     }
 
+    // Remote Makers send all ASTRO to the hub chain where it is split between stakers
+    if let Some(ibc_bridge) = &cfg.ibc_bridge {
+        result.push(build_ibc_transfer_msg(cfg, ibc_bridge, amount)?);
+
+        attributes.push(("action".to_string(), "ibc_transfer_astro".to_string()));
+        attributes.push(("channel".to_string(), ibc_bridge.channel.clone()));
+        attributes.push(("recipient".to_string(), ibc_bridge.recipient.clone()));
+        attributes.push((
+            "astro_distribution".to_string(),
+            pure_astro_reward.to_string(),
+        ));
+        if !current_preupgrade_distribution.is_zero() {
+            attributes.push((
+                "preupgrade_astro_distribution".to_string(),
+                current_preupgrade_distribution.to_string(),
+            ));
+        }
+
+        return Ok((result, attributes));
+    }
+
     let governance_amount = if let Some(governance_contract) = cfg.governance_contract.clone() {
         let amount =
             amount.multiply_ratio(Uint128::from(cfg.governance_percent), Uint128::new(100));
         if amount.u128() > 0 {
-            let send_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: cfg.astro_token_contract.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Send {
-                    contract: governance_contract.to_string(),
-                    msg: Binary::default(),
+            let send_msg = match &cfg.native_astro {
+                Some(native_astro) => Asset {
+                    info: native_astro.asset_info(),
                     amount,
-                })?,
-                funds: vec![],
-            });
+                }
+                .into_msg(&deps.querier, governance_contract)?,
+                None => CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: cfg.astro_token_contract.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: governance_contract.to_string(),
+                        msg: Binary::default(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }),
+            };
             result.push(SubMsg::new(send_msg))
         }
         amount
@@ -641,10 +717,10 @@ fn distribute(
         Uint128::zero()
     };
 
-    let to_staking_asset = token_asset(
-        cfg.astro_token_contract.clone(),
-        amount.checked_sub(governance_amount)?,
-    );
+    let to_staking_asset = Asset {
+        info: astro,
+        amount: amount.checked_sub(governance_amount)?,
+    };
 
     attributes.push(("action".to_string(), "distribute_astro".to_string()));
     attributes.push((
@@ -658,9 +734,13 @@ fn distribute(
         ));
     }
 
-    result.push(SubMsg::new(
-        to_staking_asset.into_msg(&deps.querier, cfg.staking_contract.clone())?,
-    ));
+    if cfg.notify_staking {
+        result.push(build_staking_fees_msg(cfg, to_staking_asset.amount)?);
+    } else {
+        result.push(SubMsg::new(
+            to_staking_asset.into_msg(&deps.querier, cfg.staking_contract.clone())?,
+        ));
+    }
     Ok((result, attributes))
 }
 
@@ -682,8 +762,13 @@ fn distribute(
 ///
 /// * **max_spread** is an [`Option`] field of type [`Decimal`]. Thisis the max spread used when swapping fee tokens to ASTRO.
 ///
+/// * **ibc_bridge** is an [`Option`] field of type [`UpdateIbcBridge`]. This sets or removes the IBC bridge used to send ASTRO to the hub chain.
+///
+/// * **notify_staking** is an [`Option`] field of type [`bool`]. This sets whether ASTRO is sent through the staking contract's fee distribution message.
+///
 /// ##Executor
 /// Only the owner can execute this.
+#[allow(clippy::too_many_arguments)]
 fn update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -692,6 +777,8 @@ fn update_config(
     governance_contract: Option<UpdateAddr>,
     governance_percent: Option<Uint64>,
     max_spread: Option<Decimal>,
+    ibc_bridge: Option<UpdateIbcBridge>,
+    notify_staking: Option<bool>,
 ) -> Result<Response, ContractError> {
     let mut attributes = vec![attr("action", "set_config")];
 
@@ -742,6 +829,30 @@ fn update_config(
         attributes.push(Attribute::new("max_spread", max_spread.to_string()));
     };
 
+    if let Some(action) = ibc_bridge {
+        match action {
+            UpdateIbcBridge::Set(params) => {
+                // The cw20-ics20 contract can only transfer CW20 ASTRO
+                if config.native_astro.is_some() {
+                    return Err(ContractError::NativeAstroWithIbcBridge {});
+                }
+
+                let ibc_bridge = validate_ibc_bridge(deps.api, params)?;
+                attributes.push(Attribute::new("ibc_channel", &ibc_bridge.channel));
+                attributes.push(Attribute::new("ibc_recipient", &ibc_bridge.recipient));
+                config.ibc_bridge = Some(ibc_bridge);
+            }
+            UpdateIbcBridge::Remove {} => {
+                config.ibc_bridge = None;
+            }
+        }
+    }
+
+    if let Some(notify_staking) = notify_staking {
+        config.notify_staking = notify_staking;
+        attributes.push(Attribute::new("notify_staking", notify_staking.to_string()));
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(attributes))
@@ -785,7 +896,7 @@ fn update_bridges(
     }
 
     // Add new bridges
-    let astro = token_asset_info(cfg.astro_token_contract.clone());
+    let astro = astro_asset_info(&cfg);
     if let Some(add_bridges) = add {
         for (asset, bridge) in add_bridges {
             if asset.equal(&bridge) {
@@ -809,6 +920,53 @@ fn update_bridges(
     Ok(Response::default().add_attribute("action", "update_bridges"))
 }
 
+/// ## Description
+/// Pauses fee collection. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// The owner or the factory's emergency admin can execute this.
+fn pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        let factory_config = query_factory_config(&deps.querier, cfg.factory_contract)?;
+        if Some(&info.sender) != factory_config.emergency_admin.as_ref() {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::default().add_attribute("action", "pause"))
+}
+
+/// ## Description
+/// Resumes fee collection. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != cfg.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::default().add_attribute("action", "unpause"))
+}
+
 /// ## Description
 /// Exposes all the queries available in the contract.
 /// # Params
@@ -826,12 +984,18 @@ fn update_bridges(
 ///
 /// * **QueryMsg::Bridges {}** Returns the bridges used for swapping fee tokens
 /// using a vector of [`(String, String)`] denoting Asset -> Bridge connections.
+///
+/// * **QueryMsg::SimulateCollect { assets }** Returns the expected ASTRO output of collecting
+/// certain fee tokens using a [`SimulateCollectResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
-        QueryMsg::Config {} => to_binary(&query_get_config(deps)?),
-        QueryMsg::Balances { assets } => to_binary(&query_get_balances(deps, env, assets)?),
-        QueryMsg::Bridges {} => to_binary(&query_bridges(deps, env)?),
+        QueryMsg::Config {} => Ok(to_binary(&query_get_config(deps)?)?),
+        QueryMsg::Balances { assets } => Ok(to_binary(&query_get_balances(deps, env, assets)?)?),
+        QueryMsg::Bridges {} => Ok(to_binary(&query_bridges(deps, env)?)?),
+        QueryMsg::SimulateCollect { assets } => {
+            Ok(to_binary(&query_simulate_collect(deps, env, assets)?)?)
+        }
     }
 }
 
@@ -851,6 +1015,10 @@ fn query_get_config(deps: Deps) -> StdResult<ConfigResponse> {
         max_spread: config.max_spread,
         remainder_reward: config.remainder_reward,
         pre_upgrade_astro_amount: config.pre_upgrade_astro_amount,
+        ibc_bridge: config.ibc_bridge,
+        native_astro: config.native_astro,
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+        notify_staking: config.notify_staking,
     })
 }
 
@@ -879,6 +1047,138 @@ fn query_get_balances(deps: Deps, env: Env, assets: Vec<AssetInfo>) -> StdResult
     Ok(resp)
 }
 
+/// ## Description
+/// Returns the expected result of collecting specific fee tokens using a [`SimulateCollectResponse`] object.
+/// Fee tokens are routed to ASTRO the same way as in Collect. Each fee token is simulated separately,
+/// so the result is an estimate when several fee tokens are swapped through the same pool.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **assets** is a vector that contains objects of type [`AssetWithLimit`]. These are the fee tokens to collect.
+fn query_simulate_collect(
+    deps: Deps,
+    env: Env,
+    assets: Vec<AssetWithLimit>,
+) -> Result<SimulateCollectResponse, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let astro = astro_asset_info(&cfg);
+    let cw20_astro = token_asset_info(cfg.astro_token_contract.clone());
+    let uusd = native_asset_info(UUSD_DENOM.to_string());
+    let uluna = native_asset_info(ULUNA_DENOM.to_string());
+
+    // Check for duplicate assets
+    let mut uniq = HashSet::new();
+    if !assets.iter().all(|a| uniq.insert(a.info.to_string())) {
+        return Err(ContractError::DuplicatedAsset {});
+    }
+
+    // Collect fails without the uusd - ASTRO pool and the uluna - uusd pool
+    get_pool(deps, &cfg, uusd.clone(), astro.clone())?;
+    get_pool(deps, &cfg, uluna, uusd)?;
+
+    let mut resp = SimulateCollectResponse {
+        astro_amount: Uint128::zero(),
+        conversions: vec![],
+    };
+
+    for a in assets {
+        // Get balance
+        let mut balance = a
+            .info
+            .query_pool(&deps.querier, env.contract.address.clone())?;
+        if balance.is_zero() {
+            continue;
+        }
+
+        // ASTRO is distributed and CW20 ASTRO is converted as is
+        let (route, astro_amount) = if a.info.eq(&astro) || a.info.eq(&cw20_astro) {
+            (vec![a.info.clone()], balance)
+        } else {
+            if let Some(limit) = a.limit {
+                if limit < balance && limit > Uint128::zero() {
+                    balance = limit;
+                }
+            }
+            simulate_conversion(deps, &cfg, a.info.clone(), balance)?
+        };
+
+        resp.astro_amount = resp.astro_amount.checked_add(astro_amount)?;
+        resp.conversions.push(CollectConversion {
+            offer_asset: Asset {
+                info: a.info,
+                amount: balance,
+            },
+            route,
+            astro_amount,
+        });
+    }
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Simulates swapping a fee token to ASTRO along the route used by Collect. Returns a [`ContractError`] on failure,
+/// otherwise returns the assets the fee token is swapped through and the expected amount of ASTRO.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **cfg** is an object of type [`Config`]. This is the Maker contract configuration.
+///
+/// * **from_token** is an object of type [`AssetInfo`]. This is the token to swap to ASTRO.
+///
+/// * **amount_in** is an object of type [`Uint128`]. This is the amount of fee tokens to swap.
+fn simulate_conversion(
+    deps: Deps,
+    cfg: &Config,
+    from_token: AssetInfo,
+    amount_in: Uint128,
+) -> Result<(Vec<AssetInfo>, Uint128), ContractError> {
+    let mut route = vec![from_token.clone()];
+    let mut offer_asset = Asset {
+        info: from_token,
+        amount: amount_in,
+    };
+
+    // The first swap is validated, the following ones are executed by SwapBridgeAssets
+    for depth in 0..=BRIDGES_EXECUTION_MAX_DEPTH {
+        let hop = if depth == 0 {
+            swap_hop(deps, cfg, offer_asset.info.clone())?
+        } else {
+            swap_hop_no_validate(deps, cfg, offer_asset.info.clone())?
+        };
+
+        // Deduct tax first
+        offer_asset.amount = offer_asset
+            .amount
+            .checked_sub(offer_asset.compute_tax(&deps.querier)?)?;
+
+        let return_amount =
+            simulate(&deps.querier, hop.pool.contract_addr, &offer_asset)?.return_amount;
+
+        let is_astro = hop.bridge.is_none();
+        let mut ask_asset = Asset {
+            info: hop.bridge.unwrap_or_else(|| astro_asset_info(cfg)),
+            amount: return_amount,
+        };
+
+        // The pool deducts tax when it sends native tokens
+        ask_asset.amount = ask_asset
+            .amount
+            .checked_sub(ask_asset.compute_tax(&deps.querier)?)?;
+
+        route.push(ask_asset.info.clone());
+        if is_astro {
+            return Ok((route, ask_asset.amount));
+        }
+        offer_asset = ask_asset;
+    }
+
+    Err(ContractError::MaxBridgeDepth(BRIDGES_EXECUTION_MAX_DEPTH))
+}
+
 /// ## Description
 /// Returns bridge tokens used for swapping fee tokens to ASTRO.
 /// ## Params
@@ -908,47 +1208,74 @@ pub fn query_pair(deps: Deps, contract_addr: Addr) -> StdResult<[AssetInfo; 2]>
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version.
+/// If [`MigrateMsg`] contains native ASTRO parameters, the contract is switched to native ASTRO
+/// and the CW20 ASTRO it holds is converted.
 /// ## Params
-/// * **_deps** is an object of type [`Deps`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`MigrateMsg`].
+/// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let contract_version = get_contract_version(deps.storage)?;
-
-    match contract_version.contract.as_ref() {
-        "astroport-maker" => match contract_version.version.as_ref() {
-            "1.0.0" => {
-                let config_v100 = migration::CONFIGV100.load(deps.storage)?;
-
-                let new_config = Config {
-                    owner: config_v100.owner,
-                    factory_contract: config_v100.factory_contract,
-                    staking_contract: config_v100.staking_contract,
-                    governance_contract: config_v100.governance_contract,
-                    governance_percent: config_v100.governance_percent,
-                    astro_token_contract: config_v100.astro_token_contract,
-                    max_spread: config_v100.max_spread,
-                    rewards_enabled: false,
-                    pre_upgrade_blocks: 0,
-                    last_distribution_block: 0,
-                    remainder_reward: Uint128::zero(),
-                    pre_upgrade_astro_amount: Uint128::zero(),
-                };
-
-                CONFIG.save(deps.storage, &new_config)?;
-            }
-            _ => return Err(ContractError::MigrationError {}),
-        },
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    match contract_version.version.as_ref() {
+        "1.0.0" => {
+            let config_v100 = migration::CONFIGV100.load(deps.storage)?;
+
+            let new_config = Config {
+                owner: config_v100.owner,
+                factory_contract: config_v100.factory_contract,
+                staking_contract: config_v100.staking_contract,
+                governance_contract: config_v100.governance_contract,
+                governance_percent: config_v100.governance_percent,
+                astro_token_contract: config_v100.astro_token_contract,
+                max_spread: config_v100.max_spread,
+                rewards_enabled: false,
+                pre_upgrade_blocks: 0,
+                last_distribution_block: 0,
+                remainder_reward: Uint128::zero(),
+                pre_upgrade_astro_amount: Uint128::zero(),
+                ibc_bridge: None,
+                native_astro: None,
+                notify_staking: false,
+            };
+
+            CONFIG.save(deps.storage, &new_config)?;
+        }
+        // The IBC bridge and native ASTRO are optional, so the stored config is still valid
+        "1.0.1" | "1.1.0" => {}
         _ => return Err(ContractError::MigrationError {}),
     }
 
+    let mut response = Response::new();
+
+    if let Some(params) = msg.native_astro {
+        let mut config = CONFIG.load(deps.storage)?;
+        if config.native_astro.is_some() {
+            return Err(ContractError::NativeAstroAlreadyEnabled {});
+        }
+        if config.ibc_bridge.is_some() {
+            return Err(ContractError::NativeAstroWithIbcBridge {});
+        }
+        config.native_astro = Some(params.validate(deps.api)?);
+
+        if let Some(convert_msg) =
+            build_convert_astro_msg(deps.as_ref(), &config, env.contract.address)?
+        {
+            response = response.add_submessage(convert_msg);
+        }
+
+        CONFIG.save(deps.storage, &config)?;
+        response = response.add_attribute("native_astro", params.denom);
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::new()
+    Ok(response
         .add_attribute("previous_contract_name", &contract_version.contract)
         .add_attribute("previous_contract_version", &contract_version.version)
         .add_attribute("new_contract_name", CONTRACT_NAME)
@@ -39,8 +39,20 @@ pub enum ContractError {
     #[error("Rewards collecting is already enabled")]
     RewardsAlreadyEnabled {},
 
+    #[error("IBC recipient address must be set")]
+    InvalidIbcRecipient {},
+
     #[error("An error occurred during migration")]
     MigrationError {},
+
+    #[error("Native ASTRO is already enabled")]
+    NativeAstroAlreadyEnabled {},
+
+    #[error("Native ASTRO can't be sent over the IBC bridge")]
+    NativeAstroWithIbcBridge {},
+
+    #[error("Fee collection is paused")]
+    Paused {},
 }
 
 impl From<OverflowError> for ContractError {
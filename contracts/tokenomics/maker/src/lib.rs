@@ -8,4 +8,6 @@ pub mod utils;
 
 mod migration;
 #[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
 mod testing;
@@ -1,5 +1,7 @@
 use astroport::asset::AssetInfo;
+use astroport::astro_converter::NativeAstro;
 use astroport::common::OwnershipProposal;
+use astroport::maker::IbcBridge;
 use cosmwasm_std::{Addr, Decimal, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
@@ -33,12 +35,26 @@ pub struct Config {
     pub remainder_reward: Uint128,
     /// The amount of collected ASTRO before enabling rewards distribution
     pub pre_upgrade_astro_amount: Uint128,
+    /// The IBC bridge used to send collected ASTRO to the hub chain. If set, ASTRO is not
+    /// distributed locally
+    pub ibc_bridge: Option<IbcBridge>,
+    /// The native ASTRO collected instead of CW20 ASTRO, if the contract has been switched to it
+    #[serde(default)]
+    pub native_astro: Option<NativeAstro>,
+    /// Whether ASTRO is sent to the staking contract through its fee distribution message, so
+    /// that fee inflows are recorded in the xASTRO exchange rate history
+    #[serde(default)]
+    pub notify_staking: bool,
 }
 
 /// ## Description
 /// Stores the contract configuration at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// ## Description
+/// Stores whether fee collection is paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
 /// ## Description
 /// Stores the latest proposal to change contract ownership
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
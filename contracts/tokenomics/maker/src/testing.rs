@@ -1,9 +1,17 @@
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_binary, Addr, Decimal, Uint128, Uint64};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, Decimal, Uint128, Uint64, WasmMsg};
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies as mock_dependencies_with_cw20;
 use crate::state::{Config, CONFIG};
-use astroport::maker::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use astroport::astro_converter::{Cw20HookMsg as ConverterHookMsg, NativeAstro, NativeAstroParams};
+use astroport::maker::{
+    ConfigResponse, ExecuteMsg, IbcBridge, IbcBridgeParams, InstantiateMsg, MigrateMsg, QueryMsg,
+    UpdateIbcBridge,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
 use std::str::FromStr;
 
 #[test]
@@ -27,6 +35,7 @@ fn proper_initialization() {
         governance_percent: Option::from(governance_percent),
         astro_token_contract: astro_token_contract.to_string(),
         max_spread: None,
+        ibc_bridge: None,
     };
     let res = instantiate(deps.as_mut(), env, info, instantiate_msg).unwrap();
     assert_eq!(0, res.messages.len());
@@ -47,6 +56,9 @@ fn proper_initialization() {
             last_distribution_block: 0,
             remainder_reward: Uint128::zero(),
             pre_upgrade_astro_amount: Uint128::zero(),
+            ibc_bridge: None,
+            native_astro: None,
+            notify_staking: false,
         }
     )
 }
@@ -71,6 +83,7 @@ fn update_owner() {
         governance_percent: Option::from(governance_percent),
         astro_token_contract: astro_token_contract.to_string(),
         max_spread: None,
+        ibc_bridge: None,
     };
 
     let env = mock_env();
@@ -135,3 +148,305 @@ fn update_owner() {
         from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
     assert_eq!(new_owner, config.owner);
 }
+
+#[test]
+fn update_ibc_bridge() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = Addr::unchecked("owner");
+
+    let msg = InstantiateMsg {
+        owner: owner.to_string(),
+        factory_contract: String::from("factory"),
+        staking_contract: String::from("staking"),
+        governance_contract: None,
+        governance_percent: None,
+        astro_token_contract: String::from("astro-token"),
+        max_spread: None,
+        ibc_bridge: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let params = IbcBridgeParams {
+        ics20_contract: String::from("ics20"),
+        channel: String::from("channel-0"),
+        recipient: String::from("hub_maker"),
+        timeout: None,
+        memo: None,
+    };
+    let update_config = |ibc_bridge| ExecuteMsg::UpdateConfig {
+        factory_contract: None,
+        staking_contract: None,
+        governance_contract: None,
+        governance_percent: None,
+        max_spread: None,
+        ibc_bridge: Some(ibc_bridge),
+        notify_staking: None,
+    };
+
+    // Only the owner can set the bridge
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        update_config(UpdateIbcBridge::Set(params.clone())),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Set(IbcBridgeParams {
+            channel: String::from("connection-0"),
+            ..params.clone()
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid IBC channel connection-0"
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Set(IbcBridgeParams {
+            recipient: String::new(),
+            ..params.clone()
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InvalidIbcRecipient {}.to_string()
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Set(IbcBridgeParams {
+            timeout: Some(10),
+            ..params.clone()
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: IBC timeout must be between 60 and 86400 seconds"
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Set(params)),
+    )
+    .unwrap();
+
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        res.ibc_bridge,
+        Some(IbcBridge {
+            ics20_contract: Addr::unchecked("ics20"),
+            channel: String::from("channel-0"),
+            recipient: String::from("hub_maker"),
+            timeout: 600,
+            memo: None,
+        })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Remove {}),
+    )
+    .unwrap();
+
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.ibc_bridge, None);
+}
+
+#[test]
+fn migrate_to_native_astro() {
+    let mut deps = mock_dependencies_with_cw20(&[]);
+    let owner = Addr::unchecked("owner");
+
+    let msg = InstantiateMsg {
+        owner: owner.to_string(),
+        factory_contract: String::from("factory"),
+        staking_contract: String::from("staking"),
+        governance_contract: None,
+        governance_percent: None,
+        astro_token_contract: String::from("astro-token"),
+        max_spread: None,
+        ibc_bridge: Some(IbcBridgeParams {
+            ics20_contract: String::from("ics20"),
+            channel: String::from("channel-0"),
+            recipient: String::from("hub_maker"),
+            timeout: None,
+            memo: None,
+        }),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    set_contract_version(deps.as_mut().storage, "astroport-maker", "1.0.1").unwrap();
+
+    let migrate_msg = MigrateMsg {
+        native_astro: Some(NativeAstroParams {
+            denom: String::from("factory/creator/uastro"),
+            converter: String::from("converter"),
+        }),
+    };
+
+    // The cw20-ics20 contract can't send native ASTRO
+    let err = migrate(deps.as_mut(), mock_env(), migrate_msg.clone()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::NativeAstroWithIbcBridge {}.to_string()
+    );
+
+    let update_config = |ibc_bridge| ExecuteMsg::UpdateConfig {
+        factory_contract: None,
+        staking_contract: None,
+        governance_contract: None,
+        governance_percent: None,
+        max_spread: None,
+        ibc_bridge: Some(ibc_bridge),
+        notify_staking: None,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Remove {}),
+    )
+    .unwrap();
+
+    // Collected CW20 ASTRO is converted during the migration
+    deps.querier
+        .set_token_balance("astro-token", MOCK_CONTRACT_ADDR, 100);
+    let res = migrate(deps.as_mut(), mock_env(), migrate_msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro-token"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("converter"),
+                amount: Uint128::from(100u128),
+                msg: to_binary(&ConverterHookMsg::Convert { receiver: None }).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        res.native_astro,
+        Some(NativeAstro {
+            denom: String::from("factory/creator/uastro"),
+            converter: Addr::unchecked("converter"),
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        update_config(UpdateIbcBridge::Set(IbcBridgeParams {
+            ics20_contract: String::from("ics20"),
+            channel: String::from("channel-0"),
+            recipient: String::from("hub_maker"),
+            timeout: None,
+            memo: None,
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::NativeAstroWithIbcBridge {}.to_string()
+    );
+
+    set_contract_version(deps.as_mut().storage, "astroport-maker", "1.0.1").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), migrate_msg).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::NativeAstroAlreadyEnabled {}.to_string()
+    );
+}
+
+#[test]
+fn pause() {
+    let mut deps = mock_dependencies_with_cw20(&[]);
+    let owner = Addr::unchecked("owner");
+
+    let msg = InstantiateMsg {
+        owner: owner.to_string(),
+        factory_contract: String::from("factory"),
+        staking_contract: String::from("staking"),
+        governance_contract: None,
+        governance_percent: None,
+        astro_token_contract: String::from("astro-token"),
+        max_spread: None,
+        ibc_bridge: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    // The factory's emergency admin can pause the Maker
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("emergency_admin", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert!(config.paused);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Collect { assets: vec![] },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Paused {}.to_string());
+
+    // But only the owner can resume it
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("emergency_admin", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert!(!config.paused);
+}
@@ -1,10 +1,17 @@
 use crate::error::ContractError;
 use crate::state::{Config, BRIDGES};
-use astroport::asset::{Asset, AssetInfo, PairInfo};
-use astroport::maker::ExecuteMsg;
+use astroport::asset::{token_asset_info, Asset, AssetInfo, PairInfo};
+use astroport::ics20::{
+    build_ics20_transfer_msg, validate_ibc_channel, validate_ibc_timeout, TransferMsg,
+};
+use astroport::maker::{ExecuteMsg, IbcBridge, IbcBridgeParams};
 use astroport::pair::Cw20HookMsg;
 use astroport::querier::query_pair_info;
-use cosmwasm_std::{to_binary, Coin, Deps, Env, StdResult, SubMsg, Uint128, WasmMsg};
+use astroport::staking::{Cw20HookMsg as StakingCw20HookMsg, ExecuteMsg as StakingExecuteMsg};
+use cosmwasm_std::{
+    coins, to_binary, Addr, Api, Coin, Deps, Env, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 
 /// The default bridge depth for a fee token
 pub const BRIDGES_INITIAL_DEPTH: u64 = 0;
@@ -13,18 +20,6 @@ pub const BRIDGES_MAX_DEPTH: u64 = 2;
 /// Swap execution depth limit
 pub const BRIDGES_EXECUTION_MAX_DEPTH: u64 = 3;
 
-pub fn try_build_swap_msg(
-    deps: Deps,
-    cfg: &Config,
-    from: AssetInfo,
-    to: AssetInfo,
-    amount_in: Uint128,
-) -> Result<SubMsg, ContractError> {
-    let pool = get_pool(deps, cfg, from.clone(), to)?;
-    let msg = build_swap_msg(deps, cfg, pool, from, amount_in)?;
-    Ok(msg)
-}
-
 pub fn build_swap_msg(
     deps: Deps,
     cfg: &Config,
@@ -50,6 +45,7 @@ pub fn build_swap_msg(
                 belief_price: None,
                 max_spread: Some(cfg.max_spread),
                 to: None,
+                referral_code: None,
             })?,
             funds: vec![Coin {
                 denom: from.to_string(),
@@ -66,6 +62,7 @@ pub fn build_swap_msg(
                     belief_price: None,
                     max_spread: Some(cfg.max_spread),
                     to: None,
+                    referral_code: None,
                 })?,
             })?,
             funds: vec![],
@@ -150,3 +147,126 @@ pub fn get_pool(
     )
     .map_err(|_| ContractError::InvalidBridgeNoPool(from.clone(), to.clone()))
 }
+
+/// ## Description
+/// Validates the IBC bridge parameters and returns an [`IbcBridge`] object.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **params** is an object of type [`IbcBridgeParams`]. These are the bridge parameters to validate.
+pub fn validate_ibc_bridge(
+    api: &dyn Api,
+    params: IbcBridgeParams,
+) -> Result<IbcBridge, ContractError> {
+    validate_ibc_channel(&params.channel)?;
+
+    if params.recipient.trim().is_empty() {
+        return Err(ContractError::InvalidIbcRecipient {});
+    }
+
+    Ok(IbcBridge {
+        ics20_contract: api.addr_validate(&params.ics20_contract)?,
+        channel: params.channel,
+        recipient: params.recipient,
+        timeout: validate_ibc_timeout(params.timeout)?,
+        memo: params.memo,
+    })
+}
+
+/// ## Description
+/// Builds a message that sends ASTRO to the hub chain through the cw20-ics20 contract.
+/// If the transfer fails or times out, the cw20-ics20 contract refunds the ASTRO to the Maker
+/// and it is sent again on the next collect.
+/// ## Params
+/// * **cfg** is an object of type [`Config`]. This is the Maker configuration.
+///
+/// * **ibc_bridge** is an object of type [`IbcBridge`]. This is the bridge used for the transfer.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of ASTRO to send.
+pub fn build_ibc_transfer_msg(
+    cfg: &Config,
+    ibc_bridge: &IbcBridge,
+    amount: Uint128,
+) -> StdResult<SubMsg> {
+    Ok(SubMsg::new(build_ics20_transfer_msg(
+        &cfg.astro_token_contract,
+        &ibc_bridge.ics20_contract,
+        amount,
+        &TransferMsg {
+            channel: ibc_bridge.channel.clone(),
+            remote_address: ibc_bridge.recipient.clone(),
+            timeout: Some(ibc_bridge.timeout),
+            memo: ibc_bridge.memo.clone(),
+        },
+    )?))
+}
+
+/// ## Description
+/// Returns the ASTRO asset collected by the Maker. This is native ASTRO once the contract has been
+/// switched to it, otherwise CW20 ASTRO.
+/// ## Params
+/// * **cfg** is an object of type [`Config`]. This is the Maker configuration.
+pub fn astro_asset_info(cfg: &Config) -> AssetInfo {
+    match &cfg.native_astro {
+        Some(native_astro) => native_astro.asset_info(),
+        None => token_asset_info(cfg.astro_token_contract.clone()),
+    }
+}
+
+/// ## Description
+/// Builds a message that converts all the CW20 ASTRO held by the Maker to native ASTRO.
+/// Returns [`None`] if the contract hasn't been switched to native ASTRO or holds no CW20 ASTRO.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **cfg** is an object of type [`Config`]. This is the Maker configuration.
+///
+/// * **contract_addr** is an object of type [`Addr`]. This is the Maker contract address.
+pub fn build_convert_astro_msg(
+    deps: Deps,
+    cfg: &Config,
+    contract_addr: Addr,
+) -> StdResult<Option<SubMsg>> {
+    match &cfg.native_astro {
+        Some(native_astro) => {
+            let balance = token_asset_info(cfg.astro_token_contract.clone())
+                .query_pool(&deps.querier, contract_addr)?;
+            if balance.is_zero() {
+                Ok(None)
+            } else {
+                Ok(Some(SubMsg::new(
+                    native_astro.convert_msg(&cfg.astro_token_contract, balance)?,
+                )))
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// ## Description
+/// Builds a message that sends ASTRO to the xASTRO staking contract through its fee distribution
+/// message, so that the staking contract records the inflow in its exchange rate history.
+/// ## Params
+/// * **cfg** is an object of type [`Config`]. This is the Maker configuration.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of ASTRO to send.
+pub fn build_staking_fees_msg(cfg: &Config, amount: Uint128) -> StdResult<SubMsg> {
+    let msg = match &cfg.native_astro {
+        Some(native_astro) => WasmMsg::Execute {
+            contract_addr: cfg.staking_contract.to_string(),
+            msg: to_binary(&StakingExecuteMsg::DistributeFees {})?,
+            funds: coins(amount.u128(), &native_astro.denom),
+        },
+        None => WasmMsg::Execute {
+            contract_addr: cfg.astro_token_contract.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: cfg.staking_contract.to_string(),
+                amount,
+                msg: to_binary(&StakingCw20HookMsg::DistributeFees {})?,
+            })?,
+            funds: vec![],
+        },
+    };
+
+    Ok(SubMsg::new(msg))
+}
@@ -3,15 +3,24 @@ use astroport::asset::{
     ULUNA_DENOM, UUSD_DENOM,
 };
 use astroport::factory::{PairConfig, PairType, UpdateAddr};
+use astroport::ics20::TransferMsg;
 use astroport::maker::{
-    AssetWithLimit, BalancesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    AssetWithLimit, BalancesResponse, ConfigResponse, ExecuteMsg, IbcBridgeParams, InstantiateMsg,
+    QueryMsg, SimulateCollectResponse, UpdateIbcBridge,
+};
+use astroport::staking::{
+    ConfigResponse as StakingConfigResponse, Cw20HookMsg as StakingCw20HookMsg,
+    ExchangeRateCheckpoint, InstantiateMsg as StakingInstantiateMsg, QueryMsg as StakingQueryMsg,
 };
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use astroport_test::contracts::{store_staking_code, store_token_code};
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{
-    attr, to_binary, Addr, Coin, Decimal, QueryRequest, Uint128, Uint64, WasmQuery,
+    attr, from_binary, from_slice, to_binary, to_vec, Addr, Binary, Coin, Decimal, Deps, DepsMut,
+    Empty, Env, MessageInfo, QueryRequest, Response, StdResult, Uint128, Uint64, WasmQuery,
 };
-use cw20::{BalanceResponse, Cw20QueryMsg, MinterResponse};
+use cw20::{BalanceResponse, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse};
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use terra_multi_test::{
     next_block, AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock,
@@ -57,6 +66,7 @@ fn instantiate_contracts(
             minter: owner.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let astro_token_instance = router
@@ -161,6 +171,7 @@ fn instantiate_contracts(
         governance_percent: Option::from(governance_percent),
         astro_token_contract: astro_token_instance.to_string(),
         max_spread,
+        ibc_bridge: None,
     };
     let maker_instance = router
         .instantiate_contract(
@@ -199,6 +210,7 @@ fn instantiate_token(router: &mut TerraApp, owner: Addr, name: String, symbol: S
             minter: owner.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let token_instance = router
@@ -436,6 +448,8 @@ fn update_config() {
         staking_contract: Some(new_staking.to_string()),
         factory_contract: Some(new_factory.to_string()),
         max_spread: Some(new_max_spread),
+        ibc_bridge: None,
+        notify_staking: None,
     };
 
     // Assert cannot update with improper owner
@@ -472,6 +486,8 @@ fn update_config() {
         staking_contract: None,
         factory_contract: None,
         max_spread: None,
+        ibc_bridge: None,
+        notify_staking: None,
     };
 
     router
@@ -1124,6 +1140,160 @@ fn collect_err_no_swap_pair() {
     assert_eq!(e.to_string(), "Cannot swap uabc. No swap destinations",);
 }
 
+#[test]
+fn simulate_collect() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user0000");
+    let staking = Addr::unchecked("staking");
+    let governance_percent = Uint64::new(50);
+
+    let (astro_token_instance, factory_instance, maker_instance, governance_instance) =
+        instantiate_contracts(
+            &mut router,
+            owner.clone(),
+            staking.clone(),
+            governance_percent,
+            None,
+        );
+
+    let token_instance = instantiate_token(
+        &mut router,
+        owner.clone(),
+        "Test token".to_string(),
+        "TEST".to_string(),
+    );
+
+    let uusd_asset = String::from(UUSD_DENOM);
+    let uluna_asset = String::from(ULUNA_DENOM);
+    let ukrt_asset = String::from("ukrt");
+
+    for t in vec![
+        [
+            native_asset(uusd_asset.clone(), Uint128::from(100_000_u128)),
+            token_asset(astro_token_instance.clone(), Uint128::from(100_000_u128)),
+        ],
+        [
+            native_asset(uluna_asset.clone(), Uint128::from(100_000_u128)),
+            native_asset(uusd_asset.clone(), Uint128::from(100_000_u128)),
+        ],
+        [
+            token_asset(token_instance.clone(), Uint128::from(100_000_u128)),
+            token_asset(astro_token_instance.clone(), Uint128::from(100_000_u128)),
+        ],
+    ] {
+        create_pair(
+            &mut router,
+            owner.clone(),
+            user.clone(),
+            &factory_instance,
+            t,
+        );
+    }
+
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::EnableRewards { blocks: 1 },
+            &[],
+        )
+        .unwrap();
+
+    mint_some_token(
+        &mut router,
+        owner.clone(),
+        token_instance.clone(),
+        maker_instance.clone(),
+        Uint128::new(100),
+    );
+
+    router
+        .init_bank_balance(
+            &maker_instance,
+            vec![Coin {
+                denom: ukrt_asset.clone(),
+                amount: Uint128::new(20),
+            }],
+        )
+        .unwrap();
+
+    let assets = vec![AssetWithLimit {
+        info: token_asset_info(token_instance.clone()),
+        limit: None,
+    }];
+
+    let simulation: SimulateCollectResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &maker_instance,
+            &QueryMsg::SimulateCollect {
+                assets: assets.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(simulation.conversions.len(), 1);
+    assert_eq!(
+        simulation.conversions[0].offer_asset,
+        token_asset(token_instance.clone(), Uint128::new(100))
+    );
+    assert_eq!(
+        simulation.conversions[0].route,
+        vec![
+            token_asset_info(token_instance.clone()),
+            token_asset_info(astro_token_instance.clone()),
+        ]
+    );
+    assert_eq!(
+        simulation.conversions[0].astro_amount,
+        simulation.astro_amount
+    );
+    assert!(!simulation.astro_amount.is_zero());
+
+    // Fee tokens without a route to ASTRO can't be collected
+    let err = router
+        .wrap()
+        .query_wasm_smart::<SimulateCollectResponse, _, _>(
+            &maker_instance,
+            &QueryMsg::SimulateCollect {
+                assets: vec![AssetWithLimit {
+                    info: native_asset_info(ukrt_asset),
+                    limit: None,
+                }],
+            },
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot swap ukrt. No swap destinations"));
+
+    // Collect distributes the simulated amount of ASTRO
+    router
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            maker_instance.clone(),
+            &ExecuteMsg::Collect { assets },
+            &[],
+        )
+        .unwrap();
+
+    let governance_amount = simulation
+        .astro_amount
+        .multiply_ratio(Uint128::from(governance_percent), Uint128::new(100));
+    check_balance(
+        &mut router,
+        governance_instance,
+        astro_token_instance.clone(),
+        governance_amount,
+    );
+    check_balance(
+        &mut router,
+        staking,
+        astro_token_instance,
+        simulation.astro_amount - governance_amount,
+    );
+}
+
 #[test]
 fn update_bridges() {
     let mut router = mock_app();
@@ -2015,3 +2185,375 @@ fn distribute_initially_accrued_fees() {
         .iter()
         .any(|a| a.key == "preupgrade_astro_distribution"));
 }
+
+/// A minimal cw20-ics20 contract that stores the last received transfer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum MockIcs20ExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+fn mock_ics20_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_ics20_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockIcs20ExecuteMsg,
+) -> StdResult<Response> {
+    let MockIcs20ExecuteMsg::Receive(receive_msg) = msg;
+    let transfer: TransferMsg = from_binary(&receive_msg.msg)?;
+    deps.storage.set(b"transfer", &to_vec(&transfer)?);
+
+    Ok(Response::new())
+}
+
+fn mock_ics20_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    let transfer: TransferMsg = from_slice(&deps.storage.get(b"transfer").unwrap())?;
+    to_binary(&transfer)
+}
+
+#[test]
+fn collect_with_ibc_bridge() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let staking = Addr::unchecked("staking");
+    let governance_percent = Uint64::new(10);
+
+    let (astro_token_instance, _, maker_instance, governance_instance) = instantiate_contracts(
+        &mut router,
+        owner.clone(),
+        staking.clone(),
+        governance_percent,
+        None,
+    );
+
+    let ics20_code_id = router.store_code(Box::new(ContractWrapper::new_with_empty(
+        mock_ics20_execute,
+        mock_ics20_instantiate,
+        mock_ics20_query,
+    )));
+    let ics20_instance = router
+        .instantiate_contract(
+            ics20_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            String::from("ICS20"),
+            None,
+        )
+        .unwrap();
+
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                factory_contract: None,
+                staking_contract: None,
+                governance_contract: None,
+                governance_percent: None,
+                max_spread: None,
+                ibc_bridge: Some(UpdateIbcBridge::Set(IbcBridgeParams {
+                    ics20_contract: ics20_instance.to_string(),
+                    channel: String::from("channel-1"),
+                    recipient: String::from("hub_maker"),
+                    timeout: Some(300),
+                    memo: Some(String::from("astroport fees")),
+                })),
+                notify_staking: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::EnableRewards { blocks: 1 },
+            &[],
+        )
+        .unwrap();
+
+    mint_some_token(
+        &mut router,
+        owner.clone(),
+        astro_token_instance.clone(),
+        maker_instance.clone(),
+        Uint128::new(1_000),
+    );
+
+    let res = router
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            maker_instance.clone(),
+            &ExecuteMsg::Collect {
+                assets: vec![AssetWithLimit {
+                    info: token_asset_info(astro_token_instance.clone()),
+                    limit: None,
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        res.events[1].attributes[1],
+        attr("action", "ibc_transfer_astro")
+    );
+
+    // All ASTRO is sent to the hub chain, nothing is distributed locally
+    check_balance(
+        &mut router,
+        maker_instance.clone(),
+        astro_token_instance.clone(),
+        Uint128::zero(),
+    );
+    check_balance(
+        &mut router,
+        ics20_instance.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(1_000),
+    );
+    check_balance(
+        &mut router,
+        staking,
+        astro_token_instance.clone(),
+        Uint128::zero(),
+    );
+    check_balance(
+        &mut router,
+        governance_instance,
+        astro_token_instance,
+        Uint128::zero(),
+    );
+
+    let transfer: TransferMsg = router
+        .wrap()
+        .query_wasm_smart(&ics20_instance, &Empty {})
+        .unwrap();
+    assert_eq!(
+        transfer,
+        TransferMsg {
+            channel: String::from("channel-1"),
+            remote_address: String::from("hub_maker"),
+            timeout: Some(300),
+            memo: Some(String::from("astroport fees")),
+        }
+    );
+}
+
+#[test]
+fn distribute_fees_to_staking() {
+    let mut router = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user0000");
+    let governance_percent = Uint64::new(10);
+
+    let (astro_token_instance, _, maker_instance, governance_instance) = instantiate_contracts(
+        &mut router,
+        owner.clone(),
+        Addr::unchecked("staking"),
+        governance_percent,
+        None,
+    );
+
+    let token_code_id = store_token_code(&mut router);
+    let staking_code_id = store_staking_code(&mut router);
+    let staking_instance = router
+        .instantiate_contract(
+            staking_code_id,
+            owner.clone(),
+            &StakingInstantiateMsg {
+                owner: owner.to_string(),
+                token_code_id,
+                deposit_token_addr: astro_token_instance.to_string(),
+            },
+            &[],
+            String::from("xASTRO"),
+            None,
+        )
+        .unwrap();
+    let staking_config: StakingConfigResponse = router
+        .wrap()
+        .query_wasm_smart(&staking_instance, &StakingQueryMsg::Config {})
+        .unwrap();
+
+    // Stake 10_000 ASTRO, 1_000 xASTRO of which are locked in the staking contract
+    mint_some_token(
+        &mut router,
+        owner.clone(),
+        astro_token_instance.clone(),
+        user.clone(),
+        Uint128::new(10_000),
+    );
+    router
+        .execute_contract(
+            user.clone(),
+            astro_token_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: staking_instance.to_string(),
+                amount: Uint128::new(10_000),
+                msg: to_binary(&StakingCw20HookMsg::Enter {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    check_balance(
+        &mut router,
+        user.clone(),
+        staking_config.share_token_addr.clone(),
+        Uint128::new(9_000),
+    );
+
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                factory_contract: None,
+                staking_contract: Some(staking_instance.to_string()),
+                governance_contract: None,
+                governance_percent: None,
+                max_spread: None,
+                ibc_bridge: None,
+                notify_staking: Some(true),
+            },
+            &[],
+        )
+        .unwrap();
+    let res: ConfigResponse = router
+        .wrap()
+        .query_wasm_smart(&maker_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert!(res.notify_staking);
+    assert_eq!(res.staking_contract, staking_instance);
+
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::EnableRewards { blocks: 1 },
+            &[],
+        )
+        .unwrap();
+
+    // Collect fees in the same block the stake was made
+    mint_some_token(
+        &mut router,
+        owner.clone(),
+        astro_token_instance.clone(),
+        maker_instance.clone(),
+        Uint128::new(1_000),
+    );
+    router
+        .execute_contract(
+            user.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::Collect {
+                assets: vec![AssetWithLimit {
+                    info: token_asset_info(astro_token_instance.clone()),
+                    limit: None,
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+
+    check_balance(
+        &mut router,
+        governance_instance,
+        astro_token_instance.clone(),
+        Uint128::new(100),
+    );
+    check_balance(
+        &mut router,
+        staking_instance.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(10_900),
+    );
+
+    // The fee inflow is reflected in the exchange rate right away
+    let checkpoint: Option<ExchangeRateCheckpoint> = router
+        .wrap()
+        .query_wasm_smart(
+            &staking_instance,
+            &StakingQueryMsg::ExchangeRateAt {
+                timestamp: router.block_info().time.seconds(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        checkpoint,
+        Some(ExchangeRateCheckpoint {
+            timestamp: router.block_info().time.seconds(),
+            total_deposit: Uint128::new(10_900),
+            total_shares: Uint128::new(10_000),
+            exchange_rate: Decimal::from_ratio(109u128, 100u128),
+            total_fees: Uint128::new(900),
+        })
+    );
+
+    // Without the notification ASTRO is sent to the staking contract as a plain transfer
+    router
+        .execute_contract(
+            owner.clone(),
+            maker_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                factory_contract: None,
+                staking_contract: None,
+                governance_contract: None,
+                governance_percent: None,
+                max_spread: None,
+                ibc_bridge: None,
+                notify_staking: Some(false),
+            },
+            &[],
+        )
+        .unwrap();
+    mint_some_token(
+        &mut router,
+        owner,
+        astro_token_instance.clone(),
+        maker_instance.clone(),
+        Uint128::new(1_000),
+    );
+    router
+        .execute_contract(
+            user,
+            maker_instance,
+            &ExecuteMsg::Collect {
+                assets: vec![AssetWithLimit {
+                    info: token_asset_info(astro_token_instance.clone()),
+                    limit: None,
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+    check_balance(
+        &mut router,
+        staking_instance.clone(),
+        astro_token_instance,
+        Uint128::new(11_800),
+    );
+
+    let checkpoint: Option<ExchangeRateCheckpoint> = router
+        .wrap()
+        .query_wasm_smart(
+            &staking_instance,
+            &StakingQueryMsg::ExchangeRateAt {
+                timestamp: router.block_info().time.seconds(),
+            },
+        )
+        .unwrap();
+    assert_eq!(checkpoint.unwrap().total_fees, Uint128::new(900));
+}
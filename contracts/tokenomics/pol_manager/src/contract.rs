@@ -0,0 +1,1038 @@
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, OWNERSHIP_PROPOSAL, STRATEGIES};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
+use astroport::generator::{
+    ExecuteMsg as GeneratorExecuteMsg, PendingTokenResponse, QueryMsg as GeneratorQueryMsg,
+};
+use astroport::pair::{
+    Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+};
+use astroport::pol_manager::{
+    CallbackMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PositionResponse,
+    QueryMsg, StrategyInfo,
+};
+use astroport::querier::{query_factory_config, query_pair_info, query_token_balance};
+use cosmwasm_std::{
+    attr, entry_point, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, QuerierWrapper, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Bound;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-pol-manager";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Pagination settings
+/// The maximum limit for reading strategies
+const MAX_LIMIT: u32 = 30;
+/// The default limit for reading strategies
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns a default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the parameters used for creating the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            manager: addr_validate_to_lower(deps.api, &msg.manager)?,
+            factory: addr_validate_to_lower(deps.api, &msg.factory)?,
+            astro_token: addr_validate_to_lower(deps.api, &msg.astro_token)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Exposes all the execute functions available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::SetStrategy {
+///             pair,
+///             max_deposits,
+///             slippage_tolerance,
+///             max_spread,
+///         }** Approves a strategy for a pair or replaces its parameters.
+///
+/// * **ExecuteMsg::RemoveStrategy { pair }** Removes a strategy without a position.
+///
+/// * **ExecuteMsg::ProvideLiquidity { pair, assets }** Provides liquidity to the strategy's pair and stakes the LP tokens.
+///
+/// * **ExecuteMsg::Harvest { pair }** Claims the generator rewards of the strategy.
+///
+/// * **ExecuteMsg::Compound { pair }** Claims the generator rewards of the strategy and provides them as liquidity.
+///
+/// * **ExecuteMsg::Unwind { pair, amount }** Unstakes LP tokens and withdraws the underlying assets.
+///
+/// * **ExecuteMsg::Withdraw { assets }** Sends assets held by the contract to the owner.
+///
+/// * **ExecuteMsg::UpdateConfig { manager }** Updates the manager.
+///
+/// * **ExecuteMsg::Callback(msg)** Handles callbacks described in the [`CallbackMsg`].
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetStrategy {
+            pair,
+            max_deposits,
+            slippage_tolerance,
+            max_spread,
+        } => set_strategy(
+            deps,
+            info,
+            pair,
+            max_deposits,
+            slippage_tolerance,
+            max_spread,
+        ),
+        ExecuteMsg::RemoveStrategy { pair } => remove_strategy(deps, env, info, pair),
+        ExecuteMsg::ProvideLiquidity { pair, assets } => {
+            provide_liquidity(deps, info, pair, assets)
+        }
+        ExecuteMsg::Harvest { pair } => harvest(deps, info, pair),
+        ExecuteMsg::Compound { pair } => compound(deps, env, info, pair),
+        ExecuteMsg::Unwind { pair, amount } => unwind(deps, env, info, pair, amount),
+        ExecuteMsg::Withdraw { assets } => withdraw(deps, info, assets),
+        ExecuteMsg::UpdateConfig { manager } => update_config(deps, info, manager),
+        ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, msg),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Approves a strategy for a pair registered in the factory or replaces its parameters.
+/// Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the pair the strategy provides liquidity to.
+///
+/// * **max_deposits** is an array of type [`Asset`]. These are the maximum amounts of each pair asset
+/// that can be deposited.
+///
+/// * **slippage_tolerance** is an object of type [`Decimal`]. This is the slippage tolerance used when providing liquidity.
+///
+/// * **max_spread** is an object of type [`Decimal`]. This is the maximum spread used when swapping rewards.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn set_strategy(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair: String,
+    max_deposits: [Asset; 2],
+    slippage_tolerance: Decimal,
+    max_spread: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair, &PairQueryMsg::Pair {})?;
+
+    // Only pairs created by the factory can be used
+    let registered = query_pair_info(&deps.querier, config.factory, &pair_info.asset_infos)?;
+    if registered.contract_addr != pair {
+        return Err(ContractError::PairNotRegistered {});
+    }
+
+    let max_deposits = order_assets(&pair_info, max_deposits)?;
+    let deposited = match STRATEGIES.may_load(deps.storage, &pair)? {
+        Some(strategy) => strategy.deposited,
+        None => [
+            Asset {
+                info: pair_info.asset_infos[0].clone(),
+                amount: Uint128::zero(),
+            },
+            Asset {
+                info: pair_info.asset_infos[1].clone(),
+                amount: Uint128::zero(),
+            },
+        ],
+    };
+
+    STRATEGIES.save(
+        deps.storage,
+        &pair,
+        &StrategyInfo {
+            pair: pair_info,
+            max_deposits: max_deposits.clone(),
+            deposited,
+            slippage_tolerance,
+            max_spread,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_strategy"),
+        attr("pair", pair),
+        attr(
+            "max_deposits",
+            format!("{}, {}", max_deposits[0], max_deposits[1]),
+        ),
+    ]))
+}
+
+/// ## Description
+/// Removes a strategy that has no LP tokens staked in the generator. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn remove_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+
+    let generator = query_generator(&deps.querier, &config)?;
+    let staked = query_staked(
+        &deps.querier,
+        &generator,
+        &strategy.pair.liquidity_token,
+        &env.contract.address,
+    )?;
+    if !staked.is_zero() {
+        return Err(ContractError::StrategyHasPosition {});
+    }
+
+    STRATEGIES.remove(deps.storage, &pair);
+
+    Ok(Response::new().add_attributes(vec![attr("action", "remove_strategy"), attr("pair", pair)]))
+}
+
+/// ## Description
+/// Provides liquidity to the strategy's pair within its deposit limits. The minted LP tokens are staked
+/// in the generator. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+///
+/// * **assets** is an array of type [`Asset`]. These are the assets to provide.
+///
+/// ## Executor
+/// The owner or the manager can execute this.
+fn provide_liquidity(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair: String,
+    assets: [Asset; 2],
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_operator(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let mut strategy = STRATEGIES.load(deps.storage, &pair)?;
+    let assets = order_assets(&strategy.pair, assets)?;
+
+    for (i, asset) in assets.iter().enumerate() {
+        let deposited = strategy.deposited[i].amount.checked_add(asset.amount)?;
+        if deposited > strategy.max_deposits[i].amount {
+            return Err(ContractError::DepositLimitExceeded(asset.info.to_string()));
+        }
+        strategy.deposited[i].amount = deposited;
+    }
+    STRATEGIES.save(deps.storage, &pair, &strategy)?;
+
+    Ok(Response::new()
+        .add_messages(build_provide_msgs(
+            &strategy.pair,
+            &assets,
+            strategy.slippage_tolerance,
+        )?)
+        .add_attributes(vec![
+            attr("action", "provide_liquidity"),
+            attr("pair", pair),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+        ]))
+}
+
+/// ## Description
+/// Claims the generator rewards of the strategy's LP tokens. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+///
+/// ## Executor
+/// The owner or the manager can execute this.
+fn harvest(deps: DepsMut, info: MessageInfo, pair: String) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_operator(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+    let generator = query_generator(&deps.querier, &config)?;
+
+    Ok(Response::new()
+        .add_message(build_claim_rewards_msg(
+            &generator,
+            &strategy.pair.liquidity_token,
+        )?)
+        .add_attributes(vec![attr("action", "harvest"), attr("pair", pair)]))
+}
+
+/// ## Description
+/// Claims the generator rewards of the strategy's LP tokens and schedules the callbacks that swap half
+/// of them and provide the result as liquidity. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+///
+/// ## Executor
+/// The owner or the manager can execute this.
+fn compound(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_operator(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+
+    let astro = AssetInfo::Token {
+        contract_addr: config.astro_token.clone(),
+    };
+    if !strategy
+        .pair
+        .asset_infos
+        .iter()
+        .any(|info| info.equal(&astro))
+    {
+        return Err(ContractError::CannotCompound {});
+    }
+
+    let generator = query_generator(&deps.querier, &config)?;
+    let prev_astro_balance = query_token_balance(
+        &deps.querier,
+        config.astro_token,
+        env.contract.address.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_message(build_claim_rewards_msg(
+            &generator,
+            &strategy.pair.liquidity_token,
+        )?)
+        .add_message(callback_msg(
+            &env,
+            CallbackMsg::SwapRewards {
+                pair: pair.clone(),
+                prev_astro_balance,
+            },
+        )?)
+        .add_attributes(vec![attr("action", "compound"), attr("pair", pair)]))
+}
+
+/// ## Description
+/// Unstakes LP tokens from the generator and withdraws the underlying assets from the pair.
+/// The strategy's deposited amounts are reduced proportionally. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+///
+/// * **amount** is an [`Option`] field of type [`Uint128`]. This is the amount of LP tokens to unwind.
+///
+/// ## Executor
+/// The owner or the manager can execute this.
+fn unwind(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: String,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if !config.is_operator(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let mut strategy = STRATEGIES.load(deps.storage, &pair)?;
+    let lp_token = strategy.pair.liquidity_token.clone();
+
+    let generator = query_generator(&deps.querier, &config)?;
+    let staked = query_staked(&deps.querier, &generator, &lp_token, &env.contract.address)?;
+    if staked.is_zero() {
+        return Err(ContractError::NothingStaked {});
+    }
+
+    let amount = amount.unwrap_or(staked);
+    if amount.is_zero() || amount > staked {
+        return Err(ContractError::InvalidUnwindAmount {});
+    }
+
+    for deposited in strategy.deposited.iter_mut() {
+        deposited.amount = deposited
+            .amount
+            .checked_sub(deposited.amount.multiply_ratio(amount, staked))?;
+    }
+    STRATEGIES.save(deps.storage, &pair, &strategy)?;
+
+    let messages: Vec<CosmosMsg> = vec![
+        WasmMsg::Execute {
+            contract_addr: generator.to_string(),
+            msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+                lp_token: lp_token.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount,
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {})?,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    ];
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "unwind"),
+        attr("pair", pair),
+        attr("amount", amount),
+    ]))
+}
+
+/// ## Description
+/// Sends assets held by the contract to the owner. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **assets** is a vector of type [`Asset`]. These are the assets to send.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let messages = assets
+        .iter()
+        .map(|asset| asset.clone().into_msg(&deps.querier, config.owner.clone()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "withdraw"),
+        attr(
+            "assets",
+            assets
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    ]))
+}
+
+/// ## Description
+/// Updates the manager. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **manager** is an [`Option`] field of type [`String`]. This is the new manager.
+///
+/// ## Executor
+/// Only the owner can execute this.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    manager: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(manager) = manager {
+        config.manager = addr_validate_to_lower(deps.api, &manager)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Handles callbacks described in the [`CallbackMsg`]. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **msg** is an object of type [`CallbackMsg`]. This is the callback action.
+///
+/// ## Executor
+/// Callback functions can only be called by this contract.
+fn handle_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    // Callback functions can only be called by this contract
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        CallbackMsg::SwapRewards {
+            pair,
+            prev_astro_balance,
+        } => swap_rewards(deps, env, pair, prev_astro_balance),
+        CallbackMsg::ProvideRewards {
+            pair,
+            astro_amount,
+            prev_balance,
+        } => provide_rewards(deps, env, pair, astro_amount, prev_balance),
+    }
+}
+
+/// ## Description
+/// Swaps half of the harvested ASTRO to the other pair asset. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **pair** is an object of type [`Addr`]. This is the strategy's pair.
+///
+/// * **prev_astro_balance** is an object of type [`Uint128`]. This is the contract's ASTRO balance
+/// before the rewards were claimed.
+fn swap_rewards(
+    deps: DepsMut,
+    env: Env,
+    pair: Addr,
+    prev_astro_balance: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+
+    let rewards = query_token_balance(
+        &deps.querier,
+        config.astro_token.clone(),
+        env.contract.address.clone(),
+    )?
+    .checked_sub(prev_astro_balance)?;
+    if rewards.is_zero() {
+        return Err(ContractError::NothingToCompound {});
+    }
+
+    let swap_amount = rewards.multiply_ratio(1u128, 2u128);
+    let astro = AssetInfo::Token {
+        contract_addr: config.astro_token.clone(),
+    };
+    let other_info = strategy
+        .pair
+        .asset_infos
+        .iter()
+        .find(|info| !info.equal(&astro))
+        .cloned()
+        .ok_or(ContractError::CannotCompound {})?;
+    let prev_balance = Asset {
+        amount: other_info.query_pool(&deps.querier, env.contract.address.clone())?,
+        info: other_info,
+    };
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: config.astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount: swap_amount,
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: Some(strategy.max_spread),
+                    to: None,
+                    referral_code: None,
+                })?,
+            })?,
+            funds: vec![],
+        })
+        .add_message(callback_msg(
+            &env,
+            CallbackMsg::ProvideRewards {
+                pair,
+                astro_amount: rewards.checked_sub(swap_amount)?,
+                prev_balance,
+            },
+        )?)
+        .add_attributes(vec![
+            attr("action", "swap_rewards"),
+            attr("rewards", rewards),
+            attr("swap_amount", swap_amount),
+        ]))
+}
+
+/// ## Description
+/// Provides the remaining harvested ASTRO and the swapped asset as liquidity. Compounded rewards are not
+/// counted against the strategy's deposit limits. Returns a [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **pair** is an object of type [`Addr`]. This is the strategy's pair.
+///
+/// * **astro_amount** is an object of type [`Uint128`]. This is the amount of harvested ASTRO that wasn't swapped.
+///
+/// * **prev_balance** is an object of type [`Asset`]. This is the contract's balance of the other pair asset
+/// before the swap.
+fn provide_rewards(
+    deps: DepsMut,
+    env: Env,
+    pair: Addr,
+    astro_amount: Uint128,
+    prev_balance: Asset,
+) -> Result<Response, ContractError> {
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+
+    let received = prev_balance
+        .info
+        .query_pool(&deps.querier, env.contract.address)?
+        .checked_sub(prev_balance.amount)?;
+
+    let amount_for = |info: &AssetInfo| {
+        if info.equal(&prev_balance.info) {
+            received
+        } else {
+            astro_amount
+        }
+    };
+    let assets = [
+        Asset {
+            info: strategy.pair.asset_infos[0].clone(),
+            amount: amount_for(&strategy.pair.asset_infos[0]),
+        },
+        Asset {
+            info: strategy.pair.asset_infos[1].clone(),
+            amount: amount_for(&strategy.pair.asset_infos[1]),
+        },
+    ];
+
+    Ok(Response::new()
+        .add_messages(build_provide_msgs(
+            &strategy.pair,
+            &assets,
+            strategy.slippage_tolerance,
+        )?)
+        .add_attributes(vec![
+            attr("action", "provide_rewards"),
+            attr("pair", pair),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+        ]))
+}
+
+/// ## Description
+/// Reorders the assets to match the pair's asset order. Returns a [`ContractError`] if the assets
+/// don't match the pair assets.
+/// ## Params
+/// * **pair** is an object of type [`PairInfo`].
+///
+/// * **assets** is an array of type [`Asset`]. These are the assets to reorder.
+fn order_assets(pair: &PairInfo, assets: [Asset; 2]) -> Result<[Asset; 2], ContractError> {
+    if assets[0].info.equal(&pair.asset_infos[0]) && assets[1].info.equal(&pair.asset_infos[1]) {
+        Ok(assets)
+    } else if assets[0].info.equal(&pair.asset_infos[1])
+        && assets[1].info.equal(&pair.asset_infos[0])
+    {
+        let [first, second] = assets;
+        Ok([second, first])
+    } else {
+        Err(ContractError::InvalidAssets {})
+    }
+}
+
+/// ## Description
+/// Builds the messages that provide liquidity to a pair and stake the minted LP tokens in the generator.
+/// ## Params
+/// * **pair** is an object of type [`PairInfo`].
+///
+/// * **assets** is an array of type [`Asset`]. These are the assets to provide in the pair's asset order.
+///
+/// * **slippage_tolerance** is an object of type [`Decimal`].
+fn build_provide_msgs(
+    pair: &PairInfo,
+    assets: &[Asset; 2],
+    slippage_tolerance: Decimal,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds = vec![];
+
+    for asset in assets.iter() {
+        match &asset.info {
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair.contract_addr.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
+                denom: denom.clone(),
+                amount: asset.amount,
+            }),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: pair.contract_addr.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: assets.clone(),
+                slippage_tolerance: Some(slippage_tolerance),
+                auto_stake: Some(true),
+                receiver: None,
+            })?,
+            funds,
+        }
+        .into(),
+    );
+
+    Ok(messages)
+}
+
+/// ## Description
+/// Builds a message that claims the generator rewards of an LP token.
+/// ## Params
+/// * **generator** is an object of type [`Addr`].
+///
+/// * **lp_token** is an object of type [`Addr`].
+fn build_claim_rewards_msg(generator: &Addr, lp_token: &Addr) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: generator.to_string(),
+        msg: to_binary(&GeneratorExecuteMsg::ClaimRewards {
+            lp_tokens: vec![lp_token.to_string()],
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Builds a message that executes a callback in this contract.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`CallbackMsg`].
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Returns the generator set in the factory. Returns a [`ContractError`] if it isn't set.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **config** is an object of type [`Config`].
+fn query_generator(querier: &QuerierWrapper, config: &Config) -> Result<Addr, ContractError> {
+    query_factory_config(querier, config.factory.clone())?
+        .generator_address
+        .ok_or(ContractError::GeneratorNotSet {})
+}
+
+/// ## Description
+/// Returns the amount of LP tokens the contract has staked in the generator.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **generator** is an object of type [`Addr`].
+///
+/// * **lp_token** is an object of type [`Addr`].
+///
+/// * **contract_addr** is an object of type [`Addr`]. This is the address of this contract.
+fn query_staked(
+    querier: &QuerierWrapper,
+    generator: &Addr,
+    lp_token: &Addr,
+    contract_addr: &Addr,
+) -> StdResult<Uint128> {
+    querier.query_wasm_smart(
+        generator,
+        &GeneratorQueryMsg::Deposit {
+            lp_token: lp_token.to_string(),
+            user: contract_addr.to_string(),
+        },
+    )
+}
+
+/// ## Description
+/// Exposes all the queries available in the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration using a [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Strategy { pair }** Returns a strategy using a [`StrategyInfo`] object.
+///
+/// * **QueryMsg::Strategies { start_after, limit }** Returns strategies using a vector of [`StrategyInfo`] objects.
+///
+/// * **QueryMsg::Position { pair }** Returns the strategy's position using a [`PositionResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&ConfigResponse {
+                owner: config.owner,
+                manager: config.manager,
+                factory: config.factory,
+                astro_token: config.astro_token,
+            })
+        }
+        QueryMsg::Strategy { pair } => {
+            let pair = addr_validate_to_lower(deps.api, &pair)?;
+            to_binary(&STRATEGIES.load(deps.storage, &pair)?)
+        }
+        QueryMsg::Strategies { start_after, limit } => {
+            to_binary(&query_strategies(deps, start_after, limit)?)
+        }
+        QueryMsg::Position { pair } => to_binary(&query_position(deps, env, pair)?),
+    }
+}
+
+/// ## Description
+/// Returns strategies ordered by their pair addresses.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the pair after which to start reading.
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of strategies to return.
+fn query_strategies(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<StrategyInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|pair| addr_validate_to_lower(deps.api, &pair))
+        .transpose()?
+        .map(|pair| Bound::exclusive(pair.to_string()));
+
+    STRATEGIES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// ## Description
+/// Returns the strategy's staked LP tokens and pending ASTRO rewards in the generator.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **pair** is an object of type [`String`]. This is the strategy's pair.
+fn query_position(deps: Deps, env: Env, pair: String) -> StdResult<PositionResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pair = addr_validate_to_lower(deps.api, &pair)?;
+    let strategy = STRATEGIES.load(deps.storage, &pair)?;
+    let lp_token = strategy.pair.liquidity_token;
+
+    let generator = query_generator(&deps.querier, &config)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let lp_staked = query_staked(&deps.querier, &generator, &lp_token, &env.contract.address)?;
+    let pending: PendingTokenResponse = deps.querier.query_wasm_smart(
+        &generator,
+        &GeneratorQueryMsg::PendingToken {
+            lp_token: lp_token.to_string(),
+            user: env.contract.address.to_string(),
+        },
+    )?;
+
+    Ok(PositionResponse {
+        lp_staked,
+        pending_astro: pending.pending,
+    })
+}
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -0,0 +1,46 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes POL manager contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The pair is not registered in the factory")]
+    PairNotRegistered {},
+
+    #[error("Assets must match the pair assets")]
+    InvalidAssets {},
+
+    #[error("Deposit limit exceeded for {0}")]
+    DepositLimitExceeded(String),
+
+    #[error("The factory has no generator set")]
+    GeneratorNotSet {},
+
+    #[error("The strategy has no LP tokens staked in the generator")]
+    NothingStaked {},
+
+    #[error("Invalid amount of LP tokens to unwind")]
+    InvalidUnwindAmount {},
+
+    #[error("The strategy still has LP tokens staked in the generator")]
+    StrategyHasPosition {},
+
+    #[error("Only pairs with ASTRO can be compounded")]
+    CannotCompound {},
+
+    #[error("There are no rewards to compound")]
+    NothingToCompound {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
@@ -0,0 +1,161 @@
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::{
+    ConfigResponse as FactoryConfigResponse, PairType, QueryMsg as FactoryQueryMsg,
+};
+use astroport::generator::{PendingTokenResponse, QueryMsg as GeneratorQueryMsg};
+use astroport::pair::QueryMsg as PairQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use std::collections::HashMap;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    // pairs keyed by their address
+    pairs: HashMap<String, PairInfo>,
+    // LP tokens staked in the generator keyed by LP token
+    staked: HashMap<String, Uint128>,
+    // token balances keyed by token address and holder
+    balances: HashMap<(String, String), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match contract_addr.as_str() {
+                    "factory" => match from_binary(msg).unwrap() {
+                        FactoryQueryMsg::Config {} => SystemResult::Ok(
+                            to_binary(&FactoryConfigResponse {
+                                owner: Addr::unchecked("owner"),
+                                pair_configs: vec![],
+                                token_code_id: 0,
+                                fee_address: None,
+                                generator_address: Some(Addr::unchecked("generator")),
+                                whitelist_code_id: 0,
+                                referral_registry: None,
+                                emergency_admin: None,
+                                pairs_count: vec![],
+                            })
+                            .into(),
+                        ),
+                        FactoryQueryMsg::Pair { asset_infos } => {
+                            let pair = self.pairs.values().find(|pair| {
+                                pair.asset_infos[0].equal(&asset_infos[0])
+                                    && pair.asset_infos[1].equal(&asset_infos[1])
+                            });
+                            match pair {
+                                Some(pair) => SystemResult::Ok(to_binary(pair).into()),
+                                None => SystemResult::Err(SystemError::NoSuchContract {
+                                    addr: String::from("pair"),
+                                }),
+                            }
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    },
+                    "generator" => match from_binary(msg).unwrap() {
+                        GeneratorQueryMsg::Deposit { lp_token, .. } => SystemResult::Ok(
+                            to_binary(&self.staked.get(&lp_token).cloned().unwrap_or_default())
+                                .into(),
+                        ),
+                        GeneratorQueryMsg::PendingToken { .. } => SystemResult::Ok(
+                            to_binary(&PendingTokenResponse {
+                                pending: Uint128::zero(),
+                                pending_on_proxy: None,
+                            })
+                            .into(),
+                        ),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    },
+                    _ => {
+                        if let Some(pair) = self.pairs.get(contract_addr) {
+                            match from_binary(msg).unwrap() {
+                                PairQueryMsg::Pair {} => SystemResult::Ok(to_binary(pair).into()),
+                                _ => panic!("DO NOT ENTER HERE"),
+                            }
+                        } else {
+                            match from_binary(msg).unwrap() {
+                                Cw20QueryMsg::Balance { address } => {
+                                    let balance = self
+                                        .balances
+                                        .get(&(contract_addr.clone(), address))
+                                        .cloned()
+                                        .unwrap_or_default();
+                                    SystemResult::Ok(to_binary(&BalanceResponse { balance }).into())
+                                }
+                                _ => panic!("DO NOT ENTER HERE"),
+                            }
+                        }
+                    }
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            pairs: HashMap::new(),
+            staked: HashMap::new(),
+            balances: HashMap::new(),
+        }
+    }
+
+    pub fn add_pair(&mut self, pair: &str, asset_infos: [AssetInfo; 2], lp_token: &str) {
+        self.pairs.insert(
+            pair.to_string(),
+            PairInfo {
+                asset_infos,
+                contract_addr: Addr::unchecked(pair),
+                liquidity_token: Addr::unchecked(lp_token),
+                pair_type: PairType::Xyk {},
+            },
+        );
+    }
+
+    pub fn set_staked(&mut self, lp_token: &str, amount: u128) {
+        self.staked
+            .insert(lp_token.to_string(), Uint128::from(amount));
+    }
+
+    pub fn set_token_balance(&mut self, token: &str, holder: &str, balance: u128) {
+        self.balances.insert(
+            (token.to_string(), holder.to_string()),
+            Uint128::from(balance),
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use astroport::common::OwnershipProposal;
+use astroport::pol_manager::StrategyInfo;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure stores the main parameters for the POL manager contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Address that's allowed to approve strategies and withdraw assets (the Assembly)
+    pub owner: Addr,
+    /// Address that's allowed to run approved strategies
+    pub manager: Addr,
+    /// The factory contract address
+    pub factory: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+}
+
+impl Config {
+    /// Returns true if the address can run approved strategies.
+    pub fn is_operator(&self, addr: &Addr) -> bool {
+        *addr == self.owner || *addr == self.manager
+    }
+}
+
+/// ## Description
+/// Stores the contract config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores approved strategies keyed by their pair address
+pub const STRATEGIES: Map<&Addr, StrategyInfo> = Map::new("strategies");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
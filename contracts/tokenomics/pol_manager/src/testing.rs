@@ -0,0 +1,424 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, OwnedDeps, Response, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use astroport::asset::{Asset, AssetInfo};
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use astroport::pol_manager::{CallbackMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StrategyInfo};
+
+type PolDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn token_info(contract_addr: &str) -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    }
+}
+
+fn token_asset(contract_addr: &str, amount: u128) -> Asset {
+    Asset {
+        info: token_info(contract_addr),
+        amount: Uint128::from(amount),
+    }
+}
+
+fn wasm_msg(contract_addr: &str, msg: &impl serde::Serialize) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn messages(res: Response) -> Vec<CosmosMsg> {
+    res.messages.into_iter().map(|msg| msg.msg).collect()
+}
+
+fn setup() -> PolDeps {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_pair(
+        "astro_pair",
+        [token_info("astro"), token_info("usdc")],
+        "astro_lp",
+    );
+    deps.querier.add_pair(
+        "other_pair",
+        [token_info("usdc"), token_info("usdt")],
+        "other_lp",
+    );
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: String::from("assembly"),
+            manager: String::from("manager"),
+            factory: String::from("factory"),
+            astro_token: String::from("astro"),
+        },
+    )
+    .unwrap();
+
+    for pair in ["astro_pair", "other_pair"] {
+        let (first, second) = if pair == "astro_pair" {
+            ("usdc", "astro")
+        } else {
+            ("usdt", "usdc")
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("assembly", &[]),
+            ExecuteMsg::SetStrategy {
+                pair: pair.to_string(),
+                max_deposits: [token_asset(first, 1000), token_asset(second, 2000)],
+                slippage_tolerance: Decimal::percent(1),
+                max_spread: Decimal::percent(2),
+            },
+        )
+        .unwrap();
+    }
+
+    deps
+}
+
+fn query_strategy(deps: &PolDeps, pair: &str) -> StrategyInfo {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Strategy {
+                pair: pair.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn provide_msgs(pair: &str, assets: [Asset; 2]) -> Vec<CosmosMsg> {
+    let mut msgs: Vec<CosmosMsg> = assets
+        .iter()
+        .map(|asset| {
+            wasm_msg(
+                &asset.info.to_string(),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair.to_string(),
+                    amount: asset.amount,
+                    expires: None,
+                },
+            )
+        })
+        .collect();
+    msgs.push(wasm_msg(
+        pair,
+        &PairExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance: Some(Decimal::percent(1)),
+            auto_stake: Some(true),
+            receiver: None,
+        },
+    ));
+    msgs
+}
+
+#[test]
+fn provide_and_unwind() {
+    let mut deps = setup();
+
+    // Deposit limits are stored in the pair's asset order
+    let strategy = query_strategy(&deps, "astro_pair");
+    assert_eq!(
+        strategy.max_deposits,
+        [token_asset("astro", 2000), token_asset("usdc", 1000)]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        ExecuteMsg::SetStrategy {
+            pair: String::from("astro_pair"),
+            max_deposits: [token_asset("astro", 1), token_asset("usdc", 1)],
+            slippage_tolerance: Decimal::percent(1),
+            max_spread: Decimal::percent(2),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let provide = |astro, usdc| ExecuteMsg::ProvideLiquidity {
+        pair: String::from("astro_pair"),
+        assets: [token_asset("usdc", usdc), token_asset("astro", astro)],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        provide(100, 50),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        ExecuteMsg::ProvideLiquidity {
+            pair: String::from("astro_pair"),
+            assets: [token_asset("usdt", 100), token_asset("astro", 100)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidAssets {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        provide(1500, 800),
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res),
+        provide_msgs(
+            "astro_pair",
+            [token_asset("astro", 1500), token_asset("usdc", 800)]
+        )
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        provide(100, 201),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DepositLimitExceeded(String::from("usdc"))
+    );
+
+    // Unwinding
+    let unwind = |amount| ExecuteMsg::Unwind {
+        pair: String::from("astro_pair"),
+        amount,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        unwind(None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingStaked {});
+
+    deps.querier.set_staked("astro_lp", 400);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        unwind(Some(Uint128::from(401u128))),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnwindAmount {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("assembly", &[]),
+        ExecuteMsg::RemoveStrategy {
+            pair: String::from("astro_pair"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StrategyHasPosition {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        unwind(Some(Uint128::from(100u128))),
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "generator",
+                &GeneratorExecuteMsg::Withdraw {
+                    lp_token: String::from("astro_lp"),
+                    amount: Uint128::from(100u128),
+                }
+            ),
+            wasm_msg(
+                "astro_lp",
+                &Cw20ExecuteMsg::Send {
+                    contract: String::from("astro_pair"),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+                }
+            ),
+        ]
+    );
+
+    // A quarter of the position was unwound
+    let strategy = query_strategy(&deps, "astro_pair");
+    assert_eq!(
+        strategy.deposited,
+        [token_asset("astro", 1125), token_asset("usdc", 600)]
+    );
+
+    // Unwound assets can be sent back to the treasury
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("assembly", &[]),
+        ExecuteMsg::Withdraw {
+            assets: vec![token_asset("usdc", 150)],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res),
+        vec![wasm_msg(
+            "usdc",
+            &Cw20ExecuteMsg::Transfer {
+                recipient: String::from("assembly"),
+                amount: Uint128::from(150u128),
+            }
+        )]
+    );
+}
+
+#[test]
+fn compound() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        ExecuteMsg::Compound {
+            pair: String::from("other_pair"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CannotCompound {});
+
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 30);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        ExecuteMsg::Compound {
+            pair: String::from("astro_pair"),
+        },
+    )
+    .unwrap();
+    let swap_rewards = ExecuteMsg::Callback(CallbackMsg::SwapRewards {
+        pair: Addr::unchecked("astro_pair"),
+        prev_astro_balance: Uint128::from(30u128),
+    });
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "generator",
+                &GeneratorExecuteMsg::ClaimRewards {
+                    lp_tokens: vec![String::from("astro_lp")],
+                }
+            ),
+            wasm_msg(MOCK_CONTRACT_ADDR, &swap_rewards),
+        ]
+    );
+
+    // Callbacks can only be called by the contract itself
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        swap_rewards.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        swap_rewards.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingToCompound {});
+
+    // The generator paid 101 ASTRO
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 131);
+    deps.querier
+        .set_token_balance("usdc", MOCK_CONTRACT_ADDR, 7);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        swap_rewards,
+    )
+    .unwrap();
+    let provide_rewards = ExecuteMsg::Callback(CallbackMsg::ProvideRewards {
+        pair: Addr::unchecked("astro_pair"),
+        astro_amount: Uint128::from(51u128),
+        prev_balance: token_asset("usdc", 7),
+    });
+    assert_eq!(
+        messages(res),
+        vec![
+            wasm_msg(
+                "astro",
+                &Cw20ExecuteMsg::Send {
+                    contract: String::from("astro_pair"),
+                    amount: Uint128::from(50u128),
+                    msg: to_binary(&PairCw20HookMsg::Swap {
+                        belief_price: None,
+                        max_spread: Some(Decimal::percent(2)),
+                        to: None,
+                        referral_code: None,
+                    })
+                    .unwrap(),
+                }
+            ),
+            wasm_msg(MOCK_CONTRACT_ADDR, &provide_rewards),
+        ]
+    );
+
+    // The swap returned 24 USDC
+    deps.querier
+        .set_token_balance("usdc", MOCK_CONTRACT_ADDR, 31);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        provide_rewards,
+    )
+    .unwrap();
+    assert_eq!(
+        messages(res),
+        provide_msgs(
+            "astro_pair",
+            [token_asset("astro", 51), token_asset("usdc", 24)]
+        )
+    );
+
+    // Compounded rewards don't count against the deposit limits
+    let strategy = query_strategy(&deps, "astro_pair");
+    assert_eq!(
+        strategy.deposited,
+        [token_asset("astro", 0), token_asset("usdc", 0)]
+    );
+}
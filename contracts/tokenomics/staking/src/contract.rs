@@ -1,11 +1,18 @@
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    coins, entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult, Storage,
+    SubMsg, Uint128, WasmMsg,
 };
+use cw_storage_plus::{Bound, U64Key};
 
 use crate::error::ContractError;
-use crate::state::{Config, CONFIG};
-use astroport::staking::{ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{Config, CONFIG, EXCHANGE_RATES, TOTAL_FEES};
+use astroport::common::assert_migration_allowed;
+use astroport::querier::query_balance;
+use astroport::staking::{
+    ConfigResponse, Cw20HookMsg, ExchangeRateCheckpoint, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg, EXCHANGE_RATE_CHECKPOINT_INTERVAL, MINIMUM_STAKE_AMOUNT,
+};
 use cw2::set_contract_version;
 use cw20::{
     BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse,
@@ -14,6 +21,7 @@ use cw20::{
 
 use crate::response::MsgInstantiateContractResponse;
 use astroport::asset::addr_validate_to_lower;
+use astroport::attr_builder::AttrBuilder;
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use protobuf::Message;
 
@@ -56,6 +64,7 @@ pub fn instantiate(
         &Config {
             astro_token_addr: addr_validate_to_lower(deps.api, &msg.deposit_token_addr)?,
             xastro_token_addr: Addr::unchecked(""),
+            native_astro: None,
         },
     )?;
 
@@ -73,6 +82,7 @@ pub fn instantiate(
                     minter: env.contract.address.to_string(),
                     cap: None,
                 }),
+                marketing: None,
             })?,
             funds: vec![],
             label: String::from("Staked Astroport Token"),
@@ -100,6 +110,10 @@ pub fn instantiate(
 /// ## Queries
 /// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
 /// it depending on the received template.
+///
+/// * **ExecuteMsg::Enter {}** Deposits native ASTRO in exchange for xASTRO.
+///
+/// * **ExecuteMsg::DistributeFees {}** Adds native ASTRO to the staking pool as fees.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -109,6 +123,8 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Enter {} => enter_native(deps, env, info),
+        ExecuteMsg::DistributeFees {} => distribute_native_fees(deps, env, info),
     }
 }
 
@@ -165,7 +181,7 @@ fn receive_cw20(
     let recipient = cw20_msg.sender;
     let amount = cw20_msg.amount;
 
-    let mut total_deposit = get_total_deposit(deps.as_ref(), env, config.clone())?;
+    let mut total_deposit = get_total_deposit(deps.as_ref(), env.clone(), config.clone())?;
     let total_shares = get_total_shares(deps.as_ref(), config.clone())?;
 
     match from_binary(&cw20_msg.msg)? {
@@ -176,30 +192,36 @@ fn receive_cw20(
             // In a CW20 `send`, the total balance of the recipient is already increased.
             // To properly calculate the total amount of ASTRO deposited in staking, we should subtract the user deposit from the pool
             total_deposit -= amount;
-            let mint_amount: Uint128 = if total_shares.is_zero() || total_deposit.is_zero() {
-                amount
-            } else {
-                amount
-                    .checked_mul(total_shares)?
-                    .checked_div(total_deposit)
-                    .map_err(|e| StdError::DivideByZero { source: e })?
-            };
+            checkpoint_exchange_rate(deps.storage, &env, total_deposit, total_shares)?;
 
-            let res = Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.xastro_token_addr.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Mint {
-                    recipient,
-                    amount: mint_amount,
-                })?,
-                funds: vec![],
-            }));
+            let mut messages = vec![];
+            // CW20 ASTRO deposited after the switch to native ASTRO is converted right away
+            if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+                messages.push(convert_msg);
+            }
+            messages.extend(mint_shares_msgs(
+                &env,
+                &config,
+                recipient.clone(),
+                amount,
+                total_deposit,
+                total_shares,
+            )?);
+
+            let attributes = AttrBuilder::new("enter")
+                .attr("recipient", recipient)
+                .attr("amount", amount);
 
-            Ok(res)
+            Ok(Response::new()
+                .add_messages(messages)
+                .add_event(attributes.event("staking"))
+                .add_attributes(attributes.build()))
         }
         Cw20HookMsg::Leave {} => {
             if info.sender != config.xastro_token_addr {
                 return Err(ContractError::Unauthorized {});
             }
+            checkpoint_exchange_rate(deps.storage, &env, total_deposit, total_shares)?;
 
             let what = amount
                 .checked_mul(total_deposit)?
@@ -207,24 +229,397 @@ fn receive_cw20(
                 .map_err(|e| StdError::DivideByZero { source: e })?;
 
             // Burn share
-            let res = Response::new()
-                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: config.xastro_token_addr.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
-                    funds: vec![],
-                }))
-                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: config.astro_token_addr.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                        recipient,
-                        amount: what,
-                    })?,
-                    funds: vec![],
-                }));
-
-            Ok(res)
+            let mut res = Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.xastro_token_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+                funds: vec![],
+            }));
+
+            match &config.native_astro {
+                Some(native_astro) => {
+                    // Make sure all the ASTRO in the pool is native before paying it out
+                    if let Some(convert_msg) =
+                        convert_cw20_balance_msg(deps.as_ref(), &env, &config)?
+                    {
+                        res = res.add_message(convert_msg);
+                    }
+
+                    res = res.add_message(BankMsg::Send {
+                        to_address: recipient.clone(),
+                        amount: coins(what.u128(), &native_astro.denom),
+                    });
+                }
+                None => {
+                    res = res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: config.astro_token_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient: recipient.clone(),
+                            amount: what,
+                        })?,
+                        funds: vec![],
+                    }));
+                }
+            }
+
+            let attributes = AttrBuilder::new("leave")
+                .attr("recipient", recipient)
+                .attr("xastro_amount", amount)
+                .attr("amount", what);
+
+            Ok(res
+                .add_event(attributes.event("staking"))
+                .add_attributes(attributes.build()))
+        }
+        Cw20HookMsg::DistributeFees {} => {
+            if info.sender != config.astro_token_addr {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let mut res = Response::new();
+            // CW20 ASTRO distributed after the switch to native ASTRO is converted right away
+            if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+                res = res.add_message(convert_msg);
+            }
+
+            // The contract balance already includes the fees
+            record_fee_inflow(deps.storage, &env, amount, total_deposit, total_shares)?;
+
+            let attributes = AttrBuilder::new("distribute_fees")
+                .attr("sender", recipient)
+                .attr("amount", amount);
+
+            Ok(res
+                .add_event(attributes.event("staking"))
+                .add_attributes(attributes.build()))
+        }
+    }
+}
+
+/// ## Description
+/// Deposits the native ASTRO sent along with the message in exchange for xASTRO.
+/// Returns a [`ContractError`] if the contract hasn't been switched to native ASTRO or the funds are invalid.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+fn enter_native(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let amount = native_astro_amount(&config, &info)?;
+
+    // The contract balance already includes the deposit, so we subtract it from the pool
+    let total_deposit =
+        get_total_deposit(deps.as_ref(), env.clone(), config.clone())?.checked_sub(amount)?;
+    let total_shares = get_total_shares(deps.as_ref(), config.clone())?;
+    checkpoint_exchange_rate(deps.storage, &env, total_deposit, total_shares)?;
+
+    let mut messages = vec![];
+    if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+        messages.push(convert_msg);
+    }
+    messages.extend(mint_shares_msgs(
+        &env,
+        &config,
+        info.sender.to_string(),
+        amount,
+        total_deposit,
+        total_shares,
+    )?);
+
+    let attributes = AttrBuilder::new("enter")
+        .attr("recipient", info.sender)
+        .attr("amount", amount);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_event(attributes.event("staking"))
+        .add_attributes(attributes.build()))
+}
+
+/// ## Description
+/// Adds the native ASTRO sent along with the message to the staking pool as fees without minting xASTRO.
+/// Returns a [`ContractError`] if the contract hasn't been switched to native ASTRO or the funds are invalid.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+fn distribute_native_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let amount = native_astro_amount(&config, &info)?;
+
+    // The contract balance already includes the fees
+    let total_deposit = get_total_deposit(deps.as_ref(), env.clone(), config.clone())?;
+    let total_shares = get_total_shares(deps.as_ref(), config.clone())?;
+    record_fee_inflow(deps.storage, &env, amount, total_deposit, total_shares)?;
+
+    let mut res = Response::new();
+    if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+        res = res.add_message(convert_msg);
+    }
+
+    let attributes = AttrBuilder::new("distribute_fees")
+        .attr("sender", info.sender)
+        .attr("amount", amount);
+
+    Ok(res
+        .add_event(attributes.event("staking"))
+        .add_attributes(attributes.build()))
+}
+
+/// ## Description
+/// Returns the amount of native ASTRO sent along with the message. Returns a [`ContractError`] if the contract
+/// hasn't been switched to native ASTRO or anything else than a single non-zero native ASTRO coin was sent.
+/// ## Params
+/// * **config** is an object of type [`Config`]. This is the staking contract configuration.
+///
+/// * **info** is an object of type [`MessageInfo`].
+fn native_astro_amount(config: &Config, info: &MessageInfo) -> Result<Uint128, ContractError> {
+    let native_astro = config
+        .native_astro
+        .as_ref()
+        .ok_or(ContractError::NativeAstroDisabled {})?;
+
+    match info.funds.as_slice() {
+        [coin] if coin.denom == native_astro.denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds {}),
+    }
+}
+
+/// ## Description
+/// Returns the messages that mint xASTRO for a deposit of ASTRO.
+/// On the first deposit, [`MINIMUM_STAKE_AMOUNT`] xASTRO are minted to the staking contract itself and locked forever,
+/// so the share price can't be inflated by donating ASTRO to an almost empty pool.
+/// Returns a [`ContractError`] if the first deposit is not bigger than [`MINIMUM_STAKE_AMOUNT`] or the deposit would mint no xASTRO.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`]. This is the staking contract configuration.
+///
+/// * **recipient** is an object of type [`String`]. This is the address that receives xASTRO.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of ASTRO deposited.
+///
+/// * **total_deposit** is an object of type [`Uint128`]. This is the amount of ASTRO in the pool before the deposit.
+///
+/// * **total_shares** is an object of type [`Uint128`]. This is the total xASTRO supply.
+fn mint_shares_msgs(
+    env: &Env,
+    config: &Config,
+    recipient: String,
+    amount: Uint128,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages = vec![];
+
+    let mint_amount: Uint128 = if total_shares.is_zero() {
+        if amount <= MINIMUM_STAKE_AMOUNT {
+            return Err(ContractError::MinimumStakeAmountError {
+                min_stake: MINIMUM_STAKE_AMOUNT,
+            });
+        }
+
+        // Lock the minimum amount of xASTRO in the contract forever
+        messages.push(mint_msg(
+            config,
+            env.contract.address.to_string(),
+            MINIMUM_STAKE_AMOUNT,
+        )?);
+
+        amount.checked_sub(MINIMUM_STAKE_AMOUNT)?
+    } else if total_deposit.is_zero() {
+        amount
+    } else {
+        amount
+            .checked_mul(total_shares)?
+            .checked_div(total_deposit)
+            .map_err(|e| StdError::DivideByZero { source: e })?
+    };
+
+    if mint_amount.is_zero() {
+        return Err(ContractError::StakeAmountTooSmall {});
+    }
+
+    messages.push(mint_msg(config, recipient, mint_amount)?);
+
+    Ok(messages)
+}
+
+/// ## Description
+/// Saves the current xASTRO exchange rate if no checkpoint was taken in the last
+/// [`EXCHANGE_RATE_CHECKPOINT_INTERVAL`] seconds. Nothing is saved while no xASTRO is issued.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **total_deposit** is an object of type [`Uint128`]. This is the amount of ASTRO in the pool before the deposit or withdrawal.
+///
+/// * **total_shares** is an object of type [`Uint128`]. This is the total xASTRO supply.
+fn checkpoint_exchange_rate(
+    storage: &mut dyn Storage,
+    env: &Env,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+) -> StdResult<()> {
+    if total_shares.is_zero() {
+        return Ok(());
+    }
+
+    let timestamp = env.block.time.seconds();
+    if let Some(last) = last_exchange_rate(storage, timestamp)? {
+        if timestamp < last.timestamp + EXCHANGE_RATE_CHECKPOINT_INTERVAL {
+            return Ok(());
         }
     }
+
+    save_exchange_rate(storage, timestamp, total_deposit, total_shares)
+}
+
+/// ## Description
+/// Adds a fee inflow to the total fees distributed to stakers and saves the exchange rate including the fees,
+/// regardless of when the last checkpoint was taken. Replaces an earlier checkpoint from the same block,
+/// so the exchange rate history reflects the fees in the block they were received in.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of ASTRO received as fees.
+///
+/// * **total_deposit** is an object of type [`Uint128`]. This is the amount of ASTRO in the pool including the fees.
+///
+/// * **total_shares** is an object of type [`Uint128`]. This is the total xASTRO supply.
+fn record_fee_inflow(
+    storage: &mut dyn Storage,
+    env: &Env,
+    amount: Uint128,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+) -> StdResult<()> {
+    let total_fees = TOTAL_FEES
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    TOTAL_FEES.save(storage, &total_fees)?;
+
+    if total_shares.is_zero() {
+        return Ok(());
+    }
+
+    save_exchange_rate(
+        storage,
+        env.block.time.seconds(),
+        total_deposit,
+        total_shares,
+    )
+}
+
+/// ## Description
+/// Saves an xASTRO exchange rate checkpoint taken at the specified timestamp.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **timestamp** is an object of type [`u64`]. This is the timestamp (in seconds) of the checkpoint.
+///
+/// * **total_deposit** is an object of type [`Uint128`]. This is the amount of ASTRO in the pool.
+///
+/// * **total_shares** is an object of type [`Uint128`]. This is the total xASTRO supply.
+fn save_exchange_rate(
+    storage: &mut dyn Storage,
+    timestamp: u64,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+) -> StdResult<()> {
+    let total_fees = TOTAL_FEES.may_load(storage)?.unwrap_or_default();
+
+    EXCHANGE_RATES.save(
+        storage,
+        U64Key::new(timestamp),
+        &ExchangeRateCheckpoint {
+            timestamp,
+            total_deposit,
+            total_shares,
+            exchange_rate: Decimal::from_ratio(total_deposit, total_shares),
+            total_fees,
+        },
+    )
+}
+
+/// ## Description
+/// Returns the latest xASTRO exchange rate checkpoint taken at or before the specified timestamp.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **timestamp** is an object of type [`u64`]. This is the timestamp (in seconds) at which we read the exchange rate.
+fn last_exchange_rate(
+    storage: &dyn Storage,
+    timestamp: u64,
+) -> StdResult<Option<ExchangeRateCheckpoint>> {
+    EXCHANGE_RATES
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(U64Key::new(timestamp))),
+            Order::Descending,
+        )
+        .next()
+        .map(|item| item.map(|(_, checkpoint)| checkpoint))
+        .transpose()
+}
+
+/// ## Description
+/// Returns a message that mints the specified amount of xASTRO.
+/// ## Params
+/// * **config** is an object of type [`Config`]. This is the staking contract configuration.
+///
+/// * **recipient** is an object of type [`String`]. This is the address that receives xASTRO.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of xASTRO to mint.
+fn mint_msg(config: &Config, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.xastro_token_addr.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint { recipient, amount })?,
+        funds: vec![],
+    }))
+}
+
+/// ## Description
+/// Returns a message that converts all the CW20 ASTRO held by the contract to native ASTRO.
+/// Returns [`None`] if the contract hasn't been switched to native ASTRO or holds no CW20 ASTRO.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`]. This is the staking contract configuration.
+fn convert_cw20_balance_msg(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> StdResult<Option<CosmosMsg>> {
+    match &config.native_astro {
+        Some(native_astro) => {
+            let balance = query_cw20_astro_balance(deps, env, config)?;
+            if balance.is_zero() {
+                Ok(None)
+            } else {
+                native_astro
+                    .convert_msg(&config.astro_token_addr, balance)
+                    .map(Some)
+            }
+        }
+        None => Ok(None),
+    }
 }
 
 /// ## Description
@@ -242,7 +637,8 @@ pub fn get_total_shares(deps: Deps, config: Config) -> StdResult<Uint128> {
 }
 
 /// ## Description
-/// Returns the total amount of ASTRO deposited in the contract.
+/// Returns the total amount of ASTRO deposited in the contract. Once the contract has been switched
+/// to native ASTRO, this includes both native and CW20 ASTRO.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 ///
@@ -250,6 +646,30 @@ pub fn get_total_shares(deps: Deps, config: Config) -> StdResult<Uint128> {
 ///
 /// * **config** is an object of type [`Config`]. This is the staking contract configuration.
 pub fn get_total_deposit(deps: Deps, env: Env, config: Config) -> StdResult<Uint128> {
+    let cw20_balance = query_cw20_astro_balance(deps, &env, &config)?;
+
+    match &config.native_astro {
+        Some(native_astro) => {
+            let native_balance = query_balance(
+                &deps.querier,
+                env.contract.address,
+                native_astro.denom.clone(),
+            )?;
+            Ok(cw20_balance.checked_add(native_balance)?)
+        }
+        None => Ok(cw20_balance),
+    }
+}
+
+/// ## Description
+/// Returns the amount of CW20 ASTRO held by the contract.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`]. This is the staking contract configuration.
+fn query_cw20_astro_balance(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
     let result: BalanceResponse = deps.querier.query_wasm_smart(
         &config.astro_token_addr,
         &Cw20QueryMsg::Balance {
@@ -274,6 +694,9 @@ pub fn get_total_deposit(deps: Deps, env: Env, config: Config) -> StdResult<Uint
 /// * **QueryMsg::TotalShares {}** Returns the total xASTRO supply using a [`Uint128`] object.
 ///
 /// * **QueryMsg::Config {}** Returns the amount of ASTRO that's currently in the staking pool using a [`Uint128`] object.
+///
+/// * **QueryMsg::ExchangeRateAt { timestamp }** Returns the latest xASTRO exchange rate checkpoint taken at or before
+/// the specified timestamp using an [`Option`] of [`ExchangeRateCheckpoint`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
@@ -281,8 +704,58 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Config {} => Ok(to_binary(&ConfigResponse {
             deposit_token_addr: config.astro_token_addr,
             share_token_addr: config.xastro_token_addr,
+            native_astro: config.native_astro,
         })?),
         QueryMsg::TotalShares {} => to_binary(&get_total_shares(deps, config)?),
         QueryMsg::TotalDeposit {} => to_binary(&get_total_deposit(deps, env, config)?),
+        QueryMsg::ExchangeRateAt { timestamp } => {
+            to_binary(&last_exchange_rate(deps.storage, timestamp)?)
+        }
     }
 }
+
+/// ## Description
+/// Used for contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version.
+/// If [`MigrateMsg`] contains native ASTRO parameters, the contract is switched to native ASTRO
+/// and the CW20 ASTRO it holds is converted.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    match contract_version.version.as_ref() {
+        "1.0.0" => {}
+        _ => return Err(ContractError::MigrationError {}),
+    }
+
+    let mut response = Response::new();
+
+    if let Some(params) = msg.native_astro {
+        let mut config = CONFIG.load(deps.storage)?;
+        if config.native_astro.is_some() {
+            return Err(ContractError::NativeAstroAlreadyEnabled {});
+        }
+        config.native_astro = Some(params.validate(deps.api)?);
+
+        if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+            response = response.add_message(convert_msg);
+        }
+
+        CONFIG.save(deps.storage, &config)?;
+        response = response.add_attribute("native_astro", params.denom);
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(response
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 /// ## Description
@@ -10,6 +10,24 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
+
+    #[error("Native ASTRO is not enabled")]
+    NativeAstroDisabled {},
+
+    #[error("Native ASTRO is already enabled")]
+    NativeAstroAlreadyEnabled {},
+
+    #[error("Invalid funds: exactly one non-zero native ASTRO coin must be sent")]
+    InvalidFunds {},
+
+    #[error("Initial stake amount must be more than {min_stake} ASTRO")]
+    MinimumStakeAmountError { min_stake: Uint128 },
+
+    #[error("Stake amount is too small to mint any xASTRO")]
+    StakeAmountTooSmall {},
+
+    #[error("An error occurred during migration")]
+    MigrationError {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -3,3 +3,8 @@ pub mod state;
 
 pub mod error;
 mod response;
+
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
@@ -1,5 +1,7 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use astroport::astro_converter::NativeAstro;
+use astroport::staking::ExchangeRateCheckpoint;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,8 +13,19 @@ pub struct Config {
     pub astro_token_addr: Addr,
     /// The xASTRO token contract address
     pub xastro_token_addr: Addr,
+    /// The native ASTRO used instead of CW20 ASTRO, if the contract has been switched to it
+    #[serde(default)]
+    pub native_astro: Option<NativeAstro>,
 }
 
 /// ## Description
 /// Stores the contract config at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the xASTRO exchange rate checkpoints keyed by the timestamp they were taken at
+pub const EXCHANGE_RATES: Map<U64Key, ExchangeRateCheckpoint> = Map::new("exchange_rates");
+
+/// ## Description
+/// Stores the total amount of ASTRO fees distributed to stakers
+pub const TOTAL_FEES: Item<Uint128> = Item::new("total_fees");
@@ -0,0 +1,552 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, OwnedDeps, Uint128,
+    WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::error::ContractError;
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use crate::state::CONFIG;
+use astroport::astro_converter::{Cw20HookMsg as ConverterHookMsg, NativeAstro, NativeAstroParams};
+use astroport::staking::{
+    ConfigResponse, Cw20HookMsg, ExchangeRateCheckpoint, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg, EXCHANGE_RATE_CHECKPOINT_INTERVAL, MINIMUM_STAKE_AMOUNT,
+};
+
+const NATIVE_DENOM: &str = "factory/creator/uastro";
+
+type StakingDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn setup() -> StakingDeps {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            token_code_id: 1,
+            deposit_token_addr: String::from("astro"),
+        },
+    )
+    .unwrap();
+
+    // Emulate the reply from the xASTRO instantiation
+    CONFIG
+        .update::<_, ContractError>(deps.as_mut().storage, |mut config| {
+            config.xastro_token_addr = Addr::unchecked("xastro");
+            Ok(config)
+        })
+        .unwrap();
+
+    // Native ASTRO is enabled by migrating from V1.0.0
+    set_contract_version(deps.as_mut().storage, "astroport-staking", "1.0.0").unwrap();
+
+    deps
+}
+
+fn native_astro_params() -> NativeAstroParams {
+    NativeAstroParams {
+        denom: String::from(NATIVE_DENOM),
+        converter: String::from("converter"),
+    }
+}
+
+fn convert_msg(amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("astro"),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: String::from("converter"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&ConverterHookMsg::Convert { receiver: None }).unwrap(),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn mint_msg(recipient: &str, amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("xastro"),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: String::from(recipient),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn migrate_to_native_astro() {
+    let mut deps = setup();
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 1000);
+
+    // Native deposits are disabled until the contract is switched to native ASTRO
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::Enter {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NativeAstroDisabled {});
+
+    // A migration without parameters keeps CW20 ASTRO
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { native_astro: None }).unwrap();
+    assert!(res.messages.is_empty());
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { native_astro: None }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+
+    set_contract_version(deps.as_mut().storage, "astroport-staking", "1.0.0").unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            native_astro: Some(native_astro_params()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, convert_msg(1000));
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.native_astro,
+        Some(NativeAstro {
+            denom: String::from(NATIVE_DENOM),
+            converter: Addr::unchecked("converter"),
+        })
+    );
+
+    set_contract_version(deps.as_mut().storage, "astroport-staking", "1.0.0").unwrap();
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            native_astro: Some(native_astro_params()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NativeAstroAlreadyEnabled {});
+}
+
+#[test]
+fn native_enter_and_leave() {
+    let mut deps = setup();
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            native_astro: Some(native_astro_params()),
+        },
+    )
+    .unwrap();
+
+    for funds in [
+        vec![],
+        coins(100, "uluna"),
+        coins(0, NATIVE_DENOM),
+        vec![coin(100, NATIVE_DENOM), coin(100, "uluna")],
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &funds),
+            ExecuteMsg::Enter {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFunds {});
+    }
+
+    // The pool holds 1000 ASTRO (including the deposit) and 450 xASTRO were minted
+    deps.querier
+        .set_native_balance(MOCK_CONTRACT_ADDR, &coins(1000, NATIVE_DENOM));
+    deps.querier.set_token_supply("xastro", 450);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::Enter {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, mint_msg("user", 50));
+
+    // CW20 ASTRO sent directly to the contract is converted before ASTRO is paid out
+    deps.querier.set_token_supply("xastro", 500);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("xastro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(50u128),
+            msg: to_binary(&Cw20HookMsg::Leave {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("xastro"),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(50u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            convert_msg(100),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("user"),
+                amount: coins(110, NATIVE_DENOM),
+            }),
+        ]
+    );
+
+    // Native fees are added to the pool without minting xASTRO
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("maker", &coins(200, "uluna")),
+        ExecuteMsg::DistributeFees {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    deps.querier.set_token_supply("xastro", 450);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 0);
+    deps.querier
+        .set_native_balance(MOCK_CONTRACT_ADDR, &coins(1100, NATIVE_DENOM));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("maker", &coins(200, NATIVE_DENOM)),
+        ExecuteMsg::DistributeFees {},
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let checkpoint: Option<ExchangeRateCheckpoint> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExchangeRateAt {
+                timestamp: mock_env().block.time.seconds(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        checkpoint,
+        Some(ExchangeRateCheckpoint {
+            timestamp: mock_env().block.time.seconds(),
+            total_deposit: Uint128::from(1100u128),
+            total_shares: Uint128::from(450u128),
+            exchange_rate: Decimal::from_ratio(1100u128, 450u128),
+            total_fees: Uint128::from(200u128),
+        })
+    );
+}
+
+#[test]
+fn cw20_enter_after_switch() {
+    let mut deps = setup();
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            native_astro: Some(native_astro_params()),
+        },
+    )
+    .unwrap();
+
+    // The pool holds 900 native ASTRO and the 100 CW20 ASTRO just deposited
+    deps.querier
+        .set_native_balance(MOCK_CONTRACT_ADDR, &coins(900, NATIVE_DENOM));
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 100);
+    deps.querier.set_token_supply("xastro", 450);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("user"),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![convert_msg(100), mint_msg("user", 50)]
+    );
+}
+
+#[test]
+fn first_enter_locks_minimum_stake() {
+    let mut deps = setup();
+
+    let enter_msg = |sender: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(sender),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
+        })
+    };
+
+    // The first stake must be bigger than the locked amount
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 1000);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        enter_msg("attacker", 1000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinimumStakeAmountError {
+            min_stake: MINIMUM_STAKE_AMOUNT
+        }
+    );
+
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 1001);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        enter_msg("attacker", 1001),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            mint_msg(MOCK_CONTRACT_ADDR, MINIMUM_STAKE_AMOUNT.u128()),
+            mint_msg("attacker", 1),
+        ]
+    );
+
+    // The attacker donates 1_000_000 ASTRO to inflate the share price.
+    // Without the locked xASTRO the victim's 10_000 ASTRO would mint 0 xASTRO,
+    // now they still get 10_000 * 1001 / 1_001_001 = 9 xASTRO
+    // and most of the donation is owned by the locked shares
+    deps.querier.set_token_supply("xastro", 1001);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 1_011_001);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        enter_msg("victim", 10_000),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, mint_msg("victim", 9));
+
+    // Deposits that would mint no xASTRO are rejected instead of being absorbed by the pool
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 1_001_501);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro", &[]),
+        enter_msg("victim", 500),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StakeAmountTooSmall {});
+}
+
+#[test]
+fn exchange_rate_checkpoints() {
+    let mut deps = setup();
+
+    let hook_msg = |sender: &str, amount: u128, msg: Cw20HookMsg| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from(sender),
+            amount: Uint128::from(amount),
+            msg: to_binary(&msg).unwrap(),
+        })
+    };
+    let exchange_rate_at = |deps: &StakingDeps, timestamp: u64| -> Option<ExchangeRateCheckpoint> {
+        from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ExchangeRateAt { timestamp },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let mut env = mock_env();
+    let start = env.block.time.seconds();
+
+    // The rate is checkpointed before the deposit
+    deps.querier.set_token_supply("xastro", 2000);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 4000);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("astro", &[]),
+        hook_msg("user", 1000, Cw20HookMsg::Enter {}),
+    )
+    .unwrap();
+
+    let first_checkpoint = ExchangeRateCheckpoint {
+        timestamp: start,
+        total_deposit: Uint128::from(3000u128),
+        total_shares: Uint128::from(2000u128),
+        exchange_rate: Decimal::from_ratio(3u128, 2u128),
+        total_fees: Uint128::zero(),
+    };
+    assert_eq!(exchange_rate_at(&deps, start - 1), None);
+    assert_eq!(
+        exchange_rate_at(&deps, start + 10),
+        Some(first_checkpoint.clone())
+    );
+
+    // No new checkpoint is taken within the interval
+    env.block.time = env.block.time.plus_seconds(100);
+    deps.querier.set_token_supply("xastro", 2666);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 5000);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("astro", &[]),
+        hook_msg("user", 1000, Cw20HookMsg::Enter {}),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange_rate_at(&deps, start + 100),
+        Some(first_checkpoint.clone())
+    );
+
+    // Withdrawals are checkpointed as well
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(EXCHANGE_RATE_CHECKPOINT_INTERVAL);
+    deps.querier.set_token_supply("xastro", 2000);
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 4000);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("xastro", &[]),
+        hook_msg("user", 100, Cw20HookMsg::Leave {}),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange_rate_at(&deps, env.block.time.seconds()),
+        Some(ExchangeRateCheckpoint {
+            timestamp: env.block.time.seconds(),
+            total_deposit: Uint128::from(4000u128),
+            total_shares: Uint128::from(2000u128),
+            exchange_rate: Decimal::from_ratio(2u128, 1u128),
+            total_fees: Uint128::zero(),
+        })
+    );
+    assert_eq!(exchange_rate_at(&deps, start + 10), Some(first_checkpoint));
+
+    // Only ASTRO can be distributed as fees
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("xastro", &[]),
+        hook_msg("maker", 400, Cw20HookMsg::DistributeFees {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fees received in the same block replace the checkpoint regardless of the interval
+    deps.querier
+        .set_token_balance("astro", MOCK_CONTRACT_ADDR, 4400);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("astro", &[]),
+        hook_msg("maker", 400, Cw20HookMsg::DistributeFees {}),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        exchange_rate_at(&deps, env.block.time.seconds()),
+        Some(ExchangeRateCheckpoint {
+            timestamp: env.block.time.seconds(),
+            total_deposit: Uint128::from(4400u128),
+            total_shares: Uint128::from(2000u128),
+            exchange_rate: Decimal::from_ratio(11u128, 5u128),
+            total_fees: Uint128::from(400u128),
+        })
+    );
+
+    // Regular checkpoints keep track of the fees distributed so far
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(EXCHANGE_RATE_CHECKPOINT_INTERVAL);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("xastro", &[]),
+        hook_msg("user", 100, Cw20HookMsg::Leave {}),
+    )
+    .unwrap();
+    assert_eq!(
+        exchange_rate_at(&deps, env.block.time.seconds())
+            .unwrap()
+            .total_fees,
+        Uint128::from(400u128)
+    );
+}
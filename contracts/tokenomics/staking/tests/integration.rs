@@ -1,41 +1,17 @@
 use astroport::staking::{ConfigResponse, Cw20HookMsg, InstantiateMsg as xInstatiateMsg, QueryMsg};
 use astroport::token::InstantiateMsg;
-use cosmwasm_std::{
-    attr,
-    testing::{mock_env, MockApi, MockStorage},
-    to_binary, Addr, QueryRequest, Uint128, WasmQuery,
-};
+use astroport_test::contracts::{store_staking_code, store_token_code};
+use astroport_test::mock_app;
+use cosmwasm_std::{attr, to_binary, Addr, QueryRequest, Uint128, WasmQuery};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
-use terra_multi_test::{AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock};
+use terra_multi_test::{Executor, TerraApp};
 
 const ALICE: &str = "Alice";
 const BOB: &str = "Bob";
 const CAROL: &str = "Carol";
 
-fn mock_app() -> TerraApp {
-    let env = mock_env();
-    let api = MockApi::default();
-    let bank = BankKeeper::new();
-    let storage = MockStorage::new();
-    let custom = TerraMock::luna_ust_case();
-
-    AppBuilder::new()
-        .with_api(api)
-        .with_block(env.block)
-        .with_bank(bank)
-        .with_storage(storage)
-        .with_custom(custom)
-        .build()
-}
-
 fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, Addr) {
-    let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_token::contract::execute,
-        astroport_token::contract::instantiate,
-        astroport_token::contract::query,
-    ));
-
-    let astro_token_code_id = router.store_code(astro_token_contract);
+    let astro_token_code_id = store_token_code(router);
 
     let msg = InstantiateMsg {
         name: String::from("Astro token"),
@@ -46,6 +22,7 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, Add
             minter: owner.to_string(),
             cap: None,
         }),
+        marketing: None,
     };
 
     let astro_token_instance = router
@@ -59,16 +36,7 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, Add
         )
         .unwrap();
 
-    let staking_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            astroport_staking::contract::execute,
-            astroport_staking::contract::instantiate,
-            astroport_staking::contract::query,
-        )
-        .with_reply_empty(astroport_staking::contract::reply),
-    );
-
-    let staking_code_id = router.store_code(staking_contract);
+    let staking_code_id = store_staking_code(router);
 
     let msg = xInstatiateMsg {
         owner: owner.to_string(),
@@ -112,7 +80,7 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, Add
 fn mint_some_astro(router: &mut TerraApp, owner: Addr, astro_token_instance: Addr, to: &str) {
     let msg = cw20::Cw20ExecuteMsg::Mint {
         recipient: String::from(to),
-        amount: Uint128::from(100u128),
+        amount: Uint128::from(100_000u128),
     };
     let res = router
         .execute_contract(owner.clone(), astro_token_instance.clone(), &msg, &[])
@@ -121,7 +89,7 @@ fn mint_some_astro(router: &mut TerraApp, owner: Addr, astro_token_instance: Add
     assert_eq!(res.events[1].attributes[2], attr("to", String::from(to)));
     assert_eq!(
         res.events[1].attributes[3],
-        attr("amount", Uint128::from(100u128))
+        attr("amount", Uint128::from(100_000u128))
     );
 }
 
@@ -134,7 +102,7 @@ fn cw20receive_enter_and_leave() {
     let (astro_token_instance, staking_instance, x_astro_token_instance) =
         instantiate_contracts(&mut router, owner.clone());
 
-    // Mint 100 ASTRO for Alice
+    // Mint 100_000 ASTRO for Alice
     mint_some_astro(
         &mut router,
         owner.clone(),
@@ -144,7 +112,7 @@ fn cw20receive_enter_and_leave() {
 
     let alice_address = Addr::unchecked(ALICE);
 
-    // Check if Alice's ASTRO balance is 100
+    // Check if Alice's ASTRO balance is 100_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -156,7 +124,7 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(100u128)
+            balance: Uint128::from(100_000u128)
         }
     );
 
@@ -164,7 +132,7 @@ fn cw20receive_enter_and_leave() {
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Leave {}).unwrap(),
-        amount: Uint128::from(10u128),
+        amount: Uint128::from(10_000u128),
     };
 
     let resp = router
@@ -177,11 +145,31 @@ fn cw20receive_enter_and_leave() {
         .unwrap_err();
     assert_eq!(resp.to_string(), "Unauthorized");
 
-    // Tru to stake Alice's 100 ASTRO for 100 xASTRO
+    // The first stake must be bigger than the amount of xASTRO locked in the contract
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(100u128),
+        amount: Uint128::from(1_000u128),
+    };
+
+    let resp = router
+        .execute_contract(
+            alice_address.clone(),
+            astro_token_instance.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        resp.to_string(),
+        "Initial stake amount must be more than 1000 ASTRO"
+    );
+
+    // Try to stake Alice's 100_000 ASTRO, 1_000 xASTRO are locked in the staking contract
+    let msg = Cw20ExecuteMsg::Send {
+        contract: staking_instance.to_string(),
+        msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
+        amount: Uint128::from(100_000u128),
     };
 
     router
@@ -193,7 +181,7 @@ fn cw20receive_enter_and_leave() {
         )
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 100
+    // Check if Alice's xASTRO balance is 99_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -205,7 +193,7 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(100u128)
+            balance: Uint128::from(99_000u128)
         }
     );
 
@@ -225,7 +213,7 @@ fn cw20receive_enter_and_leave() {
         }
     );
 
-    // Check if the staking contract's ASTRO balance is 100
+    // Check if the staking contract's ASTRO balance is 100_000
     let msg = Cw20QueryMsg::Balance {
         address: staking_instance.to_string(),
     };
@@ -237,7 +225,7 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(100u128)
+            balance: Uint128::from(100_000u128)
         }
     );
 
@@ -245,7 +233,7 @@ fn cw20receive_enter_and_leave() {
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(10u128),
+        amount: Uint128::from(10_000u128),
     };
 
     let resp = router
@@ -258,11 +246,11 @@ fn cw20receive_enter_and_leave() {
         .unwrap_err();
     assert_eq!(resp.to_string(), "Unauthorized");
 
-    // Try to unstake Alice's 10 xASTRO for 10 ASTRO
+    // Try to unstake Alice's 10_000 xASTRO for 10_000 ASTRO
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Leave {}).unwrap(),
-        amount: Uint128::from(10u128),
+        amount: Uint128::from(10_000u128),
     };
 
     router
@@ -274,7 +262,7 @@ fn cw20receive_enter_and_leave() {
         )
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 90
+    // Check if Alice's xASTRO balance is 89_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -286,11 +274,11 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(90u128)
+            balance: Uint128::from(89_000u128)
         }
     );
 
-    // Check if Alice's ASTRO balance is 10
+    // Check if Alice's ASTRO balance is 10_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -302,11 +290,11 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(10u128)
+            balance: Uint128::from(10_000u128)
         }
     );
 
-    // Check if the staking contract's ASTRO balance is 90
+    // Check if the staking contract's ASTRO balance is 90_000
     let msg = Cw20QueryMsg::Balance {
         address: staking_instance.to_string(),
     };
@@ -318,11 +306,11 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(90u128)
+            balance: Uint128::from(90_000u128)
         }
     );
 
-    // Check if the staking contract's xASTRO balance is 0
+    // Check if the staking contract's locked xASTRO balance is 1_000
     let msg = Cw20QueryMsg::Balance {
         address: staking_instance.to_string(),
     };
@@ -334,7 +322,7 @@ fn cw20receive_enter_and_leave() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(0u128)
+            balance: Uint128::from(1_000u128)
         }
     );
 
@@ -342,12 +330,12 @@ fn cw20receive_enter_and_leave() {
         .wrap()
         .query_wasm_smart(staking_instance.clone(), &QueryMsg::TotalDeposit {})
         .unwrap();
-    assert_eq!(res.u128(), 90);
+    assert_eq!(res.u128(), 90_000);
     let res: Uint128 = router
         .wrap()
         .query_wasm_smart(staking_instance, &QueryMsg::TotalShares {})
         .unwrap();
-    assert_eq!(res.u128(), 90);
+    assert_eq!(res.u128(), 90_000);
 }
 
 #[test]
@@ -359,7 +347,7 @@ fn should_not_allow_withdraw_more_than_what_you_have() {
     let (astro_token_instance, staking_instance, x_astro_token_instance) =
         instantiate_contracts(&mut router, owner.clone());
 
-    // Mint 100 ASTRO for Alice
+    // Mint 100_000 ASTRO for Alice
     mint_some_astro(
         &mut router,
         owner.clone(),
@@ -368,11 +356,11 @@ fn should_not_allow_withdraw_more_than_what_you_have() {
     );
     let alice_address = Addr::unchecked(ALICE);
 
-    // enter Alice's 100 ASTRO for 100 xASTRO
+    // enter Alice's 100_000 ASTRO for 99_000 xASTRO
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(100u128),
+        amount: Uint128::from(100_000u128),
     };
 
     router
@@ -384,7 +372,7 @@ fn should_not_allow_withdraw_more_than_what_you_have() {
         )
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 100
+    // Check if Alice's xASTRO balance is 99_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -396,15 +384,15 @@ fn should_not_allow_withdraw_more_than_what_you_have() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(100u128)
+            balance: Uint128::from(99_000u128)
         }
     );
 
-    // Try to burn Alice's 200 xASTRO and unstake
+    // Try to burn Alice's 200_000 xASTRO and unstake
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Leave {}).unwrap(),
-        amount: Uint128::from(200u128),
+        amount: Uint128::from(200_000u128),
     };
 
     let res = router
@@ -416,7 +404,10 @@ fn should_not_allow_withdraw_more_than_what_you_have() {
         )
         .unwrap_err();
 
-    assert_eq!(res.to_string(), "Overflow: Cannot Sub with 100 and 200");
+    assert_eq!(
+        res.to_string(),
+        "Overflow: Cannot Sub with 99000 and 200000"
+    );
 }
 
 #[test]
@@ -428,7 +419,7 @@ fn should_work_with_more_than_one_participant() {
     let (astro_token_instance, staking_instance, x_astro_token_instance) =
         instantiate_contracts(&mut router, owner.clone());
 
-    // Mint 100 ASTRO for Alice
+    // Mint 100_000 ASTRO for Alice
     mint_some_astro(
         &mut router,
         owner.clone(),
@@ -437,7 +428,7 @@ fn should_work_with_more_than_one_participant() {
     );
     let alice_address = Addr::unchecked(ALICE);
 
-    // Mint 100 ASTRO for Bob
+    // Mint 100_000 ASTRO for Bob
     mint_some_astro(
         &mut router,
         owner.clone(),
@@ -446,7 +437,7 @@ fn should_work_with_more_than_one_participant() {
     );
     let bob_address = Addr::unchecked(BOB);
 
-    // Mint 100 ASTRO for Carol
+    // Mint 100_000 ASTRO for Carol
     mint_some_astro(
         &mut router,
         owner.clone(),
@@ -455,11 +446,11 @@ fn should_work_with_more_than_one_participant() {
     );
     let carol_address = Addr::unchecked(CAROL);
 
-    // Stake Alice's 20 ASTRO for 20 xASTRO
+    // Stake Alice's 20_000 ASTRO for 19_000 xASTRO (1_000 xASTRO are locked)
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(20u128),
+        amount: Uint128::from(20_000u128),
     };
 
     router
@@ -471,18 +462,18 @@ fn should_work_with_more_than_one_participant() {
         )
         .unwrap();
 
-    // Stake Bob's 10 ASTRO for 10 xASTRO
+    // Stake Bob's 10_000 ASTRO for 10_000 xASTRO
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(10u128),
+        amount: Uint128::from(10_000u128),
     };
 
     router
         .execute_contract(bob_address.clone(), astro_token_instance.clone(), &msg, &[])
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 20
+    // Check if Alice's xASTRO balance is 19_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -494,11 +485,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(20u128)
+            balance: Uint128::from(19_000u128)
         }
     );
 
-    // Check if Bob's xASTRO balance is 10
+    // Check if Bob's xASTRO balance is 10_000
     let msg = Cw20QueryMsg::Balance {
         address: bob_address.to_string(),
     };
@@ -510,11 +501,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(10u128)
+            balance: Uint128::from(10_000u128)
         }
     );
 
-    // Check if staking contract's ASTRO balance is 30
+    // Check if staking contract's ASTRO balance is 30_000
     let msg = Cw20QueryMsg::Balance {
         address: staking_instance.to_string(),
     };
@@ -526,14 +517,14 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(30u128)
+            balance: Uint128::from(30_000u128)
         }
     );
 
-    // Staking contract gets 20 more ASTRO from external source
+    // Staking contract gets 20_000 more ASTRO from external source
     let msg = Cw20ExecuteMsg::Transfer {
         recipient: staking_instance.to_string(),
-        amount: Uint128::from(20u128),
+        amount: Uint128::from(20_000u128),
     };
     let res = router
         .execute_contract(
@@ -551,14 +542,14 @@ fn should_work_with_more_than_one_participant() {
     );
     assert_eq!(
         res.events[1].attributes[4],
-        attr("amount", Uint128::from(20u128))
+        attr("amount", Uint128::from(20_000u128))
     );
 
-    // Stake Alice's 10 ASTRO for 6 xASTRO: 10*30/50 = 6
+    // Stake Alice's 10_000 ASTRO for 6_000 xASTRO: 10_000*30_000/50_000 = 6_000
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Enter {}).unwrap(),
-        amount: Uint128::from(10u128),
+        amount: Uint128::from(10_000u128),
     };
 
     router
@@ -570,7 +561,7 @@ fn should_work_with_more_than_one_participant() {
         )
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 26
+    // Check if Alice's xASTRO balance is 25_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -582,11 +573,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(26u128)
+            balance: Uint128::from(25_000u128)
         }
     );
 
-    // Check if Bob's xASTRO balance is 10
+    // Check if Bob's xASTRO balance is 10_000
     let msg = Cw20QueryMsg::Balance {
         address: bob_address.to_string(),
     };
@@ -598,15 +589,15 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(10u128)
+            balance: Uint128::from(10_000u128)
         }
     );
 
-    // Burn Bob's 5 xASTRO and unstake: gets 5*60/36 = 8 ASTRO
+    // Burn Bob's 5_000 xASTRO and unstake: gets 5_000*60_000/36_000 = 8_333 ASTRO
     let msg = Cw20ExecuteMsg::Send {
         contract: staking_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Leave {}).unwrap(),
-        amount: Uint128::from(5u128),
+        amount: Uint128::from(5_000u128),
     };
 
     router
@@ -618,7 +609,7 @@ fn should_work_with_more_than_one_participant() {
         )
         .unwrap();
 
-    // Check if Alice's xASTRO balance is 26
+    // Check if Alice's xASTRO balance is 25_000
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -630,11 +621,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(26u128)
+            balance: Uint128::from(25_000u128)
         }
     );
 
-    // Check if Bob's xASTRO balance is 5
+    // Check if Bob's xASTRO balance is 5_000
     let msg = Cw20QueryMsg::Balance {
         address: bob_address.to_string(),
     };
@@ -646,11 +637,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(5u128)
+            balance: Uint128::from(5_000u128)
         }
     );
 
-    // Check if the staking contract's ASTRO balance is 52 (60 - 8 (Bob left 5 xASTRO))
+    // Check if the staking contract's ASTRO balance is 51_667 (60_000 - 8_333 (Bob left 5_000 xASTRO))
     let msg = Cw20QueryMsg::Balance {
         address: staking_instance.to_string(),
     };
@@ -662,11 +653,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(52u128)
+            balance: Uint128::from(51_667u128)
         }
     );
 
-    // Check if Alice's ASTRO balance is 70 (100 minted - 20 entered - 10 entered)
+    // Check if Alice's ASTRO balance is 70_000 (100_000 minted - 20_000 entered - 10_000 entered)
     let msg = Cw20QueryMsg::Balance {
         address: alice_address.to_string(),
     };
@@ -678,11 +669,11 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(70u128)
+            balance: Uint128::from(70_000u128)
         }
     );
 
-    // Check if Bob's ASTRO balance is 98 (100 minted - 10 entered + 8 by leaving)
+    // Check if Bob's ASTRO balance is 98_333 (100_000 minted - 10_000 entered + 8_333 by leaving)
     let msg = Cw20QueryMsg::Balance {
         address: bob_address.to_string(),
     };
@@ -694,7 +685,7 @@ fn should_work_with_more_than_one_participant() {
     assert_eq!(
         res.unwrap(),
         BalanceResponse {
-            balance: Uint128::from(98u128)
+            balance: Uint128::from(98_333u128)
         }
     );
 }
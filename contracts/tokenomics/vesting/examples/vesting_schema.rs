@@ -7,6 +7,7 @@ use astroport::vesting::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VestingAccountResponse,
     VestingAccountsResponse,
 };
+use cosmwasm_std::Uint128;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -29,4 +30,6 @@ fn main() {
         "VestingAccountsResponse",
     );
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "AvailableAmountResponse");
+    export_schema_with_title(&schema_for!(u64), &out_dir, "TimestampResponse");
 }
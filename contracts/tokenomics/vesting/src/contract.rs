@@ -1,19 +1,25 @@
 use cosmwasm_std::{
-    attr, entry_point, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    attr, coins, entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 
-use crate::state::{read_vesting_infos, Config, CONFIG, OWNERSHIP_PROPOSAL, VESTING_INFO};
+use crate::state::{
+    read_vesting_infos, Config, CONFIG, OWNERSHIP_PROPOSAL, TOTAL_CLAIMED, TOTAL_COMMITTED,
+    VESTING_INFO,
+};
 
 use crate::error::ContractError;
 use astroport::asset::addr_validate_to_lower;
-use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+use astroport::common::{
+    assert_migration_allowed, claim_ownership, drop_ownership_proposal, propose_new_owner,
+};
 use astroport::vesting::{
     ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderBy, QueryMsg,
     VestingAccount, VestingAccountResponse, VestingAccountsResponse, VestingInfo, VestingSchedule,
+    VestingStateResponse,
 };
 use cw2::set_contract_version;
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-vesting";
@@ -47,8 +53,11 @@ pub fn instantiate(
         &Config {
             owner: addr_validate_to_lower(deps.api, &msg.owner)?,
             token_addr: addr_validate_to_lower(deps.api, &msg.token_addr)?,
+            native_astro: None,
         },
     )?;
+    TOTAL_COMMITTED.save(deps.storage, &Uint128::zero())?;
+    TOTAL_CLAIMED.save(deps.storage, &Uint128::zero())?;
 
     Ok(Response::new())
 }
@@ -69,6 +78,8 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes it
 /// depending on the received template.
+///
+/// * **ExecuteMsg::RegisterVestingAccounts { vesting_accounts }** Creates new vesting schedules funded with native ASTRO.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -79,6 +90,9 @@ pub fn execute(
     match msg {
         ExecuteMsg::Claim { recipient, amount } => claim(deps, env, info, recipient, amount),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::RegisterVestingAccounts { vesting_accounts } => {
+            register_native_vesting_accounts(deps, env, info, vesting_accounts)
+        }
         ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
             let config: Config = CONFIG.load(deps.storage)?;
 
@@ -145,18 +159,63 @@ fn receive_cw20(
 
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::RegisterVestingAccounts { vesting_accounts } => {
-            register_vesting_accounts(deps, env, vesting_accounts, cw20_msg.amount)
+            // CW20 ASTRO received after the switch to native ASTRO is converted right away
+            let convert_msg = convert_cw20_balance_msg(deps.as_ref(), &env, &config)?;
+
+            let response = register_vesting_accounts(deps, env, vesting_accounts, cw20_msg.amount)?;
+
+            Ok(match convert_msg {
+                Some(msg) => response.add_message(msg),
+                None => response,
+            })
         }
     }
 }
 
+/// ## Description
+/// Creates new vesting schedules funded with the native ASTRO sent along with the message.
+/// Returns a [`ContractError`] if the contract hasn't been switched to native ASTRO or the funds are invalid.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **vesting_accounts** is an array with items of type [`VestingAccount`].
+/// This is the list of accounts and associated vesting schedules to create.
+fn register_native_vesting_accounts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vesting_accounts: Vec<VestingAccount>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let native_astro = config
+        .native_astro
+        .ok_or(ContractError::NativeAstroDisabled {})?;
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == native_astro.denom && !coin.amount.is_zero() => coin.amount,
+        _ => return Err(ContractError::InvalidFunds {}),
+    };
+
+    register_vesting_accounts(deps, env, vesting_accounts, amount)
+}
+
 /// ## Description
 /// Create new vesting schedules. Returns a [`Response`] with the specified attributes if the
 /// operation was successful, otherwise returns a [`ContractError`].
+/// Fails if the total amount committed to vesting schedules exceeds the ASTRO held by the contract.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **info** is an object of type [`MessageInfo`].
 ///
@@ -164,10 +223,11 @@ fn receive_cw20(
 /// This is the list of accounts and associated vesting schedules to create.
 ///
 /// * **cw20_amount** is an object of type [`Uint128`]. Sets the amount that confirms the total
-/// amount of all accounts to register
+/// amount of all accounts to register. Once the contract has been switched to native ASTRO,
+/// this is the amount of native ASTRO sent along with the message
 pub fn register_vesting_accounts(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     vesting_accounts: Vec<VestingAccount>,
     cw20_amount: Uint128,
 ) -> Result<Response, ContractError> {
@@ -209,6 +269,17 @@ pub fn register_vesting_accounts(
         return Err(ContractError::VestingScheduleAmountError {});
     }
 
+    // The deposit is already included in the contract balance
+    let committed = TOTAL_COMMITTED
+        .load(deps.storage)?
+        .checked_add(to_deposit)?;
+    let config = CONFIG.load(deps.storage)?;
+    let balance = query_astro_balance(deps.as_ref(), &env, &config)?;
+    if committed > balance {
+        return Err(ContractError::TotalCommittedExceedsBalance { committed, balance });
+    }
+    TOTAL_COMMITTED.save(deps.storage, &committed)?;
+
     Ok(response
         .add_attribute("action", "register_vesting_accounts")
         .add_attribute("deposited", to_deposit))
@@ -231,6 +302,8 @@ fn assert_vesting_schedules(
             {
                 return Err(ContractError::VestingScheduleError(addr.clone()));
             }
+        } else if sch.start_point.amount.is_zero() {
+            return Err(ContractError::VestingScheduleZeroAmount(addr.clone()));
         }
     }
 
@@ -284,24 +357,143 @@ pub fn claim(
     ]);
 
     if !claim_amount.is_zero() {
-        response
-            .messages
-            .append(&mut vec![SubMsg::new(WasmMsg::Execute {
-                contract_addr: config.token_addr.to_string(),
-                funds: vec![],
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: recipient.unwrap_or_else(|| info.sender.to_string()),
-                    amount: claim_amount,
-                })?,
-            })]);
+        let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+
+        match &config.native_astro {
+            Some(native_astro) => {
+                // Make sure all the vested ASTRO is native before paying it out
+                if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+                    response = response.add_message(convert_msg);
+                }
+
+                response = response.add_message(BankMsg::Send {
+                    to_address: recipient,
+                    amount: coins(claim_amount.u128(), &native_astro.denom),
+                });
+            }
+            None => {
+                response
+                    .messages
+                    .append(&mut vec![SubMsg::new(WasmMsg::Execute {
+                        contract_addr: config.token_addr.to_string(),
+                        funds: vec![],
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient,
+                            amount: claim_amount,
+                        })?,
+                    })]);
+            }
+        }
 
         vesting_info.released_amount = vesting_info.released_amount.checked_add(claim_amount)?;
         VESTING_INFO.save(deps.storage, &info.sender, &vesting_info)?;
+        TOTAL_COMMITTED.update::<_, StdError>(deps.storage, |committed| {
+            Ok(committed.checked_sub(claim_amount)?)
+        })?;
+        TOTAL_CLAIMED.update::<_, StdError>(deps.storage, |claimed| {
+            Ok(claimed.checked_add(claim_amount)?)
+        })?;
     };
 
     Ok(response.add_attributes(attributes))
 }
 
+/// ## Description
+/// Returns a message that converts all the CW20 ASTRO held by the contract to native ASTRO.
+/// Returns [`None`] if the contract hasn't been switched to native ASTRO or holds no CW20 ASTRO.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`]. This is the vesting contract configuration.
+fn convert_cw20_balance_msg(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> StdResult<Option<CosmosMsg>> {
+    match &config.native_astro {
+        Some(native_astro) => {
+            let result: BalanceResponse = deps.querier.query_wasm_smart(
+                &config.token_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+
+            if result.balance.is_zero() {
+                Ok(None)
+            } else {
+                native_astro
+                    .convert_msg(&config.token_addr, result.balance)
+                    .map(Some)
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// ## Description
+/// Returns the amount of ASTRO held by the contract, including native ASTRO once the contract has been switched to it.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`]. This is the vesting contract configuration.
+fn query_astro_balance(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
+    let result: BalanceResponse = deps.querier.query_wasm_smart(
+        &config.token_addr,
+        &Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+
+    match &config.native_astro {
+        Some(native_astro) => Ok(result.balance.checked_add(
+            deps.querier
+                .query_balance(&env.contract.address, &native_astro.denom)?
+                .amount,
+        )?),
+        None => Ok(result.balance),
+    }
+}
+
+/// ## Description
+/// Returns the total amount of ASTRO registered in vesting schedules and not yet claimed.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+fn compute_total_committed(deps: Deps) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for item in VESTING_INFO.range(deps.storage, None, None, Order::Ascending) {
+        let (_, info) = item?;
+        for sch in &info.schedules {
+            let amount = match &sch.end_point {
+                Some(end_point) => end_point.amount,
+                None => sch.start_point.amount,
+            };
+            total = total.checked_add(amount)?;
+        }
+        total = total.checked_sub(info.released_amount)?;
+    }
+
+    Ok(total)
+}
+
+/// ## Description
+/// Returns the total amount of ASTRO claimed from vesting schedules.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+fn compute_total_claimed(deps: Deps) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for item in VESTING_INFO.range(deps.storage, None, None, Order::Ascending) {
+        let (_, info) = item?;
+        total = total.checked_add(info.released_amount)?;
+    }
+
+    Ok(total)
+}
+
 /// ## Description
 /// Computes the amount of vested and yet unclaimed tokens for a specific vesting recipient. Returns the computed amount
 /// if the operation is successful.
@@ -359,6 +551,11 @@ fn compute_available_amount(current_time: u64, vesting_info: &VestingInfo) -> St
 ///         }** Returns a list of vesting schedules together with their vesting recipients.
 ///
 /// * **QueryMsg::AvailableAmount { address }** Returns the available amount of tokens that can be claimed by a specific vesting recipient.
+///
+/// * **QueryMsg::TotalCommitted {}** Returns the total amount of tokens registered in vesting schedules and not yet claimed.
+///
+/// * **QueryMsg::VestingState {}** Returns the total amounts of tokens granted, claimed and still locked
+/// in vesting schedules using a [`VestingStateResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -379,7 +576,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AvailableAmount { address } => Ok(to_binary(&query_vesting_available_amount(
             deps, env, address,
         )?)?),
+        QueryMsg::TotalCommitted {} => Ok(to_binary(&TOTAL_COMMITTED.load(deps.storage)?)?),
         QueryMsg::Timestamp {} => Ok(to_binary(&query_timestamp(env)?)?),
+        QueryMsg::VestingState {} => Ok(to_binary(&query_vesting_state(deps)?)?),
     }
 }
 
@@ -393,11 +592,29 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let resp = ConfigResponse {
         owner: config.owner,
         token_addr: config.token_addr,
+        native_astro: config.native_astro,
     };
 
     Ok(resp)
 }
 
+/// ## Description
+/// Returns the total amounts of tokens granted, claimed and still locked in vesting schedules
+/// using a [`VestingStateResponse`] object.
+///
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+pub fn query_vesting_state(deps: Deps) -> StdResult<VestingStateResponse> {
+    let total_locked = TOTAL_COMMITTED.load(deps.storage)?;
+    let total_claimed = TOTAL_CLAIMED.load(deps.storage)?;
+
+    Ok(VestingStateResponse {
+        total_granted: total_locked.checked_add(total_claimed)?,
+        total_claimed,
+        total_locked,
+    })
+}
+
 /// ## Description
 /// Return the current block timestamp (in seconds)
 pub fn query_timestamp(env: Env) -> StdResult<u64> {
@@ -472,14 +689,57 @@ pub fn query_vesting_available_amount(deps: Deps, env: Env, address: String) ->
 }
 
 /// ## Description
-/// Used for contract migration. Returns a default object of type [`Response`].
+/// Used for contract migration. Returns a [`ContractError`] on failure or if the contract is
+/// migrated to another contract type or to an older version.
+/// If [`MigrateMsg`] contains native ASTRO parameters, the contract is switched to native ASTRO
+/// and the CW20 ASTRO it holds is converted.
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`MigrateMsg`].
+/// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    match contract_version.version.as_ref() {
+        "1.0.0" => {}
+        _ => return Err(ContractError::MigrationError {}),
+    }
+
+    let mut response = Response::new();
+
+    if TOTAL_COMMITTED.may_load(deps.storage)?.is_none() {
+        let total_committed = compute_total_committed(deps.as_ref())?;
+        TOTAL_COMMITTED.save(deps.storage, &total_committed)?;
+    }
+
+    if TOTAL_CLAIMED.may_load(deps.storage)?.is_none() {
+        let total_claimed = compute_total_claimed(deps.as_ref())?;
+        TOTAL_CLAIMED.save(deps.storage, &total_claimed)?;
+    }
+
+    if let Some(params) = msg.native_astro {
+        let mut config: Config = CONFIG.load(deps.storage)?;
+        if config.native_astro.is_some() {
+            return Err(ContractError::NativeAstroAlreadyEnabled {});
+        }
+        config.native_astro = Some(params.validate(deps.api)?);
+
+        if let Some(convert_msg) = convert_cw20_balance_msg(deps.as_ref(), &env, &config)? {
+            response = response.add_message(convert_msg);
+        }
+
+        CONFIG.save(deps.storage, &config)?;
+        response = response.add_attribute("native_astro", params.denom);
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(response
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, OverflowError, StdError};
+use cosmwasm_std::{Addr, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 /// ## Description
@@ -19,6 +19,29 @@ pub enum ContractError {
 
     #[error("Vesting schedule amount error. The total amount should be equal to the CW20 receive amount.")]
     VestingScheduleAmountError,
+
+    #[error("Vesting schedule amount error on addr: {0}. Schedules must vest a non-zero amount")]
+    VestingScheduleZeroAmount(Addr),
+
+    #[error(
+        "Total committed amount {committed} exceeds the ASTRO held by the contract: {balance}"
+    )]
+    TotalCommittedExceedsBalance {
+        committed: Uint128,
+        balance: Uint128,
+    },
+
+    #[error("Native ASTRO is not enabled")]
+    NativeAstroDisabled {},
+
+    #[error("Native ASTRO is already enabled")]
+    NativeAstroAlreadyEnabled {},
+
+    #[error("Invalid funds: exactly one non-zero native ASTRO coin must be sent")]
+    InvalidFunds {},
+
+    #[error("An error occurred during migration")]
+    MigrationError {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,9 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use astroport::astro_converter::NativeAstro;
 use astroport::common::OwnershipProposal;
 use astroport::vesting::{OrderBy, VestingInfo};
-use cosmwasm_std::{Addr, Deps, StdResult};
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
 use cw_storage_plus::{Bound, Item, Map};
 
 /// ## Description
@@ -14,6 +15,9 @@ pub struct Config {
     pub owner: Addr,
     /// The address of the ASTRO token
     pub token_addr: Addr,
+    /// The native ASTRO vested instead of CW20 ASTRO, if the contract has been switched to it
+    #[serde(default)]
+    pub native_astro: Option<NativeAstro>,
 }
 
 /// ## Description
@@ -24,6 +28,14 @@ pub const CONFIG: Item<Config> = Item::new("config");
 /// The first key is the address of an account that's vesting, the second key is an object of type [`VestingInfo`].
 pub const VESTING_INFO: Map<&Addr, VestingInfo> = Map::new("vesting_info");
 
+/// ## Description
+/// Stores the total amount of ASTRO that is registered in vesting schedules and not yet claimed.
+pub const TOTAL_COMMITTED: Item<Uint128> = Item::new("total_committed");
+
+/// ## Description
+/// Stores the total amount of ASTRO claimed from vesting schedules.
+pub const TOTAL_CLAIMED: Item<Uint128> = Item::new("total_claimed");
+
 /// ## Description
 /// Contains a proposal to change contract ownership.
 pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
@@ -73,7 +85,7 @@ pub fn read_vesting_infos(
 
 #[test]
 fn read_vesting_infos_as_expected() {
-    use cosmwasm_std::{testing::mock_dependencies, Uint128};
+    use cosmwasm_std::testing::mock_dependencies;
 
     let mut deps = mock_dependencies(&[]);
 
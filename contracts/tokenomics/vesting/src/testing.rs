@@ -1,8 +1,20 @@
-use crate::contract::{instantiate, query};
-use astroport::vesting::{ConfigResponse, InstantiateMsg, QueryMsg};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies as mock_dependencies_with_cw20;
+use astroport::astro_converter::{Cw20HookMsg as ConverterHookMsg, NativeAstro, NativeAstroParams};
+use astroport::vesting::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VestingAccount,
+    VestingSchedule, VestingSchedulePoint, VestingStateResponse,
+};
 
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_binary, Addr};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+const NATIVE_DENOM: &str = "factory/creator/uastro";
 
 #[test]
 fn proper_initialization() {
@@ -23,6 +35,306 @@ fn proper_initialization() {
         ConfigResponse {
             owner: Addr::unchecked("owner"),
             token_addr: Addr::unchecked("astro_token"),
+            native_astro: None,
+        }
+    );
+}
+
+fn convert_msg(amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: String::from("astro_token"),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: String::from("converter"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&ConverterHookMsg::Convert { receiver: None }).unwrap(),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn vesting_accounts(amount: u128) -> Vec<VestingAccount> {
+    let now = mock_env().block.time.seconds();
+
+    vec![VestingAccount {
+        address: String::from("user"),
+        schedules: vec![VestingSchedule {
+            start_point: VestingSchedulePoint {
+                time: now - 100,
+                amount: Uint128::from(amount),
+            },
+            end_point: None,
+        }],
+    }]
+}
+
+#[test]
+fn native_astro() {
+    let mut deps = mock_dependencies_with_cw20(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            token_addr: String::from("astro_token"),
+        },
+    )
+    .unwrap();
+
+    // Native vesting accounts can't be registered before the switch to native ASTRO
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::RegisterVestingAccounts {
+            vesting_accounts: vesting_accounts(100),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NativeAstroDisabled {});
+
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 300);
+
+    // Native ASTRO is enabled by migrating from V1.0.0
+    set_contract_version(deps.as_mut().storage, "astroport-vesting", "1.0.0").unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            native_astro: Some(NativeAstroParams {
+                denom: String::from(NATIVE_DENOM),
+                converter: String::from("converter"),
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, convert_msg(300));
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 0);
+
+    assert_eq!(
+        from_binary::<ConfigResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()
+        )
+        .unwrap()
+        .native_astro,
+        Some(NativeAstro {
+            denom: String::from(NATIVE_DENOM),
+            converter: Addr::unchecked("converter"),
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::RegisterVestingAccounts {
+            vesting_accounts: vesting_accounts(100),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    for funds in [
+        vec![],
+        coins(100, "uluna"),
+        vec![coin(100, NATIVE_DENOM), coin(100, "uluna")],
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &funds),
+            ExecuteMsg::RegisterVestingAccounts {
+                vesting_accounts: vesting_accounts(100),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFunds {});
+    }
+
+    deps.querier
+        .set_native_balance(MOCK_CONTRACT_ADDR, &coins(100, NATIVE_DENOM));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &coins(100, NATIVE_DENOM)),
+        ExecuteMsg::RegisterVestingAccounts {
+            vesting_accounts: vesting_accounts(100),
+        },
+    )
+    .unwrap();
+
+    // CW20 ASTRO is still accepted and gets converted
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 50);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro_token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(50u128),
+            msg: to_binary(&Cw20HookMsg::RegisterVestingAccounts {
+                vesting_accounts: vesting_accounts(50),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, convert_msg(50));
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 0);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::Claim {
+            recipient: None,
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("user"),
+            amount: coins(150, NATIVE_DENOM),
+        })
+    );
+}
+
+#[test]
+fn total_committed() {
+    let mut deps = mock_dependencies_with_cw20(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: String::from("owner"),
+            token_addr: String::from("astro_token"),
+        },
+    )
+    .unwrap();
+
+    let register_msg = |vesting_accounts: Vec<VestingAccount>, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("owner"),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::RegisterVestingAccounts { vesting_accounts }).unwrap(),
+        })
+    };
+
+    // Schedules must vest a non-zero amount
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 0);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro_token", &[]),
+        register_msg(vesting_accounts(0), 0),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VestingScheduleZeroAmount(Addr::unchecked("user"))
+    );
+
+    // Schedule points must be strictly increasing
+    let now = mock_env().block.time.seconds();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro_token", &[]),
+        register_msg(
+            vec![VestingAccount {
+                address: String::from("user"),
+                schedules: vec![VestingSchedule {
+                    start_point: VestingSchedulePoint {
+                        time: now,
+                        amount: Uint128::zero(),
+                    },
+                    end_point: Some(VestingSchedulePoint {
+                        time: now,
+                        amount: Uint128::from(100u128),
+                    }),
+                }],
+            }],
+            100,
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VestingScheduleError(Addr::unchecked("user"))
+    );
+
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 100);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro_token", &[]),
+        register_msg(vesting_accounts(100), 100),
+    )
+    .unwrap();
+
+    let total_committed: Uint128 =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalCommitted {}).unwrap())
+            .unwrap();
+    assert_eq!(total_committed, Uint128::from(100u128));
+
+    // The contract balance doesn't cover all the schedules anymore
+    deps.querier
+        .set_token_balance("astro_token", MOCK_CONTRACT_ADDR, 120);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("astro_token", &[]),
+        register_msg(vesting_accounts(50), 50),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TotalCommittedExceedsBalance {
+            committed: Uint128::from(150u128),
+            balance: Uint128::from(120u128),
+        }
+    );
+
+    // Claims reduce the committed amount
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::Claim {
+            recipient: None,
+            amount: Some(Uint128::from(40u128)),
+        },
+    )
+    .unwrap();
+
+    let total_committed: Uint128 =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalCommitted {}).unwrap())
+            .unwrap();
+    assert_eq!(total_committed, Uint128::from(60u128));
+
+    let vesting_state: VestingStateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::VestingState {}).unwrap()).unwrap();
+    assert_eq!(
+        vesting_state,
+        VestingStateResponse {
+            total_granted: Uint128::from(100u128),
+            total_claimed: Uint128::from(40u128),
+            total_locked: Uint128::from(60u128),
         }
     );
 }
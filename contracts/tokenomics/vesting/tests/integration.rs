@@ -6,13 +6,12 @@ use astroport::{
         VestingSchedulePoint,
     },
 };
+use astroport_test::contracts::{store_token_code, store_vesting_code};
+use astroport_test::mock_app;
 use astroport_vesting::state::Config;
-use cosmwasm_std::{
-    testing::{mock_env, MockApi, MockStorage},
-    to_binary, Addr, StdResult, Timestamp, Uint128,
-};
+use cosmwasm_std::{to_binary, Addr, StdResult, Timestamp, Uint128};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
-use terra_multi_test::{AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock};
+use terra_multi_test::{Executor, TerraApp};
 
 const OWNER1: &str = "owner1";
 const USER1: &str = "user1";
@@ -456,32 +455,6 @@ fn register_vesting_accounts() {
     );
 }
 
-fn mock_app() -> TerraApp {
-    let env = mock_env();
-    let api = MockApi::default();
-    let bank = BankKeeper::new();
-    let storage = MockStorage::new();
-    let custom = TerraMock::luna_ust_case();
-
-    AppBuilder::new()
-        .with_api(api)
-        .with_block(env.block)
-        .with_bank(bank)
-        .with_storage(storage)
-        .with_custom(custom)
-        .build()
-}
-
-fn store_token_code(app: &mut TerraApp) -> u64 {
-    let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_token::contract::execute,
-        astroport_token::contract::instantiate,
-        astroport_token::contract::query,
-    ));
-
-    app.store_code(astro_token_contract)
-}
-
 fn instantiate_token(
     app: &mut TerraApp,
     token_code_id: u64,
@@ -499,6 +472,7 @@ fn instantiate_token(
             minter: String::from(OWNER1),
             cap: cap.map(|v| Uint128::from(v)),
         }),
+        marketing: None,
     };
 
     app.instantiate_contract(
@@ -513,13 +487,8 @@ fn instantiate_token(
 }
 
 fn instantiate_vesting(mut app: &mut TerraApp, astro_token_instance: &Addr) -> Addr {
-    let vesting_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_vesting::contract::execute,
-        astroport_vesting::contract::instantiate,
-        astroport_vesting::contract::query,
-    ));
     let owner = Addr::unchecked(OWNER1);
-    let vesting_code_id = app.store_code(vesting_contract);
+    let vesting_code_id = store_vesting_code(&mut app);
 
     let init_msg = InstantiateMsg {
         owner: OWNER1.to_string(),
@@ -3,10 +3,11 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use astroport::xastro_token::{InstantiateMsg, QueryMsg};
+use astroport::xastro_token::{InstantiateMsg, MigrateMsg, QueryMsg};
+use cosmwasm_std::Uint128;
 use cw20::{
-    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, MinterResponse,
-    TokenInfoResponse,
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
+    DownloadLogoResponse, MarketingInfoResponse, MinterResponse, TokenInfoResponse,
 };
 use cw20_base::msg::ExecuteMsg;
 
@@ -19,6 +20,7 @@ fn main() {
     export_schema_with_title(&schema_for!(InstantiateMsg), &out_dir, "InstantiateMsg");
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
     export_schema_with_title(
         &schema_for!(AllowanceResponse),
         &out_dir,
@@ -41,4 +43,16 @@ fn main() {
         &out_dir,
         "AllAccountsResponse",
     );
+    export_schema_with_title(&schema_for!(BalanceResponse), &out_dir, "BalanceAtResponse");
+    export_schema_with_title(&schema_for!(Uint128), &out_dir, "TotalSupplyAtResponse");
+    export_schema_with_title(
+        &schema_for!(MarketingInfoResponse),
+        &out_dir,
+        "MarketingInfoResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(DownloadLogoResponse),
+        &out_dir,
+        "DownloadLogoResponse",
+    );
 }
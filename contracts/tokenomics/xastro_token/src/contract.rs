@@ -9,6 +9,7 @@ use cw20_base::allowances::{
 
 use crate::state::{capture_total_supply_history, get_total_supply_at, BALANCES};
 use astroport::asset::addr_validate_to_lower;
+use astroport::common::assert_migration_allowed;
 use cw2::set_contract_version;
 use cw20_base::contract::{
     execute_update_marketing, execute_upload_logo, query_download_logo, query_marketing_info,
@@ -21,7 +22,7 @@ use cw20_base::ContractError;
 use cw_storage_plus::Bound;
 
 use crate::utils::deserialize_pair;
-use astroport::xastro_token::{InstantiateMsg, QueryMsg};
+use astroport::xastro_token::{InstantiateMsg, MigrateMsg, QueryMsg};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-xastro-token";
@@ -705,3 +706,25 @@ pub fn query_all_accounts(
         accounts: accounts?,
     })
 }
+
+/// ## Description
+/// Used for contract migration. Returns an error if the contract is migrated to another contract
+/// type or to an older version.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **_env** is an object of type [`Env`].
+///
+/// * **_msg** is an object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
@@ -1,11 +1,12 @@
-use crate::contract::{execute, instantiate, query_balance, query_balance_at};
+use crate::contract::{execute, instantiate, migrate, query_balance, query_balance_at};
 use crate::state::get_total_supply_at;
-use astroport::xastro_token::InstantiateMsg;
+use astroport::xastro_token::{InstantiateMsg, MigrateMsg};
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coins, Addr, Binary, BlockInfo, ContractInfo, CosmosMsg, Deps, DepsMut, Env, StdError, SubMsg,
-    Timestamp, Uint128, WasmMsg,
+    attr, coins, Addr, Binary, BlockInfo, ContractInfo, CosmosMsg, Deps, DepsMut, Env, StdError,
+    SubMsg, Timestamp, Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::{Cw20Coin, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse};
 use cw20_base::contract::{query_minter, query_token_info};
 use cw20_base::msg::ExecuteMsg;
@@ -738,3 +739,118 @@ fn snapshots_are_taken_and_retrieved_correctly() {
         balance_previous_value = expected_balance;
     }
 }
+
+#[test]
+fn allowance_operations_are_snapshotted() {
+    let mut deps = mock_dependencies(&[]);
+
+    let owner = String::from("owner");
+    let spender = String::from("spender");
+    let recipient = String::from("recipient");
+    let contract = String::from("contract");
+
+    let initial_supply = Uint128::new(100_000);
+    do_instantiate(deps.as_mut(), &owner, initial_supply);
+
+    let env = test_mock_env(MockEnvParams {
+        block_height: 20_000,
+        ..Default::default()
+    });
+    let msg = ExecuteMsg::IncreaseAllowance {
+        spender: spender.clone(),
+        amount: Uint128::new(60_000),
+        expires: None,
+    };
+    execute(deps.as_mut(), env, mock_info(owner.as_str(), &[]), msg).unwrap();
+
+    // TransferFrom at block 20_100
+    let env = test_mock_env(MockEnvParams {
+        block_height: 20_100,
+        ..Default::default()
+    });
+    let msg = ExecuteMsg::TransferFrom {
+        owner: owner.clone(),
+        recipient: recipient.clone(),
+        amount: Uint128::new(10_000),
+    };
+    execute(deps.as_mut(), env, mock_info(spender.as_str(), &[]), msg).unwrap();
+
+    // SendFrom at block 20_200
+    let env = test_mock_env(MockEnvParams {
+        block_height: 20_200,
+        ..Default::default()
+    });
+    let msg = ExecuteMsg::SendFrom {
+        owner: owner.clone(),
+        contract: contract.clone(),
+        amount: Uint128::new(20_000),
+        msg: Binary::default(),
+    };
+    execute(deps.as_mut(), env, mock_info(spender.as_str(), &[]), msg).unwrap();
+
+    // BurnFrom at block 20_300
+    let env = test_mock_env(MockEnvParams {
+        block_height: 20_300,
+        ..Default::default()
+    });
+    let msg = ExecuteMsg::BurnFrom {
+        owner: owner.clone(),
+        amount: Uint128::new(30_000),
+    };
+    execute(deps.as_mut(), env, mock_info(spender.as_str(), &[]), msg).unwrap();
+
+    let balance_at = |address: &String, block: u64| {
+        query_balance_at(deps.as_ref(), address.clone(), block)
+            .unwrap()
+            .balance
+    };
+
+    // Balances only change after the block in which the operation happened
+    assert_eq!(balance_at(&owner, 20_100), Uint128::new(100_000));
+    assert_eq!(balance_at(&owner, 20_101), Uint128::new(90_000));
+    assert_eq!(balance_at(&recipient, 20_100), Uint128::zero());
+    assert_eq!(balance_at(&recipient, 20_101), Uint128::new(10_000));
+    assert_eq!(balance_at(&owner, 20_200), Uint128::new(90_000));
+    assert_eq!(balance_at(&owner, 20_201), Uint128::new(70_000));
+    assert_eq!(balance_at(&contract, 20_200), Uint128::zero());
+    assert_eq!(balance_at(&contract, 20_201), Uint128::new(20_000));
+    assert_eq!(balance_at(&owner, 20_300), Uint128::new(70_000));
+    assert_eq!(balance_at(&owner, 20_301), Uint128::new(40_000));
+
+    // Only BurnFrom changes the total supply
+    assert_eq!(
+        get_total_supply_at(&deps.storage, 20_299).unwrap(),
+        initial_supply
+    );
+    assert_eq!(
+        get_total_supply_at(&deps.storage, 20_300).unwrap(),
+        Uint128::new(70_000)
+    );
+}
+
+#[test]
+fn migrate_from_v100() {
+    let mut deps = mock_dependencies(&[]);
+    set_contract_version(deps.as_mut().storage, "astroport-xastro-token", "1.0.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("previous_contract_name", "astroport-xastro-token"),
+            attr("previous_contract_version", "1.0.0"),
+            attr("new_contract_name", "astroport-xastro-token"),
+            attr("new_contract_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+
+    // The contract can't be migrated to the same version again
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: Can't migrate from version {0} to {0}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -3,7 +3,8 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use astroport::whitelist::{AdminListResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use astroport::whitelist::{AdminListResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use cw1::CanExecuteResponse;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -14,9 +15,15 @@ fn main() {
     export_schema_with_title(&schema_for!(InstantiateMsg), &out_dir, "InstantiateMsg");
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
     export_schema_with_title(
         &schema_for!(AdminListResponse),
         &out_dir,
         "AdminListResponse",
     );
+    export_schema_with_title(
+        &schema_for!(CanExecuteResponse),
+        &out_dir,
+        "CanExecuteResponse",
+    );
 }
@@ -13,7 +13,8 @@ use cw2::set_contract_version;
 
 use crate::error::ContractError;
 use crate::state::{AdminList, ADMIN_LIST};
-use astroport::whitelist::{AdminListResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use astroport::common::assert_migration_allowed;
+use astroport::whitelist::{AdminListResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 // Version info for contract migration.
 const CONTRACT_NAME: &str = "astroport-cw1-whitelist";
@@ -141,6 +142,19 @@ pub fn query_can_execute(
     })
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = assert_migration_allowed(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
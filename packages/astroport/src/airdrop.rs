@@ -0,0 +1,113 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to register merkle roots and claw back unclaimed tokens
+    pub owner: String,
+    /// The CW20 token that's distributed by the airdrop
+    pub token: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Registers a new airdrop stage. The contract must hold enough tokens to cover `total_amount`
+    /// ## Executor
+    /// Only the current owner can execute this
+    RegisterMerkleRoot {
+        /// The hex-encoded merkle root of the stage
+        merkle_root: String,
+        /// The timestamp (in seconds) when claims open
+        start_timestamp: u64,
+        /// The timestamp (in seconds) when claims close
+        expiration_timestamp: u64,
+        /// The total amount of tokens distributed in the stage
+        total_amount: Uint128,
+    },
+    /// Claims an airdrop allocation
+    Claim {
+        /// The airdrop stage
+        stage: u8,
+        /// The amount of tokens allocated to the sender
+        amount: Uint128,
+        /// The hex-encoded merkle proof of the allocation
+        proof: Vec<String>,
+        /// The address that receives the tokens. Defaults to the sender
+        recipient: Option<String>,
+    },
+    /// Sends the tokens that weren't claimed in an expired stage to the recipient
+    /// ## Executor
+    /// Only the current owner can execute this
+    Clawback {
+        /// The airdrop stage
+        stage: u8,
+        /// The address that receives the tokens. Defaults to the owner
+        recipient: Option<String>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the latest registered stage as a [`u8`]
+    LatestStage {},
+    /// Returns information about a stage using a [`StageResponse`] object
+    Stage { stage: u8 },
+    /// Returns whether an address claimed its allocation in a stage as a [`bool`]
+    IsClaimed { stage: u8, address: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to register merkle roots and claw back unclaimed tokens
+    pub owner: Addr,
+    /// The CW20 token that's distributed by the airdrop
+    pub token: Addr,
+}
+
+/// This structure describes an airdrop stage.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageResponse {
+    /// The hex-encoded merkle root of the stage
+    pub merkle_root: String,
+    /// The timestamp (in seconds) when claims open
+    pub start_timestamp: u64,
+    /// The timestamp (in seconds) when claims close
+    pub expiration_timestamp: u64,
+    /// The total amount of tokens distributed in the stage
+    pub total_amount: Uint128,
+    /// The amount of tokens claimed so far
+    pub claimed_amount: Uint128,
+    /// Whether the unclaimed tokens were clawed back
+    pub clawed_back: bool,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -245,18 +245,99 @@ impl AssetInfo {
                 addr_validate_to_lower(api, contract_addr.as_str())?;
             }
             AssetInfo::NativeToken { denom } => {
-                if !denom.starts_with("ibc/") && denom != &denom.to_lowercase() {
-                    return Err(StdError::generic_err(format!(
-                        "Non-IBC token denom {} should be lowercase",
-                        denom
-                    )));
-                }
+                validate_native_denom(api, denom)?;
             }
         }
         Ok(())
     }
 }
 
+/// The minimum length of a native token denom
+const MIN_DENOM_LENGTH: usize = 3;
+/// The maximum length of a native token denom
+const MAX_DENOM_LENGTH: usize = 128;
+/// The length of an IBC denom hash
+const IBC_HASH_LENGTH: usize = 64;
+/// The maximum length of a tokenfactory subdenom
+const MAX_SUBDENOM_LENGTH: usize = 44;
+
+/// ## Description
+/// Returns [`Ok`] if the specified native token denom is valid. Otherwise returns [`Err`].
+/// The following formats are accepted:
+/// * `ibc/<hash>` where `hash` is an uppercase hex encoded SHA256 hash;
+/// * `factory/<creator>/<subdenom>` where `creator` is a valid address;
+/// * a lowercase base denom that starts with a letter, e.g. `uluna`.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **denom** is an object of type [`str`]. This is the denom to validate.
+pub fn validate_native_denom(api: &dyn Api, denom: &str) -> StdResult<()> {
+    if denom.len() < MIN_DENOM_LENGTH || denom.len() > MAX_DENOM_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Invalid denom length for {}: must be between {} and {} characters",
+            denom, MIN_DENOM_LENGTH, MAX_DENOM_LENGTH
+        )));
+    }
+
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if hash.len() != IBC_HASH_LENGTH
+            || !hash
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+        {
+            return Err(StdError::generic_err(format!(
+                "Invalid IBC denom {}: expected an uppercase hex hash of {} characters",
+                denom, IBC_HASH_LENGTH
+            )));
+        }
+        return Ok(());
+    }
+
+    if let Some(rest) = denom.strip_prefix("factory/") {
+        let parts: Vec<&str> = rest.splitn(2, '/').collect();
+        if parts.len() != 2 {
+            return Err(StdError::generic_err(format!(
+                "Invalid tokenfactory denom {}: expected factory/<creator>/<subdenom>",
+                denom
+            )));
+        }
+        addr_validate_to_lower(api, parts[0])?;
+
+        let subdenom = parts[1];
+        if subdenom.is_empty()
+            || subdenom.len() > MAX_SUBDENOM_LENGTH
+            || !subdenom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '-' | '_'))
+        {
+            return Err(StdError::generic_err(format!(
+                "Invalid tokenfactory subdenom in {}",
+                denom
+            )));
+        }
+        return Ok(());
+    }
+
+    if denom != denom.to_lowercase() {
+        return Err(StdError::generic_err(format!(
+            "Non-IBC token denom {} should be lowercase",
+            denom
+        )));
+    }
+
+    let mut chars = denom.chars();
+    if !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        || !chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | ':' | '.' | '_' | '-'))
+    {
+        return Err(StdError::generic_err(format!(
+            "Invalid denom {}: must start with a letter and contain only alphanumeric characters or /:._-",
+            denom
+        )));
+    }
+
+    Ok(())
+}
+
 /// This structure stores the main parameters for an Astroport pair
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PairInfo {
@@ -377,6 +458,35 @@ pub fn token_asset_info(contract_addr: Addr) -> AssetInfo {
     AssetInfo::Token { contract_addr }
 }
 
+/// Returns a copy of the specified asset infos sorted by their byte representation.
+/// This is the canonical order used to derive pair keys.
+/// ## Params
+/// * **asset_infos** is a slice of items of type [`AssetInfo`].
+pub fn sort_asset_infos(asset_infos: &[AssetInfo]) -> Vec<AssetInfo> {
+    let mut asset_infos = asset_infos.to_vec();
+    asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    asset_infos
+}
+
+/// Returns true if the same asset appears more than once in the specified list. Otherwise returns false.
+/// ## Params
+/// * **asset_infos** is a slice of items of type [`AssetInfo`].
+pub fn has_duplicate_asset_infos(asset_infos: &[AssetInfo]) -> bool {
+    asset_infos
+        .iter()
+        .enumerate()
+        .any(|(i, a)| asset_infos[i + 1..].iter().any(|b| a.equal(b)))
+}
+
+/// Returns the key under which the factory stores a pair with the specified assets.
+/// The key does not depend on the order of the assets.
+/// ## Params
+/// * **asset_infos** is an array with two items of type [`AssetInfo`].
+pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
+    let asset_infos = sort_asset_infos(asset_infos);
+    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
+}
+
 /// Returns [`PairInfo`] by specified pool address.
 pub fn pair_info_by_pool(deps: Deps, pool: Addr) -> StdResult<PairInfo> {
     let minter_info: MinterResponse = deps
@@ -0,0 +1,157 @@
+use cosmwasm_std::{to_binary, Addr, Api, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{addr_validate_to_lower, validate_native_denom, AssetInfo};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The CW20 ASTRO token contract address
+    pub astro_token: String,
+    /// The native (tokenfactory) ASTRO denom
+    pub native_denom: String,
+    /// The timestamp (in seconds) until which native ASTRO can be converted back to CW20 ASTRO
+    pub window_end: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
+    Receive(Cw20ReceiveMsg),
+    /// Converts the native ASTRO sent with the message back to CW20 ASTRO.
+    /// Only available until the transition window ends
+    ConvertBack {
+        /// The address that receives CW20 ASTRO. Defaults to the sender
+        receiver: Option<String>,
+    },
+    /// Updates general contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new end of the transition window. It can't be set in the past
+        window_end: Option<u64>,
+    },
+    /// Burns the locked CW20 ASTRO once the transition window has ended
+    /// ## Executor
+    /// Only the current owner can execute this
+    BurnLocked {},
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Locks CW20 ASTRO and releases the same amount of native ASTRO
+    Convert {
+        /// The address that receives native ASTRO. Defaults to the sender
+        receiver: Option<String>,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The CW20 ASTRO token contract address
+    pub astro_token: Addr,
+    /// The native ASTRO denom
+    pub native_denom: String,
+    /// The timestamp (in seconds) until which native ASTRO can be converted back to CW20 ASTRO
+    pub window_end: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// This structure describes the parameters used to switch a contract to native ASTRO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeAstroParams {
+    /// The native ASTRO denom
+    pub denom: String,
+    /// The converter contract used to convert CW20 ASTRO to native ASTRO
+    pub converter: String,
+}
+
+impl NativeAstroParams {
+    /// Validates the parameters and returns a [`NativeAstro`] object.
+    /// ## Params
+    /// * **api** is an object of type [`Api`].
+    pub fn validate(&self, api: &dyn Api) -> StdResult<NativeAstro> {
+        validate_native_denom(api, &self.denom)?;
+
+        Ok(NativeAstro {
+            denom: self.denom.clone(),
+            converter: addr_validate_to_lower(api, &self.converter)?,
+        })
+    }
+}
+
+/// This structure describes the native ASTRO token used by a contract instead of CW20 ASTRO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeAstro {
+    /// The native ASTRO denom
+    pub denom: String,
+    /// The converter contract used to convert CW20 ASTRO to native ASTRO
+    pub converter: Addr,
+}
+
+impl NativeAstro {
+    /// Returns the native ASTRO asset information.
+    pub fn asset_info(&self) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: self.denom.clone(),
+        }
+    }
+
+    /// Returns a message that converts CW20 ASTRO held by the caller to native ASTRO 1:1.
+    /// The native ASTRO is sent back to the caller.
+    /// ## Params
+    /// * **astro_token** is an object of type [`Addr`]. This is the CW20 ASTRO token contract.
+    ///
+    /// * **amount** is an object of type [`Uint128`]. This is the amount of CW20 ASTRO to convert.
+    pub fn convert_msg(&self, astro_token: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: astro_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: self.converter.to_string(),
+                amount,
+                msg: to_binary(&Cw20HookMsg::Convert { receiver: None })?,
+            })?,
+            funds: vec![],
+        }))
+    }
+}
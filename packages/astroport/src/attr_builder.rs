@@ -0,0 +1,84 @@
+use crate::asset::Asset;
+use cosmwasm_std::{attr, Attribute, Event};
+
+/// The namespace used in the type of every event emitted by Astroport contracts
+pub const EVENT_NAMESPACE: &str = "astroport";
+
+/// ## Description
+/// Builds a list of event attributes that follows the same layout in every Astroport contract.
+/// The `action` attribute always comes first, followed by `sender` and `receiver` (if set) and
+/// then by any asset or custom attributes in the order they were added.
+///
+/// Assets are rendered as two attributes: `<prefix>_asset` holds the asset info and
+/// `<prefix>_amount` holds the amount, so that indexers can parse amounts without
+/// splitting strings.
+///
+/// The same attributes can also be emitted in a dedicated event of type
+/// `astroport/<contract>/<action>` (see [`AttrBuilder::event`]), so that a single
+/// subscription filter matches an action in every contract version.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttrBuilder {
+    action: String,
+    attributes: Vec<Attribute>,
+}
+
+impl AttrBuilder {
+    /// Creates a new builder with the specified `action` attribute.
+    pub fn new(action: impl Into<String>) -> Self {
+        let action = action.into();
+        AttrBuilder {
+            attributes: vec![attr("action", action.clone())],
+            action,
+        }
+    }
+
+    /// Adds the `sender` attribute.
+    pub fn sender(mut self, sender: impl Into<String>) -> Self {
+        self.attributes.push(attr("sender", sender));
+        self
+    }
+
+    /// Adds the `receiver` attribute.
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.attributes.push(attr("receiver", receiver));
+        self
+    }
+
+    /// Adds the `<prefix>_asset` and `<prefix>_amount` attributes for the specified [`Asset`].
+    pub fn asset(self, prefix: &str, asset: &Asset) -> Self {
+        self.attr(format!("{}_asset", prefix), asset.info.to_string())
+            .attr(format!("{}_amount", prefix), asset.amount)
+    }
+
+    /// Adds the `<prefix>_asset` and `<prefix>_amount` attributes for every asset in the list.
+    /// Attributes for the second and subsequent assets get the asset index appended to the prefix
+    /// (e.g. `refund_asset`, `refund_amount`, `refund_1_asset`, `refund_1_amount`).
+    pub fn assets(mut self, prefix: &str, assets: &[Asset]) -> Self {
+        for (i, asset) in assets.iter().enumerate() {
+            self = if i == 0 {
+                self.asset(prefix, asset)
+            } else {
+                self.asset(&format!("{}_{}", prefix, i), asset)
+            };
+        }
+        self
+    }
+
+    /// Adds a custom attribute.
+    pub fn attr(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.attributes.push(attr(key, value.to_string()));
+        self
+    }
+
+    /// Returns an event of type `astroport/<contract>/<action>` that holds the attributes added so far.
+    /// `contract` is the short contract name, e.g. `pair` or `generator`.
+    pub fn event(&self, contract: &str) -> Event {
+        Event::new(format!("{}/{}/{}", EVENT_NAMESPACE, contract, self.action))
+            .add_attributes(self.attributes.clone())
+    }
+
+    /// Returns the list of attributes.
+    pub fn build(self) -> Vec<Attribute> {
+        self.attributes
+    }
+}
@@ -0,0 +1,176 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The ASTRO token contract address
+    pub astro_token: String,
+    /// The native stable asset that's paired with ASTRO
+    pub stable_denom: String,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds. It starts right after the deposit window
+    pub withdrawal_window: u64,
+    /// The duration (in seconds) over which participants' LP tokens vest after the pool is initialized
+    pub lp_vesting_duration: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
+    Receive(Cw20ReceiveMsg),
+    /// Deposits the stable asset sent along with the message
+    DepositStable {},
+    /// Withdraws the stable asset during the deposit or withdrawal windows
+    WithdrawStable {
+        /// The amount to withdraw
+        amount: Uint128,
+    },
+    /// Provides all deposits to the ASTRO-stable pair once the auction has finished
+    /// ## Executor
+    /// Only the current owner can execute this
+    InitPool {
+        /// The ASTRO-stable pair contract address
+        pair: String,
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Claims the sender's share of the ASTRO incentives
+    ClaimRewards {},
+    /// Withdraws vested LP tokens
+    WithdrawLp {
+        /// The amount of LP tokens to withdraw. Defaults to everything that has vested
+        amount: Option<Uint128>,
+    },
+    /// Callbacks used during pool initialization
+    /// ## Executor
+    /// Callback functions can only be called by this contract
+    Callback(CallbackMsg),
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits ASTRO in the auction. ASTRO deposits can't be withdrawn
+    DepositAstro {},
+    /// Adds ASTRO to the incentives distributed among participants
+    IncreaseAstroIncentives {},
+}
+
+/// This structure describes the callback messages used during pool initialization.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Saves the amount of LP tokens minted for the auction deposits
+    UpdateStateOnPoolInit {
+        /// The ASTRO-stable pair contract address
+        pair: Addr,
+        /// The LP token contract address
+        lp_token: Addr,
+        /// The contract's LP token balance before liquidity was provided
+        prev_lp_balance: Uint128,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the global auction state using a [`StateResponse`] object
+    State {},
+    /// Returns information about a participant using a [`UserInfoResponse`] object
+    UserInfo { address: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The native stable asset that's paired with ASTRO
+    pub stable_denom: String,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds
+    pub withdrawal_window: u64,
+    /// The duration (in seconds) over which participants' LP tokens vest after the pool is initialized
+    pub lp_vesting_duration: u64,
+}
+
+/// This structure holds the global auction state.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct StateResponse {
+    /// The total amount of deposited ASTRO
+    pub total_astro_deposited: Uint128,
+    /// The total amount of the deposited stable asset
+    pub total_stable_deposited: Uint128,
+    /// The total amount of ASTRO incentives
+    pub astro_incentives: Uint128,
+    /// The ASTRO-stable pair. Set once the pool is initialized
+    pub pair: Option<Addr>,
+    /// The LP token of the ASTRO-stable pair. Set once the pool is initialized
+    pub lp_token: Option<Addr>,
+    /// The amount of LP tokens minted for the auction deposits
+    pub lp_shares_minted: Uint128,
+    /// The timestamp (in seconds) when the pool was initialized and LP vesting started
+    pub pool_init_timestamp: u64,
+}
+
+/// This structure holds the parameters returned when querying for a participant.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UserInfoResponse {
+    /// The amount of ASTRO deposited by the user
+    pub astro_deposited: Uint128,
+    /// The amount of the stable asset deposited by the user
+    pub stable_deposited: Uint128,
+    /// Whether the user withdrew the stable asset during the withdrawal window
+    pub withdrawal_flag: bool,
+    /// The user's share of LP tokens. Only known once the pool is initialized
+    pub lp_shares: Uint128,
+    /// The amount of LP tokens that have vested but were not withdrawn yet
+    pub withdrawable_lp_shares: Uint128,
+    /// The amount of LP tokens already withdrawn
+    pub lp_withdrawn: Uint128,
+    /// The user's share of the ASTRO incentives. Only known once the pool is initialized
+    pub astro_incentives: Uint128,
+    /// Whether the ASTRO incentives were claimed
+    pub astro_incentives_claimed: bool,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,165 @@
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of bribes that can be deposited for a pool in one voting period
+pub const MAX_BRIBES_PER_PERIOD: usize = 20;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The generator controller contract that vxASTRO holders vote in
+    pub generator_controller: String,
+    /// The amount of seconds after a period is tuned during which voters can claim bribes.
+    /// Unclaimed bribes can be recovered by their bribers afterwards
+    pub claim_window: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Deposits the native tokens sent along with the message as bribes for a pool's voters.
+    /// Bribes can only be deposited before the period is tuned
+    DepositBribe {
+        /// The LP token of the pool that voters are bribed to vote for
+        lp_token: String,
+        /// The voting period of the bribe
+        period: u64,
+    },
+    /// Claims the sender's share of all bribes deposited for a pool in a tuned period
+    ClaimBribes {
+        /// The LP token of the pool that the sender voted for
+        lp_token: String,
+        /// The voting period to claim bribes for
+        period: u64,
+    },
+    /// Sends the unclaimed part of an expired bribe back to the briber
+    /// ## Executor
+    /// Only the briber can execute this
+    RecoverBribe {
+        /// The identifier of the bribe
+        bribe_id: u64,
+    },
+    /// Updates contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new generator controller contract
+        generator_controller: Option<String>,
+        /// The new claim window (in seconds)
+        claim_window: Option<u64>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes custom hooks for the CW20.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits the sent tokens as a bribe for a pool's voters
+    DepositBribe {
+        /// The LP token of the pool that voters are bribed to vote for
+        lp_token: String,
+        /// The voting period of the bribe
+        period: u64,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns a bribe using a [`BribeInfo`] object
+    Bribe { bribe_id: u64 },
+    /// Returns all bribes deposited for a pool in a period as a vector of [`BribeInfo`] objects
+    PoolBribes { lp_token: String, period: u64 },
+    /// Returns the bribes a user can claim for a pool in a period as a vector of [`Asset`] objects
+    Claimable {
+        lp_token: String,
+        period: u64,
+        user: String,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The generator controller contract
+    pub generator_controller: Addr,
+    /// The amount of seconds after a period is tuned during which voters can claim bribes
+    pub claim_window: u64,
+}
+
+/// This structure describes a bribe deposited for a pool's voters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BribeInfo {
+    /// The identifier of the bribe
+    pub id: u64,
+    /// The address that deposited the bribe
+    pub briber: Addr,
+    /// The LP token of the pool that voters are bribed to vote for
+    pub lp_token: Addr,
+    /// The voting period of the bribe
+    pub period: u64,
+    /// The bribe token and the deposited amount
+    pub reward: Asset,
+    /// The amount already claimed by voters
+    pub claimed: Uint128,
+    /// Whether the unclaimed part was sent back to the briber
+    pub recovered: bool,
+}
+
+/// This structure describes the queries a generator controller must support for the bribes contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ControllerQueryMsg {
+    /// Returns the vote weight a user gave to a pool in a period as a [`Uint128`]
+    UserVote {
+        user: String,
+        lp_token: String,
+        period: u64,
+    },
+    /// Returns the votes of a pool in a period using a [`PoolVotesResponse`] object
+    PoolVotes { lp_token: String, period: u64 },
+}
+
+/// This structure holds the parameters returned when querying a generator controller for a pool's votes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolVotesResponse {
+    /// The total vote weight the pool received in the period
+    pub total_weight: Uint128,
+    /// The timestamp (in seconds) when the period was tuned, if it was
+    pub tuned_at: Option<u64>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -1,5 +1,6 @@
 use crate::asset::addr_validate_to_lower;
-use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage};
+use cw2::{get_contract_version, ContractVersion};
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -138,3 +139,54 @@ pub fn claim_ownership(
         attr("new_owner", p.owner),
     ]))
 }
+
+/// Checks that a contract can be migrated to the specified code. The contract name stored with cw2 must match
+/// the name of the new code and the new version must be greater than the stored one, so migrations to another
+/// contract type or to an older version are rejected. Returns the stored [`ContractVersion`] on success.
+/// ## Params
+/// `storage` is the object of type [`Storage`].
+///
+/// `contract_name` is the name of the new contract code.
+///
+/// `contract_version` is the version of the new contract code.
+pub fn assert_migration_allowed(
+    storage: &dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+) -> StdResult<ContractVersion> {
+    let stored = get_contract_version(storage)?;
+
+    if stored.contract != contract_name {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from {} to {}",
+            stored.contract, contract_name
+        )));
+    }
+
+    if parse_version(contract_version)? <= parse_version(&stored.version)? {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from version {} to {}",
+            stored.version, contract_version
+        )));
+    }
+
+    Ok(stored)
+}
+
+/// Parses the major, minor and patch numbers of a semantic version. Pre-release and build metadata are ignored.
+/// ## Params
+/// `version` is the version to parse.
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let invalid = || StdError::generic_err(format!("Invalid contract version: {}", version));
+
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let numbers = core
+        .split('.')
+        .map(|number| number.parse::<u64>().map_err(|_| invalid()))
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    match numbers[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
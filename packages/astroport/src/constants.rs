@@ -0,0 +1,27 @@
+/// The denominator used for values expressed in basis points
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// The maximum total fee (in bps) that a pair type can charge on swaps
+pub const MAX_TOTAL_FEE_BPS: u16 = 10_000;
+
+/// The maximum fee (in bps) that a pair type can send to the Maker contract
+pub const MAX_MAKER_FEE_BPS: u16 = 10_000;
+
+/// The maximum share (in bps) of the swap commission that the factory allows fee discounts to waive
+pub const MAX_FEE_DISCOUNT_BPS: u16 = 5_000;
+
+/// The maximum amount of allocation points a single generator can have.
+/// Allocation points used to be stored as [`cosmwasm_std::Uint64`], so this keeps old values valid.
+pub const MAX_ALLOC_POINT: u128 = u64::MAX as u128;
+
+/// The amount of seconds in one day
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The maximum length (in seconds) of the TWAP window used by pair price guards
+pub const MAX_TWAP_WINDOW: u64 = SECONDS_PER_DAY;
+
+/// The length (in seconds) of one vxASTRO voting period
+pub const SECONDS_PER_PERIOD: u64 = 7 * SECONDS_PER_DAY;
+
+/// Decimal precision for TWAP results
+pub const TWAP_PRECISION: u8 = 6;
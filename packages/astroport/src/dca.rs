@@ -0,0 +1,167 @@
+use crate::asset::{Asset, AssetInfo};
+use crate::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Astroport router contract address used to make purchases
+    pub router: String,
+    /// The fixed tips paid to bots for every purchase. Schedules can only be funded with these assets
+    pub tips: Vec<Asset>,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Creates a schedule funded with native tokens. The deposit must be sent along with the message
+    CreateSchedule {
+        /// The deposit that funds the schedule purchases and tips
+        deposit: Asset,
+        /// The swap operations used for every purchase. The first operation must offer the deposited asset
+        operations: Vec<SwapOperation>,
+        /// The amount of deposited tokens swapped in every purchase
+        amount_per_interval: Uint128,
+        /// The minimum time (in seconds) between two purchases
+        interval: u64,
+        /// The maximum spread allowed in every swap of a purchase
+        max_slippage: Decimal,
+        /// The minimum amount of ask tokens bought per deposited token in every purchase. A purchase
+        /// that returns less fails, so it can't be sandwiched by moving the pool price first
+        min_price: Decimal,
+    },
+    /// Cancels a schedule and refunds the remaining deposit
+    /// ## Executor
+    /// Only the schedule owner can execute this
+    CancelSchedule {
+        /// The schedule identifier
+        schedule_id: u64,
+    },
+    /// Makes the next purchase of a schedule through the router. The schedule tip is sent to the sender
+    Purchase {
+        /// The schedule identifier
+        schedule_id: u64,
+    },
+    /// Updates contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new router contract address
+        router: Option<String>,
+        /// The new tips. Existing schedules keep the tip they were created with
+        tips: Option<Vec<Asset>>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes custom hooks for the CW20.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates a schedule funded with the sent CW20 tokens
+    CreateSchedule {
+        /// The swap operations used for every purchase. The first operation must offer the sent token
+        operations: Vec<SwapOperation>,
+        /// The amount of deposited tokens swapped in every purchase
+        amount_per_interval: Uint128,
+        /// The minimum time (in seconds) between two purchases
+        interval: u64,
+        /// The maximum spread allowed in every swap of a purchase
+        max_slippage: Decimal,
+        /// The minimum amount of ask tokens bought per deposited token in every purchase. A purchase
+        /// that returns less fails, so it can't be sandwiched by moving the pool price first
+        min_price: Decimal,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns a schedule using a [`ScheduleResponse`] object
+    Schedule { schedule_id: u64 },
+    /// Returns the schedules of a user using a [`SchedulesResponse`] object
+    Schedules {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport router contract address
+    pub router: Addr,
+    /// The fixed tips paid to bots for every purchase
+    pub tips: Vec<Asset>,
+}
+
+/// This structure holds the parameters returned when querying for a schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleResponse {
+    /// The schedule identifier
+    pub schedule_id: u64,
+    /// The address that created the schedule
+    pub owner: Addr,
+    /// The remaining deposit that funds the next purchases and tips
+    pub balance: Asset,
+    /// The asset bought in every purchase
+    pub ask_asset_info: AssetInfo,
+    /// The swap operations used for every purchase
+    pub operations: Vec<SwapOperation>,
+    /// The amount of deposited tokens swapped in every purchase
+    pub amount_per_interval: Uint128,
+    /// The minimum time (in seconds) between two purchases
+    pub interval: u64,
+    /// The maximum spread allowed in every swap of a purchase
+    pub max_slippage: Decimal,
+    /// The minimum amount of ask tokens bought per deposited token in every purchase
+    pub min_price: Decimal,
+    /// The amount of deposited tokens paid to the bot that makes a purchase
+    pub tip: Uint128,
+    /// The timestamp (in seconds) after which the next purchase can be made
+    pub next_purchase_at: u64,
+    /// The amount of purchases made so far
+    pub purchases: u64,
+}
+
+/// This structure holds the parameters returned when querying for the schedules of a user.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchedulesResponse {
+    /// The schedules
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
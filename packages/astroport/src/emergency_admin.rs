@@ -0,0 +1,81 @@
+use crate::factory::PairType;
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change the guardian set (the Assembly)
+    pub owner: String,
+    /// Addresses that are allowed to trigger emergency actions
+    pub guardians: Vec<String>,
+    /// The factory contract address
+    pub factory: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Pauses swaps and liquidity provision in the specified pairs
+    /// ## Executor
+    /// Only a guardian can execute this
+    PausePairs { pairs: Vec<String> },
+    /// Disables the creation of new pairs of the specified type in the factory
+    /// ## Executor
+    /// Only a guardian can execute this
+    DisablePairType { pair_type: PairType },
+    /// Sets the generator allocation point of the specified pool to zero
+    /// ## Executor
+    /// Only a guardian can execute this
+    DeactivatePool { lp_token: String },
+    /// Pauses fee collection in the Maker
+    /// ## Executor
+    /// Only a guardian can execute this
+    PauseMaker {},
+    /// Adds or removes guardians
+    /// ## Executor
+    /// Only the owner can execute this
+    UpdateGuardians {
+        /// Guardians to add
+        add: Option<Vec<String>>,
+        /// Guardians to remove
+        remove: Option<Vec<String>>,
+    },
+    /// Creates a request to change the contract's ownership
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the proposal to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change the guardian set
+    pub owner: Addr,
+    /// Addresses that are allowed to trigger emergency actions
+    pub guardians: Vec<Addr>,
+    /// The factory contract address
+    pub factory: Addr,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -1,5 +1,6 @@
 use crate::asset::{AssetInfo, PairInfo};
-use cosmwasm_std::{Addr, Binary};
+use crate::constants::{MAX_MAKER_FEE_BPS, MAX_TOTAL_FEE_BPS};
+use cosmwasm_std::{Addr, Binary, QuerierWrapper, StdResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
@@ -58,7 +59,7 @@ impl PairConfig {
     /// ## Params
     /// `&self` is the type of the caller object.
     pub fn valid_fee_bps(&self) -> bool {
-        self.total_fee_bps <= 10_000 && self.maker_fee_bps <= 10_000
+        self.total_fee_bps <= MAX_TOTAL_FEE_BPS && self.maker_fee_bps <= MAX_MAKER_FEE_BPS
     }
 }
 
@@ -93,6 +94,8 @@ pub enum ExecuteMsg {
         generator_address: Option<String>,
         /// CW1 whitelist contract code id used to store 3rd party rewards for staking Astroport LP tokens
         whitelist_code_id: Option<u64>,
+        /// Sets or removes the referral registry contract used by pairs to pay referral fees
+        referral_registry: Option<UpdateAddr>,
     },
     /// UpdatePairConfig updates the config for a pair type.
     UpdatePairConfig {
@@ -125,6 +128,72 @@ pub enum ExecuteMsg {
     DropOwnershipProposal {},
     /// Used to claim contract ownership.
     ClaimOwnership {},
+    /// UpdateSwapHooksWhitelist adds or removes contracts that are allowed to register swap hooks in pairs.
+    UpdateSwapHooksWhitelist {
+        /// Contracts to add to the whitelist
+        add: Option<Vec<String>>,
+        /// Contracts to remove from the whitelist
+        remove: Option<Vec<String>>,
+    },
+    /// RegisterExternalAdapter registers an adapter contract that the router may use for `ExternalSwap`
+    /// operations. Each external venue can only have one adapter. New adapters are enabled.
+    RegisterExternalAdapter {
+        /// The adapter contract address
+        adapter: String,
+        /// The name of the external venue the adapter swaps on
+        venue: String,
+    },
+    /// SetExternalAdapterEnabled enables or disables a registered adapter contract.
+    SetExternalAdapterEnabled {
+        /// The adapter contract address
+        adapter: String,
+        /// Whether the router may use the adapter
+        enabled: bool,
+    },
+    /// DeregisterExternalAdapter removes an adapter contract from the registry.
+    DeregisterExternalAdapter {
+        /// The adapter contract address
+        adapter: String,
+    },
+    /// UpdateEmergencyAdmin sets or removes the emergency admin contract. The emergency admin can pause pairs
+    /// and the Maker, disable pair types and zero generator allocations, but it can never move funds.
+    UpdateEmergencyAdmin {
+        /// The new emergency admin contract, or [`UpdateAddr::Remove`] to revoke its authority
+        emergency_admin: UpdateAddr,
+    },
+    /// UpdateFeeDiscount sets or removes the fee discount contract that pairs query to reduce the swap
+    /// commission of traders holding xASTRO or vxASTRO.
+    UpdateFeeDiscount {
+        /// The new fee discount contract, or [`UpdateAddr::Remove`] to disable fee discounts
+        discount_contract: UpdateAddr,
+        /// The maximum share (in bps) of the swap commission that can be waived. Ignored when removing the contract
+        max_discount_bps: u16,
+    },
+    /// DisablePairType disables the creation of new pairs of the specified type.
+    /// ## Executor
+    /// The owner or the emergency admin can execute this.
+    DisablePairType {
+        /// The pair type to disable
+        pair_type: PairType,
+    },
+    /// UpdatePriceGuard sets or removes the guard that rejects swaps in a constant product pair whose
+    /// execution price deviates too much from the pair's TWAP.
+    /// ## Executor
+    /// Only the owner can execute this.
+    UpdatePriceGuard {
+        /// The pair contract address
+        pair_addr: String,
+        /// The new price guard, or [`None`] to remove it
+        price_guard: Option<PriceGuard>,
+    },
+    /// MigratePairTypes saves the types of the next batch of pairs registered before V1.3.0, so they
+    /// are counted in the config's `pairs_count`.
+    /// ## Executor
+    /// Only the owner can execute this.
+    MigratePairTypes {
+        /// The maximum amount of pairs to migrate
+        limit: Option<u32>,
+    },
 }
 
 /// This structure describes the available query messages for the factory contract.
@@ -152,6 +221,25 @@ pub enum QueryMsg {
     },
     /// Returns a vector that contains blacklisted pair types
     BlacklistedPairTypes {},
+    /// Returns a vector with the addresses of contracts that are allowed to register swap hooks in pairs
+    SwapHooksWhitelist {},
+    /// Returns information about a registered external adapter using an [`ExternalAdapterInfo`] object
+    ExternalAdapter { adapter: String },
+    /// Returns a vector that contains the enabled external adapters using [`ExternalAdapterInfo`] objects
+    ActiveExternalAdapters {},
+    /// Returns the price guard of a pair using an [`Option`] of [`PriceGuard`]
+    PriceGuard { pair_addr: String },
+}
+
+/// This structure describes an adapter contract that the router may use for `ExternalSwap` operations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExternalAdapterInfo {
+    /// The adapter contract address
+    pub adapter: Addr,
+    /// The name of the external venue the adapter swaps on
+    pub venue: String,
+    /// Whether the router may use the adapter
+    pub enabled: bool,
 }
 
 /// A custom struct for each query response that returns general contract settings/configs.
@@ -169,6 +257,14 @@ pub struct ConfigResponse {
     pub generator_address: Option<Addr>,
     /// CW1 whitelist contract code id used to store 3rd party rewards for staking Astroport LP tokens
     pub whitelist_code_id: u64,
+    /// Address of the referral registry contract used by pairs to pay referral fees
+    pub referral_registry: Option<Addr>,
+    /// Address of the emergency admin contract that can pause pairs and the Maker, disable pair types
+    /// and zero generator allocations
+    pub emergency_admin: Option<Addr>,
+    /// The number of registered pairs of each pair type. Pairs registered before V1.3.0 are only counted
+    /// once [`ExecuteMsg::MigratePairTypes`] has migrated them
+    pub pairs_count: Vec<(PairType, u64)>,
 }
 
 /// This structure stores the parameters used in a migration message.
@@ -193,6 +289,28 @@ pub struct FeeInfoResponse {
     pub total_fee_bps: u16,
     /// Amount of fees (in bps) sent to the Maker contract
     pub maker_fee_bps: u16,
+    /// Address of the referral registry contract used to pay referral fees
+    pub referral_registry: Option<Addr>,
+    /// The fee discount contract and the maximum discount it may grant
+    pub fee_discount: Option<FeeDiscountConfig>,
+}
+
+/// This structure describes the fee discount contract that pairs query when computing the swap commission.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountConfig {
+    /// Address of the fee discount contract
+    pub discount_contract: Addr,
+    /// The maximum share (in bps) of the swap commission that can be waived
+    pub max_discount_bps: u16,
+}
+
+/// This structure describes the guard that protects a pair from swaps at prices far from its TWAP.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceGuard {
+    /// The maximum deviation (in bps) of the execution price from the TWAP
+    pub max_deviation_bps: u16,
+    /// The minimum length (in seconds) of the window over which the TWAP is computed
+    pub twap_window: u64,
 }
 
 /// This is an enum used for setting and removing a contract address.
@@ -204,3 +322,66 @@ pub enum UpdateAddr {
     /// Removes a contract address.
     Remove {},
 }
+
+/// ## Description
+/// A typed client for querying the factory contract from other contracts.
+pub struct FactoryQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+    factory: Addr,
+}
+
+impl<'a> FactoryQuerier<'a> {
+    /// ## Description
+    /// Creates a new client for the specified factory contract.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **factory** is an object of type [`Addr`]. This is the factory contract address.
+    pub fn new(querier: &'a QuerierWrapper<'a>, factory: Addr) -> Self {
+        FactoryQuerier { querier, factory }
+    }
+
+    /// ## Description
+    /// Returns the factory configuration.
+    pub fn config(&self) -> StdResult<ConfigResponse> {
+        self.querier
+            .query_wasm_smart(&self.factory, &QueryMsg::Config {})
+    }
+
+    /// ## Description
+    /// Returns information about the pair registered for the specified assets.
+    /// ## Params
+    /// * **asset_infos** is an array with two items of type [`AssetInfo`]. These are the assets in the pair.
+    pub fn pair_info(&self, asset_infos: &[AssetInfo; 2]) -> StdResult<PairInfo> {
+        self.querier.query_wasm_smart(
+            &self.factory,
+            &QueryMsg::Pair {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns information about registered pairs, ordered by their assets.
+    /// ## Params
+    /// * **start_after** is an [`Option`] field that contains an array with two items of type [`AssetInfo`]. This is the pair after which to start reading.
+    ///
+    /// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of pairs to return.
+    pub fn pairs(
+        &self,
+        start_after: Option<[AssetInfo; 2]>,
+        limit: Option<u32>,
+    ) -> StdResult<PairsResponse> {
+        self.querier
+            .query_wasm_smart(&self.factory, &QueryMsg::Pairs { start_after, limit })
+    }
+
+    /// ## Description
+    /// Returns the fee structure of the specified pair type.
+    /// ## Params
+    /// * **pair_type** is an object of type [`PairType`]. This is the pair type we return the fees for.
+    pub fn fee_info(&self, pair_type: PairType) -> StdResult<FeeInfoResponse> {
+        self.querier
+            .query_wasm_smart(&self.factory, &QueryMsg::FeeInfo { pair_type })
+    }
+}
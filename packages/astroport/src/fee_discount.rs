@@ -0,0 +1,160 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::factory::UpdateAddr;
+use crate::querier::FeeInfo;
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of discount tiers
+pub const MAX_TIERS: usize = 10;
+
+/// The fixed point precision of [`Decimal`] values
+const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+/// This structure describes a discount tier. A trader qualifies for a tier if they hold at least one
+/// of the configured minimum amounts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountTier {
+    /// The minimum xASTRO balance needed to qualify for the tier
+    pub min_xastro: Option<Uint128>,
+    /// The minimum vxASTRO voting power needed to qualify for the tier
+    pub min_vxastro: Option<Uint128>,
+    /// The share (in bps) of the swap commission waived for traders in this tier
+    pub discount_bps: u16,
+}
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The xASTRO token contract address
+    pub xastro_token: String,
+    /// The voting escrow (vxASTRO) contract address
+    pub voting_escrow: Option<String>,
+    /// The discount tiers
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Updates the voting escrow contract and the discount tiers
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// Sets or removes the voting escrow (vxASTRO) contract
+        voting_escrow: Option<UpdateAddr>,
+        /// The new discount tiers
+        tiers: Option<Vec<FeeDiscountTier>>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the discount a trader is entitled to using a [`FeeDiscountResponse`] object
+    FeeDiscount { trader: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The xASTRO token contract address
+    pub xastro_token: Addr,
+    /// The voting escrow (vxASTRO) contract address
+    pub voting_escrow: Option<Addr>,
+    /// The discount tiers
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+/// This structure holds the parameters returned when querying for the discount of a trader.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountResponse {
+    /// The share (in bps) of the swap commission waived for the trader
+    pub discount_bps: u16,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns the discount a trader is entitled to using a [`FeeDiscountResponse`] object.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **discount_contract** is an object of type [`Addr`]. This is the fee discount contract address.
+///
+/// * **trader** is an object of type [`Addr`]. This is the trader to return the discount for.
+pub fn query_fee_discount(
+    querier: &QuerierWrapper,
+    discount_contract: &Addr,
+    trader: &Addr,
+) -> StdResult<FeeDiscountResponse> {
+    querier.query_wasm_smart(
+        discount_contract,
+        &QueryMsg::FeeDiscount {
+            trader: trader.to_string(),
+        },
+    )
+}
+
+/// ## Description
+/// Returns the commission rate charged to a trader after applying their fee discount. The discount
+/// is capped by the maximum set in the factory. Swaps must never fail because of the discount contract,
+/// so a failed discount query is treated as no discount.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **fee_info** is an object of type [`FeeInfo`]. This is the fee structure of the pair.
+///
+/// * **trader** is an object of type [`Addr`]. This is the trader that swaps in the pair.
+pub fn discounted_fee_rate(querier: &QuerierWrapper, fee_info: &FeeInfo, trader: &Addr) -> Decimal {
+    let fee_discount = match &fee_info.fee_discount {
+        Some(fee_discount) => fee_discount,
+        None => return fee_info.total_fee_rate,
+    };
+
+    let discount_bps = query_fee_discount(querier, &fee_discount.discount_contract, trader)
+        .map(|res| {
+            res.discount_bps
+                .min(fee_discount.max_discount_bps)
+                .min(BPS_DENOMINATOR)
+        })
+        .unwrap_or_default();
+    if discount_bps == 0 {
+        return fee_info.total_fee_rate;
+    }
+
+    // Decimal * Decimal is not available, so scale the rate back to its atomics
+    let atomics = fee_info.total_fee_rate * Uint128::from(DECIMAL_FRACTIONAL);
+    Decimal::from_ratio(
+        atomics.u128() * (BPS_DENOMINATOR - discount_bps) as u128,
+        DECIMAL_FRACTIONAL * BPS_DENOMINATOR as u128,
+    )
+}
@@ -1,6 +1,6 @@
-use crate::asset::AssetInfo;
-use crate::factory::PairType;
-use cosmwasm_std::{Addr, Binary, Decimal, Uint128, Uint64};
+use crate::asset::{Asset, AssetInfo};
+use crate::factory::{PairType, UpdateAddr};
+use cosmwasm_std::{Addr, Binary, Decimal, QuerierWrapper, StdResult, Uint128, Uint64};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -58,9 +58,10 @@ pub enum ExecuteMsg {
         /// This flag determines whether the pool gets 3rd party token rewards
         has_asset_rewards: bool,
     },
-    /// Update rewards and return it to user.
+    /// Sends the sender's pending rewards from the given generators without changing their staked amounts.
+    /// ASTRO that couldn't be claimed from vesting before is sent as well, even without any generators
     ClaimRewards {
-        /// the LP token contract address
+        /// The LP tokens whose generator rewards are claimed
         lp_tokens: Vec<String>,
     },
     /// Withdraw LP tokens from the Generator
@@ -80,13 +81,23 @@ pub enum ExecuteMsg {
         /// The full list of allowed proxy contracts
         proxies: Vec<String>,
     },
-    /// Sends orphan proxy rewards (which were left behind after emergency withdrawals) to another address
+    /// Sends the orphan rewards of every reward proxy (which were left behind after emergency withdrawals) to another address
     SendOrphanProxyReward {
         /// The transfer recipient
         recipient: String,
         /// The address of the LP token contract for which we send orphaned rewards
         lp_token: String,
     },
+    /// Sends the orphan stake of a generator (LP tokens that were staked under an invalid address before
+    /// V1.3.0, see [`ExecuteMsg::MigrateUsers`]) to another address
+    /// ## Executor
+    /// Only the owner can execute this.
+    SendOrphanStake {
+        /// The transfer recipient
+        recipient: String,
+        /// The LP token whose orphan stake we send
+        lp_token: String,
+    },
     /// Receives a message of type [`Cw20ReceiveMsg`]
     Receive(Cw20ReceiveMsg),
     /// Set a new amount of ASTRO to distribute per block
@@ -120,11 +131,17 @@ pub enum ExecuteMsg {
         /// Proxy contracts to remove
         remove: Option<Vec<String>>,
     },
-    /// Sets a new proxy contract for a specific generator
-    /// Sets a proxy for the pool
+    /// Sets a new proxy contract that stakes the LP tokens of a specific generator. If the generator already has
+    /// such a proxy, its LP tokens and undistributed proxy rewards are moved to the new proxy, which must pay the
+    /// same reward token for the same LP token
     /// ## Executor
     /// Only the current owner or generator controller can execute this
     MoveToProxy { lp_token: String, proxy: String },
+    /// Adds a reward proxy that pays extra rewards to the stakers of a specific generator without holding their
+    /// LP tokens. The generator must already have a proxy that stakes its LP tokens
+    /// ## Executor
+    /// Only the owner can execute this
+    AddRewardProxy { lp_token: String, proxy: String },
     /// Add or remove token to blocked list
     UpdateTokensBlockedlist {
         /// Tokens to add
@@ -132,10 +149,222 @@ pub enum ExecuteMsg {
         /// Tokens to remove
         remove: Option<Vec<AssetInfo>>,
     },
+    /// Add or remove LP tokens that are not minted by a pair registered in the factory. External LP tokens
+    /// can be set up in generators without being checked against the factory
+    /// ## Executor
+    /// Only the owner can execute this
+    UpdateExternalLpTokens {
+        /// LP tokens to add
+        add: Option<Vec<String>>,
+        /// LP tokens to remove
+        remove: Option<Vec<String>>,
+    },
     /// Sets the allocation point to zero for the specified pool
+    /// ## Executor
+    /// The factory or the factory's emergency admin can execute this
     DeactivatePool { lp_token: String },
     /// Sets the allocation point to zero for each pool by the pair type
     DeactivatePools { pair_types: Vec<PairType> },
+    /// Removes an inactive generator without stakers. The remaining alternative reward budget is sent to the owner
+    /// ## Executor
+    /// Only the owner can execute this.
+    RemovePool {
+        /// The LP token whose generator is removed
+        lp_token: String,
+    },
+    /// Updates the accrued rewards of a specific generator. The sender is tipped in ASTRO from the keeper
+    /// tip budget if the generator was stale (according to the keeper incentive settings)
+    UpdatePoolRewards {
+        /// The LP token whose generator we update
+        lp_token: String,
+    },
+    /// Updates the accrued rewards of all active generators. The sender is tipped in ASTRO from the keeper
+    /// tip budget for every stale generator (according to the keeper incentive settings)
+    MassUpdatePools {},
+    /// Sets the keeper incentive settings. Passing `None` disables keeper tips. Tips are paid from
+    /// the budget funded with [`Cw20HookMsg::FundKeeperTips`], never from the vesting allocation
+    /// ## Executor
+    /// Only the owner can execute this.
+    SetKeeperIncentive {
+        /// The new keeper incentive settings
+        keeper_incentive: Option<KeeperIncentive>,
+    },
+    /// Sends all native coins held by the Generator to the recipient. The Generator doesn't accept native
+    /// coins, so any native balance is stuck
+    /// ## Executor
+    /// Only the owner can execute this.
+    SweepNativeTokens {
+        /// The recipient of the coins. Defaults to the owner
+        recipient: Option<String>,
+    },
+    /// Allows an operator (e.g. an auto-compounder or keeper) to claim the sender's rewards from a specific
+    /// generator. Claimed rewards are always sent to the sender
+    ApproveOperator {
+        /// The address allowed to claim the sender's rewards
+        operator: String,
+        /// The LP token whose generator rewards the operator can claim
+        lp_token: String,
+    },
+    /// Revokes an operator's approval to claim the sender's rewards from a specific generator
+    RevokeOperator {
+        /// The operator whose approval is revoked
+        operator: String,
+        /// The LP token whose generator rewards the operator can no longer claim
+        lp_token: String,
+    },
+    /// Claims a user's rewards from a specific generator and sends them to the user
+    /// ## Executor
+    /// Only an operator approved by the user for this LP token can execute this.
+    ClaimRewardsFor {
+        /// The user whose rewards are claimed
+        user: String,
+        /// The LP token whose generator rewards are claimed
+        lp_token: String,
+    },
+    /// Sets a generator's alternative base reward. The reward token can't be changed once set.
+    /// The reward is funded by sending reward tokens with [`Cw20HookMsg::FundAltReward`]
+    /// ## Executor
+    /// Only the owner can execute this.
+    SetAltReward {
+        /// The LP token whose generator emits the alternative reward
+        lp_token: String,
+        /// The CW20 token emitted as the alternative reward
+        reward_token: String,
+        /// The amount of reward tokens distributed per block to the generator's stakers
+        tokens_per_block: Uint128,
+    },
+    /// Schedules a generator's allocation points to change at a future block. The change is applied
+    /// automatically, and rewards are accounted at the new allocation from exactly that block
+    /// ## Executor
+    /// Only the owner can execute this.
+    ScheduleSet {
+        /// The LP token whose generator allocation points change
+        lp_token: String,
+        /// The new allocation points
+        alloc_point: Uint128,
+        /// The block from which the new allocation points are used
+        effective_block: u64,
+    },
+    /// Cancels a scheduled allocation point change
+    /// ## Executor
+    /// Only the owner can execute this.
+    CancelScheduledSet {
+        /// The LP token whose generator allocation points were scheduled to change
+        lp_token: String,
+        /// The block from which the new allocation points were to be used
+        effective_block: u64,
+    },
+    /// Appends an epoch to the emission schedule. From its start block, the given amount of ASTRO is
+    /// distributed per block among all active generators
+    /// ## Executor
+    /// Only the owner can execute this.
+    AddEmissionEpoch {
+        /// The block from which the epoch's emission rate is used. Must be after the last scheduled epoch
+        start_block: u64,
+        /// The amount of ASTRO distributed per block during the epoch
+        tokens_per_block: Uint128,
+    },
+    /// Removes an epoch that hasn't started yet from the emission schedule
+    /// ## Executor
+    /// Only the owner can execute this.
+    CancelEmissionEpoch {
+        /// The start block of the epoch to remove
+        start_block: u64,
+    },
+    /// Sets or removes the boost provider that returns per-user multipliers applied to the stake
+    /// used for ASTRO rewards
+    /// ## Executor
+    /// Only the owner can execute this.
+    SetBoostProvider {
+        /// The new boost provider contract, or [`UpdateAddr::Remove`] to disable boosts
+        boost_provider: UpdateAddr,
+        /// The maximum multiplier applied to a staker's LP amount. Ignored when removing the contract
+        max_multiplier: Decimal,
+    },
+    /// Sets or removes the voting escrow contract whose vxASTRO voting power boosts the stake used for
+    /// ASTRO rewards
+    /// ## Executor
+    /// Only the owner can execute this.
+    SetVotingEscrow {
+        /// The new voting escrow contract, or [`UpdateAddr::Remove`] to disable vxASTRO boosts
+        voting_escrow: UpdateAddr,
+    },
+    /// Recomputes a staker's boost in a specific generator: the larger of the boost provider multiplier and
+    /// the vxASTRO boost from the staker's current voting power. Used to re-boost a position after a lock
+    /// change. Pending rewards are sent to the staker and the stake doesn't change
+    /// ## Executor
+    /// Anyone can execute this for any staker. The boost only depends on the staker's current boost
+    /// multiplier and voting power
+    CheckpointUserBoost {
+        /// The LP token staked by the user
+        lp_token: String,
+        /// The staker whose boost is updated
+        user: String,
+    },
+    /// Migrates the next batch of stakes stored before V1.3.0. Stakes stored under a non-normalized
+    /// address are merged into the normalized one. Stakes stored under an invalid address can never be
+    /// withdrawn, so they become the generator's orphan stake, which doesn't earn rewards
+    /// ## Executor
+    /// Only the owner can execute this.
+    MigrateUsers {
+        /// The maximum amount of stakes to migrate
+        limit: Option<u32>,
+    },
+}
+
+/// This structure describes the boost provider used by the Generator.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostProvider {
+    /// The contract that returns per-user boost multipliers
+    pub contract: Addr,
+    /// The maximum multiplier applied to a staker's LP amount
+    pub max_multiplier: Decimal,
+}
+
+/// This structure describes the queries a boost provider contract must support.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostQueryMsg {
+    /// Returns the boost multiplier of a user using a [`BoostResponse`] object
+    Boost { user: String },
+}
+
+/// This structure holds the parameters returned when querying a boost provider for a user's multiplier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostResponse {
+    /// The multiplier applied to the user's LP amount. Values below one mean no boost
+    pub multiplier: Decimal,
+}
+
+/// This structure describes an allocation point change scheduled for a future block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledSet {
+    /// The LP token whose generator allocation points change
+    pub lp_token: Addr,
+    /// The new allocation points
+    pub alloc_point: Uint128,
+    /// The block from which the new allocation points are used
+    pub effective_block: u64,
+}
+
+/// This structure describes an epoch of the ASTRO emission schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionEpoch {
+    /// The block from which the epoch's emission rate is used
+    pub start_block: u64,
+    /// The amount of ASTRO distributed per block among all active generators
+    pub tokens_per_block: Uint128,
+}
+
+/// This structure describes the ASTRO tip paid to whoever updates stale generators.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KeeperIncentive {
+    /// The amount of ASTRO paid for every stale generator that gets updated
+    pub tip_per_pool: Uint128,
+    /// The maximum amount of ASTRO paid in a single call
+    pub max_tip: Uint128,
+    /// The amount of blocks since a generator's last update after which it's considered stale
+    pub staleness_threshold: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -147,14 +376,27 @@ pub enum QueryMsg {
     PoolLength {},
     /// Deposit returns the LP token amount deposited in a specific generator
     Deposit { lp_token: String, user: String },
+    /// UserInfo returns the full staking information of a user in a specific generator in a [`UserInfoResponse`] object
+    UserInfo { lp_token: String, user: String },
     /// PendingToken returns the amount of rewards that can be claimed by an account that deposited a specific LP token in a generator
     PendingToken { lp_token: String, user: String },
+    /// PendingTokenList returns the amount of rewards that can be claimed by an account in multiple generators.
+    /// If `lp_tokens` is not specified, all the generators in which the account has funds are used
+    PendingTokenList {
+        user: String,
+        lp_tokens: Option<Vec<String>>,
+    },
     /// Config returns the main contract parameters
     Config {},
     /// RewardInfo returns reward information for a specified LP token
     RewardInfo { lp_token: String },
     /// OrphanProxyRewards returns orphaned reward information for the specified LP token
     OrphanProxyRewards { lp_token: String },
+    /// OrphanStake returns the amount of LP tokens staked in a generator under invalid addresses before V1.3.0
+    OrphanStake { lp_token: String },
+    /// UnpaidAstro returns the ASTRO rewards that couldn't be claimed from the vesting contract for a user.
+    /// They are sent with the user's next claim
+    UnpaidAstro { user: String },
     /// PoolInfo returns information about a pool associated with the specified LP token alongside
     /// the total pending amount of ASTRO and proxy rewards claimable by generator stakers (for that LP token)
     PoolInfo { lp_token: String },
@@ -166,8 +408,47 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Returns a list of generators in which a specific account currently has funds
+    StakedPools {
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns the blocked list of tokens
     BlockedListTokens {},
+    /// Returns everything needed to compute a generator's APR in a [`PoolAprInputsResponse`] object
+    PoolAprInputs { lp_token: String },
+    /// DepositAt returns the LP token amount deposited in a specific generator at the beginning of a past block
+    DepositAt {
+        lp_token: String,
+        user: String,
+        block: u64,
+    },
+    /// PoolInfoAt returns the stored [`PoolInfo`] of a specific generator at the beginning of a past block
+    PoolInfoAt { lp_token: String, block: u64 },
+    /// EmissionRate returns the amount of ASTRO distributed per block right now in an [`EmissionRateResponse`] object
+    EmissionRate {},
+    /// OperatorApproved returns whether an operator can claim a user's rewards from a specific generator
+    OperatorApproved {
+        user: String,
+        lp_token: String,
+        operator: String,
+    },
+    /// AltReward returns a generator's alternative base reward settings in an [`Option<AltRewardInfo>`] object
+    AltReward { lp_token: String },
+    /// PendingAltReward returns the amount of alternative base rewards that can be claimed by a staker of a specific generator
+    PendingAltReward { lp_token: String, user: String },
+    /// ScheduledSets returns the pending allocation point changes ordered by their effective block
+    /// in a vector of [`ScheduledSet`] objects
+    ScheduledSets {},
+    /// EmissionSchedule returns the emission epochs that haven't been applied yet, ordered by their start block,
+    /// in a vector of [`EmissionEpoch`] objects
+    EmissionSchedule {},
+    /// PoolList returns all the existing generators, ordered by LP token, in a vector of [`PoolListResponse`] objects
+    PoolList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 /// This structure holds the response returned when querying the total length of the array that keeps track of instantiated generators
@@ -182,8 +463,32 @@ pub struct PoolLengthResponse {
 pub struct PendingTokenResponse {
     /// The amount of pending ASTRO
     pub pending: Uint128,
-    /// The amount of pending 3rd party reward tokens
-    pub pending_on_proxy: Option<Uint128>,
+    /// The amount of pending 3rd party reward tokens of every reward proxy
+    pub pending_on_proxy: Option<Vec<Asset>>,
+}
+
+/// This structure holds the amount of pending rewards that can be claimed from a specific generator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolPendingTokenResponse {
+    /// The LP token of the generator
+    pub lp_token: String,
+    /// The amount of pending ASTRO
+    pub pending: Uint128,
+    /// The amount of pending 3rd party reward tokens of every reward proxy
+    pub pending_on_proxy: Option<Vec<Asset>>,
+}
+
+/// This structure holds the response returned when querying the staking information of a user in a generator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UserInfoResponse {
+    /// The amount of LP tokens staked
+    pub amount: Uint128,
+    /// The amount of ASTRO rewards the user already received or is not eligible for
+    pub reward_debt: Uint128,
+    /// The amount of rewards the user already received or is not eligible for from every reward proxy
+    pub reward_debt_proxy: Vec<(Addr, Uint128)>,
+    /// The extra stake credited by the user's boost at the last checkpoint
+    pub boost_bonus: Uint128,
 }
 
 /// This structure describes the main information of pool
@@ -192,15 +497,69 @@ pub struct PoolInfo {
     /// Accumulated amount of reward per share unit. Used for reward calculations
     pub last_reward_block: Uint64,
     pub accumulated_rewards_per_share: Decimal,
-    /// the reward proxy contract
-    pub reward_proxy: Option<Addr>,
-    pub accumulated_proxy_rewards_per_share: Decimal,
-    /// for calculation of new proxy rewards
-    pub proxy_reward_balance_before_update: Uint128,
-    /// the orphan proxy rewards which are left by emergency withdrawals
-    pub orphan_proxy_rewards: Uint128,
+    /// The reward proxy contracts. The first proxy holds all LP tokens staked in the generator,
+    /// the others pay their rewards without holding LP tokens
+    pub reward_proxies: Vec<RewardProxyInfo>,
     /// The pool has assets giving additional rewards
     pub has_asset_rewards: bool,
+    /// The alternative base reward emitted by the generator from its own budget, independently from ASTRO
+    #[serde(default)]
+    pub alt_reward: Option<AltRewardInfo>,
+    /// Accumulated amount of alternative base rewards per share unit
+    #[serde(default)]
+    pub accumulated_alt_rewards_per_share: Decimal,
+    /// The extra stake credited by the boosts of all stakers at their last checkpoint. Only ASTRO rewards are boosted
+    #[serde(default)]
+    pub total_boost_bonus: Uint128,
+}
+
+impl PoolInfo {
+    /// Returns the reward proxy that holds the LP tokens staked in the generator.
+    pub fn staking_proxy(&self) -> Option<&Addr> {
+        self.reward_proxies
+            .first()
+            .map(|reward_proxy| &reward_proxy.proxy)
+    }
+}
+
+/// This structure describes a reward proxy of a generator.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardProxyInfo {
+    /// The reward proxy contract
+    pub proxy: Addr,
+    /// Accumulated amount of proxy rewards per share unit
+    pub accumulated_rewards_per_share: Decimal,
+    /// The proxy reward balance after the last update, minus the proxy rewards sent since then.
+    /// Used for calculation of new proxy rewards
+    pub reward_balance_before_update: Uint128,
+    /// The orphan proxy rewards which are left by emergency withdrawals
+    pub orphan_rewards: Uint128,
+    /// The amount of proxy rewards per block observed between the two latest reward updates
+    pub reward_per_block: Uint128,
+}
+
+impl RewardProxyInfo {
+    /// Creates the reward info of a proxy that hasn't paid any rewards yet.
+    pub fn new(proxy: Addr) -> Self {
+        RewardProxyInfo {
+            proxy,
+            accumulated_rewards_per_share: Decimal::zero(),
+            reward_balance_before_update: Uint128::zero(),
+            orphan_rewards: Uint128::zero(),
+            reward_per_block: Uint128::zero(),
+        }
+    }
+}
+
+/// This structure describes a generator's alternative base reward.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AltRewardInfo {
+    /// The CW20 token emitted as the reward
+    pub reward_token: Addr,
+    /// The amount of reward tokens distributed per block to the generator's stakers
+    pub tokens_per_block: Uint128,
+    /// The amount of funded reward tokens that have not been distributed yet. Emissions stop when it runs out
+    pub budget: Uint128,
 }
 
 /// This structure holds the response returned when querying for the token addresses used to reward a specific generator
@@ -208,8 +567,8 @@ pub struct PoolInfo {
 pub struct RewardInfoResponse {
     /// The address of the base reward token
     pub base_reward_token: Addr,
-    /// The address of the 3rd party reward token
-    pub proxy_reward_token: Option<Addr>,
+    /// The addresses of the 3rd party reward tokens, one for each reward proxy
+    pub proxy_reward_tokens: Vec<Addr>,
 }
 
 /// This structure holds the response returned when querying for a pool's information
@@ -227,20 +586,53 @@ pub struct PoolInfoResponse {
     pub accumulated_rewards_per_share: Decimal,
     /// Pending amount of total ASTRO rewards which are claimable by stakers right now
     pub pending_astro_rewards: Uint128,
-    /// The address of the 3rd party reward proxy contract
-    pub reward_proxy: Option<Addr>,
-    /// Pending amount of total proxy rewards which are claimable by stakers right now
-    pub pending_proxy_rewards: Option<Uint128>,
-    /// Total amount of 3rd party token rewards already accumulated per LP token staked
-    pub accumulated_proxy_rewards_per_share: Decimal,
-    /// Reward balance for the dual rewards proxy before updating accrued rewards
-    pub proxy_reward_balance_before_update: Uint128,
-    /// The amount of orphan proxy rewards which are left behind by emergency withdrawals and not yet transferred out
-    pub orphan_proxy_rewards: Uint128,
+    /// The 3rd party reward proxies with their accumulated rewards per LP token staked, reward balances
+    /// before updating accrued rewards and orphan rewards left behind by emergency withdrawals
+    pub reward_proxies: Vec<RewardProxyInfo>,
+    /// Pending amount of total rewards of every reward proxy which are claimable by stakers right now
+    pub pending_proxy_rewards: Vec<(Addr, Uint128)>,
     /// Total amount of lp tokens staked in the pool's generator
     pub lp_supply: Uint128,
 }
 
+/// This structure holds the inputs needed to compute a generator's APR
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolAprInputsResponse {
+    /// The LP token staked in the generator
+    pub lp_token: Addr,
+    /// The pair that issues the LP token
+    pub pair: Addr,
+    /// Amount of ASTRO tokens being distributed per block to this LP pool
+    pub astro_tokens_per_block: Uint128,
+    /// The 3rd party reward tokens with the amount of rewards per block observed between the two latest
+    /// reward updates, one for each reward proxy
+    pub proxy_rewards_per_block: Vec<(Addr, Uint128)>,
+    /// Total amount of LP tokens staked in the pool's generator
+    pub lp_staked: Uint128,
+    /// Total supply of the LP token
+    pub lp_total_supply: Uint128,
+    /// The pair's reserves
+    pub assets: [Asset; 2],
+    /// The total fee rate charged on swaps in the pair
+    pub total_fee_rate: Decimal,
+    /// Current block number
+    pub current_block: u64,
+}
+
+/// This structure holds the response returned when querying the current ASTRO emission rate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionRateResponse {
+    /// Amount of ASTRO distributed per block across all generators at the current block.
+    /// This is zero before the start block or when no generator is active
+    pub tokens_per_block: Uint128,
+    /// Amount of ASTRO per block set in the contract configuration or by the current emission epoch
+    pub configured_tokens_per_block: Uint128,
+    /// The block number when the ASTRO distribution starts
+    pub start_block: Uint64,
+    /// Current block number
+    pub current_block: u64,
+}
+
 /// This structure holds the response returned when querying the contract for general parameters
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
@@ -268,6 +660,16 @@ pub struct ConfigResponse {
     pub blocked_list_tokens: Vec<AssetInfo>,
     /// The guardian address
     pub guardian: Option<Addr>,
+    /// The ASTRO tip paid to whoever updates stale generators
+    pub keeper_incentive: Option<KeeperIncentive>,
+    /// The ASTRO held by the Generator to pay keeper tips
+    pub keeper_tip_budget: Uint128,
+    /// The boost provider that returns per-user multipliers for ASTRO rewards
+    pub boost_provider: Option<BoostProvider>,
+    /// The voting escrow contract whose vxASTRO voting power boosts ASTRO rewards
+    pub voting_escrow: Option<Addr>,
+    /// LP tokens that can be set up in generators without a pair registered in the factory
+    pub external_lp_tokens: Vec<Addr>,
 }
 
 /// This structure describes a migration message.
@@ -284,6 +686,12 @@ pub enum Cw20HookMsg {
     Deposit {},
     /// DepositFor performs a token deposit on behalf of another address that's not the message sender.
     DepositFor(Addr),
+    /// FundAltReward adds the sent tokens to the budget of a generator's alternative base reward.
+    /// The sent token must be the alternative reward token
+    FundAltReward { lp_token: String },
+    /// FundKeeperTips adds the sent ASTRO to the budget that keeper tips are paid from.
+    /// The sent token must be ASTRO
+    FundKeeperTips {},
 }
 
 /// This structure holds the parameters used to return information about a staked in
@@ -295,3 +703,131 @@ pub struct StakerResponse {
     // The amount that the staker currently has in the generator
     pub amount: Uint128,
 }
+
+/// This structure holds the parameters used to return information about a generator in which
+/// a specific account staked.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct StakedPoolResponse {
+    /// The LP token of the generator
+    pub lp_token: String,
+    /// The amount that the account currently has in the generator
+    pub amount: Uint128,
+}
+
+/// This structure holds the stored state of a generator returned when listing generators.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PoolListResponse {
+    /// The LP token of the generator
+    pub lp_token: Addr,
+    /// The current allocation points of the generator. Inactive generators have none
+    pub alloc_point: Uint128,
+    /// The block at which the generator's rewards were last updated
+    pub last_reward_block: u64,
+    /// The reward proxy contracts with their accumulated rewards per share unit
+    pub reward_proxies: Vec<RewardProxyInfo>,
+    /// Whether the generator's pair pays its own rewards to stakers
+    pub has_asset_rewards: bool,
+    /// Accumulated amount of ASTRO rewards per share unit
+    pub accumulated_rewards_per_share: Decimal,
+    /// Accumulated amount of alternative base rewards per share unit
+    pub accumulated_alt_rewards_per_share: Decimal,
+}
+
+/// ## Description
+/// A typed client for querying the Generator contract from other contracts.
+pub struct GeneratorQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+    generator: Addr,
+}
+
+impl<'a> GeneratorQuerier<'a> {
+    /// ## Description
+    /// Creates a new client for the specified Generator contract.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **generator** is an object of type [`Addr`]. This is the Generator contract address.
+    pub fn new(querier: &'a QuerierWrapper<'a>, generator: Addr) -> Self {
+        GeneratorQuerier { querier, generator }
+    }
+
+    /// ## Description
+    /// Returns the amount of LP tokens that the specified user deposited in a generator.
+    /// ## Params
+    /// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+    ///
+    /// * **user** is an object of type [`Addr`]. This is the user whose deposit we query.
+    pub fn deposit_of(&self, lp_token: &Addr, user: &Addr) -> StdResult<Uint128> {
+        self.querier.query_wasm_smart(
+            &self.generator,
+            &QueryMsg::Deposit {
+                lp_token: lp_token.to_string(),
+                user: user.to_string(),
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns the staking information of the specified user in a generator.
+    /// ## Params
+    /// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+    ///
+    /// * **user** is an object of type [`Addr`]. This is the user whose staking information we query.
+    pub fn user_info(&self, lp_token: &Addr, user: &Addr) -> StdResult<UserInfoResponse> {
+        self.querier.query_wasm_smart(
+            &self.generator,
+            &QueryMsg::UserInfo {
+                lp_token: lp_token.to_string(),
+                user: user.to_string(),
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns the amount of LP tokens that the specified user had deposited in a generator at the beginning of a past block.
+    /// ## Params
+    /// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+    ///
+    /// * **user** is an object of type [`Addr`]. This is the user whose deposit we query.
+    ///
+    /// * **block** is an object of type [`u64`]. This is the block at which we read the deposit.
+    pub fn deposit_at(&self, lp_token: &Addr, user: &Addr, block: u64) -> StdResult<Uint128> {
+        self.querier.query_wasm_smart(
+            &self.generator,
+            &QueryMsg::DepositAt {
+                lp_token: lp_token.to_string(),
+                user: user.to_string(),
+                block,
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns the amount of ASTRO and proxy rewards that the specified user can claim from a generator.
+    /// ## Params
+    /// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+    ///
+    /// * **user** is an object of type [`Addr`]. This is the user whose pending rewards we query.
+    pub fn pending_token(&self, lp_token: &Addr, user: &Addr) -> StdResult<PendingTokenResponse> {
+        self.querier.query_wasm_smart(
+            &self.generator,
+            &QueryMsg::PendingToken {
+                lp_token: lp_token.to_string(),
+                user: user.to_string(),
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns information about the generator of the specified LP token.
+    /// ## Params
+    /// * **lp_token** is an object of type [`Addr`]. This is the LP token of the generator.
+    pub fn pool_info(&self, lp_token: &Addr) -> StdResult<PoolInfoResponse> {
+        self.querier.query_wasm_smart(
+            &self.generator,
+            &QueryMsg::PoolInfo {
+                lp_token: lp_token.to_string(),
+            },
+        )
+    }
+}
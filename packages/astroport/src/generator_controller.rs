@@ -0,0 +1,139 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of pools that can receive ASTRO emissions after a tune. This is the maximum
+/// amount of active pools in the Generator
+pub const MAX_POOLS_LIMIT: u64 = 100;
+
+/// The sum of the allocation points set in the Generator after a tune. Every tuned pool gets its share
+/// of the votes cast for the tuned pools
+pub const TOTAL_ALLOC_POINTS: u128 = 1_000_000;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Generator contract whose allocation points are tuned
+    pub generator: String,
+    /// The voting escrow (vxASTRO) contract
+    pub voting_escrow: String,
+    /// The duration of a voting period (in seconds). The first period starts when the contract is created
+    pub period_duration: u64,
+    /// The maximum amount of pools that receive ASTRO emissions after a tune
+    pub pools_limit: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Splits the sender's current vxASTRO voting power between whitelisted pools for the current period.
+    /// Voting again in the same period replaces the previous votes
+    Vote {
+        /// The LP tokens of the pools to vote for and the share (in bps) of the voting power each one gets
+        votes: Vec<(String, u16)>,
+    },
+    /// Replaces the Generator's allocation points with the votes of the last finished period
+    TunePools {},
+    /// Adds or removes pools that can be voted for
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateWhitelist {
+        /// The LP tokens of the pools to add
+        add: Option<Vec<String>>,
+        /// The LP tokens of the pools to remove
+        remove: Option<Vec<String>>,
+    },
+    /// Updates contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new voting escrow (vxASTRO) contract
+        voting_escrow: Option<String>,
+        /// The new maximum amount of pools that receive ASTRO emissions after a tune
+        pools_limit: Option<u64>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the current voting period as a [`u64`]
+    CurrentPeriod {},
+    /// Returns the votes of a user in the current period using a [`UserVotesResponse`] object
+    UserVotes { user: String },
+    /// Returns the vote weight a user gave to a pool in a period as a [`Uint128`]
+    UserVote {
+        user: String,
+        lp_token: String,
+        period: u64,
+    },
+    /// Returns the votes of a pool in a period using a [`crate::bribes::PoolVotesResponse`] object
+    PoolVotes { lp_token: String, period: u64 },
+    /// Returns the result of tuning a period using a [`TuneInfo`] object
+    TuneInfo { period: u64 },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Generator contract whose allocation points are tuned
+    pub generator: Addr,
+    /// The voting escrow (vxASTRO) contract
+    pub voting_escrow: Addr,
+    /// The timestamp (in seconds) when the first voting period started
+    pub start_time: u64,
+    /// The duration of a voting period (in seconds)
+    pub period_duration: u64,
+    /// The maximum amount of pools that receive ASTRO emissions after a tune
+    pub pools_limit: u64,
+    /// The pools that can be voted for
+    pub whitelist: Vec<Addr>,
+}
+
+/// This structure holds the parameters returned when querying for the votes of a user in a period.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct UserVotesResponse {
+    /// The vxASTRO voting power the user voted with
+    pub voting_power: Uint128,
+    /// The LP tokens the user voted for and the share (in bps) of the voting power each one got
+    pub votes: Vec<(Addr, u16)>,
+}
+
+/// This structure describes the result of tuning a voting period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TuneInfo {
+    /// The timestamp (in seconds) when the period was tuned
+    pub tuned_at: u64,
+    /// The LP tokens that receive ASTRO emissions and their allocation points
+    pub pools: Vec<(Addr, Uint128)>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,166 @@
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The IBC application version used by the hub and outposts
+pub const HUB_IBC_VERSION: &str = "astroport-hub-v1";
+
+/// This structure stores general parameters for the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The ASTRO token contract address
+    pub astro_token: String,
+    /// The xASTRO staking contract address
+    pub staking_contract: String,
+    /// The cw20-ics20 contract used to send ASTRO back to outposts
+    pub ics20_contract: String,
+    /// The IBC packet timeout in seconds for ASTRO sent back to outposts
+    pub ics20_timeout: Option<u64>,
+}
+
+/// This structure describes an outpost that is allowed to stake through the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OutpostInfo {
+    /// The hub channel connected to the outpost contract
+    pub channel: String,
+    /// The IBC port of the outpost contract
+    pub port: String,
+    /// The cw20-ics20 channel used to send ASTRO to the outpost chain
+    pub ics20_channel: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Updates general contract parameters
+    UpdateConfig {
+        /// The cw20-ics20 contract used to send ASTRO back to outposts
+        ics20_contract: Option<String>,
+        /// The IBC packet timeout in seconds for ASTRO sent back to outposts
+        ics20_timeout: Option<u64>,
+    },
+    /// Adds or removes outposts
+    UpdateOutposts {
+        /// The outposts to add or update
+        add: Option<Vec<OutpostInfo>>,
+        /// The hub channels of the outposts to remove
+        remove: Option<Vec<String>>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Credits ASTRO received through the cw20-ics20 contract to an outpost, so that the
+    /// outpost's users can stake it. Outposts attach this message to their ASTRO transfers
+    OutpostTransfer {
+        /// The hub channel connected to the outpost
+        channel: String,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns a vector of registered outposts using [`OutpostInfo`] objects
+    Outposts {},
+    /// Returns the amount of xASTRO staked on behalf of a remote user
+    UserStake {
+        /// The hub channel connected to the user's outpost
+        channel: String,
+        /// The user address on the outpost chain
+        user: String,
+    },
+    /// Returns the total amount of xASTRO staked on behalf of an outpost's users
+    OutpostBalance {
+        /// The hub channel connected to the outpost
+        channel: String,
+    },
+    /// Returns the amount of ASTRO received from an outpost that isn't staked yet
+    OutpostAstro {
+        /// The hub channel connected to the outpost
+        channel: String,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The xASTRO token contract address
+    pub xastro_token: Addr,
+    /// The xASTRO staking contract address
+    pub staking_contract: Addr,
+    /// The cw20-ics20 contract used to send ASTRO back to outposts
+    pub ics20_contract: Addr,
+    /// The IBC packet timeout in seconds for ASTRO sent back to outposts
+    pub ics20_timeout: u64,
+}
+
+/// This enum describes the IBC packets sent from outposts to the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HubPacket {
+    /// Stakes ASTRO on behalf of a remote user. The ASTRO must already be transferred to the hub
+    /// through the cw20-ics20 contract and credited to the outpost with
+    /// [`Cw20HookMsg::OutpostTransfer`]
+    Stake {
+        /// The user address on the outpost chain
+        receiver: String,
+        /// The amount of ASTRO to stake
+        amount: Uint128,
+    },
+    /// Unstakes xASTRO on behalf of a remote user and sends the ASTRO back to the outpost chain
+    Unstake {
+        /// The user address on the outpost chain
+        receiver: String,
+        /// The amount of xASTRO to unstake
+        amount: Uint128,
+    },
+}
+
+/// This enum describes the acknowledgements that the hub sends back to outposts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HubAck {
+    /// The packet was processed successfully. Contains the amount of xASTRO minted for a stake
+    /// or the amount of ASTRO returned for an unstake
+    Result(Binary),
+    /// The packet could not be processed
+    Error(String),
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,95 @@
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdError, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::SECONDS_PER_DAY;
+
+/// The default IBC packet timeout (in seconds) for cw20-ics20 transfers
+pub const DEFAULT_IBC_TIMEOUT: u64 = 600;
+/// The minimum IBC packet timeout (in seconds)
+pub const MIN_IBC_TIMEOUT: u64 = 60;
+/// The maximum IBC packet timeout (in seconds)
+pub const MAX_IBC_TIMEOUT: u64 = SECONDS_PER_DAY;
+
+/// This structure describes the cw20-ics20 hook message used to transfer CW20 tokens over IBC.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferMsg {
+    /// The local channel to send the packets on
+    pub channel: String,
+    /// The address on the remote chain that receives the tokens
+    pub remote_address: String,
+    /// The IBC packet timeout in seconds
+    pub timeout: Option<u64>,
+    /// An optional memo attached to the transfer. It is omitted from the message if not set,
+    /// so that gateways without memo support accept the transfer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// ## Description
+/// Checks that the specified identifier is an IBC channel identifier.
+/// ## Params
+/// * **channel** is an object of type [`str`]. This is the channel identifier to check.
+pub fn validate_ibc_channel(channel: &str) -> StdResult<()> {
+    if !channel.starts_with("channel-") {
+        return Err(StdError::generic_err(format!(
+            "Invalid IBC channel {}",
+            channel
+        )));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the IBC packet timeout to use for a transfer. If the timeout is not specified,
+/// [`DEFAULT_IBC_TIMEOUT`] is returned. Returns an error if the timeout is out of the
+/// [`MIN_IBC_TIMEOUT`]..=[`MAX_IBC_TIMEOUT`] range.
+/// ## Params
+/// * **timeout** is an [`Option`] field of type [`u64`]. This is the timeout in seconds.
+pub fn validate_ibc_timeout(timeout: Option<u64>) -> StdResult<u64> {
+    let timeout = timeout.unwrap_or(DEFAULT_IBC_TIMEOUT);
+    if !(MIN_IBC_TIMEOUT..=MAX_IBC_TIMEOUT).contains(&timeout) {
+        return Err(StdError::generic_err(format!(
+            "IBC timeout must be between {} and {} seconds",
+            MIN_IBC_TIMEOUT, MAX_IBC_TIMEOUT
+        )));
+    }
+
+    Ok(timeout)
+}
+
+/// ## Description
+/// Builds a message that sends CW20 tokens (usually ASTRO) over IBC through a cw20-ics20 contract.
+/// If the transfer fails or times out, the cw20-ics20 contract refunds the tokens to the sender.
+/// ## Params
+/// * **token** is an object of type [`Addr`]. This is the CW20 token to send.
+///
+/// * **ics20_contract** is an object of type [`Addr`]. This is the cw20-ics20 contract used for the transfer.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of tokens to send.
+///
+/// * **transfer** is an object of type [`TransferMsg`]. This describes the channel, recipient, timeout and memo.
+pub fn build_ics20_transfer_msg(
+    token: &Addr,
+    ics20_contract: &Addr,
+    amount: Uint128,
+    transfer: &TransferMsg,
+) -> StdResult<CosmosMsg> {
+    validate_ibc_channel(&transfer.channel)?;
+    if transfer.remote_address.trim().is_empty() {
+        return Err(StdError::generic_err("IBC recipient address must be set"));
+    }
+    validate_ibc_timeout(transfer.timeout)?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: ics20_contract.to_string(),
+            amount,
+            msg: to_binary(transfer)?,
+        })?,
+        funds: vec![],
+    }))
+}
@@ -0,0 +1,187 @@
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of different reward tokens that can be distributed in a pool at the same time
+pub const MAX_REWARD_TOKENS: usize = 5;
+
+/// The maximum amount of unfinished schedules of the same reward token in a pool. Together with
+/// [`MAX_REWARD_TOKENS`] it bounds the gas used to update a pool
+pub const MAX_SCHEDULES_PER_REWARD: usize = 10;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Astroport factory contract address. Only LP tokens of pairs registered in the factory can be staked
+    pub factory: String,
+    /// The fee charged for adding a new reward token to a pool
+    pub incentivization_fee_info: Option<IncentivizationFeeInfo>,
+}
+
+/// This structure describes the fee charged for adding a new reward token to a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IncentivizationFeeInfo {
+    /// The address that receives the fee
+    pub fee_receiver: Addr,
+    /// The fee amount
+    pub fee: Coin,
+}
+
+/// This structure describes a reward schedule submitted for a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InputSchedule {
+    /// The reward token and the total amount distributed over the schedule
+    pub reward: Asset,
+    /// The timestamp (in seconds) when the distribution starts
+    pub start_ts: u64,
+    /// The timestamp (in seconds) when the distribution ends
+    pub end_ts: u64,
+}
+
+/// This structure describes an active reward schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSchedule {
+    /// The reward token
+    pub reward: AssetInfo,
+    /// The amount of reward tokens distributed per second
+    pub rps: Decimal,
+    /// The timestamp (in seconds) when the distribution starts
+    pub start_ts: u64,
+    /// The timestamp (in seconds) when the distribution ends
+    pub end_ts: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Withdraws staked LP tokens along with all pending rewards
+    Withdraw {
+        /// The LP token to withdraw
+        lp_token: String,
+        /// The amount of LP tokens to withdraw
+        amount: Uint128,
+    },
+    /// Claims pending rewards from the specified pools
+    ClaimRewards {
+        /// The LP tokens whose pools to claim rewards from
+        lp_tokens: Vec<String>,
+    },
+    /// Attaches a reward schedule to a pool. The reward tokens must be sent along with the message
+    /// (native tokens) or approved for the contract to pull (CW20 tokens).
+    /// Adding a new reward token to a pool costs the incentivization fee, unless the sender is the owner
+    Incentivize {
+        /// The LP token of the pool to incentivize
+        lp_token: String,
+        /// The reward schedule
+        schedule: InputSchedule,
+    },
+    /// Removes a reward token from a pool and sends the undistributed rewards to the receiver.
+    /// Rewards that are already accrued stay claimable
+    /// ## Executor
+    /// Only the current owner can execute this
+    RemoveRewardFromPool {
+        /// The LP token of the pool
+        lp_token: String,
+        /// The reward token to remove
+        reward: AssetInfo,
+        /// The address that receives the undistributed rewards
+        receiver: String,
+    },
+    /// Adds or removes tokens from the blocked list. Blocked tokens can't be attached to pools and
+    /// aren't paid out on claims
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateTokensBlockedlist {
+        /// Tokens to add
+        add: Option<Vec<AssetInfo>>,
+        /// Tokens to remove
+        remove: Option<Vec<AssetInfo>>,
+    },
+    /// Updates contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new fee charged for adding a new reward token to a pool. Not setting it keeps the current fee
+        incentivization_fee_info: Option<IncentivizationFeeInfo>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes custom hooks for the CW20.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Stakes the sent LP tokens. Pending rewards are claimed first
+    Deposit {
+        /// The address that the LP tokens are staked for. Defaults to the sender
+        recipient: Option<String>,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the amount of LP tokens staked by a user as a [`Uint128`]
+    Deposit { lp_token: String, user: String },
+    /// Returns the pending rewards of a user in a pool as a vector of [`Asset`] objects
+    PendingRewards { lp_token: String, user: String },
+    /// Returns information about a pool using a [`PoolInfoResponse`] object
+    PoolInfo { lp_token: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport factory contract address
+    pub factory: Addr,
+    /// The fee charged for adding a new reward token to a pool
+    pub incentivization_fee_info: Option<IncentivizationFeeInfo>,
+    /// Tokens that can't be distributed as rewards
+    pub blocked_tokens: Vec<AssetInfo>,
+}
+
+/// This structure holds the parameters returned when querying for a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfoResponse {
+    /// The total amount of staked LP tokens
+    pub total_lp: Uint128,
+    /// The timestamp (in seconds) of the latest reward update
+    pub last_update_ts: u64,
+    /// Reward schedules that aren't finished yet
+    pub schedules: Vec<RewardSchedule>,
+    /// The amount of rewards accumulated per staked LP token for every reward token
+    pub reward_indices: Vec<(AssetInfo, Decimal)>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
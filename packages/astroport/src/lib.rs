@@ -1,17 +1,40 @@
+pub mod airdrop;
 pub mod asset;
+pub mod astro_converter;
+pub mod attr_builder;
+pub mod auction;
+pub mod bribes;
 pub mod common;
+pub mod constants;
+pub mod dca;
+pub mod emergency_admin;
 pub mod factory;
+pub mod fee_discount;
 pub mod generator;
+pub mod generator_controller;
 pub mod generator_proxy;
+pub mod hub;
+pub mod ics20;
+pub mod incentives;
+pub mod limit_order;
+pub mod liquidity_migrator;
+pub mod lockdrop;
 pub mod maker;
 pub mod oracle;
 pub mod pair;
 pub mod pair_stable_bluna;
+pub mod pol_manager;
+pub mod price_feed;
 pub mod querier;
+pub mod referral;
 pub mod router;
+pub mod satellite;
+pub mod simulation;
 pub mod staking;
+pub mod timelock;
 pub mod token;
 pub mod vesting;
+pub mod voting_escrow;
 pub mod whitelist;
 pub mod xastro_token;
 
@@ -35,6 +58,14 @@ mod decimal_checked_ops {
     pub trait DecimalCheckedOps {
         fn checked_add(self, other: Decimal) -> Result<Decimal, OverflowError>;
         fn checked_mul(self, other: Uint128) -> Result<Uint128, OverflowError>;
+        /// Multiplies the decimal by `other` and rounds the result down
+        fn checked_mul_floor(self, other: Uint128) -> Result<Uint128, OverflowError>;
+        /// Multiplies the decimal by `other` and rounds the result up
+        fn checked_mul_ceil(self, other: Uint128) -> Result<Uint128, OverflowError>;
+        /// Returns the integer part of the decimal
+        fn to_uint_floor(self) -> Uint128;
+        /// Returns the smallest integer that is greater than or equal to the decimal
+        fn to_uint_ceil(self) -> Uint128;
     }
 
     impl DecimalCheckedOps for Decimal {
@@ -44,22 +75,51 @@ mod decimal_checked_ops {
                 .map(|_| self + other)
         }
         fn checked_mul(self, other: Uint128) -> Result<Uint128, OverflowError> {
-            if self.is_zero() || other.is_zero() {
-                return Ok(Uint128::zero());
-            }
-            let multiply_ratio =
-                other.full_mul(self.numerator()) / Uint256::from(self.denominator());
-            if multiply_ratio > Uint256::from(Uint128::MAX) {
-                Err(OverflowError::new(
-                    cosmwasm_std::OverflowOperation::Mul,
-                    self,
-                    other,
-                ))
+            self.checked_mul_floor(other)
+        }
+        fn checked_mul_floor(self, other: Uint128) -> Result<Uint128, OverflowError> {
+            mul_with_rounding(self, other, false)
+        }
+        fn checked_mul_ceil(self, other: Uint128) -> Result<Uint128, OverflowError> {
+            mul_with_rounding(self, other, true)
+        }
+        fn to_uint_floor(self) -> Uint128 {
+            Uint128::from(self.numerator() / self.denominator())
+        }
+        fn to_uint_ceil(self) -> Uint128 {
+            let floor = self.to_uint_floor();
+            if self.numerator() % self.denominator() == 0 {
+                floor
             } else {
-                Ok(multiply_ratio.try_into().unwrap())
+                floor + Uint128::from(1u8)
             }
         }
     }
+
+    fn mul_with_rounding(
+        decimal: Decimal,
+        other: Uint128,
+        round_up: bool,
+    ) -> Result<Uint128, OverflowError> {
+        if decimal.is_zero() || other.is_zero() {
+            return Ok(Uint128::zero());
+        }
+        let product = Uint256::from(other) * Uint256::from(decimal.numerator());
+        let denominator = Uint256::from(decimal.denominator());
+        let mut multiply_ratio = product / denominator;
+        if round_up && !(product - multiply_ratio * denominator).is_zero() {
+            multiply_ratio += Uint256::from(1u8);
+        }
+        if multiply_ratio > Uint256::from(Uint128::MAX) {
+            Err(OverflowError::new(
+                cosmwasm_std::OverflowOperation::Mul,
+                decimal,
+                other,
+            ))
+        } else {
+            Ok(multiply_ratio.try_into().unwrap())
+        }
+    }
 }
 
 pub use decimal_checked_ops::DecimalCheckedOps;
@@ -0,0 +1,155 @@
+use crate::asset::{Asset, AssetInfo};
+use crate::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum keeper fee (in bps) that can be set in the contract config
+pub const MAX_KEEPER_FEE_BPS: u16 = 1000;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Astroport router contract address used to execute orders
+    pub router: String,
+    /// The maximum keeper fee (in bps of the offer amount) that an order can pay
+    pub max_keeper_fee_bps: u16,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Creates an order that offers native tokens. The offer asset must be sent along with the message
+    CreateOrder {
+        /// The asset to sell
+        offer_asset: Asset,
+        /// The swap operations used to execute the order. The first operation must offer the offer asset
+        operations: Vec<SwapOperation>,
+        /// The minimum amount of ask tokens received per offer token
+        price: Decimal,
+        /// The part of the offer amount (in bps) paid to the keeper that executes the order
+        keeper_fee_bps: u16,
+    },
+    /// Cancels an order and refunds the offer asset
+    /// ## Executor
+    /// Only the order owner can execute this
+    CancelOrder {
+        /// The order identifier
+        order_id: u64,
+    },
+    /// Executes an order through the router if the simulated return satisfies the order price.
+    /// The keeper fee is sent to the sender
+    ExecuteOrder {
+        /// The order identifier
+        order_id: u64,
+    },
+    /// Updates contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new router contract address
+        router: Option<String>,
+        /// The new maximum keeper fee (in bps of the offer amount)
+        max_keeper_fee_bps: Option<u16>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes custom hooks for the CW20.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates an order that offers the sent CW20 tokens
+    CreateOrder {
+        /// The swap operations used to execute the order. The first operation must offer the sent token
+        operations: Vec<SwapOperation>,
+        /// The minimum amount of ask tokens received per offer token
+        price: Decimal,
+        /// The part of the offer amount (in bps) paid to the keeper that executes the order
+        keeper_fee_bps: u16,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns an order using an [`OrderResponse`] object
+    Order { order_id: u64 },
+    /// Returns open orders (optionally of a specific owner) using an [`OrdersResponse`] object
+    Orders {
+        owner: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport router contract address
+    pub router: Addr,
+    /// The maximum keeper fee (in bps of the offer amount) that an order can pay
+    pub max_keeper_fee_bps: u16,
+}
+
+/// This structure holds the parameters returned when querying for an order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    /// The order identifier
+    pub order_id: u64,
+    /// The address that created the order
+    pub owner: Addr,
+    /// The asset sold in the order, including the keeper fee
+    pub offer_asset: Asset,
+    /// The asset bought in the order
+    pub ask_asset_info: AssetInfo,
+    /// The swap operations used to execute the order
+    pub operations: Vec<SwapOperation>,
+    /// The minimum amount of ask tokens received per offer token
+    pub price: Decimal,
+    /// The minimum amount of ask tokens the owner receives when the order is executed
+    pub minimum_receive: Uint128,
+    /// The amount of offer tokens paid to the keeper that executes the order
+    pub keeper_fee: Uint128,
+    /// The timestamp (in seconds) when the order was created
+    pub created_at: u64,
+}
+
+/// This structure holds the parameters returned when querying for multiple orders.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrdersResponse {
+    /// The orders
+    pub orders: Vec<OrderResponse>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
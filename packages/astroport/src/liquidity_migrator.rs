@@ -0,0 +1,147 @@
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use cosmwasm_std::{Addr, Decimal};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to configure markets
+    pub owner: String,
+    /// The factory contract address. Astroport pairs must be registered in it
+    pub factory: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Adds a market for the legacy pair's LP token or replaces its Astroport pair.
+    /// Both pairs must hold the same assets
+    /// ## Executor
+    /// Only the owner can execute this
+    SetMarket {
+        /// The legacy pair the liquidity is withdrawn from
+        legacy_pair: String,
+        /// The Astroport pair the liquidity is provided to
+        astroport_pair: String,
+    },
+    /// Removes a market
+    /// ## Executor
+    /// Only the owner can execute this
+    RemoveMarket { legacy_lp_token: String },
+    /// Callbacks used during a migration
+    /// ## Executor
+    /// Callback functions can only be called by this contract
+    Callback(Box<CallbackMsg>),
+    /// Creates a request to change the contract's ownership
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the proposal to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Withdraws the sent legacy LP tokens from the legacy pair and provides the underlying assets
+    /// to the market's Astroport pair
+    Migrate {
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether the Astroport LP tokens are staked in the generator on behalf of the receiver
+        auto_stake: Option<bool>,
+        /// The receiver of the Astroport LP tokens. Defaults to the sender of the legacy LP tokens
+        receiver: Option<String>,
+    },
+    /// Withdraws the sent Astroport LP tokens from their pair and provides the underlying assets to the
+    /// pair currently registered in the factory for the same assets
+    MigratePair {
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether the new LP tokens are staked in the generator on behalf of the receiver
+        auto_stake: Option<bool>,
+        /// The receiver of the new LP tokens. Defaults to the sender of the LP tokens
+        receiver: Option<String>,
+    },
+}
+
+/// This structure describes the callback messages used during a migration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Provides the withdrawn assets to the Astroport pair
+    ProvideLiquidity {
+        /// The Astroport pair that receives the liquidity
+        astroport_pair: PairInfo,
+        /// The contract's balances of the pair assets before the liquidity was withdrawn
+        prev_balances: [Asset; 2],
+        /// The receiver of the Astroport LP tokens
+        receiver: Addr,
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether the Astroport LP tokens are staked in the generator on behalf of the receiver
+        auto_stake: bool,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns a market using a [`MarketInfo`] object
+    Market { legacy_lp_token: String },
+    /// Returns markets using a vector of [`MarketInfo`] objects
+    Markets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to configure markets
+    pub owner: Addr,
+    /// The factory contract address
+    pub factory: Addr,
+}
+
+/// This structure describes a market migrating a legacy LP token to an Astroport pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketInfo {
+    /// The legacy LP token accepted by the market
+    pub legacy_lp_token: Addr,
+    /// The legacy pair the liquidity is withdrawn from
+    pub legacy_pair: Addr,
+    /// The Astroport pair the liquidity is provided to
+    pub astroport_pair: PairInfo,
+}
+
+/// This structure describes the pair information returned by a legacy (Terraswap compatible) pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyPairInfo {
+    /// Assets in the pool
+    pub asset_infos: [AssetInfo; 2],
+    /// Pair contract address
+    pub contract_addr: Addr,
+    /// Pair LP token address
+    pub liquidity_token: Addr,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
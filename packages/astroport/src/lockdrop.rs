@@ -0,0 +1,268 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, PairInfo};
+use crate::constants::SECONDS_PER_PERIOD;
+
+/// The number of seconds in one week. Lock durations are specified in weeks
+pub const WEEK: u64 = SECONDS_PER_PERIOD;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The ASTRO token contract address
+    pub astro_token: String,
+    /// The generator contract in which unlocked Astroport LP tokens are staked on behalf of users
+    pub generator: Option<String>,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds. It starts right after the deposit window
+    pub withdrawal_window: u64,
+    /// The period in seconds after the lockdrop ends during which the owner can migrate the pools'
+    /// liquidity. Lockups in pools that weren't migrated by then can be withdrawn
+    pub migration_window: u64,
+    /// The minimum lock duration in weeks
+    pub min_lock_duration: u64,
+    /// The maximum lock duration in weeks
+    pub max_lock_duration: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_multiplier: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_divider: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
+    Receive(Cw20ReceiveMsg),
+    /// Updates general contract parameters
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new generator contract address
+        generator: Option<String>,
+    },
+    /// Adds an LP token that can be locked in the lockdrop
+    /// ## Executor
+    /// Only the current owner can execute this
+    InitializePool {
+        /// The LP token contract address
+        lp_token: String,
+        /// The pair that issued the LP token
+        pair: String,
+        /// The pool's share of the ASTRO incentives relative to the other pools
+        incentives_share: u64,
+    },
+    /// Withdraws LP tokens from a lockup during the deposit or withdrawal windows
+    WithdrawFromLockup {
+        /// The locked LP token contract address
+        lp_token: String,
+        /// The lock duration in weeks
+        duration: u64,
+        /// The amount of LP tokens to withdraw
+        amount: Uint128,
+    },
+    /// Withdraws the pool's liquidity from the original pair and provides it to an Astroport pair
+    /// ## Executor
+    /// Only the current owner can execute this
+    MigrateLiquidity {
+        /// The locked LP token contract address
+        lp_token: String,
+        /// The Astroport pair that receives the liquidity
+        astroport_pair: String,
+        /// The slippage tolerance used when providing liquidity to the Astroport pair
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Withdraws the original LP tokens and the ASTRO rewards of a lockup in a pool whose liquidity
+    /// wasn't migrated by the end of the migration window
+    WithdrawUnmigrated {
+        /// The locked LP token contract address
+        lp_token: String,
+        /// The lock duration in weeks
+        duration: u64,
+    },
+    /// Claims the lockup's ASTRO rewards. Once the lockup expires, the lockup's share of
+    /// Astroport LP tokens is also unlocked and staked in the generator on behalf of the user
+    ClaimRewardsAndUnlock {
+        /// The locked LP token contract address
+        lp_token: String,
+        /// The lock duration in weeks
+        duration: u64,
+    },
+    /// Callbacks used during liquidity migration
+    /// ## Executor
+    /// Callback functions can only be called by this contract
+    Callback(Box<CallbackMsg>),
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Locks LP tokens for the specified number of weeks
+    IncreaseLockup {
+        /// The lock duration in weeks
+        duration: u64,
+    },
+    /// Adds ASTRO to the lockdrop incentives
+    IncreaseAstroIncentives {},
+}
+
+/// This structure describes the callback messages used during liquidity migration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Provides the assets withdrawn from the original pair to the Astroport pair
+    ProvideLiquidity {
+        /// The locked LP token contract address
+        lp_token: Addr,
+        /// The Astroport pair that receives the liquidity
+        astroport_pair: PairInfo,
+        /// The contract's balances of the pair assets before the liquidity was withdrawn
+        prev_balances: [Asset; 2],
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Saves the amount of Astroport LP tokens received for the pool
+    UpdatePoolOnMigration {
+        /// The locked LP token contract address
+        lp_token: Addr,
+        /// The Astroport pair that received the liquidity
+        astroport_pair: PairInfo,
+        /// The contract's Astroport LP token balance before liquidity was provided
+        prev_lp_balance: Uint128,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the global lockdrop state using a [`StateResponse`] object
+    State {},
+    /// Returns information about a pool using a [`PoolInfo`] object
+    Pool { lp_token: String },
+    /// Returns all lockups of a user using a [`UserInfoResponse`] object
+    UserInfo { address: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+    /// The generator contract in which unlocked Astroport LP tokens are staked on behalf of users
+    pub generator: Option<Addr>,
+    /// The timestamp (in seconds) when the deposit window starts
+    pub init_timestamp: u64,
+    /// The deposit window duration in seconds
+    pub deposit_window: u64,
+    /// The withdrawal window duration in seconds
+    pub withdrawal_window: u64,
+    /// The period in seconds after the lockdrop ends during which the owner can migrate the pools' liquidity
+    pub migration_window: u64,
+    /// The minimum lock duration in weeks
+    pub min_lock_duration: u64,
+    /// The maximum lock duration in weeks
+    pub max_lock_duration: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_multiplier: u64,
+    /// The lockup weight increase for every additional week is `weekly_multiplier / weekly_divider`
+    pub weekly_divider: u64,
+}
+
+/// This structure holds the global lockdrop state.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct StateResponse {
+    /// The sum of the incentives shares of the pools with locked LP tokens. Pools without lockups
+    /// don't take a share of the ASTRO incentives
+    pub total_incentives_share: u64,
+    /// The total amount of ASTRO distributed among lockups
+    pub total_astro_incentives: Uint128,
+}
+
+/// This structure describes a pool in which LP tokens can be locked.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    /// The pair that issued the locked LP token
+    pub pair: Addr,
+    /// The pool's share of the ASTRO incentives relative to the other pools
+    pub incentives_share: u64,
+    /// The total amount of locked LP tokens
+    pub total_lp_locked: Uint128,
+    /// The sum of the weighted amounts (amount × duration weight) of all lockups
+    pub weighted_amount: Uint128,
+    /// Information about the Astroport pair the liquidity was migrated to
+    pub migration_info: Option<MigrationInfo>,
+}
+
+/// This structure describes the Astroport pair the pool's liquidity was migrated to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationInfo {
+    /// The Astroport pair contract address
+    pub astroport_pair: Addr,
+    /// The Astroport LP token contract address
+    pub astroport_lp_token: Addr,
+    /// The amount of Astroport LP tokens received for the pool's liquidity
+    pub astroport_lp_amount: Uint128,
+}
+
+/// This structure holds the lockups returned when querying for a user.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserInfoResponse {
+    /// The user's lockups
+    pub lockups: Vec<LockupInfoResponse>,
+}
+
+/// This structure describes a single lockup.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupInfoResponse {
+    /// The locked LP token contract address
+    pub lp_token: Addr,
+    /// The lock duration in weeks
+    pub duration: u64,
+    /// The amount of locked LP tokens
+    pub lp_locked: Uint128,
+    /// The amount of ASTRO allocated to the lockup. Only known once the lockdrop has finished
+    pub astro_rewards: Uint128,
+    /// Whether the ASTRO rewards were claimed
+    pub astro_claimed: bool,
+    /// The timestamp (in seconds) when the lockup expires
+    pub unlock_timestamp: u64,
+    /// The lockup's share of Astroport LP tokens once the pool is migrated
+    pub astroport_lp_amount: Option<Uint128>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -1,4 +1,5 @@
 use crate::asset::{Asset, AssetInfo};
+use crate::astro_converter::{NativeAstro, NativeAstroParams};
 use crate::factory::UpdateAddr;
 use cosmwasm_std::{Addr, Decimal, Uint128, Uint64};
 use schemars::JsonSchema;
@@ -21,6 +22,8 @@ pub struct InstantiateMsg {
     pub governance_percent: Option<Uint64>,
     /// The maximum spread used when swapping fee tokens to ASTRO
     pub max_spread: Option<Decimal>,
+    /// Sends collected ASTRO to the hub chain over IBC instead of distributing it locally
+    pub ibc_bridge: Option<IbcBridgeParams>,
 }
 
 /// This structure describes the functions that can be executed in this contract.
@@ -44,6 +47,10 @@ pub enum ExecuteMsg {
         governance_percent: Option<Uint64>,
         /// The maximum spread used when swapping fee tokens to ASTRO
         max_spread: Option<Decimal>,
+        /// Sets or removes the IBC bridge used to send collected ASTRO to the hub chain
+        ibc_bridge: Option<UpdateIbcBridge>,
+        /// Whether ASTRO is sent to the staking contract through its fee distribution message
+        notify_staking: Option<bool>,
     },
     /// Add bridge tokens used to swap specific fee tokens to ASTRO (effectively declaring a swap route)
     UpdateBridges {
@@ -67,6 +74,14 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
     /// Enables the distribution of current fees accrued in the contract over "blocks" number of blocks
     EnableRewards { blocks: u64 },
+    /// Pauses fee collection
+    /// ## Executor
+    /// The owner or the factory's emergency admin can execute this
+    Pause {},
+    /// Resumes fee collection
+    /// ## Executor
+    /// Only the owner can execute this
+    Unpause {},
 }
 
 /// This structure describes the query functions available in the contract.
@@ -80,6 +95,10 @@ pub enum QueryMsg {
         assets: Vec<AssetInfo>,
     },
     Bridges {},
+    /// Simulates collecting the specified fee tokens and returns the expected ASTRO output in a [`SimulateCollectResponse`]
+    SimulateCollect {
+        assets: Vec<AssetWithLimit>,
+    },
 }
 
 /// A custom struct that holds contract parameters and is used to retrieve them.
@@ -103,6 +122,14 @@ pub struct ConfigResponse {
     pub remainder_reward: Uint128,
     /// The amount of ASTRO tokens accrued before upgrading the Maker implementation and enabling reward distribution
     pub pre_upgrade_astro_amount: Uint128,
+    /// The IBC bridge used to send collected ASTRO to the hub chain
+    pub ibc_bridge: Option<IbcBridge>,
+    /// The native ASTRO collected instead of CW20 ASTRO, if the contract has been switched to it
+    pub native_astro: Option<NativeAstro>,
+    /// Whether fee collection is paused
+    pub paused: bool,
+    /// Whether ASTRO is sent to the staking contract through its fee distribution message
+    pub notify_staking: bool,
 }
 
 /// A custom struct used to return multiple asset balances.
@@ -111,10 +138,34 @@ pub struct BalancesResponse {
     pub balances: Vec<Asset>,
 }
 
+/// This structure describes the expected result of collecting fee tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateCollectResponse {
+    /// The total amount of ASTRO expected from collecting the specified fee tokens
+    pub astro_amount: Uint128,
+    /// The conversion of each fee token to ASTRO
+    pub conversions: Vec<CollectConversion>,
+}
+
+/// This structure describes the expected conversion of a single fee token to ASTRO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectConversion {
+    /// The fee token and the amount of it that is collected
+    pub offer_asset: Asset,
+    /// The assets the fee token is swapped through, starting with the fee token and ending with ASTRO
+    pub route: Vec<AssetInfo>,
+    /// The amount of ASTRO expected from the fee token
+    pub astro_amount: Uint128,
+}
+
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Switches the contract to native ASTRO. The CW20 ASTRO held by the contract is converted.
+    /// Can't be combined with an IBC bridge
+    #[serde(default)]
+    pub native_astro: Option<NativeAstroParams>,
+}
 
 /// This struct holds parameters to help with swapping a specific amount of a fee token to ASTRO.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -124,3 +175,43 @@ pub struct AssetWithLimit {
     /// The amount of tokens to swap
     pub limit: Option<Uint128>,
 }
+
+/// This structure holds the parameters used to send collected ASTRO to the hub chain over IBC.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcBridgeParams {
+    /// The cw20-ics20 contract used to transfer ASTRO over IBC
+    pub ics20_contract: String,
+    /// The cw20-ics20 channel connected to the hub chain
+    pub channel: String,
+    /// The address on the hub chain that receives ASTRO (usually the hub Maker)
+    pub recipient: String,
+    /// The IBC packet timeout in seconds
+    pub timeout: Option<u64>,
+    /// An optional memo attached to every transfer
+    pub memo: Option<String>,
+}
+
+/// This structure describes the IBC bridge used by a remote Maker to send collected ASTRO to the hub chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcBridge {
+    /// The cw20-ics20 contract used to transfer ASTRO over IBC
+    pub ics20_contract: Addr,
+    /// The cw20-ics20 channel connected to the hub chain
+    pub channel: String,
+    /// The address on the hub chain that receives ASTRO
+    pub recipient: String,
+    /// The IBC packet timeout in seconds
+    pub timeout: u64,
+    /// An optional memo attached to every transfer
+    pub memo: Option<String>,
+}
+
+/// This enum describes the actions used to update the Maker's IBC bridge.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateIbcBridge {
+    /// Sets a new IBC bridge
+    Set(IbcBridgeParams),
+    /// Removes the IBC bridge, so that ASTRO is distributed locally again
+    Remove {},
+}
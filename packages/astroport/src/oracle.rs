@@ -31,6 +31,17 @@ pub enum QueryMsg {
         /// The amount of tokens for which to compute the token price
         amount: Uint128,
     },
+    /// Calculates a token price using the median of the recent spot price observations
+    ConsultMedian {
+        /// The asset for which to compute the median price
+        token: AssetInfo,
+        /// The amount of tokens for which to compute the token price
+        amount: Uint128,
+        /// The maximum age (in seconds) of the latest observation. The query fails if the latest observation is older
+        max_staleness: Option<u64>,
+    },
+    /// Returns the timestamp (in seconds) of the latest TWAP update as a [`u64`]
+    LastUpdateTime {},
 }
 
 /// This structure describes a migration message.
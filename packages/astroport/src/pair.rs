@@ -1,18 +1,25 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::asset::{Asset, AssetInfo};
+use crate::asset::{Asset, AssetInfo, PairInfo};
 
-use cosmwasm_std::{Binary, Decimal, Uint128};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, QuerierWrapper, StdResult, SubMsg, Uint128, WasmMsg,
+};
 use cw20::Cw20ReceiveMsg;
 
 /// The default swap slippage
 pub const DEFAULT_SLIPPAGE: &str = "0.005";
 /// The maximum allowed swap slippage
 pub const MAX_ALLOWED_SLIPPAGE: &str = "0.5";
+/// The maximum amount of swap hooks that can be registered in a pair
+pub const MAX_SWAP_HOOKS: usize = 5;
+/// The gas limit for a single swap hook callback
+pub const SWAP_HOOK_GAS_LIMIT: u64 = 500_000;
+/// The amount of LP tokens that is locked forever in the pair on the first liquidity provision
+pub const MINIMUM_LIQUIDITY_AMOUNT: Uint128 = Uint128::new(1_000);
 
-// Decimal precision for TWAP results
-pub const TWAP_PRECISION: u8 = 6;
+pub use crate::constants::TWAP_PRECISION;
 
 /// This structure describes the parameters used for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -50,9 +57,34 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The referral code registered in the referral registry. A share of the commission is paid to the referrer
+        referral_code: Option<String>,
     },
     /// Update the pair configuration
     UpdateConfig { params: Binary },
+    /// Registers the sender as a swap hook. The sender is notified with a [`SwapHookMsg`] after swaps that offer
+    /// at least the threshold amount of an asset. Registering again replaces the thresholds
+    /// ## Executor
+    /// Only contracts whitelisted in the factory can execute this
+    RegisterSwapHook {
+        /// The minimum offer amounts that trigger the hook. Assets without a threshold never trigger it
+        thresholds: Vec<Asset>,
+    },
+    /// Removes a swap hook
+    /// ## Executor
+    /// The hook itself can execute this. Anyone can remove a hook that isn't whitelisted in the factory anymore
+    DeregisterSwapHook {
+        /// The hook contract to remove
+        contract_addr: String,
+    },
+    /// Pauses swaps and liquidity provision. Withdrawing liquidity stays possible
+    /// ## Executor
+    /// The factory owner or the factory's emergency admin can execute this
+    Pause {},
+    /// Resumes swaps and liquidity provision
+    /// ## Executor
+    /// Only the factory owner can execute this
+    Unpause {},
 }
 
 /// This structure describes a CW20 hook message.
@@ -64,6 +96,8 @@ pub enum Cw20HookMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The referral code registered in the referral registry. A share of the commission is paid to the referrer
+        referral_code: Option<String>,
     },
     /// Withdraw liquidity from the pool
     WithdrawLiquidity {},
@@ -87,6 +121,10 @@ pub enum QueryMsg {
     ReverseSimulation { ask_asset: Asset },
     /// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object
     CumulativePrices {},
+    /// Returns the registered swap hooks in a vector that contains objects of type [`SwapHook`]
+    SwapHooks {},
+    /// Returns whether swaps and liquidity provision are paused as a [`bool`]
+    Paused {},
 }
 
 /// This struct is used to return a query result with the total amount of LP tokens and the two assets in a specific pool.
@@ -142,6 +180,84 @@ pub struct CumulativePricesResponse {
     pub price1_cumulative_last: Uint128,
 }
 
+/// This structure describes a swap hook registered in a pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapHook {
+    /// The hook contract address
+    pub contract_addr: Addr,
+    /// The minimum offer amounts that trigger the hook
+    pub thresholds: Vec<Asset>,
+}
+
+impl SwapHook {
+    /// ## Description
+    /// Returns true if a swap offering the specified asset triggers the hook.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **offer_asset** is an object of type [`Asset`]. This is the asset offered in the swap.
+    pub fn is_triggered_by(&self, offer_asset: &Asset) -> bool {
+        self.thresholds.iter().any(|threshold| {
+            threshold.info.equal(&offer_asset.info) && offer_asset.amount >= threshold.amount
+        })
+    }
+}
+
+/// This structure describes the message that pairs send to swap hooks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapHookMsg {
+    /// Notifies the hook about a swap. The sender of the message is the pair
+    AfterSwap {
+        /// The address that performed the swap
+        sender: Addr,
+        /// The address that received the ask asset
+        receiver: Addr,
+        /// The asset offered in the swap
+        offer_asset: Asset,
+        /// The asset returned by the swap
+        return_asset: Asset,
+        /// The spread charged in the swap
+        spread_amount: Uint128,
+        /// The commission charged in the swap
+        commission_amount: Uint128,
+    },
+}
+
+/// ## Description
+/// Builds the submessages that notify the hooks triggered by a swap. The submessages are gas-limited and their
+/// errors are handled in the reply with the specified ID, so a failing hook can't revert the swap.
+/// ## Params
+/// * **hooks** is a slice that contains objects of type [`SwapHook`]. These are the hooks registered in the pair.
+///
+/// * **msg** is an object of type [`SwapHookMsg`]. This is the message sent to the triggered hooks.
+///
+/// * **reply_id** is an object of type [`u64`]. This is the reply ID used for hook errors.
+pub fn build_swap_hook_msgs(
+    hooks: &[SwapHook],
+    msg: &SwapHookMsg,
+    reply_id: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let SwapHookMsg::AfterSwap { offer_asset, .. } = msg;
+
+    hooks
+        .iter()
+        .filter(|hook| hook.is_triggered_by(offer_asset))
+        .map(|hook| {
+            let mut sub_msg = SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: hook.contract_addr.to_string(),
+                    msg: to_binary(msg)?,
+                    funds: vec![],
+                },
+                reply_id,
+            );
+            sub_msg.gas_limit = Some(SWAP_HOOK_GAS_LIMIT);
+            Ok(sub_msg)
+        })
+        .collect()
+}
+
 /// This structure describes a migration message.
 /// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -170,3 +286,62 @@ pub enum StablePoolUpdateParams {
     StartChangingAmp { next_amp: u64, next_amp_time: u64 },
     StopChangingAmp {},
 }
+
+/// ## Description
+/// A typed client for querying a pair contract from other contracts.
+pub struct PairQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+    pair: Addr,
+}
+
+impl<'a> PairQuerier<'a> {
+    /// ## Description
+    /// Creates a new client for the specified pair contract.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **pair** is an object of type [`Addr`]. This is the pair contract address.
+    pub fn new(querier: &'a QuerierWrapper<'a>, pair: Addr) -> Self {
+        PairQuerier { querier, pair }
+    }
+
+    /// ## Description
+    /// Returns the assets, LP token and type of the pair.
+    pub fn pair_info(&self) -> StdResult<PairInfo> {
+        self.querier
+            .query_wasm_smart(&self.pair, &QueryMsg::Pair {})
+    }
+
+    /// ## Description
+    /// Returns the reserves of the pair and the total supply of its LP token.
+    pub fn pool(&self) -> StdResult<PoolResponse> {
+        self.querier
+            .query_wasm_smart(&self.pair, &QueryMsg::Pool {})
+    }
+
+    /// ## Description
+    /// Simulates a swap of the specified asset in the pair.
+    /// ## Params
+    /// * **offer_asset** is an object of type [`Asset`]. This is the asset to swap.
+    pub fn simulate(&self, offer_asset: &Asset) -> StdResult<SimulationResponse> {
+        self.querier.query_wasm_smart(
+            &self.pair,
+            &QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+            },
+        )
+    }
+
+    /// ## Description
+    /// Simulates a swap in the pair that returns the specified asset.
+    /// ## Params
+    /// * **ask_asset** is an object of type [`Asset`]. This is the asset to receive.
+    pub fn reverse_simulate(&self, ask_asset: &Asset) -> StdResult<ReverseSimulationResponse> {
+        self.querier.query_wasm_smart(
+            &self.pair,
+            &QueryMsg::ReverseSimulation {
+                ask_asset: ask_asset.clone(),
+            },
+        )
+    }
+}
@@ -30,6 +30,8 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The referral code registered in the referral registry. A share of the commission is paid to the referrer
+        referral_code: Option<String>,
     },
     /// Update the pair configuration
     UpdateConfig { params: Binary },
@@ -55,6 +57,28 @@ pub enum ExecuteMsg {
         total_share: Uint128,
         receiver: Option<Addr>,
     },
+    /// Registers the sender as a swap hook. See [`super::pair::ExecuteMsg::RegisterSwapHook`]
+    /// ## Executor
+    /// Only contracts whitelisted in the factory can execute this
+    RegisterSwapHook {
+        /// The minimum offer amounts that trigger the hook. Assets without a threshold never trigger it
+        thresholds: Vec<Asset>,
+    },
+    /// Removes a swap hook
+    /// ## Executor
+    /// The hook itself can execute this. Anyone can remove a hook that isn't whitelisted in the factory anymore
+    DeregisterSwapHook {
+        /// The hook contract to remove
+        contract_addr: String,
+    },
+    /// Pauses swaps and liquidity provision. See [`super::pair::ExecuteMsg::Pause`]
+    /// ## Executor
+    /// The factory owner or the factory's emergency admin can execute this
+    Pause {},
+    /// Resumes swaps and liquidity provision
+    /// ## Executor
+    /// Only the factory owner can execute this
+    Unpause {},
 }
 
 /// This structure describes the query messages available in the contract.
@@ -77,6 +101,10 @@ pub enum QueryMsg {
     CumulativePrices {},
     /// Returns pending token rewards that can be claimed by a specific user using a [`Asset`] object.
     PendingReward { user: String },
+    /// Returns the registered swap hooks in a vector that contains objects of type [`super::pair::SwapHook`]
+    SwapHooks {},
+    /// Returns whether swaps and liquidity provision are paused as a [`bool`]
+    Paused {},
 }
 
 /// This struct is used to store bLUNA stableswap specific parameters.
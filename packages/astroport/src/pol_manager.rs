@@ -0,0 +1,166 @@
+use crate::asset::{Asset, PairInfo};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to approve strategies and withdraw assets (the Assembly)
+    pub owner: String,
+    /// Address that's allowed to run approved strategies
+    pub manager: String,
+    /// The factory contract address. Pairs and the generator are resolved through it
+    pub factory: String,
+    /// The ASTRO token contract address
+    pub astro_token: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Approves a strategy for a pair or replaces its parameters. The amounts already deposited are kept
+    /// ## Executor
+    /// Only the owner can execute this
+    SetStrategy {
+        /// The pair the strategy provides liquidity to
+        pair: String,
+        /// The maximum amounts of each pair asset that can be deposited in the pair
+        max_deposits: [Asset; 2],
+        /// The slippage tolerance used when providing liquidity
+        slippage_tolerance: Decimal,
+        /// The maximum spread used when swapping rewards during compounding
+        max_spread: Decimal,
+    },
+    /// Removes a strategy. The strategy must not have LP tokens staked in the generator
+    /// ## Executor
+    /// Only the owner can execute this
+    RemoveStrategy { pair: String },
+    /// Provides liquidity to the strategy's pair and stakes the LP tokens in the generator
+    /// ## Executor
+    /// The owner or the manager can execute this
+    ProvideLiquidity { pair: String, assets: [Asset; 2] },
+    /// Claims the generator rewards of the strategy's LP tokens
+    /// ## Executor
+    /// The owner or the manager can execute this
+    Harvest { pair: String },
+    /// Claims the generator rewards of the strategy's LP tokens, swaps half of them to the other pair asset
+    /// and provides the result as liquidity. Only pairs with ASTRO can be compounded
+    /// ## Executor
+    /// The owner or the manager can execute this
+    Compound { pair: String },
+    /// Unstakes LP tokens from the generator and withdraws the underlying assets from the pair
+    /// ## Executor
+    /// The owner or the manager can execute this
+    Unwind {
+        pair: String,
+        /// The amount of LP tokens to unwind. The whole position is unwound if not specified
+        amount: Option<Uint128>,
+    },
+    /// Sends assets held by the contract to the owner
+    /// ## Executor
+    /// Only the owner can execute this
+    Withdraw { assets: Vec<Asset> },
+    /// Updates the manager
+    /// ## Executor
+    /// Only the owner can execute this
+    UpdateConfig { manager: Option<String> },
+    /// Callbacks used while compounding
+    /// ## Executor
+    /// Callback functions can only be called by this contract
+    Callback(CallbackMsg),
+    /// Creates a request to change the contract's ownership
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the proposal to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes the callback messages used while compounding.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Swaps half of the harvested ASTRO to the other pair asset
+    SwapRewards {
+        /// The strategy's pair
+        pair: Addr,
+        /// The contract's ASTRO balance before the rewards were claimed
+        prev_astro_balance: Uint128,
+    },
+    /// Provides the remaining ASTRO and the swapped asset as liquidity
+    ProvideRewards {
+        /// The strategy's pair
+        pair: Addr,
+        /// The amount of harvested ASTRO that wasn't swapped
+        astro_amount: Uint128,
+        /// The contract's balance of the other pair asset before the swap
+        prev_balance: Asset,
+    },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns a strategy using a [`StrategyInfo`] object
+    Strategy { pair: String },
+    /// Returns strategies using a vector of [`StrategyInfo`] objects
+    Strategies {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the strategy's position in the generator using a [`PositionResponse`] object
+    Position { pair: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to approve strategies and withdraw assets
+    pub owner: Addr,
+    /// Address that's allowed to run approved strategies
+    pub manager: Addr,
+    /// The factory contract address
+    pub factory: Addr,
+    /// The ASTRO token contract address
+    pub astro_token: Addr,
+}
+
+/// This structure describes a strategy approved by the owner.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyInfo {
+    /// The pair the strategy provides liquidity to
+    pub pair: PairInfo,
+    /// The maximum amounts of each pair asset that can be deposited in the pair, in the pair's asset order
+    pub max_deposits: [Asset; 2],
+    /// The amounts of each pair asset currently deposited in the pair, in the pair's asset order.
+    /// Compounded rewards are not counted
+    pub deposited: [Asset; 2],
+    /// The slippage tolerance used when providing liquidity
+    pub slippage_tolerance: Decimal,
+    /// The maximum spread used when swapping rewards during compounding
+    pub max_spread: Decimal,
+}
+
+/// This structure describes the strategy's position in the generator.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    /// The amount of LP tokens staked in the generator
+    pub lp_staked: Uint128,
+    /// The amount of ASTRO rewards that can be harvested
+    pub pending_astro: Uint128,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,118 @@
+use crate::asset::AssetInfo;
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of decimals that a feed can report prices with
+pub const MAX_FEED_DECIMALS: u8 = 18;
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates or replaces the feed of a symbol
+    /// ## Executor
+    /// Only the current owner can execute this
+    SetFeed {
+        /// The symbol (or denom) that the feed is queried by
+        symbol: String,
+        /// The Astroport oracle contract that provides the TWAP
+        oracle: String,
+        /// The priced asset. It must be one of the oracle assets
+        asset_info: AssetInfo,
+        /// The asset the price is expressed in. It must be the other oracle asset
+        quote_asset_info: AssetInfo,
+        /// The amount of decimals that prices are reported with
+        decimals: u8,
+    },
+    /// Removes the feed of a symbol
+    /// ## Executor
+    /// Only the current owner can execute this
+    RemoveFeed {
+        /// The symbol of the feed to remove
+        symbol: String,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the feed of a symbol using a [`FeedResponse`] object
+    Feed { symbol: String },
+    /// Returns feeds ordered by their symbols using a vector that contains objects of type [`FeedResponse`]
+    Feeds {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the latest price of a symbol using a [`PriceResponse`] object
+    Price { symbol: String },
+    /// Returns the latest prices of several symbols using a vector that contains objects of type [`PriceResponse`]
+    Prices { symbols: Vec<String> },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+}
+
+/// This structure holds the parameters returned when querying for a feed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedResponse {
+    /// The symbol (or denom) that the feed is queried by
+    pub symbol: String,
+    /// The Astroport oracle contract that provides the TWAP
+    pub oracle: Addr,
+    /// The priced asset
+    pub asset_info: AssetInfo,
+    /// The asset the price is expressed in
+    pub quote_asset_info: AssetInfo,
+    /// The amount of decimals that prices are reported with
+    pub decimals: u8,
+}
+
+/// This structure holds the parameters returned when querying for a price.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceResponse {
+    /// The symbol (or denom) of the priced asset
+    pub symbol: String,
+    /// The price of one whole priced token in whole quote tokens, scaled by `10^decimals`
+    pub price: Uint128,
+    /// The amount of decimals that the price is reported with
+    pub decimals: u8,
+    /// The timestamp (in seconds) of the oracle TWAP update the price is based on
+    pub publish_time: u64,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
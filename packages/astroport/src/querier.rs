@@ -1,7 +1,8 @@
 use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::constants::BPS_DENOMINATOR;
 use crate::factory::{
-    ConfigResponse as FactoryConfigResponse, FeeInfoResponse, PairType, PairsResponse,
-    QueryMsg as FactoryQueryMsg,
+    ConfigResponse as FactoryConfigResponse, ExternalAdapterInfo, FeeDiscountConfig,
+    FeeInfoResponse, PairType, PairsResponse, PriceGuard, QueryMsg as FactoryQueryMsg,
 };
 use crate::pair::{QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse};
 
@@ -142,6 +143,10 @@ pub struct FeeInfo {
     pub total_fee_rate: Decimal,
     /// The amount of fees sent to the Maker contract
     pub maker_fee_rate: Decimal,
+    /// The referral registry contract
+    pub referral_registry: Option<Addr>,
+    /// The fee discount contract and the maximum discount it may grant
+    pub fee_discount: Option<FeeDiscountConfig>,
 }
 
 /// Returns the fee information for a specific pair type.
@@ -163,8 +168,10 @@ pub fn query_fee_info(
 
     Ok(FeeInfo {
         fee_address: res.fee_address,
-        total_fee_rate: Decimal::from_ratio(Uint128::from(res.total_fee_bps), Uint128::new(10000)),
-        maker_fee_rate: Decimal::from_ratio(Uint128::from(res.maker_fee_bps), Uint128::new(10000)),
+        total_fee_rate: Decimal::from_ratio(res.total_fee_bps, BPS_DENOMINATOR),
+        maker_fee_rate: Decimal::from_ratio(res.maker_fee_bps, BPS_DENOMINATOR),
+        referral_registry: res.referral_registry,
+        fee_discount: res.fee_discount,
     })
 }
 
@@ -188,6 +195,61 @@ pub fn query_pair_info(
     }))
 }
 
+/// Returns the addresses of contracts that are allowed to register swap hooks in pairs.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is an object of type [`Addr`] and it's the Astroport factory contract address
+pub fn query_swap_hooks_whitelist(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+) -> StdResult<Vec<Addr>> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::SwapHooksWhitelist {})?,
+    }))
+}
+
+/// Returns information about an external adapter registered in the factory.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is an object of type [`Addr`] and it's the Astroport factory contract address
+///
+/// * **adapter** is an object of type [`Addr`]. This is the adapter contract address.
+pub fn query_external_adapter(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    adapter: &Addr,
+) -> StdResult<ExternalAdapterInfo> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::ExternalAdapter {
+            adapter: adapter.to_string(),
+        })?,
+    }))
+}
+
+/// Returns the price guard configured for a pair in the factory.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is an object of type [`Addr`] and it's the Astroport factory contract address
+///
+/// * **pair** is an object of type [`Addr`]. This is the pair contract address.
+pub fn query_price_guard(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    pair: &Addr,
+) -> StdResult<Option<PriceGuard>> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::PriceGuard {
+            pair_addr: pair.to_string(),
+        })?,
+    }))
+}
+
 /// Returns a vector that contains items of type [`PairInfo`] which symbolize pairs instantiated in the Astroport factory
 /// ## Params
 /// * **querier** is an object of type [`QuerierWrapper`].
@@ -0,0 +1,205 @@
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The Astroport factory contract address. Only pairs registered in the factory can record swaps
+    pub factory: String,
+    /// The share of the swap commission paid to the address registered for a referral code
+    pub commission_share: Decimal,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Registers a new referral code. The sender becomes the code owner
+    RegisterCode {
+        /// The referral code
+        code: String,
+        /// The address that receives the referral fees
+        fee_address: String,
+    },
+    /// Updates the address that receives the referral fees of a code
+    /// ## Executor
+    /// Only the code owner can execute this
+    UpdateFeeAddress {
+        /// The referral code
+        code: String,
+        /// The new address that receives the referral fees
+        fee_address: String,
+    },
+    /// Records a referred swap in the statistics of a code
+    /// ## Executor
+    /// Only pairs registered in the factory can execute this
+    RecordSwap {
+        /// The referral code
+        code: String,
+        /// The asset offered in the swap
+        offer_asset: Asset,
+        /// The referral fee paid to the code's fee address
+        referral_fee: Asset,
+    },
+    /// Updates the share of the swap commission paid to referrers
+    /// ## Executor
+    /// Only the current owner can execute this
+    UpdateConfig {
+        /// The new share of the swap commission paid to referrers
+        commission_share: Option<Decimal>,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns information about a referral code using a [`ReferralInfoResponse`] object
+    ReferralInfo { code: String },
+    /// Returns the swap statistics of a referral code using a [`StatsResponse`] object
+    Stats { code: String },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The Astroport factory contract address
+    pub factory: Addr,
+    /// The share of the swap commission paid to the address registered for a referral code
+    pub commission_share: Decimal,
+}
+
+/// This structure holds the parameters returned when querying for a referral code.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferralInfoResponse {
+    /// The address that registered the code
+    pub owner: Addr,
+    /// The address that receives the referral fees
+    pub fee_address: Addr,
+    /// The share of the swap commission paid to the fee address
+    pub commission_share: Decimal,
+}
+
+/// This structure holds the swap statistics of a referral code for a single asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetStats {
+    /// The asset the statistics are tracked for
+    pub info: AssetInfo,
+    /// The total amount of the asset offered in referred swaps
+    pub volume: Uint128,
+    /// The total amount of the asset paid as referral fees
+    pub referral_fees: Uint128,
+}
+
+/// This structure holds the parameters returned when querying for the statistics of a referral code.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    /// The amount of referred swaps
+    pub swap_count: u64,
+    /// Volumes and referral fees per asset
+    pub assets: Vec<AssetStats>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns information about a referral code using a [`ReferralInfoResponse`] object.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **referral_registry** is an object of type [`Addr`]. This is the referral registry contract address.
+///
+/// * **code** is an object of type [`str`]. This is the referral code to query.
+pub fn query_referral_info(
+    querier: &QuerierWrapper,
+    referral_registry: &Addr,
+    code: &str,
+) -> StdResult<ReferralInfoResponse> {
+    querier.query_wasm_smart(
+        referral_registry,
+        &QueryMsg::ReferralInfo {
+            code: code.to_string(),
+        },
+    )
+}
+
+/// ## Description
+/// Computes the referral fee of a swap. Returns the fee together with the messages that pay it to the
+/// fee address registered for the code and record the swap in the referral registry.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **referral_registry** is an object of type [`Addr`]. This is the referral registry contract address.
+///
+/// * **code** is an object of type [`str`]. This is the referral code used in the swap.
+///
+/// * **offer_asset** is an object of type [`Asset`]. This is the asset offered in the swap.
+///
+/// * **commission** is an object of type [`Asset`]. This is the commission charged in the swap.
+///
+/// * **max_fee** is an object of type [`Uint128`]. This is the part of the commission that can be paid to the referrer.
+pub fn build_referral_fee_msgs(
+    querier: &QuerierWrapper,
+    referral_registry: &Addr,
+    code: &str,
+    offer_asset: &Asset,
+    commission: &Asset,
+    max_fee: Uint128,
+) -> StdResult<(Asset, Vec<CosmosMsg>)> {
+    let referral_info = query_referral_info(querier, referral_registry, code)?;
+
+    let referral_fee = Asset {
+        info: commission.info.clone(),
+        amount: (commission.amount * referral_info.commission_share).min(max_fee),
+    };
+
+    let mut messages = vec![];
+    if !referral_fee.amount.is_zero() {
+        messages.push(
+            referral_fee
+                .clone()
+                .into_msg(querier, referral_info.fee_address)?,
+        );
+    }
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: referral_registry.to_string(),
+        msg: to_binary(&ExecuteMsg::RecordSwap {
+            code: code.to_string(),
+            offer_asset: offer_asset.clone(),
+            referral_fee: referral_fee.clone(),
+        })?,
+        funds: vec![],
+    }));
+
+    Ok((referral_fee, messages))
+}
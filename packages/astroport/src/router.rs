@@ -1,10 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
 
-use crate::asset::AssetInfo;
+use crate::asset::{Asset, AssetInfo};
 
 pub const MAX_SWAP_OPERATIONS: usize = 50;
 
@@ -33,15 +33,42 @@ pub enum SwapOperation {
         /// Information about the asset we swap to
         ask_asset_info: AssetInfo,
     },
+    /// Swap using an external AMM pair that implements the Astroport pair interface.
+    /// The pair must be registered and enabled as an external adapter in the factory
+    ExternalSwap {
+        /// The address of the external pair contract
+        pair_contract: String,
+        /// Information about the asset being swapped
+        offer_asset_info: AssetInfo,
+        /// Information about the asset we swap to
+        ask_asset_info: AssetInfo,
+    },
 }
 
 impl SwapOperation {
+    /// Returns information about the asset that is swapped in this operation.
+    pub fn get_offer_asset_info(&self) -> AssetInfo {
+        match self {
+            SwapOperation::NativeSwap { offer_denom, .. } => AssetInfo::NativeToken {
+                denom: offer_denom.clone(),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info, ..
+            }
+            | SwapOperation::ExternalSwap {
+                offer_asset_info, ..
+            } => offer_asset_info.clone(),
+        }
+    }
+
+    /// Returns information about the asset that is received in this operation.
     pub fn get_target_asset_info(&self) -> AssetInfo {
         match self {
             SwapOperation::NativeSwap { ask_denom, .. } => AssetInfo::NativeToken {
                 denom: ask_denom.clone(),
             },
-            SwapOperation::AstroSwap { ask_asset_info, .. } => ask_asset_info.clone(),
+            SwapOperation::AstroSwap { ask_asset_info, .. }
+            | SwapOperation::ExternalSwap { ask_asset_info, .. } => ask_asset_info.clone(),
         }
     }
 }
@@ -58,6 +85,8 @@ pub enum ExecuteMsg {
         minimum_receive: Option<Uint128>,
         to: Option<Addr>,
         max_spread: Option<Decimal>,
+        /// The referral code passed to every Astroport pair used in the swap
+        referral_code: Option<String>,
     },
 
     /// Internal use
@@ -66,6 +95,37 @@ pub enum ExecuteMsg {
         operation: SwapOperation,
         to: Option<String>,
         max_spread: Option<Decimal>,
+        referral_code: Option<String>,
+    },
+    /// IbcHookSwap swaps native tokens received in an ICS-20 transfer. It is meant to be called by the
+    /// ibc-hooks middleware with the message specified in the transfer memo
+    IbcHookSwap {
+        /// The asset received in the transfer. It must match the funds sent with the message
+        offer_asset: Asset,
+        /// A vector of swap operations. The first operation must offer the received asset
+        operations: Vec<SwapOperation>,
+        /// The minimum amount of tokens to get from a swap
+        minimum_receive: Option<Uint128>,
+        /// Max spread
+        max_spread: Option<Decimal>,
+        /// The recipient of the ask tokens
+        receiver: SwapReceiver,
+    },
+    /// Internal use
+    /// SendIbcTransfer sends the ask tokens received by the router in a swap to an address on another chain
+    SendIbcTransfer {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        receiver: RemoteReceiver,
+    },
+    /// RefundIbcTransfer sends the ask tokens of an expired IBC transfer to a recipient on this chain.
+    /// Failed and timed out transfers are refunded to the router, so only the factory owner can
+    /// return them to the swapper
+    RefundIbcTransfer {
+        /// The identifier of the transfer
+        transfer_id: u64,
+        /// The recipient of the refunded tokens
+        recipient: String,
     },
     /// Internal use
     /// AssertMinimumReceive checks that a receiver will get a minimum amount of tokens from a swap
@@ -77,6 +137,33 @@ pub enum ExecuteMsg {
     },
 }
 
+/// This enum describes the recipient of the ask tokens in an IBC hook swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapReceiver {
+    /// Sends the ask tokens to an address on this chain
+    Local {
+        /// The recipient address
+        address: String,
+    },
+    /// Sends the ask tokens to an address on another chain over IBC
+    Remote(RemoteReceiver),
+}
+
+/// This structure describes a recipient on another chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RemoteReceiver {
+    /// The local channel used to send the ask tokens
+    pub channel: String,
+    /// The recipient address on the remote chain
+    pub address: String,
+    /// The IBC packet timeout in seconds
+    pub timeout: Option<u64>,
+    /// The cw20-ics20 contract used to send CW20 ask tokens. Native ask tokens are sent
+    /// with an ICS-20 transfer
+    pub ics20_contract: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
@@ -89,6 +176,8 @@ pub enum Cw20HookMsg {
         to: Option<String>,
         /// Max spread
         max_spread: Option<Decimal>,
+        /// The referral code passed to every Astroport pair used in the swap
+        referral_code: Option<String>,
     },
 }
 
@@ -105,6 +194,47 @@ pub enum QueryMsg {
         /// The swap operations to perform, each swap involving a specific pool
         operations: Vec<SwapOperation>,
     },
+    /// QuoteRoutes finds direct and two-hop routes between two assets using the pairs registered
+    /// in the factory, simulates every route and returns the best quotes
+    QuoteRoutes {
+        /// Information about the asset being swapped
+        offer_asset_info: AssetInfo,
+        /// Information about the asset we swap to
+        ask_asset_info: AssetInfo,
+        /// The amount of tokens to swap
+        offer_amount: Uint128,
+        /// The maximum amount of quotes to return
+        limit: Option<u32>,
+    },
+    /// IbcTransfers returns the IBC transfers sent by the router that were not refunded
+    IbcTransfers {
+        /// The transfer identifier to start reading after
+        start_after: Option<u64>,
+        /// The maximum amount of transfers to return
+        limit: Option<u32>,
+    },
+}
+
+/// This structure describes an IBC transfer sent by the router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransferResponse {
+    /// The identifier of the transfer
+    pub transfer_id: u64,
+    /// The ask tokens sent in the transfer
+    pub asset: Asset,
+    /// The local channel used to send the ask tokens
+    pub channel: String,
+    /// The recipient address on the remote chain
+    pub receiver: String,
+    /// The timestamp in seconds after which the transfer can no longer be received
+    pub timeout: u64,
+}
+
+/// This structure describes a custom struct to return the IBC transfers sent by the router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransfersResponse {
+    /// The transfers ordered by their identifiers
+    pub transfers: Vec<IbcTransferResponse>,
 }
 
 /// This structure describes a custom struct to return a query response containing the base contract configuration.
@@ -121,7 +251,90 @@ pub struct SimulateSwapOperationsResponse {
     pub amount: Uint128,
 }
 
+/// This structure describes a custom struct to return a query response containing the best route quotes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuoteRoutesResponse {
+    /// The route quotes sorted by the amount of received tokens in descending order
+    pub routes: Vec<RouteQuote>,
+}
+
+/// This structure describes a swap route together with the end amount of its simulation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteQuote {
+    /// The swap operations that make up the route
+    pub operations: Vec<SwapOperation>,
+    /// The amount of tokens received in a swap simulation
+    pub return_amount: Uint128,
+}
+
 /// This structure describes a migration message.
 /// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
+
+/// ## Description
+/// A typed client for querying the router contract from other contracts.
+pub struct RouterQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+    router: Addr,
+}
+
+impl<'a> RouterQuerier<'a> {
+    /// ## Description
+    /// Creates a new client for the specified router contract.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **router** is an object of type [`Addr`]. This is the router contract address.
+    pub fn new(querier: &'a QuerierWrapper<'a>, router: Addr) -> Self {
+        RouterQuerier { querier, router }
+    }
+
+    /// ## Description
+    /// Simulates a multi-hop swap along the specified route.
+    /// ## Params
+    /// * **offer_amount** is an object of type [`Uint128`]. This is the amount of tokens offered to the first swap.
+    ///
+    /// * **operations** is a vector that contains objects of type [`SwapOperation`]. These are the swaps to simulate.
+    pub fn simulate_route(
+        &self,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> StdResult<SimulateSwapOperationsResponse> {
+        self.querier.query_wasm_smart(
+            &self.router,
+            &QueryMsg::SimulateSwapOperations {
+                offer_amount,
+                operations,
+            },
+        )
+    }
+
+    /// ## Description
+    /// Returns the best routes between two assets found by the router.
+    /// ## Params
+    /// * **offer_asset_info** is an object of type [`AssetInfo`]. This is the asset to swap.
+    ///
+    /// * **ask_asset_info** is an object of type [`AssetInfo`]. This is the asset to receive.
+    ///
+    /// * **offer_amount** is an object of type [`Uint128`]. This is the amount of tokens to swap.
+    ///
+    /// * **limit** is an [`Option`] field of type [`u32`]. This is the maximum amount of routes to return.
+    pub fn quote_routes(
+        &self,
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+        offer_amount: Uint128,
+        limit: Option<u32>,
+    ) -> StdResult<QuoteRoutesResponse> {
+        self.querier.query_wasm_smart(
+            &self.router,
+            &QueryMsg::QuoteRoutes {
+                offer_asset_info,
+                ask_asset_info,
+                offer_amount,
+                limit,
+            },
+        )
+    }
+}
@@ -0,0 +1,163 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The IBC application version used by the Assembly on the hub chain and by satellites
+pub const SATELLITE_IBC_VERSION: &str = "astroport-satellite-v1";
+
+/// This structure stores general parameters for the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Address that's allowed to change contract parameters
+    pub owner: String,
+    /// The IBC port of the Assembly contract on the hub chain
+    pub main_controller_port: String,
+    /// The delay (in seconds) between receiving a proposal and the earliest time its messages can be executed.
+    /// Proposals are executed as soon as they are received if the delay is zero
+    pub timelock_period: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Updates general contract parameters
+    UpdateConfig {
+        /// The IBC port of the Assembly contract on the hub chain
+        main_controller_port: Option<String>,
+        /// The IBC channel used to receive proposals from the hub chain
+        gov_channel: Option<String>,
+        /// The delay (in seconds) between receiving a proposal and the earliest time it can be executed
+        timelock_period: Option<u64>,
+    },
+    /// Executes the messages of a proposal received from the hub chain.
+    /// Only the contract itself can call this
+    ExecuteProposal {
+        /// The proposal identifier on the hub chain
+        id: u64,
+        /// The messages to execute
+        messages: Vec<CosmosMsg>,
+    },
+    /// Executes a queued proposal once its timelock has passed.
+    /// Anyone can execute this
+    ExecuteQueuedProposal {
+        /// The proposal identifier on the hub chain
+        id: u64,
+    },
+    /// Removes a queued proposal without executing it
+    /// ## Executor
+    /// Only the current owner can execute this
+    CancelProposal {
+        /// The proposal identifier on the hub chain
+        id: u64,
+    },
+    /// Creates a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change contract ownership
+    /// ## Executor
+    /// Only the current owner can execute this
+    DropOwnershipProposal {},
+    /// Claims contract ownership
+    /// ## Executor
+    /// Only the newly proposed owner can execute this
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns the execution status of a proposal using a [`ProposalStatus`] object
+    ProposalStatus {
+        /// The proposal identifier on the hub chain
+        id: u64,
+    },
+    /// Returns proposals that wait for their timelock to pass using a vector of [`QueuedProposalResponse`] objects
+    QueuedProposals {
+        /// The proposal identifier after which to start reading
+        start_after: Option<u64>,
+        /// The number of proposals to read
+        limit: Option<u32>,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The IBC port of the Assembly contract on the hub chain
+    pub main_controller_port: String,
+    /// The IBC channel used to receive proposals from the hub chain
+    pub gov_channel: Option<String>,
+    /// The delay (in seconds) between receiving a proposal and the earliest time it can be executed
+    pub timelock_period: u64,
+}
+
+/// This structure describes a proposal that waits for its timelock to pass.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedProposalResponse {
+    /// The proposal identifier on the hub chain
+    pub id: u64,
+    /// The messages to execute
+    pub messages: Vec<CosmosMsg>,
+    /// The timestamp (in seconds) after which the proposal can be executed
+    pub executable_at: u64,
+}
+
+/// This enum describes the execution status of a proposal received from the hub chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// The proposal waits for its timelock to pass
+    Queued {
+        /// The timestamp (in seconds) after which the proposal can be executed
+        executable_at: u64,
+    },
+    /// The proposal was canceled before it was executed
+    Canceled {},
+    /// All proposal messages were executed successfully
+    Executed {},
+    /// Proposal execution failed
+    Failed {
+        /// The execution error
+        error: String,
+    },
+}
+
+/// This enum describes the IBC packets sent from the Assembly on the hub chain to satellites.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SatelliteMsg {
+    /// Executes the messages of a passed proposal
+    ExecuteProposal {
+        /// The proposal identifier on the hub chain
+        id: u64,
+        /// The messages to execute on the satellite chain
+        messages: Vec<CosmosMsg>,
+    },
+}
+
+/// This enum describes the acknowledgements that satellites send back to the hub chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SatelliteAck {
+    /// The packet was processed successfully
+    Result(Binary),
+    /// The packet could not be processed
+    Error(String),
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
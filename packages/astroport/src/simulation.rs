@@ -0,0 +1,256 @@
+use crate::{DecimalCheckedOps, U256};
+use cosmwasm_std::{Decimal, Fraction, StdError, StdResult, Uint128, Uint256};
+use std::convert::TryFrom;
+
+/// Decimal precision (10^18) used for all intermediate swap calculations
+const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+/// ## Description
+/// Returns the result of a swap in a constant product (XYK) pool as a tuple of
+/// (return amount, spread amount, commission amount).
+/// This is the exact math used by the XYK pair contract, so it can be used to compute swap quotes off-chain.
+/// ## Params
+/// * **offer_pool** is an object of type [`Uint128`]. This is the total amount of offer assets in the pool.
+///
+/// * **ask_pool** is an object of type [`Uint128`]. This is the total amount of ask assets in the pool.
+///
+/// * **offer_amount** is an object of type [`Uint128`]. This is the amount of offer assets to swap.
+///
+/// * **commission_rate** is an object of type [`Decimal`]. This is the total amount of fees charged for the swap.
+pub fn compute_swap(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    if offer_pool.is_zero() {
+        return Err(StdError::generic_err("Offer pool is empty"));
+    }
+
+    let offer_pool = Uint256::from(offer_pool);
+    let ask_pool = Uint256::from(ask_pool);
+    let offer_amount = Uint256::from(offer_amount);
+    let fractional = Uint256::from(DECIMAL_FRACTIONAL);
+
+    // offer => ask
+    // ask_amount = (ask_pool - cp / (offer_pool + offer_amount))
+    let cp = offer_pool.checked_mul(ask_pool)?;
+    let return_amount = ask_pool
+        .checked_mul(fractional)?
+        .checked_sub(cp.multiply_ratio(fractional, offer_pool.checked_add(offer_amount)?))?
+        .checked_div(fractional)?;
+
+    // Calculate spread & commission
+    let spread_amount = offer_amount
+        .multiply_ratio(ask_pool.multiply_ratio(fractional, offer_pool), fractional)
+        .checked_sub(return_amount)?;
+    let commission_amount =
+        return_amount.multiply_ratio(commission_rate.numerator(), commission_rate.denominator());
+
+    // The commision (minus the part that goes to the Maker contract) will be absorbed by the pool
+    let return_amount = return_amount.checked_sub(commission_amount)?;
+    Ok((
+        to_uint128(return_amount)?,
+        to_uint128(spread_amount)?,
+        to_uint128(commission_amount)?,
+    ))
+}
+
+/// ## Description
+/// Returns the amount of offer assets needed to receive a specified amount of ask assets from a constant
+/// product (XYK) pool as a tuple of (offer amount, spread amount, commission amount).
+/// This is the exact math used by the XYK pair contract, so it can be used to compute swap quotes off-chain.
+/// ## Params
+/// * **offer_pool** is an object of type [`Uint128`]. This is the total amount of offer assets in the pool.
+///
+/// * **ask_pool** is an object of type [`Uint128`]. This is the total amount of ask assets in the pool.
+///
+/// * **ask_amount** is an object of type [`Uint128`]. This is the amount of ask assets to swap to.
+///
+/// * **commission_rate** is an object of type [`Decimal`]. This is the total amount of fees charged for the swap.
+pub fn compute_offer_amount(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    if offer_pool.is_zero() {
+        return Err(StdError::generic_err("Offer pool is empty"));
+    }
+
+    // ask => offer
+    // offer_amount = cp / (ask_pool - ask_amount / (1 - commission_rate)) - offer_pool
+    let cp = Uint256::from(offer_pool).checked_mul(Uint256::from(ask_pool))?;
+    let one_minus_commission =
+        Uint128::from(DECIMAL_FRACTIONAL).checked_sub(commission_rate.numerator().into())?;
+    if one_minus_commission.is_zero() {
+        return Err(StdError::generic_err("Commission rate must be less than 1"));
+    }
+    let inv_one_minus_commission = Decimal::from_ratio(DECIMAL_FRACTIONAL, one_minus_commission);
+
+    let before_commission_deduction = inv_one_minus_commission.checked_mul_floor(ask_amount)?;
+    let offer_amount =
+        to_uint128(cp.checked_div(ask_pool.checked_sub(before_commission_deduction)?.into())?)?
+            .checked_sub(offer_pool)?;
+
+    let spread_amount = Decimal::from_ratio(ask_pool, offer_pool)
+        .checked_mul_floor(offer_amount)?
+        .checked_sub(before_commission_deduction)
+        .unwrap_or_else(|_| Uint128::zero());
+    let commission_amount = commission_rate.checked_mul_floor(before_commission_deduction)?;
+    Ok((offer_amount, spread_amount, commission_amount))
+}
+
+/// ## Description
+/// Returns the amount of LP tokens minted for a deposit in a constant product (XYK) pool.
+/// The first deposit mints the geometric mean of the deposited amounts, while subsequent deposits
+/// mint LP tokens in proportion to the least valuable side of the deposit.
+/// This is the exact math used by the XYK pair contract.
+/// ## Params
+/// * **deposits** is an array of [`Uint128`] type items. These are the deposited amounts.
+///
+/// * **pools** is an array of [`Uint128`] type items. These are the pool balances before the deposit.
+///
+/// * **total_share** is an object of type [`Uint128`]. This is the total amount of LP tokens currently minted.
+pub fn compute_lp_share(
+    deposits: [Uint128; 2],
+    pools: [Uint128; 2],
+    total_share: Uint128,
+) -> StdResult<Uint128> {
+    if total_share.is_zero() {
+        // Initial share = collateral amount
+        return Ok(Uint128::new(
+            (U256::from(deposits[0].u128()) * U256::from(deposits[1].u128()))
+                .integer_sqrt()
+                .as_u128(),
+        ));
+    }
+
+    if pools[0].is_zero() || pools[1].is_zero() {
+        return Err(StdError::generic_err("Pool is empty"));
+    }
+
+    // min(1, 2)
+    // 1. sqrt(deposit_0 * exchange_rate_0_to_1 * deposit_0) * (total_share / sqrt(pool_0 * pool_1))
+    // == deposit_0 * total_share / pool_0
+    // 2. sqrt(deposit_1 * exchange_rate_1_to_0 * deposit_1) * (total_share / sqrt(pool_1 * pool_1))
+    // == deposit_1 * total_share / pool_1
+    Ok(std::cmp::min(
+        deposits[0].multiply_ratio(total_share, pools[0]),
+        deposits[1].multiply_ratio(total_share, pools[1]),
+    ))
+}
+
+/// ## Description
+/// Returns the amounts of pool assets that are withdrawn when burning LP tokens.
+/// Amounts are rounded down so that the pool never pays out more than the burned share.
+/// This is the exact math used by the XYK pair contract.
+/// ## Params
+/// * **pools** is an array of [`Uint128`] type items. These are the pool balances.
+///
+/// * **amount** is an object of type [`Uint128`]. This is the amount of LP tokens to burn.
+///
+/// * **total_share** is an object of type [`Uint128`]. This is the total amount of LP tokens currently minted.
+pub fn compute_withdrawn_amounts(
+    pools: [Uint128; 2],
+    amount: Uint128,
+    total_share: Uint128,
+) -> StdResult<[Uint128; 2]> {
+    let mut share_ratio = Decimal::zero();
+    if !total_share.is_zero() {
+        share_ratio = Decimal::from_ratio(amount, total_share);
+    }
+
+    Ok([
+        share_ratio.checked_mul_floor(pools[0])?,
+        share_ratio.checked_mul_floor(pools[1])?,
+    ])
+}
+
+/// ## Description
+/// Returns the amount of ASTRO emitted for a generator over a number of blocks.
+/// This is the exact math used by the Generator contract.
+/// ## Params
+/// * **n_blocks** is an object of type [`Uint128`]. This is the number of blocks for which rewards are accrued.
+///
+/// * **tokens_per_block** is an object of type [`Uint128`]. This is the total amount of ASTRO emitted per block.
+///
+/// * **alloc_point** is an object of type [`Uint128`]. This is the generator's allocation point.
+///
+/// * **total_alloc_point** is an object of type [`Uint128`]. This is the sum of allocation points of all active generators.
+pub fn calculate_generator_rewards(
+    n_blocks: Uint128,
+    tokens_per_block: Uint128,
+    alloc_point: Uint128,
+    total_alloc_point: Uint128,
+) -> StdResult<Uint128> {
+    if total_alloc_point.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    // The product of the block count, emission rate and allocation can exceed 128 bits
+    to_uint128(
+        Uint256::from(n_blocks)
+            .checked_mul(tokens_per_block.into())?
+            .checked_mul(alloc_point.into())?
+            .checked_div(total_alloc_point.into())?,
+    )
+}
+
+/// ## Description
+/// Returns the amount of rewards per staked LP token. Unlike [`Decimal::from_ratio`], this returns an error
+/// instead of panicking if the result doesn't fit in a [`Decimal`].
+/// ## Params
+/// * **rewards** is an object of type [`Uint128`]. These are the rewards to distribute.
+///
+/// * **lp_supply** is an object of type [`Uint128`]. This is the total amount of LP tokens staked.
+pub fn calculate_rewards_per_share(rewards: Uint128, lp_supply: Uint128) -> StdResult<Decimal> {
+    if lp_supply.is_zero() {
+        return Err(StdError::generic_err("LP supply is empty"));
+    }
+
+    let atomics = to_uint128(
+        Uint256::from(rewards)
+            .checked_mul(Uint256::from(DECIMAL_FRACTIONAL))?
+            .checked_div(lp_supply.into())?,
+    )?;
+
+    Ok(Decimal::from_ratio(atomics, DECIMAL_FRACTIONAL))
+}
+
+/// ## Description
+/// Returns the amount of rewards a staker can claim from a generator.
+/// This is the exact math used by the Generator contract.
+/// ## Params
+/// * **acc_per_share** is an object of type [`Decimal`]. This is the generator's accumulated rewards per LP token.
+///
+/// * **new_rewards** is an object of type [`Uint128`]. These are the rewards accrued since the generator was last updated.
+///
+/// * **lp_supply** is an object of type [`Uint128`]. This is the total amount of LP tokens staked in the generator.
+///
+/// * **user_amount** is an object of type [`Uint128`]. This is the amount of LP tokens staked by the user.
+///
+/// * **reward_debt** is an object of type [`Uint128`]. This is the user's reward debt.
+pub fn calculate_pending_rewards(
+    acc_per_share: Decimal,
+    new_rewards: Uint128,
+    lp_supply: Uint128,
+    user_amount: Uint128,
+    reward_debt: Uint128,
+) -> StdResult<Uint128> {
+    let mut acc_per_share = acc_per_share;
+    if !new_rewards.is_zero() && !lp_supply.is_zero() {
+        acc_per_share =
+            acc_per_share.checked_add(calculate_rewards_per_share(new_rewards, lp_supply)?)?;
+    }
+
+    Ok(acc_per_share
+        .checked_mul_floor(user_amount)?
+        .saturating_sub(reward_debt))
+}
+
+/// ## Description
+/// Converts a [`Uint256`] value into a [`Uint128`] value, returning an error on overflow.
+fn to_uint128(value: Uint256) -> StdResult<Uint128> {
+    Uint128::try_from(value).map_err(|e| StdError::generic_err(e.to_string()))
+}
@@ -1,8 +1,16 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::astro_converter::{NativeAstro, NativeAstroParams};
+
+/// The amount of xASTRO minted to the staking contract itself and locked forever on the first stake
+pub const MINIMUM_STAKE_AMOUNT: Uint128 = Uint128::new(1_000);
+
+/// The minimum amount of seconds between two xASTRO exchange rate checkpoints
+pub const EXCHANGE_RATE_CHECKPOINT_INTERVAL: u64 = 86_400;
+
 /// This structure describes the parameters used for creating a contract.
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct InstantiateMsg {
@@ -20,6 +28,13 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Receive receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
     Receive(Cw20ReceiveMsg),
+    /// Deposits native ASTRO sent along with the message in exchange for xASTRO.
+    /// Only available once the contract has been switched to native ASTRO
+    Enter {},
+    /// Adds the native ASTRO sent along with the message to the staking pool as fees without minting xASTRO.
+    /// The fee inflow is recorded in the exchange rate history right away.
+    /// Only available once the contract has been switched to native ASTRO
+    DistributeFees {},
 }
 
 /// This structure describes the query messages available in the contract.
@@ -30,6 +45,26 @@ pub enum QueryMsg {
     Config {},
     TotalShares {},
     TotalDeposit {},
+    /// ExchangeRateAt returns the latest [`ExchangeRateCheckpoint`] taken at or before the specified timestamp
+    ExchangeRateAt {
+        timestamp: u64,
+    },
+}
+
+/// This structure describes the xASTRO exchange rate at a point in time.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateCheckpoint {
+    /// The timestamp (in seconds) when the checkpoint was taken
+    pub timestamp: u64,
+    /// The amount of ASTRO in the staking pool
+    pub total_deposit: Uint128,
+    /// The total xASTRO supply
+    pub total_shares: Uint128,
+    /// The amount of ASTRO that one xASTRO can be redeemed for
+    pub exchange_rate: Decimal,
+    /// The total amount of ASTRO fees distributed to stakers up to the checkpoint
+    #[serde(default)]
+    pub total_fees: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -38,12 +73,17 @@ pub struct ConfigResponse {
     pub deposit_token_addr: Addr,
     /// The xASTRO token address
     pub share_token_addr: Addr,
+    /// The native ASTRO used instead of CW20 ASTRO, if the contract has been switched to it
+    pub native_astro: Option<NativeAstro>,
 }
 
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Switches the contract to native ASTRO. The CW20 ASTRO held by the contract is converted
+    #[serde(default)]
+    pub native_astro: Option<NativeAstroParams>,
+}
 
 /// This structure describes a CW20 hook message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -53,4 +93,7 @@ pub enum Cw20HookMsg {
     Enter {},
     /// Burns xASTRO in exchange for ASTRO
     Leave {},
+    /// Adds ASTRO to the staking pool as fees without minting xASTRO.
+    /// The fee inflow is recorded in the exchange rate history right away
+    DistributeFees {},
 }
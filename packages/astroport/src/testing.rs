@@ -1,13 +1,27 @@
-use crate::asset::{format_lp_token_name, Asset, AssetInfo, PairInfo};
+use crate::asset::{
+    format_lp_token_name, has_duplicate_asset_infos, pair_key, sort_asset_infos,
+    validate_native_denom, Asset, AssetInfo, PairInfo,
+};
+use crate::attr_builder::AttrBuilder;
+use crate::common::assert_migration_allowed;
 use crate::mock_querier::mock_dependencies;
 use crate::querier::{
-    query_all_balances, query_balance, query_pair_info, query_supply, query_token_balance,
+    query_all_balances, query_balance, query_pair_info, query_supply, query_token_balance, FeeInfo,
 };
 
-use crate::factory::PairType;
+use crate::factory::{FactoryQuerier, FeeDiscountConfig, PairType};
+use crate::fee_discount::{discounted_fee_rate, FeeDiscountResponse};
+use crate::ics20::{build_ics20_transfer_msg, validate_ibc_timeout, TransferMsg};
+use crate::simulation::{
+    calculate_generator_rewards, calculate_pending_rewards, calculate_rewards_per_share,
+    compute_offer_amount, compute_swap,
+};
 use crate::DecimalCheckedOps;
 use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
-use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
+use cosmwasm_std::{
+    attr, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Querier,
+    QuerierResult, QuerierWrapper, SystemError, SystemResult, Uint128, WasmMsg,
+};
 use cw20::Cw20ExecuteMsg;
 
 #[test]
@@ -280,6 +294,14 @@ fn query_astroport_pair_contract() {
 
     assert_eq!(pair_info.contract_addr, String::from("pair0000"),);
     assert_eq!(pair_info.liquidity_token, String::from("liquidity0000"),);
+
+    // The typed factory client returns the same pair
+    let querier = deps.as_ref().querier;
+    let factory = FactoryQuerier::new(&querier, Addr::unchecked(MOCK_CONTRACT_ADDR));
+    assert_eq!(
+        factory.pair_info(&pair_info.asset_infos).unwrap(),
+        pair_info
+    );
 }
 
 #[test]
@@ -352,3 +374,494 @@ fn test_decimal_checked_ops() {
             .is_err()
     );
 }
+
+#[test]
+fn test_decimal_rounding_ops() {
+    let dec = Decimal::from_ratio(1u128, 3u128);
+    assert_eq!(
+        dec.checked_mul_floor(Uint128::new(10)).unwrap(),
+        Uint128::new(3)
+    );
+    assert_eq!(
+        dec.checked_mul_ceil(Uint128::new(10)).unwrap(),
+        Uint128::new(4)
+    );
+    assert_eq!(
+        dec.checked_mul_ceil(Uint128::zero()).unwrap(),
+        Uint128::zero()
+    );
+
+    // Exact products are not rounded up
+    let dec = Decimal::from_ratio(1u128, 2u128);
+    assert_eq!(
+        dec.checked_mul_ceil(Uint128::new(10)).unwrap(),
+        Uint128::new(5)
+    );
+
+    assert_eq!(
+        Decimal::from_ratio(7u128, 2u128).to_uint_floor(),
+        Uint128::new(3)
+    );
+    assert_eq!(
+        Decimal::from_ratio(7u128, 2u128).to_uint_ceil(),
+        Uint128::new(4)
+    );
+    assert_eq!(
+        Decimal::from_ratio(6u128, 2u128).to_uint_ceil(),
+        Uint128::new(3)
+    );
+    assert_eq!(Decimal::zero().to_uint_ceil(), Uint128::zero());
+
+    assert!(
+        Decimal::from_ratio(Uint128::MAX, Uint128::from(10u128.pow(18u32)))
+            .checked_mul_ceil(Uint128::from(10u128.pow(18u32) + 1))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_asset_infos_utils() {
+    let token_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    };
+    let native_token_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+
+    assert_eq!(
+        sort_asset_infos(&[native_token_info.clone(), token_info.clone()]),
+        vec![token_info.clone(), native_token_info.clone()]
+    );
+
+    assert_eq!(
+        pair_key(&[token_info.clone(), native_token_info.clone()]),
+        pair_key(&[native_token_info.clone(), token_info.clone()])
+    );
+    assert_eq!(
+        pair_key(&[native_token_info.clone(), token_info.clone()]),
+        b"asset0000uusd".to_vec()
+    );
+
+    assert!(!has_duplicate_asset_infos(&[
+        token_info.clone(),
+        native_token_info.clone()
+    ]));
+    assert!(has_duplicate_asset_infos(&[
+        token_info.clone(),
+        native_token_info.clone(),
+        token_info.clone()
+    ]));
+    // A native denom and a token address with the same string are different assets
+    assert!(!has_duplicate_asset_infos(&[
+        AssetInfo::NativeToken {
+            denom: "asset0000".to_string(),
+        },
+        token_info
+    ]));
+}
+
+#[test]
+fn test_attr_builder() {
+    let offer = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(100),
+    };
+    let ask = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        amount: Uint128::new(50),
+    };
+
+    let attributes = AttrBuilder::new("swap")
+        .sender("addr0000")
+        .receiver("addr0001")
+        .asset("offer", &offer)
+        .attr("spread_amount", Uint128::new(1))
+        .build();
+    assert_eq!(
+        attributes,
+        vec![
+            attr("action", "swap"),
+            attr("sender", "addr0000"),
+            attr("receiver", "addr0001"),
+            attr("offer_asset", "uusd"),
+            attr("offer_amount", "100"),
+            attr("spread_amount", "1"),
+        ]
+    );
+
+    let builder = AttrBuilder::new("withdraw_liquidity").assets("refund", &[offer, ask]);
+    let event = builder.event("pair");
+    let attributes = builder.build();
+    assert_eq!(event.ty, "astroport/pair/withdraw_liquidity");
+    assert_eq!(event.attributes, attributes);
+    assert_eq!(
+        attributes,
+        vec![
+            attr("action", "withdraw_liquidity"),
+            attr("refund_asset", "uusd"),
+            attr("refund_amount", "100"),
+            attr("refund_1_asset", "asset0000"),
+            attr("refund_1_amount", "50"),
+        ]
+    );
+}
+
+#[test]
+fn test_xyk_simulation() {
+    let offer_pool = Uint128::new(1_000_000_000_000);
+    let ask_pool = Uint128::new(2_000_000_000_000);
+    let commission_rate = Decimal::permille(3);
+
+    assert_eq!(
+        compute_swap(
+            offer_pool,
+            ask_pool,
+            Uint128::new(1_000_000_000),
+            commission_rate
+        )
+        .unwrap(),
+        (
+            Uint128::new(1_992_007_993),
+            Uint128::new(1_998_002),
+            Uint128::new(5_994_005)
+        )
+    );
+    assert_eq!(
+        compute_offer_amount(
+            offer_pool,
+            ask_pool,
+            Uint128::new(1_000_000_000),
+            commission_rate
+        )
+        .unwrap(),
+        (
+            Uint128::new(501_756_146),
+            Uint128::new(503_265),
+            Uint128::new(3_009_027)
+        )
+    );
+
+    // Tiny swaps are rounded down to zero
+    assert_eq!(
+        compute_swap(
+            Uint128::new(5_000_000_000_000),
+            Uint128::new(1_000_000_000),
+            Uint128::new(1),
+            Decimal::zero()
+        )
+        .unwrap(),
+        (Uint128::zero(), Uint128::zero(), Uint128::zero())
+    );
+
+    // Empty pools and a 100% commission return an error instead of panicking
+    assert!(compute_swap(
+        Uint128::zero(),
+        ask_pool,
+        Uint128::new(1_000),
+        commission_rate
+    )
+    .is_err());
+    assert!(
+        compute_offer_amount(offer_pool, ask_pool, Uint128::new(1_000), Decimal::one()).is_err()
+    );
+    assert!(compute_offer_amount(offer_pool, ask_pool, ask_pool, commission_rate).is_err());
+}
+
+#[test]
+fn test_generator_rewards_simulation() {
+    assert_eq!(
+        calculate_generator_rewards(
+            Uint128::new(10),
+            Uint128::new(100),
+            Uint128::new(1),
+            Uint128::new(3)
+        )
+        .unwrap(),
+        Uint128::new(333)
+    );
+    assert_eq!(
+        calculate_generator_rewards(
+            Uint128::new(10),
+            Uint128::new(100),
+            Uint128::new(1),
+            Uint128::zero()
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+
+    // The user staked 1/4 of the pool, 1000 new tokens were accrued
+    assert_eq!(
+        calculate_pending_rewards(
+            Decimal::from_ratio(1u128, 2u128),
+            Uint128::new(1_000),
+            Uint128::new(400),
+            Uint128::new(100),
+            Uint128::new(30)
+        )
+        .unwrap(),
+        Uint128::new(270)
+    );
+    // Nothing is accrued while the generator is empty
+    assert_eq!(
+        calculate_pending_rewards(
+            Decimal::from_ratio(1u128, 2u128),
+            Uint128::new(1_000),
+            Uint128::zero(),
+            Uint128::new(100),
+            Uint128::new(30)
+        )
+        .unwrap(),
+        Uint128::new(20)
+    );
+
+    // Large emissions don't overflow intermediate results
+    assert_eq!(
+        calculate_generator_rewards(
+            Uint128::new(1_000_000),
+            Uint128::MAX,
+            Uint128::new(1),
+            Uint128::new(1_000_000)
+        )
+        .unwrap(),
+        Uint128::MAX
+    );
+    assert!(calculate_generator_rewards(
+        Uint128::new(2),
+        Uint128::MAX,
+        Uint128::new(1),
+        Uint128::new(1)
+    )
+    .is_err());
+
+    assert_eq!(
+        calculate_rewards_per_share(Uint128::new(1_000), Uint128::new(400)).unwrap(),
+        Decimal::from_ratio(5u128, 2u128)
+    );
+    // Rewards per share that don't fit in a decimal return an error instead of panicking
+    assert!(calculate_rewards_per_share(Uint128::MAX, Uint128::new(1)).is_err());
+    assert!(calculate_rewards_per_share(Uint128::new(1), Uint128::zero()).is_err());
+    assert!(calculate_pending_rewards(
+        Decimal::zero(),
+        Uint128::MAX,
+        Uint128::new(1),
+        Uint128::new(1),
+        Uint128::zero()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_validate_native_denom() {
+    let deps = mock_dependencies(&[]);
+    let api = deps.as_ref().api;
+
+    for denom in [
+        "uusd",
+        "uluna",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+        "factory/terra1creator/ucoin",
+        "factory/terra1creator/MyToken.v2",
+    ] {
+        validate_native_denom(api, denom).unwrap();
+    }
+
+    for denom in [
+        "u",
+        "UUSD",
+        "1usd",
+        "uusd!",
+        "ibc/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2",
+        "ibc/27394FB092D2ECCD",
+        "factory/terra1creator",
+        "factory/terra1creator/",
+        "factory/terra1creator/coin$",
+        "factory/TERRA1CREATOR/ucoin",
+    ] {
+        assert!(
+            validate_native_denom(api, denom).is_err(),
+            "{} should be invalid",
+            denom
+        );
+    }
+
+    let too_long = format!("u{}", "a".repeat(128));
+    assert!(validate_native_denom(api, &too_long).is_err());
+}
+
+#[test]
+fn ics20_transfer_msg() {
+    let astro = Addr::unchecked("astro");
+    let ics20 = Addr::unchecked("ics20");
+    let transfer = TransferMsg {
+        channel: String::from("channel-3"),
+        remote_address: String::from("hub_maker"),
+        timeout: None,
+        memo: None,
+    };
+
+    let msg = build_ics20_transfer_msg(&astro, &ics20, Uint128::new(100), &transfer).unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("astro"),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: String::from("ics20"),
+                amount: Uint128::new(100),
+                msg: to_binary(&transfer).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // The memo is omitted if not set
+    assert_eq!(
+        to_binary(&transfer).unwrap().to_vec(),
+        br#"{"channel":"channel-3","remote_address":"hub_maker","timeout":null}"#.to_vec()
+    );
+    let with_memo = TransferMsg {
+        memo: Some(String::from("fees")),
+        ..transfer.clone()
+    };
+    assert_eq!(
+        to_binary(&with_memo).unwrap().to_vec(),
+        br#"{"channel":"channel-3","remote_address":"hub_maker","timeout":null,"memo":"fees"}"#
+            .to_vec()
+    );
+
+    let err = build_ics20_transfer_msg(
+        &astro,
+        &ics20,
+        Uint128::new(100),
+        &TransferMsg {
+            channel: String::from("transfer"),
+            ..transfer.clone()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid IBC channel transfer"
+    );
+
+    let err = build_ics20_transfer_msg(
+        &astro,
+        &ics20,
+        Uint128::new(100),
+        &TransferMsg {
+            remote_address: String::new(),
+            ..transfer
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: IBC recipient address must be set"
+    );
+
+    assert_eq!(validate_ibc_timeout(None).unwrap(), 600);
+    assert_eq!(validate_ibc_timeout(Some(3600)).unwrap(), 3600);
+    assert!(validate_ibc_timeout(Some(59)).is_err());
+    assert!(validate_ibc_timeout(Some(86401)).is_err());
+}
+
+#[test]
+fn migration_allowed() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "astroport-pair", "1.1.0").unwrap();
+
+    let stored =
+        assert_migration_allowed(deps.as_ref().storage, "astroport-pair", "1.2.0").unwrap();
+    assert_eq!(stored.version, "1.1.0");
+    assert_migration_allowed(deps.as_ref().storage, "astroport-pair", "1.10.0").unwrap();
+    assert_migration_allowed(deps.as_ref().storage, "astroport-pair", "2.0.0-rc1").unwrap();
+
+    let err =
+        assert_migration_allowed(deps.as_ref().storage, "astroport-factory", "2.0.0").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Can't migrate from astroport-pair to astroport-factory"
+    );
+
+    for version in ["1.1.0", "1.0.9", "0.9.0"] {
+        let err =
+            assert_migration_allowed(deps.as_ref().storage, "astroport-pair", version).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Generic error: Can't migrate from version 1.1.0 to {}",
+                version
+            )
+        );
+    }
+
+    let err = assert_migration_allowed(deps.as_ref().storage, "astroport-pair", "1.2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid contract version: 1.2"
+    );
+}
+
+/// Answers every query with the configured fee discount, or fails if no discount is set
+struct FeeDiscountQuerier(Option<u16>);
+
+impl Querier for FeeDiscountQuerier {
+    fn raw_query(&self, _bin_request: &[u8]) -> QuerierResult {
+        match self.0 {
+            Some(discount_bps) => SystemResult::Ok(ContractResult::Ok(
+                to_binary(&FeeDiscountResponse { discount_bps }).unwrap(),
+            )),
+            None => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+#[test]
+fn test_discounted_fee_rate() {
+    let trader = Addr::unchecked("trader");
+    let mut fee_info = FeeInfo {
+        fee_address: None,
+        total_fee_rate: Decimal::permille(3),
+        maker_fee_rate: Decimal::from_ratio(1u128, 3u128),
+        referral_registry: None,
+        fee_discount: None,
+    };
+
+    // Without a discount contract the full commission is charged
+    let querier = FeeDiscountQuerier(Some(2_000));
+    let querier = QuerierWrapper::new(&querier);
+    assert_eq!(
+        discounted_fee_rate(&querier, &fee_info, &trader),
+        Decimal::permille(3)
+    );
+
+    fee_info.fee_discount = Some(FeeDiscountConfig {
+        discount_contract: Addr::unchecked("discount"),
+        max_discount_bps: 5_000,
+    });
+    assert_eq!(
+        discounted_fee_rate(&querier, &fee_info, &trader),
+        Decimal::from_ratio(24u128, 10_000u128)
+    );
+
+    // The discount is capped by the factory
+    let querier = FeeDiscountQuerier(Some(10_000));
+    let querier = QuerierWrapper::new(&querier);
+    assert_eq!(
+        discounted_fee_rate(&querier, &fee_info, &trader),
+        Decimal::from_ratio(15u128, 10_000u128)
+    );
+
+    // A failing discount contract doesn't block swaps
+    let querier = FeeDiscountQuerier(None);
+    let querier = QuerierWrapper::new(&querier);
+    assert_eq!(
+        discounted_fee_rate(&querier, &fee_info, &trader),
+        Decimal::permille(3)
+    );
+}
@@ -0,0 +1,91 @@
+use cosmwasm_std::{Addr, CosmosMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Addresses that are allowed to schedule, approve, execute and cancel messages
+    pub admins: Vec<String>,
+    /// The amount of admin approvals scheduled messages need before the execution delay starts
+    pub threshold: u32,
+    /// The delay (in seconds) between approving messages and the earliest time they can be executed
+    pub execution_delay: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Schedules messages that are dispatched by the contract once they're approved and the execution
+    /// delay has passed. Scheduling counts as an approval
+    /// ## Executor
+    /// Only an admin can execute this
+    Schedule { msgs: Vec<CosmosMsg> },
+    /// Approves scheduled messages. The execution delay starts once the approval threshold is reached
+    /// ## Executor
+    /// Only an admin can execute this
+    Approve { id: u64 },
+    /// Dispatches scheduled messages whose execution delay has passed
+    /// ## Executor
+    /// Only an admin can execute this
+    Execute { id: u64 },
+    /// Removes scheduled messages without dispatching them
+    /// ## Executor
+    /// Only an admin can execute this
+    Cancel { id: u64 },
+    /// Replaces the admin set and the approval threshold
+    /// ## Executor
+    /// Only the contract itself can execute this, so changes must be scheduled
+    UpdateAdmins { admins: Vec<String>, threshold: u32 },
+    /// Updates the execution delay. Messages that are already scheduled keep their execution time
+    /// ## Executor
+    /// Only the contract itself can execute this, so changes must be scheduled
+    UpdateExecutionDelay { execution_delay: u64 },
+}
+
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration using a [`ConfigResponse`] object
+    Config {},
+    /// Returns scheduled messages using a [`ScheduledMsgsResponse`] object
+    Scheduled { id: u64 },
+    /// Returns all scheduled messages using a vector of [`ScheduledMsgsResponse`] objects
+    AllScheduled {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// This structure holds the parameters returned when querying for the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Addresses that are allowed to schedule, approve, execute and cancel messages
+    pub admins: Vec<Addr>,
+    /// The amount of admin approvals scheduled messages need before the execution delay starts
+    pub threshold: u32,
+    /// The delay (in seconds) between approving messages and the earliest time they can be executed
+    pub execution_delay: u64,
+}
+
+/// This structure describes a batch of scheduled messages.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledMsgsResponse {
+    /// The identifier of the batch
+    pub id: u64,
+    /// The messages dispatched when the batch is executed
+    pub msgs: Vec<CosmosMsg>,
+    /// The admin that scheduled the batch
+    pub scheduled_by: Addr,
+    /// The admins that approved the batch
+    pub approvals: Vec<Addr>,
+    /// The timestamp (in seconds) after which the batch can be executed. Not set until the batch is approved
+    pub executable_after: Option<u64>,
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
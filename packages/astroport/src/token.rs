@@ -2,7 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{StdError, StdResult, Uint128};
-use cw20::{Cw20Coin, MinterResponse};
+use cw20::{Cw20Coin, Logo, MinterResponse};
 
 /// This structure describes the parameters used for creating a token contract.
 /// TokenContract InstantiateMsg
@@ -18,6 +18,21 @@ pub struct InstantiateMsg {
     pub initial_balances: Vec<Cw20Coin>,
     /// Minting controls specified in a [`MinterResponse`] structure
     pub mint: Option<MinterResponse>,
+    /// Marketing metadata specified in an [`InstantiateMarketingInfo`] structure
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+/// This structure describes the marketing info settings such as project, description, and token logo.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMarketingInfo {
+    /// The project name
+    pub project: Option<String>,
+    /// The project description
+    pub description: Option<String>,
+    /// The address of an admin who is able to update marketing info
+    pub marketing: Option<String>,
+    /// The token logo
+    pub logo: Option<Logo>,
 }
 
 /// This structure describes a migration message.
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Addr, Order, Uint128};
 use cw20::Cw20ReceiveMsg;
 
+use crate::astro_converter::{NativeAstro, NativeAstroParams};
+
 /// This structure describes the parameters used for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -26,6 +28,13 @@ pub enum ExecuteMsg {
     },
     /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template
     Receive(Cw20ReceiveMsg),
+    /// Registers vesting targets/accounts funded with the native ASTRO sent along with the message.
+    /// Only available once the contract has been switched to native ASTRO
+    /// ## Executor
+    /// Only the current owner can execute this
+    RegisterVestingAccounts {
+        vesting_accounts: Vec<VestingAccount>,
+    },
     /// Creates a request to change contract ownership
     /// ## Executor
     /// Only the current owner can execute this
@@ -101,8 +110,15 @@ pub enum QueryMsg {
     /// ## Description
     /// Returns the total unvested amount of tokens for a specific address.
     AvailableAmount { address: String },
+    /// ## Description
+    /// Returns the total amount of tokens registered in vesting schedules and not yet claimed.
+    TotalCommitted {},
     /// Timestamp returns the current timestamp
     Timestamp {},
+    /// ## Description
+    /// Returns the total amounts of tokens granted, claimed and still locked in vesting schedules
+    /// using a [`VestingStateResponse`] object.
+    VestingState {},
 }
 
 /// This structure describes a custom struct used to return the contract configuration.
@@ -112,6 +128,19 @@ pub struct ConfigResponse {
     pub owner: Addr,
     /// The address of the token being vested
     pub token_addr: Addr,
+    /// The native ASTRO vested instead of CW20 ASTRO, if the contract has been switched to it
+    pub native_astro: Option<NativeAstro>,
+}
+
+/// This structure describes a custom struct used to return the totals of all vesting schedules.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingStateResponse {
+    /// The total amount of tokens registered in vesting schedules
+    pub total_granted: Uint128,
+    /// The total amount of tokens claimed by vesting recipients
+    pub total_claimed: Uint128,
+    /// The total amount of tokens that are not claimed yet and are still held by the contract
+    pub total_locked: Uint128,
 }
 
 /// This structure describes a custom struct used to return vesting data about a specific vesting target.
@@ -154,9 +183,12 @@ impl Into<Order> for OrderBy {
 }
 
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Switches the contract to native ASTRO. The CW20 ASTRO held by the contract is converted
+    #[serde(default)]
+    pub native_astro: Option<NativeAstroParams>,
+}
 
 /// This structure describes a CW20 hook message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -0,0 +1,20 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the queries a voting escrow (vxASTRO) contract must support.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingEscrowQueryMsg {
+    /// Returns the current vxASTRO voting power of a user using a [`VotingPowerResponse`] object
+    UserVotingPower { user: String },
+    /// Returns the current total vxASTRO voting power using a [`VotingPowerResponse`] object
+    TotalVotingPower {},
+}
+
+/// This structure holds the parameters returned when querying a voting escrow contract for voting power.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerResponse {
+    /// The vxASTRO voting power
+    pub voting_power: Uint128,
+}
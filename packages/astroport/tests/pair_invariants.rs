@@ -0,0 +1,117 @@
+//! Property-based tests for the XYK pair math exposed in [`astroport::simulation`].
+use astroport::simulation::{compute_lp_share, compute_swap, compute_withdrawn_amounts};
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+use proptest::prelude::*;
+
+/// Returns the constant product of the pool balances
+fn product(x: u128, y: u128) -> Uint256 {
+    Uint256::from(x) * Uint256::from(y)
+}
+
+/// Returns a commission rate between 0% and 1% (in basis points)
+fn commission_rate() -> impl Strategy<Value = Decimal> {
+    (0u64..=100).prop_map(|bps| Decimal::from_ratio(bps, 10_000u64))
+}
+
+/// Returns a maker fee rate between 0% and 100% of the commission
+fn maker_fee_rate() -> impl Strategy<Value = Decimal> {
+    (0u64..=10_000).prop_map(|bps| Decimal::from_ratio(bps, 10_000u64))
+}
+
+proptest! {
+    #[test]
+    fn swaps_never_decrease_the_constant_product(
+        pools in (1_000u128..1_000_000_000_000_000, 1_000u128..1_000_000_000_000_000),
+        swaps in prop::collection::vec((any::<bool>(), 1u128..1_000_000_000_000), 1..20),
+        commission_rate in commission_rate(),
+        maker_fee_rate in maker_fee_rate(),
+    ) {
+        let (mut x, mut y) = pools;
+
+        for (x_to_y, offer_amount) in swaps {
+            let (offer_pool, ask_pool) = if x_to_y { (x, y) } else { (y, x) };
+            let (return_amount, _, commission_amount) = compute_swap(
+                offer_pool.into(),
+                ask_pool.into(),
+                offer_amount.into(),
+                commission_rate,
+            )
+            .unwrap();
+            let maker_fee = commission_amount * maker_fee_rate;
+
+            // The trader and the Maker are paid from the ask pool, the rest of the commission stays in it
+            let new_offer_pool = offer_pool + offer_amount;
+            let new_ask_pool = ask_pool - return_amount.u128() - maker_fee.u128();
+            prop_assert!(new_ask_pool > 0);
+            prop_assert!(
+                product(new_offer_pool, new_ask_pool) >= product(offer_pool, ask_pool)
+            );
+
+            if x_to_y {
+                x = new_offer_pool;
+                y = new_ask_pool;
+            } else {
+                y = new_offer_pool;
+                x = new_ask_pool;
+            }
+        }
+    }
+
+    #[test]
+    fn lp_round_trips_never_create_value(
+        initial_deposits in (1_000u128..1_000_000_000_000_000, 1_000u128..1_000_000_000_000_000),
+        deposits in (1u128..1_000_000_000_000_000, 1u128..1_000_000_000_000_000),
+    ) {
+        let pools = [
+            Uint128::new(initial_deposits.0),
+            Uint128::new(initial_deposits.1),
+        ];
+        let total_share = compute_lp_share(pools, [Uint128::zero(); 2], Uint128::zero()).unwrap();
+
+        // The first provider gets back exactly what they deposited
+        prop_assert_eq!(
+            compute_withdrawn_amounts(pools, total_share, total_share).unwrap(),
+            pools
+        );
+
+        let deposits = [Uint128::new(deposits.0), Uint128::new(deposits.1)];
+        let share = compute_lp_share(deposits, pools, total_share).unwrap();
+        let withdrawn = compute_withdrawn_amounts(
+            [pools[0] + deposits[0], pools[1] + deposits[1]],
+            share,
+            total_share + share,
+        )
+        .unwrap();
+
+        prop_assert!(withdrawn[0] <= deposits[0]);
+        prop_assert!(withdrawn[1] <= deposits[1]);
+    }
+
+    #[test]
+    fn commission_accounting_conserves_tokens(
+        pools in (1_000u128..1_000_000_000_000_000, 1_000u128..1_000_000_000_000_000),
+        offer_amount in 1u128..1_000_000_000_000,
+        commission_rate in commission_rate(),
+        maker_fee_rate in maker_fee_rate(),
+    ) {
+        let (offer_pool, ask_pool) = (Uint128::new(pools.0), Uint128::new(pools.1));
+        let offer_amount = Uint128::new(offer_amount);
+
+        let (gross_return, gross_spread, zero_commission) =
+            compute_swap(offer_pool, ask_pool, offer_amount, Decimal::zero()).unwrap();
+        prop_assert!(zero_commission.is_zero());
+
+        let (return_amount, spread_amount, commission_amount) =
+            compute_swap(offer_pool, ask_pool, offer_amount, commission_rate).unwrap();
+
+        // The commission is carved out of the swap output: nothing is created or lost
+        prop_assert_eq!(return_amount + commission_amount, gross_return);
+        prop_assert_eq!(spread_amount, gross_spread);
+        prop_assert!(commission_amount <= gross_return * commission_rate + Uint128::new(1));
+
+        // The Maker only ever receives a part of the commission
+        let maker_fee = commission_amount * maker_fee_rate;
+        prop_assert!(maker_fee <= commission_amount);
+        prop_assert!(return_amount + maker_fee <= ask_pool);
+    }
+}
@@ -0,0 +1,32 @@
+use std::{env, fs, process};
+
+use astroport_deploy::manifest::Manifest;
+use astroport_deploy::plan::build_plan;
+
+/// Reads a TOML deployment manifest and prints the ordered deployment steps as JSON.
+fn main() {
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: astroport-deploy <manifest.toml>");
+            process::exit(2);
+        }
+    };
+
+    let result = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))
+        .and_then(|contents| {
+            toml::from_str::<Manifest>(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", path, e))
+        })
+        .and_then(|manifest| build_plan(&manifest).map_err(|e| e.to_string()))
+        .and_then(|steps| serde_json::to_string_pretty(&steps).map_err(|e| e.to_string()));
+
+    match result {
+        Ok(plan) => println!("{}", plan),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes deployment manifest errors.
+#[derive(Error, Debug, PartialEq)]
+pub enum DeployError {
+    #[error("Label {0} is used more than once")]
+    DuplicateLabel(String),
+
+    #[error("Pair {0} uses a pair type that is not configured in the factory")]
+    UnknownPairType(String),
+
+    #[error("Pair type configuration {0} has invalid fees")]
+    InvalidFees(String),
+
+    #[error("Vesting schedules need {needed} ASTRO but the deployer only receives {available}")]
+    InsufficientDeployerBalance { needed: String, available: String },
+
+    #[error("Failed to serialize message: {0}")]
+    Serialization(String),
+}
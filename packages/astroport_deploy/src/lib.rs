@@ -0,0 +1,6 @@
+pub mod error;
+pub mod manifest;
+pub mod plan;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,113 @@
+use astroport::asset::AssetInfo;
+use astroport::factory::{PairConfig, PairType};
+use astroport::vesting::VestingSchedule;
+use cosmwasm_std::{Decimal, Uint128, Uint64};
+use cw20::Cw20Coin;
+use serde::{Deserialize, Serialize};
+
+/// The default validity period (in seconds) of the ownership proposals made at the end of the deployment
+pub const DEFAULT_OWNERSHIP_PROPOSAL_EXPIRES_IN: u64 = 604_800;
+
+/// ## Description
+/// This structure describes a deployment manifest for the core Astroport contracts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Manifest {
+    /// The address that signs the deployment. It owns the factory and vesting contracts until
+    /// ownership is claimed by `owner`
+    pub deployer: String,
+    /// The address that owns the protocol once it is deployed
+    pub owner: String,
+    /// The validity period (in seconds) of the ownership proposals made to `owner`
+    pub ownership_proposal_expires_in: Option<u64>,
+    /// Code IDs of the uploaded contracts
+    pub code_ids: CodeIds,
+    /// The ASTRO token parameters
+    pub astro: AstroManifest,
+    /// The factory parameters
+    pub factory: FactoryManifest,
+    /// The pairs created right after the factory is wired
+    #[serde(default)]
+    pub pairs: Vec<PairManifest>,
+    /// The generator parameters
+    pub generator: GeneratorManifest,
+    /// The vesting parameters
+    #[serde(default)]
+    pub vesting: VestingManifest,
+    /// The Maker parameters
+    #[serde(default)]
+    pub maker: MakerManifest,
+}
+
+/// ## Description
+/// This structure holds the code IDs of the uploaded contracts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CodeIds {
+    pub token: u64,
+    pub xastro_token: u64,
+    pub whitelist: u64,
+    pub factory: u64,
+    pub router: u64,
+    pub staking: u64,
+    pub vesting: u64,
+    pub generator: u64,
+    pub maker: u64,
+}
+
+/// ## Description
+/// This structure describes the ASTRO token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AstroManifest {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// The ASTRO supply. The deployer must receive enough ASTRO to fund the vesting schedules
+    pub initial_balances: Vec<Cw20Coin>,
+}
+
+/// ## Description
+/// This structure describes the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FactoryManifest {
+    /// The pair types that can be created
+    pub pair_configs: Vec<PairConfig>,
+}
+
+/// ## Description
+/// This structure describes a pair to create. The label can be used to refer to the pair address in later steps.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PairManifest {
+    pub label: String,
+    pub pair_type: PairType,
+    /// Use `${astro}` as the contract address to refer to the ASTRO token deployed by the manifest
+    pub asset_infos: [AssetInfo; 2],
+}
+
+/// ## Description
+/// This structure describes the generator.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GeneratorManifest {
+    pub tokens_per_block: Uint128,
+    pub start_block: Uint64,
+    pub generator_controller: Option<String>,
+    pub guardian: Option<String>,
+    #[serde(default)]
+    pub allowed_reward_proxies: Vec<String>,
+}
+
+/// ## Description
+/// This structure describes the vesting contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct VestingManifest {
+    /// The schedules that release ASTRO to the generator. They are funded by the deployer
+    #[serde(default)]
+    pub generator_schedules: Vec<VestingSchedule>,
+}
+
+/// ## Description
+/// This structure describes the Maker.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct MakerManifest {
+    pub governance_contract: Option<String>,
+    pub governance_percent: Option<Uint64>,
+    pub max_spread: Option<Decimal>,
+}
@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+
+use astroport::factory::{
+    ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg,
+};
+use astroport::generator::InstantiateMsg as GeneratorInstantiateMsg;
+use astroport::maker::InstantiateMsg as MakerInstantiateMsg;
+use astroport::router::InstantiateMsg as RouterInstantiateMsg;
+use astroport::staking::InstantiateMsg as StakingInstantiateMsg;
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use astroport::vesting::{
+    Cw20HookMsg as VestingHookMsg, ExecuteMsg as VestingExecuteMsg,
+    InstantiateMsg as VestingInstantiateMsg, VestingAccount,
+};
+use cosmwasm_std::Uint128;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::DeployError;
+use crate::manifest::{Manifest, DEFAULT_OWNERSHIP_PROPOSAL_EXPIRES_IN};
+
+pub const ASTRO_LABEL: &str = "astro";
+pub const STAKING_LABEL: &str = "staking";
+pub const FACTORY_LABEL: &str = "factory";
+pub const ROUTER_LABEL: &str = "router";
+pub const VESTING_LABEL: &str = "vesting";
+pub const GENERATOR_LABEL: &str = "generator";
+pub const MAKER_LABEL: &str = "maker";
+
+/// ## Description
+/// This enum describes a deployment step. Steps must be executed in order by the deployer.
+/// Every `${label}` string in a step refers to the address of the contract created by an earlier step with that label.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    /// Instantiates a contract and binds its address to the label
+    Instantiate {
+        label: String,
+        code_id: u64,
+        admin: Option<String>,
+        msg: Value,
+    },
+    /// Executes a contract. If a label is set, it is bound to the address in the `pair_contract_addr`
+    /// attribute of the result, which is emitted when the factory creates a pair
+    Execute {
+        label: Option<String>,
+        contract: String,
+        msg: Value,
+    },
+    /// Sends CW20 tokens to a contract. The hook message is base64 encoded after its placeholders are resolved
+    Cw20Send {
+        token: String,
+        contract: String,
+        amount: Uint128,
+        msg: Value,
+    },
+}
+
+/// ## Description
+/// Returns a reference to the address of the contract created by the step with the specified label.
+/// ## Params
+/// * **label** is an object of type [`str`]. This is the label of the step.
+pub fn contract_ref(label: &str) -> String {
+    format!("${{{}}}", label)
+}
+
+/// ## Description
+/// Returns the ordered steps that deploy and wire the contracts described in the manifest.
+/// Returns a [`DeployError`] if the manifest is inconsistent.
+/// ## Params
+/// * **manifest** is an object of type [`Manifest`]. This is the deployment manifest.
+pub fn build_plan(manifest: &Manifest) -> Result<Vec<Step>, DeployError> {
+    validate_manifest(manifest)?;
+
+    let astro = contract_ref(ASTRO_LABEL);
+    let factory = contract_ref(FACTORY_LABEL);
+    let vesting = contract_ref(VESTING_LABEL);
+    let generator = contract_ref(GENERATOR_LABEL);
+    let code_ids = &manifest.code_ids;
+    let admin = Some(manifest.owner.clone());
+
+    let mut steps = vec![
+        Step::Instantiate {
+            label: ASTRO_LABEL.to_string(),
+            code_id: code_ids.token,
+            admin: admin.clone(),
+            msg: to_value(&TokenInstantiateMsg {
+                name: manifest.astro.name.clone(),
+                symbol: manifest.astro.symbol.clone(),
+                decimals: manifest.astro.decimals,
+                initial_balances: manifest.astro.initial_balances.clone(),
+                mint: None,
+                marketing: None,
+            })?,
+        },
+        Step::Instantiate {
+            label: STAKING_LABEL.to_string(),
+            code_id: code_ids.staking,
+            admin: admin.clone(),
+            msg: to_value(&StakingInstantiateMsg {
+                owner: manifest.owner.clone(),
+                token_code_id: code_ids.xastro_token,
+                deposit_token_addr: astro.clone(),
+            })?,
+        },
+        // The deployer owns the factory until the pairs are created
+        Step::Instantiate {
+            label: FACTORY_LABEL.to_string(),
+            code_id: code_ids.factory,
+            admin: admin.clone(),
+            msg: to_value(&FactoryInstantiateMsg {
+                pair_configs: manifest.factory.pair_configs.clone(),
+                token_code_id: code_ids.token,
+                fee_address: None,
+                generator_address: None,
+                owner: manifest.deployer.clone(),
+                whitelist_code_id: code_ids.whitelist,
+            })?,
+        },
+        Step::Instantiate {
+            label: ROUTER_LABEL.to_string(),
+            code_id: code_ids.router,
+            admin: admin.clone(),
+            msg: to_value(&RouterInstantiateMsg {
+                astroport_factory: factory.clone(),
+            })?,
+        },
+        // The deployer owns the vesting contract until the generator schedules are registered
+        Step::Instantiate {
+            label: VESTING_LABEL.to_string(),
+            code_id: code_ids.vesting,
+            admin: admin.clone(),
+            msg: to_value(&VestingInstantiateMsg {
+                owner: manifest.deployer.clone(),
+                token_addr: astro.clone(),
+            })?,
+        },
+        Step::Instantiate {
+            label: GENERATOR_LABEL.to_string(),
+            code_id: code_ids.generator,
+            admin: admin.clone(),
+            msg: to_value(&GeneratorInstantiateMsg {
+                owner: manifest.owner.clone(),
+                factory: factory.clone(),
+                generator_controller: manifest.generator.generator_controller.clone(),
+                guardian: manifest.generator.guardian.clone(),
+                astro_token: astro.clone(),
+                tokens_per_block: manifest.generator.tokens_per_block,
+                start_block: manifest.generator.start_block,
+                allowed_reward_proxies: manifest.generator.allowed_reward_proxies.clone(),
+                vesting_contract: vesting.clone(),
+            })?,
+        },
+        Step::Instantiate {
+            label: MAKER_LABEL.to_string(),
+            code_id: code_ids.maker,
+            admin,
+            msg: to_value(&MakerInstantiateMsg {
+                owner: manifest.owner.clone(),
+                astro_token_contract: astro.clone(),
+                factory_contract: factory.clone(),
+                staking_contract: contract_ref(STAKING_LABEL),
+                governance_contract: manifest.maker.governance_contract.clone(),
+                governance_percent: manifest.maker.governance_percent,
+                max_spread: manifest.maker.max_spread,
+                ibc_bridge: None,
+            })?,
+        },
+        Step::Execute {
+            label: None,
+            contract: factory.clone(),
+            msg: to_value(&FactoryExecuteMsg::UpdateConfig {
+                token_code_id: None,
+                fee_address: Some(contract_ref(MAKER_LABEL)),
+                generator_address: Some(generator.clone()),
+                whitelist_code_id: None,
+                referral_registry: None,
+            })?,
+        },
+    ];
+
+    for pair in &manifest.pairs {
+        steps.push(Step::Execute {
+            label: Some(pair.label.clone()),
+            contract: factory.clone(),
+            msg: to_value(&FactoryExecuteMsg::CreatePair {
+                pair_type: pair.pair_type.clone(),
+                asset_infos: pair.asset_infos.clone(),
+                init_params: None,
+            })?,
+        });
+    }
+
+    let schedules = &manifest.vesting.generator_schedules;
+    if !schedules.is_empty() {
+        steps.push(Step::Cw20Send {
+            token: astro,
+            contract: vesting.clone(),
+            amount: vesting_amount(manifest),
+            msg: to_value(&VestingHookMsg::RegisterVestingAccounts {
+                vesting_accounts: vec![VestingAccount {
+                    address: generator,
+                    schedules: schedules.clone(),
+                }],
+            })?,
+        });
+    }
+
+    let expires_in = manifest
+        .ownership_proposal_expires_in
+        .unwrap_or(DEFAULT_OWNERSHIP_PROPOSAL_EXPIRES_IN);
+    steps.push(Step::Execute {
+        label: None,
+        contract: factory,
+        msg: to_value(&FactoryExecuteMsg::ProposeNewOwner {
+            owner: manifest.owner.clone(),
+            expires_in,
+        })?,
+    });
+    steps.push(Step::Execute {
+        label: None,
+        contract: vesting,
+        msg: to_value(&VestingExecuteMsg::ProposeNewOwner {
+            owner: manifest.owner.clone(),
+            expires_in,
+        })?,
+    });
+
+    Ok(steps)
+}
+
+/// ## Description
+/// Checks that labels are unique, that every pair can be created by the factory and that the deployer
+/// receives enough ASTRO to fund the vesting schedules.
+/// ## Params
+/// * **manifest** is an object of type [`Manifest`]. This is the deployment manifest.
+fn validate_manifest(manifest: &Manifest) -> Result<(), DeployError> {
+    let mut labels: HashSet<&str> = [
+        ASTRO_LABEL,
+        STAKING_LABEL,
+        FACTORY_LABEL,
+        ROUTER_LABEL,
+        VESTING_LABEL,
+        GENERATOR_LABEL,
+        MAKER_LABEL,
+    ]
+    .into_iter()
+    .collect();
+
+    for config in &manifest.factory.pair_configs {
+        if !config.valid_fee_bps() {
+            return Err(DeployError::InvalidFees(config.pair_type.to_string()));
+        }
+    }
+
+    for pair in &manifest.pairs {
+        if !labels.insert(&pair.label) {
+            return Err(DeployError::DuplicateLabel(pair.label.clone()));
+        }
+
+        if !manifest
+            .factory
+            .pair_configs
+            .iter()
+            .any(|config| config.pair_type == pair.pair_type && !config.is_disabled)
+        {
+            return Err(DeployError::UnknownPairType(pair.label.clone()));
+        }
+    }
+
+    let needed = vesting_amount(manifest);
+    let available = manifest
+        .astro
+        .initial_balances
+        .iter()
+        .filter(|coin| coin.address == manifest.deployer)
+        .map(|coin| coin.amount)
+        .sum::<Uint128>();
+    if needed > available {
+        return Err(DeployError::InsufficientDeployerBalance {
+            needed: needed.to_string(),
+            available: available.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amount of ASTRO released by the generator vesting schedules.
+/// ## Params
+/// * **manifest** is an object of type [`Manifest`]. This is the deployment manifest.
+fn vesting_amount(manifest: &Manifest) -> Uint128 {
+    manifest
+        .vesting
+        .generator_schedules
+        .iter()
+        .map(|schedule| match &schedule.end_point {
+            Some(end_point) => end_point.amount,
+            None => schedule.start_point.amount,
+        })
+        .sum()
+}
+
+/// ## Description
+/// Serializes a message to a JSON value.
+fn to_value<T: Serialize>(msg: &T) -> Result<Value, DeployError> {
+    serde_json::to_value(msg).map_err(|e| DeployError::Serialization(e.to_string()))
+}
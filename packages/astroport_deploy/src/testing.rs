@@ -0,0 +1,204 @@
+use astroport::factory::PairType;
+use cosmwasm_std::Uint128;
+use serde_json::{json, Value};
+
+use crate::error::DeployError;
+use crate::manifest::Manifest;
+use crate::plan::{build_plan, contract_ref, Step};
+
+fn manifest() -> Value {
+    json!({
+        "deployer": "deployer",
+        "owner": "owner",
+        "code_ids": {
+            "token": 1,
+            "xastro_token": 2,
+            "whitelist": 3,
+            "factory": 4,
+            "router": 5,
+            "staking": 6,
+            "vesting": 7,
+            "generator": 8,
+            "maker": 9
+        },
+        "astro": {
+            "name": "Astroport",
+            "symbol": "ASTRO",
+            "decimals": 6,
+            "initial_balances": [{ "address": "deployer", "amount": "1000" }]
+        },
+        "factory": {
+            "pair_configs": [{
+                "code_id": 10,
+                "pair_type": { "xyk": {} },
+                "total_fee_bps": 30,
+                "maker_fee_bps": 3333,
+                "is_disabled": false,
+                "is_generator_disabled": false
+            }]
+        },
+        "pairs": [{
+            "label": "astro_uluna",
+            "pair_type": { "xyk": {} },
+            "asset_infos": [
+                { "token": { "contract_addr": "${astro}" } },
+                { "native_token": { "denom": "uluna" } }
+            ]
+        }],
+        "generator": {
+            "tokens_per_block": "10",
+            "start_block": "100",
+            "generator_controller": null,
+            "guardian": null
+        },
+        "vesting": {
+            "generator_schedules": [{
+                "start_point": { "time": 100, "amount": "0" },
+                "end_point": { "time": 200, "amount": "1000" }
+            }]
+        }
+    })
+}
+
+fn parse(value: Value) -> Manifest {
+    serde_json::from_value(value).unwrap()
+}
+
+fn labels(steps: &[Step]) -> Vec<String> {
+    steps
+        .iter()
+        .filter_map(|step| match step {
+            Step::Instantiate { label, .. } => Some(label.clone()),
+            Step::Execute { label, .. } => label.clone(),
+            Step::Cw20Send { .. } => None,
+        })
+        .collect()
+}
+
+#[test]
+fn contracts_are_instantiated_in_dependency_order() {
+    let steps = build_plan(&parse(manifest())).unwrap();
+
+    assert_eq!(
+        labels(&steps),
+        vec![
+            "astro",
+            "staking",
+            "factory",
+            "router",
+            "vesting",
+            "generator",
+            "maker",
+            "astro_uluna"
+        ]
+    );
+    // instantiate x7, update config, create pair, fund vesting, propose owner x2
+    assert_eq!(steps.len(), 12);
+
+    for step in &steps {
+        if let Step::Instantiate { admin, .. } = step {
+            assert_eq!(admin.as_deref(), Some("owner"));
+        }
+    }
+}
+
+#[test]
+fn cross_references_are_wired() {
+    let steps = build_plan(&parse(manifest())).unwrap();
+
+    match &steps[5] {
+        Step::Instantiate { code_id, msg, .. } => {
+            assert_eq!(*code_id, 8);
+            assert_eq!(msg["factory"], json!(contract_ref("factory")));
+            assert_eq!(msg["astro_token"], json!("${astro}"));
+            assert_eq!(msg["vesting_contract"], json!("${vesting}"));
+            assert_eq!(msg["owner"], json!("owner"));
+        }
+        _ => panic!("Expected the generator instantiation"),
+    }
+
+    match &steps[7] {
+        Step::Execute { contract, msg, .. } => {
+            assert_eq!(contract, "${factory}");
+            assert_eq!(msg["update_config"]["fee_address"], json!("${maker}"));
+            assert_eq!(
+                msg["update_config"]["generator_address"],
+                json!("${generator}")
+            );
+        }
+        _ => panic!("Expected the factory config update"),
+    }
+
+    match &steps[9] {
+        Step::Cw20Send {
+            token,
+            contract,
+            amount,
+            msg,
+        } => {
+            assert_eq!(token, "${astro}");
+            assert_eq!(contract, "${vesting}");
+            assert_eq!(*amount, Uint128::new(1000));
+            assert_eq!(
+                msg["register_vesting_accounts"]["vesting_accounts"][0]["address"],
+                json!("${generator}")
+            );
+        }
+        _ => panic!("Expected the vesting funding"),
+    }
+
+    match &steps[11] {
+        Step::Execute { contract, msg, .. } => {
+            assert_eq!(contract, "${vesting}");
+            assert_eq!(msg["propose_new_owner"]["owner"], json!("owner"));
+            assert_eq!(msg["propose_new_owner"]["expires_in"], json!(604_800));
+        }
+        _ => panic!("Expected the vesting ownership proposal"),
+    }
+}
+
+#[test]
+fn vesting_is_skipped_without_schedules() {
+    let mut value = manifest();
+    value["vesting"] = json!({});
+
+    let steps = build_plan(&parse(value)).unwrap();
+    assert_eq!(steps.len(), 11);
+    assert!(!steps
+        .iter()
+        .any(|step| matches!(step, Step::Cw20Send { .. })));
+}
+
+#[test]
+fn invalid_manifests_are_rejected() {
+    let mut value = manifest();
+    value["pairs"][0]["label"] = json!("factory");
+    assert_eq!(
+        build_plan(&parse(value)),
+        Err(DeployError::DuplicateLabel("factory".to_string()))
+    );
+
+    let mut value = manifest();
+    value["pairs"][0]["pair_type"] = json!({ "stable": {} });
+    assert_eq!(
+        build_plan(&parse(value)).unwrap_err(),
+        DeployError::UnknownPairType("astro_uluna".to_string())
+    );
+
+    let mut value = manifest();
+    value["factory"]["pair_configs"][0]["total_fee_bps"] = json!(20_000);
+    assert_eq!(
+        build_plan(&parse(value)).unwrap_err(),
+        DeployError::InvalidFees(PairType::Xyk {}.to_string())
+    );
+
+    let mut value = manifest();
+    value["astro"]["initial_balances"][0]["amount"] = json!("999");
+    assert_eq!(
+        build_plan(&parse(value)).unwrap_err(),
+        DeployError::InsufficientDeployerBalance {
+            needed: "1000".to_string(),
+            available: "999".to_string(),
+        }
+    );
+}
@@ -0,0 +1,112 @@
+use terra_multi_test::{ContractWrapper, TerraApp};
+
+/// ## Description
+/// Stores the CW20 token code and returns its code identifier.
+pub fn store_token_code(app: &mut TerraApp) -> u64 {
+    let token_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    app.store_code(token_contract)
+}
+
+/// ## Description
+/// Stores the factory code and returns its code identifier.
+pub fn store_factory_code(app: &mut TerraApp) -> u64 {
+    let factory_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_factory::contract::execute,
+            astroport_factory::contract::instantiate,
+            astroport_factory::contract::query,
+        )
+        .with_reply_empty(astroport_factory::contract::reply),
+    );
+
+    app.store_code(factory_contract)
+}
+
+/// ## Description
+/// Stores the XYK pair code and returns its code identifier.
+pub fn store_pair_code(app: &mut TerraApp) -> u64 {
+    let pair_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_pair::contract::execute,
+            astroport_pair::contract::instantiate,
+            astroport_pair::contract::query,
+        )
+        .with_reply_empty(astroport_pair::contract::reply),
+    );
+
+    app.store_code(pair_contract)
+}
+
+/// ## Description
+/// Stores the router code and returns its code identifier.
+pub fn store_router_code(app: &mut TerraApp) -> u64 {
+    let router_contract = Box::new(ContractWrapper::new(
+        astroport_router::contract::execute,
+        astroport_router::contract::instantiate,
+        astroport_router::contract::query,
+    ));
+
+    app.store_code(router_contract)
+}
+
+/// ## Description
+/// Stores the stableswap pair code and returns its code identifier.
+pub fn store_pair_stable_code(app: &mut TerraApp) -> u64 {
+    let pair_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_pair_stable::contract::execute,
+            astroport_pair_stable::contract::instantiate,
+            astroport_pair_stable::contract::query,
+        )
+        .with_reply_empty(astroport_pair_stable::contract::reply),
+    );
+
+    app.store_code(pair_contract)
+}
+
+/// ## Description
+/// Stores the vesting code and returns its code identifier.
+pub fn store_vesting_code(app: &mut TerraApp) -> u64 {
+    let vesting_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_vesting::contract::execute,
+        astroport_vesting::contract::instantiate,
+        astroport_vesting::contract::query,
+    ));
+
+    app.store_code(vesting_contract)
+}
+
+/// ## Description
+/// Stores the generator code and returns its code identifier.
+pub fn store_generator_code(app: &mut TerraApp) -> u64 {
+    let generator_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_generator::contract::execute,
+            astroport_generator::contract::instantiate,
+            astroport_generator::contract::query,
+        )
+        .with_reply_empty(astroport_generator::contract::reply),
+    );
+
+    app.store_code(generator_contract)
+}
+
+/// ## Description
+/// Stores the xASTRO staking code and returns its code identifier.
+pub fn store_staking_code(app: &mut TerraApp) -> u64 {
+    let staking_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_staking::contract::execute,
+            astroport_staking::contract::instantiate,
+            astroport_staking::contract::query,
+        )
+        .with_reply_empty(astroport_staking::contract::reply),
+    );
+
+    app.store_code(staking_contract)
+}
@@ -0,0 +1,156 @@
+//! Storage gas metering for multitest benchmarks.
+//!
+//! Multitest does not meter gas, so contracts stored with [`store_metered_generator_code`] wrap
+//! their storage in a [`GasMeteredStorage`] that charges every read, write and iteration step
+//! with the default Cosmos SDK KV store gas costs. Storage access dominates the cost of the
+//! generator's reward loops, so these numbers track on-chain gas closely enough to catch
+//! regressions. Wasm execution gas is not included.
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Order, Pair, Reply, Response, Storage};
+use std::cell::Cell;
+use terra_multi_test::{ContractWrapper, TerraApp};
+
+use astroport::generator::{ExecuteMsg, InstantiateMsg};
+use astroport_generator::error::ContractError;
+
+/// Gas charged for each storage read
+pub const READ_COST_FLAT: u64 = 1000;
+/// Gas charged for each byte read from storage (both key and value)
+pub const READ_COST_PER_BYTE: u64 = 3;
+/// Gas charged for each storage write
+pub const WRITE_COST_FLAT: u64 = 2000;
+/// Gas charged for each byte written to storage (both key and value)
+pub const WRITE_COST_PER_BYTE: u64 = 30;
+/// Gas charged for each storage deletion
+pub const DELETE_COST: u64 = 1000;
+/// Gas charged for each step of a storage iterator
+pub const ITER_NEXT_COST_FLAT: u64 = 30;
+
+thread_local! {
+    static GAS_USED: Cell<u64> = Cell::new(0);
+}
+
+fn consume_gas(amount: u64) {
+    GAS_USED.with(|gas| gas.set(gas.get() + amount));
+}
+
+/// ## Description
+/// Runs `f` and returns its result together with the amount of storage gas consumed by metered
+/// contracts while it was running.
+///
+/// ```ignore
+/// let (res, gas) = measure_gas(|| protocol.deposit(&user, &lp_token, 100));
+/// ```
+pub fn measure_gas<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let before = GAS_USED.with(|gas| gas.get());
+    let res = f();
+    let after = GAS_USED.with(|gas| gas.get());
+
+    (res, after - before)
+}
+
+/// ## Description
+/// A [`Storage`] wrapper that charges gas for every operation.
+pub struct GasMeteredStorage<'a> {
+    storage: &'a mut dyn Storage,
+}
+
+impl<'a> GasMeteredStorage<'a> {
+    /// Wraps the specified storage.
+    pub fn new(storage: &'a mut dyn Storage) -> Self {
+        GasMeteredStorage { storage }
+    }
+}
+
+impl Storage for GasMeteredStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        let len = key.len() + value.as_ref().map(Vec::len).unwrap_or_default();
+        consume_gas(READ_COST_FLAT + READ_COST_PER_BYTE * len as u64);
+
+        value
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'a> {
+        Box::new(self.storage.range(start, end, order).map(|(key, value)| {
+            consume_gas(
+                ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * (key.len() + value.len()) as u64,
+            );
+            (key, value)
+        }))
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        consume_gas(WRITE_COST_FLAT + WRITE_COST_PER_BYTE * (key.len() + value.len()) as u64);
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        consume_gas(DELETE_COST);
+        self.storage.remove(key)
+    }
+}
+
+/// ## Description
+/// Stores the generator code with metered storage and returns its code identifier.
+/// Use [`measure_gas`] to read the gas consumed by the generator.
+pub fn store_metered_generator_code(app: &mut TerraApp) -> u64 {
+    let generator_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            metered_generator_execute,
+            metered_generator_instantiate,
+            // Queries do not consume transaction gas, so they are not metered
+            astroport_generator::contract::query,
+        )
+        .with_reply_empty(metered_generator_reply),
+    );
+
+    app.store_code(generator_contract)
+}
+
+fn metered_generator_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let mut storage = GasMeteredStorage::new(deps.storage);
+    let deps = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+
+    astroport_generator::contract::instantiate(deps, env, info, msg)
+}
+
+fn metered_generator_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let mut storage = GasMeteredStorage::new(deps.storage);
+    let deps = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+
+    astroport_generator::contract::execute(deps, env, info, msg)
+}
+
+fn metered_generator_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let mut storage = GasMeteredStorage::new(deps.storage);
+    let deps = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+
+    astroport_generator::contract::reply(deps, env, msg)
+}
@@ -0,0 +1,32 @@
+//! Shared multitest fixtures for Astroport integration tests.
+//!
+//! [`contracts`] stores the code of every Astroport contract in a [`TerraApp`], while
+//! [`ProtocolBuilder`] spins up the factory, ASTRO token and (optionally) the generator,
+//! vesting and staking contracts so that integration tests only have to describe the scenario
+//! they check. [`gas`] meters the generator's storage access for gas benchmarks.
+pub mod contracts;
+pub mod gas;
+pub mod protocol;
+
+pub use crate::protocol::{Protocol, ProtocolBuilder, OWNER};
+
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use terra_multi_test::{AppBuilder, BankKeeper, TerraApp, TerraMock};
+
+/// ## Description
+/// Returns a [`TerraApp`] that uses the mock block info and the default Luna/UST swap rates.
+pub fn mock_app() -> TerraApp {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+    let custom = TerraMock::luna_ust_case();
+
+    AppBuilder::new()
+        .with_api(api)
+        .with_block(env.block)
+        .with_bank(bank)
+        .with_storage(storage)
+        .with_custom(custom)
+        .build()
+}
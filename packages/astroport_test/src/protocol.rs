@@ -0,0 +1,509 @@
+use crate::contracts::{
+    store_factory_code, store_generator_code, store_pair_code, store_pair_stable_code,
+    store_staking_code, store_token_code, store_vesting_code,
+};
+use crate::gas::store_metered_generator_code;
+use crate::mock_app;
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::{
+    ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType,
+    QueryMsg as FactoryQueryMsg,
+};
+use astroport::generator::{
+    Cw20HookMsg as GeneratorHookMsg, ExecuteMsg as GeneratorExecuteMsg,
+    InstantiateMsg as GeneratorInstantiateMsg, PendingTokenResponse, QueryMsg as GeneratorQueryMsg,
+};
+use astroport::pair::ExecuteMsg as PairExecuteMsg;
+use astroport::staking::{
+    ConfigResponse as StakingConfigResponse, InstantiateMsg as StakingInstantiateMsg,
+    QueryMsg as StakingQueryMsg,
+};
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use astroport::vesting::{
+    Cw20HookMsg as VestingHookMsg, InstantiateMsg as VestingInstantiateMsg, VestingAccount,
+    VestingSchedule, VestingSchedulePoint,
+};
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, Uint128, Uint64};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+use terra_multi_test::{AppResponse, Executor, TerraApp};
+
+/// The default owner of every contract instantiated by the [`ProtocolBuilder`]
+pub const OWNER: &str = "owner";
+
+/// The amount of ASTRO vested to the generator
+const GENERATOR_VESTED_AMOUNT: u128 = 63_072_000_000000;
+
+/// ## Description
+/// Builds a [`Protocol`]. The ASTRO token and the factory (with an XYK pair config) are always
+/// instantiated, while the other contracts are only instantiated if requested.
+///
+/// ```ignore
+/// let mut protocol = ProtocolBuilder::new()
+///     .with_generator(10_000000)
+///     .with_staking()
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProtocolBuilder {
+    owner: Addr,
+    stable_pairs: bool,
+    generator_tokens_per_block: Option<u128>,
+    gas_metering: bool,
+    staking: bool,
+}
+
+impl Default for ProtocolBuilder {
+    fn default() -> Self {
+        ProtocolBuilder {
+            owner: Addr::unchecked(OWNER),
+            stable_pairs: false,
+            generator_tokens_per_block: None,
+            gas_metering: false,
+            staking: false,
+        }
+    }
+}
+
+impl ProtocolBuilder {
+    /// Creates a builder that only instantiates the ASTRO token and the factory.
+    pub fn new() -> Self {
+        ProtocolBuilder::default()
+    }
+
+    /// Sets the owner of every instantiated contract and the minter of every created token.
+    pub fn owner(mut self, owner: &str) -> Self {
+        self.owner = Addr::unchecked(owner);
+        self
+    }
+
+    /// Adds a stableswap pair config to the factory.
+    pub fn with_stable_pairs(mut self) -> Self {
+        self.stable_pairs = true;
+        self
+    }
+
+    /// Instantiates the vesting and generator contracts. The generator starts emitting
+    /// `tokens_per_block` ASTRO from the current block.
+    pub fn with_generator(mut self, tokens_per_block: u128) -> Self {
+        self.generator_tokens_per_block = Some(tokens_per_block);
+        self
+    }
+
+    /// Stores the generator with metered storage, so that [`crate::gas::measure_gas`] can be used
+    /// to benchmark it.
+    pub fn with_gas_metering(mut self) -> Self {
+        self.gas_metering = true;
+        self
+    }
+
+    /// Instantiates the xASTRO staking contract.
+    pub fn with_staking(mut self) -> Self {
+        self.staking = true;
+        self
+    }
+
+    /// Instantiates all requested contracts in a new [`TerraApp`].
+    pub fn build(self) -> Protocol {
+        let mut app = mock_app();
+        let owner = self.owner;
+
+        let token_code_id = store_token_code(&mut app);
+        let mut pair_configs = vec![PairConfig {
+            code_id: store_pair_code(&mut app),
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 3333,
+            is_disabled: false,
+            is_generator_disabled: false,
+        }];
+        if self.stable_pairs {
+            pair_configs.push(PairConfig {
+                code_id: store_pair_stable_code(&mut app),
+                pair_type: PairType::Stable {},
+                total_fee_bps: 5,
+                maker_fee_bps: 5000,
+                is_disabled: false,
+                is_generator_disabled: false,
+            });
+        }
+
+        let factory_code_id = store_factory_code(&mut app);
+        let factory = app
+            .instantiate_contract(
+                factory_code_id,
+                owner.clone(),
+                &FactoryInstantiateMsg {
+                    pair_configs,
+                    token_code_id,
+                    fee_address: None,
+                    generator_address: None,
+                    owner: owner.to_string(),
+                    whitelist_code_id: 0,
+                },
+                &[],
+                "Factory",
+                None,
+            )
+            .unwrap();
+
+        let mut protocol = Protocol {
+            app,
+            owner,
+            token_code_id,
+            astro_token: Addr::unchecked(""),
+            factory,
+            vesting: None,
+            generator: None,
+            staking: None,
+            xastro_token: None,
+        };
+        protocol.astro_token = protocol.create_token("ASTRO");
+
+        if let Some(tokens_per_block) = self.generator_tokens_per_block {
+            protocol.instantiate_generator(tokens_per_block, self.gas_metering);
+        }
+
+        if self.staking {
+            protocol.instantiate_staking();
+        }
+
+        protocol
+    }
+}
+
+/// ## Description
+/// A set of Astroport contracts instantiated in a [`TerraApp`], together with helpers that
+/// perform the most common test actions.
+pub struct Protocol {
+    /// The multitest app
+    pub app: TerraApp,
+    /// The owner of every instantiated contract and the minter of every created token
+    pub owner: Addr,
+    /// The CW20 token code identifier
+    pub token_code_id: u64,
+    /// The ASTRO token contract
+    pub astro_token: Addr,
+    /// The factory contract
+    pub factory: Addr,
+    /// The vesting contract, instantiated together with the generator
+    pub vesting: Option<Addr>,
+    /// The generator contract
+    pub generator: Option<Addr>,
+    /// The xASTRO staking contract
+    pub staking: Option<Addr>,
+    /// The xASTRO token contract
+    pub xastro_token: Option<Addr>,
+}
+
+impl Protocol {
+    /// Creates a CW20 token without a supply cap that can be minted by the owner.
+    pub fn create_token(&mut self, name: &str) -> Addr {
+        let msg = TokenInstantiateMsg {
+            name: name.to_string(),
+            symbol: name.to_string(),
+            decimals: 6,
+            initial_balances: vec![],
+            mint: Some(MinterResponse {
+                minter: self.owner.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        };
+
+        self.app
+            .instantiate_contract(
+                self.token_code_id,
+                self.owner.clone(),
+                &msg,
+                &[],
+                name,
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Mints CW20 tokens created with [`Protocol::create_token`].
+    pub fn mint_tokens(&mut self, token: &Addr, recipient: &Addr, amount: u128) {
+        let msg = Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        };
+
+        self.app
+            .execute_contract(self.owner.clone(), token.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    /// Sets the native token balances of an address.
+    pub fn set_native_balances(&mut self, address: &Addr, coins: Vec<Coin>) {
+        self.app.init_bank_balance(address, coins).unwrap();
+    }
+
+    /// Creates a pair via the factory and returns its [`PairInfo`].
+    pub fn create_pair(
+        &mut self,
+        pair_type: PairType,
+        asset_infos: [AssetInfo; 2],
+        init_params: Option<Binary>,
+    ) -> PairInfo {
+        self.app
+            .execute_contract(
+                self.owner.clone(),
+                self.factory.clone(),
+                &FactoryExecuteMsg::CreatePair {
+                    pair_type,
+                    asset_infos: asset_infos.clone(),
+                    init_params,
+                },
+                &[],
+            )
+            .unwrap();
+
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.factory, &FactoryQueryMsg::Pair { asset_infos })
+            .unwrap()
+    }
+
+    /// Provides liquidity to a pair. CW20 allowances are increased and native tokens are attached
+    /// automatically, so the sender only needs to hold the assets.
+    pub fn provide_liquidity(
+        &mut self,
+        sender: &Addr,
+        pair: &Addr,
+        assets: [Asset; 2],
+    ) -> anyhow::Result<AppResponse> {
+        let mut funds = vec![];
+        for asset in assets.iter() {
+            match &asset.info {
+                AssetInfo::Token { contract_addr } => {
+                    self.app.execute_contract(
+                        sender.clone(),
+                        contract_addr.clone(),
+                        &Cw20ExecuteMsg::IncreaseAllowance {
+                            spender: pair.to_string(),
+                            amount: asset.amount,
+                            expires: None,
+                        },
+                        &[],
+                    )?;
+                }
+                AssetInfo::NativeToken { denom } => funds.push(Coin {
+                    denom: denom.clone(),
+                    amount: asset.amount,
+                }),
+            }
+        }
+        funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        self.app.execute_contract(
+            sender.clone(),
+            pair.clone(),
+            &PairExecuteMsg::ProvideLiquidity {
+                assets,
+                slippage_tolerance: None,
+                auto_stake: None,
+                receiver: None,
+            },
+            &funds,
+        )
+    }
+
+    /// Replaces the generator's pools with the specified LP tokens and allocation points.
+    pub fn setup_pools(&mut self, pools: &[(&Addr, u128)]) {
+        let pools = pools
+            .iter()
+            .map(|(lp_token, alloc_point)| (lp_token.to_string(), Uint128::from(*alloc_point)))
+            .collect();
+
+        self.app
+            .execute_contract(
+                self.owner.clone(),
+                self.generator(),
+                &GeneratorExecuteMsg::SetupPools { pools },
+                &[],
+            )
+            .unwrap();
+    }
+
+    /// Deposits LP tokens in the generator.
+    pub fn deposit(
+        &mut self,
+        depositor: &Addr,
+        lp_token: &Addr,
+        amount: u128,
+    ) -> anyhow::Result<AppResponse> {
+        let msg = Cw20ExecuteMsg::Send {
+            contract: self.generator().to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&GeneratorHookMsg::Deposit {}).unwrap(),
+        };
+
+        self.app
+            .execute_contract(depositor.clone(), lp_token.clone(), &msg, &[])
+    }
+
+    /// Returns the amount of ASTRO (and proxy rewards) a user can claim from a generator.
+    pub fn pending_token(&self, lp_token: &Addr, user: &Addr) -> PendingTokenResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.generator(),
+                &GeneratorQueryMsg::PendingToken {
+                    lp_token: lp_token.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    /// Returns the CW20 token balance of an address.
+    pub fn token_balance(&self, token: &Addr, address: &Addr) -> u128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+
+        res.balance.u128()
+    }
+
+    /// Moves the chain forward by the specified number of blocks (5 seconds per block).
+    pub fn advance_blocks(&mut self, blocks: u64) {
+        self.app.update_block(|block| {
+            block.height += blocks;
+            block.time = block.time.plus_seconds(5 * blocks);
+        });
+    }
+
+    /// Returns the generator address. Panics if the generator was not instantiated.
+    pub fn generator(&self) -> Addr {
+        self.generator
+            .clone()
+            .expect("The generator was not instantiated")
+    }
+
+    fn instantiate_generator(&mut self, tokens_per_block: u128, gas_metering: bool) {
+        let vesting_code_id = store_vesting_code(&mut self.app);
+        let vesting = self
+            .app
+            .instantiate_contract(
+                vesting_code_id,
+                self.owner.clone(),
+                &VestingInstantiateMsg {
+                    owner: self.owner.to_string(),
+                    token_addr: self.astro_token.to_string(),
+                },
+                &[],
+                "Vesting",
+                None,
+            )
+            .unwrap();
+
+        let generator_code_id = if gas_metering {
+            store_metered_generator_code(&mut self.app)
+        } else {
+            store_generator_code(&mut self.app)
+        };
+        let generator = self
+            .app
+            .instantiate_contract(
+                generator_code_id,
+                self.owner.clone(),
+                &GeneratorInstantiateMsg {
+                    owner: self.owner.to_string(),
+                    factory: self.factory.to_string(),
+                    generator_controller: Some(self.owner.to_string()),
+                    guardian: None,
+                    astro_token: self.astro_token.to_string(),
+                    tokens_per_block: Uint128::from(tokens_per_block),
+                    start_block: Uint64::from(self.app.block_info().height),
+                    allowed_reward_proxies: vec![],
+                    vesting_contract: vesting.to_string(),
+                },
+                &[],
+                "Generator",
+                None,
+            )
+            .unwrap();
+
+        // Vest ASTRO to the generator
+        let amount = Uint128::from(GENERATOR_VESTED_AMOUNT);
+        let astro_token = self.astro_token.clone();
+        let owner = self.owner.clone();
+        self.mint_tokens(&astro_token, &owner, amount.u128());
+
+        let msg = Cw20ExecuteMsg::Send {
+            contract: vesting.to_string(),
+            amount,
+            msg: to_binary(&VestingHookMsg::RegisterVestingAccounts {
+                vesting_accounts: vec![VestingAccount {
+                    address: generator.to_string(),
+                    schedules: vec![VestingSchedule {
+                        start_point: VestingSchedulePoint {
+                            time: self.app.block_info().time.seconds(),
+                            amount,
+                        },
+                        end_point: None,
+                    }],
+                }],
+            })
+            .unwrap(),
+        };
+        self.app
+            .execute_contract(owner.clone(), astro_token, &msg, &[])
+            .unwrap();
+
+        // Let pairs auto stake LP tokens in the generator
+        self.app
+            .execute_contract(
+                owner,
+                self.factory.clone(),
+                &FactoryExecuteMsg::UpdateConfig {
+                    token_code_id: None,
+                    fee_address: None,
+                    generator_address: Some(generator.to_string()),
+                    whitelist_code_id: None,
+                    referral_registry: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        self.vesting = Some(vesting);
+        self.generator = Some(generator);
+    }
+
+    fn instantiate_staking(&mut self) {
+        let staking_code_id = store_staking_code(&mut self.app);
+        let staking = self
+            .app
+            .instantiate_contract(
+                staking_code_id,
+                self.owner.clone(),
+                &StakingInstantiateMsg {
+                    owner: self.owner.to_string(),
+                    token_code_id: self.token_code_id,
+                    deposit_token_addr: self.astro_token.to_string(),
+                },
+                &[],
+                "xASTRO",
+                None,
+            )
+            .unwrap();
+
+        let res: StakingConfigResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&staking, &StakingQueryMsg::Config {})
+            .unwrap();
+
+        self.staking = Some(staking);
+        self.xastro_token = Some(res.share_token_addr);
+    }
+}
@@ -0,0 +1,78 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport::factory::PairType;
+use astroport_test::ProtocolBuilder;
+use cosmwasm_std::{Addr, Uint128};
+
+#[test]
+fn full_protocol_setup() {
+    let mut protocol = ProtocolBuilder::new()
+        .with_generator(10_000000)
+        .with_staking()
+        .build();
+    assert!(protocol.staking.is_some());
+    assert!(protocol.xastro_token.is_some());
+
+    let user = Addr::unchecked("user");
+    let foo_token = protocol.create_token("FOO");
+    let bar_token = protocol.create_token("BAR");
+    protocol.mint_tokens(&foo_token, &user, 100_000);
+    protocol.mint_tokens(&bar_token, &user, 100_000);
+
+    let pair_info = protocol.create_pair(
+        PairType::Xyk {},
+        [
+            AssetInfo::Token {
+                contract_addr: foo_token.clone(),
+            },
+            AssetInfo::Token {
+                contract_addr: bar_token.clone(),
+            },
+        ],
+        None,
+    );
+
+    protocol
+        .provide_liquidity(
+            &user,
+            &pair_info.contract_addr,
+            [
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: foo_token,
+                    },
+                    amount: Uint128::new(100_000),
+                },
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: bar_token,
+                    },
+                    amount: Uint128::new(100_000),
+                },
+            ],
+        )
+        .unwrap();
+    let lp_token = pair_info.liquidity_token;
+    // The minimum liquidity is locked in the pair
+    assert_eq!(protocol.token_balance(&lp_token, &user), 99_000);
+    assert_eq!(
+        protocol.token_balance(&lp_token, &pair_info.contract_addr),
+        1_000
+    );
+
+    protocol.setup_pools(&[(&lp_token, 100)]);
+    protocol.deposit(&user, &lp_token, 99_000).unwrap();
+    assert_eq!(
+        protocol.token_balance(&lp_token, &protocol.generator()),
+        99_000
+    );
+    assert_eq!(
+        protocol.pending_token(&lp_token, &user).pending,
+        Uint128::zero()
+    );
+
+    protocol.advance_blocks(1);
+    assert_eq!(
+        protocol.pending_token(&lp_token, &user).pending,
+        Uint128::new(10_000000)
+    );
+}